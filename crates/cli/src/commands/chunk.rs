@@ -28,6 +28,8 @@ use xze_core::ai::OllamaClient;
 use xze_core::documentation::processor::{DocumentProcessor, ProcessingConfig};
 use xze_core::kb::store::KbStore;
 use xze_core::semantic::chunker::ChunkerConfig;
+use xze_core::semantic::similarity::SimilarityMetric;
+use xze_core::semantic::splitter::SplitterKind;
 use xze_core::semantic::types::ChunkMetadata;
 use xze_core::{Result, XzeError};
 
@@ -65,13 +67,19 @@ impl ChunkingStrategy {
 /// ```no_run
 /// use xze_cli::commands::chunk::{ChunkArgs, ChunkingStrategy};
 /// use std::path::PathBuf;
+/// use xze_core::semantic::similarity::SimilarityMetric;
+/// use xze_core::semantic::splitter::SplitterKind;
 ///
 /// let args = ChunkArgs {
 ///     input: vec![PathBuf::from("docs/guide.md")],
 ///     output: None,
 ///     strategy: ChunkingStrategy::Default,
 ///     threshold: None,
+///     percentile: None,
 ///     max_sentences: None,
+///     overlap: None,
+///     metric: SimilarityMetric::Cosine,
+///     splitter: SplitterKind::Sentence,
 ///     dry_run: false,
 ///     database_url: None,
 ///     ollama_url: None,
@@ -110,12 +118,40 @@ pub struct ChunkArgs {
     #[arg(long)]
     pub threshold: Option<f32>,
 
+    /// Cut below this percentile of each document's similarities (0.0-1.0)
+    ///
+    /// Derives the threshold from each document instead of using a fixed
+    /// one; overrides --threshold.
+    #[arg(long)]
+    pub percentile: Option<f32>,
+
     /// Override maximum sentences per chunk
     ///
     /// Limits the size of individual chunks.
     #[arg(long)]
     pub max_sentences: Option<usize>,
 
+    /// Sentences repeated from the end of each chunk at the start of the next
+    ///
+    /// Keeps context that spans a chunk boundary. Must be less than the
+    /// maximum sentences per chunk.
+    #[arg(long)]
+    pub overlap: Option<usize>,
+
+    /// Similarity metric for comparing sentence embeddings
+    ///
+    /// One of cosine, dot_product or euclidean. Use the metric the
+    /// embedding model was trained for.
+    #[arg(long, default_value_t = SimilarityMetric::Cosine)]
+    pub metric: SimilarityMetric,
+
+    /// How documents are split before chunking
+    ///
+    /// One of sentence or markdown. Markdown chunks each heading section
+    /// separately and keeps code blocks and tables whole.
+    #[arg(long, default_value_t = SplitterKind::Sentence)]
+    pub splitter: SplitterKind,
+
     /// Dry run - analyze without storing chunks
     ///
     /// Useful for previewing how documents will be chunked
@@ -158,6 +194,15 @@ impl ChunkArgs {
             }
         }
 
+        // Validate percentile if provided
+        if let Some(percentile) = self.percentile {
+            if !(0.0..=1.0).contains(&percentile) {
+                return Err(XzeError::validation(
+                    "Percentile must be between 0.0 and 1.0",
+                ));
+            }
+        }
+
         // Validate max_sentences if provided
         if let Some(max_sentences) = self.max_sentences {
             if max_sentences == 0 {
@@ -187,10 +232,21 @@ impl ChunkArgs {
             config.similarity_threshold = threshold;
         }
 
+        if self.percentile.is_some() {
+            config.similarity_percentile = self.percentile;
+        }
+
         if let Some(max_sentences) = self.max_sentences {
             config.max_chunk_sentences = max_sentences;
         }
 
+        if let Some(overlap) = self.overlap {
+            config.overlap_sentences = overlap;
+        }
+
+        config.similarity_metric = self.metric;
+        config.splitter = self.splitter;
+
         config
     }
 
@@ -345,6 +401,8 @@ impl CliCommand for ChunkArgs {
                 keywords: Self::extract_keywords(&content),
                 word_count: content.split_whitespace().count(),
                 char_count: content.len(),
+                overlap_sentences: 0,
+                breadcrumbs: Vec::new(),
             };
 
             // Process document
@@ -531,7 +589,11 @@ mod tests {
             output: None,
             strategy: ChunkingStrategy::Default,
             threshold: Some(1.5),
+            percentile: None,
             max_sentences: None,
+            overlap: None,
+            metric: SimilarityMetric::Cosine,
+            splitter: SplitterKind::Sentence,
             dry_run: false,
             database_url: None,
             ollama_url: None,
@@ -557,7 +619,11 @@ mod tests {
             output: None,
             strategy: ChunkingStrategy::Default,
             threshold: Some(0.85),
+            percentile: Some(0.3),
             max_sentences: Some(20),
+            overlap: Some(2),
+            metric: SimilarityMetric::Euclidean,
+            splitter: SplitterKind::Markdown,
             dry_run: false,
             database_url: None,
             ollama_url: None,
@@ -565,6 +631,10 @@ mod tests {
 
         let config = args.build_chunker_config();
         assert_eq!(config.similarity_threshold, 0.85);
+        assert_eq!(config.similarity_percentile, Some(0.3));
         assert_eq!(config.max_chunk_sentences, 20);
+        assert_eq!(config.overlap_sentences, 2);
+        assert_eq!(config.similarity_metric, SimilarityMetric::Euclidean);
+        assert_eq!(config.splitter, SplitterKind::Markdown);
     }
 }
@@ -6,8 +6,13 @@
 use crate::commands::CliCommand;
 use clap::Args;
 use std::path::PathBuf;
-use tracing::{error, info};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use xze_core::kb::backend::KbBackendKind;
+use xze_core::kb::error::{KbError, Result as KbResult};
+use xze_core::kb::hash::HashAlgorithm;
 use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+use xze_core::semantic::ChunkStrategy;
 use xze_core::Result;
 
 /// Load documents into the knowledge base
@@ -26,15 +31,37 @@ use xze_core::Result;
 ///
 /// # Dry run (preview what would be loaded)
 /// xze load --paths ./docs --resume --dry-run
+///
+/// # Chunk Markdown docs by heading section
+/// xze load --paths ./docs --chunk-strategy markdown
+///
+/// # Stream NDJSON documents from another tool
+/// cms-export --format ndjson | xze load --from-stdin --update
+///
+/// # Checkpoint progress; after Ctrl+C, rerun the same command to resume
+/// xze load --paths ./docs --force --checkpoint load.checkpoint
+///
+/// # Keep the knowledge base in a local SQLite file (`sqlite` feature)
+/// xze load --paths ./docs --database-url sqlite://xze.db
+///
+/// # Check what would be loaded without a database
+/// xze load --paths ./docs --backend memory
 /// ```
 #[derive(Debug, Clone, Args)]
 pub struct LoadArgs {
     /// Paths to load documents from
     ///
     /// Can specify multiple paths separated by spaces.
-    #[arg(short, long, required = true)]
+    #[arg(short, long, required_unless_present = "from_stdin")]
     pub paths: Vec<PathBuf>,
 
+    /// Read newline-delimited JSON documents from stdin instead of paths
+    ///
+    /// Each line is an object with `id` and `content` and optional `path`,
+    /// `category` and `tags`. Malformed lines are logged and skipped.
+    #[arg(long, conflicts_with_all = ["paths", "cleanup"])]
+    pub from_stdin: bool,
+
     /// Resume mode: skip files that are already loaded and unchanged
     ///
     /// This mode calculates hashes for all files and compares them with
@@ -51,17 +78,28 @@ pub struct LoadArgs {
 
     /// Cleanup mode: remove deleted files from database
     ///
-    /// This mode removes chunks for files that no longer exist on disk.
-    /// Typically used with --update for a full incremental sync.
+    /// This mode removes chunks for files that no longer exist on disk,
+    /// in a single transaction, then vacuums the database to reclaim the
+    /// space. Typically used with --update for a full incremental sync.
     #[arg(short, long)]
     pub cleanup: bool,
 
     /// Database connection URL
     ///
-    /// If not provided, uses the DATABASE_URL environment variable.
+    /// If not provided, uses the DATABASE_URL environment variable. A
+    /// `sqlite:` URL stores the knowledge base in a local file; this needs
+    /// the `sqlite` feature.
     #[arg(long, default_value_t = std::env::var("DATABASE_URL").unwrap_or_default())]
     pub database_url: String,
 
+    /// Storage backend: postgres or memory
+    ///
+    /// Defaults to `knowledge_base.backend` from the configuration file,
+    /// or postgres. The memory backend needs no database and keeps the
+    /// loaded chunks only for the duration of the command.
+    #[arg(long)]
+    pub backend: Option<KbBackendKind>,
+
     /// Dry run mode: show what would be done without making changes
     #[arg(long)]
     pub dry_run: bool,
@@ -75,6 +113,46 @@ pub struct LoadArgs {
     /// Note: Cannot be used with --resume or --update flags.
     #[arg(short, long)]
     pub force: bool,
+
+    /// Hash algorithm for change detection: sha256, blake3 or xxh3
+    ///
+    /// Switching algorithms makes every stored hash differ once, so the
+    /// next --update reprocesses changed files.
+    #[arg(long, default_value_t = HashAlgorithm::Sha256)]
+    pub hash_algorithm: HashAlgorithm,
+
+    /// Hash every file, even when its size and modification time match
+    /// the values recorded at the last load
+    #[arg(long)]
+    pub verify_hashes: bool,
+
+    /// Chunking strategy: sentence, markdown, semantic, or
+    /// fixed-token[:SIZE[:OVERLAP]]
+    ///
+    /// Without an embedding model, semantic falls back to sentence.
+    #[arg(long, default_value_t = ChunkStrategy::Sentence)]
+    pub chunk_strategy: ChunkStrategy,
+
+    /// Number of files to chunk, embed and store in parallel
+    ///
+    /// Keep this within what the Ollama server can handle when chunking
+    /// semantically.
+    #[arg(long, default_value_t = 1)]
+    pub concurrency: usize,
+
+    /// Number of files that may fail before the load is aborted
+    ///
+    /// Failed files are logged and listed in the summary.
+    #[arg(long, default_value_t = 0)]
+    pub max_failures: usize,
+
+    /// File recording load progress
+    ///
+    /// Completed files are written to it periodically and on Ctrl+C; a
+    /// later load with the same checkpoint skips them. It is removed once
+    /// a load completes.
+    #[arg(long)]
+    pub checkpoint: Option<PathBuf>,
 }
 
 impl LoadArgs {
@@ -87,18 +165,19 @@ impl LoadArgs {
     /// # Errors
     ///
     /// Returns error if:
-    /// - No paths are provided
-    /// - Database URL is not set
+    /// - No paths are provided and --from-stdin is not set
+    /// - Database URL is not set for the postgres backend
     /// - Paths don't exist
     /// - Conflicting flags are used (e.g., --force with --resume or --update)
+    /// - Concurrency is zero
     pub fn validate(&self) -> Result<()> {
-        if self.paths.is_empty() {
+        if self.paths.is_empty() && !self.from_stdin {
             return Err(xze_core::XzeError::validation(
                 "At least one path must be provided",
             ));
         }
 
-        if self.database_url.is_empty() {
+        if self.backend() == KbBackendKind::Postgres && self.database_url.is_empty() {
             return Err(xze_core::XzeError::validation(
                 "Database URL must be provided via --database-url or DATABASE_URL environment variable",
             ));
@@ -117,6 +196,12 @@ impl LoadArgs {
             ));
         }
 
+        if self.concurrency == 0 {
+            return Err(xze_core::XzeError::validation(
+                "--concurrency must be at least 1",
+            ));
+        }
+
         if self.resume && !self.update && !self.cleanup {
             info!("Using --resume without --update or --cleanup will only add new files");
         }
@@ -134,6 +219,40 @@ impl LoadArgs {
         Ok(())
     }
 
+    /// Storage backend selected for this load
+    fn backend(&self) -> KbBackendKind {
+        self.backend.unwrap_or_default()
+    }
+
+    /// Whether the database URL points at a SQLite file
+    fn is_sqlite(&self) -> bool {
+        self.database_url.starts_with("sqlite:")
+    }
+
+    /// Open the store named by the database URL and create a loader on it
+    async fn create_loader(&self, config: LoaderConfig) -> KbResult<IncrementalLoader> {
+        if config.backend == KbBackendKind::Memory {
+            return IncrementalLoader::new(None, config);
+        }
+
+        if self.is_sqlite() {
+            #[cfg(feature = "sqlite")]
+            {
+                let store = xze_core::kb::SqliteKbStore::connect(&self.database_url).await?;
+                return IncrementalLoader::with_backend(std::sync::Arc::new(store), config);
+            }
+            #[cfg(not(feature = "sqlite"))]
+            return Err(KbError::config(
+                "SQLite database URLs need xze built with the sqlite feature",
+            ));
+        }
+
+        let pool = sqlx::PgPool::connect(&self.database_url)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to connect to database: {}", e)))?;
+        IncrementalLoader::new(Some(pool), config)
+    }
+
     /// Convert paths to strings
     fn paths_as_strings(&self) -> Vec<String> {
         self.paths
@@ -154,43 +273,104 @@ impl CliCommand for LoadArgs {
             update: self.update,
             cleanup: self.cleanup,
             force: self.force,
+            backend: self.backend(),
+            hash_algorithm: self.hash_algorithm,
+            verify_hashes: self.verify_hashes,
+            chunk_strategy: self.chunk_strategy,
+            concurrency: self.concurrency,
+            max_failures: self.max_failures,
+            checkpoint_path: self.checkpoint.clone(),
+            ..Default::default()
         };
 
         info!("Configuration:");
         info!("  Mode: {}", config.mode_description());
-        info!("  Paths: {} path(s)", self.paths.len());
-        for path in &self.paths {
-            info!("    - {}", path.display());
+        if self.from_stdin {
+            info!("  Source: stdin (NDJSON)");
+        } else {
+            info!("  Paths: {} path(s)", self.paths.len());
+            for path in &self.paths {
+                info!("    - {}", path.display());
+            }
         }
         info!("  Update: {}", self.update);
         info!("  Cleanup: {}", self.cleanup);
         info!("  Dry run: {}", self.dry_run);
+        info!("  Backend: {}", config.backend);
+        info!("  Hash algorithm: {}", self.hash_algorithm);
+        info!("  Chunk strategy: {}", self.chunk_strategy);
+        info!("  Concurrency: {}", self.concurrency);
+        if let Some(checkpoint) = &self.checkpoint {
+            info!("  Checkpoint: {}", checkpoint.display());
+        }
 
-        // Create database connection pool
+        // Create incremental loader
         info!("Connecting to database...");
-        let pool = sqlx::PgPool::connect(&self.database_url)
+        let loader = self
+            .create_loader(config)
             .await
-            .map_err(|e| {
-                xze_core::XzeError::pipeline(format!("Failed to connect to database: {}", e))
-            })?;
-
-        // Create incremental loader
-        let loader = IncrementalLoader::new(pool, config)
             .map_err(|e| xze_core::XzeError::pipeline(format!("Failed to create loader: {}", e)))?;
 
+        // Ctrl+C finishes the files in progress, writes the checkpoint and
+        // exits
+        let cancellation = CancellationToken::new();
+        let loader = loader.with_cancellation(cancellation.clone());
+        let ctrl_c = tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                warn!("Interrupted; finishing files in progress...");
+                cancellation.cancel();
+            }
+        });
+
         // Execute load operation
-        let paths = self.paths_as_strings();
-        let stats = loader.load(&paths).await.map_err(|e| {
+        let result = if self.from_stdin {
+            let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+            loader.load_records(stdin).await
+        } else {
+            loader.load(&self.paths_as_strings()).await
+        };
+        ctrl_c.abort();
+        let stats = result.map_err(|e| {
             error!("Load operation failed: {}", e);
             xze_core::XzeError::pipeline(format!("Load operation failed: {}", e))
         })?;
 
         // Log results
-        info!("Load operation completed successfully");
+        if stats.cancelled {
+            match &self.checkpoint {
+                Some(checkpoint) => warn!(
+                    "Load interrupted; rerun with --checkpoint {} to resume",
+                    checkpoint.display()
+                ),
+                None => warn!("Load interrupted; rerun with --resume to continue"),
+            }
+        } else {
+            info!("Load operation completed successfully");
+        }
         info!("Summary:");
         info!("  Total files discovered: {}", stats.total_files());
         info!("  Files skipped:          {}", stats.files_skipped);
+        if stats.files_checkpointed > 0 {
+            info!("  Files checkpointed:     {}", stats.files_checkpointed);
+        }
         info!("  Files to process:       {}", stats.files_to_process());
+        if self.from_stdin {
+            info!("  Invalid records:        {}", stats.records_invalid);
+        }
+        if !stats.failed_files.is_empty() {
+            info!("  Files failed:           {}", stats.failed_files.len());
+            for failed in &stats.failed_files {
+                error!("    - {}: {}", failed.path, failed.error);
+            }
+        }
+        if self.cleanup {
+            info!("  Files cleaned up:       {}", stats.cleanup.files_removed);
+            info!("  Chunks cleaned up:      {}", stats.cleanup.chunks_removed);
+            info!(
+                "  Bytes reclaimed:        {}",
+                stats.cleanup.bytes_reclaimed
+            );
+        }
         info!("  Duration:               {:.2}s", stats.duration_secs);
 
         if self.dry_run {
@@ -222,12 +402,26 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = args.validate();
         assert!(result.is_err());
+
+        let stdin_args = LoadArgs {
+            from_stdin: true,
+            ..args
+        };
+        assert!(stdin_args.validate().is_ok());
     }
 
     #[test]
@@ -238,12 +432,27 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: String::new(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = args.validate();
         assert!(result.is_err());
+
+        // The memory backend does not need a database
+        let memory_args = LoadArgs {
+            backend: Some(KbBackendKind::Memory),
+            ..args
+        };
+        assert!(memory_args.validate().is_ok());
     }
 
     #[test]
@@ -254,8 +463,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = args.validate();
@@ -272,8 +489,16 @@ mod tests {
                 update: false,
                 cleanup: false,
                 database_url: "postgres://localhost/test".to_string(),
+                backend: None,
                 dry_run: false,
                 force: false,
+                hash_algorithm: HashAlgorithm::Sha256,
+                verify_hashes: false,
+                chunk_strategy: ChunkStrategy::Sentence,
+                concurrency: 1,
+                max_failures: 0,
+                checkpoint: None,
+                from_stdin: false,
             };
 
             let result = args.validate();
@@ -289,8 +514,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let paths = args.paths_as_strings();
@@ -305,8 +538,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         assert_eq!(args.name(), "load");
@@ -320,8 +561,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = CliCommand::validate(&args);
@@ -336,8 +585,16 @@ mod tests {
             update: true,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         assert!(args.update);
@@ -351,8 +608,16 @@ mod tests {
             update: false,
             cleanup: true,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         assert!(args.cleanup);
@@ -366,8 +631,16 @@ mod tests {
             update: true,
             cleanup: true,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         assert!(args.update);
@@ -382,8 +655,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: true,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         assert!(args.force);
@@ -397,8 +678,16 @@ mod tests {
             update: false,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: true,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = args.validate();
@@ -413,8 +702,16 @@ mod tests {
             update: true,
             cleanup: false,
             database_url: "postgres://localhost/test".to_string(),
+            backend: None,
             dry_run: false,
             force: true,
+            hash_algorithm: HashAlgorithm::Sha256,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Sentence,
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint: None,
+            from_stdin: false,
         };
 
         let result = args.validate();
@@ -9,6 +9,7 @@ use clap::Parser;
 use sqlx::PgPool;
 use tracing::info;
 use xze_core::semantic::search::{search_with_chunks, SearchConfig};
+use xze_core::semantic::similarity::SimilarityMetric;
 use xze_core::Result;
 
 /// Search for document chunks using semantic similarity
@@ -31,6 +32,9 @@ use xze_core::Result;
 /// # Set minimum similarity threshold
 /// xze search "error handling" --min-similarity 0.5
 ///
+/// # Rank with the metric the embedding model was trained for
+/// xze search "error handling" --metric dot_product
+///
 /// # Output as JSON
 /// xze search "deployment" --json
 /// ```
@@ -41,7 +45,7 @@ pub struct SearchArgs {
     /// Search query text
     ///
     /// The query will be embedded and compared against all chunks
-    /// in the knowledge base using cosine similarity.
+    /// in the knowledge base using the selected similarity metric.
     #[arg(value_name = "QUERY")]
     pub query: String,
 
@@ -67,6 +71,13 @@ pub struct SearchArgs {
     #[arg(long)]
     pub category: Option<String>,
 
+    /// Similarity metric used to rank chunks
+    ///
+    /// One of cosine, dot_product or euclidean. Must match the metric
+    /// recorded with the stored chunks.
+    #[arg(long, default_value_t = SimilarityMetric::Cosine)]
+    pub metric: SimilarityMetric,
+
     /// Ollama API URL
     ///
     /// URL of the Ollama server for generating embeddings.
@@ -126,12 +137,20 @@ impl CliCommand for SearchArgs {
             max_results: self.max_results,
             min_similarity: self.min_similarity,
             category_filter: self.category.clone(),
+            language_filter: None,
+            similarity_metric: self.metric,
+            alpha: None,
+            synonyms: None,
+            normalizer: None,
         };
 
         // Execute search
         info!(
-            "Searching with config: max={}, min_sim={}, category={:?}",
-            config.max_results, config.min_similarity, config.category_filter
+            "Searching with config: max={}, min_sim={}, category={:?}, metric={}",
+            config.max_results,
+            config.min_similarity,
+            config.category_filter,
+            config.similarity_metric
         );
 
         let results = search_with_chunks(&pool, &self.query, &self.ollama_url, &config)
@@ -292,6 +311,7 @@ impl SearchArgs {
             "max_results": self.max_results,
             "min_similarity": self.min_similarity,
             "category_filter": self.category,
+            "metric": self.metric,
             "results": json_results,
         });
 
@@ -337,6 +357,7 @@ mod tests {
             max_results: 10,
             min_similarity: 0.0,
             category: None,
+            metric: SimilarityMetric::Cosine,
             ollama_url: "http://localhost:11434".to_string(),
             database_url: "postgresql://localhost/test".to_string(),
             json: false,
@@ -354,6 +375,7 @@ mod tests {
             max_results: 0,
             min_similarity: 0.0,
             category: None,
+            metric: SimilarityMetric::Cosine,
             ollama_url: "http://localhost:11434".to_string(),
             database_url: "postgresql://localhost/test".to_string(),
             json: false,
@@ -371,6 +393,7 @@ mod tests {
             max_results: 10,
             min_similarity: 1.5,
             category: None,
+            metric: SimilarityMetric::Cosine,
             ollama_url: "http://localhost:11434".to_string(),
             database_url: "postgresql://localhost/test".to_string(),
             json: false,
@@ -388,6 +411,7 @@ mod tests {
             max_results: 10,
             min_similarity: 0.5,
             category: Some("tutorial".to_string()),
+            metric: SimilarityMetric::DotProduct,
             ollama_url: "http://localhost:11434".to_string(),
             database_url: "postgresql://localhost/test".to_string(),
             json: false,
@@ -444,6 +468,7 @@ mod tests {
             max_results: 10,
             min_similarity: 0.0,
             category: None,
+            metric: SimilarityMetric::Cosine,
             ollama_url: "http://localhost:11434".to_string(),
             database_url: "postgresql://localhost/test".to_string(),
             json: false,
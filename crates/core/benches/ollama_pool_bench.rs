@@ -0,0 +1,45 @@
+//! Benchmark comparing per-call and pooled Ollama HTTP connections
+//!
+//! Requests go to a local mock server, so the numbers isolate client and
+//! connection setup cost rather than model latency.
+//!
+//! Run with: cargo bench --bench ollama_pool_bench
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio::runtime::Runtime;
+use xze_core::ai::client::OllamaClient;
+
+fn bench_connection_reuse(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let (server, _mock) = rt.block_on(async {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/tags")
+            .with_body(r#"{"models":[]}"#)
+            .expect_at_least(1)
+            .create_async()
+            .await;
+        (server, mock)
+    });
+    let url = server.url();
+
+    let mut group = c.benchmark_group("ollama_connection");
+
+    group.bench_function("per_call_client", |b| {
+        b.to_async(&rt).iter(|| async {
+            let client = OllamaClient::new(url.clone());
+            client.list_models().await.unwrap()
+        });
+    });
+
+    let pooled = OllamaClient::new(url.clone());
+    group.bench_function("pooled_client", |b| {
+        b.to_async(&rt)
+            .iter(|| async { pooled.list_models().await.unwrap() });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_connection_reuse);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+//! Benchmark comparing scalar and SIMD cosine similarity
+//!
+//! Scores a query against a large embedding matrix, as search reranking
+//! does, and computes consecutive similarities, as semantic chunking does.
+//! Without the `simd` feature both variants run the scalar loop.
+//!
+//! Run with: cargo bench --features simd --bench similarity_bench
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use xze_core::semantic::similarity::{
+    cosine_similarity, cosine_similarity_scalar, pairwise_similarities, simd_enabled,
+};
+
+const EMBEDDINGS: usize = 4096;
+const DIMENSIONS: usize = 768;
+
+fn embedding(seed: usize) -> Vec<f32> {
+    (0..DIMENSIONS)
+        .map(|i| (((seed * 31 + i * 17) % 101) as f32 - 50.0) / 50.0)
+        .collect()
+}
+
+fn bench_similarity(c: &mut Criterion) {
+    let matrix: Vec<Vec<f32>> = (0..EMBEDDINGS).map(embedding).collect();
+    let query = embedding(EMBEDDINGS);
+    println!("SIMD enabled: {}", simd_enabled());
+
+    let mut group = c.benchmark_group("cosine_similarity_matrix");
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            matrix
+                .iter()
+                .map(|row| cosine_similarity_scalar(black_box(&query), row).unwrap())
+                .sum::<f32>()
+        });
+    });
+
+    group.bench_function("dispatched", |b| {
+        b.iter(|| {
+            matrix
+                .iter()
+                .map(|row| cosine_similarity(black_box(&query), row).unwrap())
+                .sum::<f32>()
+        });
+    });
+
+    group.bench_function("pairwise", |b| {
+        b.iter(|| pairwise_similarities(black_box(&matrix)).unwrap());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_similarity);
+criterion_main!(benches);
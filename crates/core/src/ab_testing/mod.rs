@@ -21,11 +21,12 @@
 //! # }
 //! ```
 
+use crate::error::{Result, XzeError};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Group assignment for A/B testing
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -165,7 +166,7 @@ impl ABTest {
     pub async fn save_assignments(
         &self,
         output_path: &Path,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
         #[derive(Serialize)]
         struct AssignmentData {
             treatment_percentage: f64,
@@ -211,7 +212,9 @@ impl ABTest {
     /// # Errors
     ///
     /// Returns error if file cannot be read or parsed
-    pub async fn load_assignments(input_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn load_assignments(
+        input_path: &Path,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
         #[derive(Deserialize)]
         struct AssignmentData {
             treatment_percentage: f64,
@@ -242,6 +245,12 @@ impl ABTest {
 }
 
 /// A/B test results for comparison
+///
+/// Results accumulate: each run records its documents with
+/// [`record`](Self::record) and [`record_error`](Self::record_error), and
+/// [`accumulate`](Self::accumulate) merges the run into the results saved
+/// next to the assignments file. The comparison is always computed on the
+/// cumulative sample.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ABTestResults {
     /// Test configuration
@@ -255,7 +264,12 @@ pub struct ABTestResults {
 }
 
 /// Statistics for a test group
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Means and variances are kept as running values with Welford's
+/// algorithm, so results can grow across runs without storing every
+/// sample. Failed documents count towards `document_count` and the error
+/// rate but not the means.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GroupStats {
     /// Number of documents
     pub document_count: usize,
@@ -267,10 +281,90 @@ pub struct GroupStats {
     pub error_count: usize,
     /// Error rate (percentage)
     pub error_rate: f64,
+    /// Sum of squared deviations of the extraction time from its mean
+    #[serde(default)]
+    pub extraction_time_m2: f64,
+    /// Sum of squared deviations of the keyword count from its mean
+    #[serde(default)]
+    pub keywords_m2: f64,
+}
+
+impl GroupStats {
+    /// Number of documents extracted without error
+    pub fn success_count(&self) -> usize {
+        self.document_count - self.error_count
+    }
+
+    /// Add a successfully extracted document
+    pub fn record(&mut self, extraction_time_ms: f64, keyword_count: usize) {
+        self.document_count += 1;
+        let n = self.success_count() as f64;
+
+        let delta = extraction_time_ms - self.avg_extraction_time_ms;
+        self.avg_extraction_time_ms += delta / n;
+        self.extraction_time_m2 += delta * (extraction_time_ms - self.avg_extraction_time_ms);
+
+        let keywords = keyword_count as f64;
+        let delta = keywords - self.avg_keywords_per_doc;
+        self.avg_keywords_per_doc += delta / n;
+        self.keywords_m2 += delta * (keywords - self.avg_keywords_per_doc);
+
+        self.update_error_rate();
+    }
+
+    /// Add a document whose extraction failed
+    pub fn record_error(&mut self) {
+        self.document_count += 1;
+        self.error_count += 1;
+        self.update_error_rate();
+    }
+
+    /// Combine with statistics gathered separately
+    ///
+    /// The result is the same as if every document had been recorded here.
+    pub fn merge(&mut self, other: &GroupStats) {
+        let (n_a, n_b) = (self.success_count() as f64, other.success_count() as f64);
+        let n = n_a + n_b;
+        if n > 0.0 {
+            let delta = other.avg_extraction_time_ms - self.avg_extraction_time_ms;
+            self.avg_extraction_time_ms += delta * n_b / n;
+            self.extraction_time_m2 += other.extraction_time_m2 + delta * delta * n_a * n_b / n;
+
+            let delta = other.avg_keywords_per_doc - self.avg_keywords_per_doc;
+            self.avg_keywords_per_doc += delta * n_b / n;
+            self.keywords_m2 += other.keywords_m2 + delta * delta * n_a * n_b / n;
+        }
+
+        self.document_count += other.document_count;
+        self.error_count += other.error_count;
+        self.update_error_rate();
+    }
+
+    /// Sample variance of the extraction time, if there are two samples
+    pub fn extraction_time_variance(&self) -> Option<f64> {
+        sample_variance(self.extraction_time_m2, self.success_count())
+    }
+
+    /// Sample variance of the keyword count, if there are two samples
+    pub fn keywords_variance(&self) -> Option<f64> {
+        sample_variance(self.keywords_m2, self.success_count())
+    }
+
+    fn update_error_rate(&mut self) {
+        self.error_rate = if self.document_count == 0 {
+            0.0
+        } else {
+            self.error_count as f64 / self.document_count as f64 * 100.0
+        };
+    }
+}
+
+fn sample_variance(m2: f64, n: usize) -> Option<f64> {
+    (n >= 2).then(|| m2 / (n - 1) as f64)
 }
 
 /// Statistical comparison between groups
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Comparison {
     /// Difference in extraction time (ms)
     pub time_difference_ms: f64,
@@ -282,7 +376,157 @@ pub struct Comparison {
     pub p_value: Option<f64>,
 }
 
+impl Comparison {
+    /// Compare the treatment group against the control group
+    ///
+    /// Differences are treatment minus control. The improvement is the
+    /// keyword difference relative to the control mean, and the p-value is
+    /// a two-sided Welch's t-test on keywords per document, available once
+    /// each group has two successful documents.
+    pub fn between(control: &GroupStats, treatment: &GroupStats) -> Self {
+        let keyword_difference = treatment.avg_keywords_per_doc - control.avg_keywords_per_doc;
+        let improvement_pct = if control.avg_keywords_per_doc > 0.0 {
+            keyword_difference / control.avg_keywords_per_doc * 100.0
+        } else {
+            0.0
+        };
+
+        let p_value = match (control.keywords_variance(), treatment.keywords_variance()) {
+            (Some(var_c), Some(var_t)) => welch_p_value(
+                control.avg_keywords_per_doc,
+                var_c,
+                control.success_count() as f64,
+                treatment.avg_keywords_per_doc,
+                var_t,
+                treatment.success_count() as f64,
+            ),
+            _ => None,
+        };
+
+        Self {
+            time_difference_ms: treatment.avg_extraction_time_ms - control.avg_extraction_time_ms,
+            keyword_difference,
+            improvement_pct,
+            p_value,
+        }
+    }
+}
+
 impl ABTestResults {
+    /// Create empty results for a test
+    pub fn new(treatment_percentage: f64) -> Self {
+        Self {
+            treatment_percentage,
+            control: GroupStats::default(),
+            treatment: GroupStats::default(),
+            comparison: Comparison::default(),
+        }
+    }
+
+    /// Path of the results file kept next to an assignments file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use xze_core::ab_testing::ABTestResults;
+    ///
+    /// assert_eq!(
+    ///     ABTestResults::path_for(Path::new("ab/assignments.json")),
+    ///     Path::new("ab/assignments.results.json")
+    /// );
+    /// ```
+    pub fn path_for(assignments_path: &Path) -> PathBuf {
+        assignments_path.with_extension("results.json")
+    }
+
+    /// Statistics of one group
+    pub fn group_mut(&mut self, group: ExtractionGroup) -> &mut GroupStats {
+        match group {
+            ExtractionGroup::Control => &mut self.control,
+            ExtractionGroup::Treatment => &mut self.treatment,
+        }
+    }
+
+    /// Add a successfully extracted document to `group`
+    pub fn record(
+        &mut self,
+        group: ExtractionGroup,
+        extraction_time_ms: f64,
+        keyword_count: usize,
+    ) {
+        self.group_mut(group)
+            .record(extraction_time_ms, keyword_count);
+        self.comparison = Comparison::between(&self.control, &self.treatment);
+    }
+
+    /// Add a document whose extraction failed to `group`
+    pub fn record_error(&mut self, group: ExtractionGroup) {
+        self.group_mut(group).record_error();
+        self.comparison = Comparison::between(&self.control, &self.treatment);
+    }
+
+    /// Add the results of another run and recompute the comparison
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the runs used different splits, since
+    /// their groups would not be comparable
+    pub fn merge(&mut self, other: &ABTestResults) -> Result<()> {
+        if self.treatment_percentage != other.treatment_percentage {
+            return Err(XzeError::validation(format!(
+                "Cannot merge A/B results with a {}% treatment split into results with a {}% split",
+                other.treatment_percentage, self.treatment_percentage
+            )));
+        }
+
+        self.control.merge(&other.control);
+        self.treatment.merge(&other.treatment);
+        self.comparison = Comparison::between(&self.control, &self.treatment);
+        Ok(())
+    }
+
+    /// Save results to a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if file cannot be written
+    pub async fn save(&self, output_path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(output_path, json).await?;
+        Ok(())
+    }
+
+    /// Load results from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if file cannot be read or parsed
+    pub async fn load(input_path: &Path) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(input_path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Merge this run into the results saved at `path` and save them
+    ///
+    /// Starts from this run when there are no saved results. Returns the
+    /// cumulative results.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the saved results cannot be read or written, or
+    /// used a different split
+    pub async fn accumulate(&self, path: &Path) -> Result<Self> {
+        let mut cumulative = if tokio::fs::try_exists(path).await? {
+            Self::load(path).await?
+        } else {
+            Self::new(self.treatment_percentage)
+        };
+        cumulative.merge(self)?;
+        cumulative.save(path).await?;
+        Ok(cumulative)
+    }
+
     /// Create a summary of the A/B test results
     pub fn summary(&self) -> String {
         format!(
@@ -325,6 +569,110 @@ impl ABTestResults {
     }
 }
 
+/// Two-sided p-value of Welch's t-test for a difference in means
+///
+/// Returns `None` when both variances are zero, where the test is
+/// undefined.
+fn welch_p_value(
+    mean_a: f64,
+    var_a: f64,
+    n_a: f64,
+    mean_b: f64,
+    var_b: f64,
+    n_b: f64,
+) -> Option<f64> {
+    let (se_a, se_b) = (var_a / n_a, var_b / n_b);
+    let se = se_a + se_b;
+    if se <= 0.0 {
+        return None;
+    }
+
+    let t = (mean_a - mean_b) / se.sqrt();
+    let df = se * se / (se_a * se_a / (n_a - 1.0) + se_b * se_b / (n_b - 1.0));
+    Some(student_t_p_value(t, df))
+}
+
+/// Two-sided tail probability of Student's t distribution
+fn student_t_p_value(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / (df + t * t), df / 2.0, 0.5).clamp(0.0, 1.0)
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // The continued fraction converges quickly only below this point
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz)
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..=200 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        for numerator in [
+            m * (b - m) * x / ((a + m2 - 1.0) * (a + m2)),
+            -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            result *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    result
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |sum, (i, c)| {
+            sum + c / (x + 1.0 + i as f64)
+        });
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,4 +779,103 @@ mod tests {
         assert_eq!(format!("{}", ExtractionGroup::Control), "control");
         assert_eq!(format!("{}", ExtractionGroup::Treatment), "treatment");
     }
+
+    fn recorded(values: &[(f64, usize)]) -> GroupStats {
+        let mut stats = GroupStats::default();
+        for &(time, keywords) in values {
+            stats.record(time, keywords);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_group_stats_running_values() {
+        let mut stats = recorded(&[(10.0, 2), (20.0, 4), (30.0, 9)]);
+        stats.record_error();
+
+        assert_eq!(stats.document_count, 4);
+        assert_eq!(stats.success_count(), 3);
+        assert_eq!(stats.error_rate, 25.0);
+        assert!((stats.avg_extraction_time_ms - 20.0).abs() < 1e-9);
+        assert!((stats.avg_keywords_per_doc - 5.0).abs() < 1e-9);
+        assert!((stats.extraction_time_variance().unwrap() - 100.0).abs() < 1e-9);
+        assert!((stats.keywords_variance().unwrap() - 13.0).abs() < 1e-9);
+        assert!(recorded(&[(1.0, 1)]).keywords_variance().is_none());
+    }
+
+    #[test]
+    fn test_group_stats_merge_matches_single_pass() {
+        let values = [(12.0, 3), (15.5, 7), (9.0, 4), (30.0, 12), (11.0, 5)];
+        let all = recorded(&values);
+
+        let mut merged = recorded(&values[..2]);
+        let mut rest = recorded(&values[2..]);
+        rest.record_error();
+        merged.merge(&rest);
+        merged.merge(&GroupStats::default());
+
+        assert_eq!(merged.document_count, 6);
+        assert_eq!(merged.error_count, 1);
+        assert!((merged.avg_extraction_time_ms - all.avg_extraction_time_ms).abs() < 1e-9);
+        assert!((merged.avg_keywords_per_doc - all.avg_keywords_per_doc).abs() < 1e-9);
+        assert!((merged.extraction_time_m2 - all.extraction_time_m2).abs() < 1e-9);
+        assert!((merged.keywords_m2 - all.keywords_m2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_student_t_p_value() {
+        // Critical values of the t distribution at p = 0.05
+        assert!((student_t_p_value(2.228, 10.0) - 0.05).abs() < 1e-3);
+        assert!((student_t_p_value(-12.706, 1.0) - 0.05).abs() < 1e-3);
+        assert!((student_t_p_value(1.96, 1e6) - 0.05).abs() < 1e-3);
+        assert!((student_t_p_value(0.0, 5.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_results_merge_recomputes_comparison() {
+        let mut first = ABTestResults::new(50.0);
+        first.record(ExtractionGroup::Control, 10.0, 4);
+        first.record(ExtractionGroup::Treatment, 30.0, 8);
+        assert!(first.comparison.p_value.is_none());
+
+        let mut second = ABTestResults::new(50.0);
+        for (time, keywords) in [(11.0, 5), (9.0, 3), (10.0, 4)] {
+            second.record(ExtractionGroup::Control, time, keywords);
+        }
+        for (time, keywords) in [(28.0, 9), (32.0, 7), (30.0, 8)] {
+            second.record(ExtractionGroup::Treatment, time, keywords);
+        }
+        first.merge(&second).unwrap();
+
+        assert_eq!(first.control.document_count, 4);
+        assert!((first.comparison.time_difference_ms - 20.0).abs() < 1e-9);
+        assert!((first.comparison.keyword_difference - 4.0).abs() < 1e-9);
+        assert!((first.comparison.improvement_pct - 100.0).abs() < 1e-9);
+        assert!(first.comparison.p_value.unwrap() < 0.01);
+
+        assert!(first.merge(&ABTestResults::new(25.0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_accumulate_across_runs() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = ABTestResults::path_for(&dir.path().join("assignments.json"));
+
+        let mut run = ABTestResults::new(50.0);
+        run.record(ExtractionGroup::Control, 10.0, 4);
+        run.record_error(ExtractionGroup::Treatment);
+
+        let cumulative = run.accumulate(&path).await.unwrap();
+        assert_eq!(cumulative.control.document_count, 1);
+
+        let cumulative = run.accumulate(&path).await.unwrap();
+        assert_eq!(cumulative.control.document_count, 2);
+        assert_eq!(cumulative.treatment.error_count, 2);
+
+        let loaded = ABTestResults::load(&path).await.unwrap();
+        assert_eq!(loaded.control.document_count, 2);
+        assert_eq!(loaded.treatment.error_rate, 100.0);
+    }
 }
@@ -0,0 +1,414 @@
+//! Global inference budget for AI calls
+//!
+//! This module provides an aggregate throughput cap shared by every AI call
+//! made through an [`OllamaClient`](super::OllamaClient). The cap is enforced
+//! with token buckets that refill continuously: one bucket for estimated
+//! tokens per minute and one for requests per minute. Either limit can be
+//! left unset, and when both are unset the budget is a no-op.
+//!
+//! When the budget is exhausted, callers wait for the buckets to refill
+//! instead of failing immediately. A caller that would have to wait longer
+//! than the configured maximum receives a timeout error.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::ai::budget::{InferenceBudget, InferenceBudgetConfig};
+//!
+//! # async fn example() -> xze_core::Result<()> {
+//! let budget = InferenceBudget::new(InferenceBudgetConfig {
+//!     tokens_per_minute: Some(60_000),
+//!     requests_per_minute: Some(30),
+//!     max_wait_ms: 10_000,
+//! });
+//!
+//! budget.acquire(500).await?;
+//! println!("{:?}", budget.snapshot());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, XzeError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "metrics")]
+use once_cell::sync::Lazy;
+
+#[cfg(feature = "metrics")]
+static BUDGET_REMAINING: Lazy<prometheus::IntGaugeVec> = Lazy::new(|| {
+    let gauge = prometheus::IntGaugeVec::new(
+        prometheus::Opts::new(
+            "xze_inference_budget_remaining",
+            "Remaining global inference budget by kind",
+        ),
+        &["kind"],
+    )
+    .expect("Failed to create inference budget gauge");
+    prometheus::default_registry()
+        .register(Box::new(gauge.clone()))
+        .expect("Failed to register inference budget gauge");
+    gauge
+});
+
+/// Configuration for the global inference budget
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InferenceBudgetConfig {
+    /// Maximum estimated tokens per minute across all AI calls
+    #[serde(
+        default,
+        deserialize_with = "crate::config::deserialize_optional_scalar"
+    )]
+    pub tokens_per_minute: Option<u64>,
+    /// Maximum requests per minute across all AI calls
+    #[serde(
+        default,
+        deserialize_with = "crate::config::deserialize_optional_scalar"
+    )]
+    pub requests_per_minute: Option<u64>,
+    /// Maximum time a call waits for budget before failing, in milliseconds
+    #[serde(
+        default = "default_max_wait_ms",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub max_wait_ms: u64,
+}
+
+impl Default for InferenceBudgetConfig {
+    fn default() -> Self {
+        Self {
+            tokens_per_minute: None,
+            requests_per_minute: None,
+            max_wait_ms: default_max_wait_ms(),
+        }
+    }
+}
+
+impl InferenceBudgetConfig {
+    /// Check whether any limit is configured
+    pub fn is_enabled(&self) -> bool {
+        self.tokens_per_minute.is_some() || self.requests_per_minute.is_some()
+    }
+}
+
+fn default_max_wait_ms() -> u64 {
+    60_000
+}
+
+/// Point-in-time view of the remaining budget
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BudgetSnapshot {
+    /// Whether any limit is configured
+    pub enabled: bool,
+    /// Remaining tokens, if a token limit is configured
+    pub remaining_tokens: Option<u64>,
+    /// Remaining requests, if a request limit is configured
+    pub remaining_requests: Option<u64>,
+    /// Number of calls that had to wait for budget
+    pub throttled_calls: u64,
+    /// Number of calls rejected after exceeding the maximum wait
+    pub rejected_calls: u64,
+}
+
+/// Continuously refilling token bucket
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn per_minute(limit: u64) -> Self {
+        let capacity = limit.max(1) as f64;
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Time until `amount` units are available, or zero if they already are
+    fn wait_for(&self, amount: f64) -> Duration {
+        let amount = amount.min(self.capacity);
+        if self.available >= amount {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((amount - self.available) / self.refill_per_sec)
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available -= amount.min(self.capacity);
+    }
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    tokens: Option<Bucket>,
+    requests: Option<Bucket>,
+    throttled_calls: u64,
+    rejected_calls: u64,
+}
+
+/// Aggregate throughput cap shared across AI calls
+///
+/// Wrap in an `Arc` and hand the same instance to every client that should
+/// draw from the same budget.
+#[derive(Debug)]
+pub struct InferenceBudget {
+    config: InferenceBudgetConfig,
+    state: Mutex<BudgetState>,
+}
+
+impl InferenceBudget {
+    /// Create a budget from configuration
+    pub fn new(config: InferenceBudgetConfig) -> Self {
+        let state = BudgetState {
+            tokens: config.tokens_per_minute.map(Bucket::per_minute),
+            requests: config.requests_per_minute.map(Bucket::per_minute),
+            throttled_calls: 0,
+            rejected_calls: 0,
+        };
+
+        Self {
+            config,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Create a budget that never limits calls
+    pub fn unlimited() -> Self {
+        Self::new(InferenceBudgetConfig::default())
+    }
+
+    /// Get the budget configuration
+    pub fn config(&self) -> &InferenceBudgetConfig {
+        &self.config
+    }
+
+    /// Check whether any limit is configured
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_enabled()
+    }
+
+    /// Reserve budget for one call using an estimated token count
+    ///
+    /// Waits for the buckets to refill when the budget is exhausted. Returns
+    /// immediately when no limit is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns a timeout error if the budget cannot be reserved within
+    /// `max_wait_ms`.
+    pub async fn acquire(&self, estimated_tokens: u64) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let deadline = Instant::now() + Duration::from_millis(self.config.max_wait_ms);
+        let mut throttled = false;
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock();
+                let now = Instant::now();
+                let wait = state.wait_for(now, estimated_tokens as f64);
+
+                if wait.is_zero() {
+                    state.take(estimated_tokens as f64);
+                    self.publish(&state);
+                    return Ok(());
+                }
+
+                if now + wait > deadline {
+                    state.rejected_calls += 1;
+                    return Err(XzeError::timeout(format!(
+                        "waiting for inference budget ({} ms max)",
+                        self.config.max_wait_ms
+                    )));
+                }
+
+                if !throttled {
+                    state.throttled_calls += 1;
+                    throttled = true;
+                }
+                wait
+            };
+
+            tracing::debug!("Inference budget exhausted, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Get a snapshot of the remaining budget
+    pub fn snapshot(&self) -> BudgetSnapshot {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        if let Some(bucket) = state.tokens.as_mut() {
+            bucket.refill(now);
+        }
+        if let Some(bucket) = state.requests.as_mut() {
+            bucket.refill(now);
+        }
+
+        BudgetSnapshot {
+            enabled: self.is_enabled(),
+            remaining_tokens: state.tokens.as_ref().map(|b| b.available as u64),
+            remaining_requests: state.requests.as_ref().map(|b| b.available as u64),
+            throttled_calls: state.throttled_calls,
+            rejected_calls: state.rejected_calls,
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn publish(&self, state: &BudgetState) {
+        if let Some(bucket) = &state.tokens {
+            BUDGET_REMAINING
+                .with_label_values(&["tokens"])
+                .set(bucket.available as i64);
+        }
+        if let Some(bucket) = &state.requests {
+            BUDGET_REMAINING
+                .with_label_values(&["requests"])
+                .set(bucket.available as i64);
+        }
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn publish(&self, _state: &BudgetState) {}
+}
+
+impl Default for InferenceBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl BudgetState {
+    fn wait_for(&mut self, now: Instant, tokens: f64) -> Duration {
+        let mut wait = Duration::ZERO;
+        if let Some(bucket) = self.tokens.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(tokens));
+        }
+        if let Some(bucket) = self.requests.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(1.0));
+        }
+        wait
+    }
+
+    fn take(&mut self, tokens: f64) {
+        if let Some(bucket) = self.tokens.as_mut() {
+            bucket.take(tokens);
+        }
+        if let Some(bucket) = self.requests.as_mut() {
+            bucket.take(1.0);
+        }
+    }
+}
+
+/// Estimate the token cost of a call from its prompt and output limit
+pub fn estimate_tokens(prompt: &str, max_output_tokens: Option<i32>) -> u64 {
+    let prompt_tokens = prompt.len().div_ceil(4) as u64;
+    prompt_tokens + max_output_tokens.unwrap_or(0).max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unlimited_budget_is_noop() {
+        let budget = InferenceBudget::unlimited();
+        for _ in 0..100 {
+            budget.acquire(1_000_000).await.unwrap();
+        }
+
+        let snapshot = budget.snapshot();
+        assert!(!snapshot.enabled);
+        assert_eq!(snapshot.remaining_tokens, None);
+        assert_eq!(snapshot.throttled_calls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_consumes_tokens() {
+        let budget = InferenceBudget::new(InferenceBudgetConfig {
+            tokens_per_minute: Some(1000),
+            requests_per_minute: None,
+            max_wait_ms: 0,
+        });
+
+        budget.acquire(400).await.unwrap();
+        let remaining = budget.snapshot().remaining_tokens.unwrap();
+        assert!((599..=601).contains(&remaining));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_budget_rejects_after_max_wait() {
+        let budget = InferenceBudget::new(InferenceBudgetConfig {
+            tokens_per_minute: None,
+            requests_per_minute: Some(1),
+            max_wait_ms: 10,
+        });
+
+        budget.acquire(0).await.unwrap();
+        let err = budget.acquire(0).await.unwrap_err();
+        assert!(matches!(err, XzeError::Timeout { .. }));
+        assert_eq!(budget.snapshot().rejected_calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_budget_queues_until_refill() {
+        // 600 requests per minute refills one request every 100 ms
+        let budget = InferenceBudget::new(InferenceBudgetConfig {
+            tokens_per_minute: None,
+            requests_per_minute: Some(600),
+            max_wait_ms: 1_000,
+        });
+
+        for _ in 0..600 {
+            budget.acquire(0).await.unwrap();
+        }
+
+        let start = Instant::now();
+        budget.acquire(0).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(budget.snapshot().throttled_calls, 1);
+    }
+
+    #[test]
+    fn test_oversized_request_is_capped_to_capacity() {
+        let mut bucket = Bucket::per_minute(100);
+        assert_eq!(bucket.wait_for(10_000.0), Duration::ZERO);
+        bucket.take(10_000.0);
+        assert_eq!(bucket.available, 0.0);
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens("", None), 0);
+        assert_eq!(estimate_tokens("abcdefgh", Some(10)), 12);
+        assert_eq!(estimate_tokens("abc", Some(-1)), 1);
+    }
+
+    #[test]
+    fn test_config_defaults_from_yaml() {
+        let config: InferenceBudgetConfig = serde_yaml::from_str("tokens_per_minute: 500").unwrap();
+        assert_eq!(config.tokens_per_minute, Some(500));
+        assert_eq!(config.requests_per_minute, None);
+        assert_eq!(config.max_wait_ms, 60_000);
+        assert!(config.is_enabled());
+        assert!(!InferenceBudgetConfig::default().is_enabled());
+    }
+}
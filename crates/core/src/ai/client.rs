@@ -1,40 +1,317 @@
 //! Ollama client implementation for AI operations
 
 use crate::{
+    ai::budget::{estimate_tokens, InferenceBudget},
     config::ModelConfig,
     error::{Result, XzeError},
+    search::EmbeddingCache,
 };
-use reqwest::{Client, ClientBuilder};
+use futures::Stream;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::{Client, ClientBuilder, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tracing::{debug, info, warn};
 
+#[cfg(feature = "metrics")]
+static HTTP_REQUESTS: Lazy<(prometheus::IntCounter, prometheus::IntGauge)> = Lazy::new(|| {
+    let total = prometheus::IntCounter::new(
+        "xze_ollama_http_requests_total",
+        "HTTP requests sent to Ollama",
+    )
+    .expect("Failed to create Ollama request counter");
+    let active = prometheus::IntGauge::new(
+        "xze_ollama_http_active_requests",
+        "Ollama HTTP requests currently holding a connection",
+    )
+    .expect("Failed to create Ollama active request gauge");
+    prometheus::default_registry()
+        .register(Box::new(total.clone()))
+        .expect("Failed to register Ollama request counter");
+    prometheus::default_registry()
+        .register(Box::new(active.clone()))
+        .expect("Failed to register Ollama active request gauge");
+    (total, active)
+});
+
+/// Number of recent inference calls kept for latency percentiles
+const LATENCY_SAMPLES: usize = 512;
+
+/// Default time allowed for streamed generation, embeddings and model
+/// pulls, unless the single-shot timeout is longer
+pub const DEFAULT_STREAM_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// Clients returned by [`OllamaClient::shared`], keyed by base URL
+static SHARED_CLIENTS: Lazy<Mutex<HashMap<String, OllamaClient>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Connection pool settings for the Ollama HTTP client
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HttpPoolConfig {
+    /// Idle connections kept open per host
+    #[serde(
+        default = "default_max_idle_per_host",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub max_idle_per_host: usize,
+    /// Seconds an idle connection is kept before it is closed
+    #[serde(
+        default = "default_idle_timeout_secs",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub idle_timeout_secs: u64,
+    /// TCP keep-alive interval in seconds (0 disables keep-alive probes)
+    #[serde(
+        default = "default_tcp_keepalive_secs",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub tcp_keepalive_secs: u64,
+}
+
+impl Default for HttpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: default_max_idle_per_host(),
+            idle_timeout_secs: default_idle_timeout_secs(),
+            tcp_keepalive_secs: default_tcp_keepalive_secs(),
+        }
+    }
+}
+
+fn default_max_idle_per_host() -> usize {
+    32
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_tcp_keepalive_secs() -> u64 {
+    60
+}
+
+/// Build a pooled HTTP client
+pub fn build_http_client(timeout: Duration, pool: &HttpPoolConfig) -> Result<Client> {
+    let keepalive =
+        (pool.tcp_keepalive_secs > 0).then(|| Duration::from_secs(pool.tcp_keepalive_secs));
+
+    ClientBuilder::new()
+        .timeout(timeout)
+        .pool_max_idle_per_host(pool.max_idle_per_host)
+        .pool_idle_timeout(Duration::from_secs(pool.idle_timeout_secs))
+        .tcp_keepalive(keepalive)
+        .build()
+        .map_err(|e| XzeError::network(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// Map an error reading a response body; timeouts are network errors
+fn read_error(context: &str, error: reqwest::Error) -> XzeError {
+    if error.is_timeout() {
+        XzeError::network(format!("{}: {}", context, error))
+    } else {
+        XzeError::ai(format!("{}: {}", context, error))
+    }
+}
+
+/// Usage of a client's connection pool
+///
+/// reqwest does not expose its idle connection count, so the pool is
+/// described by its configured idle capacity and the requests currently in
+/// flight, each of which holds one connection.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Requests sent since the client was created
+    pub total_requests: u64,
+    /// Requests currently waiting for a response
+    pub active_requests: usize,
+    /// Idle connections kept open per host
+    pub max_idle_per_host: usize,
+    /// Seconds an idle connection is kept
+    pub idle_timeout_secs: u64,
+}
+
+#[derive(Debug, Default)]
+struct PoolCounters {
+    total: AtomicU64,
+    active: AtomicUsize,
+    /// Latencies of the most recent generate and embed calls
+    latencies: Mutex<VecDeque<Duration>>,
+}
+
+/// Decrements the active request count when a request finishes
+struct ActiveRequest<'a>(&'a PoolCounters);
+
+impl<'a> ActiveRequest<'a> {
+    fn start(counters: &'a PoolCounters) -> Self {
+        counters.total.fetch_add(1, Ordering::Relaxed);
+        counters.active.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        {
+            HTTP_REQUESTS.0.inc();
+            HTTP_REQUESTS.1.inc();
+        }
+        Self(counters)
+    }
+}
+
+impl Drop for ActiveRequest<'_> {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        HTTP_REQUESTS.1.dec();
+    }
+}
+
 /// Ollama client for interacting with the Ollama API
+///
+/// The underlying HTTP client pools connections; clones share the pool, so
+/// create one client and clone it rather than constructing one per call.
 #[derive(Debug, Clone)]
 pub struct OllamaClient {
     client: Client,
     base_url: String,
+    budget: Arc<InferenceBudget>,
+    pool: HttpPoolConfig,
+    counters: Arc<PoolCounters>,
+    embedding_cache: Option<EmbeddingCache>,
+    /// Time allowed for a single-shot generation
+    timeout: Duration,
+    /// Time allowed for streamed generation, embeddings and model pulls
+    stream_timeout: Duration,
 }
 
 impl OllamaClient {
     /// Create a new Ollama client
     pub fn new(base_url: String) -> Self {
-        let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(300))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        Self { client, base_url }
+        Self::with_timeout(base_url, Duration::from_secs(300))
     }
 
     /// Create a client with custom timeout
     pub fn with_timeout(base_url: String, timeout: Duration) -> Self {
-        let client = ClientBuilder::new()
-            .timeout(timeout)
-            .build()
-            .expect("Failed to create HTTP client");
+        Self::with_pool(base_url, timeout, HttpPoolConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a client with custom timeout and connection pool settings
+    pub fn with_pool(base_url: String, timeout: Duration, pool: HttpPoolConfig) -> Result<Self> {
+        Ok(Self {
+            client: build_http_client(timeout, &pool)?,
+            base_url,
+            budget: Arc::new(InferenceBudget::unlimited()),
+            pool,
+            counters: Arc::new(PoolCounters::default()),
+            embedding_cache: None,
+            timeout,
+            stream_timeout: timeout.max(DEFAULT_STREAM_TIMEOUT),
+        })
+    }
+
+    /// Get a process-wide client for `base_url`
+    ///
+    /// Call sites that need a client briefly, such as a single search, use
+    /// this to reuse pooled connections instead of opening new ones.
+    pub fn shared(base_url: &str) -> Self {
+        SHARED_CLIENTS
+            .lock()
+            .entry(base_url.to_string())
+            .or_insert_with(|| Self::new(base_url.to_string()))
+            .clone()
+    }
+
+    /// Get usage statistics for the connection pool
+    pub fn pool_stats(&self) -> PoolStats {
+        PoolStats {
+            total_requests: self.counters.total.load(Ordering::Relaxed),
+            active_requests: self.counters.active.load(Ordering::Relaxed),
+            max_idle_per_host: self.pool.max_idle_per_host,
+            idle_timeout_secs: self.pool.idle_timeout_secs,
+        }
+    }
+
+    /// Send a request, tracking it in the pool statistics
+    async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let _active = ActiveRequest::start(&self.counters);
+        request.send().await
+    }
+
+    /// Send a generate or embed request, also recording its latency
+    async fn send_inference(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let started = Instant::now();
+        let response = self.send(request).await?;
+        let mut latencies = self.counters.latencies.lock();
+        if latencies.len() == LATENCY_SAMPLES {
+            latencies.pop_front();
+        }
+        latencies.push_back(started.elapsed());
+        Ok(response)
+    }
+
+    /// Latency percentile of recent generate and embed calls
+    ///
+    /// Covers the last 512 calls that got a response, measured until
+    /// Ollama answered. `percentile` is clamped to `0.0..=1.0`; `0.99` is
+    /// the p99. Returns `None` before the first call.
+    pub fn latency_percentile(&self, percentile: f64) -> Option<Duration> {
+        let mut latencies: Vec<Duration> = self.counters.latencies.lock().iter().copied().collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+        let rank = (percentile.clamp(0.0, 1.0) * latencies.len() as f64).ceil() as usize;
+        Some(latencies[rank.saturating_sub(1)])
+    }
 
-        Self { client, base_url }
+    /// Draw generation and embedding calls from a shared inference budget
+    pub fn with_budget(mut self, budget: Arc<InferenceBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Get the inference budget used by this client
+    pub fn budget(&self) -> &Arc<InferenceBudget> {
+        &self.budget
+    }
+
+    /// Set the time allowed for each kind of call
+    ///
+    /// `timeout` covers single-shot generation and `stream_timeout` covers
+    /// streamed generation, embeddings and model pulls, which can
+    /// legitimately run longer. A call that exceeds its timeout fails with
+    /// `XzeError::Network`.
+    pub fn with_timeouts(mut self, timeout: Duration, stream_timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.stream_timeout = stream_timeout;
+        self
+    }
+
+    /// Get the time allowed for a single-shot generation
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Get the time allowed for streamed generation, embeddings and pulls
+    pub fn stream_timeout(&self) -> Duration {
+        self.stream_timeout
+    }
+
+    /// Serve [`embeddings`](Self::embeddings) from `cache` when the same
+    /// model has embedded the same input before
+    pub fn with_embedding_cache(mut self, cache: EmbeddingCache) -> Self {
+        self.embedding_cache = Some(cache);
+        self
+    }
+
+    /// Get the embedding cache, if one is configured
+    pub fn embedding_cache(&self) -> Option<&EmbeddingCache> {
+        self.embedding_cache.as_ref()
     }
 
     /// Get the base URL of the Ollama server
@@ -46,7 +323,7 @@ impl OllamaClient {
     pub async fn health_check(&self) -> Result<bool> {
         let url = format!("{}/api/tags", self.base_url);
 
-        match self.client.get(&url).send().await {
+        match self.send(self.client.get(&url)).await {
             Ok(response) => Ok(response.status().is_success()),
             Err(e) => {
                 warn!("Ollama health check failed: {}", e);
@@ -55,6 +332,33 @@ impl OllamaClient {
         }
     }
 
+    /// Get the version reported by the Ollama server
+    pub async fn version(&self) -> Result<String> {
+        let url = format!("{}/api/version", self.base_url);
+
+        let response = self
+            .send(self.client.get(&url))
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to fetch version: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(XzeError::ai(format!(
+                "Failed to get version: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| XzeError::ai(format!("Failed to parse version response: {}", e)))?;
+
+        body["version"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| XzeError::ai("Version response has no version field"))
+    }
+
     /// List available models
     pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
         let url = format!("{}/api/tags", self.base_url);
@@ -62,9 +366,7 @@ impl OllamaClient {
         debug!("Fetching models from: {}", url);
 
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send(self.client.get(&url))
             .await
             .map_err(|e| XzeError::network(format!("Failed to fetch models: {}", e)))?;
 
@@ -85,15 +387,38 @@ impl OllamaClient {
 
     /// Generate text using a model
     pub async fn generate(&self, request: GenerateRequest) -> Result<String> {
+        self.generate_output(request)
+            .await
+            .map(|output| output.text)
+    }
+
+    /// Generate text, reporting whether it was cut short
+    ///
+    /// `num_predict` is also enforced on the client: the response is read
+    /// as it arrives, and once the text exceeds that many tokens (estimated
+    /// at four bytes each) it is truncated and the rest of the response is
+    /// dropped. This stops a backend that ignores the limit from producing
+    /// unbounded output. Output that Ollama itself stopped at the limit is
+    /// reported as truncated too.
+    pub async fn generate_output(&self, request: GenerateRequest) -> Result<GenerateOutput> {
         let url = format!("{}/api/generate", self.base_url);
 
         debug!("Generating with model: {}", request.model);
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+        if let Some(options) = &request.options {
+            options.validate()?;
+        }
+
+        let max_output = request.options.as_ref().and_then(|o| o.num_predict);
+        self.budget
+            .acquire(estimate_tokens(&request.prompt, max_output))
+            .await?;
+        let max_bytes = max_output
+            .filter(|&tokens| tokens > 0)
+            .map(|tokens| tokens as usize * 4);
+
+        let mut response = self
+            .send_inference(self.client.post(&url).json(&request).timeout(self.timeout))
             .await
             .map_err(|e| XzeError::network(format!("Failed to send generate request: {}", e)))?;
 
@@ -104,38 +429,137 @@ impl OllamaClient {
             )));
         }
 
-        // Handle streaming response (Ollama returns JSONL)
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| XzeError::ai(format!("Failed to read response: {}", e)))?;
+        // Ollama streams JSONL; read it line by line so a runaway
+        // generation can be abandoned part way through
+        let mut output = GenerateOutput::default();
+        let mut pending = Vec::new();
+        let mut done = false;
+        while !done {
+            let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| read_error("Failed to read response", e))?
+            else {
+                break;
+            };
+            pending.extend_from_slice(&chunk);
 
-        // Parse the last line of the JSONL response
-        let mut generated_text = String::new();
-        for line in response_text.lines() {
-            if line.trim().is_empty() {
-                continue;
+            while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                done = output.push_line(&line)?;
+                if let Some(max_bytes) = max_bytes {
+                    done |= output.truncate_to(max_bytes);
+                }
+                if done {
+                    break;
+                }
             }
+        }
+        if !done {
+            output.push_line(&pending)?;
+            if let Some(max_bytes) = max_bytes {
+                output.truncate_to(max_bytes);
+            }
+        }
+        drop(response);
 
-            let generate_response: GenerateResponse = serde_json::from_str(line)
-                .map_err(|e| XzeError::ai(format!("Failed to parse response line: {}", e)))?;
+        // Ollama usually omits the matched stop sequence, but strip it in
+        // case a model or proxy echoes it back
+        if let Some(options) = &request.options {
+            output.text = strip_stop_sequences(&output.text, &options.stop);
+        }
 
-            generated_text.push_str(&generate_response.response);
+        if output.text.is_empty() {
+            return Err(XzeError::ai("No response generated"));
+        }
 
-            if generate_response.done {
-                break;
-            }
+        if output.truncated {
+            warn!(
+                "Generation truncated at {} characters by the output limit",
+                output.text.len()
+            );
+        } else {
+            info!("Generated {} characters of text", output.text.len());
         }
+        Ok(output)
+    }
 
-        if generated_text.is_empty() {
-            return Err(XzeError::ai("No response generated"));
+    /// Generate text, yielding chunks as Ollama emits them
+    ///
+    /// Sends the request with `stream` set and yields the `response` text
+    /// of each NDJSON line, skipping empty ones, until the line marked
+    /// `done`. Lines split across reads are reassembled. The stream ends
+    /// with an error if a line cannot be parsed or the connection drops
+    /// before `done`; nothing is yielded after an error. Unlike
+    /// [`generate_output`](Self::generate_output), output limits and stop
+    /// sequences are left to Ollama.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for invalid options, a budget error if
+    /// the inference budget is exhausted, a network error if the request
+    /// cannot be sent and an AI error for a non-success status
+    pub async fn generate_stream(
+        &self,
+        mut request: GenerateRequest,
+    ) -> Result<impl Stream<Item = Result<String>> + Send + 'static> {
+        let url = format!("{}/api/generate", self.base_url);
+
+        debug!("Streaming generation with model: {}", request.model);
+
+        if let Some(options) = &request.options {
+            options.validate()?;
+        }
+
+        let max_output = request.options.as_ref().and_then(|o| o.num_predict);
+        self.budget
+            .acquire(estimate_tokens(&request.prompt, max_output))
+            .await?;
+
+        request.stream = true;
+        let response = self
+            .send_inference(
+                self.client
+                    .post(&url)
+                    .json(&request)
+                    .timeout(self.stream_timeout),
+            )
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to send generate request: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(XzeError::ai(format!(
+                "Generate request failed: HTTP {}",
+                response.status()
+            )));
         }
 
-        info!("Generated {} characters of text", generated_text.len());
-        Ok(generated_text)
+        let lines = GenerateLines {
+            response,
+            pending: Vec::new(),
+            done: false,
+        };
+        Ok(futures::stream::unfold(Some(lines), |lines| async move {
+            let mut lines = lines?;
+            match lines.next_text().await {
+                Ok(Some(text)) => Some((Ok(text), Some(lines))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        }))
     }
 
-    /// Pull a model if not available
+    /// Pull a model from the Ollama library
+    ///
+    /// Reads the NDJSON progress Ollama streams while downloading and emits
+    /// it as tracing events: each new status at info level and download
+    /// progress at debug level, every ten percent of a layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a network error if the request fails or the connection drops
+    /// before Ollama reports success, and an AI error for a non-success
+    /// status or an `error` line in the progress
     pub async fn pull_model(&self, model_name: &str) -> Result<()> {
         let url = format!("{}/api/pull", self.base_url);
 
@@ -143,14 +567,16 @@ impl OllamaClient {
 
         let request = PullRequest {
             name: model_name.to_string(),
-            stream: false,
+            stream: true,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+        let mut response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .json(&request)
+                    .timeout(self.stream_timeout),
+            )
             .await
             .map_err(|e| XzeError::network(format!("Failed to pull model: {}", e)))?;
 
@@ -161,6 +587,30 @@ impl OllamaClient {
             )));
         }
 
+        let mut progress = PullProgress::default();
+        let mut pending = Vec::new();
+        loop {
+            let chunk = response.chunk().await.map_err(|e| {
+                XzeError::network(format!("Model pull of {} interrupted: {}", model_name, e))
+            })?;
+            let Some(chunk) = chunk else {
+                // The final line may lack a newline
+                progress.push_line(model_name, &pending)?;
+                break;
+            };
+            pending.extend_from_slice(&chunk);
+            while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                progress.push_line(model_name, &line)?;
+            }
+        }
+
+        if !progress.succeeded {
+            return Err(XzeError::network(format!(
+                "Model pull of {} ended before Ollama reported success",
+                model_name
+            )));
+        }
         info!("Successfully pulled model: {}", model_name);
         Ok(())
     }
@@ -185,11 +635,17 @@ impl OllamaClient {
 
         debug!("Generating embeddings with model: {}", request.model);
 
+        self.budget
+            .acquire(estimate_tokens(&request.prompt, None))
+            .await?;
+
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+            .send_inference(
+                self.client
+                    .post(&url)
+                    .json(&request)
+                    .timeout(self.stream_timeout),
+            )
             .await
             .map_err(|e| XzeError::network(format!("Failed to send embed request: {}", e)))?;
 
@@ -203,10 +659,41 @@ impl OllamaClient {
         let embed_response: EmbedResponse = response
             .json()
             .await
-            .map_err(|e| XzeError::ai(format!("Failed to parse embed response: {}", e)))?;
+            .map_err(|e| read_error("Failed to parse embed response", e))?;
 
         Ok(embed_response.embedding)
     }
+
+    /// Embed `input` with `model` through `/api/embeddings`
+    ///
+    /// Uses the embedding cache when one is configured, so repeated inputs
+    /// do not reach Ollama again.
+    pub async fn embeddings(&self, model: &str, input: &str) -> Result<Vec<f32>> {
+        let request = || EmbedRequest {
+            model: model.to_string(),
+            prompt: input.to_string(),
+        };
+        let Some(cache) = &self.embedding_cache else {
+            return self.embed(request()).await;
+        };
+
+        let key = format!("{}\n{}", model, input);
+        let embedding = cache
+            .get_or_compute(&key, |_| self.embed(request()))
+            .await?;
+        Ok(embedding.as_ref().clone())
+    }
+
+    /// Embed each of `inputs` with `model`, in order
+    ///
+    /// Stops at the first failure.
+    pub async fn embeddings_batch(&self, model: &str, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            embeddings.push(self.embeddings(model, input).await?);
+        }
+        Ok(embeddings)
+    }
 }
 
 /// Information about an available model
@@ -246,6 +733,145 @@ pub struct GenerateOptions {
     pub top_k: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
+    /// Sequences that end generation; passed to Ollama's `options.stop`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+}
+
+impl GenerateOptions {
+    /// Set the stop sequences
+    pub fn with_stop(mut self, stop: Vec<String>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Validate the options
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if any stop sequence is empty, since an
+    /// empty sequence would end generation immediately.
+    pub fn validate(&self) -> Result<()> {
+        if self.stop.iter().any(|s| s.is_empty()) {
+            return Err(XzeError::validation("Stop sequences must not be empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Truncate `text` at the earliest stop sequence and trim trailing whitespace
+pub fn strip_stop_sequences(text: &str, stop: &[String]) -> String {
+    let end = stop
+        .iter()
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| text.find(s.as_str()))
+        .min()
+        .unwrap_or(text.len());
+
+    text[..end].trim_end().to_string()
+}
+
+/// Text produced by a generate call
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerateOutput {
+    /// Generated text
+    pub text: String,
+    /// Whether generation stopped at the output limit
+    pub truncated: bool,
+}
+
+impl GenerateOutput {
+    /// Append one JSONL response line; returns whether generation is done
+    fn push_line(&mut self, line: &[u8]) -> Result<bool> {
+        let line = std::str::from_utf8(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse response line: {}", e)))?;
+        if line.trim().is_empty() {
+            return Ok(false);
+        }
+
+        let generate_response: GenerateResponse = serde_json::from_str(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse response line: {}", e)))?;
+        self.text.push_str(&generate_response.response);
+        if generate_response.done_reason.as_deref() == Some("length") {
+            self.truncated = true;
+        }
+        Ok(generate_response.done)
+    }
+
+    /// Cut the text to at most `max_bytes`, on a character boundary;
+    /// returns whether it was cut
+    fn truncate_to(&mut self, max_bytes: usize) -> bool {
+        if self.text.len() <= max_bytes {
+            return false;
+        }
+        let mut end = max_bytes;
+        while !self.text.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.text.truncate(end);
+        self.truncated = true;
+        true
+    }
+}
+
+/// Reads the NDJSON lines of a streamed generate response
+struct GenerateLines {
+    response: Response,
+    /// Bytes after the last complete line
+    pending: Vec<u8>,
+    /// Whether the line marked `done` was read
+    done: bool,
+}
+
+impl GenerateLines {
+    /// The next non-empty text chunk, or `None` after the `done` line
+    async fn next_text(&mut self) -> Result<Option<String>> {
+        loop {
+            if self.done {
+                return Ok(None);
+            }
+            if let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=end).collect();
+                if let Some(text) = self.parse_line(&line)? {
+                    return Ok(Some(text));
+                }
+                continue;
+            }
+
+            let chunk =
+                self.response.chunk().await.map_err(|e| {
+                    XzeError::network(format!("Generate stream interrupted: {}", e))
+                })?;
+            match chunk {
+                Some(chunk) => self.pending.extend_from_slice(&chunk),
+                None => {
+                    // The final line may lack a newline
+                    let line = std::mem::take(&mut self.pending);
+                    let text = self.parse_line(&line)?;
+                    if !self.done {
+                        return Err(XzeError::network(
+                            "Generate stream ended before the response was done",
+                        ));
+                    }
+                    return Ok(text);
+                }
+            }
+        }
+    }
+
+    /// Parse one line, returning its text unless empty
+    fn parse_line(&mut self, line: &[u8]) -> Result<Option<String>> {
+        let line = std::str::from_utf8(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse response line: {}", e)))?;
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let generate_response: GenerateResponse = serde_json::from_str(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse response line: {}", e)))?;
+        self.done = generate_response.done;
+        Ok(Some(generate_response.response).filter(|text| !text.is_empty()))
+    }
 }
 
 /// Response from text generation
@@ -253,6 +879,9 @@ pub struct GenerateOptions {
 struct GenerateResponse {
     pub response: String,
     pub done: bool,
+    /// Why generation ended, such as `stop` or `length`
+    #[serde(default)]
+    pub done_reason: Option<String>,
     #[serde(default)]
     #[allow(dead_code)]
     pub context: Vec<i32>,
@@ -265,6 +894,72 @@ struct PullRequest {
     stream: bool,
 }
 
+/// One line of `/api/pull` progress
+#[derive(Debug, Deserialize)]
+struct PullStatus {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    digest: Option<String>,
+    #[serde(default)]
+    total: Option<u64>,
+    #[serde(default)]
+    completed: Option<u64>,
+}
+
+/// Progress of a model pull, logged as it is read
+#[derive(Debug, Default)]
+struct PullProgress {
+    status: String,
+    /// Last logged download progress, in tenths
+    tenths: u64,
+    succeeded: bool,
+}
+
+impl PullProgress {
+    /// Log one NDJSON progress line
+    fn push_line(&mut self, model_name: &str, line: &[u8]) -> Result<()> {
+        let line = std::str::from_utf8(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse pull progress: {}", e)))?;
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let update: PullStatus = serde_json::from_str(line)
+            .map_err(|e| XzeError::ai(format!("Failed to parse pull progress: {}", e)))?;
+        if let Some(error) = update.error {
+            return Err(XzeError::ai(format!(
+                "Model pull of {} failed: {}",
+                model_name, error
+            )));
+        }
+
+        if update.status != self.status {
+            info!(model = model_name, "Model pull: {}", update.status);
+            self.status = update.status;
+            self.tenths = 0;
+        }
+        if let (Some(total), Some(completed)) = (update.total, update.completed) {
+            let tenths = completed.saturating_mul(10) / total.max(1);
+            if tenths > self.tenths {
+                self.tenths = tenths;
+                debug!(
+                    model = model_name,
+                    digest = update.digest.as_deref().unwrap_or_default(),
+                    "Model pull {}% ({} of {} bytes)",
+                    tenths * 10,
+                    completed,
+                    total
+                );
+            }
+        }
+        self.succeeded = self.status == "success";
+        Ok(())
+    }
+}
+
 /// Request for embeddings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbedRequest {
@@ -285,6 +980,7 @@ impl Default for GenerateOptions {
             num_predict: Some(2048),
             top_k: None,
             top_p: None,
+            stop: Vec::new(),
         }
     }
 }
@@ -296,6 +992,7 @@ impl From<&ModelConfig> for GenerateOptions {
             num_predict: Some(config.context_window as i32),
             top_k: None,
             top_p: None,
+            stop: Vec::new(),
         }
     }
 }
@@ -303,11 +1000,123 @@ impl From<&ModelConfig> for GenerateOptions {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
 
     #[test]
     fn test_ollama_client_creation() {
         let client = OllamaClient::new("http://localhost:11434".to_string());
         assert_eq!(client.base_url, "http://localhost:11434");
+        assert!(!client.budget().is_enabled());
+    }
+
+    #[test]
+    fn test_ollama_client_with_budget() {
+        let budget = Arc::new(InferenceBudget::new(
+            crate::ai::budget::InferenceBudgetConfig {
+                tokens_per_minute: Some(1000),
+                ..Default::default()
+            },
+        ));
+        let client =
+            OllamaClient::new("http://localhost:11434".to_string()).with_budget(budget.clone());
+        assert!(Arc::ptr_eq(client.budget(), &budget));
+    }
+
+    #[test]
+    fn test_http_pool_config_defaults() {
+        let config: HttpPoolConfig = serde_yaml::from_str("max_idle_per_host: 4").unwrap();
+        assert_eq!(config.max_idle_per_host, 4);
+        assert_eq!(config.idle_timeout_secs, 90);
+        assert_eq!(config.tcp_keepalive_secs, 60);
+    }
+
+    #[test]
+    fn test_shared_client_reuses_pool() {
+        let first = OllamaClient::shared("http://shared.test:11434");
+        let second = OllamaClient::shared("http://shared.test:11434");
+        let other = OllamaClient::shared("http://other.test:11434");
+
+        assert!(Arc::ptr_eq(&first.counters, &second.counters));
+        assert!(!Arc::ptr_eq(&first.counters, &other.counters));
+    }
+
+    #[tokio::test]
+    async fn test_version() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/version")
+            .with_body(r#"{"version":"0.3.12"}"#)
+            .create_async()
+            .await;
+
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+        assert_eq!(client.version().await.unwrap(), "0.3.12");
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_count_requests() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/tags")
+            .with_body(r#"{"models":[]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = OllamaClient::with_pool(
+            server.url(),
+            Duration::from_secs(5),
+            HttpPoolConfig {
+                max_idle_per_host: 2,
+                ..HttpPoolConfig::default()
+            },
+        )
+        .unwrap();
+        let clone = client.clone();
+
+        assert!(client.list_models().await.unwrap().is_empty());
+        assert!(clone.list_models().await.unwrap().is_empty());
+
+        let stats = client.pool_stats();
+        assert_eq!(stats.total_requests, 2);
+        assert_eq!(stats.active_requests, 0);
+        assert_eq!(stats.max_idle_per_host, 2);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_latency_percentile_tracks_inference_calls() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/embeddings")
+            .with_body(r#"{"embedding":[0.5]}"#)
+            .expect(3)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/tags")
+            .with_body(r#"{"models":[]}"#)
+            .create_async()
+            .await;
+
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+        assert_eq!(client.latency_percentile(0.99), None);
+
+        client.list_models().await.unwrap();
+        assert_eq!(client.latency_percentile(0.99), None);
+
+        for _ in 0..3 {
+            let request = EmbedRequest {
+                model: "nomic-embed-text".to_string(),
+                prompt: "text".to_string(),
+            };
+            client.embed(request).await.unwrap();
+        }
+        assert_eq!(client.counters.latencies.lock().len(), 3);
+        let p50 = client.latency_percentile(0.5).unwrap();
+        let p99 = client.latency_percentile(0.99).unwrap();
+        assert!(p50 <= p99);
+        assert_eq!(client.latency_percentile(2.0), Some(p99));
     }
 
     #[test]
@@ -331,6 +1140,44 @@ mod tests {
         assert!(json.contains("Hello world"));
     }
 
+    #[test]
+    fn test_generate_options_stop_serialization() {
+        let json = serde_json::to_value(GenerateOptions::default()).unwrap();
+        assert!(json.get("stop").is_none());
+
+        let options = GenerateOptions::default().with_stop(vec!["<!-- END -->".to_string()]);
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(json["stop"], serde_json::json!(["<!-- END -->"]));
+    }
+
+    #[test]
+    fn test_generate_options_validate_stop() {
+        assert!(GenerateOptions::default().validate().is_ok());
+        assert!(GenerateOptions::default()
+            .with_stop(vec!["\nI hope".to_string()])
+            .validate()
+            .is_ok());
+        assert!(GenerateOptions::default()
+            .with_stop(vec![String::new()])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_strip_stop_sequences() {
+        let stop = vec!["<!-- END -->".to_string(), "\nI hope".to_string()];
+        assert_eq!(
+            strip_stop_sequences("# Doc\n\nBody\n<!-- END -->\ntrailing", &stop),
+            "# Doc\n\nBody"
+        );
+        assert_eq!(
+            strip_stop_sequences("Body\nI hope this helps!", &stop),
+            "Body"
+        );
+        assert_eq!(strip_stop_sequences("Body", &stop), "Body");
+        assert_eq!(strip_stop_sequences("Body", &[]), "Body");
+    }
+
     #[test]
     fn test_model_config_to_options() {
         let config = ModelConfig {
@@ -338,10 +1185,335 @@ mod tests {
             fallback: vec![],
             context_window: 4096,
             temperature: 0.3,
+            auto_pull: false,
         };
 
         let options = GenerateOptions::from(&config);
         assert_eq!(options.temperature, Some(0.3));
         assert_eq!(options.num_predict, Some(4096));
     }
+
+    fn generate_request(num_predict: Option<i32>) -> GenerateRequest {
+        GenerateRequest {
+            model: "test".to_string(),
+            prompt: "Write".to_string(),
+            stream: true,
+            options: Some(GenerateOptions {
+                num_predict,
+                ..GenerateOptions::default()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_caps_runaway_output() {
+        // A backend that ignores num_predict and never finishes
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .with_chunked_body(|w| {
+                for _ in 0..100_000 {
+                    w.write_all(b"{\"response\":\"word \",\"done\":false}\n")?;
+                }
+                Ok(())
+            })
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        let output = client
+            .generate_output(generate_request(Some(50)))
+            .await
+            .unwrap();
+        assert!(output.truncated);
+        assert_eq!(output.text.len(), 199);
+        assert!(output.text.starts_with("word word "));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_generate_reports_backend_length_stop() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"options":{"num_predict":10}}"#.to_string(),
+            ))
+            .with_body(concat!(
+                "{\"response\":\"Hello\",\"done\":false}\n",
+                "{\"response\":\" world\",\"done\":true,\"done_reason\":\"length\"}\n",
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"options":{"num_predict":2048}}"#.to_string(),
+            ))
+            .with_body("{\"response\":\"Complete\",\"done\":true,\"done_reason\":\"stop\"}")
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        let output = client
+            .generate_output(generate_request(Some(10)))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "Hello world");
+        assert!(output.truncated);
+
+        // The final line may lack a newline
+        let output = client
+            .generate_output(generate_request(Some(2048)))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "Complete");
+        assert!(!output.truncated);
+    }
+
+    async fn collect_stream(client: &OllamaClient) -> Vec<Result<String>> {
+        let mut request = generate_request(None);
+        request.stream = false;
+        let stream = client.generate_stream(request).await.unwrap();
+        stream.collect().await
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_yields_chunks_until_done() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"stream":true}"#.to_string(),
+            ))
+            .with_chunked_body(|w| {
+                // Lines and a multi-byte character split across writes
+                w.write_all(b"{\"response\":\"Hel")?;
+                w.flush()?;
+                w.write_all(b"lo\",\"done\":false}\n{\"response\":\"\",\"done\":false}\n")?;
+                w.flush()?;
+                w.write_all(b"{\"response\":\" w\xc3")?;
+                w.flush()?;
+                w.write_all(b"\xb6rld\",\"done\":false}\n{\"response\":\"!\",\"done\":true}")?;
+                // Nothing after done is read
+                w.write_all(b"\n{\"response\":\"ignored\",\"done\":false}\n")
+            })
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        let chunks: Vec<String> = collect_stream(&client)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(chunks, ["Hello", " wörld", "!"]);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_reports_dropped_connection() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_body("{\"response\":\"Partial\",\"done\":false}\n{\"respon")
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        let items = collect_stream(&client).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), "Partial");
+        assert!(items[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_reports_end_without_done() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_body("{\"response\":\"Partial\",\"done\":false}\n")
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        let items = collect_stream(&client).await;
+        assert_eq!(items.len(), 2);
+        let err = items[1].as_ref().unwrap_err();
+        assert!(matches!(err, XzeError::Network { .. }), "{:?}", err);
+        assert!(err.to_string().contains("before the response was done"));
+    }
+
+    #[tokio::test]
+    async fn test_warm_disk_cache_skips_embeddings_endpoint() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("embeddings.json");
+        let mut server = mockito::Server::new_async().await;
+        let embed = server
+            .mock("POST", "/api/embeddings")
+            .with_body(r#"{"embedding":[0.25,0.5]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let cold = EmbeddingCache::new(10).with_persistence(&path).await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5))
+            .with_embedding_cache(cold.clone());
+        client
+            .embeddings("nomic-embed-text", "query")
+            .await
+            .unwrap();
+        cold.flush().await.unwrap();
+
+        // A restarted client answers from the file without calling Ollama
+        let warm = EmbeddingCache::new(10).with_persistence(&path).await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5))
+            .with_embedding_cache(warm);
+        let embedding = client
+            .embeddings("nomic-embed-text", "query")
+            .await
+            .unwrap();
+
+        assert_eq!(embedding, vec![0.25, 0.5]);
+        embed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_are_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("POST", "/api/embeddings")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model":"nomic-embed-text","prompt":"first"}"#.to_string(),
+            ))
+            .with_body(r#"{"embedding":[0.25,0.5,0.75]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let second = server
+            .mock("POST", "/api/embeddings")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"prompt":"second"}"#.to_string(),
+            ))
+            .with_body(r#"{"embedding":[1.0,0.0,0.0]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5))
+            .with_embedding_cache(EmbeddingCache::new(10));
+
+        let embedding = client
+            .embeddings("nomic-embed-text", "first")
+            .await
+            .unwrap();
+        assert_eq!(embedding, [0.25, 0.5, 0.75]);
+        let cached =
+            crate::semantic::embeddings::generate_embeddings(&client, "nomic-embed-text", "first")
+                .await
+                .unwrap();
+        assert_eq!(cached, embedding);
+        first.assert_async().await;
+
+        // The cache is keyed by model as well as input
+        let inputs = ["second".to_string(), "second".to_string()];
+        let embeddings = client.embeddings_batch("model-a", &inputs).await.unwrap();
+        assert_eq!(embeddings, [[1.0, 0.0, 0.0], [1.0, 0.0, 0.0]]);
+        client.embeddings("model-b", "second").await.unwrap();
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_pull_model_reads_progress() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/pull")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"name":"llama3"}"#.to_string(),
+            ))
+            .with_body(concat!(
+                "{\"status\":\"pulling manifest\"}\n",
+                "{\"status\":\"pulling 6a0746a1ec1a\",\"digest\":\"sha256:6a07\",\"total\":200,\"completed\":20}\n",
+                "{\"status\":\"pulling 6a0746a1ec1a\",\"digest\":\"sha256:6a07\",\"total\":200,\"completed\":200}\n",
+                "{\"status\":\"success\"}",
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/pull")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"name":"missing"}"#.to_string(),
+            ))
+            .with_body(concat!(
+                "{\"status\":\"pulling manifest\"}\n",
+                "{\"error\":\"pull model manifest: file does not exist\"}\n",
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/pull")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"name":"cut-off"}"#.to_string(),
+            ))
+            .with_body("{\"status\":\"pulling manifest\"}\n")
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+
+        client.pull_model("llama3").await.unwrap();
+
+        let err = client.pull_model("missing").await.unwrap_err();
+        assert!(matches!(err, XzeError::AiService { .. }), "{:?}", err);
+        assert!(err.to_string().contains("file does not exist"));
+
+        let err = client.pull_model("cut-off").await.unwrap_err();
+        assert!(matches!(err, XzeError::Network { .. }), "{:?}", err);
+    }
+
+    #[test]
+    fn test_stream_timeout_defaults_to_at_least_request_timeout() {
+        let client = OllamaClient::with_timeout(
+            "http://localhost:11434".to_string(),
+            Duration::from_secs(5),
+        );
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+        assert_eq!(client.stream_timeout(), DEFAULT_STREAM_TIMEOUT);
+
+        let slow = OllamaClient::with_timeout(
+            "http://localhost:11434".to_string(),
+            Duration::from_secs(3600),
+        );
+        assert_eq!(slow.stream_timeout(), Duration::from_secs(3600));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_network_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/generate")
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(500));
+                w.write_all(b"{\"response\":\"late\",\"done\":true}\n")
+            })
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/embeddings")
+            .with_chunked_body(|w| {
+                std::thread::sleep(Duration::from_millis(500));
+                w.write_all(br#"{"embedding":[0.5]}"#)
+            })
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5))
+            .with_timeouts(Duration::from_millis(100), Duration::from_secs(5));
+
+        let err = client
+            .generate_output(generate_request(None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XzeError::Network { .. }), "{:?}", err);
+
+        // Embeddings get the longer streaming timeout
+        assert_eq!(client.embeddings("model", "text").await.unwrap(), [0.5]);
+    }
 }
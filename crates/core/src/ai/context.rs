@@ -1,7 +1,33 @@
 //! Context window and token management for AI models
+//!
+//! Prompts for large repositories do not fit in the context window whole.
+//! [`ContextManager::select_relevant`] picks the items of a
+//! [`CodeStructure`] worth spending the budget on instead of cutting the
+//! prompt off at an arbitrary point. Each module, function, type and
+//! configuration file is scored by:
+//!
+//! - relevance to the document type: the public API for reference pages,
+//!   entry points such as `main` or `new` for tutorials, public functions
+//!   and configuration for how-to guides, modules and types for
+//!   explanations
+//! - importance: undocumented public items, which the documentation should
+//!   cover, and call-graph centrality, the number of callers and callees
+//!
+//! Items are added in score order while they fit.
 
 use crate::error::{Result, XzeError};
+use crate::repository::{CodeStructure, Visibility};
+use crate::types::DiátaxisCategory;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Function names treated as entry points when selecting tutorial context
+const ENTRY_POINTS: &[&str] = &[
+    "main", "new", "run", "start", "init", "builder", "default", "open", "connect", "serve",
+];
+
+/// Call-graph degree above which centrality stops adding to an item's score
+const MAX_CENTRALITY: usize = 10;
 
 /// Manages context windows and token budgets for AI models
 #[derive(Debug, Clone)]
@@ -275,6 +301,87 @@ impl ContextManager {
 
         Ok(result)
     }
+
+    /// Select the items of `structure` most relevant to `doc_type` that fit
+    /// in `budget` tokens
+    ///
+    /// The cost of an item is the estimated size of its JSON form, which is
+    /// how prompt templates render it. When everything fits, the structure
+    /// is returned whole. Otherwise the selected structure keeps only the
+    /// chosen items, in their original order, and the call graph between
+    /// them; warnings, annotations and configuration keys are dropped.
+    pub fn select_relevant(
+        &self,
+        structure: &CodeStructure,
+        doc_type: &DiátaxisCategory,
+        budget: usize,
+    ) -> ContextSelection {
+        let mut candidates = candidates(structure, doc_type);
+        for candidate in &mut candidates {
+            candidate.tokens = self.estimate_tokens(&candidate.json);
+        }
+        let total: usize = candidates.iter().map(|c| c.tokens).sum();
+        if total <= budget {
+            return ContextSelection {
+                structure: structure.clone(),
+                included: Vec::new(),
+                omitted: 0,
+                tokens: total,
+            };
+        }
+
+        candidates.sort_by(|a, b| b.score.cmp(&a.score).then(a.order.cmp(&b.order)));
+        let mut used = 0;
+        let mut chosen = Vec::new();
+        for candidate in candidates.iter().filter(|c| c.tokens <= budget) {
+            if used + candidate.tokens <= budget {
+                used += candidate.tokens;
+                chosen.push(candidate);
+            }
+        }
+
+        let included: Vec<String> = chosen.iter().map(|c| c.label.clone()).collect();
+        let omitted = candidates.len() - chosen.len();
+        chosen.sort_by_key(|c| c.order);
+
+        let mut selected = CodeStructure::default();
+        for candidate in &chosen {
+            match candidate.item {
+                Item::Module(i) => selected.modules.push(structure.modules[i].clone()),
+                Item::Function(i) => selected.functions.push(structure.functions[i].clone()),
+                Item::Type(i) => selected.types.push(structure.types[i].clone()),
+                Item::Config(i) => selected.configs.push(structure.configs[i].clone()),
+            }
+        }
+        let functions: HashSet<&str> = selected.functions.iter().map(|f| f.name.as_str()).collect();
+        selected.call_graph = structure
+            .call_graph
+            .iter()
+            .filter(|(caller, _)| functions.contains(caller.as_str()))
+            .map(|(caller, callees)| {
+                let callees = callees
+                    .iter()
+                    .filter(|callee| functions.contains(callee.as_str()))
+                    .cloned()
+                    .collect();
+                (caller.clone(), callees)
+            })
+            .collect();
+
+        tracing::debug!(
+            "Selected {} of {} items ({} tokens) for {} context",
+            included.len(),
+            candidates.len(),
+            used,
+            doc_type
+        );
+        ContextSelection {
+            structure: selected,
+            included,
+            omitted,
+            tokens: used,
+        }
+    }
 }
 
 impl Default for ContextManager {
@@ -353,6 +460,140 @@ impl TokenBudget {
     }
 }
 
+/// Items of a code structure chosen to fit a token budget
+#[derive(Debug, Clone)]
+pub struct ContextSelection {
+    /// The structure narrowed to the selected items
+    pub structure: CodeStructure,
+    /// Labels of the selected items, such as `fn parse`, in the order they
+    /// were chosen; empty when the whole structure fit
+    pub included: Vec<String>,
+    /// Number of items left out
+    pub omitted: usize,
+    /// Estimated tokens of the selected items
+    pub tokens: usize,
+}
+
+impl ContextSelection {
+    /// Whether the whole structure fit
+    pub fn is_complete(&self) -> bool {
+        self.omitted == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    Module(usize),
+    Function(usize),
+    Type(usize),
+    Config(usize),
+}
+
+#[derive(Debug)]
+struct Candidate {
+    item: Item,
+    label: String,
+    json: String,
+    tokens: usize,
+    score: usize,
+    order: usize,
+}
+
+/// How relevant an item kind is to a document type, from 0 to 3
+fn relevance(doc_type: &DiátaxisCategory, item: Item, public: bool, entry_point: bool) -> usize {
+    match (doc_type, item) {
+        (_, Item::Config(_)) => match doc_type {
+            DiátaxisCategory::HowTo => 2,
+            DiátaxisCategory::Tutorial => 1,
+            _ => 0,
+        },
+        (_, _) if !public => match (doc_type, item) {
+            (DiátaxisCategory::Explanation, Item::Module(_) | Item::Type(_)) => 2,
+            _ => 0,
+        },
+        (DiátaxisCategory::Reference, _) => 3,
+        (DiátaxisCategory::Tutorial, Item::Function(_)) if entry_point => 3,
+        (DiátaxisCategory::Tutorial, Item::Module(_)) => 1,
+        (DiátaxisCategory::Tutorial, _) => 2,
+        (DiátaxisCategory::HowTo, Item::Function(_)) => 3,
+        (DiátaxisCategory::HowTo, Item::Type(_)) => 2,
+        (DiátaxisCategory::HowTo, _) => 1,
+        (DiátaxisCategory::Explanation, Item::Module(_) | Item::Type(_)) => 3,
+        (DiátaxisCategory::Explanation, _) => 1,
+    }
+}
+
+/// JSON form of an item, as prompt templates render it
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn candidates(structure: &CodeStructure, doc_type: &DiátaxisCategory) -> Vec<Candidate> {
+    let score = |item, public, documented: bool, entry_point, centrality: usize| {
+        let gap = if public && !documented { 5 } else { 0 };
+        relevance(doc_type, item, public, entry_point) * 20 + gap + centrality.min(MAX_CENTRALITY)
+    };
+    let mut candidates = Vec::new();
+    for (i, module) in structure.modules.iter().enumerate() {
+        let public = module.visibility == Visibility::Public;
+        candidates.push(Candidate {
+            item: Item::Module(i),
+            label: format!("module {}", module.name),
+            json: to_json(module),
+            tokens: 0,
+            score: score(
+                Item::Module(i),
+                public,
+                module.documentation.is_some(),
+                false,
+                0,
+            ),
+            order: candidates.len(),
+        });
+    }
+    for (i, function) in structure.functions.iter().enumerate() {
+        let public = function.visibility == Visibility::Public;
+        let centrality =
+            structure.callers_of(&function.name).len() + structure.callees_of(&function.name).len();
+        candidates.push(Candidate {
+            item: Item::Function(i),
+            label: format!("fn {}", function.name),
+            json: to_json(function),
+            tokens: 0,
+            score: score(
+                Item::Function(i),
+                public,
+                function.documentation.is_some(),
+                ENTRY_POINTS.contains(&function.name.as_str()),
+                centrality,
+            ),
+            order: candidates.len(),
+        });
+    }
+    for (i, ty) in structure.types.iter().enumerate() {
+        let public = ty.visibility == Visibility::Public;
+        candidates.push(Candidate {
+            item: Item::Type(i),
+            label: format!("type {}", ty.name),
+            json: to_json(ty),
+            tokens: 0,
+            score: score(Item::Type(i), public, ty.documentation.is_some(), false, 0),
+            order: candidates.len(),
+        });
+    }
+    for (i, config) in structure.configs.iter().enumerate() {
+        candidates.push(Candidate {
+            item: Item::Config(i),
+            label: format!("config {}", config.path.display()),
+            json: to_json(config),
+            tokens: 0,
+            score: score(Item::Config(i), false, true, false, 0),
+            order: candidates.len(),
+        });
+    }
+    candidates
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,4 +713,100 @@ This is the conclusion.
 
         assert_eq!(budget.usage_percentage(), 50.0);
     }
+
+    fn function(
+        name: &str,
+        visibility: Visibility,
+        documented: bool,
+    ) -> crate::repository::Function {
+        crate::repository::Function {
+            name: name.to_string(),
+            signature: format!("fn {}(input: &str) -> Result<Output>", name),
+            documentation: documented.then(|| format!("Runs {}", name)),
+            parameters: Vec::new(),
+            return_type: None,
+            visibility,
+            is_async: false,
+        }
+    }
+
+    fn structure() -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.functions = vec![
+            function("helper", Visibility::Private, false),
+            function("documented", Visibility::Public, true),
+            function("undocumented", Visibility::Public, false),
+            function("new", Visibility::Public, true),
+        ];
+        structure.call_graph.insert(
+            "documented".to_string(),
+            vec!["helper".to_string(), "undocumented".to_string()],
+        );
+        structure
+    }
+
+    /// Tokens needed for exactly the named functions
+    fn budget_for(manager: &ContextManager, structure: &CodeStructure, names: &[&str]) -> usize {
+        structure
+            .functions
+            .iter()
+            .filter(|f| names.contains(&f.name.as_str()))
+            .map(|f| manager.estimate_tokens(&to_json(f)))
+            .sum()
+    }
+
+    #[test]
+    fn test_select_relevant_keeps_everything_that_fits() {
+        let manager = ContextManager::default();
+        let structure = structure();
+        let selection = manager.select_relevant(&structure, &DiátaxisCategory::Reference, 10_000);
+
+        assert!(selection.is_complete());
+        assert!(selection.included.is_empty());
+        assert_eq!(selection.structure.functions.len(), 4);
+    }
+
+    #[test]
+    fn test_select_relevant_prefers_public_gaps_for_reference() {
+        let manager = ContextManager::default();
+        let structure = structure();
+        let budget = budget_for(&manager, &structure, &["documented", "undocumented"]);
+        let selection = manager.select_relevant(&structure, &DiátaxisCategory::Reference, budget);
+
+        assert_eq!(selection.included, ["fn undocumented", "fn documented"]);
+        assert_eq!(selection.omitted, 2);
+        assert!(selection.tokens <= budget);
+        // Original order is kept, and the call graph only links selected items
+        let names: Vec<&str> = selection
+            .structure
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["documented", "undocumented"]);
+        assert_eq!(
+            selection.structure.call_graph["documented"],
+            ["undocumented"]
+        );
+    }
+
+    #[test]
+    fn test_select_relevant_prefers_entry_points_for_tutorials() {
+        let manager = ContextManager::default();
+        let structure = structure();
+        let budget = budget_for(&manager, &structure, &["new"]);
+        let selection = manager.select_relevant(&structure, &DiátaxisCategory::Tutorial, budget);
+
+        assert_eq!(selection.included, ["fn new"]);
+    }
+
+    #[test]
+    fn test_select_relevant_with_no_budget() {
+        let manager = ContextManager::default();
+        let selection = manager.select_relevant(&structure(), &DiátaxisCategory::HowTo, 0);
+
+        assert!(selection.included.is_empty());
+        assert_eq!(selection.omitted, 4);
+        assert!(selection.structure.is_empty());
+    }
 }
@@ -54,21 +54,27 @@ pub struct ClassifierConfig {
     pub model: String,
 
     /// Temperature for generation (lower = more deterministic)
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub temperature: f32,
 
     /// Minimum confidence threshold for accepting classifications
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub confidence_threshold: f32,
 
     /// Enable detection of multiple intents
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub enable_multi_intent: bool,
 
     /// Cache size (for Phase 2)
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub cache_size: usize,
 
     /// Cache TTL in seconds (for Phase 2)
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub cache_ttl_seconds: u64,
 
     /// Enable metrics collection (for Phase 4)
+    #[serde(deserialize_with = "crate::config::deserialize_scalar")]
     pub enable_metrics: bool,
 }
 
@@ -523,6 +529,7 @@ Be precise and only respond with the format above."#,
                 num_predict: Some(500),
                 top_k: None,
                 top_p: None,
+                stop: Vec::new(),
             }),
         };
 
@@ -0,0 +1,168 @@
+//! Extracting and repairing JSON in model responses
+//!
+//! Models asked for JSON often wrap it in prose or a Markdown fence:
+//!
+//! ````text
+//! Here is the analysis:
+//!
+//! ```json
+//! {"missing": ["tutorial"]}
+//! ```
+//! ````
+//!
+//! [`extract_json`] finds the JSON in such a response. When the JSON itself
+//! is malformed, [`AIAnalysisService::parse_json_response`] sends the model
+//! a [`repair_prompt`] and parses the reply, up to a configurable number of
+//! times.
+//!
+//! [`AIAnalysisService::parse_json_response`]: super::AIAnalysisService::parse_json_response
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::ai::json::extract_json;
+//!
+//! let raw = "Sure! {\"score\": 0.9, \"note\": \"braces } in strings\"} Hope this helps.";
+//! assert_eq!(extract_json(raw), "{\"score\": 0.9, \"note\": \"braces } in strings\"}");
+//!
+//! let fenced = "```json\n[1, 2]\n```";
+//! assert_eq!(extract_json(fenced), "[1, 2]");
+//! ```
+
+/// Longest part of the invalid response quoted back in a repair prompt
+const MAX_QUOTED_CHARS: usize = 4000;
+
+/// The JSON part of a model response
+///
+/// Looks for, in order:
+///
+/// 1. a fenced code block tagged `json`
+/// 2. an untagged fenced block whose content starts with `{` or `[`
+/// 3. the first object or array in the text, matched by bracket depth
+///    outside strings
+///
+/// Returns the trimmed response when none is found, so that parsing
+/// reports an error against the whole text. An object or array that is
+/// never closed runs to the end of the response.
+pub fn extract_json(raw: &str) -> &str {
+    let text = raw.trim();
+    if let Some(block) = fenced_block(text) {
+        return block;
+    }
+    match text.find(['{', '[']) {
+        Some(start) => balanced(&text[start..]),
+        None => text,
+    }
+}
+
+/// Prompt asking the model to correct an invalid JSON response
+pub fn repair_prompt(response: &str, error: &serde_json::Error) -> String {
+    let quoted: String = response.chars().take(MAX_QUOTED_CHARS).collect();
+    format!(
+        "Your previous response was invalid JSON ({}). Return only valid JSON, \
+         with no explanation and no Markdown fences.\n\nPrevious response:\n{}",
+        error, quoted
+    )
+}
+
+/// Content of the preferred fenced code block, if any
+fn fenced_block(text: &str) -> Option<&str> {
+    let mut untagged = None;
+    let mut rest = text;
+    while let Some(open) = rest.find("```") {
+        let after = &rest[open + 3..];
+        let (tag, body) = after.split_once('\n').unwrap_or(("", after));
+        let close = body.find("```")?;
+        let content = body[..close].trim();
+        let tag = tag.trim();
+        if tag.eq_ignore_ascii_case("json") {
+            return Some(content);
+        }
+        if tag.is_empty() && untagged.is_none() && content.starts_with(['{', '[']) {
+            untagged = Some(content);
+        }
+        rest = &body[close + 3..];
+    }
+    untagged
+}
+
+/// The object or array at the start of `text`, up to its closing bracket
+fn balanced(text: &str) -> &str {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return &text[..=i];
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_plain_json() {
+        assert_eq!(extract_json("  {\"a\": 1}\n"), "{\"a\": 1}");
+        assert_eq!(extract_json("not json"), "not json");
+    }
+
+    #[test]
+    fn test_extract_from_prose() {
+        let raw = "The sections are:\n[\"tutorial\", \"how_to\"]\nLet me know.";
+        assert_eq!(extract_json(raw), "[\"tutorial\", \"how_to\"]");
+
+        let nested = "Result: {\"a\": {\"b\": [1, {\"c\": \"]\"}]}} trailing }";
+        assert_eq!(
+            extract_json(nested),
+            "{\"a\": {\"b\": [1, {\"c\": \"]\"}]}}"
+        );
+
+        let escaped = r#"{"quote": "say \"}\" twice"} done"#;
+        assert_eq!(extract_json(escaped), r#"{"quote": "say \"}\" twice"}"#);
+    }
+
+    #[test]
+    fn test_extract_from_fences() {
+        let tagged = "Example:\n```rust\nlet x = [1];\n```\nAnswer:\n```JSON\n{\"ok\": true}\n```";
+        assert_eq!(extract_json(tagged), "{\"ok\": true}");
+
+        let untagged = "```\n{\"ok\": true}\n```";
+        assert_eq!(extract_json(untagged), "{\"ok\": true}");
+    }
+
+    #[test]
+    fn test_unclosed_json_runs_to_end() {
+        assert_eq!(extract_json("Here: {\"a\": [1, 2"), "{\"a\": [1, 2");
+    }
+
+    #[test]
+    fn test_repair_prompt_quotes_response() {
+        let error = serde_json::from_str::<serde_json::Value>("{a}").unwrap_err();
+        let prompt = repair_prompt("{a}", &error);
+        assert!(prompt.starts_with("Your previous response was invalid JSON"));
+        assert!(prompt.ends_with("Previous response:\n{a}"));
+
+        let long = "x".repeat(MAX_QUOTED_CHARS * 2);
+        assert!(repair_prompt(&long, &error).len() < MAX_QUOTED_CHARS + 300);
+    }
+}
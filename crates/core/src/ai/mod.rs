@@ -1,22 +1,35 @@
 //! AI analysis service using Ollama
 
-use crate::{config::ModelConfig, error::Result, repository::CodeStructure, XzeError};
+use crate::{
+    config::{ModelConfig, OllamaConfig},
+    error::{ErrorCategory, Result},
+    repository::CodeStructure,
+    types::DiátaxisCategory,
+    XzeError,
+};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
+pub mod budget;
 pub mod client;
 pub mod confidence;
 pub mod context;
 pub mod health;
 pub mod intent_classifier;
 pub mod intent_types;
+pub mod json;
 pub mod metrics;
+pub mod model_selection;
 pub mod prompts;
 pub mod validator;
 
-pub use client::{GenerateOptions, GenerateRequest, OllamaClient};
+pub use budget::{BudgetSnapshot, InferenceBudget, InferenceBudgetConfig};
+pub use client::{
+    GenerateOptions, GenerateOutput, GenerateRequest, HttpPoolConfig, OllamaClient, PoolStats,
+};
 pub use confidence::{ConfidenceScore, ConfidenceScorer, DocumentType, ScoringContext};
-pub use context::{ContextManager, PromptComponent, TokenBudget};
+pub use context::{ContextManager, ContextSelection, PromptComponent, TokenBudget};
 pub use health::{CacheHealth, HealthCheck, HealthCheckResult, HealthStatus, ServiceHealth};
 pub use intent_classifier::{ClassifierConfig, IntentClassifier};
 pub use intent_types::{
@@ -24,9 +37,13 @@ pub use intent_types::{
     DiataxisIntent,
 };
 pub use metrics::ClassifierMetrics;
-pub use prompts::PromptTemplateLibrary;
+pub use model_selection::{select_models, ModelSelection};
+pub use prompts::{PersonaConfig, PromptTemplateLibrary};
 pub use validator::{ResponseValidator, ValidationResult};
 
+/// Models known to be installed, keyed by Ollama URL and model name
+static AVAILABLE_MODELS: Lazy<tokio::sync::Mutex<HashSet<String>>> = Lazy::new(Default::default);
+
 /// AI analysis service with validation and confidence scoring
 #[derive(Debug)]
 pub struct AIAnalysisService {
@@ -37,6 +54,7 @@ pub struct AIAnalysisService {
     confidence_scorer: ConfidenceScorer,
     context_manager: ContextManager,
     retry_attempts: u32,
+    json_repair_attempts: u32,
     intent_classifier: Option<IntentClassifier>,
 }
 
@@ -53,10 +71,50 @@ impl AIAnalysisService {
             confidence_scorer: ConfidenceScorer::new(),
             context_manager,
             retry_attempts: 3,
+            json_repair_attempts: 2,
             intent_classifier: None,
         }
     }
 
+    /// Create a service from Ollama configuration, including its inference
+    /// budget, timeout and connection pool settings
+    pub fn from_config(config: &OllamaConfig) -> Self {
+        let url = config.url.as_str().trim_end_matches('/').to_string();
+        let client = OllamaClient::with_pool(
+            url.clone(),
+            std::time::Duration::from_secs(config.timeout_seconds),
+            config.pool.clone(),
+        )
+        .expect("Failed to create HTTP client");
+
+        let client = client.with_timeouts(
+            std::time::Duration::from_secs(config.timeout_seconds),
+            std::time::Duration::from_secs(config.stream_timeout_seconds),
+        );
+
+        let mut service = Self::new(url, config.models.clone());
+        service.client = Arc::new(client);
+        service.with_budget(Arc::new(InferenceBudget::new(config.budget.clone())))
+    }
+
+    /// Set the time allowed for a generation and for streamed generation,
+    /// embeddings and model pulls
+    ///
+    /// A generation that times out fails with `XzeError::Network`, which
+    /// the retry loop backs off on like any other network failure.
+    pub fn with_timeouts(
+        mut self,
+        timeout: std::time::Duration,
+        stream_timeout: std::time::Duration,
+    ) -> Self {
+        self.client = Arc::new(
+            (*self.client)
+                .clone()
+                .with_timeouts(timeout, stream_timeout),
+        );
+        self
+    }
+
     /// Create service with custom validator
     pub fn with_validator(mut self, validator: ResponseValidator) -> Self {
         self.validator = validator;
@@ -69,12 +127,49 @@ impl AIAnalysisService {
         self
     }
 
+    /// Prepend a persona to every documentation prompt
+    pub fn with_persona(mut self, persona: PersonaConfig) -> Self {
+        self.prompt_templates = std::mem::take(&mut self.prompt_templates).with_persona(persona);
+        self
+    }
+
+    /// Get the persona used for documents of `category`, if any
+    pub fn persona_for(&self, category: &DiátaxisCategory) -> Option<&str> {
+        self.prompt_templates.persona().for_category(Some(category))
+    }
+
     /// Set maximum retry attempts
     pub fn with_retry_attempts(mut self, attempts: u32) -> Self {
         self.retry_attempts = attempts;
         self
     }
 
+    /// Set how many times an invalid JSON response is sent back to the
+    /// model for repair; 0 disables repair
+    pub fn with_json_repair_attempts(mut self, attempts: u32) -> Self {
+        self.json_repair_attempts = attempts;
+        self
+    }
+
+    /// Draw all AI calls from a shared inference budget
+    ///
+    /// Call this before `with_intent_classifier` so the classifier shares
+    /// the same budgeted client.
+    pub fn with_budget(mut self, budget: Arc<InferenceBudget>) -> Self {
+        self.client = Arc::new((*self.client).clone().with_budget(budget));
+        self
+    }
+
+    /// Get usage statistics for the Ollama connection pool
+    pub fn pool_stats(&self) -> PoolStats {
+        self.client.pool_stats()
+    }
+
+    /// Get a snapshot of the remaining inference budget
+    pub fn budget_snapshot(&self) -> BudgetSnapshot {
+        self.client.budget().snapshot()
+    }
+
     /// Enable intent classification with the given configuration
     pub fn with_intent_classifier(mut self, config: ClassifierConfig) -> Self {
         self.intent_classifier = Some(IntentClassifier::new(config, Arc::clone(&self.client)));
@@ -133,18 +228,25 @@ impl AIAnalysisService {
         let prompt = self.prompt_templates.code_analysis_prompt(structure);
         let context = ScoringContext::new(DocumentType::Summary);
 
-        let response = self.generate_with_validation(&prompt, &context).await?;
+        let response = self
+            .generate_with_validation(&prompt, &context, &[], None)
+            .await?;
 
         Ok(response)
     }
 
     /// Generate API documentation with validation
-    pub async fn generate_api_documentation(&self, structure: &CodeStructure) -> Result<String> {
-        let prompt = self.prompt_templates.api_documentation_prompt(structure);
-        let context = ScoringContext::new(DocumentType::ApiDocs).requires_code();
-
-        let result = self.generate_with_validation(&prompt, &context).await?;
-        Ok(result.summary)
+    ///
+    /// Generation ends at the first of the `stop` sequences, which is not
+    /// included in the returned text.
+    pub async fn generate_api_documentation(
+        &self,
+        structure: &CodeStructure,
+        stop: &[String],
+    ) -> Result<String> {
+        self.generate_documentation(&DiátaxisCategory::Reference, structure, "", stop, None)
+            .await
+            .map(|result| result.summary)
     }
 
     /// Generate tutorial content with validation
@@ -152,21 +254,23 @@ impl AIAnalysisService {
         &self,
         structure: &CodeStructure,
         topic: &str,
+        stop: &[String],
     ) -> Result<String> {
-        let prompt = self.prompt_templates.tutorial_prompt(structure, topic);
-        let context = ScoringContext::new(DocumentType::Tutorial).requires_code();
-
-        let result = self.generate_with_validation(&prompt, &context).await?;
-        Ok(result.summary)
+        self.generate_documentation(&DiátaxisCategory::Tutorial, structure, topic, stop, None)
+            .await
+            .map(|result| result.summary)
     }
 
     /// Generate how-to guide with validation
-    pub async fn generate_howto(&self, structure: &CodeStructure, task: &str) -> Result<String> {
-        let prompt = self.prompt_templates.howto_prompt(structure, task);
-        let context = ScoringContext::new(DocumentType::HowTo).requires_code();
-
-        let result = self.generate_with_validation(&prompt, &context).await?;
-        Ok(result.summary)
+    pub async fn generate_howto(
+        &self,
+        structure: &CodeStructure,
+        task: &str,
+        stop: &[String],
+    ) -> Result<String> {
+        self.generate_documentation(&DiátaxisCategory::HowTo, structure, task, stop, None)
+            .await
+            .map(|result| result.summary)
     }
 
     /// Generate explanation documentation with validation
@@ -174,12 +278,113 @@ impl AIAnalysisService {
         &self,
         structure: &CodeStructure,
         concept: &str,
+        stop: &[String],
     ) -> Result<String> {
-        let prompt = self.prompt_templates.explanation_prompt(structure, concept);
-        let context = ScoringContext::new(DocumentType::Explanation);
+        self.generate_documentation(
+            &DiátaxisCategory::Explanation,
+            structure,
+            concept,
+            stop,
+            None,
+        )
+        .await
+        .map(|result| result.summary)
+    }
 
-        let result = self.generate_with_validation(&prompt, &context).await?;
-        Ok(result.summary)
+    /// Generate documentation of `category`, keeping its confidence score
+    ///
+    /// `subject` is the tutorial topic, how-to task or explanation concept;
+    /// for reference documentation it narrows the page to one module or
+    /// item, and an empty subject documents everything. `max_output_tokens`
+    /// limits the generated text, replacing the context window as the
+    /// limit; output cut at the limit is marked `truncated`.
+    ///
+    /// When the structure does not fit in the context window, the items
+    /// most relevant to `category` are selected with
+    /// [`ContextManager::select_relevant`] and recorded in
+    /// [`AnalysisResult::context_items`].
+    pub async fn generate_documentation(
+        &self,
+        category: &DiátaxisCategory,
+        structure: &CodeStructure,
+        subject: &str,
+        stop: &[String],
+        max_output_tokens: Option<usize>,
+    ) -> Result<AnalysisResult> {
+        let (prompt, context, selection) = self.select_context(category, structure, subject);
+
+        let mut result = self
+            .generate_with_validation(&prompt, &context, stop, max_output_tokens)
+            .await?;
+        if !selection.is_complete() {
+            result.context_items = selection.included;
+            result.omitted_context_items = selection.omitted;
+        }
+        Ok(result)
+    }
+
+    /// Build the prompt for `category` from the items of `structure` that
+    /// fit in the context window
+    fn select_context(
+        &self,
+        category: &DiátaxisCategory,
+        structure: &CodeStructure,
+        subject: &str,
+    ) -> (String, ScoringContext, ContextSelection) {
+        let available = self.context_manager.available_tokens();
+        let (empty_prompt, _) = self.documentation_prompt(category, &CodeStructure::new(), subject);
+        let mut budget =
+            available.saturating_sub(self.context_manager.estimate_tokens(&empty_prompt));
+
+        // Templates can render an item more than once, so shrink the budget
+        // in proportion to the overshoot until the prompt fits
+        let mut attempts = 0;
+        loop {
+            let selection = self
+                .context_manager
+                .select_relevant(structure, category, budget);
+            let (prompt, context) =
+                self.documentation_prompt(category, &selection.structure, subject);
+            let tokens = self.context_manager.estimate_tokens(&prompt);
+            attempts += 1;
+            if tokens <= available || selection.tokens == 0 || attempts == 3 {
+                return (prompt, context, selection);
+            }
+            budget = (budget * available / tokens).min(budget.saturating_sub(1));
+        }
+    }
+
+    /// Prompt and scoring context for documentation of `category`
+    fn documentation_prompt(
+        &self,
+        category: &DiátaxisCategory,
+        structure: &CodeStructure,
+        subject: &str,
+    ) -> (String, ScoringContext) {
+        match category {
+            DiátaxisCategory::Reference => {
+                let mut prompt = self.prompt_templates.api_documentation_prompt(structure);
+                if !subject.is_empty() {
+                    prompt.push_str(&format!("\n\nDocument only {}.", subject));
+                }
+                (
+                    prompt,
+                    ScoringContext::new(DocumentType::ApiDocs).requires_code(),
+                )
+            }
+            DiátaxisCategory::Tutorial => (
+                self.prompt_templates.tutorial_prompt(structure, subject),
+                ScoringContext::new(DocumentType::Tutorial).requires_code(),
+            ),
+            DiátaxisCategory::HowTo => (
+                self.prompt_templates.howto_prompt(structure, subject),
+                ScoringContext::new(DocumentType::HowTo).requires_code(),
+            ),
+            DiátaxisCategory::Explanation => (
+                self.prompt_templates.explanation_prompt(structure, subject),
+                ScoringContext::new(DocumentType::Explanation),
+            ),
+        }
     }
 
     /// Generate text with validation, confidence scoring, and retry logic
@@ -187,6 +392,8 @@ impl AIAnalysisService {
         &self,
         prompt: &str,
         context: &ScoringContext,
+        stop: &[String],
+        max_output_tokens: Option<usize>,
     ) -> Result<AnalysisResult> {
         // Check if prompt fits in context window
         let optimized_prompt = if !self.context_manager.fits_in_context(prompt) {
@@ -197,13 +404,20 @@ impl AIAnalysisService {
         };
 
         let mut last_error = None;
+        let mut network_failure = false;
 
         // Try with retries
         for attempt in 1..=self.retry_attempts {
             tracing::debug!("Generation attempt {} of {}", attempt, self.retry_attempts);
 
-            match self.generate(&optimized_prompt).await {
-                Ok(response) => {
+            match self
+                .generate(&optimized_prompt, stop, max_output_tokens)
+                .await
+            {
+                Ok(GenerateOutput {
+                    text: response,
+                    truncated,
+                }) => {
                     // Validate response
                     let validation = self.validator.validate(&response)?;
 
@@ -212,6 +426,7 @@ impl AIAnalysisService {
                         if attempt < self.retry_attempts {
                             last_error =
                                 Some(format!("Validation failed: {}", validation.summary()));
+                            network_failure = false;
                             continue;
                         }
                         return Err(XzeError::validation(format!(
@@ -234,6 +449,7 @@ impl AIAnalysisService {
                         tracing::warn!("Low confidence score: {:.2}", confidence.overall);
                         if attempt < self.retry_attempts {
                             last_error = Some(format!("Low confidence: {:.2}", confidence.overall));
+                            network_failure = false;
                             continue;
                         }
                     }
@@ -243,11 +459,15 @@ impl AIAnalysisService {
                         confidence: confidence.overall,
                         validation: Some(validation),
                         confidence_score: Some(confidence),
+                        truncated,
+                        context_items: Vec::new(),
+                        omitted_context_items: 0,
                     });
                 }
                 Err(e) => {
                     tracing::warn!("Generation attempt {} failed: {}", attempt, e);
                     last_error = Some(e.to_string());
+                    network_failure = e.category() == ErrorCategory::Network;
 
                     if attempt < self.retry_attempts {
                         // Exponential backoff
@@ -258,68 +478,170 @@ impl AIAnalysisService {
             }
         }
 
-        Err(XzeError::ai(format!(
+        let message = format!(
             "Failed to generate valid response after {} attempts: {}",
             self.retry_attempts,
             last_error.unwrap_or_else(|| "Unknown error".to_string())
-        )))
+        );
+        if network_failure {
+            Err(XzeError::network(message))
+        } else {
+            Err(XzeError::ai(message))
+        }
+    }
+
+    /// Parse a model response that should contain JSON
+    ///
+    /// The JSON is taken from a Markdown fence or surrounding prose with
+    /// [`json::extract_json`]. If it does not parse as `T`, the model is
+    /// asked to return only valid JSON, up to the configured number of
+    /// repair attempts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last parse error as `XzeError::Json` if every repair
+    /// fails, or the generation error if a repair request fails
+    pub async fn parse_json_response<T: serde::de::DeserializeOwned>(
+        &self,
+        raw: &str,
+    ) -> Result<T> {
+        let mut response = raw.to_string();
+        let mut attempt = 0;
+        loop {
+            let error = match serde_json::from_str(json::extract_json(&response)) {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+            if attempt == self.json_repair_attempts {
+                return Err(error.into());
+            }
+            attempt += 1;
+            tracing::warn!(
+                "Invalid JSON response ({}), requesting repair {} of {}",
+                error,
+                attempt,
+                self.json_repair_attempts
+            );
+            response = self
+                .generate(&json::repair_prompt(&response, &error), &[], None)
+                .await?
+                .text;
+        }
     }
 
     /// Generate text using the configured model with fallback
-    async fn generate(&self, prompt: &str) -> Result<String> {
+    async fn generate(
+        &self,
+        prompt: &str,
+        stop: &[String],
+        max_output_tokens: Option<usize>,
+    ) -> Result<GenerateOutput> {
+        if self.model_config.auto_pull {
+            if let Err(e) = self.ensure_model_pulled(&self.model_config.primary).await {
+                tracing::warn!(
+                    "Could not pull primary model {}: {}",
+                    self.model_config.primary,
+                    e
+                );
+            }
+        }
+
         // Try primary model
         match self
-            .generate_with_model(&self.model_config.primary, prompt)
+            .generate_with_model(&self.model_config.primary, prompt, stop, max_output_tokens)
             .await
         {
             Ok(response) => Ok(response),
             Err(e) => {
                 tracing::warn!("Primary model failed: {}, trying fallback", e);
+                let mut last_error = e;
 
                 // Try fallback models
                 for fallback_model in &self.model_config.fallback {
-                    match self.generate_with_model(fallback_model, prompt).await {
+                    match self
+                        .generate_with_model(fallback_model, prompt, stop, max_output_tokens)
+                        .await
+                    {
                         Ok(response) => return Ok(response),
                         Err(e) => {
                             tracing::warn!("Fallback model {} failed: {}", fallback_model, e);
-                            continue;
+                            last_error = e;
                         }
                     }
                 }
 
-                Err(XzeError::ai("All models failed to generate response"))
+                let message = format!("All models failed to generate response: {}", last_error);
+                // Keep an unreachable or hung server a network error
+                if last_error.category() == ErrorCategory::Network {
+                    Err(XzeError::network(message))
+                } else {
+                    Err(XzeError::ai(message))
+                }
             }
         }
     }
 
     /// Generate with a specific model
-    async fn generate_with_model(&self, model: &str, prompt: &str) -> Result<String> {
+    async fn generate_with_model(
+        &self,
+        model: &str,
+        prompt: &str,
+        stop: &[String],
+        max_output_tokens: Option<usize>,
+    ) -> Result<GenerateOutput> {
         let request = client::GenerateRequest {
             model: model.to_string(),
             prompt: prompt.to_string(),
-            stream: false,
+            stream: true,
             options: Some(client::GenerateOptions {
                 temperature: Some(self.model_config.temperature),
-                num_predict: Some(self.model_config.context_window as i32),
+                num_predict: Some(
+                    max_output_tokens.unwrap_or(self.model_config.context_window) as i32,
+                ),
                 top_k: None,
                 top_p: None,
+                stop: stop.to_vec(),
             }),
         };
 
-        self.client.generate(request).await
+        self.client.generate_output(request).await
     }
 
     /// Check if a model is available
+    ///
+    /// A name without a tag matches the `latest` tag.
     pub async fn check_model_availability(&self, model: &str) -> Result<bool> {
         self.client
             .list_models()
             .await
-            .map(|models| models.iter().any(|m| m.name == model))
+            .map(|models| model_selection::is_installed(&models, model))
+    }
+
+    /// Pull `model` unless it is installed
+    ///
+    /// Models known to be installed are remembered per Ollama server for
+    /// the lifetime of the process, so only the first call checks. Checks
+    /// and pulls are serialized so concurrent first requests pull once.
+    async fn ensure_model_pulled(&self, model: &str) -> Result<()> {
+        let key = format!("{}\n{}", self.client.base_url(), model);
+        let mut available = AVAILABLE_MODELS.lock().await;
+        if available.contains(&key) {
+            return Ok(());
+        }
+
+        if !self.check_model_availability(model).await? {
+            tracing::info!("Model {} is not installed, pulling it", model);
+            self.client.pull_model(model).await?;
+        }
+        available.insert(key);
+        Ok(())
     }
 
     /// Generate text with a simple prompt (utility method)
     pub async fn generate_text(&self, prompt: &str) -> Result<String> {
-        self.generate(prompt).await
+        self.generate(prompt, &[], None)
+            .await
+            .map(|output| output.text)
     }
 }
 
@@ -336,6 +658,16 @@ pub struct AnalysisResult {
     /// Detailed confidence scoring
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence_score: Option<ConfidenceScore>,
+    /// Whether the text was cut at the output limit
+    #[serde(default)]
+    pub truncated: bool,
+    /// Items the prompt was limited to when the whole structure did not
+    /// fit in the context window; empty when it did
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_items: Vec<String>,
+    /// Items left out of the prompt to fit the context window
+    #[serde(default)]
+    pub omitted_context_items: usize,
 }
 
 impl AnalysisResult {
@@ -373,12 +705,48 @@ mod tests {
             fallback: vec![],
             context_window: 4096,
             temperature: 0.3,
+            auto_pull: false,
         };
 
         let service = AIAnalysisService::new("http://localhost:11434".to_string(), config);
         assert_eq!(service.model_config.primary, "test-model");
     }
 
+    #[test]
+    fn test_ai_service_from_config_applies_budget() {
+        let mut config = OllamaConfig::default();
+        config.budget.requests_per_minute = Some(10);
+
+        let service = AIAnalysisService::from_config(&config);
+        assert_eq!(service.client.base_url(), "http://localhost:11434");
+        let snapshot = service.budget_snapshot();
+        assert!(snapshot.enabled);
+        assert_eq!(snapshot.remaining_requests, Some(10));
+    }
+
+    #[test]
+    fn test_ai_service_persona_for_category() {
+        let persona = PersonaConfig {
+            system_prompt: Some("You write for fintech engineers.".to_string()),
+            overrides: std::collections::HashMap::from([(
+                DiátaxisCategory::Reference,
+                "You write terse API references.".to_string(),
+            )]),
+        };
+        let service =
+            AIAnalysisService::new("http://localhost:11434".to_string(), ModelConfig::default())
+                .with_persona(persona);
+
+        assert_eq!(
+            service.persona_for(&DiátaxisCategory::Reference),
+            Some("You write terse API references.")
+        );
+        assert_eq!(
+            service.persona_for(&DiátaxisCategory::Tutorial),
+            Some("You write for fintech engineers.")
+        );
+    }
+
     #[test]
     fn test_generate_request_serialization() {
         let request = client::GenerateRequest {
@@ -390,6 +758,7 @@ mod tests {
                 num_predict: Some(100),
                 top_k: None,
                 top_p: None,
+                stop: Vec::new(),
             }),
         };
 
@@ -412,6 +781,9 @@ mod tests {
                 section_count: 5,
             }),
             confidence_score: None,
+            truncated: false,
+            context_items: Vec::new(),
+            omitted_context_items: 0,
         };
 
         assert!(high_quality.is_high_quality());
@@ -421,8 +793,211 @@ mod tests {
             confidence: 0.3,
             validation: None,
             confidence_score: None,
+            truncated: false,
+            context_items: Vec::new(),
+            omitted_context_items: 0,
         };
 
         assert!(!low_quality.is_high_quality());
     }
+
+    #[test]
+    fn test_select_context_fits_large_structure() {
+        let config = ModelConfig {
+            context_window: 2048,
+            ..ModelConfig::default()
+        };
+        let service = AIAnalysisService::new("http://localhost:11434".to_string(), config);
+        let mut structure = CodeStructure::new();
+        for i in 0..200 {
+            structure.functions.push(crate::repository::Function {
+                name: format!("function_{}", i),
+                signature: format!("pub fn function_{}(input: &str) -> Result<()>", i),
+                documentation: None,
+                parameters: Vec::new(),
+                return_type: None,
+                visibility: crate::repository::Visibility::Public,
+                is_async: false,
+            });
+        }
+
+        let (prompt, _, selection) =
+            service.select_context(&DiátaxisCategory::Reference, &structure, "");
+        assert!(service.context_manager.fits_in_context(&prompt));
+        assert!(!selection.is_complete());
+        assert!(!selection.included.is_empty());
+        assert_eq!(
+            selection.included.len() + selection.omitted,
+            structure.functions.len()
+        );
+
+        let small = CodeStructure {
+            functions: structure.functions[..2].to_vec(),
+            ..CodeStructure::default()
+        };
+        let (_, _, selection) = service.select_context(&DiátaxisCategory::Reference, &small, "");
+        assert!(selection.is_complete());
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sections {
+        missing: Vec<String>,
+    }
+
+    fn json_service(url: String) -> AIAnalysisService {
+        let config = ModelConfig {
+            primary: "llama3".to_string(),
+            fallback: vec![],
+            ..ModelConfig::default()
+        };
+        AIAnalysisService::new(url, config)
+    }
+
+    fn generate_body(response: &str) -> String {
+        let response = serde_json::to_string(response).unwrap();
+        format!(
+            "{{\"response\":{},\"done\":true,\"done_reason\":\"stop\"}}\n",
+            response
+        )
+    }
+
+    #[tokio::test]
+    async fn test_parse_json_response_without_repair() {
+        let service = json_service("http://127.0.0.1:1".to_string());
+        let sections: Sections = service
+            .parse_json_response("Here you go:\n```json\n{\"missing\": [\"tutorial\"]}\n```")
+            .await
+            .unwrap();
+        assert_eq!(sections.missing, ["tutorial"]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_json_response_repairs() {
+        let mut server = mockito::Server::new_async().await;
+        let repair = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::Regex(
+                "previous response was invalid JSON".to_string(),
+            ))
+            .with_body(generate_body("{\"missing\": [\"how_to\"]}"))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = json_service(server.url());
+        let sections: Sections = service
+            .parse_json_response("{missing: [how_to]}")
+            .await
+            .unwrap();
+        assert_eq!(sections.missing, ["how_to"]);
+        repair.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_parse_json_response_returns_last_parse_error() {
+        let mut server = mockito::Server::new_async().await;
+        let repair = server
+            .mock("POST", "/api/generate")
+            .with_body(generate_body("still not json"))
+            .expect(3)
+            .create_async()
+            .await;
+
+        let service = json_service(server.url()).with_json_repair_attempts(3);
+        let err = service
+            .parse_json_response::<Sections>("nope")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XzeError::Json(_)), "{:?}", err);
+        repair.assert_async().await;
+
+        let disabled = json_service(server.url()).with_json_repair_attempts(0);
+        assert!(disabled
+            .parse_json_response::<Sections>("nope")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_pull_pulls_missing_primary_once() {
+        let mut server = mockito::Server::new_async().await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .with_body(r#"{"models":[]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let pull = server
+            .mock("POST", "/api/pull")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"name":"llama3","stream":true}"#.to_string(),
+            ))
+            .with_body(concat!(
+                "{\"status\":\"pulling manifest\"}\n",
+                "{\"status\":\"downloading\",\"digest\":\"sha256:1\",\"total\":100,\"completed\":50}\n",
+                "{\"status\":\"success\"}\n",
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let generate = server
+            .mock("POST", "/api/generate")
+            .with_body(generate_body("Generated text"))
+            .expect(2)
+            .create_async()
+            .await;
+
+        let config = ModelConfig {
+            primary: "llama3".to_string(),
+            fallback: vec![],
+            auto_pull: true,
+            ..ModelConfig::default()
+        };
+        let service = AIAnalysisService::new(server.url(), config);
+        for _ in 0..2 {
+            assert_eq!(
+                service.generate_text("Write").await.unwrap(),
+                "Generated text"
+            );
+        }
+        tags.assert_async().await;
+        pull.assert_async().await;
+        generate.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_auto_pull_is_opt_in() {
+        let mut server = mockito::Server::new_async().await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .expect(0)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/generate")
+            .with_body(generate_body("Generated text"))
+            .create_async()
+            .await;
+
+        let service = json_service(server.url());
+        service.generate_text("Write").await.unwrap();
+        tags.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_network_error() {
+        let config = ModelConfig {
+            fallback: vec!["codellama".to_string()],
+            ..ModelConfig::default()
+        };
+        let service = AIAnalysisService::new("http://127.0.0.1:1".to_string(), config)
+            .with_timeouts(
+                std::time::Duration::from_secs(1),
+                std::time::Duration::from_secs(1),
+            );
+
+        let err = service.generate_text("Write").await.unwrap_err();
+        assert!(matches!(err, XzeError::Network { .. }), "{:?}", err);
+        assert!(err.to_string().contains("All models failed"));
+    }
 }
@@ -0,0 +1,219 @@
+//! Per-request model selection
+//!
+//! Lets a caller override the configured primary and fallback models for a
+//! single request, for example to use a larger model on a difficult
+//! repository. Overrides are checked against the models installed on the
+//! Ollama server so an unknown name is rejected up front instead of failing
+//! halfway through generation.
+
+use super::client::{ModelInfo, OllamaClient};
+use crate::config::ModelConfig;
+use crate::error::{Result, XzeError};
+use serde::{Deserialize, Serialize};
+
+/// Models chosen for one request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelSelection {
+    /// Model tried first
+    pub model: String,
+    /// Models tried in order if the primary fails
+    pub fallback_models: Vec<String>,
+}
+
+impl ModelSelection {
+    /// The configured models, without overrides
+    pub fn from_config(config: &ModelConfig) -> Self {
+        Self {
+            model: config.primary.clone(),
+            fallback_models: config.fallback.clone(),
+        }
+    }
+
+    /// A copy of `config` that uses the selected models
+    pub fn apply_to(&self, config: &ModelConfig) -> ModelConfig {
+        ModelConfig {
+            primary: self.model.clone(),
+            fallback: self.fallback_models.clone(),
+            ..config.clone()
+        }
+    }
+}
+
+/// Whether `name` is among the installed models
+///
+/// A name without a tag matches the `latest` tag, as it does for
+/// `ollama run`.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::ai::client::ModelInfo;
+/// use xze_core::ai::model_selection::is_installed;
+///
+/// let installed = vec![ModelInfo {
+///     name: "llama3:latest".to_string(),
+///     size: 0,
+///     digest: String::new(),
+///     modified_at: String::new(),
+/// }];
+/// assert!(is_installed(&installed, "llama3"));
+/// assert!(is_installed(&installed, "llama3:latest"));
+/// assert!(!is_installed(&installed, "llama3:70b"));
+/// ```
+pub fn is_installed(installed: &[ModelInfo], name: &str) -> bool {
+    installed
+        .iter()
+        .any(|m| m.name == name || (!name.contains(':') && m.name == format!("{}:latest", name)))
+}
+
+/// Resolve the models for a request
+///
+/// `model` replaces the configured primary model and `fallback_models`
+/// replaces the configured fallbacks; either may be given alone. The
+/// server is only queried when there is an override to check. The primary
+/// model is never repeated among the fallbacks.
+///
+/// # Errors
+///
+/// Returns `XzeError::Validation` if an override is empty or names a model
+/// that is not installed, and a network or AI error if the installed models
+/// cannot be listed
+pub async fn select_models(
+    client: &OllamaClient,
+    config: &ModelConfig,
+    model: Option<&str>,
+    fallback_models: Option<&[String]>,
+) -> Result<ModelSelection> {
+    let mut selection = ModelSelection::from_config(config);
+    if model.is_none() && fallback_models.is_none() {
+        return Ok(selection);
+    }
+
+    let requested: Vec<&str> = model
+        .into_iter()
+        .chain(
+            fallback_models
+                .unwrap_or_default()
+                .iter()
+                .map(String::as_str),
+        )
+        .collect();
+    if requested.iter().any(|name| name.trim().is_empty()) {
+        return Err(XzeError::validation("Model names cannot be empty"));
+    }
+
+    let installed = client.list_models().await?;
+    let missing: Vec<&str> = requested
+        .into_iter()
+        .filter(|name| !is_installed(&installed, name))
+        .collect();
+    if !missing.is_empty() {
+        let mut available: Vec<&str> = installed.iter().map(|m| m.name.as_str()).collect();
+        available.sort_unstable();
+        return Err(XzeError::validation(format!(
+            "Models not installed: {} (available: {})",
+            missing.join(", "),
+            available.join(", ")
+        )));
+    }
+
+    if let Some(model) = model {
+        selection.model = model.to_string();
+    }
+    if let Some(fallback_models) = fallback_models {
+        selection.fallback_models = fallback_models.to_vec();
+    }
+    let primary = selection.model.clone();
+    selection.fallback_models.retain(|m| *m != primary);
+
+    Ok(selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn server_with_models() -> (mockito::ServerGuard, OllamaClient) {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/tags")
+            .with_body(
+                r#"{"models":[
+                    {"name":"llama3:latest","size":1,"digest":"a","modified_at":""},
+                    {"name":"llama3:70b","size":2,"digest":"b","modified_at":""},
+                    {"name":"codellama:latest","size":3,"digest":"c","modified_at":""}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let client = OllamaClient::with_timeout(server.url(), Duration::from_secs(5));
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_defaults_do_not_query_server() {
+        // Nothing listens on port 1, so any request would fail
+        let client =
+            OllamaClient::with_timeout("http://127.0.0.1:1".to_string(), Duration::from_secs(1));
+        let config = ModelConfig::default();
+
+        let selection = select_models(&client, &config, None, None).await.unwrap();
+        assert_eq!(selection, ModelSelection::from_config(&config));
+    }
+
+    #[tokio::test]
+    async fn test_overrides_replace_defaults() {
+        let (_server, client) = server_with_models().await;
+        let config = ModelConfig {
+            primary: "llama3".to_string(),
+            fallback: vec!["codellama".to_string()],
+            ..ModelConfig::default()
+        };
+
+        let selection = select_models(&client, &config, Some("llama3:70b"), None)
+            .await
+            .unwrap();
+        assert_eq!(selection.model, "llama3:70b");
+        assert_eq!(selection.fallback_models, vec!["codellama"]);
+
+        let fallbacks = vec!["llama3:70b".to_string(), "llama3".to_string()];
+        let selection = select_models(&client, &config, None, Some(&fallbacks))
+            .await
+            .unwrap();
+        assert_eq!(selection.model, "llama3");
+        assert_eq!(selection.fallback_models, vec!["llama3:70b"]);
+
+        let applied = selection.apply_to(&config);
+        assert_eq!(applied.primary, "llama3");
+        assert_eq!(applied.temperature, config.temperature);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_models_not_installed() {
+        let (_server, client) = server_with_models().await;
+        let config = ModelConfig::default();
+
+        let err = select_models(
+            &client,
+            &config,
+            Some("mixtral"),
+            Some(&["codellama".to_string(), "phi3".to_string()]),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }));
+        let message = err.to_string();
+        assert!(message.contains("mixtral, phi3"), "{}", message);
+        assert!(
+            message.contains("available: codellama:latest"),
+            "{}",
+            message
+        );
+
+        let err = select_models(&client, &config, Some(" "), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }));
+    }
+}
@@ -1,15 +1,89 @@
 //! AI prompt templates for documentation generation
 
-use crate::{repository::CodeStructure, types::DiátaxisCategory};
+use crate::{
+    error::{self, XzeError},
+    repository::CodeStructure,
+    types::DiátaxisCategory,
+};
 use handlebars::Handlebars;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 
+/// System prompt that sets the voice of generated documentation
+///
+/// The persona is prepended to every prompt built by
+/// [`PromptTemplateLibrary`], so tone can be set once instead of in each
+/// template.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonaConfig {
+    /// Persona for prompts without a category override
+    /// (e.g., "You are a concise technical writer for a fintech audience")
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Personas that replace `system_prompt` for specific categories
+    #[serde(default)]
+    pub overrides: HashMap<DiátaxisCategory, String>,
+}
+
+impl PersonaConfig {
+    /// Get the persona for prompts of `category`
+    ///
+    /// Prompts that are not tied to a category, such as code analysis,
+    /// use `system_prompt`.
+    pub fn for_category(&self, category: Option<&DiátaxisCategory>) -> Option<&str> {
+        category
+            .and_then(|category| self.overrides.get(category))
+            .or(self.system_prompt.as_ref())
+            .map(|persona| persona.trim())
+    }
+
+    /// Validate the configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if any persona is blank.
+    pub fn validate(&self) -> error::Result<()> {
+        let blank = self
+            .system_prompt
+            .iter()
+            .chain(self.overrides.values())
+            .any(|persona| persona.trim().is_empty());
+        if blank {
+            return Err(XzeError::validation("Persona prompts must not be empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Summarize the call graph as "called by" / "calls" entries per function
+fn call_relationships(structure: &CodeStructure) -> Vec<serde_json::Value> {
+    let mut names: Vec<&String> = structure
+        .call_graph
+        .iter()
+        .flat_map(|(caller, callees)| std::iter::once(caller).chain(callees))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            json!({
+                "name": name,
+                "callers": structure.callers_of(name),
+                "callees": structure.callees_of(name),
+            })
+        })
+        .collect()
+}
+
 /// Prompt template library for AI documentation generation
 #[derive(Debug)]
 pub struct PromptTemplateLibrary {
     handlebars: Handlebars<'static>,
     templates: HashMap<String, String>,
+    persona: PersonaConfig,
 }
 
 impl PromptTemplateLibrary {
@@ -18,12 +92,32 @@ impl PromptTemplateLibrary {
         let mut library = Self {
             handlebars: Handlebars::new(),
             templates: HashMap::new(),
+            persona: PersonaConfig::default(),
         };
 
         library.register_default_templates();
         library
     }
 
+    /// Set the persona prepended to every prompt
+    pub fn with_persona(mut self, persona: PersonaConfig) -> Self {
+        self.persona = persona;
+        self
+    }
+
+    /// Get the persona configuration
+    pub fn persona(&self) -> &PersonaConfig {
+        &self.persona
+    }
+
+    /// Prepend the persona for `category`, if any, to `prompt`
+    fn with_system_prompt(&self, category: Option<&DiátaxisCategory>, prompt: String) -> String {
+        match self.persona.for_category(category) {
+            Some(persona) => format!("{}\n\n{}", persona, prompt),
+            None => prompt,
+        }
+    }
+
     /// Register all default templates
     fn register_default_templates(&mut self) {
         // Code analysis template
@@ -95,26 +189,49 @@ impl PromptTemplateLibrary {
             "public_functions": structure.public_functions().len(),
         });
 
-        self.render_template("code_analysis", &data)
+        let prompt = self
+            .render_template("code_analysis", &data)
             .unwrap_or_else(|_| {
                 format!(
                     "Analyze this codebase with {} items",
                     structure.item_count()
                 )
-            })
+            });
+        self.with_system_prompt(None, prompt)
     }
 
     /// Generate API documentation prompt
+    ///
+    /// Public methods are listed under their type rather than with the free
+    /// functions.
     pub fn api_documentation_prompt(&self, structure: &CodeStructure) -> String {
-        let public_functions = structure.public_functions();
+        let public_functions: Vec<_> = structure
+            .free_functions()
+            .into_iter()
+            .filter(|f| f.visibility == crate::repository::Visibility::Public)
+            .collect();
+        let public_types: Vec<_> = structure
+            .types
+            .iter()
+            .filter(|t| t.visibility == crate::repository::Visibility::Public)
+            .map(|t| {
+                let mut public_type = t.clone();
+                public_type
+                    .methods
+                    .retain(|m| m.visibility == crate::repository::Visibility::Public);
+                public_type
+            })
+            .collect();
         let data = json!({
             "functions": public_functions,
-            "types": structure.types.iter().filter(|t| t.visibility == crate::repository::Visibility::Public).collect::<Vec<_>>(),
+            "types": public_types,
             "modules": structure.modules.iter().filter(|m| m.visibility == crate::repository::Visibility::Public).collect::<Vec<_>>(),
         });
 
-        self.render_template("api_docs", &data)
-            .unwrap_or_else(|_| "Generate API documentation for this codebase".to_string())
+        let prompt = self
+            .render_template("api_docs", &data)
+            .unwrap_or_else(|_| "Generate API documentation for this codebase".to_string());
+        self.with_system_prompt(Some(&DiátaxisCategory::Reference), prompt)
     }
 
     /// Generate tutorial prompt
@@ -126,8 +243,10 @@ impl PromptTemplateLibrary {
             "types": structure.types,
         });
 
-        self.render_template("tutorial", &data)
-            .unwrap_or_else(|_| format!("Create a tutorial about {} for this codebase", topic))
+        let prompt = self
+            .render_template("tutorial", &data)
+            .unwrap_or_else(|_| format!("Create a tutorial about {} for this codebase", topic));
+        self.with_system_prompt(Some(&DiátaxisCategory::Tutorial), prompt)
     }
 
     /// Generate how-to guide prompt
@@ -139,8 +258,10 @@ impl PromptTemplateLibrary {
             "configs": structure.configs,
         });
 
-        self.render_template("howto", &data)
-            .unwrap_or_else(|_| format!("Create a how-to guide for: {}", task))
+        let prompt = self
+            .render_template("howto", &data)
+            .unwrap_or_else(|_| format!("Create a how-to guide for: {}", task));
+        self.with_system_prompt(Some(&DiátaxisCategory::HowTo), prompt)
     }
 
     /// Generate explanation prompt
@@ -150,10 +271,13 @@ impl PromptTemplateLibrary {
             "structure": structure,
             "types": structure.types,
             "modules": structure.modules,
+            "call_relationships": call_relationships(structure),
         });
 
-        self.render_template("explanation", &data)
-            .unwrap_or_else(|_| format!("Explain the concept of {} in this codebase", concept))
+        let prompt = self
+            .render_template("explanation", &data)
+            .unwrap_or_else(|_| format!("Explain the concept of {} in this codebase", concept));
+        self.with_system_prompt(Some(&DiátaxisCategory::Explanation), prompt)
     }
 
     /// Generate documentation for a specific Diátaxis category
@@ -176,8 +300,10 @@ impl PromptTemplateLibrary {
             "category": category.to_string(),
         });
 
-        self.render_template(template_name, &data)
-            .unwrap_or_else(|_| format!("Generate {} documentation: {}", category, context))
+        let prompt = self
+            .render_template(template_name, &data)
+            .unwrap_or_else(|_| format!("Generate {} documentation: {}", category, context));
+        self.with_system_prompt(Some(category), prompt)
     }
 
     /// Generate summary prompt
@@ -187,8 +313,10 @@ impl PromptTemplateLibrary {
             "total_items": structure.item_count(),
         });
 
-        self.render_template("summary", &data)
-            .unwrap_or_else(|_| "Provide a summary of this codebase".to_string())
+        let prompt = self
+            .render_template("summary", &data)
+            .unwrap_or_else(|_| "Provide a summary of this codebase".to_string());
+        self.with_system_prompt(None, prompt)
     }
 
     /// Render a template with data
@@ -391,6 +519,9 @@ Generate API documentation for this codebase.
 **Public Types:**
 {{#each types}}
 - {{kind}} {{name}}{{#if documentation}} - {{documentation}}{{/if}}
+{{#each methods}}
+  - {{name}}: {{signature}}{{#if documentation}} - {{documentation}}{{/if}}
+{{/each}}
 {{/each}}
 
 **Public Modules:**
@@ -459,6 +590,7 @@ mod tests {
             documentation: Some("A test struct".to_string()),
             fields: Vec::new(),
             visibility: Visibility::Public,
+            methods: Vec::new(),
         });
 
         structure
@@ -495,6 +627,42 @@ mod tests {
         assert!(!prompt.is_empty());
     }
 
+    #[test]
+    fn test_api_documentation_prompt_nests_methods() {
+        let library = PromptTemplateLibrary::new();
+        let mut structure = create_test_structure();
+        let method = |name: &str, visibility| Function {
+            name: name.to_string(),
+            signature: format!("pub fn {}()", name),
+            documentation: None,
+            parameters: Vec::new(),
+            return_type: None,
+            visibility,
+            is_async: false,
+        };
+        let methods = vec![
+            (
+                "TestStruct".to_string(),
+                method("start", Visibility::Public),
+            ),
+            (
+                "TestStruct".to_string(),
+                method("reset", Visibility::Private),
+            ),
+        ];
+        structure
+            .functions
+            .extend(methods.iter().map(|(_, m)| m.clone()));
+        structure.attach_methods(methods);
+
+        let prompt = library.api_documentation_prompt(&structure);
+        let methods_start = prompt.find("### struct `TestStruct`").unwrap();
+        assert!(prompt[methods_start..].contains("#### Methods\n\n- **start**: `pub fn start()`"));
+        assert!(!prompt.contains("### start"));
+        assert!(!prompt.contains("reset"));
+        assert!(prompt.contains("### test_function"));
+    }
+
     #[test]
     fn test_tutorial_prompt() {
         let library = PromptTemplateLibrary::new();
@@ -504,6 +672,19 @@ mod tests {
         assert!(prompt.contains("Getting Started"));
     }
 
+    #[test]
+    fn test_explanation_prompt_includes_call_relationships() {
+        let library = PromptTemplateLibrary::new();
+        let mut structure = create_test_structure();
+        structure
+            .call_graph
+            .insert("main".to_string(), vec!["test_function".to_string()]);
+
+        let prompt = library.explanation_prompt(&structure, "startup");
+        assert!(prompt.contains("Call Relationships"));
+        assert!(prompt.contains("`test_function` is called by `main`"));
+    }
+
     #[test]
     fn test_category_prompt() {
         let library = PromptTemplateLibrary::new();
@@ -526,4 +707,38 @@ mod tests {
         let names = library.template_names();
         assert!(names.contains(&"custom".to_string()));
     }
+
+    #[test]
+    fn test_persona_is_prepended_with_category_overrides() {
+        let persona = PersonaConfig {
+            system_prompt: Some("You are a concise technical writer.".to_string()),
+            overrides: HashMap::from([(
+                DiátaxisCategory::Tutorial,
+                "You are a patient teacher.".to_string(),
+            )]),
+        };
+        let library = PromptTemplateLibrary::new().with_persona(persona);
+        let structure = create_test_structure();
+
+        let tutorial = library.tutorial_prompt(&structure, "Basics");
+        assert!(tutorial.starts_with("You are a patient teacher.\n\n"));
+        assert!(!tutorial.contains("concise technical writer"));
+
+        let reference = library.api_documentation_prompt(&structure);
+        assert!(reference.starts_with("You are a concise technical writer.\n\n"));
+
+        let analysis = library.code_analysis_prompt(&structure);
+        assert!(analysis.starts_with("You are a concise technical writer."));
+    }
+
+    #[test]
+    fn test_persona_validation_rejects_blank_prompts() {
+        assert!(PersonaConfig::default().validate().is_ok());
+
+        let blank = PersonaConfig {
+            system_prompt: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert!(blank.validate().is_err());
+    }
 }
@@ -7,6 +7,7 @@
 //! - Documentation impact mapping
 //! - File pattern matching for selective monitoring
 
+pub use crate::git::CommitInfo;
 use crate::{error::Result, git::GitOperations, types::RepositoryId, XzeError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -246,6 +247,46 @@ impl ChangeDetector {
             .any(|f| self.is_documentation_relevant(&f.path))
     }
 
+    /// Commit time of the newest line in a source file, from `git blame`
+    ///
+    /// Returns `None` for files without blame, such as untracked or binary
+    /// files. `source_path` is relative to the repository root.
+    pub fn latest_source_change(
+        &self,
+        repo_path: &Path,
+        source_path: &Path,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let repo = self.git_ops.open(repo_path)?;
+        let blame = self.git_ops.blame_file(&repo, source_path)?;
+        Ok(blame.into_iter().map(|line| line.commit_time).max())
+    }
+
+    /// Check whether a document is older than the sources it describes
+    ///
+    /// A document is stale when any line of `source_paths` was committed
+    /// after `doc_updated_at`. Sources without blame are ignored.
+    pub fn is_documentation_stale(
+        &self,
+        repo_path: &Path,
+        source_paths: &[PathBuf],
+        doc_updated_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        for source_path in source_paths {
+            if let Some(changed_at) = self.latest_source_change(repo_path, source_path)? {
+                if changed_at > doc_updated_at {
+                    debug!(
+                        "{} changed at {}, after documentation update at {}",
+                        source_path.display(),
+                        changed_at,
+                        doc_updated_at
+                    );
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
     // Private helper methods
 
     fn get_head_commit(&self, repo_path: &Path) -> Result<String> {
@@ -354,17 +395,8 @@ impl ChangeDetector {
 
         let mut commits = Vec::new();
         for oid in revwalk {
-            let oid = oid?;
-            let commit = repo.find_commit(oid)?;
-
-            commits.push(CommitInfo {
-                hash: oid.to_string(),
-                author: commit.author().name().unwrap_or("Unknown").to_string(),
-                email: commit.author().email().unwrap_or("").to_string(),
-                message: commit.message().unwrap_or("").to_string(),
-                timestamp: DateTime::from_timestamp(commit.time().seconds(), 0)
-                    .unwrap_or_else(Utc::now),
-            });
+            let commit = repo.find_commit(oid?)?;
+            commits.push(CommitInfo::from(&commit));
         }
 
         Ok(commits)
@@ -533,14 +565,30 @@ pub struct RepositoryChanges {
     pub detected_at: DateTime<Utc>,
 }
 
-/// Individual commit information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CommitInfo {
-    pub hash: String,
-    pub author: String,
-    pub email: String,
-    pub message: String,
-    pub timestamp: DateTime<Utc>,
+impl From<&RepositoryChanges> for crate::repository::ChangeAnalysis {
+    fn from(changes: &RepositoryChanges) -> Self {
+        let mut analysis = Self::new();
+        for file in &changes.files_changed {
+            let path = file.path.to_string_lossy().into_owned();
+            let change_type = match file.change_type {
+                FileChangeType::Added => crate::types::ChangeType::Added,
+                FileChangeType::Modified => crate::types::ChangeType::Modified,
+                FileChangeType::Deleted => crate::types::ChangeType::Deleted,
+                // The previous path is not recorded
+                FileChangeType::Renamed => crate::types::ChangeType::Renamed {
+                    from: path.clone(),
+                    to: path.clone(),
+                },
+            };
+            analysis.add_file_change(crate::types::FileChange {
+                path,
+                change_type,
+                lines_added: file.additions,
+                lines_removed: file.deletions,
+            });
+        }
+        analysis
+    }
 }
 
 /// File change information
@@ -737,6 +785,31 @@ mod tests {
         assert!(impact.affected_sections.contains(&DocSection::ApiReference));
     }
 
+    #[test]
+    fn test_change_analysis_from_repository_changes() {
+        let changes = RepositoryChanges {
+            repository_id: RepositoryId::from("test-repo"),
+            branch: "main".to_string(),
+            from_commit: "abc123".to_string(),
+            to_commit: "def456".to_string(),
+            commits: vec![],
+            files_changed: vec![FileChange {
+                path: PathBuf::from("src/api/handler.rs"),
+                change_type: FileChangeType::Modified,
+                additions: 50,
+                deletions: 20,
+            }],
+            total_additions: 50,
+            total_deletions: 20,
+            detected_at: Utc::now(),
+        };
+
+        let analysis = crate::repository::ChangeAnalysis::from(&changes);
+        assert_eq!(analysis.changed_files.len(), 1);
+        assert_eq!(analysis.total_lines_changed(), 70);
+        assert!(analysis.affects_api());
+    }
+
     #[test]
     fn test_should_update_documentation() {
         let detector = create_test_detector();
@@ -1,13 +1,36 @@
 //! Configuration types for XZe core library
 
-use crate::{ai::ClassifierConfig, types::RepositoryId, Result};
+use crate::{
+    ai::{client::HttpPoolConfig, ClassifierConfig, InferenceBudgetConfig, PersonaConfig},
+    documentation::{
+        granularity::DocumentGranularity,
+        secrets::SecretScanConfig,
+        sink::{OutputConfig, OutputSinkKind},
+    },
+    git::GitPlatform,
+    kb::backend::KbBackendKind,
+    repository::coverage::CoveragePolicy,
+    search::{normalize::NormalizerConfig, rerank::RerankConfig},
+    types::RepositoryId,
+    Result, XzeError,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 use url::Url;
 
+/// Key of the include directive in config files
+const INCLUDE_KEY: &str = "include";
+
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XzeConfig {
+    /// Config files merged beneath this one, relative to this file; see
+    /// [`XzeConfig::from_file`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<PathBuf>,
     /// Version of the configuration schema
     pub version: String,
     /// Documentation repository configuration
@@ -32,11 +55,24 @@ pub struct XzeConfig {
     /// Git settings
     #[serde(default)]
     pub git: GitConfig,
+    /// Delivery of generated documents
+    #[serde(default)]
+    pub output: OutputConfig,
+    /// Item kinds that require documentation, per language
+    #[serde(default)]
+    pub coverage: CoveragePolicy,
+    /// Knowledge base storage settings
+    #[serde(default)]
+    pub knowledge_base: KnowledgeBaseConfig,
+    /// Search API settings used by `xze serve`
+    #[serde(default)]
+    pub search: SearchConfig,
 }
 
 impl Default for XzeConfig {
     fn default() -> Self {
         Self {
+            include: Vec::new(),
             version: "1.0".to_string(),
             documentation_repo: DocumentationRepoConfig::default(),
             repositories: Vec::new(),
@@ -47,6 +83,10 @@ impl Default for XzeConfig {
             logging: LoggingConfig::default(),
             filesystem: FileSystemConfig::default(),
             git: GitConfig::default(),
+            output: OutputConfig::default(),
+            coverage: CoveragePolicy::default(),
+            knowledge_base: KnowledgeBaseConfig::default(),
+            search: SearchConfig::default(),
         }
     }
 }
@@ -93,8 +133,14 @@ pub struct RepositoryConfig {
     /// Custom configuration for this repository
     #[serde(default)]
     pub custom: HashMap<String, String>,
+    /// Group for listing and summarizing repositories, such as a team
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Free-form labels, such as a domain or criticality
+    #[serde(default)]
+    pub tags: Vec<String>,
     /// Whether to enable auto-documentation updates
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", deserialize_with = "deserialize_scalar")]
     pub auto_update: bool,
     /// Directories to exclude from analysis
     #[serde(default)]
@@ -110,6 +156,21 @@ impl RepositoryConfig {
         RepositoryId::from(self.name.as_str())
     }
 
+    /// Group, trimmed, lowercased and checked
+    ///
+    /// See [`crate::repository::labels`] for the label syntax.
+    pub fn normalized_group(&self) -> Result<Option<String>> {
+        self.group
+            .as_deref()
+            .map(|group| crate::repository::labels::normalize_label(group, "group"))
+            .transpose()
+    }
+
+    /// Tags, normalized like the group and without repeats
+    pub fn normalized_tags(&self) -> Result<Vec<String>> {
+        crate::repository::labels::normalize_tags(&self.tags)
+    }
+
     /// Check if a path should be excluded
     pub fn should_exclude_path(&self, path: &str) -> bool {
         // Check exclude directories
@@ -168,14 +229,30 @@ pub struct OllamaConfig {
     /// Model configuration
     pub models: ModelConfig,
     /// Request timeout in seconds
-    #[serde(default = "default_timeout")]
+    #[serde(default = "default_timeout", deserialize_with = "deserialize_scalar")]
     pub timeout_seconds: u64,
+    /// Timeout in seconds for streamed generation, embeddings and model
+    /// pulls, which can run longer than a single generation
+    #[serde(
+        default = "default_stream_timeout",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub stream_timeout_seconds: u64,
     /// Maximum concurrent requests
-    #[serde(default = "default_max_concurrent")]
+    #[serde(
+        default = "default_max_concurrent",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub max_concurrent_requests: usize,
     /// Retry configuration
     #[serde(default)]
     pub retry: RetryConfig,
+    /// Global inference budget shared by all AI calls (unlimited when unset)
+    #[serde(default)]
+    pub budget: InferenceBudgetConfig,
+    /// HTTP connection pool settings
+    #[serde(default)]
+    pub pool: HttpPoolConfig,
 }
 
 impl Default for OllamaConfig {
@@ -184,8 +261,11 @@ impl Default for OllamaConfig {
             url: Url::parse("http://localhost:11434").unwrap(),
             models: ModelConfig::default(),
             timeout_seconds: default_timeout(),
+            stream_timeout_seconds: default_stream_timeout(),
             max_concurrent_requests: default_max_concurrent(),
             retry: RetryConfig::default(),
+            budget: InferenceBudgetConfig::default(),
+            pool: HttpPoolConfig::default(),
         }
     }
 }
@@ -199,11 +279,20 @@ pub struct ModelConfig {
     #[serde(default)]
     pub fallback: Vec<String>,
     /// Context window size
-    #[serde(default = "default_context_window")]
+    #[serde(
+        default = "default_context_window",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub context_window: usize,
     /// Generation temperature
-    #[serde(default = "default_temperature")]
+    #[serde(
+        default = "default_temperature",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub temperature: f32,
+    /// Pull the primary model before its first use if Ollama lacks it
+    #[serde(default, deserialize_with = "deserialize_scalar")]
+    pub auto_pull: bool,
 }
 
 impl Default for ModelConfig {
@@ -213,6 +302,7 @@ impl Default for ModelConfig {
             fallback: vec!["codellama".to_string()],
             context_window: default_context_window(),
             temperature: default_temperature(),
+            auto_pull: false,
         }
     }
 }
@@ -221,16 +311,25 @@ impl Default for ModelConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetryConfig {
     /// Maximum number of retries
-    #[serde(default = "default_max_retries")]
+    #[serde(
+        default = "default_max_retries",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub max_retries: usize,
     /// Initial retry delay in milliseconds
-    #[serde(default = "default_initial_delay")]
+    #[serde(
+        default = "default_initial_delay",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub initial_delay_ms: u64,
     /// Maximum retry delay in milliseconds
-    #[serde(default = "default_max_delay")]
+    #[serde(default = "default_max_delay", deserialize_with = "deserialize_scalar")]
     pub max_delay_ms: u64,
     /// Backoff multiplier
-    #[serde(default = "default_backoff_multiplier")]
+    #[serde(
+        default = "default_backoff_multiplier",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub backoff_multiplier: f64,
 }
 
@@ -249,17 +348,32 @@ impl Default for RetryConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationConfig {
     /// Temperature for generation
-    #[serde(default = "default_temperature")]
+    #[serde(
+        default = "default_temperature",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub temperature: f32,
     /// Maximum tokens to generate
-    #[serde(default = "default_max_tokens")]
+    #[serde(
+        default = "default_max_tokens",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub max_tokens: usize,
     /// Whether to enable streaming responses
-    #[serde(default = "default_false")]
+    #[serde(default = "default_false", deserialize_with = "deserialize_scalar")]
     pub streaming: bool,
     /// Custom prompt templates
     #[serde(default)]
     pub prompt_templates: HashMap<String, String>,
+    /// Secret scanning of generated documents
+    #[serde(default)]
+    pub secret_scan: SecretScanConfig,
+    /// Persona prepended to generation prompts
+    #[serde(default)]
+    pub persona: PersonaConfig,
+    /// How many documents each Diátaxis category produces
+    #[serde(default)]
+    pub granularity: DocumentGranularity,
 }
 
 impl Default for GenerationConfig {
@@ -269,6 +383,9 @@ impl Default for GenerationConfig {
             max_tokens: default_max_tokens(),
             streaming: false,
             prompt_templates: HashMap::new(),
+            secret_scan: SecretScanConfig::default(),
+            persona: PersonaConfig::default(),
+            granularity: DocumentGranularity::default(),
         }
     }
 }
@@ -277,7 +394,7 @@ impl Default for GenerationConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequestConfig {
     /// Automatically assign reviewers
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", deserialize_with = "deserialize_scalar")]
     pub auto_assign_reviewers: bool,
     /// Default reviewers to assign
     #[serde(default)]
@@ -294,6 +411,10 @@ pub struct PullRequestConfig {
     /// Auto-merge configuration
     #[serde(default)]
     pub auto_merge: AutoMergeConfig,
+    /// Hosting platform of the documentation repository; detected from its
+    /// URL when absent
+    #[serde(default)]
+    pub platform: Option<GitPlatform>,
 }
 
 impl Default for PullRequestConfig {
@@ -305,6 +426,7 @@ impl Default for PullRequestConfig {
             title_template: default_pr_title(),
             body_template: default_pr_body(),
             auto_merge: AutoMergeConfig::default(),
+            platform: None,
         }
     }
 }
@@ -313,16 +435,22 @@ impl Default for PullRequestConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutoMergeConfig {
     /// Enable auto-merge
-    #[serde(default = "default_false")]
+    #[serde(default = "default_false", deserialize_with = "deserialize_scalar")]
     pub enabled: bool,
     /// Require approvals before auto-merge
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", deserialize_with = "deserialize_scalar")]
     pub require_approvals: bool,
     /// Minimum number of approvals required
-    #[serde(default = "default_min_approvals")]
+    #[serde(
+        default = "default_min_approvals",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub min_approvals: usize,
     /// Wait time before auto-merge (in hours)
-    #[serde(default = "default_auto_merge_delay")]
+    #[serde(
+        default = "default_auto_merge_delay",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub delay_hours: u64,
 }
 
@@ -349,7 +477,7 @@ pub struct LoggingConfig {
     /// Log file path (optional)
     pub file: Option<PathBuf>,
     /// Whether to log to stdout
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", deserialize_with = "deserialize_scalar")]
     pub stdout: bool,
 }
 
@@ -372,10 +500,13 @@ pub struct FileSystemConfig {
     /// Temporary directory
     pub temp_dir: Option<PathBuf>,
     /// Maximum cache size in MB
-    #[serde(default = "default_cache_size")]
+    #[serde(
+        default = "default_cache_size",
+        deserialize_with = "deserialize_scalar"
+    )]
     pub max_cache_size_mb: usize,
     /// Cache TTL in hours
-    #[serde(default = "default_cache_ttl")]
+    #[serde(default = "default_cache_ttl", deserialize_with = "deserialize_scalar")]
     pub cache_ttl_hours: u64,
 }
 
@@ -401,8 +532,11 @@ pub struct GitConfig {
     #[serde(default = "default_commit_message")]
     pub commit_message_template: String,
     /// Whether to sign commits
-    #[serde(default = "default_false")]
+    #[serde(default = "default_false", deserialize_with = "deserialize_scalar")]
     pub sign_commits: bool,
+    /// Key used to sign commits; commits are unsigned when absent
+    #[serde(default)]
+    pub signing: Option<SigningConfig>,
 }
 
 impl Default for GitConfig {
@@ -412,23 +546,123 @@ impl Default for GitConfig {
             author_email: None,
             commit_message_template: default_commit_message(),
             sign_commits: false,
+            signing: None,
         }
     }
 }
 
+/// Knowledge base configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeBaseConfig {
+    /// Storage backend used by `xze load`
+    #[serde(default)]
+    pub backend: KbBackendKind,
+    /// Text normalization applied to indexed chunks and search queries
+    #[serde(default)]
+    pub normalization: NormalizerConfig,
+}
+
+/// Search API configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchConfig {
+    /// Largest page size a search request may ask for
+    #[serde(
+        default = "default_search_max_results",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub max_results: usize,
+    /// Largest offset a search request may ask for; deeper pages use
+    /// cursors
+    #[serde(
+        default = "default_search_max_offset",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub max_offset: usize,
+    /// Model reranking for requests that set `rerank_top_n`; such requests
+    /// are rejected when unset
+    #[serde(default)]
+    pub rerank: Option<RerankConfig>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_results: default_search_max_results(),
+            max_offset: default_search_max_offset(),
+            rerank: None,
+        }
+    }
+}
+
+/// Commit signing configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Signature format
+    pub format: SigningFormat,
+    /// GPG key ID, or path to the SSH private key
+    pub key: String,
+    /// Signing program; defaults to `gpg` or `ssh-keygen`
+    #[serde(default)]
+    pub program: Option<String>,
+}
+
+/// Format of commit signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningFormat {
+    /// OpenPGP signature made with `gpg`
+    Gpg,
+    /// SSH signature made with `ssh-keygen -Y sign`
+    Ssh,
+}
+
 impl XzeConfig {
     /// Load configuration from a file
+    ///
+    /// String values may reference environment variables as `${VAR}`, or
+    /// as `${VAR:-default}` to fall back to `default` when `VAR` is unset
+    /// or empty. `$${` writes a literal `${`. References are resolved in
+    /// every string of the file, including per-repository settings, so
+    /// secrets such as tokens can stay out of the file. Expanded values are
+    /// strings; numeric and boolean fields also accept strings, so
+    /// `timeout_seconds: ${OLLAMA_TIMEOUT}` works too.
+    ///
+    /// The `include` list names config files to merge beneath this one,
+    /// relative to this file. Included files may include others; later
+    /// entries override earlier ones, and the including file overrides
+    /// them all. Maps are merged key by key, and lists of named entries,
+    /// such as `repositories`, are merged entry by entry by `name`; other
+    /// values are replaced. Only the merged result needs to be a complete
+    /// config, so call [`validate`](Self::validate) on it rather than on
+    /// the individual files.
+    ///
+    /// # Errors
+    ///
+    /// Returns `XzeError::validation` naming the variable and the field
+    /// when a variable without a default is unset, or listing the files
+    /// when includes form a cycle, and a parse error when a file is
+    /// neither YAML nor JSON
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use xze_core::config::XzeConfig;
+    ///
+    /// // include:
+    /// //   - ../shared/base.yaml
+    /// // ollama:
+    /// //   url: ${OLLAMA_URL:-http://localhost:11434}
+    /// let config = XzeConfig::from_file("xze.yaml").unwrap();
+    /// config.validate().unwrap();
+    /// ```
     pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-
-        // Try YAML first, then JSON
-        match serde_yaml::from_str(&content) {
-            Ok(config) => Ok(config),
-            Err(_) => {
-                let config = serde_json::from_str(&content)?;
-                Ok(config)
-            }
-        }
+        Self::from_file_with_env(path.as_ref(), &|name| std::env::var(name).ok())
+    }
+
+    /// Load configuration from a file, resolving references with `lookup`
+    fn from_file_with_env(path: &Path, lookup: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        let value = load_config_value(path, &mut Vec::new(), lookup)?;
+        Ok(serde_yaml::from_value(value)?)
     }
 
     /// Save configuration to a file
@@ -459,6 +693,8 @@ impl XzeConfig {
                     repo.name
                 )));
             }
+            repo.normalized_group()?;
+            repo.normalized_tags()?;
         }
 
         // Validate Ollama URL
@@ -473,10 +709,267 @@ impl XzeConfig {
             return Err(crate::XzeError::validation("Primary model cannot be empty"));
         }
 
+        // Validate secret patterns
+        crate::documentation::SecretScanner::new(&self.generation.secret_scan)?;
+
+        // Validate persona prompts
+        self.generation.persona.validate()?;
+
+        // Validate output sink
+        if self.output.sink == OutputSinkKind::S3 && self.output.s3.is_none() {
+            return Err(crate::XzeError::validation(
+                "The s3 output sink requires an `output.s3` section",
+            ));
+        }
+
+        // Validate inference budget
+        if self.ollama.budget.tokens_per_minute == Some(0)
+            || self.ollama.budget.requests_per_minute == Some(0)
+        {
+            return Err(crate::XzeError::validation(
+                "Inference budget limits must be greater than zero",
+            ));
+        }
+
         Ok(())
     }
 }
 
+/// Read the config file at `path`, merged over the files it includes
+///
+/// `chain` holds the files currently being loaded, to detect cycles.
+fn load_config_value(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<serde_yaml::Value> {
+    let canonical = path.canonicalize().map_err(|e| {
+        XzeError::filesystem(format!("Failed to read config {}: {}", path.display(), e))
+    })?;
+    if let Some(start) = chain.iter().position(|p| *p == canonical) {
+        let cycle: Vec<String> = chain[start..]
+            .iter()
+            .chain([&canonical])
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(XzeError::validation(format!(
+            "Config include cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+
+    // Try YAML first, then JSON
+    let mut value: serde_yaml::Value = match serde_yaml::from_str(&content) {
+        Ok(value) => value,
+        Err(_) => {
+            let json: serde_json::Value = serde_json::from_str(&content)?;
+            serde_yaml::to_value(json)?
+        }
+    };
+    substitute_env(&mut value, "", lookup)?;
+
+    let include = match value.as_mapping_mut() {
+        Some(mapping) => mapping.remove(INCLUDE_KEY),
+        None => None,
+    };
+    let Some(include) = include else {
+        return Ok(value);
+    };
+    let includes: Vec<PathBuf> = serde_yaml::from_value(include.clone()).map_err(|e| {
+        XzeError::validation(format!(
+            "Invalid include list in config {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut merged = serde_yaml::Value::Null;
+    chain.push(canonical);
+    for included in &includes {
+        let included = load_config_value(&dir.join(included), chain, lookup)?;
+        merge_config_values(&mut merged, included);
+    }
+    chain.pop();
+    merge_config_values(&mut merged, value);
+
+    // Keep the top-level directive so the config round-trips through
+    // `to_file`
+    if chain.is_empty() {
+        if let Some(mapping) = merged.as_mapping_mut() {
+            mapping.insert(INCLUDE_KEY.into(), include);
+        }
+    }
+    Ok(merged)
+}
+
+/// Merge `overlay` over `base`
+///
+/// Maps merge key by key and lists of named maps entry by entry; anything
+/// else in `overlay` replaces `base`.
+fn merge_config_values(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    use serde_yaml::Value;
+
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_config_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Sequence(base), Value::Sequence(overlay))
+            if !overlay.is_empty() && all_named(base) && all_named(&overlay) =>
+        {
+            for item in overlay {
+                match base
+                    .iter_mut()
+                    .find(|existing| existing["name"] == item["name"])
+                {
+                    Some(existing) => merge_config_values(existing, item),
+                    None => base.push(item),
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Whether every item is a map with a string `name`
+fn all_named(items: &[serde_yaml::Value]) -> bool {
+    items.iter().all(|item| item["name"].is_string())
+}
+
+/// Resolve `${VAR}` references in every string of `value`
+///
+/// `field` is the path of `value` within the file, used in errors.
+fn substitute_env(
+    value: &mut serde_yaml::Value,
+    field: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match value {
+        serde_yaml::Value::String(text) if text.contains("${") => {
+            *text = expand_env(text, field, lookup)?;
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                substitute_env(item, &format!("{}[{}]", field, index), lookup)?;
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, item) in mapping.iter_mut() {
+                let key = match key {
+                    serde_yaml::Value::String(key) => key.clone(),
+                    other => serde_yaml::to_string(other)?.trim().to_string(),
+                };
+                let path = if field.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", field, key)
+                };
+                substitute_env(item, &path, lookup)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => substitute_env(&mut tagged.value, field, lookup)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Expand the `${VAR}` and `${VAR:-default}` references in `text`
+fn expand_env(text: &str, field: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            expanded.push_str(&rest[..start - 1]);
+            expanded.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        expanded.push_str(&rest[..start]);
+
+        let reference = &rest[start + 2..];
+        let end = reference.find('}').ok_or_else(|| {
+            XzeError::validation(format!("Unterminated '${{' in config field '{}'", field))
+        })?;
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+        if name.is_empty() {
+            return Err(XzeError::validation(format!(
+                "Empty environment variable reference in config field '{}'",
+                field
+            )));
+        }
+
+        match (lookup(name).filter(|v| !v.is_empty()), default) {
+            (Some(value), _) => expanded.push_str(&value),
+            (None, Some(default)) => expanded.push_str(default),
+            (None, None) => {
+                return Err(XzeError::validation(format!(
+                    "Environment variable '{}' is not set (referenced by config field '{}')",
+                    name, field
+                )));
+            }
+        }
+        rest = &reference[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Deserialize a number or boolean that may also be written as a string
+///
+/// `${VAR}` references always expand to strings, so numeric and boolean
+/// config fields use this to accept `timeout_seconds: ${OLLAMA_TIMEOUT}`
+/// as well as `timeout_seconds: 300`.
+pub(crate) fn deserialize_scalar<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scalar<T> {
+        Value(T),
+        Text(String),
+    }
+
+    match Scalar::<T>::deserialize(deserializer)? {
+        Scalar::Value(value) => Ok(value),
+        Scalar::Text(text) => text
+            .trim()
+            .parse()
+            .map_err(|e| serde::de::Error::custom(format!("invalid value '{}': {}", text, e))),
+    }
+}
+
+/// [`deserialize_scalar`] for optional fields
+pub(crate) fn deserialize_optional_scalar<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(bound = "T: Deserialize<'de> + std::str::FromStr, T::Err: std::fmt::Display")]
+    struct Wrapper<T>(#[serde(deserialize_with = "deserialize_scalar")] T);
+
+    let value: Option<Wrapper<T>> = Option::deserialize(deserializer)?;
+    Ok(value.map(|Wrapper(value)| value))
+}
+
 // Default value functions
 fn default_true() -> bool {
     true
@@ -487,6 +980,9 @@ fn default_false() -> bool {
 fn default_timeout() -> u64 {
     300
 }
+fn default_stream_timeout() -> u64 {
+    900
+}
 fn default_max_concurrent() -> usize {
     4
 }
@@ -529,6 +1025,12 @@ fn default_cache_size() -> usize {
 fn default_cache_ttl() -> u64 {
     24
 }
+fn default_search_max_results() -> usize {
+    100
+}
+fn default_search_max_offset() -> usize {
+    10_000
+}
 
 fn default_pr_title() -> String {
     "docs: Update documentation for {{repository_name}}".to_string()
@@ -567,6 +1069,29 @@ mod tests {
         assert!(config.pr.auto_assign_reviewers);
     }
 
+    #[test]
+    fn test_search_limits() {
+        let search: SearchConfig = serde_yaml::from_str("max_results: 50\n").unwrap();
+        assert_eq!(search.max_results, 50);
+        assert_eq!(search.max_offset, 10_000);
+        assert_eq!(XzeConfig::default().search.max_results, 100);
+        assert!(search.rerank.is_none());
+
+        let search: SearchConfig =
+            serde_yaml::from_str("rerank:\n  model: llama3\n  max_top_n: 20\n").unwrap();
+        assert_eq!(search.rerank.unwrap().max_top_n, 20);
+    }
+
+    #[test]
+    fn test_knowledge_base_backend() {
+        let kb: KnowledgeBaseConfig = serde_yaml::from_str("backend: memory\n").unwrap();
+        assert_eq!(kb.backend, KbBackendKind::Memory);
+        assert_eq!(
+            XzeConfig::default().knowledge_base.backend,
+            KbBackendKind::Postgres
+        );
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = XzeConfig::default();
@@ -594,12 +1119,33 @@ mod tests {
             local_path: None,
             credentials: None,
             custom: HashMap::new(),
+            group: None,
+            tags: vec![],
             auto_update: true,
             exclude_dirs: Vec::new(),
             exclude_patterns: Vec::new(),
         });
 
         assert!(config.validate().is_ok());
+
+        // Tags and groups must be valid labels
+        config.repositories[0].group = Some("Payments".to_string());
+        config.repositories[0].tags = vec!["critical".to_string(), "Critical".to_string()];
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.repositories[0]
+                .normalized_group()
+                .unwrap()
+                .as_deref(),
+            Some("payments")
+        );
+        assert_eq!(
+            config.repositories[0].normalized_tags().unwrap(),
+            vec!["critical"]
+        );
+
+        config.repositories[0].tags.push("not valid".to_string());
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -612,6 +1158,8 @@ mod tests {
             local_path: None,
             credentials: None,
             custom: HashMap::new(),
+            group: None,
+            tags: vec![],
             auto_update: true,
             exclude_dirs: vec!["target".to_string(), "node_modules".to_string()],
             exclude_patterns: vec!["*.log".to_string(), "temp_*".to_string()],
@@ -624,6 +1172,19 @@ mod tests {
         assert!(!repo.should_exclude_path("src/main.rs"));
     }
 
+    #[test]
+    fn test_git_signing_config() {
+        let git: GitConfig = serde_yaml::from_str("author_name: Bot\n").unwrap();
+        assert!(git.signing.is_none());
+
+        let git: GitConfig =
+            serde_yaml::from_str("signing:\n  format: ssh\n  key: ~/.ssh/id_ed25519\n").unwrap();
+        let signing = git.signing.unwrap();
+        assert_eq!(signing.format, SigningFormat::Ssh);
+        assert_eq!(signing.key, "~/.ssh/id_ed25519");
+        assert!(signing.program.is_none());
+    }
+
     #[test]
     fn test_file_io() {
         let config = XzeConfig::default();
@@ -637,6 +1198,200 @@ mod tests {
         assert_eq!(config.version, loaded_config.version);
     }
 
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "XZE_TEST_HOST" => Some("ollama.internal".to_string()),
+            "XZE_TEST_TOKEN" => Some("s3cret".to_string()),
+            "XZE_TEST_EMPTY" => Some(String::new()),
+            "XZE_TEST_TIMEOUT" => Some("120".to_string()),
+            "XZE_TEST_FLAG" => Some("true".to_string()),
+            "XZE_TEST_BRANCH" => Some("2024".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_env() {
+        let expand = |text: &str| expand_env(text, "field", &lookup);
+        assert_eq!(
+            expand("http://${XZE_TEST_HOST}:11434").unwrap(),
+            "http://ollama.internal:11434"
+        );
+        assert_eq!(expand("${XZE_TEST_UNSET:-fallback}").unwrap(), "fallback");
+        assert_eq!(expand("${XZE_TEST_EMPTY:-fallback}").unwrap(), "fallback");
+        assert_eq!(expand("${XZE_TEST_TOKEN:-unused}").unwrap(), "s3cret");
+        assert_eq!(expand("${XZE_TEST_UNSET:-}").unwrap(), "");
+        assert_eq!(expand("$${XZE_TEST_HOST}").unwrap(), "${XZE_TEST_HOST}");
+        assert_eq!(expand("no references").unwrap(), "no references");
+        assert!(expand("${XZE_TEST_HOST").is_err());
+        assert!(expand("${}").is_err());
+    }
+
+    #[test]
+    fn test_substitute_env_reports_missing_variable_and_field() {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(
+            "repositories:\n  - name: api\n    custom:\n      token: ${XZE_TEST_MISSING}\n",
+        )
+        .unwrap();
+        let err = substitute_env(&mut value, "", &lookup).unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }), "{:?}", err);
+        let message = err.to_string();
+        assert!(message.contains("'XZE_TEST_MISSING'"), "{}", message);
+        assert!(
+            message.contains("'repositories[0].custom.token'"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_from_file_substitutes_env() {
+        let mut config = XzeConfig::default();
+        config.repositories.push(RepositoryConfig {
+            name: "api".to_string(),
+            url: Url::parse("https://github.com/test/api").unwrap(),
+            language: None,
+            watch_branches: vec!["${XZE_CONFIG_TEST_BRANCH:-main}".to_string()],
+            local_path: None,
+            credentials: None,
+            custom: HashMap::from([("token".to_string(), "${XZE_TEST_TOKEN}".to_string())]),
+            group: None,
+            tags: vec![],
+            auto_update: true,
+            exclude_dirs: Vec::new(),
+            exclude_patterns: Vec::new(),
+        });
+        let yaml = serde_yaml::to_string(&config)
+            .unwrap()
+            .replace("http://localhost:11434", "http://${XZE_TEST_HOST}:11434")
+            .replace(
+                "timeout_seconds: 300",
+                "timeout_seconds: ${XZE_TEST_TIMEOUT}",
+            );
+        assert!(yaml.contains("${XZE_TEST_HOST}"));
+        assert!(yaml.contains("${XZE_TEST_TIMEOUT}"));
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), yaml).unwrap();
+
+        let loaded = XzeConfig::from_file_with_env(temp_file.path(), &lookup).unwrap();
+        assert_eq!(loaded.ollama.url.as_str(), "http://ollama.internal:11434/");
+        assert_eq!(loaded.ollama.timeout_seconds, 120);
+        assert_eq!(loaded.repositories[0].watch_branches, vec!["main"]);
+        assert_eq!(loaded.repositories[0].custom["token"], "s3cret");
+    }
+
+    #[test]
+    fn test_from_file_reads_expanded_values_by_field_type() {
+        let yaml = serde_yaml::to_string(&XzeConfig::default()).unwrap();
+        let yaml = yaml
+            .replace("branch: main", "branch: ${XZE_TEST_BRANCH}")
+            .replace("auto_pull: false", "auto_pull: ${XZE_TEST_FLAG}")
+            .replace(
+                "tokens_per_minute: null",
+                "tokens_per_minute: ${XZE_TEST_TIMEOUT}",
+            );
+        assert!(yaml.contains("${XZE_TEST_BRANCH}"));
+        assert!(yaml.contains("${XZE_TEST_FLAG}"));
+        assert!(yaml.contains("${XZE_TEST_TIMEOUT}"));
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), &yaml).unwrap();
+
+        let loaded = XzeConfig::from_file_with_env(temp_file.path(), &lookup).unwrap();
+        assert_eq!(loaded.documentation_repo.branch, "2024");
+        assert!(loaded.ollama.models.auto_pull);
+        assert_eq!(loaded.ollama.budget.tokens_per_minute, Some(120));
+
+        let invalid = yaml.replace("timeout_seconds: 300", "timeout_seconds: ${XZE_TEST_HOST}");
+        std::fs::write(temp_file.path(), invalid).unwrap();
+        let err = XzeConfig::from_file_with_env(temp_file.path(), &lookup).unwrap_err();
+        assert!(err.to_string().contains("ollama.internal"), "{}", err);
+    }
+
+    #[test]
+    fn test_from_file_merges_include_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("shared")).unwrap();
+
+        let mut base = XzeConfig::default();
+        base.repositories.push(RepositoryConfig {
+            name: "api".to_string(),
+            url: Url::parse("https://github.com/test/api").unwrap(),
+            language: Some("rust".to_string()),
+            watch_branches: vec!["main".to_string()],
+            local_path: None,
+            credentials: None,
+            custom: HashMap::new(),
+            group: None,
+            tags: vec![],
+            auto_update: true,
+            exclude_dirs: Vec::new(),
+            exclude_patterns: Vec::new(),
+        });
+        base.to_file(dir.path().join("shared/base.yaml")).unwrap();
+        std::fs::write(
+            dir.path().join("shared/team.yaml"),
+            concat!(
+                "include: [base.yaml]\n",
+                "ollama:\n  models:\n    primary: llama3\n",
+                "repositories:\n  - name: api\n    auto_update: false\n",
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("xze.yaml"),
+            concat!(
+                "include: [shared/team.yaml]\n",
+                "ollama:\n  timeout_seconds: 60\n",
+                "repositories:\n",
+                "  - name: web\n",
+                "    url: https://github.com/test/web\n",
+                "    language: null\n",
+                "    watch_branches: [develop]\n",
+                "    local_path: null\n",
+                "    credentials: null\n",
+            ),
+        )
+        .unwrap();
+
+        let config = XzeConfig::from_file(dir.path().join("xze.yaml")).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.include, vec![PathBuf::from("shared/team.yaml")]);
+
+        // Maps merge deeply across all three files
+        assert_eq!(config.ollama.url.as_str(), "http://localhost:11434/");
+        assert_eq!(config.ollama.models.primary, "llama3");
+        assert_eq!(config.ollama.models.fallback, vec!["codellama"]);
+        assert_eq!(config.ollama.timeout_seconds, 60);
+
+        // Repositories merge by name
+        let names: Vec<&str> = config
+            .repositories
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["api", "web"]);
+        let api = config.get_repository("api").unwrap();
+        assert!(!api.auto_update);
+        assert_eq!(api.watch_branches, vec!["main"]);
+        assert_eq!(api.language.as_deref(), Some("rust"));
+    }
+
+    #[test]
+    fn test_from_file_rejects_include_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "include: [b.yaml]\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "include: [./a.yaml]\n").unwrap();
+
+        let err = XzeConfig::from_file(dir.path().join("a.yaml")).unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }), "{:?}", err);
+        let message = err.to_string();
+        assert!(message.contains("include cycle"), "{}", message);
+        assert!(message.contains("a.yaml -> "), "{}", message);
+
+        std::fs::write(dir.path().join("self.yaml"), "include: [self.yaml]\n").unwrap();
+        assert!(XzeConfig::from_file(dir.path().join("self.yaml")).is_err());
+    }
+
     #[test]
     fn test_repository_lookup() {
         let mut config = XzeConfig::default();
@@ -648,6 +1403,8 @@ mod tests {
             local_path: None,
             credentials: None,
             custom: HashMap::new(),
+            group: None,
+            tags: vec![],
             auto_update: true,
             exclude_dirs: Vec::new(),
             exclude_patterns: Vec::new(),
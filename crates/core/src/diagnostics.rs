@@ -0,0 +1,253 @@
+//! Diagnostic bundle for support requests
+//!
+//! Collects what is needed to triage a problem report: the basic health
+//! checks, versions of the external components XZe talks to, the cargo
+//! features compiled in and the AI backend in use. Backs `xze health`.
+//! Probes never fail the collection; an unreachable component is recorded
+//! with the error instead of a version.
+
+use crate::ai::OllamaClient;
+use crate::config::XzeConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Time allowed for each version probe
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Version of one external component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentVersion {
+    /// Component name, such as `libgit2` or `ollama`
+    pub name: String,
+    /// Reported version, if the component could be reached
+    pub version: Option<String>,
+    /// Extra context, such as the URL probed or how it was built
+    pub detail: Option<String>,
+    /// Why the version could not be determined
+    pub error: Option<String>,
+}
+
+impl ComponentVersion {
+    /// A component whose version is known
+    pub fn found(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: Some(version.into()),
+            detail: None,
+            error: None,
+        }
+    }
+
+    /// A component whose version could not be determined
+    pub fn unavailable(name: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            detail: None,
+            error: Some(error.into()),
+        }
+    }
+
+    /// Attach extra context
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Diagnostic bundle reported by `xze health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// XZe version
+    pub xze_version: String,
+    /// Whether the basic health checks passed
+    pub healthy: bool,
+    /// Why the basic health checks failed
+    pub health_error: Option<String>,
+    /// AI backend in use
+    pub ai_backend: String,
+    /// Endpoint of the AI backend
+    pub ai_endpoint: String,
+    /// Versions of external components
+    pub components: Vec<ComponentVersion>,
+    /// Cargo features compiled in, keyed by crate
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+impl Diagnostics {
+    /// Record the cargo features compiled into another crate
+    pub fn with_features(mut self, krate: impl Into<String>, features: Vec<&'static str>) -> Self {
+        self.features.insert(
+            krate.into(),
+            features.into_iter().map(str::to_string).collect(),
+        );
+        self
+    }
+}
+
+/// Cargo features compiled into `xze-core`
+///
+/// # Examples
+///
+/// ```
+/// let features = xze_core::diagnostics::compiled_features();
+/// assert_eq!(features.contains(&"ollama"), cfg!(feature = "ollama"));
+/// ```
+pub fn compiled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "ollama") {
+        features.push("ollama");
+    }
+    if cfg!(feature = "openai") {
+        features.push("openai");
+    }
+    if cfg!(feature = "metrics") {
+        features.push("metrics");
+    }
+    if cfg!(feature = "s3") {
+        features.push("s3");
+    }
+    if cfg!(feature = "simd") {
+        features.push("simd");
+    }
+    features
+}
+
+/// Version of the libgit2 library linked through `git2`
+pub fn libgit2_version() -> ComponentVersion {
+    let version = git2::Version::get();
+    let (major, minor, patch) = version.libgit2_version();
+    let mut detail = vec![if version.vendored() {
+        "vendored"
+    } else {
+        "system"
+    }];
+    if version.https() {
+        detail.push("https");
+    }
+    if version.ssh() {
+        detail.push("ssh");
+    }
+
+    ComponentVersion::found("libgit2", format!("{}.{}.{}", major, minor, patch)).with_detail(
+        format!("git2 {} ({})", version.crate_version(), detail.join(", ")),
+    )
+}
+
+/// Version reported by the Ollama server's `/api/version`
+pub async fn ollama_version(url: &str) -> ComponentVersion {
+    let client = OllamaClient::with_timeout(url.trim_end_matches('/').to_string(), PROBE_TIMEOUT);
+
+    match client.version().await {
+        Ok(version) => ComponentVersion::found("ollama", version),
+        Err(e) => ComponentVersion::unavailable("ollama", e.to_string()),
+    }
+    .with_detail(url)
+}
+
+/// Version of the PostgreSQL server behind the knowledge base
+pub async fn postgres_version(database_url: &str) -> ComponentVersion {
+    let probe = async {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(PROBE_TIMEOUT)
+            .connect(database_url)
+            .await?;
+        let version: String = sqlx::query_scalar("SHOW server_version")
+            .fetch_one(&pool)
+            .await?;
+        pool.close().await;
+        Ok::<_, sqlx::Error>(version)
+    };
+
+    match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(version)) => ComponentVersion::found("postgresql", version),
+        Ok(Err(e)) => ComponentVersion::unavailable("postgresql", e.to_string()),
+        Err(_) => ComponentVersion::unavailable("postgresql", "Timed out"),
+    }
+}
+
+/// Collect the diagnostic bundle
+///
+/// # Arguments
+///
+/// * `config` - Configuration naming the AI backend to probe
+/// * `database_url` - Knowledge base database to probe, if any
+pub async fn collect(config: &XzeConfig, database_url: Option<&str>) -> Diagnostics {
+    let health = crate::health_check();
+    let ollama_url = config.ollama.url.as_str().trim_end_matches('/').to_string();
+
+    let mut components = vec![libgit2_version(), ollama_version(&ollama_url).await];
+    if let Some(url) = database_url {
+        components.push(postgres_version(url).await);
+    }
+
+    Diagnostics {
+        xze_version: crate::VERSION.to_string(),
+        healthy: health.is_ok(),
+        health_error: health.err(),
+        ai_backend: "ollama".to_string(),
+        ai_endpoint: ollama_url,
+        components,
+        features: BTreeMap::new(),
+    }
+    .with_features("xze-core", compiled_features())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    #[test]
+    fn test_libgit2_version() {
+        let component = libgit2_version();
+        assert_eq!(component.name, "libgit2");
+        assert!(component.version.unwrap().split('.').count() == 3);
+        assert!(component.detail.unwrap().contains("git2 "));
+    }
+
+    #[tokio::test]
+    async fn test_collect_reports_ollama_version() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/version")
+            .with_body(r#"{"version":"0.3.12"}"#)
+            .create_async()
+            .await;
+
+        let mut config = XzeConfig::default();
+        config.ollama.url = Url::parse(&server.url()).unwrap();
+        let diagnostics = collect(&config, None).await;
+
+        assert_eq!(diagnostics.ai_backend, "ollama");
+        let ollama = diagnostics
+            .components
+            .iter()
+            .find(|c| c.name == "ollama")
+            .unwrap();
+        assert_eq!(ollama.version.as_deref(), Some("0.3.12"));
+        assert!(!diagnostics
+            .components
+            .iter()
+            .any(|c| c.name == "postgresql"));
+        assert_eq!(
+            diagnostics.features["xze-core"].len(),
+            compiled_features().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_components_record_errors() {
+        // Nothing listens on port 1, so the connections are refused
+        let ollama = ollama_version("http://127.0.0.1:1").await;
+        assert!(ollama.version.is_none());
+        assert!(ollama.error.is_some());
+        assert_eq!(ollama.detail.as_deref(), Some("http://127.0.0.1:1"));
+
+        let postgres = postgres_version("postgres://127.0.0.1:1/xze").await;
+        assert_eq!(postgres.name, "postgresql");
+        assert!(postgres.error.is_some());
+    }
+}
@@ -0,0 +1,314 @@
+//! Confidence badge for generated documents
+//!
+//! Generated documents are scored for confidence when they are produced
+//! (see [`ConfidenceScore`]). [`ConfidenceBadgeConfig`] makes that score
+//! visible to readers, either as a footer at the end of the document or as
+//! a `confidence` field in its front-matter. Documents scoring below the
+//! threshold also carry a review note.
+//!
+//! The badge text is a template with these placeholders:
+//!
+//! | Placeholder   | Value                                              |
+//! | ------------- | -------------------------------------------------- |
+//! | `{score}`     | Overall confidence as a whole percentage           |
+//! | `{level}`     | Confidence level, such as `Medium`                 |
+//! | `{threshold}` | Review threshold as a whole percentage             |
+//! | `{review}`    | The review note below the threshold, otherwise empty |
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::ai::ConfidenceScore;
+//! use xze_core::documentation::badge::ConfidenceBadgeConfig;
+//!
+//! let score = ConfidenceScore {
+//!     overall: 0.55,
+//!     length: 0.0,
+//!     structure: 0.0,
+//!     completeness: 0.0,
+//!     code_quality: 0.0,
+//!     clarity: 0.0,
+//!     factors: vec![],
+//! };
+//!
+//! let badge = ConfidenceBadgeConfig::default().render(&score).unwrap();
+//! assert_eq!(badge, "> **Confidence:** 55% (Low) - review recommended");
+//! ```
+
+use crate::{
+    ai::ConfidenceScore,
+    documentation::{front_matter::FrontMatterConfig, generator::Document},
+    error::{Result, XzeError},
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Front-matter key holding the badge
+pub const FRONT_MATTER_KEY: &str = "confidence";
+
+const PLACEHOLDERS: &[&str] = &["score", "level", "threshold", "review"];
+
+/// Where the badge is placed in a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgePlacement {
+    /// A line at the end of the document
+    #[default]
+    Footer,
+    /// A `confidence` field in the front-matter block
+    FrontMatter,
+}
+
+/// Reader-visible confidence badge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceBadgeConfig {
+    /// Where the badge goes
+    #[serde(default)]
+    pub placement: BadgePlacement,
+    /// Scores below this (0.0-1.0) get the review note
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    /// Badge text; defaults depend on the placement
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Text substituted for `{review}` below the threshold
+    #[serde(default = "default_review_note")]
+    pub review_note: String,
+}
+
+fn default_threshold() -> f32 {
+    0.7
+}
+
+fn default_review_note() -> String {
+    " - review recommended".to_string()
+}
+
+impl Default for ConfidenceBadgeConfig {
+    fn default() -> Self {
+        Self {
+            placement: BadgePlacement::default(),
+            threshold: default_threshold(),
+            template: None,
+            review_note: default_review_note(),
+        }
+    }
+}
+
+impl ConfidenceBadgeConfig {
+    /// Template in use, falling back to the default for the placement
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or(match self.placement {
+            BadgePlacement::Footer => "> **Confidence:** {score}% ({level}){review}",
+            BadgePlacement::FrontMatter => "{score}% ({level}){review}",
+        })
+    }
+
+    /// Check the threshold range and the template placeholders
+    ///
+    /// The review note may not contain braces, because a front-matter badge
+    /// would read them as placeholders.
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(XzeError::validation(format!(
+                "Confidence badge threshold must be between 0.0 and 1.0, found {}",
+                self.threshold
+            )));
+        }
+        for name in self
+            .template()
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        {
+            if !PLACEHOLDERS.contains(&name) {
+                return Err(XzeError::validation(format!(
+                    "Unknown confidence badge placeholder {{{}}}",
+                    name
+                )));
+            }
+        }
+        if self.review_note.contains(['{', '}']) {
+            return Err(XzeError::validation(
+                "Confidence badge review note cannot contain braces",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Render the badge text for `score`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the configuration is invalid
+    pub fn render(&self, score: &ConfidenceScore) -> Result<String> {
+        self.validate()?;
+
+        let review = if score.meets_threshold(self.threshold) {
+            ""
+        } else {
+            self.review_note.as_str()
+        };
+        Ok(self
+            .template()
+            .replace("{score}", &percent(score.overall))
+            .replace("{level}", &score.level().to_string())
+            .replace("{threshold}", &percent(self.threshold))
+            .replace("{review}", review))
+    }
+
+    /// Add the badge to `document` and then its front-matter
+    ///
+    /// A footer badge is appended to the content before the front-matter is
+    /// prepended; a front-matter badge is added as the `confidence` field,
+    /// which emits a front-matter block even when `front_matter` is
+    /// otherwise disabled.
+    pub fn apply(
+        &self,
+        document: &mut Document,
+        score: &ConfidenceScore,
+        front_matter: &FrontMatterConfig,
+    ) -> Result<()> {
+        let badge = self.render(score)?;
+        let mut front_matter = Cow::Borrowed(front_matter);
+        match self.placement {
+            BadgePlacement::Footer => {
+                document.content = format!("{}\n\n{}\n", document.content.trim_end(), badge);
+            }
+            BadgePlacement::FrontMatter => {
+                front_matter
+                    .to_mut()
+                    .fields
+                    .insert(FRONT_MATTER_KEY.into(), badge.into());
+            }
+        }
+        front_matter.apply(document)
+    }
+}
+
+fn percent(value: f32) -> String {
+    format!("{:.0}", value * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documentation::front_matter::FrontMatterPreset;
+    use crate::types::DiátaxisCategory;
+    use std::path::PathBuf;
+
+    fn score(overall: f32) -> ConfidenceScore {
+        ConfidenceScore {
+            overall,
+            length: overall,
+            structure: overall,
+            completeness: overall,
+            code_quality: overall,
+            clarity: overall,
+            factors: vec![],
+        }
+    }
+
+    fn document() -> Document {
+        Document::new(
+            DiátaxisCategory::Reference,
+            "API Reference".to_string(),
+            "# API Reference\n\nBody\n".to_string(),
+            PathBuf::from("reference/api_reference.md"),
+        )
+    }
+
+    #[test]
+    fn test_review_note_only_below_threshold() {
+        let badge = ConfidenceBadgeConfig::default();
+        assert_eq!(
+            badge.render(&score(0.85)).unwrap(),
+            "> **Confidence:** 85% (High)"
+        );
+        assert_eq!(
+            badge.render(&score(0.7)).unwrap(),
+            "> **Confidence:** 70% (Medium)"
+        );
+        assert!(badge
+            .render(&score(0.69))
+            .unwrap()
+            .ends_with("review recommended"));
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let badge = ConfidenceBadgeConfig {
+            template: Some("Score {score}/100, review below {threshold}.{review}".to_string()),
+            threshold: 0.5,
+            review_note: " Please review.".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            badge.render(&score(0.42)).unwrap(),
+            "Score 42/100, review below 50. Please review."
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        for badge in [
+            ConfidenceBadgeConfig {
+                template: Some("{confidence}".to_string()),
+                ..Default::default()
+            },
+            ConfidenceBadgeConfig {
+                threshold: 70.0,
+                ..Default::default()
+            },
+            ConfidenceBadgeConfig {
+                review_note: "{x}".to_string(),
+                ..Default::default()
+            },
+        ] {
+            assert!(badge.render(&score(0.5)).is_err(), "{:?}", badge);
+        }
+    }
+
+    #[test]
+    fn test_footer_placement() {
+        let mut doc = document();
+        let front_matter = FrontMatterConfig {
+            preset: FrontMatterPreset::Docusaurus,
+            ..Default::default()
+        };
+        ConfidenceBadgeConfig::default()
+            .apply(&mut doc, &score(0.9), &front_matter)
+            .unwrap();
+
+        assert!(doc.content.starts_with("---\ntitle: API Reference\n"));
+        assert!(doc
+            .content
+            .ends_with("Body\n\n> **Confidence:** 90% (High)\n"));
+    }
+
+    #[test]
+    fn test_front_matter_placement() {
+        let mut doc = document();
+        let badge = ConfidenceBadgeConfig {
+            placement: BadgePlacement::FrontMatter,
+            ..Default::default()
+        };
+        badge
+            .apply(&mut doc, &score(0.5), &FrontMatterConfig::default())
+            .unwrap();
+
+        assert_eq!(
+            doc.content,
+            "---\nconfidence: 50% (Low) - review recommended\n---\n\n# API Reference\n\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_config_from_yaml() {
+        let badge: ConfidenceBadgeConfig =
+            serde_yaml::from_str("placement: front_matter\nthreshold: 0.8\n").unwrap();
+        assert_eq!(badge.placement, BadgePlacement::FrontMatter);
+        assert_eq!(badge.threshold, 0.8);
+        assert_eq!(badge.review_note, default_review_note());
+    }
+}
@@ -0,0 +1,510 @@
+//! Mermaid diagrams of the analyzed code structure
+//!
+//! Diagrams are built straight from a [`CodeStructure`], without a model
+//! call, so they are available offline and identical between runs:
+//!
+//! - a `classDiagram` of the types, with their fields and `impl` methods,
+//!   and an association wherever a field's type is another diagrammed type
+//! - a `graph` of the modules, with an edge from each module to the
+//!   modules declared inside it
+//!
+//! [`CodeStructure`] records no imports, so the module graph follows the
+//! module tree rather than `use` statements.
+//!
+//! Large repositories are cut down to [`DiagramConfig::max_nodes`] types
+//! and modules each, public items first, and private items can be left out
+//! entirely with [`DiagramConfig::collapse_private`]. Either way a Mermaid
+//! comment records how much was omitted.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use xze_core::documentation::diagram::{class_diagram, DiagramConfig};
+//! use xze_core::repository::{CodeStructure, TypeDefinition, TypeKind, Visibility};
+//!
+//! let mut structure = CodeStructure::new();
+//! structure.types.push(TypeDefinition {
+//!     name: "Parser".to_string(),
+//!     kind: TypeKind::Struct,
+//!     documentation: None,
+//!     fields: vec![],
+//!     visibility: Visibility::Public,
+//!     methods: vec![],
+//! });
+//!
+//! let diagram = class_diagram(&structure, &DiagramConfig::default()).unwrap();
+//! assert!(diagram.starts_with("classDiagram\n"));
+//! assert!(diagram.contains("class Parser"));
+//! ```
+
+use crate::repository::{CodeStructure, Function, Module, TypeKind, Visibility};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// File stems that stand for their directory's module
+const DIRECTORY_MODULES: &[&str] = &["mod", "lib", "main", "__init__", "index"];
+
+/// Architecture diagrams embedded in generated documents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagramConfig {
+    /// Whether reference and explanation documents get diagrams
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Most types, and most modules, drawn in one diagram
+    #[serde(default = "default_max_nodes")]
+    pub max_nodes: usize,
+    /// Leave private types, modules and methods out of the diagrams
+    #[serde(default = "default_collapse_private")]
+    pub collapse_private: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_nodes() -> usize {
+    40
+}
+
+fn default_collapse_private() -> bool {
+    true
+}
+
+impl Default for DiagramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_nodes: default_max_nodes(),
+            collapse_private: default_collapse_private(),
+        }
+    }
+}
+
+/// Document section holding both diagrams as fenced `mermaid` blocks
+///
+/// Returns `None` when diagrams are disabled or the structure has neither
+/// types nor modules to draw.
+pub fn architecture_section(structure: &CodeStructure, config: &DiagramConfig) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+
+    let mut section = String::new();
+    if let Some(diagram) = class_diagram(structure, config) {
+        section.push_str("\n### Types\n\n");
+        section.push_str(&fenced(&diagram));
+    }
+    if let Some(diagram) = module_graph(structure, config) {
+        section.push_str("\n### Modules\n\n");
+        section.push_str(&fenced(&diagram));
+    }
+    if section.is_empty() {
+        return None;
+    }
+    Some(format!("## Architecture\n{}", section))
+}
+
+/// Mermaid `classDiagram` of the types in `structure`
+///
+/// Returns `None` when there are no types left to draw.
+pub fn class_diagram(structure: &CodeStructure, config: &DiagramConfig) -> Option<String> {
+    let (types, omitted) = select(&structure.types, config, |t| t.visibility);
+    if types.is_empty() {
+        return None;
+    }
+
+    let mut ids: HashMap<&str, String> = HashMap::new();
+    let mut diagram = "classDiagram\n".to_string();
+    for ty in &types {
+        let id = identifier(&ty.name);
+        if ids.values().any(|taken| *taken == id) {
+            continue;
+        }
+        ids.insert(ty.name.as_str(), id.clone());
+
+        diagram.push_str(&format!("    class {} {{\n", id));
+        if let Some(annotation) = annotation(ty.kind) {
+            diagram.push_str(&format!("        <<{}>>\n", annotation));
+        }
+        for field in &ty.fields {
+            diagram.push_str(&format!(
+                "        +{} {}\n",
+                member_type(&field.type_annotation),
+                identifier(&field.name)
+            ));
+        }
+        let mut hidden = 0;
+        for method in &ty.methods {
+            if config.collapse_private && method.visibility == Visibility::Private {
+                hidden += 1;
+                continue;
+            }
+            diagram.push_str(&format!("        {}\n", method_member(method)));
+        }
+        diagram.push_str("    }\n");
+        if hidden > 0 {
+            diagram.push_str(&format!(
+                "    %% {} private methods of {} hidden\n",
+                hidden, id
+            ));
+        }
+    }
+
+    let mut associations = BTreeSet::new();
+    for ty in &types {
+        let Some(owner) = ids.get(ty.name.as_str()) else {
+            continue;
+        };
+        for field in &ty.fields {
+            for word in words(&field.type_annotation) {
+                match ids.get(word) {
+                    Some(target) if target != owner => {
+                        associations.insert((owner.clone(), target.clone()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    for (owner, target) in associations {
+        diagram.push_str(&format!("    {} --> {}\n", owner, target));
+    }
+
+    if omitted > 0 {
+        diagram.push_str(&format!("    %% {} more types omitted\n", omitted));
+    }
+    Some(diagram)
+}
+
+/// Mermaid `graph` of the modules in `structure`
+///
+/// Returns `None` when there are no modules left to draw.
+pub fn module_graph(structure: &CodeStructure, config: &DiagramConfig) -> Option<String> {
+    let (modules, omitted) = select(&structure.modules, config, |m| m.visibility);
+    if modules.is_empty() {
+        return None;
+    }
+
+    let mut diagram = "graph TD\n".to_string();
+    let mut dirs: HashMap<PathBuf, usize> = HashMap::new();
+    for (index, module) in modules.iter().enumerate() {
+        diagram.push_str(&format!(
+            "    m{}[\"{}\"]\n",
+            index,
+            module.name.replace('"', "#quot;")
+        ));
+        dirs.entry(module_dir(module)).or_insert(index);
+    }
+    for (index, module) in modules.iter().enumerate() {
+        let parent_dir = if is_directory_module(&module.path) {
+            module_dir(module).parent().map(Path::to_path_buf)
+        } else {
+            module.path.parent().map(Path::to_path_buf)
+        };
+        match parent_dir.and_then(|dir| dirs.get(&dir)) {
+            Some(&parent) if parent != index => {
+                diagram.push_str(&format!("    m{} --> m{}\n", parent, index));
+            }
+            _ => {}
+        }
+    }
+
+    if omitted > 0 {
+        diagram.push_str(&format!("    %% {} more modules omitted\n", omitted));
+    }
+    Some(diagram)
+}
+
+/// Items to draw, public first and capped at `max_nodes`, with the number
+/// left out
+fn select<'a, T>(
+    items: &'a [T],
+    config: &DiagramConfig,
+    visibility: impl Fn(&T) -> Visibility,
+) -> (Vec<&'a T>, usize) {
+    let mut selected: Vec<&T> = items
+        .iter()
+        .filter(|item| !(config.collapse_private && visibility(item) == Visibility::Private))
+        .collect();
+    selected.sort_by_key(|item| visibility(item) != Visibility::Public);
+    let omitted = items
+        .len()
+        .saturating_sub(config.max_nodes.min(selected.len()));
+    selected.truncate(config.max_nodes);
+    (selected, omitted)
+}
+
+/// Mermaid annotation for kinds other than plain structs and classes
+fn annotation(kind: TypeKind) -> Option<&'static str> {
+    match kind {
+        TypeKind::Enum => Some("enumeration"),
+        TypeKind::Trait => Some("trait"),
+        TypeKind::Interface => Some("interface"),
+        TypeKind::Struct | TypeKind::Class => None,
+    }
+}
+
+fn method_member(method: &Function) -> String {
+    let marker = match method.visibility {
+        Visibility::Public => '+',
+        Visibility::Private => '-',
+        Visibility::Protected => '#',
+    };
+    let parameters: Vec<String> = method
+        .parameters
+        .iter()
+        .map(|p| identifier(&p.name))
+        .collect();
+    let mut member = format!(
+        "{}{}({})",
+        marker,
+        identifier(&method.name),
+        parameters.join(", ")
+    );
+    if let Some(return_type) = &method.return_type {
+        member.push(' ');
+        member.push_str(&member_type(return_type));
+    }
+    member
+}
+
+/// A type annotation in Mermaid's syntax, which writes generics as `~T~`
+fn member_type(annotation: &str) -> String {
+    annotation
+        .chars()
+        .filter_map(|c| match c {
+            '<' | '>' => Some('~'),
+            '{' | '}' | '"' | '\n' => None,
+            ' ' => Some('_'),
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// A name usable as a Mermaid identifier
+fn identifier(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if id.is_empty() {
+        "_".to_string()
+    } else {
+        id
+    }
+}
+
+/// Identifiers in a type annotation
+fn words(annotation: &str) -> impl Iterator<Item = &str> {
+    annotation
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .collect::<HashSet<_>>()
+        .into_iter()
+}
+
+fn is_directory_module(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .is_some_and(|stem| DIRECTORY_MODULES.contains(&stem))
+}
+
+/// Directory holding the modules declared inside `module`
+fn module_dir(module: &Module) -> PathBuf {
+    if is_directory_module(&module.path) {
+        module
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    } else {
+        module.path.with_extension("")
+    }
+}
+
+fn fenced(diagram: &str) -> String {
+    format!("```mermaid\n{}```\n", diagram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Field, Parameter, TypeDefinition};
+
+    fn method(name: &str, visibility: Visibility) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: None,
+            parameters: vec![Parameter {
+                name: "input".to_string(),
+                type_annotation: "&str".to_string(),
+                default_value: None,
+            }],
+            return_type: Some("Result<Document>".to_string()),
+            visibility,
+            is_async: false,
+        }
+    }
+
+    fn module(name: &str, path: &str, visibility: Visibility) -> Module {
+        Module {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+            documentation: None,
+            visibility,
+        }
+    }
+
+    fn structure() -> CodeStructure {
+        let ty = |name: &str, kind, visibility, fields: Vec<Field>, methods| TypeDefinition {
+            name: name.to_string(),
+            kind,
+            documentation: None,
+            fields,
+            visibility,
+            methods,
+        };
+        let field = |name: &str, type_annotation: &str| Field {
+            name: name.to_string(),
+            type_annotation: type_annotation.to_string(),
+            documentation: None,
+        };
+        CodeStructure {
+            types: vec![
+                ty(
+                    "Parser",
+                    TypeKind::Struct,
+                    Visibility::Public,
+                    vec![
+                        field("config", "ParserConfig"),
+                        field("tokens", "Vec<Token>"),
+                    ],
+                    vec![
+                        method("parse", Visibility::Public),
+                        method("advance", Visibility::Private),
+                    ],
+                ),
+                ty(
+                    "ParserConfig",
+                    TypeKind::Struct,
+                    Visibility::Public,
+                    vec![field("strict", "bool")],
+                    vec![],
+                ),
+                ty("Token", TypeKind::Enum, Visibility::Public, vec![], vec![]),
+                ty(
+                    "Cursor",
+                    TypeKind::Struct,
+                    Visibility::Private,
+                    vec![],
+                    vec![],
+                ),
+            ],
+            modules: vec![
+                module("crate", "src/lib.rs", Visibility::Public),
+                module("parser", "src/parser/mod.rs", Visibility::Public),
+                module("lexer", "src/parser/lexer.rs", Visibility::Public),
+                module("util", "src/util.rs", Visibility::Private),
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// Whether every `class X {` body is closed before the next opens
+    fn balanced(diagram: &str) -> bool {
+        let mut open = false;
+        for line in diagram.lines() {
+            let line = line.trim();
+            if line.starts_with("class ") && line.ends_with('{') {
+                if open {
+                    return false;
+                }
+                open = true;
+            } else if line == "}" {
+                if !open {
+                    return false;
+                }
+                open = false;
+            }
+        }
+        !open
+    }
+
+    #[test]
+    fn test_class_diagram() {
+        let diagram = class_diagram(&structure(), &DiagramConfig::default()).unwrap();
+
+        assert!(diagram.starts_with("classDiagram\n"));
+        assert!(balanced(&diagram), "{}", diagram);
+        assert!(diagram.contains("    class Parser {\n"));
+        assert!(diagram.contains("    class ParserConfig {\n"));
+        assert!(diagram.contains("    class Token {\n        <<enumeration>>\n"));
+        assert!(diagram.contains("        +Vec~Token~ tokens\n"));
+        assert!(diagram.contains("        +parse(input) Result~Document~\n"));
+        assert!(diagram.contains("    Parser --> ParserConfig\n"));
+        assert!(diagram.contains("    Parser --> Token\n"));
+
+        // Private items are collapsed by default
+        assert!(!diagram.contains("Cursor"));
+        assert!(!diagram.contains("advance"));
+        assert!(diagram.contains("%% 1 private methods of Parser hidden"));
+        assert!(diagram.contains("%% 1 more types omitted"));
+    }
+
+    #[test]
+    fn test_class_diagram_includes_private_items() {
+        let config = DiagramConfig {
+            collapse_private: false,
+            ..Default::default()
+        };
+        let diagram = class_diagram(&structure(), &config).unwrap();
+        assert!(diagram.contains("    class Cursor {\n"));
+        assert!(diagram.contains("        -advance(input) Result~Document~\n"));
+        assert!(!diagram.contains("omitted"));
+    }
+
+    #[test]
+    fn test_class_diagram_caps_nodes() {
+        let config = DiagramConfig {
+            max_nodes: 2,
+            ..Default::default()
+        };
+        let diagram = class_diagram(&structure(), &config).unwrap();
+        assert_eq!(diagram.matches("    class ").count(), 2);
+        assert!(diagram.contains("%% 2 more types omitted"));
+        assert!(!diagram.contains("--> Token"));
+    }
+
+    #[test]
+    fn test_module_graph_follows_module_tree() {
+        let graph = module_graph(&structure(), &DiagramConfig::default()).unwrap();
+        assert!(graph.starts_with("graph TD\n"));
+        assert!(graph.contains("    m0[\"crate\"]\n"));
+        assert!(graph.contains("    m0 --> m1\n"));
+        assert!(graph.contains("    m1 --> m2\n"));
+        assert!(!graph.contains("util"));
+    }
+
+    #[test]
+    fn test_architecture_section() {
+        let section = architecture_section(&structure(), &DiagramConfig::default()).unwrap();
+        assert!(section.starts_with("## Architecture\n"));
+        assert_eq!(section.matches("```mermaid\n").count(), 2);
+
+        let disabled = DiagramConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(architecture_section(&structure(), &disabled).is_none());
+        assert!(architecture_section(&CodeStructure::new(), &DiagramConfig::default()).is_none());
+    }
+}
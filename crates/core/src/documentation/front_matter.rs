@@ -0,0 +1,399 @@
+//! YAML front-matter for generated documents
+//!
+//! Static site generators read page metadata from a YAML block at the top
+//! of each Markdown file. [`FrontMatterConfig`] describes that block as a
+//! preset ([`FrontMatterPreset::Hugo`] or [`FrontMatterPreset::Docusaurus`])
+//! plus custom fields, filled in from the [`Document`] and its metadata.
+//!
+//! String values may contain placeholders:
+//!
+//! | Placeholder      | Value                                             |
+//! | ---------------- | ------------------------------------------------- |
+//! | `{title}`        | Document title                                    |
+//! | `{category}`     | Diátaxis category, such as `how-to`               |
+//! | `{tags}`         | Document tags                                     |
+//! | `{weight}`       | Sort weight of the category (see [`category_weight`]) |
+//! | `{date}`         | Creation time, RFC 3339                           |
+//! | `{updated}`      | Last update time, RFC 3339                        |
+//! | `{author}`       | Document author                                   |
+//! | `{version}`      | Document version                                  |
+//! | `{custom.<key>}` | Custom metadata value, such as `{custom.repository}` |
+//!
+//! A value that is exactly one placeholder keeps its type, so `{tags}` is a
+//! YAML sequence and `{weight}` a number. Placeholders inside longer strings
+//! are substituted as text. Any other value is copied as given.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use xze_core::documentation::front_matter::{FrontMatterConfig, FrontMatterPreset};
+//! use xze_core::documentation::Document;
+//! use xze_core::types::DiátaxisCategory;
+//!
+//! let config = FrontMatterConfig {
+//!     preset: FrontMatterPreset::Docusaurus,
+//!     ..Default::default()
+//! };
+//! let doc = Document::new(
+//!     DiátaxisCategory::Tutorial,
+//!     "Quick Start".to_string(),
+//!     "# Quick Start\n".to_string(),
+//!     PathBuf::from("tutorials/quick_start.md"),
+//! );
+//!
+//! let front_matter = config.render(&doc).unwrap().unwrap();
+//! assert!(front_matter.starts_with("---\ntitle: Quick Start\n"));
+//! ```
+
+use crate::{
+    documentation::generator::Document,
+    error::{Result, XzeError},
+    types::DiátaxisCategory,
+};
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// Built-in front-matter layouts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontMatterPreset {
+    /// No preset keys; only custom fields are emitted
+    #[default]
+    None,
+    /// Hugo page front-matter
+    Hugo,
+    /// Docusaurus docs front-matter
+    Docusaurus,
+}
+
+/// Front-matter emitted at the top of each generated document
+///
+/// The default emits nothing, which leaves plain Markdown.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrontMatterConfig {
+    /// Preset providing the base keys
+    #[serde(default)]
+    pub preset: FrontMatterPreset,
+    /// Custom fields, added after the preset keys
+    ///
+    /// A field with the same name as a preset key replaces it.
+    #[serde(default)]
+    pub fields: Mapping,
+}
+
+impl FrontMatterConfig {
+    /// Whether any front-matter is emitted
+    pub fn is_enabled(&self) -> bool {
+        self.preset != FrontMatterPreset::None || !self.fields.is_empty()
+    }
+
+    /// Check that every field key is a string and every placeholder is known
+    pub fn validate(&self) -> Result<()> {
+        for (key, value) in &self.fields {
+            if !key.is_string() {
+                return Err(XzeError::validation(format!(
+                    "Front-matter keys must be strings, found {:?}",
+                    key
+                )));
+            }
+            check_placeholders(value)?;
+        }
+        Ok(())
+    }
+
+    /// Render the front-matter block for `document`
+    ///
+    /// Returns `None` when front-matter is disabled. The block starts and
+    /// ends with a `---` line.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for an unknown placeholder or if the
+    /// rendered block does not parse back as a YAML mapping.
+    pub fn render(&self, document: &Document) -> Result<Option<String>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        self.validate()?;
+
+        let mut mapping = preset_fields(self.preset);
+        for (key, value) in &self.fields {
+            mapping.insert(key.clone(), value.clone());
+        }
+        let mapping: Mapping = mapping
+            .into_iter()
+            .map(|(key, value)| (key, fill(&value, document)))
+            .collect();
+
+        let yaml = serde_yaml::to_string(&mapping)
+            .map_err(|e| XzeError::validation(format!("Invalid front-matter: {}", e)))?;
+        serde_yaml::from_str::<Mapping>(&yaml)
+            .map_err(|e| XzeError::validation(format!("Invalid front-matter: {}", e)))?;
+
+        Ok(Some(format!("---\n{}---\n", yaml)))
+    }
+
+    /// Prepend the front-matter block to the document content
+    pub fn apply(&self, document: &mut Document) -> Result<()> {
+        if let Some(front_matter) = self.render(document)? {
+            document.content = format!("{}\n{}", front_matter, document.content);
+        }
+        Ok(())
+    }
+}
+
+/// Sort weight of a category in generated sidebars
+///
+/// Follows the Diátaxis reading order: tutorials, how-to guides, reference,
+/// then explanation.
+pub fn category_weight(category: &DiátaxisCategory) -> u32 {
+    match category {
+        DiátaxisCategory::Tutorial => 10,
+        DiátaxisCategory::HowTo => 20,
+        DiátaxisCategory::Reference => 30,
+        DiátaxisCategory::Explanation => 40,
+    }
+}
+
+fn preset_fields(preset: FrontMatterPreset) -> Mapping {
+    let fields = match preset {
+        FrontMatterPreset::None => vec![],
+        FrontMatterPreset::Hugo => vec![
+            ("title", Value::from("{title}")),
+            ("date", Value::from("{date}")),
+            ("lastmod", Value::from("{updated}")),
+            ("weight", Value::from("{weight}")),
+            (
+                "categories",
+                Value::Sequence(vec![Value::from("{category}")]),
+            ),
+            ("tags", Value::from("{tags}")),
+            ("draft", Value::Bool(false)),
+        ],
+        FrontMatterPreset::Docusaurus => vec![
+            ("title", Value::from("{title}")),
+            ("sidebar_label", Value::from("{title}")),
+            ("sidebar_position", Value::from("{weight}")),
+            ("tags", Value::from("{tags}")),
+        ],
+    };
+
+    fields
+        .into_iter()
+        .map(|(key, value)| (Value::from(key), value))
+        .collect()
+}
+
+const PLACEHOLDERS: &[&str] = &[
+    "title", "category", "tags", "weight", "date", "updated", "author", "version",
+];
+
+fn placeholder_names(text: &str) -> impl Iterator<Item = &str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+}
+
+fn check_placeholders(value: &Value) -> Result<()> {
+    match value {
+        Value::String(text) => {
+            for name in placeholder_names(text) {
+                if !PLACEHOLDERS.contains(&name) && !name.starts_with("custom.") {
+                    return Err(XzeError::validation(format!(
+                        "Unknown front-matter placeholder {{{}}}",
+                        name
+                    )));
+                }
+            }
+            Ok(())
+        }
+        Value::Sequence(items) => items.iter().try_for_each(check_placeholders),
+        Value::Mapping(mapping) => mapping.values().try_for_each(check_placeholders),
+        _ => Ok(()),
+    }
+}
+
+/// Typed value of a placeholder, or `None` for a missing custom key
+fn resolve(name: &str, document: &Document) -> Option<Value> {
+    let metadata = &document.metadata;
+    let value = match name {
+        "title" => Value::from(document.title.as_str()),
+        "category" => Value::from(document.category.to_string().to_lowercase()),
+        "tags" => Value::Sequence(
+            metadata
+                .tags
+                .iter()
+                .map(|t| Value::from(t.as_str()))
+                .collect(),
+        ),
+        "weight" => Value::from(category_weight(&document.category)),
+        "date" => Value::from(
+            metadata
+                .created_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        ),
+        "updated" => Value::from(
+            metadata
+                .updated_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        ),
+        "author" => Value::from(metadata.author.as_str()),
+        "version" => Value::from(metadata.version.as_str()),
+        _ => Value::from(metadata.custom.get(name.strip_prefix("custom.")?)?.as_str()),
+    };
+    Some(value)
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Sequence(items) => items.iter().map(as_text).collect::<Vec<_>>().join(", "),
+        _ => String::new(),
+    }
+}
+
+fn fill(value: &Value, document: &Document) -> Value {
+    match value {
+        Value::String(text) => {
+            let whole = text
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .filter(|name| !name.contains(['{', '}']));
+            if let Some(name) = whole {
+                return resolve(name, document).unwrap_or(Value::Null);
+            }
+
+            let mut filled = text.clone();
+            for name in placeholder_names(text) {
+                let replacement = resolve(name, document)
+                    .map(|v| as_text(&v))
+                    .unwrap_or_default();
+                filled = filled.replace(&format!("{{{}}}", name), &replacement);
+            }
+            Value::String(filled)
+        }
+        Value::Sequence(items) => {
+            Value::Sequence(items.iter().map(|v| fill(v, document)).collect())
+        }
+        Value::Mapping(mapping) => Value::Mapping(
+            mapping
+                .iter()
+                .map(|(k, v)| (k.clone(), fill(v, document)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn document() -> Document {
+        let mut doc = Document::new(
+            DiátaxisCategory::HowTo,
+            "How to: Configure".to_string(),
+            "# Configure\n".to_string(),
+            PathBuf::from("how_to/configure.md"),
+        );
+        doc.metadata.add_tag("howto".to_string());
+        doc.metadata.add_tag("guide".to_string());
+        doc.add_metadata("repository".to_string(), "xze".to_string());
+        doc
+    }
+
+    fn parse(front_matter: &str) -> Mapping {
+        let yaml = front_matter
+            .strip_prefix("---\n")
+            .and_then(|rest| rest.strip_suffix("---\n"))
+            .unwrap();
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_default_emits_nothing() {
+        let mut doc = document();
+        FrontMatterConfig::default().apply(&mut doc).unwrap();
+        assert_eq!(doc.content, "# Configure\n");
+    }
+
+    #[test]
+    fn test_hugo_preset() {
+        let config = FrontMatterConfig {
+            preset: FrontMatterPreset::Hugo,
+            ..Default::default()
+        };
+        let front_matter = config.render(&document()).unwrap().unwrap();
+        let mapping = parse(&front_matter);
+
+        // Title needs quoting because of the colon
+        assert_eq!(mapping["title"], Value::from("How to: Configure"));
+        assert_eq!(mapping["weight"], Value::from(20));
+        assert_eq!(
+            mapping["categories"],
+            Value::Sequence(vec![Value::from("how-to")])
+        );
+        assert_eq!(
+            mapping["tags"],
+            Value::Sequence(vec![Value::from("howto"), Value::from("guide")])
+        );
+        assert_eq!(mapping["draft"], Value::Bool(false));
+        assert!(mapping["date"].as_str().unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn test_docusaurus_preset_with_custom_fields() {
+        let mut fields = Mapping::new();
+        fields.insert("sidebar_position".into(), 5.into());
+        fields.insert(
+            "description".into(),
+            "{title} for {custom.repository}".into(),
+        );
+        fields.insert("source".into(), "{custom.missing}".into());
+        let config = FrontMatterConfig {
+            preset: FrontMatterPreset::Docusaurus,
+            fields,
+        };
+
+        let mut doc = document();
+        config.apply(&mut doc).unwrap();
+        assert!(doc.content.starts_with("---\ntitle: "));
+        assert!(doc.content.ends_with("---\n\n# Configure\n"));
+
+        let mapping = parse(doc.content.strip_suffix("\n# Configure\n").unwrap());
+        assert_eq!(mapping["sidebar_label"], Value::from("How to: Configure"));
+        assert_eq!(mapping["sidebar_position"], Value::from(5));
+        assert_eq!(
+            mapping["description"],
+            Value::from("How to: Configure for xze")
+        );
+        assert_eq!(mapping["source"], Value::Null);
+    }
+
+    #[test]
+    fn test_config_from_yaml() {
+        let config: FrontMatterConfig =
+            serde_yaml::from_str("preset: hugo\nfields:\n  weight: 3\n").unwrap();
+        assert_eq!(config.preset, FrontMatterPreset::Hugo);
+
+        let mapping = parse(&config.render(&document()).unwrap().unwrap());
+        assert_eq!(mapping["weight"], Value::from(3));
+    }
+
+    #[test]
+    fn test_unknown_placeholder_is_rejected() {
+        let mut fields = Mapping::new();
+        fields.insert("summary".into(), "{description}".into());
+        let config = FrontMatterConfig {
+            fields,
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_err());
+        assert!(config.render(&document()).is_err());
+    }
+}
@@ -1,9 +1,16 @@
 //! Documentation generator for creating Diátaxis-compliant documentation
 
 use crate::{
-    ai::AIAnalysisService,
+    ai::{AIAnalysisService, AnalysisResult},
+    documentation::{
+        badge::ConfidenceBadgeConfig,
+        diagram::{architecture_section, DiagramConfig},
+        front_matter::FrontMatterConfig,
+        granularity::{slugify, DocumentSubject},
+        render::{category_dir, render_document, OutputFormat},
+    },
     error::{Result, XzeError},
-    repository::{CodeStructure, Repository},
+    repository::{CodeStructure, ConfigKey, ConfigKeyKind, Repository},
     types::DiátaxisCategory,
 };
 use async_trait::async_trait;
@@ -18,6 +25,18 @@ pub trait DocumentationGenerator: Send + Sync {
     /// Generate reference documentation
     async fn generate_reference(&self, repo: &Repository) -> Result<Document>;
 
+    /// Generate a reference page for one module, function or type
+    ///
+    /// Generators that cannot focus on a subject document the whole
+    /// repository.
+    async fn generate_reference_for(
+        &self,
+        repo: &Repository,
+        _subject: &DocumentSubject,
+    ) -> Result<Document> {
+        self.generate_reference(repo).await
+    }
+
     /// Generate how-to documentation
     async fn generate_howto(&self, repo: &Repository, task: &str) -> Result<Document>;
 
@@ -27,6 +46,13 @@ pub trait DocumentationGenerator: Send + Sync {
     /// Generate explanation documentation
     async fn generate_explanation(&self, repo: &Repository, concept: &str) -> Result<Document>;
 
+    /// Whether generated documents are linked to each other afterwards
+    ///
+    /// See [`linker`](crate::documentation::linker).
+    fn cross_reference(&self) -> bool {
+        false
+    }
+
     /// Generate all documentation types
     async fn generate_all(&self, repo: &Repository) -> Result<Vec<Document>> {
         let mut documents = Vec::new();
@@ -155,6 +181,29 @@ pub struct DocumentMetadata {
     pub tags: Vec<String>,
     /// Custom metadata
     pub custom: HashMap<String, String>,
+    /// Headings of the sections rewritten by the last section-level update
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patched_sections: Vec<String>,
+    /// Persona prepended to the generation prompt, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    /// Overall confidence score (0.0-1.0) of the generated content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Whether generation was cut short at the output limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Items the generation prompt was limited to, when the repository
+    /// did not fit in the context window
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_items: Vec<String>,
+    /// Items left out of the generation prompt
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub omitted_context_items: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 impl DocumentMetadata {
@@ -169,6 +218,12 @@ impl DocumentMetadata {
             version: "1.0".to_string(),
             tags: Vec::new(),
             custom: HashMap::new(),
+            patched_sections: Vec::new(),
+            persona: None,
+            confidence: None,
+            truncated: false,
+            context_items: Vec::new(),
+            omitted_context_items: 0,
         }
     }
 
@@ -203,6 +258,14 @@ impl AIDocumentationGenerator {
         }
     }
 
+    /// Mermaid architecture diagrams of `structure`, as a document section
+    ///
+    /// Built from the structure alone, without a model call. Returns `None`
+    /// when diagrams are disabled or there is nothing to draw.
+    pub fn architecture_diagrams(&self, structure: &CodeStructure) -> Option<String> {
+        architecture_section(structure, &self.config.diagrams)
+    }
+
     /// Load template from cache or file
     #[allow(dead_code)]
     async fn load_template(&self, template_name: &str) -> Result<Option<String>> {
@@ -458,31 +521,102 @@ impl AIDocumentationGenerator {
             DiátaxisCategory::Explanation => "explanation",
         };
 
-        let filename = format!(
-            "{}.md",
-            title
-                .to_lowercase()
-                .replace([' ', '-'], "_")
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '_')
-                .collect::<String>()
-        );
+        let filename = format!("{}.md", slugify(title));
 
         self.config.output_dir.join(category_dir).join(filename)
     }
 
-    /// Post-process generated content
-    fn post_process_content(&self, content: &str, category: &DiátaxisCategory) -> String {
-        let mut processed = content.to_string();
+    /// Record the confidence score, then add the confidence badge and
+    /// front-matter
+    fn finish_document(&self, document: &mut Document, result: &AnalysisResult) -> Result<()> {
+        document.metadata.confidence = Some(result.confidence);
+        document.metadata.truncated = result.truncated;
+        document.metadata.context_items = result.context_items.clone();
+        document.metadata.omitted_context_items = result.omitted_context_items;
+        if result.truncated {
+            warn!(
+                "'{}' was truncated at the output limit for {}",
+                document.title, document.category
+            );
+        }
+        match (&self.config.confidence_badge, &result.confidence_score) {
+            (Some(badge), Some(score)) => badge.apply(document, score, &self.config.front_matter),
+            _ => self.config.front_matter.apply(document),
+        }
+    }
 
-        // Add frontmatter if configured
-        if self.config.add_frontmatter {
-            let frontmatter = self.generate_frontmatter(category);
-            processed = format!("{}\n\n{}", frontmatter, processed);
+    /// Generate a reference page for `structure`
+    ///
+    /// The configuration and deprecation sections are only added to the
+    /// whole-repository page.
+    async fn reference_document(
+        &self,
+        repo: &Repository,
+        structure: &CodeStructure,
+        title: String,
+        subject: Option<&DocumentSubject>,
+    ) -> Result<Document> {
+        let result = self
+            .ai_service
+            .generate_documentation(
+                &DiátaxisCategory::Reference,
+                structure,
+                &subject.map(DocumentSubject::describe).unwrap_or_default(),
+                self.config.stop_sequences_for(&DiátaxisCategory::Reference),
+                self.config
+                    .max_output_tokens_for(&DiátaxisCategory::Reference),
+            )
+            .await?;
+
+        let mut processed_content = self.post_process_content(&result.summary);
+        if subject.is_none() {
+            for section in [
+                configuration_section(&repo.structure),
+                deprecated_section(&repo.structure),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                processed_content = format!("{}\n\n{}", processed_content.trim_end(), section);
+            }
+        }
+        if let Some(section) = self.architecture_diagrams(structure) {
+            processed_content = format!("{}\n\n{}", processed_content.trim_end(), section);
         }
+        let file_path = self.generate_file_path(&DiátaxisCategory::Reference, &title);
 
+        let mut document = Document::new(
+            DiátaxisCategory::Reference,
+            title,
+            processed_content,
+            file_path,
+        );
+
+        document.add_metadata("repository".to_string(), repo.name().to_string());
+        document.add_metadata("language".to_string(), repo.language.to_string());
+        if let Some(subject) = subject {
+            document.add_metadata("subject".to_string(), subject.name.clone());
+        }
+        document.metadata.add_tag("api".to_string());
+        document.metadata.add_tag("reference".to_string());
+        document.metadata.persona = self
+            .ai_service
+            .persona_for(&DiátaxisCategory::Reference)
+            .map(str::to_string);
+
+        self.finish_document(&mut document, &result)?;
+
+        debug!(
+            "Generated reference document with {} words",
+            document.word_count()
+        );
+        Ok(document)
+    }
+
+    /// Post-process generated content
+    fn post_process_content(&self, content: &str) -> String {
         // Clean up common AI generation artifacts
-        processed = processed
+        let mut processed = content
             .replace("```markdown\n", "")
             .replace("\n```", "")
             .trim()
@@ -502,48 +636,30 @@ impl AIDocumentationGenerator {
 
         processed
     }
-
-    /// Generate frontmatter for document
-    fn generate_frontmatter(&self, category: &DiátaxisCategory) -> String {
-        format!(
-            "---\ntype: {}\ngenerated_by: XZe\ngenerated_at: {}\n---",
-            category.to_string().to_lowercase(),
-            chrono::Utc::now().to_rfc3339()
-        )
-    }
 }
 
 #[async_trait]
 impl DocumentationGenerator for AIDocumentationGenerator {
     async fn generate_reference(&self, repo: &Repository) -> Result<Document> {
         info!("Generating reference documentation for {}", repo.name());
-
-        let content = self
-            .ai_service
-            .generate_api_documentation(&repo.structure)
-            .await?;
-
-        let processed_content = self.post_process_content(&content, &DiátaxisCategory::Reference);
         let title = format!("{} API Reference", repo.name());
-        let file_path = self.generate_file_path(&DiátaxisCategory::Reference, &title);
-
-        let mut document = Document::new(
-            DiátaxisCategory::Reference,
-            title,
-            processed_content,
-            file_path,
-        );
-
-        document.add_metadata("repository".to_string(), repo.name().to_string());
-        document.add_metadata("language".to_string(), repo.language.to_string());
-        document.metadata.add_tag("api".to_string());
-        document.metadata.add_tag("reference".to_string());
+        self.reference_document(repo, &repo.structure, title, None)
+            .await
+    }
 
-        debug!(
-            "Generated reference document with {} words",
-            document.word_count()
+    async fn generate_reference_for(
+        &self,
+        repo: &Repository,
+        subject: &DocumentSubject,
+    ) -> Result<Document> {
+        info!(
+            "Generating reference documentation for {} - {}",
+            repo.name(),
+            subject.name
         );
-        Ok(document)
+        let title = format!("{} {} Reference", repo.name(), subject.name);
+        self.reference_document(repo, &subject.structure, title, Some(subject))
+            .await
     }
 
     async fn generate_howto(&self, repo: &Repository, task: &str) -> Result<Document> {
@@ -553,12 +669,18 @@ impl DocumentationGenerator for AIDocumentationGenerator {
             task
         );
 
-        let content = self
+        let result = self
             .ai_service
-            .generate_howto(&repo.structure, task)
+            .generate_documentation(
+                &DiátaxisCategory::HowTo,
+                &repo.structure,
+                task,
+                self.config.stop_sequences_for(&DiátaxisCategory::HowTo),
+                self.config.max_output_tokens_for(&DiátaxisCategory::HowTo),
+            )
             .await?;
 
-        let processed_content = self.post_process_content(&content, &DiátaxisCategory::HowTo);
+        let processed_content = self.post_process_content(&result.summary);
         let title = format!("How to: {}", task);
         let file_path = self.generate_file_path(&DiátaxisCategory::HowTo, &title);
 
@@ -569,6 +691,12 @@ impl DocumentationGenerator for AIDocumentationGenerator {
         document.add_metadata("task".to_string(), task.to_string());
         document.metadata.add_tag("howto".to_string());
         document.metadata.add_tag("guide".to_string());
+        document.metadata.persona = self
+            .ai_service
+            .persona_for(&DiátaxisCategory::HowTo)
+            .map(str::to_string);
+
+        self.finish_document(&mut document, &result)?;
 
         debug!(
             "Generated how-to document with {} words",
@@ -584,12 +712,19 @@ impl DocumentationGenerator for AIDocumentationGenerator {
             topic
         );
 
-        let content = self
+        let result = self
             .ai_service
-            .generate_tutorial(&repo.structure, topic)
+            .generate_documentation(
+                &DiátaxisCategory::Tutorial,
+                &repo.structure,
+                topic,
+                self.config.stop_sequences_for(&DiátaxisCategory::Tutorial),
+                self.config
+                    .max_output_tokens_for(&DiátaxisCategory::Tutorial),
+            )
             .await?;
 
-        let processed_content = self.post_process_content(&content, &DiátaxisCategory::Tutorial);
+        let processed_content = self.post_process_content(&result.summary);
         let title = format!("{} Tutorial", topic);
         let file_path = self.generate_file_path(&DiátaxisCategory::Tutorial, &title);
 
@@ -604,6 +739,12 @@ impl DocumentationGenerator for AIDocumentationGenerator {
         document.add_metadata("topic".to_string(), topic.to_string());
         document.metadata.add_tag("tutorial".to_string());
         document.metadata.add_tag("learning".to_string());
+        document.metadata.persona = self
+            .ai_service
+            .persona_for(&DiátaxisCategory::Tutorial)
+            .map(str::to_string);
+
+        self.finish_document(&mut document, &result)?;
 
         debug!(
             "Generated tutorial document with {} words",
@@ -619,12 +760,23 @@ impl DocumentationGenerator for AIDocumentationGenerator {
             concept
         );
 
-        let content = self
+        let result = self
             .ai_service
-            .generate_explanation(&repo.structure, concept)
+            .generate_documentation(
+                &DiátaxisCategory::Explanation,
+                &repo.structure,
+                concept,
+                self.config
+                    .stop_sequences_for(&DiátaxisCategory::Explanation),
+                self.config
+                    .max_output_tokens_for(&DiátaxisCategory::Explanation),
+            )
             .await?;
 
-        let processed_content = self.post_process_content(&content, &DiátaxisCategory::Explanation);
+        let mut processed_content = self.post_process_content(&result.summary);
+        if let Some(section) = self.architecture_diagrams(&repo.structure) {
+            processed_content = format!("{}\n\n{}", processed_content.trim_end(), section);
+        }
         let title = format!("{} Explanation", concept);
         let file_path = self.generate_file_path(&DiátaxisCategory::Explanation, &title);
 
@@ -639,6 +791,12 @@ impl DocumentationGenerator for AIDocumentationGenerator {
         document.add_metadata("concept".to_string(), concept.to_string());
         document.metadata.add_tag("explanation".to_string());
         document.metadata.add_tag("understanding".to_string());
+        document.metadata.persona = self
+            .ai_service
+            .persona_for(&DiátaxisCategory::Explanation)
+            .map(str::to_string);
+
+        self.finish_document(&mut document, &result)?;
 
         debug!(
             "Generated explanation document with {} words",
@@ -646,13 +804,18 @@ impl DocumentationGenerator for AIDocumentationGenerator {
         );
         Ok(document)
     }
+
+    fn cross_reference(&self) -> bool {
+        self.config.cross_reference
+    }
 }
 
 /// Generator configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratorConfig {
-    /// Whether to add YAML frontmatter to documents
-    pub add_frontmatter: bool,
+    /// YAML front-matter emitted at the top of each document
+    #[serde(default)]
+    pub front_matter: FrontMatterConfig,
     /// Output directory for generated docs
     pub output_dir: PathBuf,
     /// Template directory (optional)
@@ -661,20 +824,105 @@ pub struct GeneratorConfig {
     pub overwrite_existing: bool,
     /// Maximum content length per document
     pub max_content_length: usize,
+    /// Stop sequences per document category
+    #[serde(default = "default_stop_sequences")]
+    pub stop_sequences: HashMap<DiátaxisCategory, Vec<String>>,
+    /// Maximum generated tokens per document category; categories not
+    /// listed are limited only by the model's context window
+    #[serde(default = "default_max_output_tokens")]
+    pub max_output_tokens: HashMap<DiátaxisCategory, usize>,
+    /// Badge showing readers each document's confidence score (omitted
+    /// when unset)
+    #[serde(default)]
+    pub confidence_badge: Option<ConfidenceBadgeConfig>,
+    /// Format documents are written in; see [`crate::documentation::render`]
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Mermaid diagrams added to reference and explanation documents
+    #[serde(default)]
+    pub diagrams: DiagramConfig,
+    /// Link mentions of other documents' titles and of code items across
+    /// the generated set; see [`crate::documentation::linker`]
+    #[serde(default)]
+    pub cross_reference: bool,
+}
+
+impl GeneratorConfig {
+    /// Get the stop sequences for a document category
+    pub fn stop_sequences_for(&self, category: &DiátaxisCategory) -> &[String] {
+        self.stop_sequences
+            .get(category)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the output token limit for a document category
+    pub fn max_output_tokens_for(&self, category: &DiátaxisCategory) -> Option<usize> {
+        self.max_output_tokens.get(category).copied()
+    }
 }
 
 impl Default for GeneratorConfig {
     fn default() -> Self {
         Self {
-            add_frontmatter: true,
+            front_matter: FrontMatterConfig::default(),
             output_dir: PathBuf::from("docs"),
             template_dir: None,
             overwrite_existing: false,
             max_content_length: 50000, // ~50KB
+            stop_sequences: default_stop_sequences(),
+            max_output_tokens: default_max_output_tokens(),
+            confidence_badge: None,
+            output_format: OutputFormat::default(),
+            diagrams: DiagramConfig::default(),
+            cross_reference: false,
         }
     }
 }
 
+/// Default output token limits for every Diátaxis category
+///
+/// Reference pages and how-to guides are kept short; tutorials, which walk
+/// through several steps, get the most room.
+pub fn default_max_output_tokens() -> HashMap<DiátaxisCategory, usize> {
+    HashMap::from([
+        (DiátaxisCategory::Reference, 2048),
+        (DiátaxisCategory::HowTo, 2048),
+        (DiátaxisCategory::Explanation, 3072),
+        (DiátaxisCategory::Tutorial, 4096),
+    ])
+}
+
+/// Phrases that start meta-commentary after the document body
+const META_COMMENTARY_STOPS: &[&str] = &[
+    "\nI hope this helps",
+    "\nLet me know if",
+    "\nFeel free to ask",
+];
+
+/// Default stop sequence presets for every Diátaxis category
+///
+/// Every category stops on common chat sign-offs. Tutorials and how-to
+/// guides additionally stop when the model starts offering follow-ups.
+pub fn default_stop_sequences() -> HashMap<DiátaxisCategory, Vec<String>> {
+    DiátaxisCategory::ALL
+        .iter()
+        .map(|category| {
+            let mut stops: Vec<String> = META_COMMENTARY_STOPS
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            if matches!(
+                category,
+                DiátaxisCategory::Tutorial | DiátaxisCategory::HowTo
+            ) {
+                stops.push("\nWould you like".to_string());
+            }
+            (category.clone(), stops)
+        })
+        .collect()
+}
+
 /// Document writer for saving generated documents
 pub struct DocumentWriter {
     config: GeneratorConfig,
@@ -686,9 +934,22 @@ impl DocumentWriter {
         Self { config }
     }
 
+    /// Configuration documents are written with
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
     /// Write a document to file
+    ///
+    /// The document is rendered in the configured output format and its
+    /// file extension replaced with the format's extension.
     pub async fn write_document(&self, document: &Document) -> Result<PathBuf> {
-        let full_path = self.config.output_dir.join(&document.file_path);
+        let format = self.config.output_format;
+        let full_path = self
+            .config
+            .output_dir
+            .join(&document.file_path)
+            .with_extension(format.extension());
 
         // Create parent directories
         if let Some(parent) = full_path.parent() {
@@ -706,7 +967,8 @@ impl DocumentWriter {
         }
 
         // Write content
-        tokio::fs::write(&full_path, &document.content)
+        let content = render_document(document, format)?;
+        tokio::fs::write(&full_path, content)
             .await
             .map_err(|e| XzeError::filesystem(format!("Failed to write file: {}", e)))?;
 
@@ -732,19 +994,21 @@ impl DocumentWriter {
     }
 
     /// Generate index file for a category
+    ///
+    /// HTML output gets an `index.html` page, which the nav bar of every
+    /// page links to; the other formats get a Markdown `README.md`.
     pub async fn generate_index(
         &self,
         category: &DiátaxisCategory,
         documents: &[Document],
     ) -> Result<PathBuf> {
-        let category_dir = match category {
-            DiátaxisCategory::Tutorial => "tutorials",
-            DiátaxisCategory::HowTo => "how_to",
-            DiátaxisCategory::Reference => "reference",
-            DiátaxisCategory::Explanation => "explanation",
-        };
-
-        let index_path = self.config.output_dir.join(category_dir).join("README.md");
+        let html = self.config.output_format == OutputFormat::Html;
+        let index_name = if html { "index.html" } else { "README.md" };
+        let index_path = self
+            .config
+            .output_dir
+            .join(category_dir(category))
+            .join(index_name);
 
         // Create parent directories
         if let Some(parent) = index_path.parent() {
@@ -769,17 +1033,26 @@ impl DocumentWriter {
             content.push_str("No documents available yet.\n");
         } else {
             for doc in category_docs {
-                let filename = doc
-                    .file_path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("");
-                content.push_str(&format!("- [{}]({})\n", doc.title, filename));
+                let mut filename = PathBuf::from(doc.file_path.file_name().unwrap_or_default());
+                if html {
+                    filename.set_extension("html");
+                }
+                content.push_str(&format!("- [{}]({})\n", doc.title, filename.display()));
             }
         }
 
         content.push('\n');
 
+        if html {
+            let index = Document::new(
+                category.clone(),
+                category.to_string(),
+                content,
+                PathBuf::from(index_name),
+            );
+            content = render_document(&index, OutputFormat::Html)?;
+        }
+
         // Write index file
         tokio::fs::write(&index_path, content)
             .await
@@ -921,6 +1194,75 @@ impl IndexGenerator {
     }
 }
 
+/// Reference section listing deprecated items, taken from the analyzed
+/// deprecation markers rather than the model output
+fn deprecated_section(structure: &CodeStructure) -> Option<String> {
+    let mut deprecations = structure.deprecations().peekable();
+    deprecations.peek()?;
+
+    let mut section = "## Deprecated\n\n".to_string();
+    for annotation in deprecations {
+        let name = annotation.item.as_deref().unwrap_or("(unnamed item)");
+        let location = format!("{}:{}", annotation.path.display(), annotation.line);
+        if annotation.text.is_empty() {
+            section.push_str(&format!("- `{}` ({})\n", name, location));
+        } else {
+            section.push_str(&format!(
+                "- `{}`: {} ({})\n",
+                name, annotation.text, location
+            ));
+        }
+    }
+    Some(section)
+}
+
+/// Reference section listing the environment variables and configuration
+/// keys found during analysis
+fn configuration_section(structure: &CodeStructure) -> Option<String> {
+    let keys = structure.merged_config_keys();
+    if keys.is_empty() {
+        return None;
+    }
+
+    let mut section = "## Configuration\n".to_string();
+    for (kind, heading) in [
+        (ConfigKeyKind::EnvVar, "Environment Variables"),
+        (ConfigKeyKind::ConfigKey, "Configuration Keys"),
+    ] {
+        let rows: Vec<&ConfigKey> = keys.iter().filter(|k| k.kind == kind).collect();
+        if rows.is_empty() {
+            continue;
+        }
+
+        section.push_str(&format!(
+            "\n### {}\n\n| Name | Default | Description | Source |\n| --- | --- | --- | --- |\n",
+            heading
+        ));
+        for key in rows {
+            let cell = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .map(|v| v.replace('|', "\\|"))
+                    .unwrap_or_default()
+            };
+            let default = key
+                .default
+                .as_ref()
+                .map(|_| format!("`{}`", cell(&key.default)))
+                .unwrap_or_default();
+            section.push_str(&format!(
+                "| `{}` | {} | {} | {}:{} |\n",
+                key.name,
+                default,
+                cell(&key.description),
+                key.path.display(),
+                key.line
+            ));
+        }
+    }
+    Some(section)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -970,12 +1312,54 @@ mod tests {
     #[test]
     fn test_generator_config_default() {
         let config = GeneratorConfig::default();
-        assert!(config.add_frontmatter);
+        assert!(!config.front_matter.is_enabled());
         assert_eq!(config.output_dir, PathBuf::from("docs"));
         assert!(!config.overwrite_existing);
         assert_eq!(config.max_content_length, 50000);
     }
 
+    #[test]
+    fn test_generator_config_output_limits() {
+        let config = GeneratorConfig::default();
+        let reference = config
+            .max_output_tokens_for(&DiátaxisCategory::Reference)
+            .unwrap();
+        let tutorial = config
+            .max_output_tokens_for(&DiátaxisCategory::Tutorial)
+            .unwrap();
+        assert!(reference < tutorial);
+
+        let unlimited = GeneratorConfig {
+            max_output_tokens: HashMap::new(),
+            ..Default::default()
+        };
+        assert!(unlimited
+            .max_output_tokens_for(&DiátaxisCategory::Tutorial)
+            .is_none());
+    }
+
+    #[test]
+    fn test_generator_config_stop_sequence_presets() {
+        let config = GeneratorConfig::default();
+        for category in DiátaxisCategory::ALL.iter() {
+            let stops = config.stop_sequences_for(category);
+            assert!(stops.iter().any(|s| s == "\nI hope this helps"));
+            assert!(stops.iter().all(|s| !s.is_empty()));
+        }
+        assert!(config
+            .stop_sequences_for(&DiátaxisCategory::Tutorial)
+            .iter()
+            .any(|s| s == "\nWould you like"));
+
+        let empty = GeneratorConfig {
+            stop_sequences: HashMap::new(),
+            ..Default::default()
+        };
+        assert!(empty
+            .stop_sequences_for(&DiátaxisCategory::Reference)
+            .is_empty());
+    }
+
     #[test]
     fn test_file_path_generation() {
         let ai_service = Arc::new(AIAnalysisService::new(
@@ -991,6 +1375,78 @@ mod tests {
         assert_eq!(path, PathBuf::from("docs/how-to/how_to_configure.md"));
     }
 
+    #[test]
+    fn test_finish_document_adds_confidence_badge() {
+        let content = "# Guide\n\nBody\n".to_string();
+        let score = crate::ai::ConfidenceScorer::new()
+            .score(
+                &content,
+                &crate::ai::ScoringContext::new(crate::ai::DocumentType::HowTo),
+            )
+            .unwrap();
+        let result = AnalysisResult {
+            summary: content.clone(),
+            confidence: score.overall,
+            validation: None,
+            confidence_score: Some(score),
+            truncated: false,
+            context_items: Vec::new(),
+            omitted_context_items: 0,
+        };
+        let ai_service = Arc::new(AIAnalysisService::new(
+            "http://localhost:11434".to_string(),
+            ModelConfig::default(),
+        ));
+        let new_document = || {
+            Document::new(
+                DiátaxisCategory::HowTo,
+                "Guide".to_string(),
+                content.clone(),
+                PathBuf::from("guide.md"),
+            )
+        };
+
+        let plain = AIDocumentationGenerator::new(ai_service.clone(), GeneratorConfig::default());
+        let mut document = new_document();
+        plain.finish_document(&mut document, &result).unwrap();
+        assert_eq!(document.content, content);
+        assert_eq!(document.metadata.confidence, Some(result.confidence));
+        assert!(!document.metadata.truncated);
+
+        let truncated = AnalysisResult {
+            truncated: true,
+            ..result.clone()
+        };
+        let mut document = new_document();
+        plain.finish_document(&mut document, &truncated).unwrap();
+        assert!(document.metadata.truncated);
+        let json = serde_json::to_value(&document.metadata).unwrap();
+        assert_eq!(json["truncated"], true);
+        assert!(json.get("context_items").is_none());
+
+        let selected = AnalysisResult {
+            context_items: vec!["fn parse".to_string()],
+            omitted_context_items: 4,
+            ..result.clone()
+        };
+        let mut document = new_document();
+        plain.finish_document(&mut document, &selected).unwrap();
+        assert_eq!(document.metadata.context_items, ["fn parse"]);
+        assert_eq!(document.metadata.omitted_context_items, 4);
+
+        let config = GeneratorConfig {
+            confidence_badge: Some(ConfidenceBadgeConfig::default()),
+            ..Default::default()
+        };
+        let badged = AIDocumentationGenerator::new(ai_service, config);
+        let mut document = new_document();
+        badged.finish_document(&mut document, &result).unwrap();
+        assert!(document.content.starts_with(&content));
+        assert!(document
+            .content
+            .ends_with("% (Very Low) - review recommended\n"));
+    }
+
     #[test]
     fn test_content_post_processing() {
         let ai_service = Arc::new(AIAnalysisService::new(
@@ -1000,9 +1456,9 @@ mod tests {
         let generator = AIDocumentationGenerator::new(ai_service, GeneratorConfig::default());
 
         let raw_content = "```markdown\n# Test\nContent\n```";
-        let processed = generator.post_process_content(raw_content, &DiátaxisCategory::Tutorial);
+        let processed = generator.post_process_content(raw_content);
 
-        assert!(processed.starts_with("---\n"));
+        assert!(processed.starts_with("# Test"));
         assert!(processed.contains("# Test"));
         assert!(processed.contains("Content"));
         assert!(!processed.contains("```markdown"));
@@ -1032,4 +1488,77 @@ mod tests {
         let content = std::fs::read_to_string(&written_path).unwrap();
         assert!(content.contains("# Test Tutorial"));
     }
+
+    #[tokio::test]
+    async fn test_document_writer_html_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let writer = DocumentWriter::new(GeneratorConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            output_format: OutputFormat::Html,
+            ..Default::default()
+        });
+
+        let document = Document::new(
+            DiátaxisCategory::Tutorial,
+            "Test Tutorial".to_string(),
+            "# Test Tutorial\n\n## Setup\n\nContent here.".to_string(),
+            PathBuf::from("tutorials/test.md"),
+        );
+
+        let written_path = writer.write_document(&document).await.unwrap();
+        assert_eq!(written_path, temp_dir.path().join("tutorials/test.html"));
+        let content = std::fs::read_to_string(&written_path).unwrap();
+        assert!(content.contains("<h2 id=\"setup\">Setup</h2>"));
+
+        let index_path = writer
+            .generate_index(&DiátaxisCategory::Tutorial, &[document])
+            .await
+            .unwrap();
+        assert_eq!(index_path, temp_dir.path().join("tutorials/index.html"));
+        let index = std::fs::read_to_string(&index_path).unwrap();
+        assert!(index.contains("<a href=\"test.html\">Test Tutorial</a>"));
+    }
+
+    #[test]
+    fn test_deprecated_section() {
+        let mut structure = CodeStructure::new();
+        assert!(deprecated_section(&structure).is_none());
+
+        structure.record_annotations(
+            std::path::Path::new("src/client.rs"),
+            "#[deprecated(note = \"use connect\")]\npub fn dial() {}\n\n#[deprecated]\npub struct OldConfig;\n",
+            &ProgrammingLanguage::Rust,
+        );
+
+        let section = deprecated_section(&structure).unwrap();
+        assert!(section.starts_with("## Deprecated\n\n"));
+        assert!(section.contains("- `dial`: use connect (src/client.rs:1)\n"));
+        assert!(section.contains("- `OldConfig` (src/client.rs:4)\n"));
+    }
+
+    #[test]
+    fn test_configuration_section() {
+        let mut structure = CodeStructure::new();
+        assert!(configuration_section(&structure).is_none());
+
+        structure.record_config_keys(
+            std::path::Path::new("src/main.rs"),
+            "let port = env::var(\"PORT\").unwrap_or(\"8080\"); // HTTP port\nlet url = env::var(\"DATABASE_URL\");\n",
+            &ProgrammingLanguage::Rust,
+        );
+        structure
+            .config_keys
+            .extend(crate::repository::extract_config_file_keys(
+                std::path::Path::new("config.toml"),
+                "[log]\nformat = \"a|b\"\n",
+                crate::repository::ConfigFormat::Toml,
+            ));
+
+        let section = configuration_section(&structure).unwrap();
+        assert!(section.starts_with("## Configuration\n\n### Environment Variables\n"));
+        assert!(section.contains("| `DATABASE_URL` |  |  | src/main.rs:2 |\n"));
+        assert!(section.contains("| `PORT` | `8080` | HTTP port | src/main.rs:1 |\n"));
+        assert!(section.contains("### Configuration Keys\n"));
+        assert!(section.contains("| `log.format` | `a\\|b` |  | config.toml:2 |\n"));
+    }
 }
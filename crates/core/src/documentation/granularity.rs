@@ -0,0 +1,383 @@
+//! Documents per Diátaxis category
+//!
+//! By default every category produces one document. A large service needs
+//! more: a reference page per module, a how-to guide per task. The
+//! [`DocumentGranularity`] setting splits the reference and how-to
+//! categories into one document per subject:
+//!
+//! | Granularity  | Reference                          | How-to                        |
+//! |--------------|------------------------------------|-------------------------------|
+//! | `single`     | one page                           | one guide                     |
+//! | `per_module` | one page per public module         | one guide per public module   |
+//! | `per_item`   | one page per public function/type  | one guide per public function |
+//!
+//! Tutorials and explanations are narratives about the whole project and
+//! always produce one document. When the structure has no subjects for the
+//! chosen granularity, the category falls back to a single document.
+//!
+//! File names are derived from document titles. Two titles can reduce to
+//! the same name, such as `parse-url` and `parse_url`, so a
+//! [`SlugRegistry`] suffixes repeats with `_2`, `_3` and so on.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::documentation::granularity::{subjects, DocumentGranularity};
+//! use xze_core::repository::{CodeStructure, Module, Visibility};
+//! use xze_core::types::DiátaxisCategory;
+//! use std::path::PathBuf;
+//!
+//! let mut structure = CodeStructure::new();
+//! for name in ["parser", "render"] {
+//!     structure.modules.push(Module {
+//!         name: name.to_string(),
+//!         path: PathBuf::from(format!("src/{}.rs", name)),
+//!         documentation: None,
+//!         visibility: Visibility::Public,
+//!     });
+//! }
+//!
+//! let reference = subjects(DocumentGranularity::PerModule, &DiátaxisCategory::Reference, &structure);
+//! assert_eq!(reference.len(), 2);
+//! assert_eq!(reference[0].describe(), "the `parser` module");
+//!
+//! assert!(subjects(DocumentGranularity::PerModule, &DiátaxisCategory::Tutorial, &structure).is_empty());
+//! ```
+
+use crate::{
+    repository::{CodeStructure, Visibility},
+    types::DiátaxisCategory,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// How many documents a Diátaxis category produces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentGranularity {
+    /// One document per category
+    #[default]
+    Single,
+    /// One reference page and how-to guide per public module
+    PerModule,
+    /// One reference page per public function or type, and one how-to
+    /// guide per public function
+    PerItem,
+}
+
+impl fmt::Display for DocumentGranularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DocumentGranularity::Single => "single",
+            DocumentGranularity::PerModule => "per_module",
+            DocumentGranularity::PerItem => "per_item",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for DocumentGranularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "single" => Ok(DocumentGranularity::Single),
+            "per_module" => Ok(DocumentGranularity::PerModule),
+            "per_item" => Ok(DocumentGranularity::PerItem),
+            other => Err(format!(
+                "Unknown granularity '{}': expected single, per_module or per_item",
+                other
+            )),
+        }
+    }
+}
+
+/// What a document subject is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectKind {
+    /// A module
+    Module,
+    /// A function
+    Function,
+    /// A type
+    Type,
+}
+
+/// The part of a repository one document covers
+#[derive(Debug, Clone)]
+pub struct DocumentSubject {
+    /// Name of the module, function or type
+    pub name: String,
+    /// What the subject is
+    pub kind: SubjectKind,
+    /// The analyzed structure narrowed to the subject
+    ///
+    /// Functions and types are narrowed to the item itself. Modules keep
+    /// every function and type, since analyzers do not record which module
+    /// an item belongs to.
+    pub structure: CodeStructure,
+}
+
+impl DocumentSubject {
+    /// Phrase naming the subject in prompts, such as ``the `parser` module``
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            SubjectKind::Module => "module",
+            SubjectKind::Function => "function",
+            SubjectKind::Type => "type",
+        };
+        format!("the `{}` {}", self.name, kind)
+    }
+
+    /// Task of the how-to guide for the subject
+    pub fn task(&self) -> String {
+        match self.kind {
+            SubjectKind::Module => format!("Use the {} module", self.name),
+            SubjectKind::Function | SubjectKind::Type => format!("Use {}", self.name),
+        }
+    }
+}
+
+/// The subjects `category` is split into at `granularity`
+///
+/// Returns no subjects when the category produces a single document.
+/// Subjects are in structure order with repeated names dropped.
+pub fn subjects(
+    granularity: DocumentGranularity,
+    category: &DiátaxisCategory,
+    structure: &CodeStructure,
+) -> Vec<DocumentSubject> {
+    let splits = matches!(
+        category,
+        DiátaxisCategory::Reference | DiátaxisCategory::HowTo
+    );
+    if !splits {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::new();
+    let mut subjects = Vec::new();
+    let mut push = |name: &str, kind: SubjectKind, structure: CodeStructure| {
+        if seen.insert((name.to_string(), kind)) {
+            subjects.push(DocumentSubject {
+                name: name.to_string(),
+                kind,
+                structure,
+            });
+        }
+    };
+
+    match granularity {
+        DocumentGranularity::Single => {}
+        DocumentGranularity::PerModule => {
+            for module in structure
+                .modules
+                .iter()
+                .filter(|m| m.visibility == Visibility::Public)
+            {
+                let mut focused = structure.clone();
+                focused.modules = vec![module.clone()];
+                push(&module.name, SubjectKind::Module, focused);
+            }
+        }
+        DocumentGranularity::PerItem => {
+            for function in structure.public_functions() {
+                let focused = CodeStructure {
+                    functions: vec![function.clone()],
+                    ..CodeStructure::default()
+                };
+                push(&function.name, SubjectKind::Function, focused);
+            }
+            if *category == DiátaxisCategory::Reference {
+                for ty in structure
+                    .types
+                    .iter()
+                    .filter(|t| t.visibility == Visibility::Public)
+                {
+                    let focused = CodeStructure {
+                        types: vec![ty.clone()],
+                        ..CodeStructure::default()
+                    };
+                    push(&ty.name, SubjectKind::Type, focused);
+                }
+            }
+        }
+    }
+
+    subjects
+}
+
+/// File name stem for a document title
+///
+/// Lowercases the title, turns spaces and dashes into underscores and
+/// drops other punctuation.
+pub fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        .replace([' ', '-'], "_")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// Hands out document paths, renaming repeats
+#[derive(Debug, Clone, Default)]
+pub struct SlugRegistry {
+    taken: HashSet<PathBuf>,
+}
+
+impl SlugRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve `path`, or the first free `<stem>_<n>` variant of it
+    pub fn claim(&mut self, path: &Path) -> PathBuf {
+        let mut candidate = path.to_path_buf();
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let extension = path.extension().map(|e| e.to_string_lossy().into_owned());
+        let mut n = 2;
+        while self.taken.contains(&candidate) {
+            let name = match &extension {
+                Some(extension) => format!("{}_{}.{}", stem, n, extension),
+                None => format!("{}_{}", stem, n),
+            };
+            candidate = path.with_file_name(name);
+            n += 1;
+        }
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, Module, TypeDefinition, TypeKind};
+
+    fn structure() -> CodeStructure {
+        let module = |name: &str, visibility| Module {
+            name: name.to_string(),
+            path: PathBuf::from(format!("src/{}.rs", name)),
+            documentation: None,
+            visibility,
+        };
+        let function = |name: &str, visibility| Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: None,
+            parameters: Vec::new(),
+            return_type: None,
+            visibility,
+            is_async: false,
+        };
+        CodeStructure {
+            modules: vec![
+                module("parser", Visibility::Public),
+                module("internal", Visibility::Private),
+                module("parser", Visibility::Public),
+            ],
+            functions: vec![
+                function("parse", Visibility::Public),
+                function("helper", Visibility::Private),
+            ],
+            types: vec![TypeDefinition {
+                name: "Config".to_string(),
+                kind: TypeKind::Struct,
+                documentation: None,
+                fields: Vec::new(),
+                visibility: Visibility::Public,
+                methods: Vec::new(),
+            }],
+            ..CodeStructure::default()
+        }
+    }
+
+    fn names(subjects: &[DocumentSubject]) -> Vec<&str> {
+        subjects.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_single_produces_no_subjects() {
+        for category in DiátaxisCategory::ALL {
+            assert!(subjects(DocumentGranularity::Single, &category, &structure()).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_per_module_uses_public_modules_once() {
+        let structure = structure();
+        let reference = subjects(
+            DocumentGranularity::PerModule,
+            &DiátaxisCategory::Reference,
+            &structure,
+        );
+        assert_eq!(names(&reference), ["parser"]);
+        assert_eq!(reference[0].structure.modules.len(), 1);
+        assert_eq!(reference[0].structure.functions.len(), 2);
+        assert_eq!(reference[0].task(), "Use the parser module");
+    }
+
+    #[test]
+    fn test_per_item_narrows_structure() {
+        let structure = structure();
+        let reference = subjects(
+            DocumentGranularity::PerItem,
+            &DiátaxisCategory::Reference,
+            &structure,
+        );
+        assert_eq!(names(&reference), ["parse", "Config"]);
+        assert_eq!(reference[1].describe(), "the `Config` type");
+        assert!(reference[1].structure.functions.is_empty());
+
+        let howto = subjects(
+            DocumentGranularity::PerItem,
+            &DiátaxisCategory::HowTo,
+            &structure,
+        );
+        assert_eq!(names(&howto), ["parse"]);
+        assert!(subjects(
+            DocumentGranularity::PerItem,
+            &DiátaxisCategory::Explanation,
+            &structure
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_slug_registry_renames_repeats() {
+        let mut slugs = SlugRegistry::new();
+        let path =
+            Path::new("docs/how_to").join(format!("{}.md", slugify("How to: Use parse-url")));
+        assert_eq!(path, PathBuf::from("docs/how_to/how_to_use_parse_url.md"));
+
+        assert_eq!(slugs.claim(&path), path);
+        assert_eq!(
+            slugs.claim(&path),
+            PathBuf::from("docs/how_to/how_to_use_parse_url_2.md")
+        );
+        assert_eq!(
+            slugs.claim(&path),
+            PathBuf::from("docs/how_to/how_to_use_parse_url_3.md")
+        );
+    }
+
+    #[test]
+    fn test_parse_granularity() {
+        assert_eq!("per-module".parse(), Ok(DocumentGranularity::PerModule));
+        assert_eq!(DocumentGranularity::PerItem.to_string(), "per_item");
+        assert!("per_file".parse::<DocumentGranularity>().is_err());
+        let json = serde_json::to_string(&DocumentGranularity::PerModule).unwrap();
+        assert_eq!(json, "\"per_module\"");
+    }
+}
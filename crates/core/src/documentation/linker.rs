@@ -0,0 +1,777 @@
+//! Inline links between generated documents
+//!
+//! [`link_documents`] rewrites mentions in each document into relative
+//! Markdown links to the document they refer to:
+//!
+//! - the title of another generated document links to that document
+//! - the name of a type or function from the [`CodeStructure`] links to the
+//!   reference document covering it: the page generated for it as a
+//!   subject, or a reference page with a heading naming it, at that
+//!   heading's anchor
+//!
+//! Only the first mention of each target in a document is linked. Names
+//! that are plain lowercase words, such as `parse`, are only linked when
+//! written as inline code; names with an uppercase letter or underscore are
+//! distinctive enough to link in prose too.
+//!
+//! A name with several possible targets, or defined more than once in the
+//! code, is not guessed at: it links to a disambiguation anchor in a
+//! section appended to the document, which lists every candidate.
+//!
+//! Fenced and indented code blocks, headings, existing links, inline HTML
+//! and front matter are left untouched.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use xze_core::documentation::{linker::link_documents, Document};
+//! use xze_core::repository::CodeStructure;
+//! use xze_core::types::DiátaxisCategory;
+//!
+//! let mut documents = vec![
+//!     Document::new(
+//!         DiátaxisCategory::Tutorial,
+//!         "Getting Started".to_string(),
+//!         "# Getting Started\n\nSee Deploying for production.\n".to_string(),
+//!         PathBuf::from("tutorials/getting_started.md"),
+//!     ),
+//!     Document::new(
+//!         DiátaxisCategory::HowTo,
+//!         "Deploying".to_string(),
+//!         "# Deploying\n".to_string(),
+//!         PathBuf::from("how_to/deploying.md"),
+//!     ),
+//! ];
+//!
+//! link_documents(&mut documents, &CodeStructure::new());
+//! assert!(documents[0]
+//!     .content
+//!     .contains("See [Deploying](../how_to/deploying.md) for production."));
+//! ```
+
+use crate::{
+    documentation::{generator::Document, render::heading_id},
+    repository::CodeStructure,
+    types::DiátaxisCategory,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Heading of the section listing the candidates of ambiguous mentions
+const DISAMBIGUATION_HEADING: &str = "## Disambiguation";
+
+/// Something a mention can refer to
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    /// Index of the document in the generated set
+    document: usize,
+    /// Heading anchor within the document
+    anchor: Option<String>,
+}
+
+/// A title or code name that documents may mention
+#[derive(Debug, Clone)]
+struct Term {
+    text: String,
+    /// Whether mentions outside inline code are linked
+    in_prose: bool,
+    /// Whether the term is a code name rather than a title
+    code: bool,
+    targets: Vec<Target>,
+    /// Definitions of a code name, when there are several
+    definitions: Vec<String>,
+}
+
+/// How a document refers to a term
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Link {
+    Direct(String),
+    Ambiguous(String),
+}
+
+/// Link mentions of document titles and code names across `documents`
+///
+/// Documents that gain links have their `updated_at` timestamp refreshed.
+pub fn link_documents(documents: &mut [Document], structure: &CodeStructure) {
+    let terms = collect_terms(documents, structure);
+    if terms.is_empty() {
+        return;
+    }
+
+    let paths: Vec<_> = documents.iter().map(|d| d.file_path.clone()).collect();
+    let titles: Vec<_> = documents
+        .iter()
+        .map(|d| (d.title.clone(), d.category.clone()))
+        .collect();
+
+    for (index, document) in documents.iter_mut().enumerate() {
+        let mut linker = DocumentLinker {
+            index,
+            terms: &terms,
+            paths: &paths,
+            linked: HashSet::new(),
+            ambiguous: Vec::new(),
+        };
+        let content = linker.link(&document.content);
+        if content == document.content {
+            continue;
+        }
+
+        document.content = content;
+        if !linker.ambiguous.is_empty() {
+            let section = disambiguation_section(&linker, &titles);
+            document.content = format!("{}\n\n{}", document.content.trim_end(), section);
+        }
+        document.metadata.touch();
+    }
+}
+
+/// Titles and code names with at least one target, longest first
+fn collect_terms(documents: &[Document], structure: &CodeStructure) -> Vec<Term> {
+    let mut titles: BTreeMap<&str, Vec<Target>> = BTreeMap::new();
+    for (index, document) in documents.iter().enumerate() {
+        if !document.title.trim().is_empty() {
+            titles.entry(&document.title).or_default().push(Target {
+                document: index,
+                anchor: None,
+            });
+        }
+    }
+    let mut terms: Vec<Term> = titles
+        .into_iter()
+        .map(|(title, targets)| Term {
+            text: title.to_string(),
+            in_prose: true,
+            code: false,
+            targets,
+            definitions: Vec::new(),
+        })
+        .collect();
+
+    let mut definitions: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for ty in &structure.types {
+        definitions
+            .entry(&ty.name)
+            .or_default()
+            .insert(format!("{:?} {}", ty.kind, ty.name).to_lowercase());
+    }
+    for function in &structure.functions {
+        definitions
+            .entry(&function.name)
+            .or_default()
+            .insert(function.signature.clone());
+    }
+
+    let headings: Vec<Vec<(String, String)>> = documents
+        .iter()
+        .map(|document| {
+            if document.category == DiátaxisCategory::Reference {
+                headings(&document.content)
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+
+    for (name, defined) in definitions {
+        if name.chars().count() < 2 || terms.iter().any(|t| t.text == name) {
+            continue;
+        }
+        let targets = code_targets(name, documents, &headings);
+        if targets.is_empty() {
+            continue;
+        }
+        terms.push(Term {
+            text: name.to_string(),
+            in_prose: name.chars().any(|c| c.is_uppercase() || c == '_'),
+            code: true,
+            targets,
+            definitions: if defined.len() > 1 {
+                defined.into_iter().collect()
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    terms.sort_by(|a, b| b.text.len().cmp(&a.text.len()).then(a.text.cmp(&b.text)));
+    terms
+}
+
+/// Reference documents covering `name`, one target per document
+fn code_targets(
+    name: &str,
+    documents: &[Document],
+    headings: &[Vec<(String, String)>],
+) -> Vec<Target> {
+    let mut targets = Vec::new();
+    for (index, document) in documents.iter().enumerate() {
+        if document.category != DiátaxisCategory::Reference {
+            continue;
+        }
+        let anchor = headings[index]
+            .iter()
+            .find(|(text, _)| find_word(text, name).is_some())
+            .map(|(_, id)| id.clone());
+        let is_subject = document.metadata.custom.get("subject").map(String::as_str) == Some(name);
+        if anchor.is_some() || is_subject {
+            targets.push(Target {
+                document: index,
+                anchor,
+            });
+        }
+    }
+    targets
+}
+
+/// Text and anchor of each heading outside code blocks
+fn headings(content: &str) -> Vec<(String, String)> {
+    let mut used: BTreeMap<String, usize> = BTreeMap::new();
+    let mut headings = Vec::new();
+    let mut blocks = CodeBlocks::default();
+    for line in content.lines() {
+        if blocks.is_code(line) {
+            continue;
+        }
+        let Some(text) = heading_text(line) else {
+            continue;
+        };
+        let id = heading_id(&text);
+        let count = used.entry(id.clone()).or_insert(0);
+        let id = if *count == 0 {
+            id
+        } else {
+            format!("{}-{}", id, count)
+        };
+        *count += 1;
+        headings.push((text, id));
+    }
+    headings
+}
+
+/// Text of an ATX heading line, without its markup
+fn heading_text(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 || line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some(
+        rest.trim()
+            .trim_end_matches('#')
+            .trim()
+            .replace(['`', '*'], ""),
+    )
+}
+
+/// Tracks fenced and indented code blocks across lines
+#[derive(Default)]
+struct CodeBlocks {
+    fence: Option<(char, usize)>,
+    previous_blank: bool,
+    indented: bool,
+}
+
+impl CodeBlocks {
+    /// Whether `line` belongs to a code block, fences included
+    fn is_code(&mut self, line: &str) -> bool {
+        let trimmed = line.trim_start();
+        let blank = trimmed.is_empty();
+
+        if let Some((marker, length)) = self.fence {
+            let closing = trimmed.chars().take_while(|&c| c == marker).count();
+            if closing >= length && trimmed[closing..].trim().is_empty() {
+                self.fence = None;
+            }
+            self.previous_blank = false;
+            return true;
+        }
+
+        for marker in ['`', '~'] {
+            let length = trimmed.chars().take_while(|&c| c == marker).count();
+            if length >= 3 {
+                self.fence = Some((marker, length));
+                self.indented = false;
+                self.previous_blank = false;
+                return true;
+            }
+        }
+
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        self.indented = !blank && indented && (self.indented || self.previous_blank);
+        self.previous_blank = blank;
+        self.indented
+    }
+}
+
+/// Rewrites the mentions in one document
+struct DocumentLinker<'a> {
+    index: usize,
+    terms: &'a [Term],
+    paths: &'a [PathBuf],
+    /// Terms already linked in this document
+    linked: HashSet<usize>,
+    /// Ambiguous terms linked to the disambiguation section, in order
+    ambiguous: Vec<usize>,
+}
+
+impl DocumentLinker<'_> {
+    fn link(&mut self, content: &str) -> String {
+        let (front_matter, body) = split_front_matter(content);
+        let mut output = String::with_capacity(content.len());
+        output.push_str(front_matter);
+
+        let mut blocks = CodeBlocks::default();
+        for line in body.split_inclusive('\n') {
+            if blocks.is_code(line) || heading_text(line).is_some() {
+                output.push_str(line);
+            } else {
+                output.push_str(&self.link_line(line));
+            }
+        }
+        output
+    }
+
+    fn link_line(&mut self, line: &str) -> String {
+        let mut output = String::with_capacity(line.len());
+        for segment in segments(line) {
+            match segment {
+                Segment::Text(text) => output.push_str(&self.link_text(text)),
+                Segment::Code { raw, code } => {
+                    let name = code.trim().trim_end_matches("()");
+                    let term = self
+                        .terms
+                        .iter()
+                        .position(|t| t.code && t.text == name)
+                        .filter(|&t| !self.linked.contains(&t));
+                    match term.and_then(|t| self.resolve(t)) {
+                        Some(href) => output.push_str(&format!("[{}]({})", raw, href)),
+                        None => output.push_str(raw),
+                    }
+                }
+                Segment::Opaque(raw) => output.push_str(raw),
+            }
+        }
+        output
+    }
+
+    /// Link the first unlinked mention of each term in plain text
+    fn link_text(&mut self, text: &str) -> String {
+        // Pieces already turned into links are not searched again
+        let mut pieces: Vec<(String, bool)> = vec![(text.to_string(), false)];
+        for (term_index, term) in self.terms.iter().enumerate() {
+            if !term.in_prose || self.linked.contains(&term_index) {
+                continue;
+            }
+            let found = pieces.iter().enumerate().find_map(|(i, (piece, linked))| {
+                if *linked {
+                    return None;
+                }
+                find_word(piece, &term.text).map(|start| (i, start))
+            });
+            let Some((i, start)) = found else {
+                continue;
+            };
+            let Some(href) = self.resolve(term_index) else {
+                continue;
+            };
+
+            let piece = pieces.remove(i).0;
+            let end = start + term.text.len();
+            let link = format!("[{}]({})", &piece[start..end], href);
+            pieces.insert(i, (piece[end..].to_string(), false));
+            pieces.insert(i, (link, true));
+            pieces.insert(i, (piece[..start].to_string(), false));
+        }
+        pieces.into_iter().map(|(piece, _)| piece).collect()
+    }
+
+    /// Link destination for a term, marking it linked
+    ///
+    /// Returns `None` when the term only refers to this document.
+    fn resolve(&mut self, term_index: usize) -> Option<String> {
+        let href = match self.link_for(&self.terms[term_index])? {
+            Link::Direct(href) => href,
+            Link::Ambiguous(href) => {
+                self.ambiguous.push(term_index);
+                href
+            }
+        };
+        self.linked.insert(term_index);
+        Some(href)
+    }
+
+    fn link_for(&self, term: &Term) -> Option<Link> {
+        let targets: Vec<&Target> = term
+            .targets
+            .iter()
+            .filter(|t| t.document != self.index)
+            .collect();
+        match targets.as_slice() {
+            [] => None,
+            [target] if term.definitions.is_empty() => Some(Link::Direct(self.href(target))),
+            _ => Some(Link::Ambiguous(format!(
+                "#{}",
+                disambiguation_anchor(&term.text)
+            ))),
+        }
+    }
+
+    fn href(&self, target: &Target) -> String {
+        let from = &self.paths[self.index];
+        let to = &self.paths[target.document];
+        let mut href = relative_path(from, to);
+        if let Some(anchor) = &target.anchor {
+            href.push('#');
+            href.push_str(anchor);
+        }
+        href
+    }
+}
+
+/// Section listing every candidate of the ambiguous mentions
+fn disambiguation_section(
+    linker: &DocumentLinker<'_>,
+    titles: &[(String, DiátaxisCategory)],
+) -> String {
+    let mut section = format!("{}\n", DISAMBIGUATION_HEADING);
+    for &term_index in &linker.ambiguous {
+        let term = &linker.terms[term_index];
+        let name = if term.code {
+            format!("`{}`", term.text)
+        } else {
+            format!("**{}**", term.text)
+        };
+        section.push_str(&format!(
+            "\n<a id=\"{}\"></a>\n{} may refer to:\n\n",
+            disambiguation_anchor(&term.text),
+            name
+        ));
+        for target in term.targets.iter().filter(|t| t.document != linker.index) {
+            let (title, category) = &titles[target.document];
+            section.push_str(&format!(
+                "- [{} ({})]({})\n",
+                title,
+                category,
+                linker.href(target)
+            ));
+        }
+        for definition in &term.definitions {
+            section.push_str(&format!("- `{}`\n", definition));
+        }
+    }
+    section
+}
+
+fn disambiguation_anchor(text: &str) -> String {
+    format!("disambiguation-{}", heading_id(text))
+}
+
+/// A leading `---` front-matter block, and the rest of the content
+fn split_front_matter(content: &str) -> (&str, &str) {
+    if let Some(rest) = content.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return content.split_at(4 + end + 5);
+        }
+    }
+    ("", content)
+}
+
+/// Part of a line of Markdown
+#[derive(Debug, PartialEq, Eq)]
+enum Segment<'a> {
+    /// Plain text that mentions may be found in
+    Text(&'a str),
+    /// An inline code span, with and without its backticks
+    Code { raw: &'a str, code: &'a str },
+    /// A link, image or inline HTML, copied as is
+    Opaque(&'a str),
+}
+
+/// Split a line into text, inline code and opaque segments
+fn segments(line: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+    let bytes = line.as_bytes();
+
+    while i < bytes.len() {
+        let special = match bytes[i] {
+            b'`' => {
+                let ticks = line[i..].bytes().take_while(|&b| b == b'`').count();
+                let fence = &line[i..i + ticks];
+                line[i + ticks..].find(fence).map(|offset| {
+                    let end = i + ticks + offset + ticks;
+                    let segment = Segment::Code {
+                        raw: &line[i..end],
+                        code: &line[i + ticks..end - ticks],
+                    };
+                    (segment, end)
+                })
+            }
+            b'[' | b'!' => link_end(line, i).map(|end| (Segment::Opaque(&line[i..end]), end)),
+            b'<' => line[i..]
+                .find('>')
+                .map(|offset| (Segment::Opaque(&line[i..i + offset + 1]), i + offset + 1)),
+            _ => None,
+        };
+
+        match special {
+            Some((segment, end)) => {
+                if text_start < i {
+                    segments.push(Segment::Text(&line[text_start..i]));
+                }
+                segments.push(segment);
+                i = end;
+                text_start = end;
+            }
+            None => i += 1,
+        }
+    }
+    if text_start < line.len() {
+        segments.push(Segment::Text(&line[text_start..]));
+    }
+    segments
+}
+
+/// End of a `[text](url)` link or `![alt](url)` image starting at `start`
+fn link_end(line: &str, start: usize) -> Option<usize> {
+    let open = if line[start..].starts_with("![") {
+        start + 1
+    } else if line[start..].starts_with('[') {
+        start
+    } else {
+        return None;
+    };
+    let close = open + line[open..].find("](")?;
+    let end = close + line[close..].find(')')?;
+    Some(end + 1)
+}
+
+/// Byte offset of the first whole-word occurrence of `word` in `text`
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut from = 0;
+    while let Some(offset) = text[from..].find(word) {
+        let start = from + offset;
+        let end = start + word.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        if !before.is_some_and(is_word) && !after.is_some_and(is_word) {
+            return Some(start);
+        }
+        from = start + text[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+/// Relative link from the document at `from` to the document at `to`
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from_dir: Vec<Component> = from
+        .parent()
+        .map(|p| p.components().collect())
+        .unwrap_or_default();
+    let to_parts: Vec<Component> = to.components().collect();
+    let common = from_dir
+        .iter()
+        .zip(&to_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from_dir.len() - common];
+    parts.extend(
+        to_parts[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, TypeDefinition, TypeKind, Visibility};
+
+    fn document(category: DiátaxisCategory, title: &str, content: &str, path: &str) -> Document {
+        Document::new(
+            category,
+            title.to_string(),
+            content.to_string(),
+            PathBuf::from(path),
+        )
+    }
+
+    fn function(name: &str, signature: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: signature.to_string(),
+            documentation: None,
+            parameters: Vec::new(),
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    fn structure() -> CodeStructure {
+        CodeStructure {
+            types: vec![TypeDefinition {
+                name: "ParserConfig".to_string(),
+                kind: TypeKind::Struct,
+                documentation: None,
+                fields: Vec::new(),
+                visibility: Visibility::Public,
+                methods: Vec::new(),
+            }],
+            functions: vec![
+                function("parse", "fn parse(input: &str) -> Document"),
+                function("new", "fn new() -> Parser"),
+                function("new", "fn new() -> ParserConfig"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn documents() -> Vec<Document> {
+        vec![
+            document(
+                DiátaxisCategory::Reference,
+                "API Reference",
+                "# API Reference\n\n## `ParserConfig`\n\n## parse\n\n## new\n\n### new\n",
+                "reference/api_reference.md",
+            ),
+            document(
+                DiátaxisCategory::Tutorial,
+                "Getting Started",
+                concat!(
+                    "# Getting Started\n\n",
+                    "Read the API Reference first, then the API Reference again.\n\n",
+                    "Call `parse()` with a ParserConfig, then `new`.\n\n",
+                    "```rust\nlet config = ParserConfig::default(); // see API Reference\n```\n\n",
+                    "    ParserConfig in an indented block\n\n",
+                    "Already [linked API Reference](elsewhere.md) and <a title=\"API Reference\">.\n",
+                ),
+                "tutorials/getting_started.md",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_title_mention_becomes_link() {
+        let mut documents = documents();
+        link_documents(&mut documents, &CodeStructure::new());
+
+        let content = &documents[1].content;
+        assert!(
+            content.contains(
+                "Read the [API Reference](../reference/api_reference.md) first, then the API Reference again."
+            ),
+            "{}",
+            content
+        );
+        // Headings, code blocks, links and HTML are left alone
+        assert!(content.starts_with("# Getting Started\n"));
+        assert!(content.contains("// see API Reference\n"));
+        assert!(content.contains("[linked API Reference](elsewhere.md)"));
+        assert!(content.contains("<a title=\"API Reference\">"));
+        // A document does not link to itself
+        assert!(!documents[0].content.contains("]("));
+    }
+
+    #[test]
+    fn test_code_names_link_to_reference_headings() {
+        let mut documents = documents();
+        link_documents(&mut documents, &structure());
+
+        let content = &documents[1].content;
+        assert!(content.contains(
+            "Call [`parse()`](../reference/api_reference.md#parse) with a [ParserConfig](../reference/api_reference.md#parserconfig)"
+        ), "{}", content);
+        assert!(content.contains("let config = ParserConfig::default();"));
+        assert!(content.contains("    ParserConfig in an indented block\n"));
+    }
+
+    #[test]
+    fn test_ambiguous_names_link_to_disambiguation() {
+        let mut documents = documents();
+        link_documents(&mut documents, &structure());
+
+        let content = &documents[1].content;
+        assert!(
+            content.contains("then [`new`](#disambiguation-new)."),
+            "{}",
+            content
+        );
+        assert!(content.contains(concat!(
+            "## Disambiguation\n\n",
+            "<a id=\"disambiguation-new\"></a>\n",
+            "`new` may refer to:\n\n",
+            "- [API Reference (Reference)](../reference/api_reference.md#new)\n",
+            "- `fn new() -> Parser`\n",
+            "- `fn new() -> ParserConfig`\n",
+        )));
+    }
+
+    #[test]
+    fn test_lowercase_names_only_link_in_code() {
+        let mut documents = vec![
+            document(
+                DiátaxisCategory::Reference,
+                "API",
+                "# API\n\n## parse\n",
+                "reference/api.md",
+            ),
+            document(
+                DiátaxisCategory::HowTo,
+                "Parsing",
+                "To parse a file, call `parse`.\n",
+                "how_to/parsing.md",
+            ),
+        ];
+        link_documents(&mut documents, &structure());
+        assert_eq!(
+            documents[1].content,
+            "To parse a file, call [`parse`](../reference/api.md#parse).\n"
+        );
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path(Path::new("tutorials/a.md"), Path::new("how_to/b.md")),
+            "../how_to/b.md"
+        );
+        assert_eq!(
+            relative_path(Path::new("reference/a.md"), Path::new("reference/b.md")),
+            "b.md"
+        );
+        assert_eq!(
+            relative_path(Path::new("a.md"), Path::new("x/b.md")),
+            "x/b.md"
+        );
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            segments("a `b` [c](d) <e> f"),
+            vec![
+                Segment::Text("a "),
+                Segment::Code {
+                    raw: "`b`",
+                    code: "b"
+                },
+                Segment::Text(" "),
+                Segment::Opaque("[c](d)"),
+                Segment::Text(" "),
+                Segment::Opaque("<e>"),
+                Segment::Text(" f"),
+            ]
+        );
+    }
+}
@@ -1,27 +1,57 @@
 //! Documentation generation and management
 
 use crate::{
-    ai::AIAnalysisService, error::Result, repository::Repository, types::DiátaxisCategory,
+    ai::AIAnalysisService,
+    config::{GenerationConfig, XzeConfig},
+    error::{Result, XzeError},
+    repository::{ChangeAnalysis, CodeStructure, Repository},
+    types::DiátaxisCategory,
 };
 use async_trait::async_trait;
+use granularity::{DocumentSubject, SlugRegistry};
 use serde::{Deserialize, Serialize};
 use std::{path::Path, sync::Arc};
 
+pub mod badge;
 pub mod crossref;
+pub mod diagram;
+pub mod front_matter;
 pub mod generator;
+pub mod granularity;
 pub mod index;
+pub mod linker;
+pub mod patch;
 pub mod processor;
+pub mod render;
+pub mod secrets;
+pub mod sink;
+pub mod site;
+pub mod summary;
 pub mod validator;
+pub mod versions;
 
+pub use badge::{BadgePlacement, ConfidenceBadgeConfig};
 pub use crossref::{CrossReference, CrossReferenceGenerator, LinkStrategy, Relationship};
+pub use diagram::DiagramConfig;
+pub use front_matter::{FrontMatterConfig, FrontMatterPreset};
 pub use generator::{
     AIDocumentationGenerator, Document, DocumentMetadata, DocumentationGenerator, GeneratorConfig,
 };
+pub use granularity::DocumentGranularity;
 pub use index::IndexGenerator;
+pub use patch::{PatchConfig, PatchPlan};
 pub use processor::{DocumentProcessor, ProcessingConfig, ProcessingError, ProcessingResult};
+pub use render::OutputFormat;
+pub use secrets::{SecretAction, SecretFinding, SecretScanConfig, SecretScanner};
+pub use sink::{
+    build_sink, write_documents, FileSystemSink, KbSink, OutputConfig, OutputSink, OutputSinkKind,
+    S3SinkConfig, StdoutSink,
+};
+pub use site::{SiteConfig, SiteExporter, SiteFormat};
 pub use validator::{
     DiátaxisValidator, DocumentationValidator, ValidationResult, ValidatorConfig
 };
+pub use versions::{DocumentDiff, DocumentVersionStore};
 
 /// Documentation analysis result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +86,176 @@ impl Default for DocumentationAnalysis {
     }
 }
 
+/// A Diátaxis category that failed to generate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationFailure {
+    /// Category that failed
+    pub category: DiátaxisCategory,
+    /// Module or item the failed document covered, when the category is
+    /// split into several documents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Why generation failed
+    pub error: String,
+}
+
+/// Overall outcome of a generation run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GenerationStatus {
+    /// Every requested category was generated
+    Complete,
+    /// Some categories were generated and some failed
+    Partial,
+    /// No category was generated
+    Failed,
+}
+
+impl GenerationStatus {
+    /// Process exit code for the CLI: `0` complete, `2` partial, `1` failed
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Complete => 0,
+            Self::Partial => 2,
+            Self::Failed => 1,
+        }
+    }
+
+    /// HTTP status for the API: `200` complete, `207` partial, `502` failed
+    pub fn http_status(self) -> u16 {
+        match self {
+            Self::Complete => 200,
+            Self::Partial => 207,
+            Self::Failed => 502,
+        }
+    }
+
+    /// Outcome of two runs taken together, such as runs over several
+    /// repositories: partial unless both runs had the same outcome
+    pub fn combine(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Self::Partial
+        }
+    }
+}
+
+/// Documents generated for a set of categories, plus the categories that
+/// failed
+///
+/// Categories are generated independently, so one failure does not discard
+/// the others. Callers decide whether a partial result is acceptable; use
+/// [`GenerationReport::into_complete`] to treat any failure as an error.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Successfully generated documents, in generation order
+    pub documents: Vec<Document>,
+    /// Categories that failed, in generation order
+    pub failures: Vec<GenerationFailure>,
+}
+
+impl GenerationReport {
+    /// Overall outcome of the run
+    pub fn status(&self) -> GenerationStatus {
+        match (self.documents.is_empty(), self.failures.is_empty()) {
+            (_, true) => GenerationStatus::Complete,
+            (false, false) => GenerationStatus::Partial,
+            (true, false) => GenerationStatus::Failed,
+        }
+    }
+
+    /// Add a generated document, or the reason it failed
+    fn record(
+        &mut self,
+        category: &DiátaxisCategory,
+        subject: Option<&str>,
+        result: Result<Document>,
+        slugs: &mut SlugRegistry,
+    ) {
+        match result {
+            Ok(mut document) => {
+                document.file_path = slugs.claim(&document.file_path);
+                self.documents.push(document);
+            }
+            Err(e) => {
+                match subject {
+                    Some(subject) => tracing::warn!(
+                        "Failed to generate {} documentation for {}: {}",
+                        category,
+                        subject,
+                        e
+                    ),
+                    None => tracing::warn!("Failed to generate {} documentation: {}", category, e),
+                }
+                self.failures.push(GenerationFailure {
+                    category: category.clone(),
+                    subject: subject.map(str::to_string),
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Whether every requested category was generated
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The generated documents, or an error naming every failed category
+    pub fn into_complete(self) -> Result<Vec<Document>> {
+        if self.failures.is_empty() {
+            return Ok(self.documents);
+        }
+
+        let failures = self
+            .failures
+            .iter()
+            .map(|f| match &f.subject {
+                Some(subject) => format!("{} ({}): {}", f.category, subject, f.error),
+                None => format!("{}: {}", f.category, f.error),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(XzeError::pipeline(format!(
+            "Documentation generation failed for {} of {} categories: {}",
+            self.failures.len(),
+            self.failures.len() + self.documents.len(),
+            failures
+        )))
+    }
+}
+
+/// Outcome of regenerating the documents affected by a change
+///
+/// Produced by [`DocumentationService::update_changed`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeUpdateReport {
+    /// Categories the change affected, in generation order
+    pub affected: Vec<DiátaxisCategory>,
+    /// Regeneration of the affected categories
+    pub regenerated: GenerationReport,
+    /// Existing documents left as they were: those of unaffected
+    /// categories, and those of affected categories that failed to
+    /// regenerate
+    pub kept: Vec<Document>,
+}
+
+impl ChangeUpdateReport {
+    /// Whether the change required no regeneration
+    pub fn is_unchanged(&self) -> bool {
+        self.affected.is_empty()
+    }
+
+    /// The full document set after the update: the kept documents followed
+    /// by the regenerated ones
+    pub fn into_documents(self) -> Vec<Document> {
+        let mut documents = self.kept;
+        documents.extend(self.regenerated.documents);
+        documents
+    }
+}
+
 /// Trait for documentation generation
 #[async_trait]
 pub trait DocGenerator: Send + Sync {
@@ -70,6 +270,9 @@ pub struct DocumentationService {
     ai_service: Arc<AIAnalysisService>,
     generator: Box<dyn DocumentationGenerator>,
     validator: Box<dyn DocumentationValidator>,
+    secret_scanner: SecretScanner,
+    patch_config: PatchConfig,
+    granularity: DocumentGranularity,
 }
 
 impl DocumentationService {
@@ -82,6 +285,9 @@ impl DocumentationService {
             ai_service,
             generator,
             validator,
+            secret_scanner: SecretScanner::default(),
+            patch_config: PatchConfig::default(),
+            granularity: DocumentGranularity::default(),
         }
     }
 
@@ -94,36 +300,256 @@ impl DocumentationService {
             ai_service: ai_service.clone(),
             generator: Box::new(AIDocumentationGenerator::new(ai_service, generator_config)),
             validator: Box::new(DiátaxisValidator::new(validator_config)),
+            secret_scanner: SecretScanner::default(),
+            patch_config: PatchConfig::default(),
+            granularity: DocumentGranularity::default(),
         }
     }
 
+    /// Create a documentation service from configuration
+    ///
+    /// Talks to the configured Ollama server and applies the generation
+    /// settings; see [`with_generation_config`](Self::with_generation_config).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a secret scan pattern is invalid
+    pub fn from_config(config: &XzeConfig) -> Result<Self> {
+        let ai_service = Arc::new(AIAnalysisService::from_config(&config.ollama));
+        Self::with_defaults(ai_service).with_generation_config(&config.generation)
+    }
+
+    /// Apply the secret scan and granularity generation settings
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a secret scan pattern is invalid
+    pub fn with_generation_config(self, config: &GenerationConfig) -> Result<Self> {
+        let secret_scanner = SecretScanner::new(&config.secret_scan)?;
+        Ok(self
+            .with_secret_scanner(secret_scanner)
+            .with_granularity(config.granularity))
+    }
+
+    /// Set the scanner run over every generated document
+    pub fn with_secret_scanner(mut self, secret_scanner: SecretScanner) -> Self {
+        self.secret_scanner = secret_scanner;
+        self
+    }
+
+    /// Set the limits for section-level updates
+    pub fn with_patch_config(mut self, patch_config: PatchConfig) -> Self {
+        self.patch_config = patch_config;
+        self
+    }
+
+    /// Set how many documents each category produces
+    pub fn with_granularity(mut self, granularity: DocumentGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
     /// Generate all documentation for a repository
-    pub async fn generate_all(&self, repo: &Repository) -> Result<Vec<Document>> {
-        let mut documents = Vec::new();
+    ///
+    /// See [`generate_selected`](Self::generate_selected) for how failures
+    /// are reported.
+    pub async fn generate_all(&self, repo: &Repository) -> Result<GenerationReport> {
+        self.generate_selected(repo, &DiátaxisCategory::ALL).await
+    }
 
-        // Generate reference documentation
-        let reference_doc = self.generator.generate_reference(repo).await?;
-        documents.push(reference_doc);
+    /// Generate documentation for the selected Diátaxis categories
+    ///
+    /// Categories are generated in the order given. An empty selection
+    /// falls back to every category so callers that forward an unset
+    /// option keep the previous behavior.
+    ///
+    /// A category whose generation fails, or whose document is blocked by
+    /// the secret scanner, is recorded in [`GenerationReport::failures`] and
+    /// the remaining categories are still generated.
+    ///
+    /// With a [`DocumentGranularity`] other than `single`, reference and
+    /// how-to documentation is split into one document per subject (see
+    /// [`granularity`]), and each subject succeeds or fails on its own.
+    /// Document paths are made unique across the run.
+    ///
+    /// When the generator enables cross-referencing, mentions of other
+    /// documents and of code items are then turned into links (see
+    /// [`linker`]).
+    pub async fn generate_selected(
+        &self,
+        repo: &Repository,
+        categories: &[DiátaxisCategory],
+    ) -> Result<GenerationReport> {
+        let categories = if categories.is_empty() {
+            &DiátaxisCategory::ALL[..]
+        } else {
+            categories
+        };
 
-        // Generate how-to guides (example task)
-        let howto_doc = self.generator.generate_howto(repo, "setup").await?;
-        documents.push(howto_doc);
+        let mut report = GenerationReport::default();
+        let mut slugs = SlugRegistry::new();
+        for category in categories {
+            let subjects = granularity::subjects(self.granularity, category, &repo.structure);
+            if subjects.is_empty() {
+                let result = self.generate_category(repo, category).await;
+                report.record(category, None, result, &mut slugs);
+                continue;
+            }
+            for subject in &subjects {
+                let result = self.generate_subject(repo, category, subject).await;
+                report.record(category, Some(&subject.name), result, &mut slugs);
+            }
+        }
 
-        // Generate tutorials (example topic)
-        let tutorial_doc = self
-            .generator
-            .generate_tutorial(repo, "getting started")
-            .await?;
-        documents.push(tutorial_doc);
+        if self.generator.cross_reference() {
+            linker::link_documents(&mut report.documents, &repo.structure);
+        }
+
+        Ok(report)
+    }
+
+    /// Generate and scan the document for one category
+    async fn generate_category(
+        &self,
+        repo: &Repository,
+        category: &DiátaxisCategory,
+    ) -> Result<Document> {
+        let mut document = match category {
+            DiátaxisCategory::Reference => self.generator.generate_reference(repo).await?,
+            // How-to guides (example task)
+            DiátaxisCategory::HowTo => self.generator.generate_howto(repo, "setup").await?,
+            // Tutorials (example topic)
+            DiátaxisCategory::Tutorial => {
+                self.generator
+                    .generate_tutorial(repo, "getting started")
+                    .await?
+            }
+            // Explanations (example concept)
+            DiátaxisCategory::Explanation => {
+                self.generator
+                    .generate_explanation(repo, "architecture")
+                    .await?
+            }
+        };
+
+        // Catch credentials echoed from the analyzed source before any
+        // document is written or committed
+        self.secret_scanner.apply(&mut document)?;
+
+        Ok(document)
+    }
+
+    /// Generate and scan the document for one subject of a split category
+    async fn generate_subject(
+        &self,
+        repo: &Repository,
+        category: &DiátaxisCategory,
+        subject: &DocumentSubject,
+    ) -> Result<Document> {
+        let mut document = match category {
+            DiátaxisCategory::Reference => {
+                self.generator.generate_reference_for(repo, subject).await?
+            }
+            _ => {
+                let mut focused = repo.clone();
+                focused.structure = subject.structure.clone();
+                self.generator
+                    .generate_howto(&focused, &subject.task())
+                    .await?
+            }
+        };
+        self.secret_scanner.apply(&mut document)?;
+
+        Ok(document)
+    }
+
+    /// Generate the selected categories and deliver them to `sink`
+    ///
+    /// The documents that generated successfully are written in generation
+    /// order; see [`sink`] for the delivery contract. Returns the report.
+    pub async fn generate_to_sink(
+        &self,
+        repo: &Repository,
+        categories: &[DiátaxisCategory],
+        sink: &dyn OutputSink,
+    ) -> Result<GenerationReport> {
+        let report = self.generate_selected(repo, categories).await?;
+        write_documents(sink, &report.documents).await?;
+        Ok(report)
+    }
 
-        // Generate explanations (example concept)
-        let explanation_doc = self
-            .generator
-            .generate_explanation(repo, "architecture")
+    /// Regenerate only the documents a change affects
+    ///
+    /// The categories to regenerate come from
+    /// [`ChangeAnalysis::affected_categories`]; for example, an API change
+    /// regenerates the reference and how-to guides. `existing` documents of
+    /// other categories are kept without calling the model. A
+    /// [`RepositoryChanges`](crate::change_detector::RepositoryChanges)
+    /// converts into a [`ChangeAnalysis`] with `From`.
+    ///
+    /// If an affected category fails to regenerate, its existing documents
+    /// are kept and the failure is recorded in the report.
+    pub async fn update_changed(
+        &self,
+        repo: &Repository,
+        changes: &ChangeAnalysis,
+        existing: Vec<Document>,
+    ) -> Result<ChangeUpdateReport> {
+        let affected = changes.affected_categories();
+        if affected.is_empty() {
+            tracing::info!(
+                "No documentation affected by {} changed files",
+                changes.changed_files.len()
+            );
+            return Ok(ChangeUpdateReport {
+                affected,
+                regenerated: GenerationReport::default(),
+                kept: existing,
+            });
+        }
+
+        let regenerated = self.generate_selected(repo, &affected).await?;
+        let kept = existing
+            .into_iter()
+            .filter(|doc| {
+                !regenerated
+                    .documents
+                    .iter()
+                    .any(|new| new.category == doc.category)
+            })
+            .collect();
+        tracing::info!(
+            "Regenerated {} documents for {:?}",
+            regenerated.documents.len(),
+            affected
+        );
+
+        Ok(ChangeUpdateReport {
+            affected,
+            regenerated,
+            kept,
+        })
+    }
+
+    /// Generate a README-style overview of the repository
+    ///
+    /// Only the opening purpose statement comes from the model; the rest is
+    /// rendered from the analyzed structure (see [`summary`]). `documents`
+    /// are the generated Diátaxis documents to link from the overview.
+    pub async fn generate_summary(
+        &self,
+        repo: &Repository,
+        documents: &[Document],
+    ) -> Result<Document> {
+        let purpose = self
+            .ai_service
+            .generate_text(&summary::purpose_prompt(repo))
             .await?;
-        documents.push(explanation_doc);
 
-        Ok(documents)
+        let mut document = summary::render_summary(repo, &purpose, documents);
+        self.secret_scanner.apply(&mut document)?;
+
+        Ok(document)
     }
 
     /// Analyze existing documentation
@@ -156,17 +582,48 @@ impl DocumentationService {
         let mut updated_docs = Vec::new();
 
         for doc in existing_docs {
-            let updated_content = self
-                .ai_service
-                .generate_text(&format!(
-                    "Update this documentation:\n\n{}\n\nChanges:\n{}",
-                    doc.content, changes
-                ))
-                .await?;
-
-            let mut updated_doc = doc.clone();
-            updated_doc.content = updated_content;
-            updated_doc.metadata.updated_at = chrono::Utc::now();
+            updated_docs.push(self.update_document(&doc, changes).await?);
+        }
+
+        Ok(updated_docs)
+    }
+
+    /// Update existing documentation from the structural diff since `previous`
+    ///
+    /// Only the sections mentioning changed items are sent to the model and
+    /// rewritten; see [`patch`] for when a document is regenerated in full
+    /// instead. Documents are returned unchanged when the structure has not
+    /// changed.
+    pub async fn update_documentation_from_diff(
+        &self,
+        repo: &Repository,
+        existing_docs: Vec<Document>,
+        previous: &CodeStructure,
+    ) -> Result<Vec<Document>> {
+        let diff = previous.diff(&repo.structure);
+        if diff.is_empty() {
+            return Ok(existing_docs);
+        }
+
+        let changes = diff.to_prompt();
+        let mut updated_docs = Vec::new();
+
+        for doc in existing_docs {
+            let Some(plan) = PatchPlan::new(&doc, &diff, &self.patch_config) else {
+                updated_docs.push(self.update_document(&doc, &changes).await?);
+                continue;
+            };
+
+            let response = self.ai_service.generate_text(&plan.prompt()).await?;
+            let mut updated_doc = match plan.apply(&doc, &response) {
+                Ok(updated_doc) => updated_doc,
+                Err(e) => {
+                    tracing::warn!("Falling back to full update of {:?}: {}", doc.file_path, e);
+                    updated_docs.push(self.update_document(&doc, &changes).await?);
+                    continue;
+                }
+            };
+            self.secret_scanner.apply(&mut updated_doc)?;
 
             updated_docs.push(updated_doc);
         }
@@ -174,6 +631,25 @@ impl DocumentationService {
         Ok(updated_docs)
     }
 
+    /// Regenerate a whole document from its content and a description of changes
+    async fn update_document(&self, doc: &Document, changes: &str) -> Result<Document> {
+        let updated_content = self
+            .ai_service
+            .generate_text(&format!(
+                "Update this documentation:\n\n{}\n\nChanges:\n{}",
+                doc.content, changes
+            ))
+            .await?;
+
+        let mut updated_doc = doc.clone();
+        updated_doc.content = updated_content;
+        updated_doc.metadata.updated_at = chrono::Utc::now();
+        updated_doc.metadata.patched_sections.clear();
+        self.secret_scanner.apply(&mut updated_doc)?;
+
+        Ok(updated_doc)
+    }
+
     /// Validate documentation quality
     pub async fn validate_documentation(&self, document: &Document) -> Result<ValidationResult> {
         // Create a temporary path for validation
@@ -202,6 +678,350 @@ mod tests {
         assert_eq!(doc.title, "API Reference");
     }
 
+    struct StubGenerator;
+
+    #[async_trait]
+    impl DocumentationGenerator for StubGenerator {
+        async fn generate_reference(&self, _repo: &Repository) -> Result<Document> {
+            Ok(stub_document(DiátaxisCategory::Reference))
+        }
+
+        async fn generate_howto(&self, _repo: &Repository, _task: &str) -> Result<Document> {
+            Ok(stub_document(DiátaxisCategory::HowTo))
+        }
+
+        async fn generate_tutorial(&self, _repo: &Repository, _topic: &str) -> Result<Document> {
+            Ok(stub_document(DiátaxisCategory::Tutorial))
+        }
+
+        async fn generate_explanation(
+            &self,
+            _repo: &Repository,
+            _concept: &str,
+        ) -> Result<Document> {
+            Ok(stub_document(DiátaxisCategory::Explanation))
+        }
+    }
+
+    fn stub_document(category: DiátaxisCategory) -> Document {
+        Document::new(
+            category.clone(),
+            category.to_string(),
+            String::new(),
+            PathBuf::from("stub.md"),
+        )
+    }
+
+    fn stub_service() -> DocumentationService {
+        let ai_service = Arc::new(AIAnalysisService::new(
+            "http://localhost:11434".to_string(),
+            crate::config::ModelConfig::default(),
+        ));
+        DocumentationService::new(
+            ai_service,
+            Box::new(StubGenerator),
+            Box::new(DiátaxisValidator::new(ValidatorConfig::default())),
+        )
+    }
+
+    fn stub_repository() -> Repository {
+        Repository::new(
+            crate::types::RepositoryId::from("test-repo"),
+            "https://example.com/test-repo".to_string(),
+            PathBuf::from("/tmp/test-repo"),
+            crate::types::ProgrammingLanguage::Rust,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_generate_selected_only_requested_categories() {
+        let service = stub_service();
+        let documents = service
+            .generate_selected(
+                &stub_repository(),
+                &[DiátaxisCategory::Reference, DiátaxisCategory::HowTo],
+            )
+            .await
+            .unwrap()
+            .into_complete()
+            .unwrap();
+
+        let categories: Vec<DiátaxisCategory> =
+            documents.iter().map(|doc| doc.category.clone()).collect();
+        assert_eq!(
+            categories,
+            vec![DiátaxisCategory::Reference, DiátaxisCategory::HowTo]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_all_covers_every_category() {
+        let service = stub_service();
+        let report = service.generate_all(&stub_repository()).await.unwrap();
+        assert_eq!(report.status(), GenerationStatus::Complete);
+        assert_eq!(report.documents.len(), DiátaxisCategory::ALL.len());
+
+        let empty = service
+            .generate_selected(&stub_repository(), &[])
+            .await
+            .unwrap();
+        assert_eq!(empty.documents.len(), DiátaxisCategory::ALL.len());
+    }
+
+    /// Fails tutorials and delegates everything else to [`StubGenerator`]
+    struct FailingTutorialGenerator;
+
+    #[async_trait]
+    impl DocumentationGenerator for FailingTutorialGenerator {
+        async fn generate_reference(&self, repo: &Repository) -> Result<Document> {
+            StubGenerator.generate_reference(repo).await
+        }
+
+        async fn generate_howto(&self, repo: &Repository, task: &str) -> Result<Document> {
+            StubGenerator.generate_howto(repo, task).await
+        }
+
+        async fn generate_tutorial(&self, _repo: &Repository, _topic: &str) -> Result<Document> {
+            Err(XzeError::ai("model timed out"))
+        }
+
+        async fn generate_explanation(&self, repo: &Repository, concept: &str) -> Result<Document> {
+            StubGenerator.generate_explanation(repo, concept).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_all_keeps_documents_when_a_category_fails() {
+        let mut service = stub_service();
+        service.generator = Box::new(FailingTutorialGenerator);
+
+        let report = service.generate_all(&stub_repository()).await.unwrap();
+        assert_eq!(report.status(), GenerationStatus::Partial);
+        assert_eq!(report.documents.len(), DiátaxisCategory::ALL.len() - 1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].category, DiátaxisCategory::Tutorial);
+        assert!(report.failures[0].error.contains("model timed out"));
+
+        let err = report.into_complete().unwrap_err();
+        assert!(err.to_string().contains("1 of 4 categories"));
+
+        let report = service
+            .generate_selected(&stub_repository(), &[DiátaxisCategory::Tutorial])
+            .await
+            .unwrap();
+        assert_eq!(report.status(), GenerationStatus::Failed);
+        assert_eq!(report.status().exit_code(), 1);
+        assert_eq!(GenerationStatus::Partial.http_status(), 207);
+        assert_eq!(
+            GenerationStatus::Complete.combine(GenerationStatus::Failed),
+            GenerationStatus::Partial
+        );
+        assert_eq!(
+            GenerationStatus::Failed.combine(GenerationStatus::Failed),
+            GenerationStatus::Failed
+        );
+    }
+
+    /// Leaks an internal ticket token into its tutorial
+    struct LeakingGenerator;
+
+    #[async_trait]
+    impl DocumentationGenerator for LeakingGenerator {
+        async fn generate_reference(&self, repo: &Repository) -> Result<Document> {
+            StubGenerator.generate_reference(repo).await
+        }
+
+        async fn generate_howto(&self, repo: &Repository, task: &str) -> Result<Document> {
+            StubGenerator.generate_howto(repo, task).await
+        }
+
+        async fn generate_tutorial(&self, repo: &Repository, topic: &str) -> Result<Document> {
+            let mut document = StubGenerator.generate_tutorial(repo, topic).await?;
+            document.content = "Deploy with token ACME-4821-PROD.\n".to_string();
+            Ok(document)
+        }
+
+        async fn generate_explanation(&self, repo: &Repository, concept: &str) -> Result<Document> {
+            StubGenerator.generate_explanation(repo, concept).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generation_config_applies_custom_secret_patterns() {
+        let mut generation = GenerationConfig::default();
+        generation
+            .secret_scan
+            .patterns
+            .insert("acme_token".to_string(), r"ACME-\d{4}-[A-Z]+".to_string());
+
+        let mut service = stub_service().with_generation_config(&generation).unwrap();
+        service.generator = Box::new(LeakingGenerator);
+        let tutorial = [DiátaxisCategory::Tutorial];
+
+        let report = service
+            .generate_selected(&stub_repository(), &tutorial)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.documents[0].content,
+            "Deploy with token [REDACTED].\n"
+        );
+
+        generation.secret_scan.action = SecretAction::Fail;
+        service = service.with_generation_config(&generation).unwrap();
+        let report = service
+            .generate_selected(&stub_repository(), &tutorial)
+            .await
+            .unwrap();
+        assert_eq!(report.status(), GenerationStatus::Failed);
+        assert!(report.failures[0].error.contains("acme_token"));
+
+        generation
+            .secret_scan
+            .patterns
+            .insert("broken".to_string(), "(".to_string());
+        assert!(stub_service().with_generation_config(&generation).is_err());
+    }
+
+    /// Mentions the reference document from its tutorial
+    struct CrossReferencingGenerator;
+
+    #[async_trait]
+    impl DocumentationGenerator for CrossReferencingGenerator {
+        async fn generate_reference(&self, repo: &Repository) -> Result<Document> {
+            StubGenerator.generate_reference(repo).await
+        }
+
+        async fn generate_howto(&self, repo: &Repository, task: &str) -> Result<Document> {
+            StubGenerator.generate_howto(repo, task).await
+        }
+
+        async fn generate_tutorial(&self, repo: &Repository, topic: &str) -> Result<Document> {
+            let mut document = StubGenerator.generate_tutorial(repo, topic).await?;
+            document.content = "See the Reference for every option.\n".to_string();
+            Ok(document)
+        }
+
+        async fn generate_explanation(&self, repo: &Repository, concept: &str) -> Result<Document> {
+            StubGenerator.generate_explanation(repo, concept).await
+        }
+
+        fn cross_reference(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cross_reference_links_document_titles() {
+        let mut service = stub_service();
+        service.generator = Box::new(CrossReferencingGenerator);
+
+        let report = service.generate_all(&stub_repository()).await.unwrap();
+        let tutorial = report
+            .documents
+            .iter()
+            .find(|doc| doc.category == DiátaxisCategory::Tutorial)
+            .unwrap();
+        assert_eq!(
+            tutorial.content,
+            "See the [Reference](stub.md) for every option.\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_changed_regenerates_only_affected_categories() {
+        let service = stub_service();
+        let existing: Vec<Document> = DiátaxisCategory::ALL
+            .iter()
+            .map(|category| {
+                let mut document = stub_document(category.clone());
+                document.content = "previous".to_string();
+                document
+            })
+            .collect();
+
+        let mut changes = ChangeAnalysis::new();
+        changes.add_file_change(crate::types::FileChange {
+            path: "config/server.yaml".to_string(),
+            change_type: crate::types::ChangeType::Modified,
+            lines_added: 2,
+            lines_removed: 1,
+        });
+
+        let report = service
+            .update_changed(&stub_repository(), &changes, existing)
+            .await
+            .unwrap();
+        let categories = |documents: &[Document]| -> Vec<DiátaxisCategory> {
+            documents.iter().map(|doc| doc.category.clone()).collect()
+        };
+        assert_eq!(
+            categories(&report.regenerated.documents),
+            vec![DiátaxisCategory::Reference, DiátaxisCategory::HowTo]
+        );
+        assert_eq!(
+            categories(&report.kept),
+            vec![DiátaxisCategory::Tutorial, DiátaxisCategory::Explanation]
+        );
+        assert!(report.kept.iter().all(|doc| doc.content == "previous"));
+        assert_eq!(report.into_documents().len(), DiátaxisCategory::ALL.len());
+
+        // Nothing is regenerated for a change that affects no category
+        let mut changes = ChangeAnalysis::new();
+        changes.add_file_change(crate::types::FileChange {
+            path: "README.md".to_string(),
+            change_type: crate::types::ChangeType::Modified,
+            lines_added: 1,
+            lines_removed: 0,
+        });
+        let report = service
+            .update_changed(&stub_repository(), &changes, Vec::new())
+            .await
+            .unwrap();
+        assert!(report.is_unchanged());
+        assert!(report.regenerated.documents.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_per_module_granularity_splits_reference_and_howto() {
+        let service = stub_service().with_granularity(DocumentGranularity::PerModule);
+        let mut repo = stub_repository();
+        for name in ["parser", "render"] {
+            repo.structure.modules.push(crate::repository::Module {
+                name: name.to_string(),
+                path: PathBuf::from(format!("src/{}.rs", name)),
+                documentation: None,
+                visibility: crate::repository::Visibility::Public,
+            });
+        }
+
+        let report = service.generate_all(&repo).await.unwrap();
+        assert_eq!(report.status(), GenerationStatus::Complete);
+        let count = |category: DiátaxisCategory| {
+            report
+                .documents
+                .iter()
+                .filter(|doc| doc.category == category)
+                .count()
+        };
+        assert_eq!(count(DiátaxisCategory::Reference), 2);
+        assert_eq!(count(DiátaxisCategory::HowTo), 2);
+        assert_eq!(count(DiátaxisCategory::Tutorial), 1);
+
+        // Every stub document asks for the same path
+        let paths: std::collections::HashSet<&PathBuf> =
+            report.documents.iter().map(|doc| &doc.file_path).collect();
+        assert_eq!(paths.len(), report.documents.len());
+        assert!(paths.contains(&PathBuf::from("stub_2.md")));
+    }
+
+    #[tokio::test]
+    async fn test_split_category_without_subjects_falls_back_to_single() {
+        let service = stub_service().with_granularity(DocumentGranularity::PerItem);
+        let report = service.generate_all(&stub_repository()).await.unwrap();
+        assert_eq!(report.documents.len(), DiátaxisCategory::ALL.len());
+    }
+
     #[test]
     fn test_documentation_analysis() {
         let mut analysis = DocumentationAnalysis::new();
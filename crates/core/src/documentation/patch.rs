@@ -0,0 +1,367 @@
+//! Section-level patching of existing documents
+//!
+//! Instead of sending a whole document back through the model after a code
+//! change, a [`PatchPlan`] selects only the Markdown sections that mention a
+//! changed item and asks the model to rewrite those. Every other section is
+//! copied through byte for byte, which keeps generated diffs small.
+//!
+//! A plan is not built, and the caller should regenerate the document in
+//! full, when:
+//!
+//! - the diff has more than [`PatchConfig::max_changed_items`] items,
+//! - an added item is not mentioned anywhere in the document, since there is
+//!   no section to patch it into,
+//! - no section mentions a changed item, or
+//! - the selected sections exceed [`PatchConfig::max_patched_fraction`] of
+//!   the document.
+
+use crate::{
+    documentation::generator::Document,
+    error::{Result, XzeError},
+    repository::{DiffChange, StructureDiff},
+};
+use serde::{Deserialize, Serialize};
+
+/// Limits for section-level patching
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatchConfig {
+    /// Largest diff patched in place
+    #[serde(default = "default_max_changed_items")]
+    pub max_changed_items: usize,
+    /// Largest share of the document, by bytes, sent for patching
+    #[serde(default = "default_max_patched_fraction")]
+    pub max_patched_fraction: f32,
+}
+
+impl Default for PatchConfig {
+    fn default() -> Self {
+        Self {
+            max_changed_items: default_max_changed_items(),
+            max_patched_fraction: default_max_patched_fraction(),
+        }
+    }
+}
+
+fn default_max_changed_items() -> usize {
+    20
+}
+
+fn default_max_patched_fraction() -> f32 {
+    0.5
+}
+
+/// A run of Markdown starting at a heading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Heading text without the leading `#`s; `None` for the preamble
+    pub heading: Option<String>,
+    /// Exact source text, including the heading line
+    pub text: String,
+}
+
+/// Split Markdown into sections at ATX headings outside code fences
+///
+/// Concatenating the `text` of every section reproduces `content` exactly.
+pub fn split_sections(content: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut current = Section {
+        heading: None,
+        text: String::new(),
+    };
+    let mut in_fence = false;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        if let Some(heading) = heading_text(line).filter(|_| !in_fence) {
+            if current.heading.is_some() || !current.text.is_empty() {
+                sections.push(current);
+            }
+            current = Section {
+                heading: Some(heading),
+                text: String::new(),
+            };
+        }
+        current.text.push_str(line);
+    }
+
+    if current.heading.is_some() || !current.text.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+fn heading_text(line: &str) -> Option<String> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if !rest.starts_with(' ') && !rest.trim().is_empty() {
+        return None;
+    }
+    Some(rest.trim().to_string())
+}
+
+/// Whether `text` mentions `name` as a whole identifier
+fn mentions(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// Sections of one document selected for patching
+#[derive(Debug, Clone)]
+pub struct PatchPlan {
+    sections: Vec<Section>,
+    targets: Vec<usize>,
+    changes: String,
+}
+
+impl PatchPlan {
+    /// Select the sections of `doc` affected by `diff`
+    ///
+    /// Returns `None` when the document should be regenerated in full.
+    pub fn new(doc: &Document, diff: &StructureDiff, config: &PatchConfig) -> Option<Self> {
+        if diff.is_empty() || diff.len() > config.max_changed_items {
+            return None;
+        }
+
+        let sections = split_sections(&doc.content);
+        let targets: Vec<usize> = sections
+            .iter()
+            .enumerate()
+            .filter(|(_, section)| section.heading.is_some())
+            .filter(|(_, section)| diff.names().any(|name| mentions(&section.text, name)))
+            .map(|(index, _)| index)
+            .collect();
+
+        let unplaced_addition = diff
+            .items
+            .iter()
+            .any(|item| item.change == DiffChange::Added && !mentions(&doc.content, &item.name));
+        if targets.is_empty() || unplaced_addition {
+            return None;
+        }
+
+        let patched_bytes: usize = targets.iter().map(|&i| sections[i].text.len()).sum();
+        if patched_bytes as f32 > doc.content.len() as f32 * config.max_patched_fraction {
+            return None;
+        }
+
+        Some(Self {
+            sections,
+            targets,
+            changes: diff.to_prompt(),
+        })
+    }
+
+    /// Headings of the sections that will be patched, in document order
+    pub fn headings(&self) -> Vec<String> {
+        self.targets
+            .iter()
+            .filter_map(|&i| self.sections[i].heading.clone())
+            .collect()
+    }
+
+    /// Prompt asking the model to rewrite only the selected sections
+    pub fn prompt(&self) -> String {
+        let sections: String = self
+            .targets
+            .iter()
+            .map(|&i| self.sections[i].text.as_str())
+            .collect();
+
+        format!(
+            "Patch the documentation sections below so they reflect the code changes.\n\
+             Return only these sections, in the same order, each starting with its \
+             original heading line unchanged. Do not add, remove or rename sections.\n\n\
+             Code changes:\n{}\nSections:\n\n{}",
+            self.changes, sections
+        )
+    }
+
+    /// Splice the model's response into `doc`
+    ///
+    /// Fails when the response does not contain exactly the requested
+    /// sections, in order, so the caller can fall back to a full update.
+    pub fn apply(&self, doc: &Document, response: &str) -> Result<Document> {
+        let patched: Vec<Section> = split_sections(strip_code_fence(response))
+            .into_iter()
+            .filter(|section| section.heading.is_some())
+            .collect();
+
+        let expected = self.headings();
+        let returned: Vec<String> = patched.iter().filter_map(|s| s.heading.clone()).collect();
+        if returned != expected {
+            return Err(XzeError::ai(format!(
+                "Patch response returned sections {:?}, expected {:?}",
+                returned, expected
+            )));
+        }
+
+        let mut sections = self.sections.clone();
+        for (&index, section) in self.targets.iter().zip(patched) {
+            // Keep the original spacing before the next heading
+            let original = &sections[index].text;
+            let trailing = &original[original.trim_end().len()..];
+            sections[index].text = format!("{}{}", section.text.trim_end(), trailing);
+        }
+
+        let mut updated = doc.clone();
+        updated.content = sections.into_iter().map(|s| s.text).collect();
+        updated.metadata.touch();
+        updated.metadata.patched_sections = expected;
+        Ok(updated)
+    }
+}
+
+/// Remove a fence the model wrapped the whole response in
+fn strip_code_fence(response: &str) -> &str {
+    let trimmed = response.trim();
+    match trimmed.strip_prefix("```") {
+        Some(rest) if trimmed.ends_with("```") && rest.len() >= 3 => {
+            let body = &rest[..rest.len() - 3];
+            body.split_once('\n').map(|(_, body)| body).unwrap_or("")
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{CodeStructure, Function, Visibility};
+    use crate::types::DiátaxisCategory;
+    use std::path::PathBuf;
+
+    const CONTENT: &str = "# API\n\nIntro.\n\n## run\n\nRuns once.\n\n## load\n\nLoads config.\n\n```rust\n# not a heading\nload();\n```\n\n## Other\n\nUnrelated text that is long enough to keep the patched share small.\n";
+
+    fn document() -> Document {
+        Document::new(
+            DiátaxisCategory::Reference,
+            "API".to_string(),
+            CONTENT.to_string(),
+            PathBuf::from("reference/api.md"),
+        )
+    }
+
+    fn function(name: &str, signature: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: signature.to_string(),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    fn diff(old: Vec<Function>, new: Vec<Function>) -> StructureDiff {
+        let mut before = CodeStructure::new();
+        before.functions = old;
+        let mut after = CodeStructure::new();
+        after.functions = new;
+        before.diff(&after)
+    }
+
+    #[test]
+    fn test_split_sections_round_trips() {
+        let sections = split_sections(CONTENT);
+        let headings: Vec<Option<&str>> = sections.iter().map(|s| s.heading.as_deref()).collect();
+
+        assert_eq!(
+            headings,
+            vec![Some("API"), Some("run"), Some("load"), Some("Other")]
+        );
+        let joined: String = sections.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(joined, CONTENT);
+    }
+
+    #[test]
+    fn test_mentions_matches_whole_identifiers() {
+        assert!(mentions("call `run` first", "run"));
+        assert!(!mentions("running", "run"));
+        assert!(!mentions("rerun", "run"));
+    }
+
+    #[test]
+    fn test_plan_targets_only_mentioning_sections() {
+        let diff = diff(
+            vec![function("load", "fn load()")],
+            vec![function("load", "fn load(path: &Path)")],
+        );
+        let plan = PatchPlan::new(&document(), &diff, &PatchConfig::default()).unwrap();
+
+        assert_eq!(plan.headings(), vec!["load"]);
+        assert!(plan.prompt().contains("Loads config."));
+        assert!(!plan.prompt().contains("Runs once."));
+    }
+
+    #[test]
+    fn test_apply_keeps_other_sections_byte_stable() {
+        let diff = diff(
+            vec![function("load", "fn load()")],
+            vec![function("load", "fn load(path: &Path)")],
+        );
+        let doc = document();
+        let plan = PatchPlan::new(&doc, &diff, &PatchConfig::default()).unwrap();
+
+        let updated = plan
+            .apply(
+                &doc,
+                "```markdown\n## load\n\nLoads config from `path`.\n```",
+            )
+            .unwrap();
+
+        assert!(updated
+            .content
+            .contains("## load\n\nLoads config from `path`.\n\n## Other"));
+        assert!(updated
+            .content
+            .starts_with("# API\n\nIntro.\n\n## run\n\nRuns once.\n\n"));
+        assert_eq!(updated.metadata.patched_sections, vec!["load"]);
+    }
+
+    #[test]
+    fn test_apply_rejects_mismatched_sections() {
+        let diff = diff(
+            vec![function("load", "fn load()")],
+            vec![function("load", "fn load(path: &Path)")],
+        );
+        let doc = document();
+        let plan = PatchPlan::new(&doc, &diff, &PatchConfig::default()).unwrap();
+
+        assert!(plan.apply(&doc, "## Loading\n\nRenamed.\n").is_err());
+    }
+
+    #[test]
+    fn test_falls_back_for_unplaced_additions_and_large_diffs() {
+        let doc = document();
+        let added = diff(vec![], vec![function("save", "fn save()")]);
+        assert!(PatchPlan::new(&doc, &added, &PatchConfig::default()).is_none());
+
+        let modified = diff(
+            vec![function("load", "fn load()")],
+            vec![function("load", "fn load(path: &Path)")],
+        );
+        let strict = PatchConfig {
+            max_changed_items: 0,
+            ..PatchConfig::default()
+        };
+        assert!(PatchPlan::new(&doc, &modified, &strict).is_none());
+
+        let tiny = PatchConfig {
+            max_patched_fraction: 0.01,
+            ..PatchConfig::default()
+        };
+        assert!(PatchPlan::new(&doc, &modified, &tiny).is_none());
+    }
+}
@@ -273,6 +273,8 @@ impl DocumentProcessor {
     ///     keywords: vec!["guide".to_string(), "tutorial".to_string()],
     ///     word_count: 0,
     ///     char_count: 0,
+    ///     overlap_sentences: 0,
+    ///     breadcrumbs: Vec::new(),
     /// });
     ///
     /// let result = processor.process_document_with_chunking(
@@ -378,6 +380,8 @@ impl DocumentProcessor {
                 keywords: vec![],
                 word_count: content.split_whitespace().count(),
                 char_count: content.len(),
+                overlap_sentences: 0,
+                breadcrumbs: Vec::new(),
             });
 
             vec![SemanticChunk {
@@ -389,6 +393,7 @@ impl DocumentProcessor {
                 avg_similarity: 0.0,
                 metadata: meta,
                 embedding: vec![],
+                similarity_metric: Default::default(),
             }]
         };
 
@@ -0,0 +1,437 @@
+//! Output formats for generated documents
+//!
+//! Documents are generated as Markdown. [`OutputFormat`] selects how they
+//! are written out: as that Markdown, as standalone HTML pages, or as the
+//! serialized [`Document`].
+//!
+//! HTML pages carry the [`DocumentMetadata`](crate::documentation::DocumentMetadata)
+//! as `<meta>` tags, a table of contents linking every heading, and a nav
+//! bar linking the index pages of the four Diátaxis categories. Fenced code
+//! blocks keep their language as a `language-*` class, which the bundled
+//! highlight.js script uses to highlight them. Front-matter at the top of
+//! the Markdown is dropped from the page, since the meta tags replace it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use xze_core::documentation::render::{render_document, OutputFormat};
+//! use xze_core::documentation::Document;
+//! use xze_core::types::DiátaxisCategory;
+//!
+//! let doc = Document::new(
+//!     DiátaxisCategory::HowTo,
+//!     "Deploy".to_string(),
+//!     "# Deploy\n\n## Build\n\n```sh\ncargo build\n```\n".to_string(),
+//!     PathBuf::from("how_to/deploy.md"),
+//! );
+//!
+//! let html = render_document(&doc, OutputFormat::Html).unwrap();
+//! assert!(html.contains("<h2 id=\"build\">Build</h2>"));
+//! assert!(html.contains("<code class=\"language-sh\">"));
+//! ```
+
+use crate::{
+    documentation::generator::Document,
+    error::{Result, XzeError},
+    types::DiátaxisCategory,
+};
+use pulldown_cmark::{html, CowStr, Event, HeadingLevel, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// highlight.js release used by generated pages
+const HIGHLIGHT_JS: &str = "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0";
+
+/// Format generated documents are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Markdown, as generated (default)
+    #[default]
+    Markdown,
+    /// Standalone HTML pages
+    Html,
+    /// The serialized document, including its metadata
+    Json,
+}
+
+impl OutputFormat {
+    /// File extension of documents written in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Html => write!(f, "html"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = XzeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(XzeError::validation(format!(
+                "Unknown output format '{}', expected markdown, html or json",
+                other
+            ))),
+        }
+    }
+}
+
+/// Directory holding the documents of a category
+pub fn category_dir(category: &DiátaxisCategory) -> &'static str {
+    match category {
+        DiátaxisCategory::Tutorial => "tutorials",
+        DiátaxisCategory::HowTo => "how_to",
+        DiátaxisCategory::Reference => "reference",
+        DiátaxisCategory::Explanation => "explanation",
+    }
+}
+
+/// Render `document` in `format`
+///
+/// # Errors
+///
+/// Returns a serialization error if the document cannot be encoded as JSON
+pub fn render_document(document: &Document, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Markdown => Ok(document.content.clone()),
+        OutputFormat::Html => Ok(render_html(document)),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(document)?),
+    }
+}
+
+/// A heading of the rendered page
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Heading {
+    level: HeadingLevel,
+    id: String,
+    text: String,
+}
+
+/// Render `document` as a standalone HTML page
+pub fn render_html(document: &Document) -> String {
+    let (body, headings) = markdown_to_html(strip_front_matter(&document.content));
+
+    let mut page = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    page.push_str("<meta charset=\"utf-8\">\n");
+    page.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    page.push_str(&format!("<title>{}</title>\n", escape(&document.title)));
+    for (name, content) in meta_tags(document) {
+        page.push_str(&format!(
+            "<meta name=\"{}\" content=\"{}\">\n",
+            escape(&name),
+            escape(&content)
+        ));
+    }
+    page.push_str(&format!(
+        "<link rel=\"stylesheet\" href=\"{}/styles/default.min.css\">\n",
+        HIGHLIGHT_JS
+    ));
+    page.push_str("</head>\n<body>\n");
+    page.push_str(&category_nav(&document.category));
+    page.push_str("<main>\n");
+    page.push_str(&table_of_contents(&headings));
+    page.push_str("<article>\n");
+    page.push_str(&body);
+    page.push_str("</article>\n</main>\n");
+    page.push_str(&format!(
+        "<script src=\"{}/highlight.min.js\"></script>\n<script>hljs.highlightAll();</script>\n",
+        HIGHLIGHT_JS
+    ));
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+/// Convert Markdown to HTML, giving every heading an `id` anchor
+fn markdown_to_html(markdown: &str) -> (String, Vec<Heading>) {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut events: Vec<Event> = Parser::new_ext(markdown, options).collect();
+    let mut headings = Vec::new();
+    let mut used_ids: HashMap<String, usize> = HashMap::new();
+
+    let mut i = 0;
+    while i < events.len() {
+        let Event::Start(Tag::Heading(level, _, _)) = events[i] else {
+            i += 1;
+            continue;
+        };
+        let end = events[i..]
+            .iter()
+            .position(|event| matches!(event, Event::End(Tag::Heading(..))))
+            .map_or(events.len() - 1, |offset| i + offset);
+
+        let text: String = events[i + 1..end]
+            .iter()
+            .filter_map(|event| match event {
+                Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect();
+        let id = unique_id(&heading_id(&text), &mut used_ids);
+
+        events[i] = Event::Html(CowStr::from(format!("<{} id=\"{}\">", level, id)));
+        events[end] = Event::Html(CowStr::from(format!("</{}>\n", level)));
+        headings.push(Heading { level, id, text });
+        i = end + 1;
+    }
+
+    let mut body = String::new();
+    html::push_html(&mut body, events.into_iter());
+    (body, headings)
+}
+
+/// Anchor for a heading, in the style of GitHub heading links
+pub(crate) fn heading_id(text: &str) -> String {
+    let id: String = text
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            c if c.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .collect();
+    if id.is_empty() {
+        "section".to_string()
+    } else {
+        id
+    }
+}
+
+/// Suffix repeated anchors with `-1`, `-2`, ...
+fn unique_id(id: &str, used: &mut HashMap<String, usize>) -> String {
+    let count = used.entry(id.to_string()).or_insert(0);
+    let unique = if *count == 0 {
+        id.to_string()
+    } else {
+        format!("{}-{}", id, count)
+    };
+    *count += 1;
+    unique
+}
+
+/// Table of contents listing the section headings below the title
+fn table_of_contents(headings: &[Heading]) -> String {
+    let sections: Vec<&Heading> = headings
+        .iter()
+        .filter(|h| h.level > HeadingLevel::H1 && h.level <= HeadingLevel::H4)
+        .collect();
+    if sections.is_empty() {
+        return String::new();
+    }
+
+    let mut toc = String::from("<nav class=\"toc\">\n<h2>Contents</h2>\n<ul>\n");
+    for heading in sections {
+        toc.push_str(&format!(
+            "<li class=\"toc-{}\"><a href=\"#{}\">{}</a></li>\n",
+            heading.level,
+            heading.id,
+            escape(&heading.text)
+        ));
+    }
+    toc.push_str("</ul>\n</nav>\n");
+    toc
+}
+
+/// Nav bar linking the index page of each Diátaxis category
+///
+/// Links are relative to a document in `current`'s directory.
+fn category_nav(current: &DiátaxisCategory) -> String {
+    let mut nav = String::from("<nav class=\"diataxis\">\n<ul>\n");
+    for category in DiátaxisCategory::ALL {
+        let class = if category == *current {
+            " class=\"active\""
+        } else {
+            ""
+        };
+        nav.push_str(&format!(
+            "<li{}><a href=\"../{}/index.html\">{}</a></li>\n",
+            class,
+            category_dir(&category),
+            category
+        ));
+    }
+    nav.push_str("</ul>\n</nav>\n");
+    nav
+}
+
+/// Name and content of the meta tags describing `document`
+fn meta_tags(document: &Document) -> Vec<(String, String)> {
+    let metadata = &document.metadata;
+    let mut tags = vec![
+        ("generator".to_string(), "xze".to_string()),
+        ("author".to_string(), metadata.author.clone()),
+        ("xze:category".to_string(), document.category.to_string()),
+        ("xze:version".to_string(), metadata.version.clone()),
+        ("xze:created".to_string(), metadata.created_at.to_rfc3339()),
+        ("xze:updated".to_string(), metadata.updated_at.to_rfc3339()),
+    ];
+    if !metadata.tags.is_empty() {
+        tags.push(("keywords".to_string(), metadata.tags.join(", ")));
+    }
+    if let Some(confidence) = metadata.confidence {
+        tags.push(("xze:confidence".to_string(), format!("{:.2}", confidence)));
+    }
+    if let Some(persona) = &metadata.persona {
+        tags.push(("xze:persona".to_string(), persona.clone()));
+    }
+    if metadata.truncated {
+        tags.push(("xze:truncated".to_string(), "true".to_string()));
+    }
+
+    let mut custom: Vec<_> = metadata.custom.iter().collect();
+    custom.sort();
+    for (key, value) in custom {
+        tags.push((format!("xze:{}", key), value.clone()));
+    }
+    tags
+}
+
+/// Content without a leading `---` front-matter block
+pub(crate) fn strip_front_matter(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return content;
+    };
+    match rest.find("\n---\n") {
+        Some(end) => rest[end + 5..].trim_start_matches('\n'),
+        None => content,
+    }
+}
+
+/// Escape text for use in HTML content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn tutorial() -> Document {
+        let mut doc = Document::new(
+            DiátaxisCategory::Tutorial,
+            "Getting Started".to_string(),
+            "---\ntitle: Getting Started\n---\n\n# Getting Started\n\n\
+             ## Install\n\nRun this:\n\n```rust\nfn main() {}\n```\n\n\
+             ## Step 1 & `cargo`\n\nDone.\n\n## Install\n"
+                .to_string(),
+            PathBuf::from("tutorials/getting_started.md"),
+        );
+        doc.metadata.tags = vec!["intro".to_string()];
+        doc.add_metadata("repository".to_string(), "xze \"core\"".to_string());
+        doc
+    }
+
+    #[test]
+    fn test_output_format_parse_and_extension() {
+        assert_eq!("HTML".parse::<OutputFormat>().unwrap(), OutputFormat::Html);
+        assert_eq!(
+            "md".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Markdown
+        );
+        assert!("pdf".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Json.extension(), "json");
+        assert_eq!(OutputFormat::default(), OutputFormat::Markdown);
+    }
+
+    #[test]
+    fn test_tutorial_renders_html_with_headings() {
+        let html = render_document(&tutorial(), OutputFormat::Html).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(html.contains("<title>Getting Started</title>"));
+        assert!(html.contains("<h1 id=\"getting-started\">Getting Started</h1>"));
+        assert!(html.contains("<h2 id=\"install\">Install</h2>"));
+        assert!(html.contains("<h2 id=\"install-1\">Install</h2>"));
+        assert!(html.contains("<h2 id=\"step-1--cargo\">Step 1 &amp; <code>cargo</code></h2>"));
+        assert!(html.contains("<a href=\"#step-1--cargo\">Step 1 &amp; cargo</a>"));
+        assert!(html.contains("<code class=\"language-rust\">fn main() {}"));
+        assert!(!html.contains("title: Getting Started"));
+
+        // Every opened element is closed
+        for tag in [
+            "html", "head", "body", "main", "article", "nav", "ul", "h1", "h2",
+        ] {
+            assert_eq!(
+                html.matches(&format!("<{}", tag)).count(),
+                html.matches(&format!("</{}>", tag)).count(),
+                "<{}>",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn test_html_metadata_and_nav() {
+        let html = render_html(&tutorial());
+
+        assert!(html.contains("<meta name=\"keywords\" content=\"intro\">"));
+        assert!(html.contains("<meta name=\"xze:category\" content=\"Tutorial\">"));
+        assert!(html.contains("<meta name=\"xze:repository\" content=\"xze &quot;core&quot;\">"));
+        assert!(
+            html.contains("<li class=\"active\"><a href=\"../tutorials/index.html\">Tutorial</a>")
+        );
+        for dir in ["how_to", "reference", "explanation"] {
+            assert!(html.contains(&format!("<li><a href=\"../{}/index.html\">", dir)));
+        }
+    }
+
+    #[test]
+    fn test_json_and_markdown_rendering() {
+        let doc = tutorial();
+        assert_eq!(
+            render_document(&doc, OutputFormat::Markdown).unwrap(),
+            doc.content
+        );
+
+        let json = render_document(&doc, OutputFormat::Json).unwrap();
+        let parsed: Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.title, doc.title);
+        assert_eq!(parsed.metadata.tags, doc.metadata.tags);
+    }
+
+    #[test]
+    fn test_strip_front_matter() {
+        assert_eq!(strip_front_matter("---\na: 1\n---\n\n# T\n"), "# T\n");
+        assert_eq!(strip_front_matter("# T\n---\n"), "# T\n---\n");
+        assert_eq!(
+            strip_front_matter("---\nunterminated\n"),
+            "---\nunterminated\n"
+        );
+    }
+}
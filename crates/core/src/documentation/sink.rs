@@ -0,0 +1,596 @@
+//! Output sinks for delivering generated documents
+//!
+//! Generation produces [`Document`] values; an [`OutputSink`] decides where
+//! they end up. The sink is selected from [`OutputConfig`] with
+//! [`build_sink`], so server mode can persist to the knowledge base while
+//! the CLI writes files, without either caller branching on the destination.
+//!
+//! # Contract
+//!
+//! - `write` returns only after the document has been handed to the
+//!   destination: written to disk, committed to the store or accepted by the
+//!   object store. An `Ok` means the document is retrievable.
+//! - Writing the same document path again replaces the previous content.
+//! - Sinks must be safe to share between tasks, but callers such as
+//!   [`write_documents`] invoke `write` sequentially in generation order and
+//!   stop at the first error. Documents before the failing one have been
+//!   delivered; documents after it have not.
+
+use crate::{
+    documentation::generator::{Document, DocumentWriter, GeneratorConfig},
+    documentation::render::OutputFormat,
+    error::{Result, XzeError},
+    kb::{calculate_content_hash, store::DocumentChunk, KbBackend},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, info};
+
+/// Destination for generated documents
+#[async_trait]
+pub trait OutputSink: Send + Sync {
+    /// Deliver a single document
+    async fn write(&self, doc: &Document) -> Result<()>;
+
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+}
+
+/// Write documents to a sink in order, stopping at the first failure
+///
+/// Returns the number of documents written.
+pub async fn write_documents(sink: &dyn OutputSink, documents: &[Document]) -> Result<usize> {
+    for doc in documents {
+        sink.write(doc).await?;
+    }
+
+    info!(
+        "Wrote {} documents to {} sink",
+        documents.len(),
+        sink.name()
+    );
+    Ok(documents.len())
+}
+
+/// Output sink selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputSinkKind {
+    /// Files under `output_dir` (default)
+    #[default]
+    Filesystem,
+    /// Knowledge base store
+    Kb,
+    /// Standard output, for dry runs and piping
+    Stdout,
+    /// S3-compatible object store (requires the `s3` feature)
+    S3,
+}
+
+impl fmt::Display for OutputSinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputSinkKind::Filesystem => write!(f, "filesystem"),
+            OutputSinkKind::Kb => write!(f, "kb"),
+            OutputSinkKind::Stdout => write!(f, "stdout"),
+            OutputSinkKind::S3 => write!(f, "s3"),
+        }
+    }
+}
+
+/// Output configuration for generated documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// Where generated documents are delivered
+    #[serde(default)]
+    pub sink: OutputSinkKind,
+    /// Root directory for the filesystem sink
+    #[serde(default = "default_output_dir")]
+    pub output_dir: PathBuf,
+    /// Format files are written in by the filesystem sink
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Object store settings for the S3 sink
+    #[serde(default)]
+    pub s3: Option<S3SinkConfig>,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            sink: OutputSinkKind::default(),
+            output_dir: default_output_dir(),
+            format: OutputFormat::default(),
+            s3: None,
+        }
+    }
+}
+
+/// Settings for the S3 sink
+///
+/// Credentials are read from `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+/// when the sink is built, never from the configuration file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConfig {
+    /// Bucket name
+    pub bucket: String,
+    /// Key prefix prepended to every document path
+    #[serde(default)]
+    pub prefix: String,
+    /// Bucket region
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Custom endpoint for S3-compatible stores (path-style addressing)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+fn default_output_dir() -> PathBuf {
+    PathBuf::from("docs")
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Build the sink selected by `config`
+///
+/// `kb` is required when the knowledge base sink is selected.
+pub fn build_sink(
+    config: &OutputConfig,
+    kb: Option<Arc<dyn KbBackend>>,
+) -> Result<Arc<dyn OutputSink>> {
+    debug!("Building {} output sink", config.sink);
+
+    match config.sink {
+        OutputSinkKind::Filesystem => Ok(Arc::new(
+            FileSystemSink::new(&config.output_dir).with_format(config.format),
+        )),
+        OutputSinkKind::Stdout => Ok(Arc::new(StdoutSink)),
+        OutputSinkKind::Kb => {
+            let backend = kb.ok_or_else(|| {
+                XzeError::validation("The kb output sink requires a knowledge base backend")
+            })?;
+            Ok(Arc::new(KbSink::new(backend)))
+        }
+        OutputSinkKind::S3 => {
+            let s3 = config.s3.as_ref().ok_or_else(|| {
+                XzeError::validation("The s3 output sink requires an `s3` section")
+            })?;
+            build_s3_sink(s3)
+        }
+    }
+}
+
+#[cfg(feature = "s3")]
+fn build_s3_sink(config: &S3SinkConfig) -> Result<Arc<dyn OutputSink>> {
+    Ok(Arc::new(s3::S3Sink::from_env(config.clone())?))
+}
+
+#[cfg(not(feature = "s3"))]
+fn build_s3_sink(_config: &S3SinkConfig) -> Result<Arc<dyn OutputSink>> {
+    Err(XzeError::unsupported(
+        "s3 output sink (rebuild with the `s3` feature)",
+    ))
+}
+
+/// Writes documents as files under a root directory
+pub struct FileSystemSink {
+    writer: DocumentWriter,
+}
+
+impl FileSystemSink {
+    /// Create a sink rooted at `output_dir`, replacing existing files
+    pub fn new(output_dir: &Path) -> Self {
+        let config = GeneratorConfig {
+            output_dir: output_dir.to_path_buf(),
+            overwrite_existing: true,
+            ..GeneratorConfig::default()
+        };
+        Self {
+            writer: DocumentWriter::new(config),
+        }
+    }
+
+    /// Write files in `format` instead of Markdown
+    pub fn with_format(self, format: OutputFormat) -> Self {
+        let config = GeneratorConfig {
+            output_format: format,
+            ..self.writer.config().clone()
+        };
+        Self {
+            writer: DocumentWriter::new(config),
+        }
+    }
+}
+
+#[async_trait]
+impl OutputSink for FileSystemSink {
+    async fn write(&self, doc: &Document) -> Result<()> {
+        self.writer.write_document(doc).await.map(|_| ())
+    }
+
+    fn name(&self) -> &'static str {
+        "filesystem"
+    }
+}
+
+/// Prints documents to standard output
+///
+/// Each document is preceded by an HTML comment naming its path so the
+/// stream stays valid Markdown and can be split again.
+pub struct StdoutSink;
+
+#[async_trait]
+impl OutputSink for StdoutSink {
+    async fn write(&self, doc: &Document) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "<!-- {} -->", doc.file_path.display())?;
+        writeln!(stdout, "{}", doc.content)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+}
+
+/// Stores documents in the knowledge base
+///
+/// Each document becomes a single chunk keyed by its path. Embeddings are
+/// left empty; chunking and embedding happen when the knowledge base is
+/// (re)loaded.
+pub struct KbSink {
+    backend: Arc<dyn KbBackend>,
+}
+
+impl KbSink {
+    /// Create a sink over a knowledge base backend
+    pub fn new(backend: Arc<dyn KbBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+#[async_trait]
+impl OutputSink for KbSink {
+    async fn write(&self, doc: &Document) -> Result<()> {
+        let hash = calculate_content_hash(&doc.content);
+        let chunk = DocumentChunk::new(
+            "chunk_0".to_string(),
+            doc.content.clone(),
+            Vec::new(),
+            serde_json::json!({
+                "title": doc.title,
+                "category": doc.category.to_string(),
+            }),
+        );
+
+        self.backend
+            .update_file_chunks(&doc.file_path, &hash, &[chunk])
+            .await
+            .map_err(|e| {
+                XzeError::documentation(format!(
+                    "Failed to store {:?} in knowledge base: {}",
+                    doc.file_path, e
+                ))
+            })
+    }
+
+    fn name(&self) -> &'static str {
+        "kb"
+    }
+}
+
+#[cfg(feature = "s3")]
+pub mod s3 {
+    //! S3 sink using AWS Signature Version 4 over plain HTTP requests
+
+    use super::{Document, OutputSink, S3SinkConfig};
+    use crate::error::{Result, XzeError};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+
+    type HmacSha256 = Hmac<Sha256>;
+
+    /// Uploads documents to an S3-compatible bucket
+    pub struct S3Sink {
+        config: S3SinkConfig,
+        access_key: String,
+        secret_key: String,
+        client: reqwest::Client,
+    }
+
+    impl S3Sink {
+        /// Create a sink with explicit credentials
+        pub fn new(config: S3SinkConfig, access_key: String, secret_key: String) -> Self {
+            Self {
+                config,
+                access_key,
+                secret_key,
+                client: reqwest::Client::new(),
+            }
+        }
+
+        /// Create a sink with credentials from the standard AWS variables
+        pub fn from_env(config: S3SinkConfig) -> Result<Self> {
+            let access_key = std::env::var("AWS_ACCESS_KEY_ID")
+                .map_err(|_| XzeError::auth("AWS_ACCESS_KEY_ID is not set"))?;
+            let secret_key = std::env::var("AWS_SECRET_ACCESS_KEY")
+                .map_err(|_| XzeError::auth("AWS_SECRET_ACCESS_KEY is not set"))?;
+            Ok(Self::new(config, access_key, secret_key))
+        }
+
+        /// Object key for a document
+        pub fn object_key(&self, doc: &Document) -> String {
+            let path = doc.file_path.to_string_lossy().replace('\\', "/");
+            let prefix = self.config.prefix.trim_matches('/');
+            if prefix.is_empty() {
+                path
+            } else {
+                format!("{}/{}", prefix, path)
+            }
+        }
+
+        fn host(&self) -> String {
+            match &self.config.endpoint {
+                Some(endpoint) => endpoint
+                    .trim_start_matches("https://")
+                    .trim_start_matches("http://")
+                    .trim_end_matches('/')
+                    .to_string(),
+                None => format!("s3.{}.amazonaws.com", self.config.region),
+            }
+        }
+
+        fn scheme(&self) -> &str {
+            match &self.config.endpoint {
+                Some(endpoint) if endpoint.starts_with("http://") => "http",
+                _ => "https",
+            }
+        }
+
+        /// Canonical path-style URI for a key
+        fn canonical_uri(&self, key: &str) -> String {
+            let mut uri = format!("/{}", urlencoding::encode(&self.config.bucket));
+            for segment in key.split('/') {
+                uri.push('/');
+                uri.push_str(&urlencoding::encode(segment));
+            }
+            uri
+        }
+
+        /// Compute the `Authorization` header for a PUT request
+        pub fn authorization(&self, uri: &str, payload_hash: &str, now: DateTime<Utc>) -> String {
+            let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+            let date = now.format("%Y%m%d").to_string();
+            let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+            let canonical_request = format!(
+                "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+                uri,
+                self.host(),
+                payload_hash,
+                amz_date,
+                signed_headers,
+                payload_hash
+            );
+
+            let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+            let string_to_sign = format!(
+                "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+                amz_date,
+                scope,
+                hex::encode(Sha256::digest(canonical_request.as_bytes()))
+            );
+
+            let key = [self.config.region.as_str(), "s3", "aws4_request"]
+                .iter()
+                .fold(
+                    hmac(
+                        format!("AWS4{}", self.secret_key).as_bytes(),
+                        date.as_bytes(),
+                    ),
+                    |key, part| hmac(&key, part.as_bytes()),
+                );
+            let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                self.access_key, scope, signed_headers, signature
+            )
+        }
+    }
+
+    fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    #[async_trait]
+    impl OutputSink for S3Sink {
+        async fn write(&self, doc: &Document) -> Result<()> {
+            let uri = self.canonical_uri(&self.object_key(doc));
+            let payload_hash = hex::encode(Sha256::digest(doc.content.as_bytes()));
+            let now = Utc::now();
+
+            let response = self
+                .client
+                .put(format!("{}://{}{}", self.scheme(), self.host(), uri))
+                .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+                .header("x-amz-content-sha256", &payload_hash)
+                .header(
+                    "authorization",
+                    self.authorization(&uri, &payload_hash, now),
+                )
+                .header("content-type", "text/markdown; charset=utf-8")
+                .body(doc.content.clone())
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(XzeError::network(format!(
+                    "S3 upload of {} failed with status {}",
+                    uri,
+                    response.status()
+                )));
+            }
+
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "s3"
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::types::DiátaxisCategory;
+        use chrono::TimeZone;
+        use std::path::PathBuf;
+
+        fn sink(prefix: &str) -> S3Sink {
+            S3Sink::new(
+                S3SinkConfig {
+                    bucket: "docs".to_string(),
+                    prefix: prefix.to_string(),
+                    region: "us-east-1".to_string(),
+                    endpoint: None,
+                },
+                "AKIDEXAMPLE".to_string(),
+                "secret".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_object_key_applies_prefix() {
+            let doc = Document::new(
+                DiátaxisCategory::Reference,
+                "API".to_string(),
+                String::new(),
+                PathBuf::from("reference/api.md"),
+            );
+            assert_eq!(sink("").object_key(&doc), "reference/api.md");
+            assert_eq!(
+                sink("/service/").object_key(&doc),
+                "service/reference/api.md"
+            );
+        }
+
+        #[test]
+        fn test_authorization_is_deterministic() {
+            let sink = sink("");
+            let now = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+            let auth = sink.authorization("/docs/a.md", "abc", now);
+
+            assert!(auth.starts_with(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260102/us-east-1/s3/aws4_request"
+            ));
+            assert_eq!(auth, sink.authorization("/docs/a.md", "abc", now));
+            assert_ne!(auth, sink.authorization("/docs/b.md", "abc", now));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kb::InMemoryKbStore;
+    use crate::types::DiátaxisCategory;
+    use tempfile::TempDir;
+
+    fn document(path: &str, content: &str) -> Document {
+        Document::new(
+            DiátaxisCategory::HowTo,
+            "Setup".to_string(),
+            content.to_string(),
+            PathBuf::from(path),
+        )
+    }
+
+    #[test]
+    fn test_output_config_defaults() {
+        let config: OutputConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config, OutputConfig::default());
+        assert_eq!(config.sink, OutputSinkKind::Filesystem);
+
+        let config: OutputConfig = serde_yaml::from_str("sink: stdout").unwrap();
+        assert_eq!(config.sink, OutputSinkKind::Stdout);
+
+        let config: OutputConfig = serde_yaml::from_str("format: html").unwrap();
+        assert_eq!(config.format, OutputFormat::Html);
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_sink_replaces_existing() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = FileSystemSink::new(temp_dir.path());
+
+        let written = write_documents(
+            &sink,
+            &[
+                document("how-to/setup.md", "first"),
+                document("how-to/setup.md", "second"),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(written, 2);
+        let content = std::fs::read_to_string(temp_dir.path().join("how-to/setup.md")).unwrap();
+        assert_eq!(content, "second");
+    }
+
+    #[tokio::test]
+    async fn test_kb_sink_stores_document() {
+        let store = Arc::new(InMemoryKbStore::new());
+        let sink = KbSink::new(store.clone());
+
+        sink.write(&document("how-to/setup.md", "content"))
+            .await
+            .unwrap();
+
+        let files = store.query_existing_files().await.unwrap();
+        assert_eq!(
+            files.get("how-to/setup.md"),
+            Some(&calculate_content_hash("content"))
+        );
+    }
+
+    #[test]
+    fn test_build_sink_requirements() {
+        let kb = OutputConfig {
+            sink: OutputSinkKind::Kb,
+            ..OutputConfig::default()
+        };
+        assert!(build_sink(&kb, None).is_err());
+        assert_eq!(
+            build_sink(&kb, Some(Arc::new(InMemoryKbStore::new())))
+                .unwrap()
+                .name(),
+            "kb"
+        );
+
+        let s3 = OutputConfig {
+            sink: OutputSinkKind::S3,
+            ..OutputConfig::default()
+        };
+        assert!(build_sink(&s3, None).is_err());
+
+        assert_eq!(
+            build_sink(&OutputConfig::default(), None).unwrap().name(),
+            "filesystem"
+        );
+    }
+}
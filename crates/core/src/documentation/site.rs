@@ -0,0 +1,448 @@
+//! Static site export
+//!
+//! [`SiteExporter`] writes a generated documentation set out as the source
+//! tree of a static site, ready for `mkdocs build` or `hugo`:
+//!
+//! - **MkDocs**: `mkdocs.yml` with a `nav` listing every category and its
+//!   documents, and a `docs/` tree with one directory per category
+//! - **Hugo**: `hugo.yaml` with a main menu of the categories, and a
+//!   `content/` tree with one section per category
+//!
+//! Every page starts with YAML front matter built from its
+//! [`DocumentMetadata`](crate::documentation::DocumentMetadata), replacing
+//! any front matter the document already had. Page file names are slugs of
+//! the document titles; repeated slugs within a category get a numeric
+//! suffix.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::path::PathBuf;
+//! use xze_core::documentation::site::{SiteConfig, SiteExporter, SiteFormat};
+//!
+//! # async fn example(documents: Vec<xze_core::documentation::Document>) -> xze_core::Result<()> {
+//! let exporter = SiteExporter::new(SiteConfig {
+//!     format: SiteFormat::Hugo,
+//!     output_dir: PathBuf::from("site"),
+//!     ..Default::default()
+//! });
+//! let written = exporter.export(&documents).await?;
+//! println!("Exported {} files", written.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    documentation::{
+        front_matter::category_weight,
+        generator::Document,
+        granularity::{slugify, SlugRegistry},
+        render::{category_dir, strip_front_matter},
+    },
+    error::{Result, XzeError},
+    types::DiátaxisCategory,
+};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Static site generator to export for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SiteFormat {
+    /// MkDocs project (default)
+    #[default]
+    MkDocs,
+    /// Hugo site
+    Hugo,
+}
+
+impl fmt::Display for SiteFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiteFormat::MkDocs => write!(f, "mkdocs"),
+            SiteFormat::Hugo => write!(f, "hugo"),
+        }
+    }
+}
+
+impl std::str::FromStr for SiteFormat {
+    type Err = XzeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mkdocs" => Ok(SiteFormat::MkDocs),
+            "hugo" => Ok(SiteFormat::Hugo),
+            other => Err(XzeError::validation(format!(
+                "Unknown site format '{}', expected mkdocs or hugo",
+                other
+            ))),
+        }
+    }
+}
+
+/// Configuration for site export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteConfig {
+    /// Site generator to export for
+    pub format: SiteFormat,
+    /// Root directory of the exported site
+    pub output_dir: PathBuf,
+    /// Site title
+    pub site_name: String,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            format: SiteFormat::default(),
+            output_dir: PathBuf::from("site"),
+            site_name: "Documentation".to_string(),
+        }
+    }
+}
+
+/// A document placed in the site
+struct Page<'a> {
+    document: &'a Document,
+    /// Path relative to the content root
+    path: PathBuf,
+}
+
+/// Exports documents as a static site source tree
+#[derive(Debug, Clone, Default)]
+pub struct SiteExporter {
+    config: SiteConfig,
+}
+
+impl SiteExporter {
+    /// Create an exporter with the given configuration
+    pub fn new(config: SiteConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the exporter configuration
+    pub fn config(&self) -> &SiteConfig {
+        &self.config
+    }
+
+    /// Write `documents` out as a site, returning the paths written
+    ///
+    /// All four categories get a section, even those without documents, so
+    /// the navigation is the same for every export.
+    ///
+    /// # Errors
+    ///
+    /// Returns a filesystem error if a file cannot be written
+    pub async fn export(&self, documents: &[Document]) -> Result<Vec<PathBuf>> {
+        let sections = place_pages(documents);
+        let written = match self.config.format {
+            SiteFormat::MkDocs => self.export_mkdocs(&sections).await?,
+            SiteFormat::Hugo => self.export_hugo(&sections).await?,
+        };
+
+        info!(
+            "Exported {} documents as a {} site to {:?}",
+            documents.len(),
+            self.config.format,
+            self.config.output_dir
+        );
+        Ok(written)
+    }
+
+    async fn export_mkdocs(
+        &self,
+        sections: &[(DiátaxisCategory, Vec<Page<'_>>)],
+    ) -> Result<Vec<PathBuf>> {
+        let root = &self.config.output_dir;
+        let docs_dir = root.join("docs");
+        let mut written = Vec::new();
+
+        let mut nav = vec![single("Home", Value::from("index.md"))];
+        let mut home = format!("# {}\n\n", self.config.site_name);
+        for (category, pages) in sections {
+            let dir = category_dir(category);
+            let index = format!("{}/index.md", dir);
+            home.push_str(&format!("- [{}]({})\n", section_title(category), index));
+
+            let mut entries = vec![single("Overview", Value::from(index.clone()))];
+            let mut listing = format!("# {}\n\n", section_title(category));
+            for page in pages {
+                let path = page.path.to_string_lossy().into_owned();
+                entries.push(single(&page.document.title, Value::from(path)));
+                let name = page.path.file_name().unwrap_or_default().to_string_lossy();
+                listing.push_str(&format!("- [{}]({})\n", page.document.title, name));
+
+                let contents = page_contents(page.document, Mapping::new(), "updated")?;
+                written.push(write_file(&docs_dir.join(&page.path), &contents).await?);
+            }
+            written.push(write_file(&docs_dir.join(&index), &listing).await?);
+            nav.push(single(section_title(category), Value::Sequence(entries)));
+        }
+        written.push(write_file(&docs_dir.join("index.md"), &home).await?);
+
+        let mut mkdocs = Mapping::new();
+        mkdocs.insert("site_name".into(), self.config.site_name.clone().into());
+        mkdocs.insert("docs_dir".into(), "docs".into());
+        mkdocs.insert("nav".into(), Value::Sequence(nav));
+        written.push(write_file(&root.join("mkdocs.yml"), &to_yaml(&mkdocs)?).await?);
+
+        Ok(written)
+    }
+
+    async fn export_hugo(
+        &self,
+        sections: &[(DiátaxisCategory, Vec<Page<'_>>)],
+    ) -> Result<Vec<PathBuf>> {
+        let root = &self.config.output_dir;
+        let content_dir = root.join("content");
+        let mut written = Vec::new();
+
+        let mut menu = Vec::new();
+        for (category, pages) in sections {
+            let dir = category_dir(category);
+            let weight = category_weight(category);
+
+            let mut entry = Mapping::new();
+            entry.insert("name".into(), section_title(category).into());
+            entry.insert("pageRef".into(), format!("/{}", dir).into());
+            entry.insert("weight".into(), weight.into());
+            menu.push(Value::Mapping(entry));
+
+            let mut section = Mapping::new();
+            section.insert("title".into(), section_title(category).into());
+            section.insert("weight".into(), weight.into());
+            let index = front_matter_block(&section)?;
+            written.push(write_file(&content_dir.join(dir).join("_index.md"), &index).await?);
+
+            for (position, page) in pages.iter().enumerate() {
+                let mut fields = Mapping::new();
+                fields.insert("weight".into(), (position as u64 + 1).into());
+                let contents = page_contents(page.document, fields, "lastmod")?;
+                written.push(write_file(&content_dir.join(&page.path), &contents).await?);
+            }
+        }
+
+        let mut home = Mapping::new();
+        home.insert("title".into(), self.config.site_name.clone().into());
+        let index = front_matter_block(&home)?;
+        written.push(write_file(&content_dir.join("_index.md"), &index).await?);
+
+        let mut menus = Mapping::new();
+        menus.insert("main".into(), Value::Sequence(menu));
+        let mut hugo = Mapping::new();
+        hugo.insert("title".into(), self.config.site_name.clone().into());
+        hugo.insert("menus".into(), Value::Mapping(menus));
+        written.push(write_file(&root.join("hugo.yaml"), &to_yaml(&hugo)?).await?);
+
+        Ok(written)
+    }
+}
+
+/// Group documents by category in reading order, sorted by title, and give
+/// each a unique slugged path
+fn place_pages(documents: &[Document]) -> Vec<(DiátaxisCategory, Vec<Page<'_>>)> {
+    let mut categories = DiátaxisCategory::ALL;
+    categories.sort_by_key(category_weight);
+
+    let mut registry = SlugRegistry::new();
+    categories
+        .into_iter()
+        .map(|category| {
+            let mut members: Vec<&Document> = documents
+                .iter()
+                .filter(|doc| doc.category == category)
+                .collect();
+            members.sort_by(|a, b| a.title.cmp(&b.title));
+
+            let pages = members
+                .into_iter()
+                .map(|document| {
+                    let mut slug = slugify(&document.title);
+                    // `index` would collide with the section page
+                    if slug.is_empty() || slug == "index" || slug == "_index" {
+                        slug = format!("page{}", slug);
+                    }
+                    let path = Path::new(category_dir(&category)).join(format!("{}.md", slug));
+                    Page {
+                        document,
+                        path: registry.claim(&path),
+                    }
+                })
+                .collect();
+            (category, pages)
+        })
+        .collect()
+}
+
+/// Heading of a category's section in the navigation
+fn section_title(category: &DiátaxisCategory) -> &'static str {
+    match category {
+        DiátaxisCategory::Tutorial => "Tutorials",
+        DiátaxisCategory::HowTo => "How-To Guides",
+        DiátaxisCategory::Reference => "Reference",
+        DiátaxisCategory::Explanation => "Explanation",
+    }
+}
+
+/// A document's page: metadata front matter, then its Markdown
+///
+/// `fields` are added after the metadata; `updated_key` names the
+/// last-modified field the site generator reads.
+fn page_contents(document: &Document, fields: Mapping, updated_key: &str) -> Result<String> {
+    let metadata = &document.metadata;
+    let mut front = Mapping::new();
+    front.insert("title".into(), document.title.clone().into());
+    front.insert("category".into(), document.category.to_string().into());
+    front.insert("date".into(), metadata.created_at.to_rfc3339().into());
+    front.insert(updated_key.into(), metadata.updated_at.to_rfc3339().into());
+    if !metadata.author.is_empty() {
+        front.insert("author".into(), metadata.author.clone().into());
+    }
+    if !metadata.version.is_empty() {
+        front.insert("version".into(), metadata.version.clone().into());
+    }
+    if !metadata.tags.is_empty() {
+        front.insert("tags".into(), metadata.tags.clone().into());
+    }
+    let mut custom: Vec<_> = metadata.custom.iter().collect();
+    custom.sort();
+    for (key, value) in custom {
+        front.insert(key.clone().into(), value.clone().into());
+    }
+    front.extend(fields);
+
+    let body = strip_front_matter(&document.content);
+    Ok(format!("{}\n{}", front_matter_block(&front)?, body))
+}
+
+/// A `---` delimited YAML front-matter block
+fn front_matter_block(fields: &Mapping) -> Result<String> {
+    Ok(format!("---\n{}---\n", to_yaml(fields)?))
+}
+
+fn to_yaml(mapping: &Mapping) -> Result<String> {
+    serde_yaml::to_string(mapping)
+        .map_err(|e| XzeError::validation(format!("Failed to serialize site YAML: {}", e)))
+}
+
+/// A one-entry mapping, the shape of an MkDocs nav item
+fn single(key: &str, value: Value) -> Value {
+    let mut mapping = Mapping::new();
+    mapping.insert(key.into(), value);
+    Value::Mapping(mapping)
+}
+
+async fn write_file(path: &Path, contents: &str) -> Result<PathBuf> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| XzeError::filesystem(format!("Failed to create directory: {}", e)))?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(|e| XzeError::filesystem(format!("Failed to write file: {}", e)))?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(category: DiátaxisCategory, title: &str) -> Document {
+        let mut doc = Document::new(
+            category,
+            title.to_string(),
+            format!("---\nstale: true\n---\n# {}\n\nBody.\n", title),
+            PathBuf::from("ignored.md"),
+        );
+        doc.metadata.author = "xze".to_string();
+        doc.metadata.tags = vec!["api".to_string()];
+        doc
+    }
+
+    fn documents() -> Vec<Document> {
+        vec![
+            document(DiátaxisCategory::Tutorial, "Getting Started"),
+            document(DiátaxisCategory::HowTo, "Deploy to Production"),
+            document(DiátaxisCategory::Reference, "API Reference"),
+            document(DiátaxisCategory::Reference, "API-Reference"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_mkdocs_export_lists_all_categories() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = SiteExporter::new(SiteConfig {
+            output_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        exporter.export(&documents()).await.unwrap();
+
+        let mkdocs = std::fs::read_to_string(dir.path().join("mkdocs.yml")).unwrap();
+        let config: Value = serde_yaml::from_str(&mkdocs).unwrap();
+        let nav = config["nav"].as_sequence().unwrap();
+        let sections: Vec<&str> = nav
+            .iter()
+            .filter_map(|item| item.as_mapping()?.keys().next()?.as_str())
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                "Home",
+                "Tutorials",
+                "How-To Guides",
+                "Reference",
+                "Explanation"
+            ]
+        );
+        assert!(mkdocs.contains("Getting Started: tutorials/getting_started.md"));
+        assert!(mkdocs.contains("API Reference: reference/api_reference.md"));
+        assert!(mkdocs.contains("API-Reference: reference/api_reference_2.md"));
+
+        let page = std::fs::read_to_string(dir.path().join("docs/how_to/deploy_to_production.md"))
+            .unwrap();
+        assert!(page.starts_with("---\ntitle: Deploy to Production\ncategory: How-To\n"));
+        assert!(page.contains("author: xze\n"));
+        assert!(page.contains("tags:\n- api\n"));
+        assert!(!page.contains("stale"));
+        assert!(page.ends_with("---\n\n# Deploy to Production\n\nBody.\n"));
+        assert!(dir.path().join("docs/explanation/index.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_hugo_export_writes_sections_and_menu() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = SiteExporter::new(SiteConfig {
+            format: SiteFormat::Hugo,
+            output_dir: dir.path().to_path_buf(),
+            site_name: "Demo".to_string(),
+        });
+        exporter.export(&documents()).await.unwrap();
+
+        let hugo: Value =
+            serde_yaml::from_str(&std::fs::read_to_string(dir.path().join("hugo.yaml")).unwrap())
+                .unwrap();
+        assert_eq!(hugo["title"], Value::from("Demo"));
+        assert_eq!(hugo["menus"]["main"].as_sequence().unwrap().len(), 4);
+
+        let section =
+            std::fs::read_to_string(dir.path().join("content/reference/_index.md")).unwrap();
+        assert!(section.contains("title: Reference\nweight: 30\n"));
+
+        let page = std::fs::read_to_string(dir.path().join("content/reference/api_reference_2.md"))
+            .unwrap();
+        assert!(page.contains("lastmod: "));
+        assert!(page.contains("weight: 2\n"));
+    }
+
+    #[test]
+    fn test_site_format_parse() {
+        assert_eq!("MkDocs".parse::<SiteFormat>().unwrap(), SiteFormat::MkDocs);
+        assert_eq!("hugo".parse::<SiteFormat>().unwrap(), SiteFormat::Hugo);
+        assert!("jekyll".parse::<SiteFormat>().is_err());
+    }
+}
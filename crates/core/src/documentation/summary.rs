@@ -0,0 +1,325 @@
+//! Repository summary documents
+//!
+//! A summary is the "what is this repository" page that ties the Diátaxis
+//! documents together. To keep it factual, everything except the opening
+//! purpose paragraph is rendered directly from the [`CodeStructure`]: the
+//! language, module and public API listings, configuration files, and links
+//! to the generated documents. Only the purpose is written by the model, from
+//! a prompt that contains nothing but names taken from the structure.
+
+use crate::{
+    documentation::generator::Document,
+    repository::{CodeStructure, Repository, TypeDefinition, Visibility},
+    types::DiátaxisCategory,
+};
+use std::path::{Path, PathBuf};
+
+/// Items listed per section before the rest are summarized as a count
+const MAX_LISTED: usize = 10;
+
+/// File name of the summary, next to the category directories
+pub const SUMMARY_FILE_NAME: &str = "overview.md";
+
+/// Prompt asking the model for a short purpose statement
+pub fn purpose_prompt(repo: &Repository) -> String {
+    let structure = &repo.structure;
+    let modules: Vec<&str> = structure.modules.iter().map(|m| m.name.as_str()).collect();
+    let types: Vec<&str> = structure
+        .types
+        .iter()
+        .filter(|t| t.visibility == Visibility::Public)
+        .map(|t| t.name.as_str())
+        .collect();
+    let functions: Vec<&str> = structure
+        .public_functions()
+        .into_iter()
+        .map(|f| f.name.as_str())
+        .collect();
+
+    format!(
+        "Write two or three sentences describing the purpose of the {} repository \
+         \"{}\". Base the description only on the names below. Do not mention \
+         features, dependencies or behavior that the names do not show. Return \
+         plain prose without headings or lists.\n\n\
+         Modules: {}\nPublic types: {}\nPublic functions: {}\n",
+        repo.language,
+        repo.name(),
+        name_list(&modules),
+        name_list(&types),
+        name_list(&functions),
+    )
+}
+
+/// Render the summary document
+///
+/// `purpose` is the model's answer to [`purpose_prompt`]. `documents` are
+/// the generated documents to link; the summary is placed in their output
+/// directory so the links are relative.
+pub fn render_summary(repo: &Repository, purpose: &str, documents: &[Document]) -> Document {
+    let structure = &repo.structure;
+    let base = output_dir(documents);
+    let mut content = format!("# {}\n\n{}\n\n", repo.name(), purpose.trim());
+
+    content.push_str("## At a Glance\n\n");
+    content.push_str(&format!("- **Language**: {}\n", repo.language));
+    content.push_str(&format!("- **Modules**: {}\n", structure.modules.len()));
+    content.push_str(&format!(
+        "- **Public functions**: {}\n",
+        structure.public_functions().len()
+    ));
+    content.push_str(&format!(
+        "- **Public types**: {}\n\n",
+        public_types(structure).count()
+    ));
+
+    if !structure.modules.is_empty() {
+        content.push_str("## Modules\n\n");
+        let lines = structure.modules.iter().map(|module| {
+            with_summary(
+                format!("`{}`", module.path.display()),
+                &module.documentation,
+            )
+        });
+        push_list(&mut content, lines, structure.modules.len());
+    }
+
+    let types: Vec<_> = documented_first(public_types(structure).map(|t| (t, &t.documentation)));
+    let functions: Vec<_> = documented_first(
+        structure
+            .public_functions()
+            .into_iter()
+            .map(|f| (f, &f.documentation)),
+    );
+    if !types.is_empty() || !functions.is_empty() {
+        content.push_str("## Key Public APIs\n\n");
+        if !types.is_empty() {
+            content.push_str("### Types\n\n");
+            let lines = types.iter().map(|t| {
+                with_summary(
+                    format!("`{}` ({})", t.name, format!("{:?}", t.kind).to_lowercase()),
+                    &t.documentation,
+                )
+            });
+            push_list(&mut content, lines, types.len());
+        }
+        if !functions.is_empty() {
+            content.push_str("### Functions\n\n");
+            let lines = functions
+                .iter()
+                .map(|f| with_summary(format!("`{}`", f.signature.trim()), &f.documentation));
+            push_list(&mut content, lines, functions.len());
+        }
+    }
+
+    if !structure.configs.is_empty() {
+        content.push_str("## Configuration Files\n\n");
+        let lines = structure.configs.iter().map(|config| {
+            format!(
+                "`{}` ({})",
+                config.path.display(),
+                format!("{:?}", config.format).to_lowercase()
+            )
+        });
+        push_list(&mut content, lines, structure.configs.len());
+    }
+
+    if !documents.is_empty() {
+        content.push_str("## Documentation\n\n");
+        for doc in documents {
+            let link = doc.file_path.strip_prefix(&base).unwrap_or(&doc.file_path);
+            content.push_str(&format!(
+                "- [{}]({}) ({})\n",
+                doc.title,
+                link.to_string_lossy().replace('\\', "/"),
+                doc.category
+            ));
+        }
+        content.push('\n');
+    }
+
+    let mut document = Document::new(
+        DiátaxisCategory::Explanation,
+        format!("{} Overview", repo.name()),
+        content.trim_end().to_string() + "\n",
+        base.join(SUMMARY_FILE_NAME),
+    );
+    document.add_metadata("repository".to_string(), repo.name().to_string());
+    document.add_metadata("language".to_string(), repo.language.to_string());
+    document.metadata.add_tag("summary".to_string());
+    document
+}
+
+/// Directory holding the category directories of `documents`
+fn output_dir(documents: &[Document]) -> PathBuf {
+    documents
+        .first()
+        .and_then(|doc| doc.file_path.parent())
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+fn public_types(structure: &CodeStructure) -> impl Iterator<Item = &TypeDefinition> {
+    structure
+        .types
+        .iter()
+        .filter(|t| t.visibility == Visibility::Public)
+}
+
+/// Stable ordering that lists documented items before undocumented ones
+fn documented_first<'a, T>(items: impl Iterator<Item = (&'a T, &'a Option<String>)>) -> Vec<&'a T> {
+    let (documented, undocumented): (Vec<_>, Vec<_>) =
+        items.partition(|(_, documentation)| documentation.is_some());
+    documented
+        .into_iter()
+        .chain(undocumented)
+        .map(|(item, _)| item)
+        .collect()
+}
+
+fn with_summary(label: String, documentation: &Option<String>) -> String {
+    match documentation
+        .as_deref()
+        .and_then(|docs| docs.lines().map(str::trim).find(|line| !line.is_empty()))
+    {
+        Some(first_line) => format!("{}: {}", label, first_line),
+        None => label,
+    }
+}
+
+fn push_list(content: &mut String, lines: impl Iterator<Item = String>, total: usize) {
+    for line in lines.take(MAX_LISTED) {
+        content.push_str(&format!("- {}\n", line));
+    }
+    if total > MAX_LISTED {
+        content.push_str(&format!("- ... and {} more\n", total - MAX_LISTED));
+    }
+    content.push('\n');
+}
+
+fn name_list(names: &[&str]) -> String {
+    if names.is_empty() {
+        return "(none)".to_string();
+    }
+    let mut list = names
+        .iter()
+        .take(MAX_LISTED * 3)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > MAX_LISTED * 3 {
+        list.push_str(&format!(" and {} more", names.len() - MAX_LISTED * 3));
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{ConfigFile, ConfigFormat, Function, Module, TypeKind};
+    use crate::types::{ProgrammingLanguage, RepositoryId};
+
+    fn function(name: &str, documentation: Option<&str>) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("pub fn {}()", name),
+            documentation: documentation.map(str::to_string),
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    fn repository() -> Repository {
+        let mut repo = Repository::new(
+            RepositoryId::from("widgets"),
+            String::new(),
+            PathBuf::from("/tmp/widgets"),
+            ProgrammingLanguage::Rust,
+        );
+        repo.structure.modules.push(Module {
+            name: "render".to_string(),
+            path: PathBuf::from("src/render.rs"),
+            documentation: Some("Widget rendering\n\nMore detail.".to_string()),
+            visibility: Visibility::Public,
+        });
+        repo.structure.functions = vec![
+            function("helper", None),
+            function("draw", Some("Draw a widget")),
+        ];
+        repo.structure.types.push(TypeDefinition {
+            name: "Widget".to_string(),
+            kind: TypeKind::Struct,
+            documentation: None,
+            fields: vec![],
+            visibility: Visibility::Public,
+            methods: Vec::new(),
+        });
+        repo.structure.configs.push(ConfigFile {
+            path: PathBuf::from("Cargo.toml"),
+            format: ConfigFormat::Toml,
+            content: String::new(),
+        });
+        repo
+    }
+
+    #[test]
+    fn test_purpose_prompt_lists_only_structure_names() {
+        let prompt = purpose_prompt(&repository());
+
+        assert!(prompt.contains("Rust repository \"widgets\""));
+        assert!(prompt.contains("Modules: render"));
+        assert!(prompt.contains("Public types: Widget"));
+        assert!(prompt.contains("Public functions: helper, draw"));
+    }
+
+    #[test]
+    fn test_render_summary_is_grounded_in_structure() {
+        let doc = render_summary(&repository(), "Renders widgets.\n", &[]);
+
+        assert_eq!(doc.title, "widgets Overview");
+        assert_eq!(doc.file_path, PathBuf::from(SUMMARY_FILE_NAME));
+        assert!(doc.content.starts_with("# widgets\n\nRenders widgets.\n"));
+        assert!(doc.content.contains("- **Public functions**: 2\n"));
+        assert!(doc
+            .content
+            .contains("- `src/render.rs`: Widget rendering\n"));
+        assert!(doc.content.contains("- `Widget` (struct)\n"));
+        assert!(doc.content.contains("- `Cargo.toml` (toml)\n"));
+        assert!(!doc.content.contains("## Documentation"));
+
+        // Documented functions are listed first
+        let draw = doc.content.find("`pub fn draw()`: Draw a widget").unwrap();
+        let helper = doc.content.find("`pub fn helper()`").unwrap();
+        assert!(draw < helper);
+    }
+
+    #[test]
+    fn test_render_summary_links_documents_relatively() {
+        let documents = vec![
+            Document::new(
+                DiátaxisCategory::Reference,
+                "widgets API Reference".to_string(),
+                String::new(),
+                PathBuf::from("docs/reference/widgets_api_reference.md"),
+            ),
+            Document::new(
+                DiátaxisCategory::Tutorial,
+                "getting started Tutorial".to_string(),
+                String::new(),
+                PathBuf::from("docs/tutorials/getting_started_tutorial.md"),
+            ),
+        ];
+
+        let doc = render_summary(&repository(), "Renders widgets.", &documents);
+
+        assert_eq!(doc.file_path, PathBuf::from("docs/overview.md"));
+        assert!(doc.content.contains(
+            "- [widgets API Reference](reference/widgets_api_reference.md) (Reference)\n"
+        ));
+        assert!(doc
+            .content
+            .contains("- [getting started Tutorial](tutorials/getting_started_tutorial.md)"));
+    }
+}
@@ -0,0 +1,365 @@
+//! Document versions and the differences between them
+//!
+//! A [`DocumentVersionStore`] keeps every distinct version of each generated
+//! document, identified by the SHA-256 of its content. A
+//! [`DocumentDiff`] compares two versions for review:
+//!
+//! - sections added, removed or changed, matched by heading using
+//!   [`split_sections`]; a heading that appears several times is matched by
+//!   occurrence
+//! - the change in word count and confidence score
+//! - a unified text diff
+//!
+//! Versions are looked up by full hash or by a unique prefix of at least
+//! [`MIN_HASH_PREFIX`] characters, as with git commits.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use xze_core::documentation::versions::{DocumentVersionStore, SectionChangeKind};
+//! use xze_core::documentation::Document;
+//! use xze_core::types::DiátaxisCategory;
+//!
+//! let doc = |content: &str| {
+//!     Document::new(
+//!         DiátaxisCategory::Reference,
+//!         "API".to_string(),
+//!         content.to_string(),
+//!         PathBuf::from("reference/api.md"),
+//!     )
+//! };
+//!
+//! let mut store = DocumentVersionStore::new();
+//! let from = store.record("api", doc("# API\n\n## load\n\nLoads a file.\n"));
+//! let to = store.record("api", doc("# API\n\n## load\n\nLoads a file from disk.\n"));
+//!
+//! let diff = store.diff("api", &from[..8], &to).unwrap();
+//! assert_eq!(diff.sections.len(), 1);
+//! assert_eq!(diff.sections[0].kind, SectionChangeKind::Changed);
+//! assert_eq!(diff.word_count_delta, 2);
+//! ```
+
+use super::{
+    patch::{split_sections, Section},
+    Document,
+};
+use crate::{
+    error::{Result, XzeError},
+    kb::calculate_content_hash,
+};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::collections::HashMap;
+
+/// Shortest hash prefix accepted when looking up a version
+pub const MIN_HASH_PREFIX: usize = 7;
+
+/// Lines of context around each change in the unified diff
+const CONTEXT_LINES: usize = 3;
+
+/// One stored version of a document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentVersion {
+    /// SHA-256 of the content
+    pub hash: String,
+    /// The document as generated
+    pub document: Document,
+}
+
+/// Every distinct version of each document, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct DocumentVersionStore {
+    versions: HashMap<String, Vec<DocumentVersion>>,
+}
+
+impl DocumentVersionStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `document` as the latest version of `id` and return its hash
+    ///
+    /// Content identical to a stored version is not stored again.
+    pub fn record(&mut self, id: &str, document: Document) -> String {
+        let hash = calculate_content_hash(&document.content);
+        let versions = self.versions.entry(id.to_string()).or_default();
+        if !versions.iter().any(|v| v.hash == hash) {
+            versions.push(DocumentVersion {
+                hash: hash.clone(),
+                document,
+            });
+        }
+        hash
+    }
+
+    /// Versions of `id`, oldest first
+    pub fn versions(&self, id: &str) -> &[DocumentVersion] {
+        self.versions.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Find a version of `id` by full hash or unique prefix
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if the document or version does not exist,
+    /// and a validation error if the prefix is too short or ambiguous
+    pub fn get(&self, id: &str, hash: &str) -> Result<&DocumentVersion> {
+        let versions = self
+            .versions
+            .get(id)
+            .ok_or_else(|| XzeError::not_found(format!("document {}", id)))?;
+        let hash = hash.trim().to_ascii_lowercase();
+        if hash.len() < MIN_HASH_PREFIX {
+            return Err(XzeError::validation(format!(
+                "Version hash '{}' is too short; use at least {} characters",
+                hash, MIN_HASH_PREFIX
+            )));
+        }
+
+        let mut matches = versions.iter().filter(|v| v.hash.starts_with(&hash));
+        match (matches.next(), matches.next()) {
+            (Some(version), None) => Ok(version),
+            (Some(_), Some(_)) => Err(XzeError::validation(format!(
+                "Version hash '{}' matches several versions of {}",
+                hash, id
+            ))),
+            (None, _) => Err(XzeError::not_found(format!(
+                "version {} of document {}",
+                hash, id
+            ))),
+        }
+    }
+
+    /// Compare two versions of `id`
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`get`](Self::get) for either hash
+    pub fn diff(&self, id: &str, from: &str, to: &str) -> Result<DocumentDiff> {
+        let from = self.get(id, from)?;
+        let to = self.get(id, to)?;
+        Ok(DocumentDiff::between(from, to))
+    }
+}
+
+/// How a section differs between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionChangeKind {
+    /// Only in the newer version
+    Added,
+    /// Only in the older version
+    Removed,
+    /// In both, with different text
+    Changed,
+}
+
+/// A section that differs between two versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionChange {
+    /// Heading text; `None` for the text before the first heading
+    pub heading: Option<String>,
+    /// How the section differs
+    pub kind: SectionChangeKind,
+}
+
+/// Differences between two versions of a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentDiff {
+    /// Hash of the older version
+    pub from: String,
+    /// Hash of the newer version
+    pub to: String,
+    /// Sections that differ, in the order of the newer version followed by
+    /// removed sections
+    pub sections: Vec<SectionChange>,
+    /// Word count of the newer version minus the older
+    pub word_count_delta: i64,
+    /// Confidence of the newer version minus the older, when both were
+    /// scored
+    pub confidence_delta: Option<f32>,
+    /// Unified diff of the content
+    pub unified_diff: String,
+}
+
+impl DocumentDiff {
+    /// Compare `from` with `to`
+    pub fn between(from: &DocumentVersion, to: &DocumentVersion) -> Self {
+        let old = &from.document;
+        let new = &to.document;
+        let unified_diff = TextDiff::from_lines(&old.content, &new.content)
+            .unified_diff()
+            .context_radius(CONTEXT_LINES)
+            .header(&short(&from.hash), &short(&to.hash))
+            .to_string();
+
+        Self {
+            from: from.hash.clone(),
+            to: to.hash.clone(),
+            sections: section_changes(&old.content, &new.content),
+            word_count_delta: new.word_count() as i64 - old.word_count() as i64,
+            confidence_delta: old
+                .metadata
+                .confidence
+                .zip(new.metadata.confidence)
+                .map(|(old, new)| new - old),
+            unified_diff,
+        }
+    }
+
+    /// Whether the versions have the same content
+    pub fn is_empty(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+fn short(hash: &str) -> String {
+    hash.chars().take(MIN_HASH_PREFIX + 1).collect()
+}
+
+/// Sections keyed by heading and occurrence, so repeated headings such as
+/// `Example` pair up in order
+fn keyed(sections: Vec<Section>) -> Vec<((Option<String>, usize), String)> {
+    let mut seen: HashMap<Option<String>, usize> = HashMap::new();
+    sections
+        .into_iter()
+        .map(|section| {
+            let occurrence = seen.entry(section.heading.clone()).or_default();
+            *occurrence += 1;
+            ((section.heading, *occurrence), section.text)
+        })
+        .collect()
+}
+
+fn section_changes(old: &str, new: &str) -> Vec<SectionChange> {
+    let old = keyed(split_sections(old));
+    let new = keyed(split_sections(new));
+    let old_text: HashMap<_, _> = old.iter().map(|(key, text)| (key, text)).collect();
+    let new_keys: Vec<_> = new.iter().map(|(key, _)| key).collect();
+
+    let mut changes = Vec::new();
+    for (key, text) in &new {
+        let kind = match old_text.get(key) {
+            None => SectionChangeKind::Added,
+            Some(old) if old.trim_end() != text.trim_end() => SectionChangeKind::Changed,
+            Some(_) => continue,
+        };
+        changes.push(SectionChange {
+            heading: key.0.clone(),
+            kind,
+        });
+    }
+    for (key, _) in &old {
+        if !new_keys.contains(&key) {
+            changes.push(SectionChange {
+                heading: key.0.clone(),
+                kind: SectionChangeKind::Removed,
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DiátaxisCategory;
+    use std::path::PathBuf;
+
+    fn document(content: &str, confidence: Option<f32>) -> Document {
+        let mut document = Document::new(
+            DiátaxisCategory::HowTo,
+            "Deploy".to_string(),
+            content.to_string(),
+            PathBuf::from("how_to/deploy.md"),
+        );
+        document.metadata.confidence = confidence;
+        document
+    }
+
+    const V1: &str = "# Deploy\n\nIntro.\n\n## Build\n\nRun make.\n\n## Example\n\nOne.\n\n## Example\n\nTwo.\n\n## Legacy\n\nOld way.\n";
+    const V2: &str = "# Deploy\n\nIntro.\n\n## Build\n\nRun make release.\n\n## Example\n\nOne.\n\n## Example\n\nTwo.\n\n## Verify\n\nCheck the logs.\n";
+
+    #[test]
+    fn test_record_skips_identical_content() {
+        let mut store = DocumentVersionStore::new();
+        let first = store.record("deploy", document(V1, None));
+        let again = store.record("deploy", document(V1, Some(0.9)));
+        let second = store.record("deploy", document(V2, None));
+
+        assert_eq!(first, again);
+        assert_ne!(first, second);
+        assert_eq!(store.versions("deploy").len(), 2);
+        assert!(store.versions("missing").is_empty());
+    }
+
+    #[test]
+    fn test_get_by_prefix() {
+        let mut store = DocumentVersionStore::new();
+        let hash = store.record("deploy", document(V1, None));
+
+        assert_eq!(store.get("deploy", &hash[..7]).unwrap().hash, hash);
+        assert_eq!(
+            store.get("deploy", &hash.to_uppercase()).unwrap().hash,
+            hash
+        );
+        assert!(matches!(
+            store.get("deploy", &hash[..6]),
+            Err(XzeError::Validation { .. })
+        ));
+        assert!(store
+            .get("deploy", "0000000")
+            .unwrap_err()
+            .to_string()
+            .contains("version 0000000 of document deploy"));
+        assert!(matches!(
+            store.get("other", &hash),
+            Err(XzeError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_diff_reports_sections_and_deltas() {
+        let mut store = DocumentVersionStore::new();
+        let from = store.record("deploy", document(V1, Some(0.6)));
+        let to = store.record("deploy", document(V2, Some(0.8)));
+
+        let diff = store.diff("deploy", &from, &to).unwrap();
+        let changes: Vec<(Option<&str>, SectionChangeKind)> = diff
+            .sections
+            .iter()
+            .map(|c| (c.heading.as_deref(), c.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (Some("Build"), SectionChangeKind::Changed),
+                (Some("Verify"), SectionChangeKind::Added),
+                (Some("Legacy"), SectionChangeKind::Removed),
+            ]
+        );
+        assert_eq!(diff.word_count_delta, 2);
+        assert!((diff.confidence_delta.unwrap() - 0.2).abs() < 1e-6);
+        assert!(diff
+            .unified_diff
+            .contains("-Run make.\n+Run make release.\n"));
+        assert!(diff
+            .unified_diff
+            .starts_with(&format!("--- {}", &from[..8])));
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_of_same_version_is_empty() {
+        let mut store = DocumentVersionStore::new();
+        let hash = store.record("deploy", document(V1, None));
+
+        let diff = store.diff("deploy", &hash, &hash).unwrap();
+        assert!(diff.is_empty());
+        assert!(diff.sections.is_empty());
+        assert!(diff.unified_diff.is_empty());
+        assert_eq!(diff.confidence_delta, None);
+    }
+}
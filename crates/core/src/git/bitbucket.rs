@@ -0,0 +1,567 @@
+//! Bitbucket pull request management
+//!
+//! This module provides the Bitbucket Cloud implementation of the
+//! PullRequestManager trait, using the 2.0 REST API. Bitbucket has no PR
+//! labels, closes pull requests by declining them, and identifies reviewers
+//! by account ID rather than username.
+
+use crate::{Result, XzeError};
+
+use super::pr::{
+    Author, CreatePrRequest, MergeMethod, PrState, PrUpdate, PullRequest, PullRequestManager,
+};
+
+/// Bitbucket pull request manager implementation
+#[derive(Clone)]
+pub struct BitbucketPrManager {
+    client: reqwest::Client,
+    token: String,
+    api_url: String,
+}
+
+impl BitbucketPrManager {
+    /// Create a new Bitbucket PR manager for Bitbucket Cloud
+    ///
+    /// `token` is a repository, project or workspace access token.
+    pub fn new(token: String) -> Self {
+        Self::new_with_url(token, "https://api.bitbucket.org/2.0".to_string())
+    }
+
+    /// Create a new Bitbucket PR manager with a custom API URL
+    pub fn new_with_url(token: String, api_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("xze-bot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            token,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Extract workspace and repository slug from a Bitbucket URL
+    fn parse_bitbucket_url(&self, repo_url: &str) -> Result<(String, String)> {
+        let path = if let Some((_, rest)) = repo_url.split_once("://") {
+            // https://[user@]bitbucket.org/workspace/repo
+            rest.split_once('/').map(|(_, path)| path)
+        } else {
+            // git@bitbucket.org:workspace/repo.git
+            repo_url.split_once(':').map(|(_, path)| path)
+        }
+        .ok_or_else(|| XzeError::validation("Invalid Bitbucket URL format"))?;
+
+        let path = path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let parts: Vec<&str> = path.split('/').collect();
+
+        if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+            return Err(XzeError::validation("Invalid Bitbucket repository format"));
+        }
+
+        Ok((parts[0].to_string(), parts[1].to_string()))
+    }
+
+    /// Build Bitbucket API URL for a repository's pull requests
+    fn api_url(&self, workspace: &str, repo: &str, endpoint: &str) -> String {
+        let base = format!(
+            "{}/repositories/{}/{}/pullrequests",
+            self.api_url, workspace, repo
+        );
+        if endpoint.is_empty() {
+            base
+        } else {
+            format!("{}/{}", base, endpoint)
+        }
+    }
+
+    /// Parse Bitbucket API PR response into our PR struct
+    fn parse_bitbucket_pr(&self, data: &serde_json::Value) -> Result<PullRequest> {
+        let number = data["id"]
+            .as_u64()
+            .ok_or_else(|| XzeError::validation("Missing PR id"))?;
+
+        let title = data["title"]
+            .as_str()
+            .ok_or_else(|| XzeError::validation("Missing PR title"))?
+            .to_string();
+
+        let body = data["description"].as_str().unwrap_or("").to_string();
+
+        let head_branch = data["source"]["branch"]["name"]
+            .as_str()
+            .ok_or_else(|| XzeError::validation("Missing source branch"))?
+            .to_string();
+
+        let base_branch = data["destination"]["branch"]["name"]
+            .as_str()
+            .ok_or_else(|| XzeError::validation("Missing destination branch"))?
+            .to_string();
+
+        let state_str = data["state"]
+            .as_str()
+            .ok_or_else(|| XzeError::validation("Missing PR state"))?;
+        let is_draft = data["draft"].as_bool().unwrap_or(false);
+
+        let state = match (state_str, is_draft) {
+            ("MERGED", _) => PrState::Merged,
+            ("DECLINED", _) | ("SUPERSEDED", _) => PrState::Closed,
+            ("OPEN", true) => PrState::Draft,
+            _ => PrState::Open,
+        };
+
+        let author = Author {
+            username: data["author"]["nickname"]
+                .as_str()
+                .ok_or_else(|| XzeError::validation("Missing author nickname"))?
+                .to_string(),
+            name: data["author"]["display_name"]
+                .as_str()
+                .map(|s| s.to_string()),
+            email: None, // Bitbucket API doesn't return email in PR response
+        };
+
+        let reviewers = data["reviewers"]
+            .as_array()
+            .map(|reviewers| {
+                reviewers
+                    .iter()
+                    .filter_map(|reviewer| reviewer["nickname"].as_str())
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let url = data["links"]["html"]["href"]
+            .as_str()
+            .ok_or_else(|| XzeError::validation("Missing PR URL"))?
+            .to_string();
+
+        let created_at = data["created_on"]
+            .as_str()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .ok_or_else(|| XzeError::validation("Invalid created_on timestamp"))?;
+
+        let updated_at = data["updated_on"]
+            .as_str()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .ok_or_else(|| XzeError::validation("Invalid updated_on timestamp"))?;
+
+        Ok(PullRequest {
+            number,
+            title,
+            body,
+            head_branch,
+            base_branch,
+            state,
+            author,
+            labels: Vec::new(),
+            reviewers,
+            url,
+            created_at,
+            updated_at,
+        })
+    }
+
+    /// Reviewer objects for a list of Bitbucket account IDs
+    fn reviewer_ids(reviewers: &[String]) -> serde_json::Value {
+        reviewers
+            .iter()
+            .map(|id| serde_json::json!({ "account_id": id }))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for BitbucketPrManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BitbucketPrManager")
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(async_fn_in_trait)]
+impl PullRequestManager for BitbucketPrManager {
+    async fn create_pr(&self, repo_url: &str, request: CreatePrRequest) -> Result<PullRequest> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, "");
+
+        let mut bitbucket_request = serde_json::json!({
+            "title": request.title,
+            "description": request.body,
+            "source": { "branch": { "name": request.head } },
+            "destination": { "branch": { "name": request.base } },
+            "draft": request.draft,
+        });
+
+        if !request.reviewers.is_empty() {
+            bitbucket_request["reviewers"] = Self::reviewer_ids(&request.reviewers);
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&bitbucket_request)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to create PR: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(XzeError::ai(format!("Bitbucket API error: {}", error_text)));
+        }
+
+        let pr_data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| XzeError::ai(format!("Failed to parse PR response: {}", e)))?;
+
+        self.parse_bitbucket_pr(&pr_data)
+    }
+
+    async fn get_pr(&self, repo_url: &str, pr_number: u64) -> Result<PullRequest> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, &pr_number.to_string());
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to get PR: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(XzeError::not_found(format!("PR #{} not found", pr_number)));
+        }
+
+        let pr_data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| XzeError::ai(format!("Failed to parse PR response: {}", e)))?;
+
+        self.parse_bitbucket_pr(&pr_data)
+    }
+
+    async fn list_prs(&self, repo_url: &str, state: Option<PrState>) -> Result<Vec<PullRequest>> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let mut url = self.api_url(&workspace, &repo, "");
+
+        if let Some(state) = state {
+            let state_param = match state {
+                PrState::Open | PrState::Draft => "OPEN",
+                PrState::Closed => "DECLINED",
+                PrState::Merged => "MERGED",
+            };
+            url.push_str(&format!("?state={}", state_param));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to list PRs: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(XzeError::ai("Failed to list pull requests"));
+        }
+
+        let page: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| XzeError::ai(format!("Failed to parse PRs response: {}", e)))?;
+
+        let mut prs = Vec::new();
+        for pr_data in page["values"].as_array().into_iter().flatten() {
+            if let Ok(pr) = self.parse_bitbucket_pr(pr_data) {
+                prs.push(pr);
+            }
+        }
+
+        Ok(prs)
+    }
+
+    async fn update_pr(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        updates: PrUpdate,
+    ) -> Result<PullRequest> {
+        match updates.state {
+            Some(PrState::Closed) => {
+                self.close_pr(repo_url, pr_number).await?;
+            }
+            Some(_) => {
+                return Err(XzeError::validation(
+                    "Bitbucket pull requests can only be updated to closed",
+                ));
+            }
+            None => {}
+        }
+
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, &pr_number.to_string());
+
+        let mut update_data = serde_json::Map::new();
+
+        if let Some(title) = updates.title {
+            update_data.insert("title".to_string(), serde_json::Value::String(title));
+        }
+
+        if let Some(body) = updates.body {
+            update_data.insert("description".to_string(), serde_json::Value::String(body));
+        }
+
+        if let Some(reviewers) = updates.reviewers {
+            update_data.insert("reviewers".to_string(), Self::reviewer_ids(&reviewers));
+        }
+
+        if update_data.is_empty() {
+            return self.get_pr(repo_url, pr_number).await;
+        }
+
+        let response = self
+            .client
+            .put(&url)
+            .bearer_auth(&self.token)
+            .json(&update_data)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to update PR: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(XzeError::ai(format!(
+                "Failed to update pull request: {}",
+                error_text
+            )));
+        }
+
+        let pr_data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| XzeError::ai(format!("Failed to parse PR response: {}", e)))?;
+
+        self.parse_bitbucket_pr(&pr_data)
+    }
+
+    async fn close_pr(&self, repo_url: &str, pr_number: u64) -> Result<()> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, &format!("{}/decline", pr_number));
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to decline PR: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(XzeError::ai(format!(
+                "Failed to decline pull request: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn merge_pr(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        merge_method: MergeMethod,
+    ) -> Result<()> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, &format!("{}/merge", pr_number));
+
+        let merge_strategy = match merge_method {
+            MergeMethod::Merge => "merge_commit",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "fast_forward",
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({ "merge_strategy": merge_strategy }))
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to merge PR: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(XzeError::ai(format!(
+                "Failed to merge pull request: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn add_comment(&self, repo_url: &str, pr_number: u64, comment: &str) -> Result<()> {
+        let (workspace, repo) = self.parse_bitbucket_url(repo_url)?;
+        let url = self.api_url(&workspace, &repo, &format!("{}/comments", pr_number));
+
+        let comment_data = serde_json::json!({
+            "content": { "raw": comment }
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&comment_data)
+            .send()
+            .await
+            .map_err(|e| XzeError::network(format!("Failed to add comment: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(XzeError::ai(format!(
+                "Failed to add comment to pull request: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn request_review(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        reviewers: Vec<String>,
+    ) -> Result<()> {
+        // Bitbucket has no review request endpoint; reviewers are set on the PR
+        let updates = PrUpdate {
+            reviewers: Some(reviewers),
+            ..Default::default()
+        };
+
+        self.update_pr(repo_url, pr_number, updates).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr_json(id: u64, state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "title": "Update docs",
+            "description": "Generated documentation",
+            "state": state,
+            "draft": false,
+            "source": { "branch": { "name": "docs/update" } },
+            "destination": { "branch": { "name": "main" } },
+            "author": { "nickname": "xze-bot", "display_name": "XZe Bot" },
+            "reviewers": [{ "nickname": "alice" }],
+            "links": { "html": { "href": format!("https://bitbucket.org/team/repo/pull-requests/{}", id) } },
+            "created_on": "2024-01-02T03:04:05.123456+00:00",
+            "updated_on": "2024-01-02T04:04:05.123456+00:00",
+        })
+    }
+
+    #[test]
+    fn test_bitbucket_url_parsing() {
+        let manager = BitbucketPrManager::new("fake-token".to_string());
+
+        for url in [
+            "https://bitbucket.org/team/repo",
+            "https://user@bitbucket.org/team/repo.git",
+            "git@bitbucket.org:team/repo.git",
+            "ssh://git@bitbucket.org/team/repo.git",
+        ] {
+            let (workspace, repo) = manager.parse_bitbucket_url(url).unwrap();
+            assert_eq!(
+                (workspace.as_str(), repo.as_str()),
+                ("team", "repo"),
+                "{}",
+                url
+            );
+        }
+
+        assert!(manager.parse_bitbucket_url("invalid-url").is_err());
+        assert!(manager
+            .parse_bitbucket_url("https://bitbucket.org/team")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_bitbucket_pr() {
+        let manager = BitbucketPrManager::new("fake-token".to_string());
+
+        let pr = manager.parse_bitbucket_pr(&pr_json(7, "OPEN")).unwrap();
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.head_branch, "docs/update");
+        assert_eq!(pr.base_branch, "main");
+        assert_eq!(pr.state, PrState::Open);
+        assert_eq!(pr.author.username, "xze-bot");
+        assert_eq!(pr.reviewers, vec!["alice"]);
+        assert!(pr.labels.is_empty());
+
+        let pr = manager.parse_bitbucket_pr(&pr_json(8, "DECLINED")).unwrap();
+        assert_eq!(pr.state, PrState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_create_and_list_prs() {
+        let mut server = mockito::Server::new_async().await;
+        let create = server
+            .mock("POST", "/repositories/team/repo/pullrequests")
+            .match_header("authorization", "Bearer fake-token")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "source": { "branch": { "name": "docs/update" } },
+                "destination": { "branch": { "name": "main" } },
+            })))
+            .with_status(201)
+            .with_body(pr_json(7, "OPEN").to_string())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/repositories/team/repo/pullrequests?state=MERGED")
+            .with_status(200)
+            .with_body(serde_json::json!({ "values": [pr_json(3, "MERGED")] }).to_string())
+            .create_async()
+            .await;
+
+        let manager = BitbucketPrManager::new_with_url("fake-token".to_string(), server.url());
+        let request = CreatePrRequest {
+            title: "Update docs".to_string(),
+            body: "Generated documentation".to_string(),
+            head: "docs/update".to_string(),
+            base: "main".to_string(),
+            draft: false,
+            labels: vec![],
+            reviewers: vec![],
+            assignees: vec![],
+        };
+
+        let pr = manager
+            .create_pr("https://bitbucket.org/team/repo", request)
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 7);
+        create.assert_async().await;
+
+        let merged = manager
+            .list_prs("git@bitbucket.org:team/repo.git", Some(PrState::Merged))
+            .await
+            .unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].state, PrState::Merged);
+    }
+}
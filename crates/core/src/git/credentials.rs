@@ -4,9 +4,12 @@
 //! - Username/password authentication
 //! - SSH key authentication
 //! - Git credential helper integration
+//! - GitHub App installation tokens
 //! - Secure credential storage
 //! - Environment variable loading
 
+use super::github_app::{GitHubAppAuth, INSTALLATION_TOKEN_USERNAME};
+use crate::config::{GitAuth, GitCredentials};
 use crate::{Result, XzeError};
 use git2::{Cred, CredentialType};
 use std::path::{Path, PathBuf};
@@ -27,6 +30,7 @@ struct CredentialStoreInner {
     ssh_passphrase: Option<String>,
     use_agent: bool,
     use_credential_helper: bool,
+    github_app: Option<GitHubAppAuth>,
 }
 
 impl CredentialStore {
@@ -49,6 +53,7 @@ impl CredentialStore {
                 ssh_passphrase: None,
                 use_agent: true,
                 use_credential_helper: true,
+                github_app: None,
             })),
         }
     }
@@ -115,6 +120,96 @@ impl CredentialStore {
         self
     }
 
+    /// Create a credential store that authenticates as a GitHub App
+    ///
+    /// HTTPS operations use a short-lived installation token, minted on
+    /// first use and minted again before it expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - GitHub App ID
+    /// * `installation_id` - Installation of the app on the target account
+    /// * `private_key_pem` - PEM-encoded private key downloaded for the app
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use xze_core::git::{CredentialStore, GitHubPrManager};
+    ///
+    /// let pem = std::fs::read("app.private-key.pem")?;
+    /// let store = CredentialStore::from_github_app(12345, 678, &pem)?;
+    /// let prs = GitHubPrManager::from_github_app(store.github_app().unwrap());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_github_app(
+        app_id: u64,
+        installation_id: u64,
+        private_key_pem: &[u8],
+    ) -> Result<Self> {
+        let auth = GitHubAppAuth::new(app_id, installation_id, private_key_pem)?;
+        Ok(Self::new().with_github_app(auth))
+    }
+
+    /// Authenticate HTTPS operations with a GitHub App installation token
+    ///
+    /// # Arguments
+    ///
+    /// * `auth` - GitHub App authenticator
+    pub fn with_github_app(self, auth: GitHubAppAuth) -> Self {
+        let mut inner = self.inner.write().unwrap();
+        inner.github_app = Some(auth);
+        drop(inner);
+        self
+    }
+
+    /// Get the GitHub App authenticator, if configured
+    pub fn github_app(&self) -> Option<GitHubAppAuth> {
+        let inner = self.inner.read().unwrap();
+        inner.github_app.clone()
+    }
+
+    /// Create a credential store from repository configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `credentials` - Credentials configured for a repository
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xze_core::config::{GitAuth, GitCredentials};
+    /// use xze_core::git::CredentialStore;
+    ///
+    /// let store = CredentialStore::from_config(&GitCredentials {
+    ///     auth: GitAuth::SshAgent { username: "git".to_string() },
+    /// });
+    /// assert_eq!(store.username(), Some("git".to_string()));
+    /// ```
+    pub fn from_config(credentials: &GitCredentials) -> Self {
+        let store = Self::new();
+        match &credentials.auth {
+            GitAuth::UserPass { username, password } => {
+                store.set_userpass(username.clone(), password.clone());
+            }
+            GitAuth::SshKey {
+                username,
+                private_key_path,
+                passphrase,
+            } => {
+                store.set_ssh_key(
+                    username.clone(),
+                    private_key_path.clone(),
+                    None,
+                    passphrase.clone(),
+                );
+            }
+            GitAuth::SshAgent { username } => {
+                store.inner.write().unwrap().username = Some(username.clone());
+            }
+        }
+        store
+    }
+
     /// Enable or disable SSH agent
     ///
     /// # Arguments
@@ -198,11 +293,12 @@ impl CredentialStore {
     ///
     /// # Returns
     ///
-    /// True if either username/password or SSH key is configured
+    /// True if username/password, an SSH key or a GitHub App is configured
     pub fn has_credentials(&self) -> bool {
         let inner = self.inner.read().unwrap();
         (inner.username.is_some() && inner.password.is_some())
             || (inner.username.is_some() && inner.ssh_key_path.is_some())
+            || inner.github_app.is_some()
     }
 
     /// Clear all credentials
@@ -213,6 +309,7 @@ impl CredentialStore {
         inner.ssh_key_path = None;
         inner.ssh_public_key_path = None;
         inner.ssh_passphrase = None;
+        inner.github_app = None;
     }
 
     /// Create Git credentials based on the allowed types
@@ -259,6 +356,24 @@ impl CredentialStore {
             }
         }
 
+        // Try GitHub App installation token
+        if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(app) = &inner.github_app {
+                tracing::debug!(
+                    "Attempting GitHub App authentication for app {}",
+                    app.app_id()
+                );
+
+                let token = app.token_blocking()?;
+                return Cred::userpass_plaintext(INSTALLATION_TOKEN_USERNAME, &token).map_err(
+                    |e| {
+                        tracing::error!("GitHub App authentication failed");
+                        XzeError::auth(app.redact(&e.to_string()))
+                    },
+                );
+            }
+        }
+
         // Try username/password authentication
         if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
             if let (Some(username), Some(password)) = (&inner.username, &inner.password) {
@@ -603,4 +718,82 @@ mod tests {
         let result = store.validate();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_from_config() {
+        let store = CredentialStore::from_config(&GitCredentials {
+            auth: GitAuth::UserPass {
+                username: "user".to_string(),
+                password: "token".to_string(),
+            },
+        });
+        assert_eq!(
+            store.get_userpass(),
+            Some(("user".to_string(), "token".to_string()))
+        );
+
+        let store = CredentialStore::from_config(&GitCredentials {
+            auth: GitAuth::SshKey {
+                username: "git".to_string(),
+                private_key_path: PathBuf::from("/keys/id_ed25519"),
+                passphrase: None,
+            },
+        });
+        assert_eq!(
+            store.get_ssh_key_path(),
+            Some(PathBuf::from("/keys/id_ed25519"))
+        );
+    }
+
+    #[test]
+    fn test_github_app_credentials() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(
+                serde_json::json!({
+                    "token": "ghs_installation",
+                    "expires_at": (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create();
+
+        let pem = include_bytes!("../../tests/fixtures/github_app/test_private_key.pem");
+        let auth = GitHubAppAuth::new(12345, 678, pem)
+            .unwrap()
+            .with_api_url(server.url());
+        let store = CredentialStore::new()
+            .with_agent(false)
+            .with_credential_helper(false)
+            .with_github_app(auth);
+
+        assert!(store.has_credentials());
+        assert!(store
+            .create_credentials(
+                "https://github.com/owner/repo.git",
+                None,
+                CredentialType::USER_PASS_PLAINTEXT,
+            )
+            .is_ok());
+        assert!(store
+            .create_credentials(
+                "https://github.com/owner/repo.git",
+                None,
+                CredentialType::USER_PASS_PLAINTEXT,
+            )
+            .is_ok());
+        mock.assert();
+
+        assert_eq!(
+            store.github_app().unwrap().cached_token().as_deref(),
+            Some("ghs_installation")
+        );
+        assert!(!format!("{:?}", store).contains("ghs_installation"));
+
+        store.clear();
+        assert!(store.github_app().is_none());
+    }
 }
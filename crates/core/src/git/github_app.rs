@@ -0,0 +1,404 @@
+//! GitHub App authentication
+//!
+//! Mints short-lived installation access tokens for a GitHub App, so CI
+//! environments can push and open pull requests without a personal access
+//! token. An app JWT signed with the app's private key is exchanged for an
+//! installation token, which is cached and minted again shortly before it
+//! expires.
+//!
+//! Tokens never appear in `Debug` output, logs or error messages.
+
+use crate::{Result, XzeError};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// Default GitHub REST API base URL
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Username GitHub expects alongside an installation token over HTTPS
+pub const INSTALLATION_TOKEN_USERNAME: &str = "x-access-token";
+
+/// Tokens this close to expiry are minted again
+const REFRESH_MARGIN_SECS: i64 = 300;
+
+/// Placeholder that replaces tokens in error messages
+const REDACTED: &str = "[REDACTED]";
+
+/// Claims of the JWT that authenticates as the app itself
+#[derive(Debug, Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+/// Response of `POST /app/installations/{id}/access_tokens`
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// A minted installation token
+#[derive(Clone)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl InstallationToken {
+    /// Whether the token is still usable without minting a new one
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - Duration::seconds(REFRESH_MARGIN_SECS) > now
+    }
+}
+
+/// Installation token source for a GitHub App
+///
+/// Clones share the cached token.
+#[derive(Clone)]
+pub struct GitHubAppAuth {
+    app_id: u64,
+    installation_id: u64,
+    key: EncodingKey,
+    api_url: String,
+    client: reqwest::Client,
+    token: Arc<RwLock<Option<InstallationToken>>>,
+}
+
+impl GitHubAppAuth {
+    /// Create an authenticator from the app's RSA private key
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - GitHub App ID
+    /// * `installation_id` - Installation of the app on the target account
+    /// * `private_key_pem` - PEM-encoded private key downloaded for the app
+    pub fn new(app_id: u64, installation_id: u64, private_key_pem: &[u8]) -> Result<Self> {
+        let key = EncodingKey::from_rsa_pem(private_key_pem)
+            .map_err(|e| XzeError::auth(format!("Invalid GitHub App private key: {}", e)))?;
+
+        Ok(Self {
+            app_id,
+            installation_id,
+            key,
+            api_url: GITHUB_API_URL.to_string(),
+            client: http_client()?,
+            token: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Use a different API base URL, such as GitHub Enterprise Server
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// GitHub App ID
+    pub fn app_id(&self) -> u64 {
+        self.app_id
+    }
+
+    /// Installation ID tokens are minted for
+    pub fn installation_id(&self) -> u64 {
+        self.installation_id
+    }
+
+    /// Get an installation token, minting one if none is cached or it
+    /// expires within five minutes
+    pub async fn token(&self) -> Result<String> {
+        if let Some(token) = self.cached_token() {
+            return Ok(token);
+        }
+        self.mint_token(&self.client).await
+    }
+
+    /// Get an installation token from synchronous code
+    ///
+    /// Minting runs on a private runtime in a separate thread, so this is
+    /// safe to call from git2 callbacks whether or not a Tokio runtime is
+    /// active.
+    pub fn token_blocking(&self) -> Result<String> {
+        if let Some(token) = self.cached_token() {
+            return Ok(token);
+        }
+
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .map_err(|e| {
+                            XzeError::auth(format!("Failed to start token runtime: {}", e))
+                        })?;
+                    // The shared client's connections belong to another runtime
+                    let client = http_client()?;
+                    runtime.block_on(self.mint_token(&client))
+                })
+                .join()
+                .map_err(|_| XzeError::auth("GitHub App token minting panicked"))?
+        })
+    }
+
+    /// Cached token, if it is not about to expire
+    pub fn cached_token(&self) -> Option<String> {
+        let cached = self.token.read().unwrap();
+        cached
+            .as_ref()
+            .filter(|token| token.is_fresh(Utc::now()))
+            .map(|token| token.token.clone())
+    }
+
+    /// Replace any cached token in `text` with a placeholder
+    pub fn redact(&self, text: &str) -> String {
+        match self.token.read().unwrap().as_ref() {
+            Some(token) if !token.token.is_empty() => text.replace(&token.token, REDACTED),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Build the JWT that authenticates as the app
+    fn app_jwt(&self) -> Result<String> {
+        // Backdated to tolerate clock drift, and under GitHub's 10 minute cap
+        let now = Utc::now().timestamp();
+        let claims = AppClaims {
+            iat: now - 60,
+            exp: now + 540,
+            iss: self.app_id.to_string(),
+        };
+
+        jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)
+            .map_err(|e| XzeError::auth(format!("Failed to sign GitHub App JWT: {}", e)))
+    }
+
+    /// Exchange an app JWT for a new installation token and cache it
+    async fn mint_token(&self, client: &reqwest::Client) -> Result<String> {
+        tracing::debug!(
+            "Minting installation token for GitHub App {} installation {}",
+            self.app_id,
+            self.installation_id
+        );
+
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            self.api_url, self.installation_id
+        );
+        let response = client
+            .post(&url)
+            .bearer_auth(self.app_jwt()?)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| {
+                XzeError::network(format!("Failed to request installation token: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(XzeError::auth(format!(
+                "GitHub App token request failed with {}: {}",
+                status,
+                self.redact(&body)
+            )));
+        }
+
+        let minted: AccessTokenResponse = response
+            .json()
+            .await
+            .map_err(|_| XzeError::auth("Failed to parse installation token response"))?;
+
+        tracing::info!(
+            "Minted installation token for GitHub App {}, expires at {}",
+            self.app_id,
+            minted.expires_at
+        );
+
+        let token = minted.token.clone();
+        *self.token.write().unwrap() = Some(InstallationToken {
+            token: minted.token,
+            expires_at: minted.expires_at,
+        });
+        Ok(token)
+    }
+}
+
+impl fmt::Debug for GitHubAppAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expires_at = self
+            .token
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|token| token.expires_at);
+
+        f.debug_struct("GitHubAppAuth")
+            .field("app_id", &self.app_id)
+            .field("installation_id", &self.installation_id)
+            .field("api_url", &self.api_url)
+            .field("token_expires_at", &expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent("xze-bot/1.0")
+        .build()
+        .map_err(|e| XzeError::network(format!("Failed to create HTTP client: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &[u8] = include_bytes!("../../tests/fixtures/github_app/test_private_key.pem");
+
+    fn token_response(token: &str, expires_in: Duration) -> String {
+        serde_json::json!({
+            "token": token,
+            "expires_at": (Utc::now() + expires_in).to_rfc3339(),
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_rejects_invalid_private_key() {
+        assert!(GitHubAppAuth::new(1, 2, b"not a key").is_err());
+    }
+
+    #[test]
+    fn test_app_jwt_claims() {
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY).unwrap();
+        let jwt = auth.app_jwt().unwrap();
+
+        let header = jsonwebtoken::decode_header(&jwt).unwrap();
+        assert_eq!(header.alg, Algorithm::RS256);
+
+        let mut validation = jsonwebtoken::Validation::new(Algorithm::RS256);
+        validation.insecure_disable_signature_validation();
+        let claims = jsonwebtoken::decode::<serde_json::Value>(
+            &jwt,
+            &jsonwebtoken::DecodingKey::from_secret(&[]),
+            &validation,
+        )
+        .unwrap()
+        .claims;
+        assert_eq!(claims["iss"], "12345");
+        let lifetime = claims["exp"].as_i64().unwrap() - claims["iat"].as_i64().unwrap();
+        assert!(lifetime <= 600);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_minted_and_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex("^Bearer .+".to_string()),
+            )
+            .with_status(201)
+            .with_body(token_response("ghs_first", Duration::hours(1)))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY)
+            .unwrap()
+            .with_api_url(server.url());
+
+        assert_eq!(auth.token().await.unwrap(), "ghs_first");
+        assert_eq!(auth.token().await.unwrap(), "ghs_first");
+        assert_eq!(auth.cached_token().as_deref(), Some("ghs_first"));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_token_near_expiry_is_refreshed() {
+        let mut server = mockito::Server::new_async().await;
+        let expiring = server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(token_response("ghs_expiring", Duration::minutes(2)))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY)
+            .unwrap()
+            .with_api_url(server.url());
+        assert_eq!(auth.token().await.unwrap(), "ghs_expiring");
+        assert!(auth.cached_token().is_none());
+        expiring.assert_async().await;
+        expiring.remove_async().await;
+
+        server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(token_response("ghs_renewed", Duration::hours(1)))
+            .create_async()
+            .await;
+        assert_eq!(auth.token().await.unwrap(), "ghs_renewed");
+    }
+
+    #[test]
+    fn test_token_blocking_mints_without_runtime() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(token_response("ghs_blocking", Duration::hours(1)))
+            .create();
+
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY)
+            .unwrap()
+            .with_api_url(server.url());
+
+        assert_eq!(auth.token_blocking().unwrap(), "ghs_blocking");
+    }
+
+    #[tokio::test]
+    async fn test_token_never_shown() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(token_response("ghs_secret", Duration::hours(1)))
+            .create_async()
+            .await;
+
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY)
+            .unwrap()
+            .with_api_url(server.url());
+        auth.token().await.unwrap();
+
+        assert!(!format!("{:?}", auth).contains("ghs_secret"));
+        assert_eq!(
+            auth.redact("remote rejected ghs_secret"),
+            "remote rejected [REDACTED]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_request_failure() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(401)
+            .with_body(r#"{"message":"Bad credentials"}"#)
+            .create_async()
+            .await;
+
+        let auth = GitHubAppAuth::new(12345, 678, TEST_KEY)
+            .unwrap()
+            .with_api_url(server.url());
+
+        let err = auth.token().await.unwrap_err();
+        assert!(err.to_string().contains("Bad credentials"));
+    }
+}
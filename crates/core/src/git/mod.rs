@@ -4,36 +4,53 @@
 //! - Repository cloning and initialization
 //! - Branch creation and management
 //! - Commit operations with proper messages
+//! - GPG and SSH commit signing
 //! - Push/pull operations with credential handling
 //! - Diff analysis and change detection
+//! - Line blame for staleness checks
+//! - Commit history traversal
 //! - Remote branch management
 //! - Conflict detection
 //! - Stash operations
 //! - Tag management
 
-use crate::{error::Result, XzeError};
+use crate::{config::SigningConfig, error::Result, XzeError};
+use chrono::{DateTime, Utc};
 use git2::{
-    BranchType, Commit, Cred, Delta, DiffOptions, FetchOptions, IndexAddOption, Oid, PushOptions,
-    RemoteCallbacks, Repository, Signature, Status, StatusOptions,
+    AnnotatedCommit, BlameOptions, BranchType, Commit, Cred, Delta, DiffOptions, FetchOptions,
+    IndexAddOption, Oid, PushOptions, RemoteCallbacks, Repository, Signature, Sort, Status,
+    StatusOptions,
 };
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
 
+pub mod bitbucket;
 pub mod credentials;
+pub mod github_app;
 pub mod gitlab;
 pub mod pr;
+pub mod provider;
+pub mod reachability;
+pub mod signing;
 
+pub use bitbucket::BitbucketPrManager;
 pub use credentials::{credentials_from_env, CredentialStore};
+pub use github_app::GitHubAppAuth;
 pub use gitlab::GitLabPrManager;
 pub use pr::{
     Author, CreatePrRequest, GitHubPrManager, GitPlatform, MergeMethod, PrState, PrTemplateBuilder,
     PrTemplateData, PrUpdate, PullRequest, PullRequestManager, StatusCheck,
 };
+pub use provider::PullRequestProvider;
+pub use reachability::{check_repositories, check_repository, RepositoryCheck, RepositoryLocation};
 
-// Type alias for convenience
+// Type alias for convenience; use `PullRequestProvider` to support every host
 pub type PrManager = GitHubPrManager;
 
+/// Credential prompts answered before a remote operation gives up
+const MAX_AUTH_ATTEMPTS: usize = 3;
+
 /// Represents a file change in the repository
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileChange {
@@ -127,10 +144,64 @@ pub struct BranchInfo {
     pub commit_hash: String,
 }
 
+/// Summary of a single commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&Commit<'_>> for CommitInfo {
+    fn from(commit: &Commit<'_>) -> Self {
+        Self {
+            hash: commit.id().to_string(),
+            author: commit.author().name().unwrap_or("Unknown").to_string(),
+            email: commit.author().email().unwrap_or("").to_string(),
+            message: commit.message().unwrap_or("").to_string(),
+            timestamp: DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// Commit that last touched a line of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// 1-based line number in the committed file
+    pub line: usize,
+    /// Hash of the commit that last changed the line
+    pub commit_hash: String,
+    /// Author name of that commit
+    pub author: String,
+    /// Commit time of that commit
+    pub commit_time: DateTime<Utc>,
+}
+
+/// Options for [`GitOperations::clone_with_options`]
+///
+/// The default is a full clone of every branch, the same as
+/// [`GitOperations::clone`].
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    /// Fetch only this many commits of history
+    ///
+    /// Shallow clones lack older commits, so [`GitOperations::diff_analysis`]
+    /// cannot compare against revisions beyond the fetched depth.
+    pub depth: Option<u32>,
+    /// Branch to check out instead of the remote's default branch
+    pub branch: Option<String>,
+    /// Fetch only `branch`, which must then be set
+    pub single_branch: bool,
+}
+
 /// Git operations manager
 #[derive(Debug)]
 pub struct GitOperations {
     credentials: CredentialStore,
+    signing: Option<SigningConfig>,
 }
 
 impl GitOperations {
@@ -149,7 +220,18 @@ impl GitOperations {
     /// let git_ops = GitOperations::new(creds);
     /// ```
     pub fn new(credentials: CredentialStore) -> Self {
-        Self { credentials }
+        Self {
+            credentials,
+            signing: None,
+        }
+    }
+
+    /// Sign commits made by [`GitOperations::commit`] with the given key
+    ///
+    /// Passing `None` keeps commits unsigned.
+    pub fn with_signing(mut self, signing: Option<SigningConfig>) -> Self {
+        self.signing = signing;
+        self
     }
 
     /// Clone a repository
@@ -170,16 +252,78 @@ impl GitOperations {
     /// # }
     /// ```
     pub async fn clone(&self, url: &str, dest: &Path) -> Result<Repository> {
+        self.clone_with_options(url, dest, &CloneOptions::default())
+            .await
+    }
+
+    /// Clone a repository, optionally shallow or limited to one branch
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Repository URL to clone
+    /// * `dest` - Destination path for the cloned repository
+    /// * `options` - History depth and branch selection
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `options.depth` is 0, or if
+    /// `options.single_branch` is set without `options.branch`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use xze_core::git::{CloneOptions, GitOperations, CredentialStore};
+    /// # async fn example() -> xze_core::Result<()> {
+    /// let git_ops = GitOperations::new(CredentialStore::new());
+    /// let options = CloneOptions {
+    ///     depth: Some(1),
+    ///     branch: Some("main".to_string()),
+    ///     single_branch: true,
+    /// };
+    /// let repo = git_ops
+    ///     .clone_with_options("https://github.com/user/repo", std::path::Path::new("./repo"), &options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn clone_with_options(
+        &self,
+        url: &str,
+        dest: &Path,
+        options: &CloneOptions,
+    ) -> Result<Repository> {
         tracing::info!("Cloning repository {} to {}", url, dest.display());
 
+        if options.depth == Some(0) {
+            return Err(XzeError::validation("Clone depth must be at least 1"));
+        }
+        if options.single_branch && options.branch.is_none() {
+            return Err(XzeError::validation(
+                "Single-branch clone requires a branch",
+            ));
+        }
+
         let mut callbacks = RemoteCallbacks::new();
         self.setup_credentials(&mut callbacks)?;
 
         let mut fetch_options = FetchOptions::new();
         fetch_options.remote_callbacks(callbacks);
+        if let Some(depth) = options.depth {
+            fetch_options.depth(i32::try_from(depth).unwrap_or(i32::MAX));
+        }
 
         let mut builder = git2::build::RepoBuilder::new();
         builder.fetch_options(fetch_options);
+        if let Some(branch) = &options.branch {
+            builder.branch(branch);
+            if options.single_branch {
+                let branch = branch.clone();
+                builder.remote_create(move |repo, name, url| {
+                    let refspec = format!("+refs/heads/{0}:refs/remotes/{1}/{0}", branch, name);
+                    repo.remote_with_fetch(name, url, &refspec)
+                });
+            }
+        }
 
         let repo = builder.clone(url, dest).map_err(XzeError::Git)?;
 
@@ -198,6 +342,51 @@ impl GitOperations {
         Repository::open(path).map_err(XzeError::Git)
     }
 
+    /// List the references advertised by a remote without cloning it
+    ///
+    /// The equivalent of `git ls-remote`, which makes it a cheap check that
+    /// a URL is reachable and that the configured credentials are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Repository URL to contact
+    pub fn ls_remote(&self, url: &str) -> Result<Vec<String>> {
+        tracing::debug!("Listing references of {}", url);
+
+        let mut remote = git2::Remote::create_detached(url).map_err(XzeError::Git)?;
+
+        // libgit2 keeps asking for credentials while the remote rejects
+        // them, so give up after a few attempts instead of looping
+        let creds = self.credentials.clone();
+        let mut attempts = 0;
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username_from_url, allowed_types| {
+            attempts += 1;
+            if attempts > MAX_AUTH_ATTEMPTS {
+                return Err(git2::Error::from_str("authentication failed"));
+            }
+            creds
+                .create_credentials(url, username_from_url, allowed_types)
+                .or_else(|_| match username_from_url {
+                    Some(username) => Cred::ssh_key_from_agent(username),
+                    None => Cred::default(),
+                })
+        });
+
+        let mut connection = remote
+            .connect_auth(git2::Direction::Fetch, Some(callbacks), None)
+            .map_err(XzeError::Git)?;
+        let refs = connection
+            .remote()
+            .list()
+            .map_err(XzeError::Git)?
+            .iter()
+            .map(|head| head.name().to_string())
+            .collect();
+
+        Ok(refs)
+    }
+
     /// Initialize a new repository
     ///
     /// # Arguments
@@ -270,54 +459,112 @@ impl GitOperations {
 
     /// List all branches in the repository
     ///
+    /// Local branches come first. Remote-tracking branches follow unless
+    /// `local_only` is set, skipping any that a local branch tracks or
+    /// shares a name with.
+    ///
     /// # Arguments
     ///
     /// * `repo` - Repository reference
     /// * `local_only` - If true, only list local branches
-    pub fn list_branches(&self, repo: &Repository, _local_only: bool) -> Result<Vec<BranchInfo>> {
+    pub fn list_branches(&self, repo: &Repository, local_only: bool) -> Result<Vec<BranchInfo>> {
         let mut branches = Vec::new();
         let current_branch = repo
             .head()
             .ok()
             .and_then(|h| h.shorthand().map(|s| s.to_string()));
 
-        let branch_type = BranchType::Local;
-
-        for branch_result in repo.branches(Some(branch_type)).map_err(XzeError::Git)? {
+        for branch_result in repo
+            .branches(Some(BranchType::Local))
+            .map_err(XzeError::Git)?
+        {
             let (branch, _) = branch_result.map_err(XzeError::Git)?;
-            let name = branch
-                .name()
-                .map_err(XzeError::Git)?
-                .ok_or_else(|| XzeError::repository("Invalid branch name"))?
-                .to_string();
+            let name = Self::branch_name(&branch)?;
 
             let is_current = current_branch.as_ref().map(|c| c == &name).unwrap_or(false);
-            let is_remote = matches!(branch.get().kind(), Some(git2::ReferenceType::Symbolic));
 
             let upstream = branch
                 .upstream()
                 .ok()
                 .and_then(|u| u.name().ok().flatten().map(|s| s.to_string()));
 
-            let commit_hash = branch
-                .get()
-                .peel_to_commit()
-                .ok()
-                .map(|c| c.id().to_string())
-                .unwrap_or_default();
-
             branches.push(BranchInfo {
                 name,
                 is_current,
-                is_remote,
+                is_remote: false,
                 upstream,
-                commit_hash,
+                commit_hash: Self::branch_commit_hash(&branch),
+            });
+        }
+
+        if local_only {
+            return Ok(branches);
+        }
+
+        // A remote branch already listed through a local branch that tracks
+        // it, or shares its name, is not listed again
+        let tracked: HashSet<String> = branches
+            .iter()
+            .flat_map(|b| b.upstream.iter().cloned())
+            .collect();
+        let local_names: HashSet<String> = branches.iter().map(|b| b.name.clone()).collect();
+
+        for branch_result in repo
+            .branches(Some(BranchType::Remote))
+            .map_err(XzeError::Git)?
+        {
+            let (branch, _) = branch_result.map_err(XzeError::Git)?;
+
+            // Skip symbolic refs such as origin/HEAD
+            if branch.get().kind() == Some(git2::ReferenceType::Symbolic) {
+                continue;
+            }
+
+            let name = Self::branch_name(&branch)?;
+            let short_name = branch
+                .get()
+                .name()
+                .and_then(|refname| repo.branch_remote_name(refname).ok())
+                .and_then(|remote| remote.as_str().map(|r| format!("{}/", r)))
+                .and_then(|prefix| name.strip_prefix(&prefix).map(|s| s.to_string()));
+
+            if tracked.contains(&name)
+                || short_name.is_some_and(|short| local_names.contains(&short))
+            {
+                continue;
+            }
+
+            branches.push(BranchInfo {
+                commit_hash: Self::branch_commit_hash(&branch),
+                name,
+                is_current: false,
+                is_remote: true,
+                upstream: None,
             });
         }
 
         Ok(branches)
     }
 
+    /// Name of a branch, e.g. `main` or `origin/main`
+    fn branch_name(branch: &git2::Branch) -> Result<String> {
+        Ok(branch
+            .name()
+            .map_err(XzeError::Git)?
+            .ok_or_else(|| XzeError::repository("Invalid branch name"))?
+            .to_string())
+    }
+
+    /// Hash of the commit a branch points at, or empty if it has none
+    fn branch_commit_hash(branch: &git2::Branch) -> String {
+        branch
+            .get()
+            .peel_to_commit()
+            .ok()
+            .map(|c| c.id().to_string())
+            .unwrap_or_default()
+    }
+
     /// Get the current branch name
     ///
     /// # Arguments
@@ -372,6 +619,10 @@ impl GitOperations {
     /// * `repo` - Repository reference
     /// * `message` - Commit message
     pub fn commit(&self, repo: &Repository, message: &str) -> Result<Oid> {
+        if let Some(signing) = &self.signing {
+            return self.commit_signed(repo, message, signing);
+        }
+
         tracing::info!("Committing changes: {}", message);
 
         let mut index = repo.index().map_err(XzeError::Git)?;
@@ -396,6 +647,54 @@ impl GitOperations {
         Ok(oid)
     }
 
+    /// Commit staged changes with a GPG or SSH signature
+    ///
+    /// The commit is written with `Repository::commit_signed` and the current
+    /// branch is moved to it, matching [`GitOperations::commit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `message` - Commit message
+    /// * `signing` - Signing format and key
+    pub fn commit_signed(
+        &self,
+        repo: &Repository,
+        message: &str,
+        signing: &SigningConfig,
+    ) -> Result<Oid> {
+        tracing::info!("Committing signed changes: {}", message);
+
+        let mut index = repo.index().map_err(XzeError::Git)?;
+        let tree_id = index.write_tree().map_err(XzeError::Git)?;
+        let tree = repo.find_tree(tree_id).map_err(XzeError::Git)?;
+
+        let signature = self.get_signature(repo)?;
+        let parent_commit = self.get_head_commit(repo)?;
+
+        let buffer = repo
+            .commit_create_buffer(&signature, &signature, message, &tree, &[&parent_commit])
+            .map_err(XzeError::Git)?;
+        let content = buffer
+            .as_str()
+            .ok_or_else(|| XzeError::repository("Commit content is not valid UTF-8"))?;
+        let commit_signature = signing::sign_buffer(signing, content)?;
+
+        let oid = repo
+            .commit_signed(content, &commit_signature, None)
+            .map_err(XzeError::Git)?;
+
+        let head = repo.head().map_err(XzeError::Git)?;
+        let head_name = head
+            .name()
+            .ok_or_else(|| XzeError::repository("HEAD reference name is not valid UTF-8"))?;
+        repo.reference(head_name, oid, true, &format!("commit: {}", message))
+            .map_err(XzeError::Git)?;
+
+        tracing::info!("Created signed commit: {}", oid);
+        Ok(oid)
+    }
+
     /// Commit changes with author and committer information
     ///
     /// # Arguments
@@ -491,6 +790,10 @@ impl GitOperations {
 
     /// Pull from remote (fetch + merge)
     ///
+    /// Divergent histories are joined with a merge commit. If the merge
+    /// conflicts, the index is left conflicted and the error lists the
+    /// conflicted paths.
+    ///
     /// # Arguments
     ///
     /// * `repo` - Repository reference
@@ -524,14 +827,168 @@ impl GitOperations {
             repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
                 .map_err(XzeError::Git)?;
         } else {
-            return Err(XzeError::repository(
-                "Pull requires merge, which is not implemented yet",
-            ));
+            tracing::info!("Merging upstream changes");
+            self.merge_commit(repo, &fetch_commit, branch_name)?;
         }
 
         Ok(())
     }
 
+    /// Merge `their_commit` into HEAD and commit the result with both parents
+    fn merge_commit(
+        &self,
+        repo: &Repository,
+        their_commit: &AnnotatedCommit,
+        branch_name: &str,
+    ) -> Result<Oid> {
+        repo.merge(&[their_commit], None, None)
+            .map_err(XzeError::Git)?;
+
+        self.ensure_no_conflicts(repo, "Merge")?;
+
+        let mut index = repo.index().map_err(XzeError::Git)?;
+        let tree_id = index.write_tree().map_err(XzeError::Git)?;
+        let tree = repo.find_tree(tree_id).map_err(XzeError::Git)?;
+        let signature = self.get_signature(repo)?;
+        let head_commit = self.get_head_commit(repo)?;
+        let upstream_commit = repo.find_commit(their_commit.id()).map_err(XzeError::Git)?;
+
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &format!("Merge upstream changes into {}", branch_name),
+                &tree,
+                &[&head_commit, &upstream_commit],
+            )
+            .map_err(XzeError::Git)?;
+        repo.cleanup_state().map_err(XzeError::Git)?;
+
+        tracing::info!("Created merge commit: {}", oid);
+        Ok(oid)
+    }
+
+    /// Fail with the conflicted paths if `operation` left the index conflicted
+    fn ensure_no_conflicts(&self, repo: &Repository, operation: &str) -> Result<()> {
+        if !self.has_conflicts(repo)? {
+            return Ok(());
+        }
+
+        let conflicts: Vec<String> = self
+            .get_conflicts(repo)?
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        Err(XzeError::repository(format!(
+            "{} conflicts in: {}",
+            operation,
+            conflicts.join(", ")
+        )))
+    }
+
+    /// Cherry-pick a commit onto HEAD
+    ///
+    /// The new commit keeps the original author and message, noting the
+    /// source commit, and uses the configured signature as committer. On
+    /// conflict the working tree is left conflicted and an error lists the
+    /// conflicted files.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `commit_hash` - Commit to apply, as any revision git understands
+    pub fn cherry_pick(&self, repo: &Repository, commit_hash: &str) -> Result<Oid> {
+        tracing::info!("Cherry-picking commit: {}", commit_hash);
+
+        let commit = self.resolve_onto_clean_tree(repo, commit_hash, "cherry-pick")?;
+        repo.cherrypick(&commit, None).map_err(XzeError::Git)?;
+        self.ensure_no_conflicts(repo, "Cherry-pick")?;
+
+        let message = format!(
+            "{}\n\n(cherry picked from commit {})",
+            commit.message().unwrap_or("").trim_end(),
+            commit.id()
+        );
+        let author = commit.author();
+        let committer = self.get_signature(repo)?;
+        self.commit_applied(repo, &author, &committer, &message)
+    }
+
+    /// Revert a commit on top of HEAD
+    ///
+    /// The revert commit is authored with the configured signature. On
+    /// conflict the working tree is left conflicted and an error lists the
+    /// conflicted files.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `commit_hash` - Commit to revert, as any revision git understands
+    pub fn revert(&self, repo: &Repository, commit_hash: &str) -> Result<Oid> {
+        tracing::info!("Reverting commit: {}", commit_hash);
+
+        let commit = self.resolve_onto_clean_tree(repo, commit_hash, "revert")?;
+        repo.revert(&commit, None).map_err(XzeError::Git)?;
+        self.ensure_no_conflicts(repo, "Revert")?;
+
+        let message = format!(
+            "Revert \"{}\"\n\nThis reverts commit {}.",
+            commit.summary().unwrap_or(""),
+            commit.id()
+        );
+        let signature = self.get_signature(repo)?;
+        self.commit_applied(repo, &signature, &signature, &message)
+    }
+
+    /// Resolve `revision` to a commit, refusing to apply it to a dirty tree
+    fn resolve_onto_clean_tree<'repo>(
+        &self,
+        repo: &'repo Repository,
+        revision: &str,
+        operation: &str,
+    ) -> Result<Commit<'repo>> {
+        if !self.is_clean(repo)? {
+            return Err(XzeError::repository(format!(
+                "Cannot {} onto a working tree with uncommitted changes",
+                operation
+            )));
+        }
+
+        repo.revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(XzeError::Git)
+    }
+
+    /// Commit the index written by a cherry-pick or revert and end that state
+    fn commit_applied(
+        &self,
+        repo: &Repository,
+        author: &Signature,
+        committer: &Signature,
+        message: &str,
+    ) -> Result<Oid> {
+        let mut index = repo.index().map_err(XzeError::Git)?;
+        let tree_id = index.write_tree().map_err(XzeError::Git)?;
+        let tree = repo.find_tree(tree_id).map_err(XzeError::Git)?;
+        let head_commit = self.get_head_commit(repo)?;
+
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                author,
+                committer,
+                message,
+                &tree,
+                &[&head_commit],
+            )
+            .map_err(XzeError::Git)?;
+        repo.cleanup_state().map_err(XzeError::Git)?;
+
+        tracing::info!("Created commit: {}", oid);
+        Ok(oid)
+    }
+
     /// Analyze changes between two commits or working directory
     ///
     /// # Arguments
@@ -571,11 +1028,8 @@ impl GitOperations {
             .map_err(XzeError::Git)?;
 
         let mut changes = Vec::new();
-        let insertions = Arc::new(RwLock::new(0usize));
-        let deletions = Arc::new(RwLock::new(0usize));
-
-        let ins_clone = Arc::clone(&insertions);
-        let del_clone = Arc::clone(&deletions);
+        // Added and deleted lines per file, keyed like `FileChange::path`
+        let mut line_counts: HashMap<PathBuf, (usize, usize)> = HashMap::new();
 
         diff.foreach(
             &mut |delta, _progress| {
@@ -598,14 +1052,12 @@ impl GitOperations {
             },
             None,
             None,
-            Some(&mut |_delta, _hunk, line| {
+            Some(&mut |delta, _hunk, line| {
+                let path = delta.new_file().path().unwrap_or_else(|| Path::new(""));
+                let counts = line_counts.entry(path.to_path_buf()).or_default();
                 match line.origin() {
-                    '+' => {
-                        *ins_clone.write().unwrap() += 1;
-                    }
-                    '-' => {
-                        *del_clone.write().unwrap() += 1;
-                    }
+                    '+' => counts.0 += 1,
+                    '-' => counts.1 += 1,
                     _ => {}
                 }
                 true
@@ -613,18 +1065,14 @@ impl GitOperations {
         )
         .map_err(XzeError::Git)?;
 
-        let total_insertions = *insertions.read().unwrap();
-        let total_deletions = *deletions.read().unwrap();
-
-        // Update individual file stats by running diff again
-        let _stats = diff.stats().map_err(XzeError::Git)?;
-        for (i, _delta) in diff.deltas().enumerate() {
-            if i < changes.len() {
-                // Approximate stats distribution
-                changes[i].additions = total_insertions / changes.len().max(1);
-                changes[i].deletions = total_deletions / changes.len().max(1);
+        for change in &mut changes {
+            if let Some((additions, deletions)) = line_counts.get(&change.path) {
+                change.additions = *additions;
+                change.deletions = *deletions;
             }
         }
+        let total_insertions = changes.iter().map(|c| c.additions).sum();
+        let total_deletions = changes.iter().map(|c| c.deletions).sum();
 
         Ok(DiffSummary {
             files_changed: changes.len(),
@@ -634,6 +1082,157 @@ impl GitOperations {
         })
     }
 
+    /// Blame each line of a committed file
+    ///
+    /// Lines are expanded one hunk at a time, so only the blame hunks and the
+    /// resulting lines are held in memory, never the file contents. Files
+    /// that are untracked, missing from `HEAD`, or binary yield an empty
+    /// blame.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `path` - File path relative to the repository root
+    pub fn blame_file(&self, repo: &Repository, path: &Path) -> Result<Vec<BlameLine>> {
+        let head = match repo.head().and_then(|head| head.peel_to_tree()) {
+            Ok(tree) => tree,
+            Err(_) => return Ok(Vec::new()),
+        };
+        let entry = match head.get_path(path) {
+            Ok(entry) => entry,
+            Err(_) => {
+                tracing::debug!("Skipping blame for untracked file: {}", path.display());
+                return Ok(Vec::new());
+            }
+        };
+        let is_binary = entry
+            .to_object(repo)
+            .ok()
+            .and_then(|object| object.into_blob().ok())
+            .is_none_or(|blob| blob.is_binary());
+        if is_binary {
+            tracing::debug!("Skipping blame for binary file: {}", path.display());
+            return Ok(Vec::new());
+        }
+
+        let mut opts = BlameOptions::new();
+        let blame = repo
+            .blame_file(path, Some(&mut opts))
+            .map_err(XzeError::Git)?;
+
+        let mut lines = Vec::new();
+        for hunk in blame.iter() {
+            let signature = hunk.final_signature();
+            let commit_hash = hunk.final_commit_id().to_string();
+            let author = signature.name().unwrap_or("Unknown").to_string();
+            let commit_time =
+                DateTime::from_timestamp(signature.when().seconds(), 0).unwrap_or_else(Utc::now);
+
+            let start = hunk.final_start_line();
+            lines.extend((0..hunk.lines_in_hunk()).map(|offset| BlameLine {
+                line: start + offset,
+                commit_hash: commit_hash.clone(),
+                author: author.clone(),
+                commit_time,
+            }));
+        }
+
+        Ok(lines)
+    }
+
+    /// Walk the commit history from HEAD, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `max_count` - Stop after this many commits
+    /// * `since` - Stop at the first commit older than this time
+    pub fn log(
+        &self,
+        repo: &Repository,
+        max_count: Option<usize>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CommitInfo>> {
+        self.walk_history(repo, None, max_count, since)
+    }
+
+    /// Walk the history of a single file from HEAD, newest first
+    ///
+    /// Only commits that change `path` relative to their first parent are
+    /// returned; `max_count` counts those commits, not every commit walked.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    /// * `path` - File path relative to the repository root
+    /// * `max_count` - Stop after this many commits
+    /// * `since` - Stop at the first commit older than this time
+    pub fn log_path(
+        &self,
+        repo: &Repository,
+        path: &Path,
+        max_count: Option<usize>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CommitInfo>> {
+        self.walk_history(repo, Some(path), max_count, since)
+    }
+
+    /// Walk commits from HEAD in time order, stopping as soon as a bound is hit
+    fn walk_history(
+        &self,
+        repo: &Repository,
+        path: Option<&Path>,
+        max_count: Option<usize>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CommitInfo>> {
+        let mut revwalk = repo.revwalk().map_err(XzeError::Git)?;
+        revwalk
+            .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+            .map_err(XzeError::Git)?;
+        revwalk.push_head().map_err(XzeError::Git)?;
+
+        let since = since.map(|time| time.timestamp());
+        let mut commits = Vec::new();
+
+        for oid in revwalk {
+            if max_count.is_some_and(|max| commits.len() >= max) {
+                break;
+            }
+
+            let commit = repo
+                .find_commit(oid.map_err(XzeError::Git)?)
+                .map_err(XzeError::Git)?;
+            if since.is_some_and(|since| commit.time().seconds() < since) {
+                break;
+            }
+            if let Some(path) = path {
+                if !Self::commit_touches_path(&commit, path)? {
+                    continue;
+                }
+            }
+
+            commits.push(CommitInfo::from(&commit));
+        }
+
+        Ok(commits)
+    }
+
+    /// Check whether `commit` changed `path` relative to its first parent
+    fn commit_touches_path(commit: &Commit, path: &Path) -> Result<bool> {
+        let entry_id = |commit: &Commit| -> Result<Option<Oid>> {
+            let tree = commit.tree().map_err(XzeError::Git)?;
+            Ok(tree.get_path(path).ok().map(|entry| entry.id()))
+        };
+
+        let current = entry_id(commit)?;
+        let parent = match commit.parent(0) {
+            Ok(parent) => entry_id(&parent)?,
+            Err(_) => None,
+        };
+
+        Ok(current != parent)
+    }
+
     /// Check if there are uncommitted changes
     ///
     /// # Arguments
@@ -666,6 +1265,31 @@ impl GitOperations {
         Ok(status_map)
     }
 
+    /// Get files with changes staged for the next commit
+    ///
+    /// Covers added, modified, renamed and type-changed paths in the index,
+    /// sorted by path. Deleted files and unstaged changes are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Repository reference
+    pub fn get_staged_files(&self, repo: &Repository) -> Result<Vec<PathBuf>> {
+        let staged = Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE;
+
+        let mut files: Vec<PathBuf> = self
+            .get_status(repo)?
+            .into_iter()
+            .filter(|(_, status)| status.intersects(staged))
+            .map(|(path, _)| path)
+            .collect();
+        files.sort();
+
+        Ok(files)
+    }
+
     /// Check for merge conflicts
     ///
     /// # Arguments
@@ -9,6 +9,7 @@
 //! - PR template generation with Handlebars
 //! - AI-powered description generation
 
+use super::github_app::GitHubAppAuth;
 use crate::{Result, XzeError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -182,24 +183,76 @@ pub enum MergeMethod {
 }
 
 /// Platform detection from repository URL
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum GitPlatform {
     GitHub,
     GitLab,
+    Bitbucket,
     Unknown,
 }
 
 impl GitPlatform {
-    /// Detect platform from repository URL
+    /// Detect platform from the host of a repository URL
+    ///
+    /// Self-hosted instances are recognized when the platform name appears
+    /// in the host, e.g. `gitlab.example.com`.
     pub fn detect(repo_url: &str) -> Self {
-        if repo_url.contains("github.com") {
+        let Some(host) = Self::host(repo_url) else {
+            return GitPlatform::Unknown;
+        };
+
+        if host.contains("github") {
             GitPlatform::GitHub
-        } else if repo_url.contains("gitlab.com") || repo_url.contains("gitlab") {
+        } else if host.contains("gitlab") {
             GitPlatform::GitLab
+        } else if host.contains("bitbucket") {
+            GitPlatform::Bitbucket
         } else {
             GitPlatform::Unknown
         }
     }
+
+    /// Lowercased host of an HTTPS, `ssh://` or scp-style (`git@host:path`) URL
+    pub fn host(repo_url: &str) -> Option<String> {
+        let rest = match repo_url.split_once("://") {
+            Some((_, rest)) => rest,
+            None => repo_url.split_once(':').map(|(host, _)| host)?,
+        };
+        let authority = rest.split('/').next()?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+
+        (!host.is_empty()).then(|| host.to_lowercase())
+    }
+}
+
+impl std::fmt::Display for GitPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GitPlatform::GitHub => "github",
+            GitPlatform::GitLab => "gitlab",
+            GitPlatform::Bitbucket => "bitbucket",
+            GitPlatform::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+impl std::str::FromStr for GitPlatform {
+    type Err = XzeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(GitPlatform::GitHub),
+            "gitlab" => Ok(GitPlatform::GitLab),
+            "bitbucket" => Ok(GitPlatform::Bitbucket),
+            _ => Err(XzeError::validation(format!(
+                "Unknown git platform '{}': expected github, gitlab or bitbucket",
+                s
+            ))),
+        }
+    }
 }
 
 /// PR template builder
@@ -276,22 +329,61 @@ impl Default for PrTemplateBuilder {
     }
 }
 
+/// How the GitHub PR manager authenticates API requests
+#[derive(Clone)]
+enum GitHubAuth {
+    /// Personal access token or other fixed token
+    Token(String),
+    /// Installation token minted for a GitHub App
+    App(GitHubAppAuth),
+}
+
 /// GitHub pull request manager implementation
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct GitHubPrManager {
     client: reqwest::Client,
-    token: String,
+    auth: GitHubAuth,
 }
 
 impl GitHubPrManager {
     /// Create a new GitHub PR manager
     pub fn new(token: String) -> Self {
+        Self::with_auth(GitHubAuth::Token(token))
+    }
+
+    /// Create a GitHub PR manager that authenticates as a GitHub App
+    ///
+    /// Pass the same `GitHubAppAuth` used by the `CredentialStore` for
+    /// pushes so both share one cached installation token.
+    pub fn from_github_app(auth: GitHubAppAuth) -> Self {
+        Self::with_auth(GitHubAuth::App(auth))
+    }
+
+    fn with_auth(auth: GitHubAuth) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("xze-bot/1.0")
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, token }
+        Self { client, auth }
+    }
+
+    /// Authorization header value, minting an installation token if needed
+    async fn authorization(&self) -> Result<String> {
+        let token = match &self.auth {
+            GitHubAuth::Token(token) => token.clone(),
+            GitHubAuth::App(app) => app.token().await?,
+        };
+        Ok(format!("token {}", token))
+    }
+
+    /// Remove the token from text that is about to go into an error
+    fn redact(&self, text: &str) -> String {
+        match &self.auth {
+            GitHubAuth::Token(token) if !token.is_empty() => text.replace(token, "[REDACTED]"),
+            GitHubAuth::Token(_) => text.to_string(),
+            GitHubAuth::App(app) => app.redact(text),
+        }
     }
 
     /// Extract owner and repo from GitHub URL
@@ -323,6 +415,18 @@ impl GitHubPrManager {
     }
 }
 
+impl std::fmt::Debug for GitHubPrManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let auth = match &self.auth {
+            GitHubAuth::Token(_) => "token",
+            GitHubAuth::App(_) => "github_app",
+        };
+        f.debug_struct("GitHubPrManager")
+            .field("auth", &auth)
+            .finish_non_exhaustive()
+    }
+}
+
 impl PullRequestManager for GitHubPrManager {
     async fn create_pr(&self, repo_url: &str, request: CreatePrRequest) -> Result<PullRequest> {
         let (owner, repo) = self.parse_github_url(repo_url)?;
@@ -339,7 +443,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&github_request)
             .send()
@@ -348,7 +452,10 @@ impl PullRequestManager for GitHubPrManager {
 
         if !response.status().is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(XzeError::ai(format!("GitHub API error: {}", error_text)));
+            return Err(XzeError::ai(format!(
+                "GitHub API error: {}",
+                self.redact(&error_text)
+            )));
         }
 
         let pr_data: serde_json::Value = response
@@ -366,7 +473,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .get(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .send()
             .await
@@ -401,7 +508,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .get(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .send()
             .await
@@ -460,7 +567,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .patch(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&update_data)
             .send()
@@ -505,7 +612,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .put(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&merge_data)
             .send()
@@ -530,7 +637,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&comment_data)
             .send()
@@ -564,7 +671,7 @@ impl PullRequestManager for GitHubPrManager {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&review_data)
             .send()
@@ -688,7 +795,7 @@ impl GitHubPrManager {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .json(&labels_data)
             .send()
@@ -714,7 +821,7 @@ impl GitHubPrManager {
         let response = self
             .client
             .delete(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .send()
             .await
@@ -747,7 +854,7 @@ impl GitHubPrManager {
         let pr_data: serde_json::Value = self
             .client
             .get(self.api_url(&owner, &repo, &format!("pulls/{}", pr_number)))
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .send()
             .await
@@ -765,7 +872,7 @@ impl GitHubPrManager {
         let response = self
             .client
             .get(&url)
-            .header("Authorization", format!("token {}", self.token))
+            .header("Authorization", self.authorization().await?)
             .header("Accept", "application/vnd.github.v3+json")
             .send()
             .await
@@ -912,10 +1019,27 @@ mod tests {
             GitPlatform::detect("https://gitlab.example.com/owner/repo"),
             GitPlatform::GitLab
         );
+        assert_eq!(
+            GitPlatform::detect("https://bitbucket.org/team/repo"),
+            GitPlatform::Bitbucket
+        );
+        assert_eq!(
+            GitPlatform::detect("ssh://git@bitbucket.example.com:7999/team/repo.git"),
+            GitPlatform::Bitbucket
+        );
         assert_eq!(
             GitPlatform::detect("https://unknown.com/owner/repo"),
             GitPlatform::Unknown
         );
+        assert_eq!(
+            GitPlatform::detect("https://example.com/github/repo"),
+            GitPlatform::Unknown
+        );
+        assert_eq!(
+            "Bitbucket".parse::<GitPlatform>().unwrap(),
+            GitPlatform::Bitbucket
+        );
+        assert!("svn".parse::<GitPlatform>().is_err());
     }
 
     #[test]
@@ -973,4 +1097,46 @@ mod tests {
 
         assert_eq!(description, "PR: Test PR\nFrom: test-branch");
     }
+
+    #[test]
+    fn test_github_manager_hides_token() {
+        let manager = GitHubPrManager::new("ghp_secret".to_string());
+
+        assert!(!format!("{:?}", manager).contains("ghp_secret"));
+        assert_eq!(
+            manager.redact("bad token ghp_secret"),
+            "bad token [REDACTED]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_github_manager_authorization() {
+        let manager = GitHubPrManager::new("ghp_static".to_string());
+        assert_eq!(manager.authorization().await.unwrap(), "token ghp_static");
+
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/app/installations/678/access_tokens")
+            .with_status(201)
+            .with_body(
+                serde_json::json!({
+                    "token": "ghs_installation",
+                    "expires_at": (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let pem = include_bytes!("../../tests/fixtures/github_app/test_private_key.pem");
+        let auth = GitHubAppAuth::new(12345, 678, pem)
+            .unwrap()
+            .with_api_url(server.url());
+
+        let manager = GitHubPrManager::from_github_app(auth);
+        assert_eq!(
+            manager.authorization().await.unwrap(),
+            "token ghs_installation"
+        );
+        assert_eq!(manager.redact("ghs_installation"), "[REDACTED]");
+    }
 }
@@ -0,0 +1,185 @@
+//! Pull request provider selection
+//!
+//! Picks the GitHub, GitLab or Bitbucket manager for a repository, detected
+//! from the remote URL host or given explicitly, so documentation PRs can be
+//! opened against any supported host through one `PullRequestManager`.
+
+use crate::{Result, XzeError};
+
+use super::bitbucket::BitbucketPrManager;
+use super::gitlab::GitLabPrManager;
+use super::pr::{
+    CreatePrRequest, GitHubPrManager, GitPlatform, MergeMethod, PrState, PrUpdate, PullRequest,
+    PullRequestManager,
+};
+
+/// Pull request manager for whichever platform hosts a repository
+#[derive(Debug, Clone)]
+pub enum PullRequestProvider {
+    GitHub(GitHubPrManager),
+    GitLab(GitLabPrManager),
+    Bitbucket(BitbucketPrManager),
+}
+
+impl PullRequestProvider {
+    /// Select a provider for `repo_url`
+    ///
+    /// `platform` overrides detection from the URL host, for hosts whose
+    /// name does not reveal the platform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xze_core::git::{GitPlatform, PullRequestProvider};
+    ///
+    /// let provider = PullRequestProvider::select(
+    ///     "git@bitbucket.org:team/docs.git",
+    ///     "token".to_string(),
+    ///     None,
+    /// )?;
+    /// assert_eq!(provider.platform(), GitPlatform::Bitbucket);
+    /// # Ok::<(), xze_core::XzeError>(())
+    /// ```
+    pub fn select(repo_url: &str, token: String, platform: Option<GitPlatform>) -> Result<Self> {
+        let platform = platform.unwrap_or_else(|| GitPlatform::detect(repo_url));
+
+        match platform {
+            GitPlatform::GitHub => Ok(Self::GitHub(GitHubPrManager::new(token))),
+            GitPlatform::GitLab => {
+                let manager = match GitPlatform::host(repo_url) {
+                    Some(host) if host != "gitlab.com" => {
+                        GitLabPrManager::new_with_url(token, format!("https://{}", host))
+                    }
+                    _ => GitLabPrManager::new(token),
+                };
+                Ok(Self::GitLab(manager))
+            }
+            GitPlatform::Bitbucket => Ok(Self::Bitbucket(BitbucketPrManager::new(token))),
+            GitPlatform::Unknown => Err(XzeError::validation(format!(
+                "Cannot detect the git platform of {}; set it explicitly",
+                repo_url
+            ))),
+        }
+    }
+
+    /// Platform this provider talks to
+    pub fn platform(&self) -> GitPlatform {
+        match self {
+            Self::GitHub(_) => GitPlatform::GitHub,
+            Self::GitLab(_) => GitPlatform::GitLab,
+            Self::Bitbucket(_) => GitPlatform::Bitbucket,
+        }
+    }
+}
+
+impl PullRequestManager for PullRequestProvider {
+    async fn create_pr(&self, repo_url: &str, request: CreatePrRequest) -> Result<PullRequest> {
+        match self {
+            Self::GitHub(m) => m.create_pr(repo_url, request).await,
+            Self::GitLab(m) => m.create_pr(repo_url, request).await,
+            Self::Bitbucket(m) => m.create_pr(repo_url, request).await,
+        }
+    }
+
+    async fn get_pr(&self, repo_url: &str, pr_number: u64) -> Result<PullRequest> {
+        match self {
+            Self::GitHub(m) => m.get_pr(repo_url, pr_number).await,
+            Self::GitLab(m) => m.get_pr(repo_url, pr_number).await,
+            Self::Bitbucket(m) => m.get_pr(repo_url, pr_number).await,
+        }
+    }
+
+    async fn list_prs(&self, repo_url: &str, state: Option<PrState>) -> Result<Vec<PullRequest>> {
+        match self {
+            Self::GitHub(m) => m.list_prs(repo_url, state).await,
+            Self::GitLab(m) => m.list_prs(repo_url, state).await,
+            Self::Bitbucket(m) => m.list_prs(repo_url, state).await,
+        }
+    }
+
+    async fn update_pr(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        updates: PrUpdate,
+    ) -> Result<PullRequest> {
+        match self {
+            Self::GitHub(m) => m.update_pr(repo_url, pr_number, updates).await,
+            Self::GitLab(m) => m.update_pr(repo_url, pr_number, updates).await,
+            Self::Bitbucket(m) => m.update_pr(repo_url, pr_number, updates).await,
+        }
+    }
+
+    async fn close_pr(&self, repo_url: &str, pr_number: u64) -> Result<()> {
+        match self {
+            Self::GitHub(m) => m.close_pr(repo_url, pr_number).await,
+            Self::GitLab(m) => m.close_pr(repo_url, pr_number).await,
+            Self::Bitbucket(m) => m.close_pr(repo_url, pr_number).await,
+        }
+    }
+
+    async fn merge_pr(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        merge_method: MergeMethod,
+    ) -> Result<()> {
+        match self {
+            Self::GitHub(m) => m.merge_pr(repo_url, pr_number, merge_method).await,
+            Self::GitLab(m) => m.merge_pr(repo_url, pr_number, merge_method).await,
+            Self::Bitbucket(m) => m.merge_pr(repo_url, pr_number, merge_method).await,
+        }
+    }
+
+    async fn add_comment(&self, repo_url: &str, pr_number: u64, comment: &str) -> Result<()> {
+        match self {
+            Self::GitHub(m) => m.add_comment(repo_url, pr_number, comment).await,
+            Self::GitLab(m) => m.add_comment(repo_url, pr_number, comment).await,
+            Self::Bitbucket(m) => m.add_comment(repo_url, pr_number, comment).await,
+        }
+    }
+
+    async fn request_review(
+        &self,
+        repo_url: &str,
+        pr_number: u64,
+        reviewers: Vec<String>,
+    ) -> Result<()> {
+        match self {
+            Self::GitHub(m) => m.request_review(repo_url, pr_number, reviewers).await,
+            Self::GitLab(m) => m.request_review(repo_url, pr_number, reviewers).await,
+            Self::Bitbucket(m) => m.request_review(repo_url, pr_number, reviewers).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_detects_platform() {
+        let cases = [
+            ("https://github.com/owner/repo", GitPlatform::GitHub),
+            ("git@gitlab.com:owner/repo.git", GitPlatform::GitLab),
+            ("https://gitlab.example.com/owner/repo", GitPlatform::GitLab),
+            ("https://bitbucket.org/team/repo", GitPlatform::Bitbucket),
+        ];
+
+        for (url, platform) in cases {
+            let provider = PullRequestProvider::select(url, "token".to_string(), None).unwrap();
+            assert_eq!(provider.platform(), platform, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_select_override() {
+        let url = "https://git.example.com/team/repo";
+        assert!(PullRequestProvider::select(url, "token".to_string(), None).is_err());
+
+        let provider =
+            PullRequestProvider::select(url, "token".to_string(), Some(GitPlatform::GitLab))
+                .unwrap();
+        assert_eq!(provider.platform(), GitPlatform::GitLab);
+    }
+}
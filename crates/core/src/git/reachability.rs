@@ -0,0 +1,195 @@
+//! Repository reachability checks
+//!
+//! Confirms that configured repositories can be used before a pipeline run:
+//! local repositories must exist and open as git repositories, and remote
+//! repositories must answer an `ls-remote` with the configured credentials.
+//! Backs `xze validate --check-repos`.
+
+use super::{credentials_from_env, CredentialStore, GitOperations};
+use crate::config::RepositoryConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Where a checked repository lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepositoryLocation {
+    /// A path on the local filesystem
+    Local,
+    /// A URL reached over the network
+    Remote,
+}
+
+/// Outcome of checking one configured repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryCheck {
+    /// Repository name from the configuration
+    pub name: String,
+    /// Whether the repository was checked as a path or a URL
+    pub location: RepositoryLocation,
+    /// Path or URL that was checked
+    pub target: String,
+    /// Whether the repository is usable
+    pub reachable: bool,
+    /// What was found, or why the check failed
+    pub message: String,
+}
+
+/// Check that a configured repository is reachable
+///
+/// `file://` URLs and an existing `local_path` are checked on disk: the path
+/// must exist and open as a git repository. Any other URL is contacted with
+/// `ls-remote`, using the repository's credentials if configured and the
+/// `GIT_*` environment variables otherwise. Nothing is cloned or fetched.
+pub fn check_repository(config: &RepositoryConfig) -> RepositoryCheck {
+    let local = match &config.local_path {
+        Some(path) if path.exists() => Some(path.clone()),
+        _ if config.url.scheme() == "file" => Some(
+            config
+                .url
+                .to_file_path()
+                .unwrap_or_else(|_| PathBuf::from(config.url.path())),
+        ),
+        _ => None,
+    };
+
+    match local {
+        Some(path) => check_local(&config.name, &path),
+        None => check_remote(config),
+    }
+}
+
+/// Check every configured repository, in configuration order
+pub fn check_repositories(configs: &[RepositoryConfig]) -> Vec<RepositoryCheck> {
+    configs.iter().map(check_repository).collect()
+}
+
+fn check_local(name: &str, path: &Path) -> RepositoryCheck {
+    tracing::debug!("Checking local repository {} at {}", name, path.display());
+
+    let (reachable, message) = if !path.exists() {
+        (false, "Path does not exist".to_string())
+    } else {
+        match GitOperations::new(CredentialStore::new()).open(path) {
+            Ok(_) => (true, "Git repository found".to_string()),
+            Err(e) => (false, format!("Not a git repository: {}", e)),
+        }
+    };
+
+    RepositoryCheck {
+        name: name.to_string(),
+        location: RepositoryLocation::Local,
+        target: path.display().to_string(),
+        reachable,
+        message,
+    }
+}
+
+fn check_remote(config: &RepositoryConfig) -> RepositoryCheck {
+    tracing::debug!(
+        "Checking remote repository {} at {}",
+        config.name,
+        config.url
+    );
+
+    let credentials = config
+        .credentials
+        .as_ref()
+        .map(CredentialStore::from_config)
+        .unwrap_or_else(credentials_from_env);
+
+    let (reachable, message) = match GitOperations::new(credentials).ls_remote(config.url.as_str())
+    {
+        Ok(refs) => (true, format!("{} references advertised", refs.len())),
+        Err(e) => (false, e.to_string()),
+    };
+
+    RepositoryCheck {
+        name: config.name.clone(),
+        location: RepositoryLocation::Remote,
+        target: config.url.to_string(),
+        reachable,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use url::Url;
+
+    fn repo_config(url: Url, local_path: Option<PathBuf>) -> RepositoryConfig {
+        RepositoryConfig {
+            name: "example".to_string(),
+            url,
+            language: None,
+            watch_branches: vec!["main".to_string()],
+            local_path,
+            credentials: None,
+            custom: Default::default(),
+            group: None,
+            tags: Vec::new(),
+            auto_update: true,
+            exclude_dirs: Vec::new(),
+            exclude_patterns: Vec::new(),
+        }
+    }
+
+    fn init_with_commit(path: &Path) {
+        let repo = git2::Repository::init(path).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_local_repository_checks() {
+        let temp = tempdir().unwrap();
+        let repo_dir = temp.path().join("repo");
+        init_with_commit(&repo_dir);
+        let plain_dir = temp.path().join("plain");
+        std::fs::create_dir(&plain_dir).unwrap();
+
+        let check = check_repository(&repo_config(Url::from_file_path(&repo_dir).unwrap(), None));
+        assert_eq!(check.location, RepositoryLocation::Local);
+        assert!(check.reachable, "{}", check.message);
+
+        let check = check_repository(&repo_config(
+            Url::from_file_path(temp.path().join("missing")).unwrap(),
+            None,
+        ));
+        assert!(!check.reachable);
+        assert_eq!(check.message, "Path does not exist");
+
+        let check = check_repository(&repo_config(
+            Url::parse("https://example.com/repo.git").unwrap(),
+            Some(plain_dir),
+        ));
+        assert_eq!(check.location, RepositoryLocation::Local);
+        assert!(!check.reachable);
+        assert!(check.message.starts_with("Not a git repository"));
+    }
+
+    #[test]
+    fn test_remote_check_uses_ls_remote() {
+        let temp = tempdir().unwrap();
+        init_with_commit(temp.path());
+        let url = Url::from_file_path(temp.path()).unwrap();
+
+        let refs = GitOperations::new(CredentialStore::new())
+            .ls_remote(url.as_str())
+            .unwrap();
+        assert!(refs.iter().any(|r| r == "HEAD"));
+
+        // Nothing listens on port 1, so the connection is refused
+        let check = check_repository(&repo_config(
+            Url::parse("http://127.0.0.1:1/repo.git").unwrap(),
+            None,
+        ));
+        assert_eq!(check.location, RepositoryLocation::Remote);
+        assert!(!check.reachable);
+    }
+}
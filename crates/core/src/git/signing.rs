@@ -0,0 +1,69 @@
+//! Commit signing
+//!
+//! Signs commit buffers with an external program, the same way `git` does:
+//! `gpg` produces an armored detached OpenPGP signature and `ssh-keygen -Y sign`
+//! produces an SSH signature. The buffer is passed on stdin and the signature
+//! is read from stdout.
+
+use crate::config::{SigningConfig, SigningFormat};
+use crate::{error::Result, XzeError};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Namespace git uses for SSH commit signatures
+const SSH_SIGNATURE_NAMESPACE: &str = "git";
+
+/// Sign a commit buffer, returning the signature text
+///
+/// # Arguments
+///
+/// * `config` - Signing format and key
+/// * `buffer` - Commit content from `Repository::commit_create_buffer`
+pub fn sign_buffer(config: &SigningConfig, buffer: &str) -> Result<String> {
+    let mut command = match config.format {
+        SigningFormat::Gpg => {
+            let mut command = Command::new(config.program.as_deref().unwrap_or("gpg"));
+            command.args(["--batch", "--armor", "--detach-sign", "--local-user"]);
+            command.arg(&config.key);
+            command
+        }
+        SigningFormat::Ssh => {
+            let mut command = Command::new(config.program.as_deref().unwrap_or("ssh-keygen"));
+            command.args(["-Y", "sign", "-n", SSH_SIGNATURE_NAMESPACE, "-f"]);
+            command.arg(&config.key);
+            command
+        }
+    };
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| XzeError::repository(format!("Failed to start commit signer: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(buffer.as_bytes()).map_err(|e| {
+            XzeError::repository(format!("Failed to write commit to signer: {}", e))
+        })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| XzeError::repository(format!("Commit signer failed: {}", e)))?;
+    if !output.status.success() {
+        return Err(XzeError::repository(format!(
+            "Commit signer exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let signature = String::from_utf8(output.stdout)
+        .map_err(|_| XzeError::repository("Commit signature is not valid UTF-8"))?;
+    if signature.trim().is_empty() {
+        return Err(XzeError::repository("Commit signer produced no signature"));
+    }
+
+    Ok(signature)
+}
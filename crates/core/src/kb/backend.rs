@@ -0,0 +1,229 @@
+//! Storage backend abstraction for knowledge base operations
+//!
+//! This module defines the `KbBackend` trait that every knowledge base
+//! storage implementation must satisfy. The incremental loader and other
+//! callers only depend on this trait, so the PostgreSQL store and the
+//! in-memory store can be swapped without changing caller code.
+
+use crate::kb::error::Result;
+use crate::kb::hash::FileMetadata;
+use crate::kb::saved_search::{NewSavedSearch, SavedSearchChanges, SavedSearchRecord};
+use crate::kb::store::DocumentChunk;
+use crate::semantic::types::SemanticChunk;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// Storage backend selection for the knowledge base
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::kb::backend::KbBackendKind;
+///
+/// assert_eq!(KbBackendKind::default(), KbBackendKind::Postgres);
+/// assert_eq!("memory".parse::<KbBackendKind>().unwrap(), KbBackendKind::Memory);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KbBackendKind {
+    /// PostgreSQL-backed store (default)
+    #[default]
+    Postgres,
+    /// Process-local in-memory store, contents are lost on drop
+    Memory,
+}
+
+impl fmt::Display for KbBackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KbBackendKind::Postgres => write!(f, "postgres"),
+            KbBackendKind::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+impl std::str::FromStr for KbBackendKind {
+    type Err = crate::kb::error::KbError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(KbBackendKind::Postgres),
+            "memory" | "in-memory" => Ok(KbBackendKind::Memory),
+            other => Err(crate::kb::error::KbError::config(format!(
+                "Unknown knowledge base backend: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Result of [`KbBackend::vacuum`]
+///
+/// `bytes_reclaimed` counts the content and embedding bytes of the removed
+/// chunks, so it is comparable across backends whatever their on-disk
+/// layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupStats {
+    /// Number of files whose chunks were removed
+    pub files_removed: usize,
+    /// Number of document and semantic chunks removed
+    pub chunks_removed: usize,
+    /// Bytes of chunk content and embeddings removed
+    pub bytes_reclaimed: u64,
+}
+
+/// Operations required from a knowledge base storage backend
+///
+/// Covers document chunk CRUD, semantic chunk CRUD, the queries used by
+/// the incremental loader, and per-user saved searches. Implementations
+/// must behave identically from the caller's point of view, including error
+/// cases such as duplicate semantic chunk indices for the same file.
+#[async_trait]
+pub trait KbBackend: Send + Sync {
+    /// Return every stored file path mapped to its content hash
+    async fn query_existing_files(&self) -> Result<HashMap<String, String>>;
+
+    /// Return the size and modification time recorded for stored files
+    ///
+    /// The loader reuses a stored hash without reading the file when both
+    /// still match. Backends that do not record metadata return an empty
+    /// map, which turns the fast path off.
+    async fn query_file_metadata(&self) -> Result<HashMap<String, FileMetadata>> {
+        Ok(HashMap::new())
+    }
+
+    /// Record the size and modification time of a stored file
+    ///
+    /// Writing new chunks for a file clears its recorded metadata, so this
+    /// is called after the chunks are stored.
+    async fn record_file_metadata(&self, _file_path: &Path, _metadata: FileMetadata) -> Result<()> {
+        Ok(())
+    }
+
+    /// Delete all document chunks for a file, returning the number removed
+    async fn delete_chunks_for_file(&self, file_path: &Path) -> Result<u64>;
+
+    /// Insert document chunks for a file
+    async fn insert_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()>;
+
+    /// Atomically replace the document chunks for a file
+    async fn update_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()>;
+
+    /// Remove chunks for files that no longer exist, returning the number removed
+    async fn cleanup_deleted_files(&self, deleted_files: &[String]) -> Result<u64>;
+
+    /// Remove every chunk of orphaned files, then reclaim storage space
+    ///
+    /// A stored file is orphaned if it is listed in `deleted_files`, usually
+    /// the files categorized as [`Delete`](crate::kb::FileCategory::Delete),
+    /// or no longer exists on disk. Document and semantic chunks are removed
+    /// in a single transaction, so an interrupted run removes nothing.
+    async fn vacuum(&self, deleted_files: &[String]) -> Result<CleanupStats>;
+
+    /// Insert semantic chunks for a file
+    async fn store_semantic_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[SemanticChunk],
+    ) -> Result<()>;
+
+    /// Delete all semantic chunks for a file, returning the number removed
+    async fn delete_semantic_chunks_for_file(&self, file_path: &Path) -> Result<u64>;
+
+    /// Return the semantic chunks for a file ordered by chunk index
+    async fn get_semantic_chunks_for_file(&self, file_path: &Path) -> Result<Vec<SemanticChunk>>;
+
+    /// Store a saved search, assigning its id and timestamps
+    async fn create_saved_search(&self, search: NewSavedSearch) -> Result<SavedSearchRecord>;
+
+    /// Return a user's saved searches, newest first
+    async fn list_saved_searches(&self, user_id: &str) -> Result<Vec<SavedSearchRecord>>;
+
+    /// Return a saved search if it exists and belongs to `user_id`
+    async fn get_saved_search(&self, user_id: &str, id: i32) -> Result<Option<SavedSearchRecord>>;
+
+    /// Apply `changes` to a user's saved search, returning the updated
+    /// search or `None` if the user has no search with that id
+    async fn update_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+        changes: SavedSearchChanges,
+    ) -> Result<Option<SavedSearchRecord>>;
+
+    /// Delete a user's saved search, returning whether it existed
+    async fn delete_saved_search(&self, user_id: &str, id: i32) -> Result<bool>;
+}
+
+/// Stored files that [`KbBackend::vacuum`] removes, in sorted order
+pub(crate) fn orphaned_files<'a, I>(stored: I, deleted_files: &[String]) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let deleted: HashSet<&String> = deleted_files.iter().collect();
+    let orphaned: BTreeSet<&String> = stored
+        .into_iter()
+        .filter(|path| deleted.contains(path) || !Path::new(path.as_str()).exists())
+        .collect();
+    orphaned.into_iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_kind_default() {
+        assert_eq!(KbBackendKind::default(), KbBackendKind::Postgres);
+    }
+
+    #[test]
+    fn test_backend_kind_from_str() {
+        assert_eq!(
+            "postgres".parse::<KbBackendKind>().unwrap(),
+            KbBackendKind::Postgres
+        );
+        assert_eq!(
+            "Memory".parse::<KbBackendKind>().unwrap(),
+            KbBackendKind::Memory
+        );
+        assert!("sqlite".parse::<KbBackendKind>().is_err());
+    }
+
+    #[test]
+    fn test_orphaned_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("present.md");
+        std::fs::write(&present, "still here").unwrap();
+        let present = present.to_string_lossy().to_string();
+        let missing = dir.path().join("missing.md").to_string_lossy().to_string();
+
+        let stored = vec![present.clone(), missing.clone()];
+        assert_eq!(orphaned_files(&stored, &[]), vec![missing.clone()]);
+        assert_eq!(
+            orphaned_files(&stored, &[present.clone(), "unknown.md".to_string()]),
+            vec![missing, present]
+        );
+    }
+
+    #[test]
+    fn test_backend_kind_display_roundtrip() {
+        for kind in [KbBackendKind::Postgres, KbBackendKind::Memory] {
+            assert_eq!(kind.to_string().parse::<KbBackendKind>().unwrap(), kind);
+        }
+    }
+}
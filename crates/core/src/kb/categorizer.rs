@@ -5,7 +5,7 @@
 //! and Delete (removed from filesystem but still in database).
 
 use crate::kb::error::{KbError, Result};
-use crate::kb::hash::calculate_file_hash;
+use crate::kb::hash::{calculate_file_hash_with, FileMetadata, HashAlgorithm};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::{debug, info};
@@ -15,7 +15,7 @@ use walkdir::WalkDir;
 ///
 /// This enum determines what action to take for each file
 /// during the loading process.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileCategory {
     /// File exists and hash matches - skip processing
     Skip,
@@ -90,6 +90,40 @@ impl CategorizedFiles {
     }
 }
 
+/// Files found on disk with their hashes and metadata
+#[derive(Debug, Default, Clone)]
+pub struct DiscoveredFiles {
+    /// File path to content hash
+    pub hashes: HashMap<String, String>,
+    /// File path to size and modification time, where available
+    pub metadata: HashMap<String, FileMetadata>,
+    /// Number of files whose stored hash was reused without reading them
+    pub hashes_reused: usize,
+}
+
+/// Stored state used to skip hashing unchanged files
+///
+/// A stored hash is reused when the file's current size and modification
+/// time equal the recorded metadata.
+#[derive(Debug, Clone, Copy)]
+pub struct KnownFiles<'a> {
+    /// File path to stored content hash
+    pub hashes: &'a HashMap<String, String>,
+    /// File path to recorded size and modification time
+    pub metadata: &'a HashMap<String, FileMetadata>,
+}
+
+impl<'a> KnownFiles<'a> {
+    fn reusable_hash(self, path: &str, metadata: Option<&FileMetadata>) -> Option<&'a String> {
+        let metadata = metadata?;
+        if self.metadata.get(path) == Some(metadata) {
+            self.hashes.get(path)
+        } else {
+            None
+        }
+    }
+}
+
 /// File categorizer for determining which files need processing
 ///
 /// This struct compares current filesystem state with database state
@@ -132,7 +166,42 @@ impl FileCategorizer {
     /// # }
     /// ```
     pub async fn discover_files_with_hashes(paths: &[String]) -> Result<HashMap<String, String>> {
-        let mut discovered_files = HashMap::new();
+        let discovered = Self::discover_files(paths, HashAlgorithm::Sha256, None).await?;
+        Ok(discovered.hashes)
+    }
+
+    /// Discover files and hash them with `algorithm`
+    ///
+    /// When `known` is given, a file whose size and modification time match
+    /// its recorded metadata keeps its stored hash and is not read. Anything
+    /// else, including files without recorded metadata, is hashed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::InvalidPath` if a path does not exist
+    /// Returns `KbError::HashCalculation` if an explicitly listed file cannot
+    /// be hashed; unreadable files found while walking a directory are
+    /// skipped
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use xze_core::kb::categorizer::FileCategorizer;
+    /// use xze_core::kb::hash::HashAlgorithm;
+    ///
+    /// # async fn example() -> xze_core::kb::error::Result<()> {
+    /// let paths = vec!["./docs".to_string()];
+    /// let files = FileCategorizer::discover_files(&paths, HashAlgorithm::Blake3, None).await?;
+    /// println!("Discovered {} files", files.hashes.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn discover_files(
+        paths: &[String],
+        algorithm: HashAlgorithm,
+        known: Option<KnownFiles<'_>>,
+    ) -> Result<DiscoveredFiles> {
+        let mut discovered = DiscoveredFiles::default();
 
         for path_str in paths {
             let path = Path::new(path_str);
@@ -141,8 +210,7 @@ impl FileCategorizer {
             }
 
             if path.is_file() {
-                let hash = calculate_file_hash(path).await?;
-                discovered_files.insert(path_str.clone(), hash);
+                Self::discover_file(&mut discovered, path_str.clone(), algorithm, known).await?;
             } else if path.is_dir() {
                 for entry in WalkDir::new(path)
                     .follow_links(false)
@@ -161,22 +229,56 @@ impl FileCategorizer {
                             })?
                             .to_string();
 
-                        match calculate_file_hash(file_path).await {
-                            Ok(hash) => {
-                                discovered_files.insert(path_string.clone(), hash);
-                                debug!("Discovered file: {} with hash", path_string);
-                            }
-                            Err(e) => {
-                                debug!("Skipping file {} due to error: {}", path_string, e);
-                            }
+                        if let Err(e) = Self::discover_file(
+                            &mut discovered,
+                            path_string.clone(),
+                            algorithm,
+                            known,
+                        )
+                        .await
+                        {
+                            debug!("Skipping file {} due to error: {}", path_string, e);
                         }
                     }
                 }
             }
         }
 
-        info!("Discovered {} files in total", discovered_files.len());
-        Ok(discovered_files)
+        info!(
+            "Discovered {} files in total ({} hashes reused)",
+            discovered.hashes.len(),
+            discovered.hashes_reused
+        );
+        Ok(discovered)
+    }
+
+    /// Hash one file, or reuse its stored hash if its metadata is unchanged
+    async fn discover_file(
+        discovered: &mut DiscoveredFiles,
+        path: String,
+        algorithm: HashAlgorithm,
+        known: Option<KnownFiles<'_>>,
+    ) -> Result<()> {
+        let metadata = FileMetadata::read(&path).await;
+
+        let hash = match known.and_then(|known| known.reusable_hash(&path, metadata.as_ref())) {
+            Some(hash) => {
+                debug!("Reusing stored hash for unchanged file: {}", path);
+                discovered.hashes_reused += 1;
+                hash.clone()
+            }
+            None => {
+                let hash = calculate_file_hash_with(&path, algorithm).await?;
+                debug!("Discovered file: {} with hash", path);
+                hash
+            }
+        };
+
+        if let Some(metadata) = metadata {
+            discovered.metadata.insert(path.clone(), metadata);
+        }
+        discovered.hashes.insert(path, hash);
+        Ok(())
     }
 
     /// Create a new file categorizer
@@ -270,6 +372,28 @@ impl FileCategorizer {
         result
     }
 
+    /// Categorize a single file from its current and stored hashes
+    ///
+    /// Used when files arrive one at a time, such as records streamed on
+    /// stdin, instead of as a discovered set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::kb::categorizer::{FileCategorizer, FileCategory};
+    ///
+    /// assert_eq!(FileCategorizer::categorize_one("h1", None), FileCategory::Add);
+    /// assert_eq!(FileCategorizer::categorize_one("h1", Some("h1")), FileCategory::Skip);
+    /// assert_eq!(FileCategorizer::categorize_one("h2", Some("h1")), FileCategory::Update);
+    /// ```
+    pub fn categorize_one(current_hash: &str, existing_hash: Option<&str>) -> FileCategory {
+        match existing_hash {
+            Some(existing) if existing == current_hash => FileCategory::Skip,
+            Some(_) => FileCategory::Update,
+            None => FileCategory::Add,
+        }
+    }
+
     /// Log a summary of categorization results
     ///
     /// Logs the counts of files in each category at info level.
@@ -446,6 +570,43 @@ mod tests {
         assert!(matches!(result.unwrap_err(), KbError::InvalidPath { .. }));
     }
 
+    #[tokio::test]
+    async fn test_discover_files_reuses_hash_when_metadata_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let unchanged = dir.path().join("unchanged.md");
+        let touched = dir.path().join("touched.md");
+        std::fs::write(&unchanged, "same").unwrap();
+        std::fs::write(&touched, "same").unwrap();
+        let unchanged = unchanged.to_string_lossy().to_string();
+        let touched = touched.to_string_lossy().to_string();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let first = FileCategorizer::discover_files(&paths, HashAlgorithm::Xxh3, None)
+            .await
+            .unwrap();
+        assert_eq!(first.hashes.len(), 2);
+        assert_eq!(first.hashes_reused, 0);
+        assert_eq!(first.hashes[&unchanged].len(), 16);
+
+        // A stale stored hash is trusted while the metadata still matches,
+        // which shows the file was not read
+        let mut hashes = first.hashes.clone();
+        hashes.insert(unchanged.clone(), "stored".to_string());
+        let mut metadata = first.metadata.clone();
+        metadata.get_mut(&touched).unwrap().modified_ns -= 1;
+        let known = KnownFiles {
+            hashes: &hashes,
+            metadata: &metadata,
+        };
+
+        let second = FileCategorizer::discover_files(&paths, HashAlgorithm::Xxh3, Some(known))
+            .await
+            .unwrap();
+        assert_eq!(second.hashes_reused, 1);
+        assert_eq!(second.hashes[&unchanged], "stored");
+        assert_eq!(second.hashes[&touched], first.hashes[&touched]);
+    }
+
     #[test]
     fn test_log_summary_does_not_panic() {
         let mut categorized = CategorizedFiles::new();
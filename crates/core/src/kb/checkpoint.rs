@@ -0,0 +1,158 @@
+//! Checkpoints for resuming interrupted loads
+//!
+//! A checkpoint lists the files a load has finished, with the content hash
+//! each was loaded at. [`IncrementalLoader`](crate::kb::loader::IncrementalLoader)
+//! writes it periodically and when cancelled, and on the next run skips
+//! files whose hash is unchanged since they were checkpointed. The file is
+//! removed once a load completes.
+//!
+//! Checkpoints are JSON:
+//!
+//! ```text
+//! {"completed": {"docs/guide.md": "9f86d08..."}}
+//! ```
+
+use crate::kb::error::{KbError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Files completed by an interrupted load
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadCheckpoint {
+    /// Content hash of each completed file, by path
+    pub completed: HashMap<String, String>,
+}
+
+impl LoadCheckpoint {
+    /// Create an empty checkpoint
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the checkpoint at `path`
+    ///
+    /// A missing file is an empty checkpoint, so the first run of a load
+    /// needs no special handling.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Io` if the file cannot be read and
+    /// `KbError::Loader` if it is not a checkpoint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::kb::checkpoint::LoadCheckpoint;
+    ///
+    /// # async fn example() -> xze_core::kb::error::Result<()> {
+    /// let dir = tempfile::tempdir()?;
+    /// let path = dir.path().join("load.checkpoint");
+    /// assert!(LoadCheckpoint::read(&path).await?.is_empty());
+    ///
+    /// let mut checkpoint = LoadCheckpoint::new();
+    /// checkpoint.record("docs/a.md", "abc");
+    /// checkpoint.write(&path).await?;
+    /// assert!(LoadCheckpoint::read(&path).await?.contains("docs/a.md", "abc"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read(path: &Path) -> Result<Self> {
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&contents)
+            .map_err(|e| KbError::loader(format!("Invalid checkpoint {}: {}", path.display(), e)))
+    }
+
+    /// Write the checkpoint to `path`
+    ///
+    /// The checkpoint is written to a temporary file that then replaces
+    /// `path`, so a crash mid-write leaves the previous checkpoint intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Io` if the file cannot be written
+    pub async fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| KbError::loader(format!("Failed to serialize checkpoint: {}", e)))?;
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        tokio::fs::write(&temp, json).await?;
+        tokio::fs::rename(&temp, path).await?;
+        Ok(())
+    }
+
+    /// Delete the checkpoint at `path`, if there is one
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Io` if an existing file cannot be removed
+    pub async fn remove(path: &Path) -> Result<()> {
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record that `file` was loaded with content hash `hash`
+    pub fn record(&mut self, file: &str, hash: &str) {
+        self.completed.insert(file.to_string(), hash.to_string());
+    }
+
+    /// Whether `file` was loaded with content hash `hash`
+    ///
+    /// A file changed since it was checkpointed has a different hash and
+    /// must be loaded again.
+    pub fn contains(&self, file: &str, hash: &str) -> bool {
+        self.completed.get(file).is_some_and(|h| h == hash)
+    }
+
+    /// Number of completed files
+    pub fn len(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Whether no files are completed
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_checkpoint_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("load.checkpoint");
+
+        let mut checkpoint = LoadCheckpoint::new();
+        checkpoint.record("a.md", "hash-a");
+        checkpoint.record("b.md", "hash-b");
+        checkpoint.write(&path).await.unwrap();
+
+        let read = LoadCheckpoint::read(&path).await.unwrap();
+        assert_eq!(read, checkpoint);
+        assert!(read.contains("a.md", "hash-a"));
+        assert!(!read.contains("a.md", "changed"));
+        assert!(!read.contains("c.md", "hash-a"));
+
+        LoadCheckpoint::remove(&path).await.unwrap();
+        assert!(!path.exists());
+        LoadCheckpoint::remove(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_rejects_invalid_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("load.checkpoint");
+        std::fs::write(&path, "not json").unwrap();
+
+        let err = LoadCheckpoint::read(&path).await.unwrap_err();
+        assert!(matches!(err, KbError::Loader(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,283 @@
+//! Text encoding and natural language of loaded documents
+//!
+//! Chunking, embedding and storage all work on UTF-8 text. Documents
+//! written on other systems may be UTF-16 or Latin-1, so the loader
+//! decodes every file with [`decode`] before chunking:
+//!
+//! - a byte order mark selects UTF-8, UTF-16LE or UTF-16BE
+//! - without one, text with many NUL bytes in alternating positions is
+//!   UTF-16, valid UTF-8 is UTF-8, and anything else is Latin-1
+//!   (Windows-1252)
+//!
+//! Bytes that are not valid in the detected encoding, such as an odd-length
+//! UTF-16 file or Latin-1 text containing control characters, are reported
+//! as errors instead of being replaced. [`EncodingMode::Utf8`] turns
+//! detection off and accepts only UTF-8.
+//!
+//! [`detect_language`] names the natural language of the decoded text with
+//! an ISO 639-3 code such as `eng` or `deu`. The loader records the
+//! encoding and language in chunk metadata, and search can filter on the
+//! language.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::kb::encoding::{decode, EncodingMode, TextEncoding};
+//!
+//! let utf16: Vec<u8> = [0xFF, 0xFE]
+//!     .into_iter()
+//!     .chain("Café".encode_utf16().flat_map(u16::to_le_bytes))
+//!     .collect();
+//! let (text, encoding) = decode(&utf16, EncodingMode::Detect).unwrap();
+//! assert_eq!(text, "Café");
+//! assert_eq!(encoding, TextEncoding::Utf16Le);
+//!
+//! let (text, encoding) = decode(b"Caf\xe9", EncodingMode::Detect).unwrap();
+//! assert_eq!(text, "Café");
+//! assert_eq!(encoding, TextEncoding::Latin1);
+//!
+//! assert!(decode(b"Caf\xe9", EncodingMode::Utf8).is_err());
+//! ```
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Share of NUL bytes in one byte position that marks BOM-less UTF-16
+const UTF16_NUL_RATIO: f64 = 0.3;
+
+/// Encoding a document was stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    /// UTF-8, with or without a byte order mark
+    Utf8,
+    /// UTF-16, little-endian
+    Utf16Le,
+    /// UTF-16, big-endian
+    Utf16Be,
+    /// ISO-8859-1, decoded as its Windows-1252 superset
+    Latin1,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TextEncoding::Utf8 => "utf-8",
+            TextEncoding::Utf16Le => "utf-16le",
+            TextEncoding::Utf16Be => "utf-16be",
+            TextEncoding::Latin1 => "latin-1",
+        };
+        f.pad(name)
+    }
+}
+
+impl TextEncoding {
+    fn encoding(self) -> &'static Encoding {
+        match self {
+            TextEncoding::Utf8 => UTF_8,
+            TextEncoding::Utf16Le => UTF_16LE,
+            TextEncoding::Utf16Be => UTF_16BE,
+            TextEncoding::Latin1 => WINDOWS_1252,
+        }
+    }
+}
+
+/// How the loader decodes document bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncodingMode {
+    /// Detect UTF-8, UTF-16 and Latin-1 and transcode to UTF-8
+    #[default]
+    Detect,
+    /// Accept only UTF-8
+    Utf8,
+}
+
+/// Decode `bytes` to UTF-8 text
+///
+/// Strips a byte order mark and returns the text with the encoding it was
+/// read as.
+///
+/// # Errors
+///
+/// Returns a description of the problem if the bytes are malformed in the
+/// detected encoding, or are not UTF-8 in [`EncodingMode::Utf8`]
+pub fn decode(bytes: &[u8], mode: EncodingMode) -> Result<(String, TextEncoding), String> {
+    let (encoding, body) = match Encoding::for_bom(bytes) {
+        Some((bom, length)) if bom == UTF_8 => (TextEncoding::Utf8, &bytes[length..]),
+        Some((bom, length)) if mode == EncodingMode::Detect => {
+            let encoding = if bom == UTF_16LE {
+                TextEncoding::Utf16Le
+            } else {
+                TextEncoding::Utf16Be
+            };
+            (encoding, &bytes[length..])
+        }
+        _ => (detect(bytes, mode), bytes),
+    };
+
+    let text = encoding
+        .encoding()
+        .decode_without_bom_handling_and_without_replacement(body)
+        .ok_or_else(|| match mode {
+            EncodingMode::Utf8 => "not valid UTF-8".to_string(),
+            EncodingMode::Detect => format!("malformed {} text", encoding),
+        })?;
+
+    if encoding == TextEncoding::Latin1 {
+        if let Some(c) = text.chars().find(|c| is_unexpected_control(*c)) {
+            return Err(format!(
+                "neither UTF-8 nor Latin-1 text (contains control character U+{:04X})",
+                c as u32
+            ));
+        }
+    }
+
+    Ok((text.into_owned(), encoding))
+}
+
+/// Guess the encoding of text without a byte order mark
+fn detect(bytes: &[u8], mode: EncodingMode) -> TextEncoding {
+    if mode == EncodingMode::Utf8 {
+        return TextEncoding::Utf8;
+    }
+    if bytes.len() >= 2 && bytes.contains(&0) {
+        let pairs = bytes.len() / 2;
+        let nul_ratio = |offset: usize| {
+            bytes
+                .iter()
+                .skip(offset)
+                .step_by(2)
+                .filter(|b| **b == 0)
+                .count() as f64
+                / pairs as f64
+        };
+        let (even, odd) = (nul_ratio(0), nul_ratio(1));
+        if odd >= UTF16_NUL_RATIO && even < UTF16_NUL_RATIO {
+            return TextEncoding::Utf16Le;
+        }
+        if even >= UTF16_NUL_RATIO && odd < UTF16_NUL_RATIO {
+            return TextEncoding::Utf16Be;
+        }
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        TextEncoding::Utf8
+    } else {
+        TextEncoding::Latin1
+    }
+}
+
+/// Control characters that do not occur in text documents
+fn is_unexpected_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0c')
+}
+
+/// ISO 639-3 code of the natural language of `text`
+///
+/// Returns `None` when the language cannot be identified reliably, for
+/// example because the text is too short or mostly code.
+pub fn detect_language(text: &str) -> Option<String> {
+    whatlang::detect(text)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang().code().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+        let units = bom.then_some('\u{feff}').into_iter().chain(text.chars());
+        let text: String = units.collect();
+        text.encode_utf16()
+            .flat_map(|u| {
+                if big_endian {
+                    u.to_be_bytes()
+                } else {
+                    u.to_le_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(
+            decode("Grüße".as_bytes(), EncodingMode::Detect).unwrap(),
+            ("Grüße".to_string(), TextEncoding::Utf8)
+        );
+        let with_bom = [&[0xEF, 0xBB, 0xBF][..], "Grüße".as_bytes()].concat();
+        assert_eq!(
+            decode(&with_bom, EncodingMode::Utf8).unwrap().0,
+            "Grüße".to_string()
+        );
+    }
+
+    #[test]
+    fn test_decode_utf16_with_and_without_bom() {
+        let text = "# Übersicht\n\nDie Datei ist in UTF-16 gespeichert.";
+        for (big_endian, expected) in [
+            (false, TextEncoding::Utf16Le),
+            (true, TextEncoding::Utf16Be),
+        ] {
+            for bom in [true, false] {
+                let bytes = utf16(text, big_endian, bom);
+                assert_eq!(
+                    decode(&bytes, EncodingMode::Detect).unwrap(),
+                    (text.to_string(), expected),
+                    "big_endian={} bom={}",
+                    big_endian,
+                    bom
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_latin1() {
+        let (text, encoding) = decode(b"na\xefve r\xe9sum\xe9\r\n", EncodingMode::Detect).unwrap();
+        assert_eq!(text, "naïve résumé\r\n");
+        assert_eq!(encoding, TextEncoding::Latin1);
+        assert_eq!(encoding.to_string(), "latin-1");
+    }
+
+    #[test]
+    fn test_malformed_text_is_reported() {
+        let mut odd = utf16("Hello", false, true);
+        odd.push(b'!');
+        assert_eq!(
+            decode(&odd, EncodingMode::Detect).unwrap_err(),
+            "malformed utf-16le text"
+        );
+
+        let binary = b"\x89PNG\r\n\x1a\n\xff\x01";
+        assert!(decode(binary, EncodingMode::Detect)
+            .unwrap_err()
+            .contains("control character"));
+
+        assert_eq!(
+            decode(&utf16("Hello", false, true), EncodingMode::Utf8).unwrap_err(),
+            "not valid UTF-8"
+        );
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language(
+                "The loader reads every document, splits it into chunks and stores them for search."
+            )
+            .as_deref(),
+            Some("eng")
+        );
+        assert_eq!(
+            detect_language(
+                "Der Lader liest jedes Dokument, teilt es in Abschnitte und speichert sie für die Suche."
+            )
+            .as_deref(),
+            Some("deu")
+        );
+        assert_eq!(detect_language("fn main() {}"), None);
+    }
+}
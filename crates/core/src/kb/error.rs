@@ -83,6 +83,10 @@ pub enum KbError {
     /// Transaction errors
     #[error("Transaction error: {0}")]
     Transaction(String),
+
+    /// File content is not text in a supported encoding
+    #[error("Encoding error in {path}: {reason}")]
+    Encoding { path: String, reason: String },
 }
 
 impl KbError {
@@ -149,6 +153,14 @@ impl KbError {
     pub fn transaction<S: Into<String>>(message: S) -> Self {
         Self::Transaction(message.into())
     }
+
+    /// Create an encoding error
+    pub fn encoding<S1: Into<String>, S2: Into<String>>(path: S1, reason: S2) -> Self {
+        Self::Encoding {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -246,6 +258,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_creation_encoding() {
+        let err = KbError::encoding("docs/a.md", "malformed utf-16le text");
+        assert!(matches!(err, KbError::Encoding { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Encoding error in docs/a.md: malformed utf-16le text"
+        );
+    }
+
     #[test]
     fn test_error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -1,9 +1,14 @@
 //! File hashing utilities for knowledge base operations
 //!
-//! This module provides functions for calculating SHA-256 hashes of files and content.
+//! This module provides functions for calculating hashes of files and content.
 //! Hashes are used to detect file changes and determine which files need to be
 //! reprocessed during incremental loading operations.
 //!
+//! SHA-256 is the default. BLAKE3 and XXH3 are faster on large corpora and can
+//! be selected with [`HashAlgorithm`]; XXH3 is not cryptographic, which is fine
+//! for change detection. [`FileMetadata`] records a file's size and
+//! modification time so unchanged files can be recognized without hashing them.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -23,8 +28,11 @@
 //! ```
 
 use crate::kb::error::{KbError, Result};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::fmt;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, BufReader};
 use tracing::{debug, warn};
@@ -32,6 +40,146 @@ use tracing::{debug, warn};
 /// Buffer size for reading files during hash calculation
 const BUFFER_SIZE: usize = 8192;
 
+/// Hash algorithm used for change detection
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::kb::hash::HashAlgorithm;
+///
+/// assert_eq!(HashAlgorithm::default(), HashAlgorithm::Sha256);
+/// assert_eq!("blake3".parse::<HashAlgorithm>().unwrap(), HashAlgorithm::Blake3);
+/// assert_eq!(HashAlgorithm::Xxh3.hex_len(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// SHA-256 (default)
+    #[default]
+    Sha256,
+    /// BLAKE3, 256-bit output
+    Blake3,
+    /// XXH3, 64-bit output, not cryptographic
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    /// All supported algorithms
+    pub const ALL: [HashAlgorithm; 3] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Xxh3,
+    ];
+
+    /// Length of the hex-encoded digest
+    pub fn hex_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 | HashAlgorithm::Blake3 => 64,
+            HashAlgorithm::Xxh3 => 16,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgorithm::Sha256 => write!(f, "sha256"),
+            HashAlgorithm::Blake3 => write!(f, "blake3"),
+            HashAlgorithm::Xxh3 => write!(f, "xxh3"),
+        }
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = KbError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            "xxh3" | "xxhash" => Ok(HashAlgorithm::Xxh3),
+            other => Err(KbError::config(format!(
+                "Unknown hash algorithm: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Incremental hasher for any supported algorithm
+enum Hasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Xxh3 => Hasher::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Hasher::Xxh3(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Hasher::Xxh3(hasher) => format!("{:016x}", hasher.digest()),
+        }
+    }
+}
+
+/// Size and modification time of a file
+///
+/// When both match the values recorded at the last load, the file is
+/// assumed unchanged and its stored hash is reused without reading it.
+/// Modification times are kept in nanoseconds since the Unix epoch so they
+/// round-trip exactly through the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMetadata {
+    /// File size in bytes
+    pub size: u64,
+    /// Modification time in nanoseconds since the Unix epoch
+    pub modified_ns: i64,
+}
+
+impl FileMetadata {
+    /// Build from filesystem metadata
+    ///
+    /// Returns `None` if the platform does not report a modification time.
+    pub fn from_std(metadata: &std::fs::Metadata) -> Option<Self> {
+        let modified = metadata.modified().ok()?;
+        let modified_ns = match modified.duration_since(UNIX_EPOCH) {
+            Ok(since) => i64::try_from(since.as_nanos()).ok()?,
+            Err(before) => -i64::try_from(before.duration().as_nanos()).ok()?,
+        };
+        Some(Self {
+            size: metadata.len(),
+            modified_ns,
+        })
+    }
+
+    /// Read the metadata of the file at `path`
+    ///
+    /// Returns `None` if the file cannot be inspected.
+    pub async fn read<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        Self::from_std(&metadata)
+    }
+}
+
 /// Calculate SHA-256 hash of a file
 ///
 /// Reads the file in chunks to efficiently handle large files without
@@ -62,8 +210,38 @@ const BUFFER_SIZE: usize = 8192;
 /// # }
 /// ```
 pub async fn calculate_file_hash<P: AsRef<Path>>(path: P) -> Result<String> {
+    calculate_file_hash_with(path, HashAlgorithm::Sha256).await
+}
+
+/// Calculate the hash of a file with the given algorithm
+///
+/// Streams the file like [`calculate_file_hash`].
+///
+/// # Errors
+///
+/// Returns `KbError::HashCalculation` if the file cannot be read
+///
+/// # Examples
+///
+/// ```no_run
+/// use xze_core::kb::hash::{calculate_file_hash_with, HashAlgorithm};
+///
+/// # async fn example() -> xze_core::kb::error::Result<()> {
+/// let hash = calculate_file_hash_with("/path/to/file.txt", HashAlgorithm::Xxh3).await?;
+/// assert_eq!(hash.len(), 16);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn calculate_file_hash_with<P: AsRef<Path>>(
+    path: P,
+    algorithm: HashAlgorithm,
+) -> Result<String> {
     let path = path.as_ref();
-    debug!("Calculating hash for file: {}", path.display());
+    debug!(
+        "Calculating {} hash for file: {}",
+        algorithm,
+        path.display()
+    );
 
     // Open file
     let file = File::open(path).await.map_err(|e| {
@@ -76,7 +254,7 @@ pub async fn calculate_file_hash<P: AsRef<Path>>(path: P) -> Result<String> {
 
     // Create buffered reader for efficient I/O
     let mut reader = BufReader::with_capacity(BUFFER_SIZE, file);
-    let mut hasher = Sha256::new();
+    let mut hasher = Hasher::new(algorithm);
     let mut buffer = vec![0u8; BUFFER_SIZE];
 
     // Read file in chunks and update hash
@@ -101,8 +279,7 @@ pub async fn calculate_file_hash<P: AsRef<Path>>(path: P) -> Result<String> {
     }
 
     // Finalize hash and convert to hex string
-    let result = hasher.finalize();
-    let hash_string = format!("{:x}", result);
+    let hash_string = hasher.finalize_hex();
 
     debug!("Calculated hash for {}: {}", path.display(), hash_string);
 
@@ -136,16 +313,32 @@ pub async fn calculate_file_hash<P: AsRef<Path>>(path: P) -> Result<String> {
 /// assert_eq!(hash, hash2);
 /// ```
 pub fn calculate_content_hash(content: &str) -> String {
-    let mut hasher = Sha256::new();
+    calculate_content_hash_with(content, HashAlgorithm::Sha256)
+}
+
+/// Calculate the hash of string content with the given algorithm
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::kb::hash::{calculate_content_hash_with, HashAlgorithm};
+///
+/// let hash = calculate_content_hash_with("Hello, world!", HashAlgorithm::Blake3);
+/// assert_eq!(hash.len(), 64);
+/// ```
+pub fn calculate_content_hash_with(content: &str, algorithm: HashAlgorithm) -> String {
+    let mut hasher = Hasher::new(algorithm);
     hasher.update(content.as_bytes());
-    let result = hasher.finalize();
-    format!("{:x}", result)
+    hasher.finalize_hex()
 }
 
-/// Verify that a hash string is valid SHA-256 format
+/// Verify that a hash string is a valid hex digest
 ///
-/// Checks if the provided string is a valid hex-encoded SHA-256 hash
-/// (64 hexadecimal characters).
+/// Accepts the digest length of any supported [`HashAlgorithm`]: 64
+/// hexadecimal characters for SHA-256 and BLAKE3, 16 for XXH3. Stores may
+/// hold a mix after the algorithm is changed, so the length alone is not
+/// tied to one algorithm; use [`verify_hash_format_for`] to check a
+/// specific one.
 ///
 /// # Arguments
 ///
@@ -164,22 +357,61 @@ pub fn calculate_content_hash(content: &str) -> String {
 /// ```
 /// use xze_core::kb::hash::verify_hash_format;
 ///
-/// // Valid hash (64 hex characters)
+/// // Valid hashes (64 or 16 hex characters)
 /// let valid = "a".repeat(64);
 /// assert!(verify_hash_format(&valid).is_ok());
+/// assert!(verify_hash_format(&"a".repeat(16)).is_ok());
 ///
 /// // Invalid hash (too short)
 /// let invalid = "abc123";
 /// assert!(verify_hash_format(&invalid).is_err());
 /// ```
 pub fn verify_hash_format(hash: &str) -> Result<()> {
-    if hash.len() != 64 {
+    if !HashAlgorithm::ALL
+        .iter()
+        .any(|algorithm| algorithm.hex_len() == hash.len())
+    {
+        return Err(KbError::invalid_hash(
+            hash,
+            format!("Expected 16 or 64 characters, got {}", hash.len()),
+        ));
+    }
+
+    verify_hex_digits(hash)
+}
+
+/// Verify that a hash string is a valid digest for `algorithm`
+///
+/// # Errors
+///
+/// Returns `KbError::InvalidHash` if the length does not match the
+/// algorithm or the hash contains non-hexadecimal characters
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::kb::hash::{verify_hash_format_for, HashAlgorithm};
+///
+/// assert!(verify_hash_format_for(&"a".repeat(16), HashAlgorithm::Xxh3).is_ok());
+/// assert!(verify_hash_format_for(&"a".repeat(16), HashAlgorithm::Sha256).is_err());
+/// ```
+pub fn verify_hash_format_for(hash: &str, algorithm: HashAlgorithm) -> Result<()> {
+    if hash.len() != algorithm.hex_len() {
         return Err(KbError::invalid_hash(
             hash,
-            format!("Expected 64 characters, got {}", hash.len()),
+            format!(
+                "Expected {} characters for {}, got {}",
+                algorithm.hex_len(),
+                algorithm,
+                hash.len()
+            ),
         ));
     }
 
+    verify_hex_digits(hash)
+}
+
+fn verify_hex_digits(hash: &str) -> Result<()> {
     if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
         return Err(KbError::invalid_hash(
             hash,
@@ -361,4 +593,60 @@ mod tests {
         let mixed = "aAbBcCdDeEfF".repeat(5) + "aAbB";
         assert!(verify_hash_format(&mixed).is_ok());
     }
+
+    #[test]
+    fn test_hash_algorithms_known_vectors() {
+        assert_eq!(
+            calculate_content_hash_with("", HashAlgorithm::Blake3),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(
+            calculate_content_hash_with("", HashAlgorithm::Xxh3),
+            "2d06800538d394c2"
+        );
+
+        for algorithm in HashAlgorithm::ALL {
+            let hash = calculate_content_hash_with("test content", algorithm);
+            assert!(verify_hash_format_for(&hash, algorithm).is_ok());
+            assert!(verify_hash_format(&hash).is_ok());
+            assert_eq!(
+                algorithm.to_string().parse::<HashAlgorithm>().unwrap(),
+                algorithm
+            );
+        }
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_hash_matches_content_hash_for_each_algorithm() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let content = "x".repeat(BUFFER_SIZE * 3 + 17);
+        std::io::Write::write_all(&mut temp_file, content.as_bytes()).unwrap();
+
+        for algorithm in HashAlgorithm::ALL {
+            let file_hash = calculate_file_hash_with(temp_file.path(), algorithm)
+                .await
+                .unwrap();
+            assert_eq!(file_hash, calculate_content_hash_with(&content, algorithm));
+        }
+    }
+
+    #[test]
+    fn test_verify_hash_format_for_rejects_other_lengths() {
+        let xxh3 = "a".repeat(16);
+        assert!(verify_hash_format_for(&xxh3, HashAlgorithm::Xxh3).is_ok());
+        assert!(verify_hash_format_for(&xxh3, HashAlgorithm::Blake3).is_err());
+        assert!(verify_hash_format(&"a".repeat(32)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_metadata_read() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut temp_file, b"12345").unwrap();
+
+        let metadata = FileMetadata::read(temp_file.path()).await.unwrap();
+        assert_eq!(metadata.size, 5);
+        assert!(metadata.modified_ns > 0);
+        assert!(FileMetadata::read("/nonexistent/file.txt").await.is_none());
+    }
 }
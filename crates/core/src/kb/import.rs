@@ -0,0 +1,143 @@
+//! Newline-delimited JSON document import
+//!
+//! Parses the records accepted by `xze load --from-stdin`, one JSON object
+//! per line:
+//!
+//! ```text
+//! {"id": "guide-1", "path": "cms/guide.md", "content": "# Guide ...", "category": "how-to", "tags": ["setup"]}
+//! ```
+//!
+//! `id` and `content` are required. `path` names the document in the
+//! knowledge base and defaults to the id. `category` must be a Diátaxis
+//! category when present.
+
+use crate::kb::error::{KbError, Result};
+use crate::kb::hash::{calculate_content_hash_with, HashAlgorithm};
+use crate::types::DiátaxisCategory;
+use serde::{Deserialize, Serialize};
+
+/// One document read from an NDJSON stream
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    /// Identifier of the document in the source system
+    pub id: String,
+    /// Path under which the document is stored; defaults to `id`
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Document text
+    pub content: String,
+    /// Diátaxis category
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Free-form tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl ImportRecord {
+    /// Parse and validate one NDJSON line
+    ///
+    /// The category is normalized to its lowercase name, such as `how-to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Loader` if the line is not a JSON record, the id
+    /// or content is empty, or the category is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::kb::import::ImportRecord;
+    ///
+    /// let record = ImportRecord::parse(r#"{"id": "a", "content": "Text", "category": "howto"}"#)
+    ///     .unwrap();
+    /// assert_eq!(record.key(), "a");
+    /// assert_eq!(record.category.as_deref(), Some("how-to"));
+    ///
+    /// assert!(ImportRecord::parse(r#"{"id": "a"}"#).is_err());
+    /// ```
+    pub fn parse(line: &str) -> Result<Self> {
+        let mut record: ImportRecord = serde_json::from_str(line)
+            .map_err(|e| KbError::loader(format!("Invalid record: {}", e)))?;
+
+        if record.id.trim().is_empty() {
+            return Err(KbError::loader("Record id is empty"));
+        }
+        if record.content.trim().is_empty() {
+            return Err(KbError::loader(format!(
+                "Record {} has no content",
+                record.id
+            )));
+        }
+        if record.path.as_deref().is_some_and(|p| p.trim().is_empty()) {
+            record.path = None;
+        }
+        if let Some(category) = &record.category {
+            let parsed: DiátaxisCategory = category
+                .parse()
+                .map_err(|e| KbError::loader(format!("Record {}: {}", record.id, e)))?;
+            record.category = Some(parsed.to_string().to_lowercase());
+        }
+
+        Ok(record)
+    }
+
+    /// Key under which the record is stored
+    pub fn key(&self) -> &str {
+        self.path.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Hash of everything stored for the record
+    ///
+    /// Covers the category and tags as well as the content, so retagging a
+    /// document counts as a change.
+    pub fn content_hash(&self, algorithm: HashAlgorithm) -> String {
+        let mut tags = self.tags.clone();
+        tags.sort();
+        let material = serde_json::json!([self.content, self.category, tags]).to_string();
+        calculate_content_hash_with(&material, algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_record() {
+        let record = ImportRecord::parse(
+            r#"{"id":"1","path":"cms/a.md","content":"Body","category":"Tutorials","tags":["x"]}"#,
+        )
+        .unwrap();
+        assert_eq!(record.key(), "cms/a.md");
+        assert_eq!(record.category.as_deref(), Some("tutorial"));
+        assert_eq!(record.tags, vec!["x"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_records() {
+        for line in [
+            "not json",
+            r#"{"content":"Body"}"#,
+            r#"{"id":"","content":"Body"}"#,
+            r#"{"id":"1","content":"   "}"#,
+            r#"{"id":"1","content":"Body","category":"recipes"}"#,
+            r#"{"id":"1","content":"Body","tags":"x"}"#,
+        ] {
+            assert!(ImportRecord::parse(line).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_content_hash_covers_metadata() {
+        let record =
+            ImportRecord::parse(r#"{"id":"1","content":"Body","tags":["a","b"]}"#).unwrap();
+        let reordered =
+            ImportRecord::parse(r#"{"id":"1","content":"Body","tags":["b","a"]}"#).unwrap();
+        let retagged = ImportRecord::parse(r#"{"id":"1","content":"Body","tags":["a"]}"#).unwrap();
+
+        let hash = record.content_hash(HashAlgorithm::Sha256);
+        assert_eq!(hash, reordered.content_hash(HashAlgorithm::Sha256));
+        assert_ne!(hash, retagged.content_hash(HashAlgorithm::Sha256));
+    }
+}
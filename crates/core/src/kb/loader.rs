@@ -3,14 +3,28 @@
 //! This module orchestrates the incremental loading process, coordinating
 //! file discovery, categorization, and processing based on configuration.
 
-use crate::kb::categorizer::{CategorizedFiles, FileCategorizer};
+use crate::kb::backend::{CleanupStats, KbBackend, KbBackendKind};
+use crate::kb::categorizer::{
+    CategorizedFiles, DiscoveredFiles, FileCategorizer, FileCategory, KnownFiles,
+};
+use crate::kb::checkpoint::LoadCheckpoint;
+use crate::kb::encoding::{self, EncodingMode, TextEncoding};
 use crate::kb::error::{KbError, Result};
-use crate::kb::hash;
+use crate::kb::hash::{self, HashAlgorithm};
+use crate::kb::import::ImportRecord;
+use crate::kb::memory::InMemoryKbStore;
 use crate::kb::store::{DocumentChunk, KbStore};
+use crate::search::index::{IndexedDocument, SearchIndex};
+use crate::semantic::{ChunkStrategy, ChunkingError, SemanticChunk, SemanticChunker};
+use futures::future;
+use futures::stream::{self, StreamExt};
 use sqlx::PgPool;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 /// Configuration for incremental loader operations
@@ -34,7 +48,7 @@ use tracing::{debug, info, warn};
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct LoaderConfig {
     /// Resume an interrupted load (skip unchanged files)
     pub resume: bool,
@@ -46,6 +60,69 @@ pub struct LoaderConfig {
     pub dry_run: bool,
     /// Force full reload (ignore existing state)
     pub force: bool,
+    /// Storage backend used by the loader
+    pub backend: KbBackendKind,
+    /// Algorithm used to hash file contents
+    ///
+    /// Changing it makes every stored hash differ once, so the next
+    /// update reprocesses all files whose metadata has changed.
+    pub hash_algorithm: HashAlgorithm,
+    /// Hash every file even when its size and modification time match the
+    /// recorded values
+    pub verify_hashes: bool,
+    /// How files are split into chunks
+    ///
+    /// See [`crate::semantic::strategy`] for which strategy suits which
+    /// content. `Semantic` needs a chunker attached with
+    /// [`IncrementalLoader::with_semantic_chunker`]; without one the loader
+    /// falls back to `Sentence`.
+    pub chunk_strategy: ChunkStrategy,
+    /// How file bytes are decoded
+    ///
+    /// By default UTF-16 and Latin-1 files are detected and converted to
+    /// UTF-8; see [`crate::kb::encoding`].
+    pub encoding: EncodingMode,
+    /// Number of files chunked, embedded and stored at once
+    ///
+    /// With a semantic chunker attached every file in flight sends
+    /// embedding requests, so keep this within what the Ollama server can
+    /// serve. Defaults to 1, which processes files one after another.
+    pub concurrency: usize,
+    /// Number of files that may fail before the load is aborted
+    ///
+    /// Failed files are logged and listed in [`LoadStats::failed_files`].
+    /// Defaults to 0, which aborts on the first failure.
+    pub max_failures: usize,
+    /// File recording completed files so an interrupted load can resume
+    ///
+    /// It is written every `checkpoint_interval` files, when the load is
+    /// cancelled and when it aborts; the next load skips the files it lists
+    /// unless they have changed. It is removed once a load completes. See
+    /// [`crate::kb::checkpoint`].
+    pub checkpoint_path: Option<PathBuf>,
+    /// Number of completed files between checkpoint writes
+    pub checkpoint_interval: usize,
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self {
+            resume: false,
+            update: false,
+            cleanup: false,
+            dry_run: false,
+            force: false,
+            backend: KbBackendKind::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::default(),
+            encoding: EncodingMode::default(),
+            concurrency: 1,
+            max_failures: 0,
+            checkpoint_path: None,
+            checkpoint_interval: 10,
+        }
+    }
 }
 
 impl LoaderConfig {
@@ -71,6 +148,18 @@ impl LoaderConfig {
             ));
         }
 
+        if self.concurrency == 0 {
+            return Err(KbError::config("Concurrency must be at least 1"));
+        }
+
+        if self.checkpoint_interval == 0 {
+            return Err(KbError::config("Checkpoint interval must be at least 1"));
+        }
+
+        self.chunk_strategy
+            .validate()
+            .map_err(|e| KbError::config(e.to_string()))?;
+
         Ok(())
     }
 
@@ -109,6 +198,9 @@ impl LoaderConfig {
 pub struct LoadStats {
     /// Number of files skipped (unchanged)
     pub files_skipped: usize,
+    /// Number of files skipped because an interrupted load had already
+    /// loaded them, per its checkpoint
+    pub files_checkpointed: usize,
     /// Number of files added (new)
     pub files_added: usize,
     /// Number of files updated (modified)
@@ -119,6 +211,16 @@ pub struct LoadStats {
     pub chunks_inserted: usize,
     /// Number of chunks deleted
     pub chunks_deleted: usize,
+    /// Number of files whose stored hash was reused without reading them
+    pub hashes_reused: usize,
+    /// Number of streamed records skipped because they were malformed
+    pub records_invalid: usize,
+    /// Files that failed to load without aborting the run
+    pub failed_files: Vec<FailedFile>,
+    /// Whether the load was cancelled before all files were processed
+    pub cancelled: bool,
+    /// What the cleanup removed; all zero unless cleanup ran
+    pub cleanup: CleanupStats,
     /// Duration of operation in seconds
     pub duration_secs: f64,
 }
@@ -131,7 +233,11 @@ impl LoadStats {
 
     /// Get total number of files discovered
     pub fn total_files(&self) -> usize {
-        self.files_skipped + self.files_added + self.files_updated + self.files_deleted
+        self.files_skipped
+            + self.files_checkpointed
+            + self.files_added
+            + self.files_updated
+            + self.files_deleted
     }
 
     /// Get number of files that need processing (excludes skipped)
@@ -144,14 +250,36 @@ impl LoadStats {
         info!("Load operation completed in {:.2}s", self.duration_secs);
         info!("  Files discovered: {}", self.total_files());
         info!("  Files skipped:    {}", self.files_skipped);
+        if self.files_checkpointed > 0 {
+            info!("  Checkpointed:     {}", self.files_checkpointed);
+        }
         info!("  Files added:      {}", self.files_added);
         info!("  Files updated:    {}", self.files_updated);
         info!("  Files deleted:    {}", self.files_deleted);
         info!("  Chunks inserted:  {}", self.chunks_inserted);
         info!("  Chunks deleted:   {}", self.chunks_deleted);
+        info!("  Hashes reused:    {}", self.hashes_reused);
+        if self.records_invalid > 0 {
+            info!("  Invalid records:  {}", self.records_invalid);
+        }
+        if !self.failed_files.is_empty() {
+            info!("  Files failed:     {}", self.failed_files.len());
+        }
+        if self.cleanup.files_removed > 0 {
+            info!("  Bytes reclaimed:  {}", self.cleanup.bytes_reclaimed);
+        }
     }
 }
 
+/// A file that could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedFile {
+    /// Path of the file
+    pub path: String,
+    /// Why loading it failed
+    pub error: String,
+}
+
 /// Incremental loader for knowledge base operations
 ///
 /// Orchestrates the incremental loading process by discovering files,
@@ -169,7 +297,7 @@ impl LoadStats {
 ///     ..Default::default()
 /// };
 ///
-/// let loader = IncrementalLoader::new(pool, config)?;
+/// let loader = IncrementalLoader::new(Some(pool), config)?;
 /// let paths = vec!["./docs".to_string()];
 /// let stats = loader.load(&paths).await?;
 ///
@@ -178,16 +306,55 @@ impl LoadStats {
 /// # }
 /// ```
 pub struct IncrementalLoader {
-    store: KbStore,
+    store: Arc<dyn KbBackend>,
     config: LoaderConfig,
+    search_index: Option<Arc<SearchIndex>>,
+    semantic_chunker: Option<Arc<SemanticChunker>>,
+    cancellation: CancellationToken,
 }
 
 impl IncrementalLoader {
     /// Create a new incremental loader
     ///
+    /// The storage backend is chosen from `config.backend`. The pool is only
+    /// needed by the PostgreSQL backend; the memory backend ignores it and
+    /// starts empty.
+    ///
     /// # Arguments
     ///
-    /// * `pool` - Database connection pool
+    /// * `pool` - Database connection pool, required for PostgreSQL
+    /// * `config` - Loader configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns a new IncrementalLoader instance
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::ConfigError` if configuration is invalid or the
+    /// PostgreSQL backend is selected without a pool
+    pub fn new(pool: Option<PgPool>, config: LoaderConfig) -> Result<Self> {
+        let store: Arc<dyn KbBackend> = match config.backend {
+            KbBackendKind::Postgres => {
+                let pool = pool.ok_or_else(|| {
+                    KbError::config("The postgres backend requires a database connection pool")
+                })?;
+                Arc::new(KbStore::new(pool))
+            }
+            KbBackendKind::Memory => Arc::new(InMemoryKbStore::new()),
+        };
+
+        Self::with_backend(store, config)
+    }
+
+    /// Create a new incremental loader on top of an existing backend
+    ///
+    /// Use this to share a store between the loader and other callers, for
+    /// example to inspect an `InMemoryKbStore` after a load in tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Storage backend to load into
     /// * `config` - Loader configuration
     ///
     /// # Returns
@@ -197,15 +364,97 @@ impl IncrementalLoader {
     /// # Errors
     ///
     /// Returns `KbError::ConfigError` if configuration is invalid
-    pub fn new(pool: PgPool, config: LoaderConfig) -> Result<Self> {
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+    /// use xze_core::kb::memory::InMemoryKbStore;
+    ///
+    /// let store = InMemoryKbStore::new();
+    /// let loader = IncrementalLoader::with_backend(Arc::new(store), LoaderConfig::default());
+    /// assert!(loader.is_ok());
+    /// ```
+    pub fn with_backend(store: Arc<dyn KbBackend>, config: LoaderConfig) -> Result<Self> {
         config.validate()?;
 
         Ok(Self {
-            store: KbStore::new(pool),
+            store,
             config,
+            search_index: None,
+            semantic_chunker: None,
+            cancellation: CancellationToken::new(),
         })
     }
 
+    /// Keep a search index in step with the store
+    ///
+    /// After each file is written to the store, its chunks are upserted into
+    /// `index`; deleted files are removed from it. Files skipped as unchanged
+    /// are not touched, so the index only reflects files this loader wrote.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+    /// use xze_core::kb::memory::InMemoryKbStore;
+    /// use xze_core::search::SearchIndex;
+    ///
+    /// let index = Arc::new(SearchIndex::new());
+    /// let loader = IncrementalLoader::with_backend(
+    ///     Arc::new(InMemoryKbStore::new()),
+    ///     LoaderConfig::default(),
+    /// )
+    /// .unwrap()
+    /// .with_search_index(index.clone());
+    /// ```
+    pub fn with_search_index(mut self, index: Arc<SearchIndex>) -> Self {
+        self.search_index = Some(index);
+        self
+    }
+
+    /// Use `chunker` for the `Semantic` chunk strategy
+    ///
+    /// The chunker generates sentence embeddings, so chunks produced this
+    /// way are stored with real embeddings instead of placeholders. It is
+    /// not used by the other strategies.
+    pub fn with_semantic_chunker(mut self, chunker: Arc<SemanticChunker>) -> Self {
+        self.semantic_chunker = Some(chunker);
+        self
+    }
+
+    /// Stop loading files once `token` is cancelled
+    ///
+    /// Files already being processed are finished, then the checkpoint is
+    /// written and [`load`](Self::load) returns with `cancelled` set in its
+    /// statistics. Cleanup is not run for a cancelled load.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio_util::sync::CancellationToken;
+    /// use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+    /// use xze_core::kb::memory::InMemoryKbStore;
+    ///
+    /// let token = CancellationToken::new();
+    /// let loader = IncrementalLoader::with_backend(
+    ///     Arc::new(InMemoryKbStore::new()),
+    ///     LoaderConfig::default(),
+    /// )
+    /// .unwrap()
+    /// .with_cancellation(token.clone());
+    ///
+    /// // From a Ctrl+C handler, for example
+    /// token.cancel();
+    /// ```
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
     /// Load files from specified paths
     ///
     /// Discovers files, categorizes them based on hash comparison,
@@ -228,31 +477,67 @@ impl IncrementalLoader {
 
         self.log_mode(paths);
 
-        // Phase 1: Discover files with hashes
-        debug!("Discovering files and calculating hashes...");
-        let current_files = FileCategorizer::discover_files_with_hashes(paths).await?;
-        info!("Discovered {} files", current_files.len());
-
-        // Store hashes for later use
-        let file_hashes: HashMap<String, String> = current_files.clone();
-
-        // Phase 2: Query existing files from database
-        let existing_files = if self.config.force {
+        // Phase 1: Query existing files from database
+        let (existing_files, existing_metadata) = if self.config.force {
             // In force mode, treat all files as new
             debug!("Force mode: treating all files as new");
-            std::collections::HashMap::new()
+            (HashMap::new(), HashMap::new())
         } else {
             debug!("Querying existing files from database...");
-            self.store.query_existing_files().await?
+            let files = self.store.query_existing_files().await?;
+            let metadata = if self.config.verify_hashes {
+                HashMap::new()
+            } else {
+                self.store.query_file_metadata().await?
+            };
+            (files, metadata)
         };
         info!("Found {} existing files in database", existing_files.len());
 
+        // Phase 2: Discover files with hashes, reusing stored hashes for
+        // files whose size and modification time are unchanged
+        debug!(
+            "Discovering files and calculating {} hashes...",
+            self.config.hash_algorithm
+        );
+        let known = KnownFiles {
+            hashes: &existing_files,
+            metadata: &existing_metadata,
+        };
+        let discovered =
+            FileCategorizer::discover_files(paths, self.config.hash_algorithm, Some(known)).await?;
+        info!("Discovered {} files", discovered.hashes.len());
+        stats.hashes_reused = discovered.hashes_reused;
+
         // Phase 3: Categorize files
         debug!("Categorizing files...");
-        let categorizer = FileCategorizer::new(current_files, existing_files);
-        let categorized = categorizer.categorize();
+        let categorizer = FileCategorizer::new(discovered.hashes.clone(), existing_files);
+        let mut categorized = categorizer.categorize();
         FileCategorizer::log_summary(&categorized);
 
+        // Skip files that an interrupted load already finished, unless they
+        // have changed since
+        let mut checkpoint = match &self.config.checkpoint_path {
+            Some(path) => Some(LoadCheckpoint::read(path).await?),
+            None => None,
+        };
+        if let Some(checkpoint) = checkpoint.as_ref().filter(|c| !c.is_empty()) {
+            for files in [&mut categorized.add, &mut categorized.update] {
+                files.retain(|file| {
+                    let done = discovered
+                        .hashes
+                        .get(file)
+                        .is_some_and(|hash| checkpoint.contains(file, hash));
+                    stats.files_checkpointed += usize::from(done);
+                    !done
+                });
+            }
+            info!(
+                "Skipping {} files completed by an interrupted load",
+                stats.files_checkpointed
+            );
+        }
+
         // Update statistics from categorization
         stats.files_skipped = categorized.skip.len();
         stats.files_added = categorized.add.len();
@@ -268,7 +553,13 @@ impl IncrementalLoader {
             if !categorized.add.is_empty() {
                 info!("Processing {} new files...", categorized.add.len());
                 let chunks = self
-                    .process_add_files(&categorized.add, &file_hashes)
+                    .process_files(
+                        &categorized.add,
+                        &discovered,
+                        FileCategory::Add,
+                        &mut stats.failed_files,
+                        &mut checkpoint,
+                    )
                     .await?;
                 stats.chunks_inserted += chunks;
                 info!("Inserted {} chunks for new files", chunks);
@@ -278,7 +569,13 @@ impl IncrementalLoader {
             if self.config.update && !categorized.update.is_empty() {
                 info!("Processing {} modified files...", categorized.update.len());
                 let chunks = self
-                    .process_update_files(&categorized.update, &file_hashes)
+                    .process_files(
+                        &categorized.update,
+                        &discovered,
+                        FileCategory::Update,
+                        &mut stats.failed_files,
+                        &mut checkpoint,
+                    )
                     .await?;
                 stats.chunks_inserted += chunks;
                 info!("Updated {} chunks for modified files", chunks);
@@ -289,18 +586,45 @@ impl IncrementalLoader {
                 );
             }
 
-            // Process delete files (only if cleanup flag is set)
-            if self.config.cleanup && !categorized.delete.is_empty() {
+            // A cancelled load stops here so it can be resumed from the
+            // checkpoint; the remaining phases run on the resumed load
+            if self.cancellation.is_cancelled() {
+                stats.cancelled = true;
+                self.write_checkpoint(&checkpoint).await?;
+                warn!(
+                    "Load cancelled; {} files are checkpointed for the next run",
+                    checkpoint.as_ref().map_or(0, LoadCheckpoint::len)
+                );
+                stats.duration_secs = start.elapsed().as_secs_f64();
+                self.log_completion(&stats);
+                return Ok(stats);
+            }
+
+            // Remove deleted files and reclaim space (only if cleanup flag
+            // is set)
+            if self.config.cleanup {
                 info!("Processing {} deleted files...", categorized.delete.len());
-                let chunks = self.process_delete_files(&categorized.delete).await?;
-                stats.chunks_deleted += chunks;
-                info!("Removed {} chunks for deleted files", chunks);
+                stats.cleanup = self.process_delete_files(&categorized.delete).await?;
+                stats.chunks_deleted += stats.cleanup.chunks_removed;
             } else if !categorized.delete.is_empty() && !self.config.cleanup {
                 warn!(
                     "Skipping {} deleted files (use --cleanup to remove them)",
                     categorized.delete.len()
                 );
             }
+
+            // Record metadata for unchanged files that were hashed, such as
+            // files touched without being modified, so the next load can
+            // skip them without reading
+            for file_str in &categorized.skip {
+                if discovered.metadata.get(file_str) != existing_metadata.get(file_str) {
+                    self.record_metadata(file_str, &discovered).await?;
+                }
+            }
+
+            if let Some(path) = &self.config.checkpoint_path {
+                LoadCheckpoint::remove(path).await?;
+            }
         }
 
         stats.duration_secs = start.elapsed().as_secs_f64();
@@ -310,6 +634,156 @@ impl IncrementalLoader {
         Ok(stats)
     }
 
+    /// Load documents streamed as newline-delimited JSON
+    ///
+    /// Each line is parsed as an [`ImportRecord`] and processed before the
+    /// next is read, so the stream is never held in memory. Records are
+    /// categorized against the store by key and content hash like
+    /// discovered files: unchanged records are skipped, changed records are
+    /// replaced only in update mode, and new records are added. Malformed
+    /// lines are logged and counted in `records_invalid`. Cleanup does not
+    /// apply, since a stream is not a complete listing. Cancelling stops
+    /// reading; records stored so far are skipped as unchanged when the
+    /// stream is loaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of NDJSON lines, such as stdin
+    ///
+    /// # Returns
+    ///
+    /// Returns statistics about the load operation
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError` if reading the stream, chunking or storage fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+    /// use xze_core::kb::memory::InMemoryKbStore;
+    ///
+    /// # async fn example() -> xze_core::kb::error::Result<()> {
+    /// let loader =
+    ///     IncrementalLoader::with_backend(Arc::new(InMemoryKbStore::new()), LoaderConfig::default())?;
+    /// let input = "{\"id\": \"a\", \"content\": \"Some text here.\"}\nnot json\n";
+    /// let stats = loader.load_records(input.as_bytes()).await?;
+    /// assert_eq!(stats.files_added, 1);
+    /// assert_eq!(stats.records_invalid, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load_records<R>(&self, reader: R) -> Result<LoadStats>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let start = Instant::now();
+        let mut stats = LoadStats::new();
+
+        self.log_mode(&["<stdin>".to_string()]);
+        if self.config.cleanup {
+            warn!("Cleanup does not apply to streamed records and is ignored");
+        }
+
+        let mut existing = if self.config.force {
+            HashMap::new()
+        } else {
+            self.store.query_existing_files().await?
+        };
+
+        let mut lines = reader.lines();
+        let mut line_number = 0;
+        while let Some(line) = lines.next_line().await? {
+            if self.cancellation.is_cancelled() {
+                warn!("Load cancelled after {} lines", line_number);
+                stats.cancelled = true;
+                break;
+            }
+            line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = match ImportRecord::parse(&line) {
+                Ok(record) => record,
+                Err(e) => {
+                    warn!("Skipping line {}: {}", line_number, e);
+                    stats.records_invalid += 1;
+                    continue;
+                }
+            };
+
+            let key = record.key().to_string();
+            let hash = record.content_hash(self.config.hash_algorithm);
+            let category =
+                FileCategorizer::categorize_one(&hash, existing.get(&key).map(String::as_str));
+
+            match category {
+                FileCategory::Skip => {
+                    stats.files_skipped += 1;
+                    continue;
+                }
+                FileCategory::Update => {
+                    stats.files_updated += 1;
+                    if !self.config.update {
+                        warn!(
+                            "Skipping modified record {} (use --update to process it)",
+                            key
+                        );
+                        continue;
+                    }
+                }
+                FileCategory::Add | FileCategory::Delete => stats.files_added += 1,
+            }
+
+            if self.config.dry_run {
+                info!("Would {:?} record: {}", category, key);
+                existing.insert(key, hash);
+                continue;
+            }
+
+            let chunks = self.record_chunks(&record).await?;
+            let path = Path::new(&key);
+            if category == FileCategory::Update {
+                self.store.update_file_chunks(path, &hash, &chunks).await?;
+            } else {
+                self.store.insert_file_chunks(path, &hash, &chunks).await?;
+            }
+            stats.chunks_inserted += chunks.len();
+            debug!("Stored {} chunks for record: {}", chunks.len(), key);
+
+            if let Some(index) = &self.search_index {
+                index.upsert(IndexedDocument::new(key.clone(), chunks));
+            }
+            existing.insert(key, hash);
+        }
+
+        stats.duration_secs = start.elapsed().as_secs_f64();
+
+        self.log_completion(&stats);
+
+        Ok(stats)
+    }
+
+    /// Chunk a streamed record, tagging each chunk with the record's
+    /// identity, category and tags
+    async fn record_chunks(&self, record: &ImportRecord) -> Result<Vec<DocumentChunk>> {
+        let mut chunks = self.chunk_content(record.key(), &record.content).await?;
+        for chunk in &mut chunks {
+            if let Some(metadata) = chunk.metadata.as_object_mut() {
+                metadata.insert("record_id".to_string(), record.id.clone().into());
+                if let Some(category) = &record.category {
+                    metadata.insert("category".to_string(), category.clone().into());
+                }
+                metadata.insert("tags".to_string(), record.tags.clone().into());
+            }
+        }
+        tag_language(&mut chunks, &record.content);
+        Ok(chunks)
+    }
+
     /// Log the current operation mode and configuration
     ///
     /// Provides clear information about what mode is active and what
@@ -340,6 +814,11 @@ impl IncrementalLoader {
             info!("  -> DRY RUN: No changes will be made to database");
         }
 
+        info!("Chunk strategy: {}", self.config.chunk_strategy);
+        if self.config.chunk_strategy.requires_embeddings() && self.semantic_chunker.is_none() {
+            warn!("  -> No semantic chunker attached; chunking by sentence instead");
+        }
+
         info!("Paths to process: {}", paths.len());
         for (i, path) in paths.iter().enumerate() {
             info!("  [{}] {}", i + 1, path);
@@ -452,14 +931,25 @@ impl IncrementalLoader {
         info!("======================");
     }
 
-    /// Process files to be added
+    /// Process files to be added or updated
+    ///
+    /// Up to `concurrency` files are chunked, embedded and stored at once.
+    /// Results are tallied here as each file finishes, so the counts do not
+    /// depend on the order in which files complete. A failed file is
+    /// recorded in `failed_files`; once more than `max_failures` files have
+    /// failed, the remaining files are abandoned and the last error is
+    /// returned. No new files are started after the loader is cancelled.
     ///
-    /// Generates chunks for new files and inserts them into the database.
+    /// Completed files are added to `checkpoint`, which is written every
+    /// `checkpoint_interval` files and before an error is returned.
     ///
     /// # Arguments
     ///
-    /// * `files` - List of file paths to add
-    /// * `file_hashes` - Map of file paths to their SHA-256 hashes
+    /// * `files` - List of file paths to process
+    /// * `discovered` - Hashes and metadata of the discovered files
+    /// * `category` - `Add` to insert chunks, `Update` to replace them
+    /// * `failed_files` - Collects the files that failed
+    /// * `checkpoint` - Files completed so far, if checkpointing is enabled
     ///
     /// # Returns
     ///
@@ -467,115 +957,149 @@ impl IncrementalLoader {
     ///
     /// # Errors
     ///
-    /// Returns `KbError` if chunk generation or insertion fails
-    async fn process_add_files(
+    /// Returns the error of the file that exceeded the failure threshold
+    async fn process_files(
         &self,
         files: &[String],
-        file_hashes: &HashMap<String, String>,
+        discovered: &DiscoveredFiles,
+        category: FileCategory,
+        failed_files: &mut Vec<FailedFile>,
+        checkpoint: &mut Option<LoadCheckpoint>,
     ) -> Result<usize> {
-        debug!("Processing {} files to add", files.len());
-
-        let mut total_chunks = 0;
-
-        for file_str in files {
-            let file_path = PathBuf::from(file_str);
-            let hash = file_hashes
-                .get(file_str)
-                .ok_or_else(|| KbError::loader(format!("Hash not found for file: {}", file_str)))?;
-
-            info!("Adding file: {}", file_str);
+        debug!(
+            "Processing {} files ({:?}, {} at a time)",
+            files.len(),
+            category,
+            self.config.concurrency
+        );
 
-            // Generate chunks for the file
-            let chunks = self.generate_chunks(&file_path).await?;
+        let mut results = stream::iter(files)
+            .take_while(|_| future::ready(!self.cancellation.is_cancelled()))
+            .map(|file_str| async move {
+                let result = self.process_file(file_str, discovered, category).await;
+                (file_str, result)
+            })
+            .buffer_unordered(self.config.concurrency);
 
-            if chunks.is_empty() {
-                warn!("No chunks generated for file: {}", file_str);
-                continue;
+        let mut total_chunks = 0;
+        let mut since_checkpoint = 0;
+        while let Some((file_str, result)) = results.next().await {
+            match result {
+                Ok(chunks) => {
+                    total_chunks += chunks;
+                    if let (Some(checkpoint), Some(hash)) =
+                        (checkpoint.as_mut(), discovered.hashes.get(file_str))
+                    {
+                        checkpoint.record(file_str, hash);
+                        since_checkpoint += 1;
+                    }
+                    if since_checkpoint >= self.config.checkpoint_interval {
+                        self.write_checkpoint(checkpoint).await?;
+                        since_checkpoint = 0;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to load file {}: {}", file_str, e);
+                    failed_files.push(FailedFile {
+                        path: file_str.clone(),
+                        error: e.to_string(),
+                    });
+                    if failed_files.len() > self.config.max_failures {
+                        warn!(
+                            "Aborting load after {} failed files (max failures: {})",
+                            failed_files.len(),
+                            self.config.max_failures
+                        );
+                        self.write_checkpoint(checkpoint).await?;
+                        return Err(e);
+                    }
+                }
             }
-
-            // Insert chunks into database
-            self.store
-                .insert_file_chunks(&file_path, hash, &chunks)
-                .await?;
-
-            total_chunks += chunks.len();
-            info!("Inserted {} chunks for file: {}", chunks.len(), file_str);
         }
 
         Ok(total_chunks)
     }
 
-    /// Process files to be updated
-    ///
-    /// Generates chunks for modified files and updates them in the database
-    /// atomically within a transaction.
-    ///
-    /// # Arguments
-    ///
-    /// * `files` - List of file paths to update
-    /// * `file_hashes` - Map of file paths to their new SHA-256 hashes
-    ///
-    /// # Returns
-    ///
-    /// Returns the total number of chunks inserted
-    ///
-    /// # Errors
+    /// Write `checkpoint` to the configured checkpoint file
+    async fn write_checkpoint(&self, checkpoint: &Option<LoadCheckpoint>) -> Result<()> {
+        if let (Some(path), Some(checkpoint)) = (&self.config.checkpoint_path, checkpoint) {
+            checkpoint.write(path).await?;
+            debug!(
+                "Checkpointed {} completed files to {}",
+                checkpoint.len(),
+                path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Chunk one file and write its chunks to the store
     ///
-    /// Returns `KbError` if chunk generation or update fails
-    async fn process_update_files(
+    /// Returns the number of chunks stored; a file without chunks is
+    /// skipped and stores none.
+    async fn process_file(
         &self,
-        files: &[String],
-        file_hashes: &HashMap<String, String>,
+        file_str: &str,
+        discovered: &DiscoveredFiles,
+        category: FileCategory,
     ) -> Result<usize> {
-        debug!("Processing {} files to update", files.len());
-
-        let mut total_chunks = 0;
-
-        for file_str in files {
-            let file_path = PathBuf::from(file_str);
-            let hash = file_hashes
-                .get(file_str)
-                .ok_or_else(|| KbError::loader(format!("Hash not found for file: {}", file_str)))?;
+        let file_path = PathBuf::from(file_str);
+        let hash = discovered
+            .hashes
+            .get(file_str)
+            .ok_or_else(|| KbError::loader(format!("Hash not found for file: {}", file_str)))?;
 
+        if category == FileCategory::Update {
             info!("Updating file: {}", file_str);
+        } else {
+            info!("Adding file: {}", file_str);
+        }
 
-            // Generate new chunks for the file
-            let chunks = self.generate_chunks(&file_path).await?;
+        let chunks = self.generate_chunks(&file_path).await?;
 
-            if chunks.is_empty() {
-                warn!("No chunks generated for file: {}", file_str);
-                continue;
-            }
+        if chunks.is_empty() {
+            warn!("No chunks generated for file: {}", file_str);
+            return Ok(0);
+        }
 
-            // Update chunks atomically in transaction
+        // Updates replace the old chunks atomically in a transaction
+        if category == FileCategory::Update {
             self.store
                 .update_file_chunks(&file_path, hash, &chunks)
                 .await?;
+        } else {
+            self.store
+                .insert_file_chunks(&file_path, hash, &chunks)
+                .await?;
+        }
+        self.record_metadata(file_str, discovered).await?;
 
-            total_chunks += chunks.len();
-            info!("Updated {} chunks for file: {}", chunks.len(), file_str);
+        info!("Stored {} chunks for file: {}", chunks.len(), file_str);
+
+        let count = chunks.len();
+        if let Some(index) = &self.search_index {
+            index.upsert(IndexedDocument::new(file_str.to_string(), chunks));
         }
 
-        Ok(total_chunks)
+        Ok(count)
     }
 
-    /// Process files to be deleted
-    ///
-    /// Stub implementation for Phase 3 - actual implementation
-    /// will be completed in Phase 5.
-    ///
-    /// # Arguments
-    ///
-    /// * `files` - List of file paths to delete
-    ///
-    /// # Returns
-    ///
-    /// Returns the number of chunks deleted
-    ///
-    /// # Errors
+    /// Record the discovered size and modification time of a stored file
+    async fn record_metadata(&self, file_str: &str, discovered: &DiscoveredFiles) -> Result<()> {
+        match discovered.metadata.get(file_str) {
+            Some(metadata) => {
+                self.store
+                    .record_file_metadata(Path::new(file_str), *metadata)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Remove chunks of deleted files and reclaim space
     ///
-    /// Returns `KbError` if processing fails
-    /// Process deleted files by removing their chunks from the database
+    /// Runs [`KbBackend::vacuum`], which also removes any other stored
+    /// file that no longer exists on disk.
     ///
     /// # Arguments
     ///
@@ -583,44 +1107,41 @@ impl IncrementalLoader {
     ///
     /// # Returns
     ///
-    /// Returns the total number of chunks deleted
+    /// Returns what the vacuum removed
     ///
     /// # Errors
     ///
-    /// Returns `KbError::Database` if deletion fails
-    async fn process_delete_files(&self, files: &[String]) -> Result<usize> {
-        if files.is_empty() {
-            debug!("No deleted files to process");
-            return Ok(0);
-        }
-
-        info!("Processing {} deleted files", files.len());
-
+    /// Returns `KbError::Database` if the vacuum fails
+    async fn process_delete_files(&self, files: &[String]) -> Result<CleanupStats> {
         if self.config.dry_run {
             info!("DRY RUN: Would delete chunks for {} files", files.len());
             for file in files {
                 info!("  Would delete: {}", file);
             }
-            return Ok(0);
+            return Ok(CleanupStats::default());
         }
 
-        // Call store to cleanup deleted files
-        let chunks_deleted = self.store.cleanup_deleted_files(files).await?;
+        let cleanup = self.store.vacuum(files).await?;
+
+        if let Some(index) = &self.search_index {
+            for file in files {
+                index.remove(file);
+            }
+        }
 
         info!(
-            "Successfully deleted {} chunks from {} files",
-            chunks_deleted,
-            files.len()
+            "Removed {} chunks ({} bytes) from {} files",
+            cleanup.chunks_removed, cleanup.bytes_reclaimed, cleanup.files_removed
         );
 
-        Ok(chunks_deleted as usize)
+        Ok(cleanup)
     }
 
     /// Generate document chunks from a file
     ///
-    /// This is a placeholder implementation that creates basic chunks.
-    /// Future phases will integrate with the existing AIDocumentationGenerator
-    /// or more sophisticated chunking logic.
+    /// Splits the file with the configured chunk strategy. Chunks from the
+    /// semantic chunker keep their embeddings; all others get placeholder
+    /// embeddings.
     ///
     /// # Arguments
     ///
@@ -632,18 +1153,27 @@ impl IncrementalLoader {
     ///
     /// # Errors
     ///
-    /// Returns `KbError` if file cannot be read or chunked
+    /// Returns `KbError` if file cannot be read, decoded or chunked
     async fn generate_chunks(&self, file_path: &Path) -> Result<Vec<DocumentChunk>> {
         debug!("Generating chunks for file: {}", file_path.display());
 
-        // Read file content
-        let content = tokio::fs::read_to_string(file_path).await.map_err(|e| {
+        // Read file content and normalize it to UTF-8
+        let bytes = tokio::fs::read(file_path).await.map_err(|e| {
             KbError::loader(format!(
                 "Failed to read file {}: {}",
                 file_path.display(),
                 e
             ))
         })?;
+        let (content, text_encoding) = encoding::decode(&bytes, self.config.encoding)
+            .map_err(|reason| KbError::encoding(file_path.to_string_lossy(), reason))?;
+        if text_encoding != TextEncoding::Utf8 {
+            debug!(
+                "Converted {} from {} to UTF-8",
+                file_path.display(),
+                text_encoding
+            );
+        }
 
         // Skip empty files
         if content.trim().is_empty() {
@@ -651,67 +1181,89 @@ impl IncrementalLoader {
             return Ok(Vec::new());
         }
 
-        // Simple chunking strategy: split by paragraphs (double newline)
-        // This is a placeholder - future implementation will use semantic chunking
-        let paragraphs: Vec<&str> = content
-            .split("\n\n")
-            .filter(|p| !p.trim().is_empty())
-            .collect();
-
-        if paragraphs.is_empty() {
-            // Fall back to treating entire content as one chunk
-            return Ok(vec![self.create_chunk(0, &content).await?]);
+        let mut chunks = self
+            .chunk_content(&file_path.to_string_lossy(), &content)
+            .await?;
+        for chunk in &mut chunks {
+            if let Some(metadata) = chunk.metadata.as_object_mut() {
+                metadata.insert("encoding".to_string(), text_encoding.to_string().into());
+            }
         }
+        tag_language(&mut chunks, &content);
+        Ok(chunks)
+    }
 
-        let mut chunks = Vec::new();
-        for (idx, paragraph) in paragraphs.iter().enumerate() {
-            let chunk = self.create_chunk(idx, paragraph).await?;
-            chunks.push(chunk);
+    /// Split document content into chunks with the configured strategy
+    ///
+    /// # Arguments
+    ///
+    /// * `source_file` - Name recorded in chunk metadata and errors
+    /// * `content` - Document text
+    async fn chunk_content(&self, source_file: &str, content: &str) -> Result<Vec<DocumentChunk>> {
+        let mut strategy = self.config.chunk_strategy;
+        if strategy.requires_embeddings() && self.semantic_chunker.is_none() {
+            strategy = ChunkStrategy::Sentence;
         }
+        let chunked = match &self.semantic_chunker {
+            Some(chunker) if strategy.requires_embeddings() => {
+                chunker
+                    .chunk_with_strategy(strategy, content, source_file)
+                    .await
+            }
+            _ => strategy.chunk(content, source_file),
+        };
 
-        debug!(
-            "Generated {} chunks for file: {}",
-            chunks.len(),
-            file_path.display()
-        );
+        // Text with no usable sentences, such as a list of short fragments,
+        // is kept whole per heading section rather than dropped
+        let semantic_chunks = match chunked {
+            Ok(chunks) if !chunks.is_empty() => chunks,
+            Ok(_) | Err(ChunkingError::EmptyDocument) => {
+                strategy = ChunkStrategy::Markdown;
+                strategy
+                    .chunk(content, source_file)
+                    .map_err(|e| KbError::chunk_processing(format!("{}: {}", source_file, e)))?
+            }
+            Err(e) => return Err(KbError::chunk_processing(format!("{}: {}", source_file, e))),
+        };
+
+        let chunks: Vec<DocumentChunk> = semantic_chunks
+            .into_iter()
+            .map(|chunk| self.create_chunk(chunk, strategy))
+            .collect();
+
+        debug!("Generated {} chunks for: {}", chunks.len(), source_file);
 
         Ok(chunks)
     }
 
-    /// Create a single document chunk with embedding
-    ///
-    /// # Arguments
-    ///
-    /// * `index` - Chunk index within the document
-    /// * `content` - Text content for the chunk
-    ///
-    /// # Returns
-    ///
-    /// Returns a DocumentChunk instance
+    /// Convert a semantic chunk into a stored document chunk
     ///
-    /// # Errors
-    ///
-    /// Returns `KbError` if embedding generation fails
-    async fn create_chunk(&self, index: usize, content: &str) -> Result<DocumentChunk> {
-        let chunk_id = format!("chunk_{}", index);
+    /// Every strategy yields the same metadata keys, so stored chunks can be
+    /// compared regardless of how they were split. `strategy` is the one
+    /// actually used, which differs from the configured one after a fallback.
+    fn create_chunk(&self, chunk: SemanticChunk, strategy: ChunkStrategy) -> DocumentChunk {
+        let chunk_id = format!("chunk_{}", chunk.chunk_index);
 
-        // Placeholder embedding - in production, this would call an AI service
-        // to generate semantic embeddings. For now, use a simple hash-based approach.
-        let embedding = self.generate_placeholder_embedding(content);
+        // Placeholder embedding unless the chunker produced a real one
+        let embedding = if chunk.embedding.is_empty() {
+            self.generate_placeholder_embedding(&chunk.content)
+        } else {
+            chunk.embedding
+        };
 
-        // Create metadata
         let metadata = serde_json::json!({
-            "chunk_index": index,
-            "content_length": content.len(),
+            "chunk_index": chunk.chunk_index,
+            "total_chunks": chunk.total_chunks,
+            "chunk_strategy": strategy.to_string(),
+            "similarity_metric": chunk.similarity_metric.to_string(),
+            "title": chunk.metadata.title,
+            "word_count": chunk.metadata.word_count,
+            "char_count": chunk.metadata.char_count,
+            "content_length": chunk.content.len(),
             "created_at": chrono::Utc::now().to_rfc3339(),
         });
 
-        Ok(DocumentChunk::new(
-            chunk_id,
-            content.to_string(),
-            embedding,
-            metadata,
-        ))
+        DocumentChunk::new(chunk_id, chunk.content, embedding, metadata)
     }
 
     /// Generate a placeholder embedding vector
@@ -745,6 +1297,22 @@ impl IncrementalLoader {
     }
 }
 
+/// Record the natural language of a document in each of its chunks
+///
+/// The language is detected once from the whole document, since single
+/// chunks are often too short to identify. Undetected languages are left
+/// out so search language filters skip the document.
+fn tag_language(chunks: &mut [DocumentChunk], content: &str) {
+    let Some(language) = encoding::detect_language(content) else {
+        return;
+    };
+    for chunk in chunks {
+        if let Some(metadata) = chunk.metadata.as_object_mut() {
+            metadata.insert("language".to_string(), language.clone().into());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -757,6 +1325,13 @@ mod tests {
         assert!(!config.cleanup);
         assert!(!config.dry_run);
         assert!(!config.force);
+        assert_eq!(config.backend, KbBackendKind::Postgres);
+        assert_eq!(config.hash_algorithm, HashAlgorithm::Sha256);
+        assert!(!config.verify_hashes);
+        assert_eq!(config.chunk_strategy, ChunkStrategy::Sentence);
+        assert_eq!(config.encoding, EncodingMode::Detect);
+        assert_eq!(config.concurrency, 1);
+        assert_eq!(config.max_failures, 0);
     }
 
     #[test]
@@ -767,6 +1342,15 @@ mod tests {
             cleanup: true,
             dry_run: true,
             force: false,
+            backend: KbBackendKind::Memory,
+            hash_algorithm: HashAlgorithm::Xxh3,
+            verify_hashes: false,
+            chunk_strategy: ChunkStrategy::Markdown,
+            encoding: EncodingMode::Utf8,
+            concurrency: 4,
+            max_failures: 2,
+            checkpoint_path: Some(PathBuf::from("load.checkpoint")),
+            checkpoint_interval: 5,
         };
         assert!(config.validate().is_ok());
     }
@@ -858,4 +1442,549 @@ mod tests {
         assert_eq!(stats.total_files(), 100);
         assert_eq!(stats.duration_secs, 12.5);
     }
+
+    #[tokio::test]
+    async fn test_load_with_memory_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.md"),
+            "First paragraph.\n\nSecond paragraph.",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("b.md"), "Only paragraph.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        let loader =
+            IncrementalLoader::with_backend(Arc::new(store.clone()), LoaderConfig::default())
+                .unwrap();
+        let stats = loader.load(&paths).await.unwrap();
+        assert_eq!(stats.files_added, 2);
+        // Sentences are grouped, so each short file is a single chunk
+        assert_eq!(stats.chunks_inserted, 2);
+        assert_eq!(store.chunk_count().await, 2);
+
+        // A resumed load skips everything that is already stored
+        let resume = IncrementalLoader::with_backend(
+            Arc::new(store.clone()),
+            LoaderConfig {
+                resume: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let stats = resume.load(&paths).await.unwrap();
+        assert_eq!(stats.files_skipped, 2);
+        assert_eq!(stats.files_to_process(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_new_without_pool() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.md"), "Only paragraph.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let memory = LoaderConfig {
+            backend: KbBackendKind::Memory,
+            ..Default::default()
+        };
+        let stats = IncrementalLoader::new(None, memory)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        assert_eq!(stats.files_added, 1);
+
+        let err = IncrementalLoader::new(None, LoaderConfig::default())
+            .err()
+            .unwrap();
+        assert!(matches!(err, KbError::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_load_update_and_cleanup_with_memory_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        std::fs::write(&a, "Original.").unwrap();
+        std::fs::write(&b, "Going away.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        IncrementalLoader::with_backend(Arc::new(store.clone()), LoaderConfig::default())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+
+        std::fs::write(&a, "# One\n\nChanged.\n\n# Two\n\nNow two sections.").unwrap();
+        std::fs::remove_file(&b).unwrap();
+
+        let config = LoaderConfig {
+            update: true,
+            cleanup: true,
+            chunk_strategy: ChunkStrategy::Markdown,
+            ..Default::default()
+        };
+        let stats = IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_updated, 1);
+        assert_eq!(stats.files_deleted, 1);
+        assert_eq!(stats.chunks_deleted, 1);
+        assert_eq!(stats.cleanup.files_removed, 1);
+        assert!(stats.cleanup.bytes_reclaimed > 0);
+        assert_eq!(store.get_file_chunks(&a).await.len(), 2);
+        assert!(store.get_file_chunks(&b).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_reuses_hashes_when_size_and_mtime_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        std::fs::write(&a, "Original.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            update: true,
+            hash_algorithm: HashAlgorithm::Blake3,
+            ..Default::default()
+        };
+        let loader =
+            IncrementalLoader::with_backend(Arc::new(store.clone()), config.clone()).unwrap();
+        let stats = loader.load(&paths).await.unwrap();
+        assert_eq!(stats.files_added, 1);
+        assert_eq!(stats.hashes_reused, 0);
+
+        // Same size and restored mtime: the change goes unnoticed unless
+        // hashes are verified
+        let modified = std::fs::metadata(&a).unwrap().modified().unwrap();
+        std::fs::write(&a, "Rewritten").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&a)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        let stats = loader.load(&paths).await.unwrap();
+        assert_eq!(stats.hashes_reused, 1);
+        assert_eq!(stats.files_skipped, 1);
+
+        let verify = LoaderConfig {
+            verify_hashes: true,
+            ..config
+        };
+        let stats = IncrementalLoader::with_backend(Arc::new(store.clone()), verify)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        assert_eq!(stats.hashes_reused, 0);
+        assert_eq!(stats.files_updated, 1);
+        assert_eq!(
+            store.query_existing_files().await.unwrap()[&a.to_string_lossy().to_string()],
+            hash::calculate_content_hash_with("Rewritten", HashAlgorithm::Blake3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_keeps_search_index_in_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        std::fs::write(&a, "Original.").unwrap();
+        std::fs::write(&b, "Going away.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = Arc::new(InMemoryKbStore::new());
+        let index = Arc::new(SearchIndex::new());
+        let config = LoaderConfig {
+            update: true,
+            cleanup: true,
+            chunk_strategy: ChunkStrategy::Markdown,
+            ..Default::default()
+        };
+        let loader = IncrementalLoader::with_backend(store, config)
+            .unwrap()
+            .with_search_index(index.clone());
+
+        loader.load(&paths).await.unwrap();
+        assert_eq!(index.stats().documents, 2);
+
+        std::fs::write(&a, "# One\n\nChanged.\n\n# Two\n\nNow two sections.").unwrap();
+        std::fs::remove_file(&b).unwrap();
+        loader.load(&paths).await.unwrap();
+
+        let snapshot = index.snapshot();
+        assert!(snapshot.contains(&a.to_string_lossy()));
+        assert!(!snapshot.contains(&b.to_string_lossy()));
+        assert_eq!(snapshot.stats().chunks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_uses_configured_chunk_strategy() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let words: Vec<String> = (0..10).map(|i| format!("word{}", i)).collect();
+        std::fs::write(&a, words.join(" ")).unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            chunk_strategy: ChunkStrategy::FixedToken {
+                size: 4,
+                overlap: 2,
+            },
+            ..Default::default()
+        };
+        IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+
+        let chunks = store.get_file_chunks(&a).await;
+        assert_eq!(chunks.len(), 4);
+        for chunk in &chunks {
+            assert_eq!(chunk.metadata["chunk_strategy"], "fixed-token:4:2");
+            assert_eq!(chunk.metadata["total_chunks"], 4);
+            assert_eq!(
+                chunk.metadata["word_count"],
+                chunk.content.split_whitespace().count()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_semantic_strategy_without_chunker_falls_back_to_sentence() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        std::fs::write(&a, "The first sentence is here. The second follows it.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            chunk_strategy: ChunkStrategy::Semantic,
+            ..Default::default()
+        };
+        IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+
+        let chunks = store.get_file_chunks(&a).await;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].metadata["chunk_strategy"], "sentence");
+        assert_eq!(chunks[0].metadata["similarity_metric"], "cosine");
+    }
+
+    #[test]
+    fn test_loader_config_validate_chunk_strategy() {
+        let config = LoaderConfig {
+            chunk_strategy: ChunkStrategy::FixedToken {
+                size: 8,
+                overlap: 8,
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_records_from_stream() {
+        let input = concat!(
+            r#"{"id": "a", "path": "cms/a.md", "content": "First document text.", "category": "howto", "tags": ["cms"]}"#,
+            "\n",
+            "{not json\n",
+            "\n",
+            r#"{"id": "b", "content": "Second document text."}"#,
+            "\n",
+            r#"{"id": "c", "content": "Bad category.", "category": "recipes"}"#,
+            "\n",
+        );
+
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            update: true,
+            ..Default::default()
+        };
+        let loader = IncrementalLoader::with_backend(Arc::new(store.clone()), config).unwrap();
+
+        let stats = loader.load_records(input.as_bytes()).await.unwrap();
+        assert_eq!(stats.files_added, 2);
+        assert_eq!(stats.records_invalid, 2);
+        assert_eq!(stats.chunks_inserted, 2);
+
+        let chunks = store.get_file_chunks(Path::new("cms/a.md")).await;
+        assert_eq!(chunks[0].metadata["record_id"], "a");
+        assert_eq!(chunks[0].metadata["category"], "how-to");
+        assert_eq!(chunks[0].metadata["tags"], serde_json::json!(["cms"]));
+        assert_eq!(store.get_file_chunks(Path::new("b")).await.len(), 1);
+
+        // Replaying skips unchanged records; a changed record is replaced
+        let replay = concat!(
+            r#"{"id": "a", "path": "cms/a.md", "content": "First document text.", "category": "howto", "tags": ["cms"]}"#,
+            "\n",
+            r#"{"id": "b", "content": "Second document, now revised."}"#,
+            "\n",
+        );
+        let stats = loader.load_records(replay.as_bytes()).await.unwrap();
+        assert_eq!(stats.files_skipped, 1);
+        assert_eq!(stats.files_updated, 1);
+        assert!(store.get_file_chunks(Path::new("b")).await[0]
+            .content
+            .contains("revised"));
+    }
+
+    #[tokio::test]
+    async fn test_load_records_dry_run_writes_nothing() {
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+        let stats = IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+            .unwrap()
+            .load_records(r#"{"id": "a", "content": "Some text here."}"#.as_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_added, 1);
+        assert_eq!(store.chunk_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_utf16_document_is_stored_as_utf8() {
+        let text = "# Überblick\n\nThis guide explains how the loader stores documents. \
+                    Every file is converted to UTF-8 before it is split into chunks.";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("guide.md");
+        let bytes: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        std::fs::write(&path, bytes).unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        IncrementalLoader::with_backend(Arc::new(store.clone()), LoaderConfig::default())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+
+        let chunks = store.get_file_chunks(&path).await;
+        assert!(!chunks.is_empty());
+        let stored: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert!(stored.join(" ").contains("Überblick"));
+        assert!(stored.iter().all(|c| !c.contains('\0')));
+        for chunk in &chunks {
+            assert_eq!(chunk.metadata["encoding"], "utf-16le");
+            assert_eq!(chunk.metadata["language"], "eng");
+        }
+    }
+
+    #[test]
+    fn test_loader_config_validate_concurrency() {
+        let config = LoaderConfig {
+            concurrency: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_loader_config_validate_checkpoint_interval() {
+        let config = LoaderConfig {
+            checkpoint_interval: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_load_matches_sequential() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..40 {
+            std::fs::write(
+                dir.path().join(format!("doc{:02}.md", i)),
+                format!(
+                    "# Document {}\n\nFirst part of {}.\n\n# More\n\nSecond part.",
+                    i, i
+                ),
+            )
+            .unwrap();
+        }
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let mut loaded = Vec::new();
+        for concurrency in [1, 8] {
+            let store = InMemoryKbStore::new();
+            let config = LoaderConfig {
+                chunk_strategy: ChunkStrategy::Markdown,
+                concurrency,
+                ..Default::default()
+            };
+            let stats = IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+                .unwrap()
+                .load(&paths)
+                .await
+                .unwrap();
+
+            let mut chunks = Vec::new();
+            for i in 0..40 {
+                let path = dir.path().join(format!("doc{:02}.md", i));
+                for chunk in store.get_file_chunks(&path).await {
+                    chunks.push((i, chunk.chunk_id, chunk.content, chunk.embedding));
+                }
+            }
+            loaded.push((stats.files_added, stats.chunks_inserted, chunks));
+        }
+
+        assert_eq!(loaded[0].0, 40);
+        assert_eq!(loaded[0].1, 80);
+        assert_eq!(loaded[0], loaded[1]);
+    }
+
+    #[tokio::test]
+    async fn test_load_collects_failures_up_to_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bad1.md"), b"Caf\xe9 one").unwrap();
+        std::fs::write(dir.path().join("bad2.md"), b"Caf\xe9 two").unwrap();
+        std::fs::write(dir.path().join("good.md"), "Plain text.").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let config = LoaderConfig {
+            encoding: EncodingMode::Utf8,
+            concurrency: 4,
+            max_failures: 2,
+            ..Default::default()
+        };
+        let store = InMemoryKbStore::new();
+        let stats = IncrementalLoader::with_backend(Arc::new(store.clone()), config.clone())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        let mut failed: Vec<&str> = stats
+            .failed_files
+            .iter()
+            .map(|f| f.path.rsplit('/').next().unwrap())
+            .collect();
+        failed.sort();
+        assert_eq!(failed, vec!["bad1.md", "bad2.md"]);
+        assert_eq!(stats.chunks_inserted, 1);
+        assert_eq!(store.chunk_count().await, 1);
+
+        // One failure fewer allowed aborts the run
+        let config = LoaderConfig {
+            max_failures: 1,
+            ..config
+        };
+        let err = IncrementalLoader::with_backend(Arc::new(InMemoryKbStore::new()), config)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, KbError::Encoding { .. }), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_load_resumes_from_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        for i in 0..10 {
+            std::fs::write(
+                docs.join(format!("doc{}.md", i)),
+                format!("Document {}.", i),
+            )
+            .unwrap();
+        }
+        // Fails to decode as UTF-8, interrupting the first load
+        std::fs::write(docs.join("bad.md"), b"Caf\xe9 au lait").unwrap();
+        let paths = vec![docs.to_string_lossy().to_string()];
+        let checkpoint_path = dir.path().join("load.checkpoint");
+
+        // Force mode ignores what is stored, so only the checkpoint can
+        // prevent files from being loaded twice
+        let config = LoaderConfig {
+            force: true,
+            encoding: EncodingMode::Utf8,
+            checkpoint_path: Some(checkpoint_path.clone()),
+            checkpoint_interval: 1,
+            ..Default::default()
+        };
+        let store = InMemoryKbStore::new();
+        let loader = |config: LoaderConfig| {
+            IncrementalLoader::with_backend(Arc::new(store.clone()), config).unwrap()
+        };
+
+        assert!(loader(config.clone()).load(&paths).await.is_err());
+        let completed = LoadCheckpoint::read(&checkpoint_path).await.unwrap().len();
+        assert_eq!(store.chunk_count().await, completed);
+
+        // A cancelled load processes nothing and keeps the checkpoint
+        let token = CancellationToken::new();
+        token.cancel();
+        let stats = loader(config.clone())
+            .with_cancellation(token)
+            .load(&paths)
+            .await
+            .unwrap();
+        assert!(stats.cancelled);
+        assert_eq!(stats.chunks_inserted, 0);
+        assert_eq!(stats.files_checkpointed, completed);
+        assert_eq!(
+            LoadCheckpoint::read(&checkpoint_path).await.unwrap().len(),
+            completed
+        );
+
+        // The resumed load processes only the remaining files
+        std::fs::write(docs.join("bad.md"), "Café au lait").unwrap();
+        let stats = loader(config).load(&paths).await.unwrap();
+        assert!(!stats.cancelled);
+        assert_eq!(stats.files_checkpointed, completed);
+        assert_eq!(stats.files_skipped, 0);
+        assert_eq!(stats.files_added, 11 - completed);
+        assert_eq!(stats.chunks_inserted, 11 - completed);
+        assert_eq!(store.chunk_count().await, 11);
+        assert!(!checkpoint_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_load_reports_malformed_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.md"), b"Caf\xe9 au lait").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let store = InMemoryKbStore::new();
+        let config = LoaderConfig {
+            encoding: EncodingMode::Utf8,
+            ..Default::default()
+        };
+        let err = IncrementalLoader::with_backend(Arc::new(store.clone()), config)
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, KbError::Encoding { .. }), "{:?}", err);
+        assert!(err.to_string().contains("a.md"));
+
+        // Detection reads the same file as Latin-1
+        IncrementalLoader::with_backend(Arc::new(store.clone()), LoaderConfig::default())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        let chunks = store.get_file_chunks(&dir.path().join("a.md")).await;
+        assert_eq!(chunks[0].content, "Café au lait");
+        assert_eq!(chunks[0].metadata["encoding"], "latin-1");
+    }
 }
@@ -0,0 +1,694 @@
+//! In-memory store for knowledge base operations
+//!
+//! This module provides a `KbBackend` implementation that keeps all file
+//! metadata, chunks and saved searches in process memory. It is intended for tests and
+//! small, ephemeral deployments where a database is unnecessary. Behavior
+//! mirrors the PostgreSQL store so callers can swap backends transparently.
+
+use crate::kb::backend::{orphaned_files, CleanupStats, KbBackend};
+use crate::kb::error::{KbError, Result};
+use crate::kb::hash::FileMetadata;
+use crate::kb::saved_search::{NewSavedSearch, SavedSearchChanges, SavedSearchRecord};
+use crate::kb::store::DocumentChunk;
+use crate::semantic::types::SemanticChunk;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+/// Document chunks stored for a single file
+#[derive(Debug, Clone)]
+struct StoredFile {
+    file_hash: String,
+    chunks: Vec<DocumentChunk>,
+    metadata: Option<FileMetadata>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    documents: HashMap<String, StoredFile>,
+    semantic_chunks: HashMap<String, Vec<SemanticChunk>>,
+    saved_searches: BTreeMap<i32, SavedSearchRecord>,
+    last_saved_search_id: i32,
+}
+
+/// Knowledge base store that keeps everything in memory
+///
+/// Cloning the store is cheap and clones share the same underlying data,
+/// matching the semantics of cloning a `KbStore` that shares a pool.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use xze_core::kb::backend::KbBackend;
+/// use xze_core::kb::memory::InMemoryKbStore;
+/// use xze_core::kb::store::DocumentChunk;
+///
+/// # async fn example() -> xze_core::kb::error::Result<()> {
+/// let store = InMemoryKbStore::new();
+/// let chunk = DocumentChunk::new(
+///     "chunk_0".to_string(),
+///     "Example content".to_string(),
+///     vec![0.1, 0.2, 0.3],
+///     serde_json::json!({}),
+/// );
+/// store
+///     .insert_file_chunks(Path::new("docs/example.md"), "abc123", &[chunk])
+///     .await?;
+///
+/// let files = store.query_existing_files().await?;
+/// assert_eq!(files.get("docs/example.md").map(String::as_str), Some("abc123"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryKbStore {
+    state: Arc<RwLock<MemoryState>>,
+}
+
+impl InMemoryKbStore {
+    /// Create a new, empty in-memory store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the document chunks stored for a file
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path of the file to look up
+    ///
+    /// # Returns
+    ///
+    /// Returns the stored chunks in insertion order, or an empty vector if
+    /// the file is unknown
+    pub async fn get_file_chunks(&self, file_path: &Path) -> Vec<DocumentChunk> {
+        let key = file_path.to_string_lossy();
+        let state = self.state.read().await;
+        state
+            .documents
+            .get(key.as_ref())
+            .map(|file| file.chunks.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the total number of document chunks across all files
+    pub async fn chunk_count(&self) -> usize {
+        let state = self.state.read().await;
+        state.documents.values().map(|file| file.chunks.len()).sum()
+    }
+}
+
+#[async_trait]
+impl KbBackend for InMemoryKbStore {
+    async fn query_existing_files(&self) -> Result<HashMap<String, String>> {
+        let state = self.state.read().await;
+        let files: HashMap<String, String> = state
+            .documents
+            .iter()
+            .map(|(path, file)| (path.clone(), file.file_hash.clone()))
+            .collect();
+
+        info!("Retrieved {} files from memory store", files.len());
+        Ok(files)
+    }
+
+    async fn query_file_metadata(&self) -> Result<HashMap<String, FileMetadata>> {
+        let state = self.state.read().await;
+        Ok(state
+            .documents
+            .iter()
+            .filter_map(|(path, file)| file.metadata.map(|metadata| (path.clone(), metadata)))
+            .collect())
+    }
+
+    async fn record_file_metadata(&self, file_path: &Path, metadata: FileMetadata) -> Result<()> {
+        let key = file_path.to_string_lossy();
+        let mut state = self.state.write().await;
+        if let Some(file) = state.documents.get_mut(key.as_ref()) {
+            file.metadata = Some(metadata);
+        }
+        Ok(())
+    }
+
+    async fn delete_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        let key = file_path.to_string_lossy();
+        let mut state = self.state.write().await;
+        let removed = state
+            .documents
+            .remove(key.as_ref())
+            .map(|file| file.chunks.len() as u64)
+            .unwrap_or(0);
+
+        debug!("Deleted {} chunks for file: {}", removed, key);
+        Ok(removed)
+    }
+
+    async fn insert_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        // The database store inserts one row per chunk, so an empty batch
+        // leaves no trace of the file.
+        if chunks.is_empty() {
+            return Ok(());
+        }
+
+        let key = file_path.to_string_lossy().to_string();
+        let mut state = self.state.write().await;
+        let entry = state.documents.entry(key).or_insert_with(|| StoredFile {
+            file_hash: file_hash.to_string(),
+            chunks: Vec::new(),
+            metadata: None,
+        });
+        entry.file_hash = file_hash.to_string();
+        entry.chunks.extend_from_slice(chunks);
+        entry.metadata = None;
+
+        debug!(
+            "Inserted {} chunks for file: {}",
+            chunks.len(),
+            file_path.display()
+        );
+        Ok(())
+    }
+
+    async fn update_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        // Holding the write lock for both steps gives the same atomicity as
+        // the transactional database update.
+        let key = file_path.to_string_lossy().to_string();
+        let mut state = self.state.write().await;
+        state.documents.remove(&key);
+        if !chunks.is_empty() {
+            state.documents.insert(
+                key,
+                StoredFile {
+                    file_hash: file_hash.to_string(),
+                    chunks: chunks.to_vec(),
+                    metadata: None,
+                },
+            );
+        }
+
+        debug!(
+            "Updated file: {} with {} chunks",
+            file_path.display(),
+            chunks.len()
+        );
+        Ok(())
+    }
+
+    async fn cleanup_deleted_files(&self, deleted_files: &[String]) -> Result<u64> {
+        if deleted_files.is_empty() {
+            return Ok(0);
+        }
+
+        let mut state = self.state.write().await;
+        let total_deleted = deleted_files
+            .iter()
+            .filter_map(|path| state.documents.remove(path))
+            .map(|file| file.chunks.len() as u64)
+            .sum();
+
+        info!(
+            "Cleanup complete: deleted {} total chunks from {} files",
+            total_deleted,
+            deleted_files.len()
+        );
+        Ok(total_deleted)
+    }
+
+    async fn vacuum(&self, deleted_files: &[String]) -> Result<CleanupStats> {
+        // Holding the write lock makes the removal atomic, like the
+        // transaction of the database stores
+        let mut state = self.state.write().await;
+        let stored: Vec<String> = state
+            .documents
+            .keys()
+            .chain(state.semantic_chunks.keys())
+            .cloned()
+            .collect();
+        let orphaned = orphaned_files(&stored, deleted_files);
+
+        let mut stats = CleanupStats {
+            files_removed: orphaned.len(),
+            ..Default::default()
+        };
+        for path in &orphaned {
+            let chunks = state.documents.remove(path).map(|file| file.chunks);
+            for chunk in chunks.iter().flatten() {
+                stats.chunks_removed += 1;
+                stats.bytes_reclaimed += (chunk.content.len() + chunk.embedding.len() * 4) as u64;
+            }
+            let semantic = state.semantic_chunks.remove(path);
+            for chunk in semantic.iter().flatten() {
+                stats.chunks_removed += 1;
+                stats.bytes_reclaimed += (chunk.content.len() + chunk.embedding.len() * 4) as u64;
+            }
+        }
+
+        info!(
+            "Vacuum complete: removed {} chunks ({} bytes) from {} files",
+            stats.chunks_removed, stats.bytes_reclaimed, stats.files_removed
+        );
+        Ok(stats)
+    }
+
+    async fn store_semantic_chunks(
+        &self,
+        file_path: &Path,
+        _file_hash: &str,
+        chunks: &[SemanticChunk],
+    ) -> Result<()> {
+        let key = file_path.to_string_lossy().to_string();
+        let mut state = self.state.write().await;
+        let stored = state.semantic_chunks.entry(key.clone()).or_default();
+
+        for chunk in chunks {
+            // Mirror the (file_path, chunk_index) unique constraint
+            if stored
+                .iter()
+                .any(|existing| existing.chunk_index == chunk.chunk_index)
+            {
+                return Err(KbError::database(format!(
+                    "Failed to insert semantic chunk {} for file {}: duplicate chunk index",
+                    chunk.chunk_index, key
+                )));
+            }
+
+            stored.push(chunk.clone());
+        }
+
+        debug!("Stored {} semantic chunks for file: {}", chunks.len(), key);
+        Ok(())
+    }
+
+    async fn delete_semantic_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        let key = file_path.to_string_lossy();
+        let mut state = self.state.write().await;
+        let removed = state
+            .semantic_chunks
+            .remove(key.as_ref())
+            .map(|chunks| chunks.len() as u64)
+            .unwrap_or(0);
+
+        debug!("Deleted {} semantic chunks for file: {}", removed, key);
+        Ok(removed)
+    }
+
+    async fn get_semantic_chunks_for_file(&self, file_path: &Path) -> Result<Vec<SemanticChunk>> {
+        let key = file_path.to_string_lossy();
+        let state = self.state.read().await;
+        let mut chunks: Vec<SemanticChunk> = state
+            .semantic_chunks
+            .get(key.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        chunks.sort_by_key(|chunk| chunk.chunk_index);
+
+        Ok(chunks)
+    }
+
+    async fn create_saved_search(&self, search: NewSavedSearch) -> Result<SavedSearchRecord> {
+        let mut state = self.state.write().await;
+        // Ids are never reused, like a SERIAL column
+        state.last_saved_search_id += 1;
+        let now = chrono::Utc::now();
+        let saved = SavedSearchRecord {
+            id: state.last_saved_search_id,
+            user_id: search.user_id,
+            name: search.name,
+            description: search.description,
+            search_request: search.search_request,
+            created_at: now,
+            updated_at: now,
+        };
+        state.saved_searches.insert(saved.id, saved.clone());
+
+        debug!(
+            "Created saved search {} for user {}",
+            saved.id, saved.user_id
+        );
+        Ok(saved)
+    }
+
+    async fn list_saved_searches(&self, user_id: &str) -> Result<Vec<SavedSearchRecord>> {
+        let state = self.state.read().await;
+        // Newest first; ids break ties between searches created together
+        Ok(state
+            .saved_searches
+            .values()
+            .rev()
+            .filter(|saved| saved.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_saved_search(&self, user_id: &str, id: i32) -> Result<Option<SavedSearchRecord>> {
+        let state = self.state.read().await;
+        Ok(state
+            .saved_searches
+            .get(&id)
+            .filter(|saved| saved.user_id == user_id)
+            .cloned())
+    }
+
+    async fn update_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+        changes: SavedSearchChanges,
+    ) -> Result<Option<SavedSearchRecord>> {
+        let mut state = self.state.write().await;
+        let Some(saved) = state
+            .saved_searches
+            .get_mut(&id)
+            .filter(|saved| saved.user_id == user_id)
+        else {
+            return Ok(None);
+        };
+        changes.apply(saved, chrono::Utc::now());
+        Ok(Some(saved.clone()))
+    }
+
+    async fn delete_saved_search(&self, user_id: &str, id: i32) -> Result<bool> {
+        let mut state = self.state.write().await;
+        let owned = state
+            .saved_searches
+            .get(&id)
+            .is_some_and(|saved| saved.user_id == user_id);
+        if owned {
+            state.saved_searches.remove(&id);
+        }
+        Ok(owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::types::ChunkMetadata;
+
+    fn doc_chunk(id: &str) -> DocumentChunk {
+        DocumentChunk::new(
+            id.to_string(),
+            format!("content of {}", id),
+            vec![0.1, 0.2],
+            serde_json::json!({}),
+        )
+    }
+
+    fn semantic_chunk(index: usize) -> SemanticChunk {
+        let metadata = ChunkMetadata::new("docs/a.md".to_string(), "content");
+        SemanticChunk::new(format!("chunk {}", index), index, 3, 0, 1, 0.9, metadata)
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_query_existing_files() {
+        let store = InMemoryKbStore::new();
+        store
+            .insert_file_chunks(Path::new("a.md"), "hash_a", &[doc_chunk("c0")])
+            .await
+            .unwrap();
+        store
+            .insert_file_chunks(Path::new("b.md"), "hash_b", &[])
+            .await
+            .unwrap();
+
+        let files = store.query_existing_files().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files.get("a.md").unwrap(), "hash_a");
+    }
+
+    #[tokio::test]
+    async fn test_update_replaces_chunks() {
+        let store = InMemoryKbStore::new();
+        let path = Path::new("a.md");
+        store
+            .insert_file_chunks(path, "old", &[doc_chunk("c0"), doc_chunk("c1")])
+            .await
+            .unwrap();
+        store
+            .update_file_chunks(path, "new", &[doc_chunk("c2")])
+            .await
+            .unwrap();
+
+        let chunks = store.get_file_chunks(path).await;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].chunk_id, "c2");
+        assert_eq!(
+            store
+                .query_existing_files()
+                .await
+                .unwrap()
+                .get("a.md")
+                .unwrap(),
+            "new"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_metadata_is_cleared_by_writes() {
+        let store = InMemoryKbStore::new();
+        let path = Path::new("a.md");
+        let metadata = FileMetadata {
+            size: 12,
+            modified_ns: 1_700_000_000_000_000_000,
+        };
+
+        // Unknown files are ignored
+        store.record_file_metadata(path, metadata).await.unwrap();
+        assert!(store.query_file_metadata().await.unwrap().is_empty());
+
+        store
+            .insert_file_chunks(path, "old", &[doc_chunk("c0")])
+            .await
+            .unwrap();
+        store.record_file_metadata(path, metadata).await.unwrap();
+        assert_eq!(
+            store.query_file_metadata().await.unwrap().get("a.md"),
+            Some(&metadata)
+        );
+
+        store
+            .update_file_chunks(path, "new", &[doc_chunk("c1")])
+            .await
+            .unwrap();
+        assert!(store.query_file_metadata().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_cleanup_return_chunk_counts() {
+        let store = InMemoryKbStore::new();
+        store
+            .insert_file_chunks(Path::new("a.md"), "h", &[doc_chunk("c0"), doc_chunk("c1")])
+            .await
+            .unwrap();
+        store
+            .insert_file_chunks(Path::new("b.md"), "h", &[doc_chunk("c0")])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store
+                .delete_chunks_for_file(Path::new("a.md"))
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            store
+                .cleanup_deleted_files(&["b.md".to_string(), "missing.md".to_string()])
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(store.chunk_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_removes_orphaned_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("kept.md");
+        let deleted = dir.path().join("deleted.md");
+        std::fs::write(&kept, "kept").unwrap();
+        std::fs::write(&deleted, "deleted").unwrap();
+        let missing = dir.path().join("missing.md");
+
+        let store = InMemoryKbStore::new();
+        for path in [&kept, &deleted, &missing] {
+            store
+                .insert_file_chunks(path, "hash", &[doc_chunk("0")])
+                .await
+                .unwrap();
+        }
+        store
+            .store_semantic_chunks(&missing, "hash", &[semantic_chunk(0)])
+            .await
+            .unwrap();
+
+        let stats = store
+            .vacuum(&[deleted.to_string_lossy().to_string()])
+            .await
+            .unwrap();
+        assert_eq!(stats.files_removed, 2);
+        assert_eq!(stats.chunks_removed, 3);
+        // Two "content of 0" chunks with 2 floats each, and "chunk 0"
+        assert_eq!(stats.bytes_reclaimed, 2 * (12 + 8) + 7);
+        assert_eq!(store.chunk_count().await, 1);
+        assert!(store
+            .get_semantic_chunks_for_file(&missing)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(store.vacuum(&[]).await.unwrap(), CleanupStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_semantic_chunks_roundtrip_ordered() {
+        let store = InMemoryKbStore::new();
+        let path = Path::new("docs/a.md");
+        store
+            .store_semantic_chunks(path, "h", &[semantic_chunk(2), semantic_chunk(0)])
+            .await
+            .unwrap();
+
+        let chunks = store.get_semantic_chunks_for_file(path).await.unwrap();
+        let indices: Vec<usize> = chunks.iter().map(|c| c.chunk_index).collect();
+        assert_eq!(indices, vec![0, 2]);
+
+        assert_eq!(
+            store.delete_semantic_chunks_for_file(path).await.unwrap(),
+            2
+        );
+        assert!(store
+            .get_semantic_chunks_for_file(path)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_semantic_chunks_duplicate_index_rejected() {
+        let store = InMemoryKbStore::new();
+        let path = Path::new("docs/a.md");
+        store
+            .store_semantic_chunks(path, "h", &[semantic_chunk(0)])
+            .await
+            .unwrap();
+
+        let result = store
+            .store_semantic_chunks(path, "h", &[semantic_chunk(0)])
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_state() {
+        let store = InMemoryKbStore::new();
+        let clone = store.clone();
+        clone
+            .insert_file_chunks(Path::new("a.md"), "h", &[doc_chunk("c0")])
+            .await
+            .unwrap();
+
+        assert_eq!(store.chunk_count().await, 1);
+    }
+
+    fn new_search(user_id: &str, name: &str) -> NewSavedSearch {
+        NewSavedSearch {
+            user_id: user_id.to_string(),
+            name: name.to_string(),
+            description: None,
+            search_request: serde_json::json!({ "query": name }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches_are_scoped_by_user() {
+        let store = InMemoryKbStore::new();
+        let first = store
+            .create_saved_search(new_search("alice", "first"))
+            .await
+            .unwrap();
+        let second = store
+            .create_saved_search(new_search("alice", "second"))
+            .await
+            .unwrap();
+        let other = store
+            .create_saved_search(new_search("bob", "other"))
+            .await
+            .unwrap();
+        assert_eq!((first.id, second.id, other.id), (1, 2, 3));
+        assert_eq!(first.created_at, first.updated_at);
+
+        let names: Vec<String> = store
+            .list_saved_searches("alice")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|saved| saved.name)
+            .collect();
+        assert_eq!(names, vec!["second", "first"]);
+
+        assert!(store
+            .get_saved_search("bob", first.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store
+            .update_saved_search("bob", first.id, SavedSearchChanges::default())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!store.delete_saved_search("bob", first.id).await.unwrap());
+        assert_eq!(
+            store.get_saved_search("alice", first.id).await.unwrap(),
+            Some(first)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_update_and_delete() {
+        let store = InMemoryKbStore::new();
+        let saved = store
+            .create_saved_search(new_search("alice", "first"))
+            .await
+            .unwrap();
+
+        let changes = SavedSearchChanges {
+            description: Some("Described".to_string()),
+            ..Default::default()
+        };
+        let updated = store
+            .update_saved_search("alice", saved.id, changes)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.name, "first");
+        assert_eq!(updated.description.as_deref(), Some("Described"));
+        assert_eq!(updated.created_at, saved.created_at);
+        assert!(updated.updated_at >= saved.updated_at);
+
+        assert!(store.delete_saved_search("alice", saved.id).await.unwrap());
+        assert!(!store.delete_saved_search("alice", saved.id).await.unwrap());
+
+        // Ids of deleted searches are not reused
+        let next = store
+            .create_saved_search(new_search("alice", "next"))
+            .await
+            .unwrap();
+        assert_eq!(next.id, saved.id + 1);
+    }
+}
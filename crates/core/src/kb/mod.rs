@@ -10,8 +10,14 @@
 //!
 //! - `hash`: File hashing utilities using SHA-256
 //! - `error`: Error types for KB operations
+//! - `encoding`: Text encoding and language detection for loaded files
 //! - `categorizer`: File categorization (Skip, Add, Update, Delete)
+//! - `checkpoint`: Progress files for resuming interrupted loads
+//! - `backend`: Storage backend trait shared by all store implementations
 //! - `store`: Database operations for file metadata and chunks
+//! - `memory`: In-memory store for tests and ephemeral deployments
+//! - `sqlite`: Single-file SQLite store (requires the `sqlite` feature)
+//! - `saved_search`: Per-user saved search records kept by every backend
 //! - `loader`: Incremental loading orchestration
 //!
 //! # Usage
@@ -46,17 +52,35 @@
 //! - Phase 6 (Planned): CLI polish
 //! - Phase 7 (Planned): Testing and documentation
 
+pub mod backend;
 pub mod categorizer;
+pub mod checkpoint;
+pub mod encoding;
 pub mod error;
 pub mod hash;
+pub mod import;
 pub mod loader;
+pub mod memory;
+pub mod saved_search;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod store;
 
 // Re-export commonly used types
-pub use categorizer::{CategorizedFiles, FileCategorizer, FileCategory};
+pub use backend::{CleanupStats, KbBackend, KbBackendKind};
+pub use categorizer::{
+    CategorizedFiles, DiscoveredFiles, FileCategorizer, FileCategory, KnownFiles,
+};
+pub use checkpoint::LoadCheckpoint;
 pub use error::{KbError, Result};
-pub use hash::{calculate_content_hash, calculate_file_hash, verify_hash_format};
-pub use loader::{IncrementalLoader, LoadStats, LoaderConfig};
+pub use hash::{
+    calculate_content_hash, calculate_file_hash, verify_hash_format, FileMetadata, HashAlgorithm,
+};
+pub use import::ImportRecord;
+pub use loader::{FailedFile, IncrementalLoader, LoadStats, LoaderConfig};
+pub use memory::InMemoryKbStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteKbStore;
 pub use store::KbStore;
 
 #[cfg(test)]
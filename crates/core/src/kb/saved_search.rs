@@ -0,0 +1,103 @@
+//! Saved search records
+//!
+//! Users can save search requests and run them again later. Every
+//! [`KbBackend`](crate::kb::backend::KbBackend) stores them per user: the
+//! PostgreSQL store in the `saved_searches` table and the in-memory store
+//! in process memory. The store assigns ids and maintains the `created_at`
+//! and `updated_at` timestamps.
+//!
+//! The search request itself is kept as JSON, so this module does not
+//! depend on the request types of the server.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::kb::backend::KbBackend;
+//! use xze_core::kb::memory::InMemoryKbStore;
+//! use xze_core::kb::saved_search::{NewSavedSearch, SavedSearchChanges};
+//!
+//! # async fn example() -> xze_core::kb::error::Result<()> {
+//! let store = InMemoryKbStore::new();
+//! let saved = store
+//!     .create_saved_search(NewSavedSearch {
+//!         user_id: "alice".to_string(),
+//!         name: "Async docs".to_string(),
+//!         description: None,
+//!         search_request: serde_json::json!({ "query": "rust async" }),
+//!     })
+//!     .await?;
+//!
+//! let changes = SavedSearchChanges {
+//!     name: Some("Async Rust".to_string()),
+//!     ..Default::default()
+//! };
+//! let updated = store.update_saved_search("alice", saved.id, changes).await?;
+//! assert_eq!(updated.unwrap().name, "Async Rust");
+//!
+//! // Other users cannot see it
+//! assert!(store.get_saved_search("bob", saved.id).await?.is_none());
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A stored saved search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearchRecord {
+    /// Identifier assigned by the store
+    pub id: i32,
+    /// User who owns the search
+    pub user_id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// The saved search request as JSON
+    pub search_request: serde_json::Value,
+    /// When the search was created
+    pub created_at: DateTime<Utc>,
+    /// When the search was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A saved search to create
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewSavedSearch {
+    /// User who owns the search
+    pub user_id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// The search request as JSON
+    pub search_request: serde_json::Value,
+}
+
+/// Changes to a saved search; `None` fields are left unchanged
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SavedSearchChanges {
+    /// New name
+    pub name: Option<String>,
+    /// New description
+    pub description: Option<String>,
+    /// New search request as JSON
+    pub search_request: Option<serde_json::Value>,
+}
+
+impl SavedSearchChanges {
+    /// Apply the changes to `record`, setting its `updated_at` to `now`
+    pub(crate) fn apply(self, record: &mut SavedSearchRecord, now: DateTime<Utc>) {
+        if let Some(name) = self.name {
+            record.name = name;
+        }
+        if let Some(description) = self.description {
+            record.description = Some(description);
+        }
+        if let Some(search_request) = self.search_request {
+            record.search_request = search_request;
+        }
+        record.updated_at = now;
+    }
+}
@@ -0,0 +1,934 @@
+//! SQLite store for knowledge base operations
+//!
+//! This module provides a `KbBackend` implementation that keeps file
+//! metadata, chunks and saved searches in a single SQLite file, for local
+//! use where running PostgreSQL is not worth it. Behavior mirrors the
+//! PostgreSQL store so the incremental loader works the same on both: a
+//! second load of an unchanged directory skips every file using the stored
+//! content hashes.
+//!
+//! The schema is created and upgraded when the store is opened. Each entry
+//! of [`MIGRATIONS`] runs once, in order, and the number applied is kept in
+//! the database's `user_version` pragma.
+//!
+//! Only available with the `sqlite` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use xze_core::kb::loader::{IncrementalLoader, LoaderConfig};
+//! use xze_core::kb::sqlite::SqliteKbStore;
+//!
+//! # async fn example() -> xze_core::kb::error::Result<()> {
+//! let store = SqliteKbStore::open("xze.db").await?;
+//! let loader = IncrementalLoader::with_backend(Arc::new(store), LoaderConfig::default())?;
+//! let stats = loader.load(&["./docs".to_string()]).await?;
+//! println!("Skipped {} unchanged files", stats.files_skipped);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::kb::backend::{orphaned_files, CleanupStats, KbBackend};
+use crate::kb::error::{KbError, Result};
+use crate::kb::hash::FileMetadata;
+use crate::kb::saved_search::{NewSavedSearch, SavedSearchChanges, SavedSearchRecord};
+use crate::kb::store::DocumentChunk;
+use crate::semantic::types::{ChunkMetadata, SemanticChunk};
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::types::Json;
+use sqlx::{Executor, Row};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use tracing::{debug, info};
+
+/// Schema migrations, applied in order when a store is opened
+///
+/// Never edit an entry once released; append a new one instead.
+pub const MIGRATIONS: &[&str] = &[r#"
+    CREATE TABLE documents (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        file_path TEXT NOT NULL,
+        file_hash TEXT NOT NULL,
+        chunk_id TEXT NOT NULL,
+        content TEXT NOT NULL,
+        embedding BLOB NOT NULL,
+        metadata TEXT NOT NULL,
+        file_size INTEGER,
+        file_mtime_ns INTEGER
+    );
+    CREATE INDEX idx_documents_path_hash ON documents(file_path, file_hash);
+
+    CREATE TABLE semantic_chunks (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        file_path TEXT NOT NULL,
+        file_hash TEXT NOT NULL,
+        chunk_index INTEGER NOT NULL CHECK (chunk_index >= 0),
+        total_chunks INTEGER NOT NULL CHECK (total_chunks > 0),
+        start_sentence INTEGER NOT NULL,
+        end_sentence INTEGER NOT NULL,
+        content TEXT NOT NULL,
+        embedding BLOB NOT NULL,
+        avg_similarity REAL NOT NULL,
+        source_file TEXT,
+        title TEXT,
+        category TEXT,
+        keywords TEXT NOT NULL DEFAULT '[]',
+        word_count INTEGER NOT NULL DEFAULT 0,
+        char_count INTEGER NOT NULL DEFAULT 0,
+        similarity_metric TEXT NOT NULL DEFAULT 'cosine',
+        overlap_sentences INTEGER NOT NULL DEFAULT 0,
+        UNIQUE (file_path, chunk_index)
+    );
+
+    CREATE TABLE saved_searches (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        user_id TEXT NOT NULL CHECK (length(trim(user_id)) > 0),
+        name TEXT NOT NULL CHECK (length(trim(name)) > 0),
+        description TEXT,
+        search_request TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_saved_searches_user_created ON saved_searches(user_id, created_at DESC);
+"#];
+
+/// Knowledge base store backed by a SQLite database
+///
+/// Cloning the store is cheap and clones share the same connection pool.
+#[derive(Debug, Clone)]
+pub struct SqliteKbStore {
+    pool: SqlitePool,
+}
+
+impl SqliteKbStore {
+    /// Open the database file at `path`, creating it if missing
+    ///
+    /// Pending migrations are applied before the store is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the file cannot be opened or a
+    /// migration fails
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(path.as_ref())
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.map_err(|e| {
+            KbError::database(format!(
+                "Failed to open SQLite database {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+
+        Self::with_pool(pool).await
+    }
+
+    /// Open a database from a `sqlite:` URL such as `sqlite://xze.db`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Config` if the URL is invalid and
+    /// `KbError::Database` if the database cannot be opened or migrated
+    pub async fn connect(url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(url)
+            .map_err(|e| KbError::config(format!("Invalid SQLite URL {}: {}", url, e)))?
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.map_err(|e| {
+            KbError::database(format!("Failed to open SQLite database {}: {}", url, e))
+        })?;
+
+        Self::with_pool(pool).await
+    }
+
+    /// Open a private database that lives only as long as the store
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the database cannot be created
+    pub async fn open_in_memory() -> Result<Self> {
+        // Every connection to `:memory:` is a separate database, so keep a
+        // single connection open for the lifetime of the pool
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .map_err(|e| {
+                KbError::database(format!("Failed to open in-memory SQLite database: {}", e))
+            })?;
+
+        Self::with_pool(pool).await
+    }
+
+    /// Use an existing pool, applying pending migrations
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if a migration fails
+    pub async fn with_pool(pool: SqlitePool) -> Result<Self> {
+        let store = Self { pool };
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Number of migrations applied to the database
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the query fails
+    pub async fn schema_version(&self) -> Result<usize> {
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to read schema version: {}", e)))?;
+        Ok(version as usize)
+    }
+
+    /// Apply the migrations newer than the database's schema version
+    async fn migrate(&self) -> Result<()> {
+        let current = self.schema_version().await?;
+        if current > MIGRATIONS.len() {
+            return Err(KbError::database(format!(
+                "SQLite schema version {} is newer than this build supports ({})",
+                current,
+                MIGRATIONS.len()
+            )));
+        }
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(current) {
+            let version = index + 1;
+            let failed = |e: sqlx::Error| {
+                KbError::database(format!(
+                    "Failed to apply SQLite migration {}: {}",
+                    version, e
+                ))
+            };
+
+            let mut tx = self.pool.begin().await.map_err(failed)?;
+            tx.execute(*migration).await.map_err(failed)?;
+            tx.execute(format!("PRAGMA user_version = {}", version).as_str())
+                .await
+                .map_err(failed)?;
+            tx.commit().await.map_err(failed)?;
+
+            info!("Applied SQLite migration {}", version);
+        }
+        Ok(())
+    }
+
+    /// Insert document chunk rows inside `tx`
+    async fn insert_chunk_rows(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        file_path: &str,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        for chunk in chunks {
+            sqlx::query(
+                "INSERT INTO documents (file_path, file_hash, chunk_id, content, embedding, metadata) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(file_path)
+            .bind(file_hash)
+            .bind(&chunk.chunk_id)
+            .bind(&chunk.content)
+            .bind(chunk.embedding_as_bytes())
+            .bind(chunk.metadata.to_string())
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to insert chunk {} for file {}: {}",
+                    chunk.chunk_id, file_path, e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KbBackend for SqliteKbStore {
+    async fn query_existing_files(&self) -> Result<HashMap<String, String>> {
+        let rows = sqlx::query("SELECT DISTINCT file_path, file_hash FROM documents")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to query existing files: {}", e)))?;
+
+        let files = rows
+            .iter()
+            .map(|row| Ok((get(row, "file_path")?, get(row, "file_hash")?)))
+            .collect::<Result<HashMap<String, String>>>()?;
+
+        info!("Retrieved {} files from SQLite store", files.len());
+        Ok(files)
+    }
+
+    async fn query_file_metadata(&self) -> Result<HashMap<String, FileMetadata>> {
+        // Same rule as the PostgreSQL store: every chunk row of the file
+        // must carry the same metadata
+        let rows = sqlx::query(
+            "SELECT file_path, MIN(file_size) AS file_size, MIN(file_mtime_ns) AS file_mtime_ns \
+             FROM documents \
+             GROUP BY file_path \
+             HAVING COUNT(*) = COUNT(file_size) \
+                AND COUNT(*) = COUNT(file_mtime_ns) \
+                AND MIN(file_size) = MAX(file_size) \
+                AND MIN(file_mtime_ns) = MAX(file_mtime_ns)",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to query file metadata: {}", e)))?;
+
+        rows.iter()
+            .map(|row| {
+                let size: i64 = get(row, "file_size")?;
+                Ok((
+                    get(row, "file_path")?,
+                    FileMetadata {
+                        size: size as u64,
+                        modified_ns: get(row, "file_mtime_ns")?,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    async fn record_file_metadata(&self, file_path: &Path, metadata: FileMetadata) -> Result<()> {
+        let file_path_str = file_path.to_string_lossy();
+        sqlx::query("UPDATE documents SET file_size = ?, file_mtime_ns = ? WHERE file_path = ?")
+            .bind(metadata.size as i64)
+            .bind(metadata.modified_ns)
+            .bind(file_path_str.as_ref())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to record metadata for file {}: {}",
+                    file_path_str, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    async fn delete_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        let file_path_str = file_path.to_string_lossy();
+        let result = sqlx::query("DELETE FROM documents WHERE file_path = ?")
+            .bind(file_path_str.as_ref())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to delete chunks for file {}: {}",
+                    file_path_str, e
+                ))
+            })?;
+
+        debug!(
+            "Deleted {} chunks for file: {}",
+            result.rows_affected(),
+            file_path_str
+        );
+        Ok(result.rows_affected())
+    }
+
+    async fn insert_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        let file_path_str = file_path.to_string_lossy();
+        // One transaction per file; SQLite commits each statement to disk
+        // otherwise
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| KbError::database(format!("Failed to begin transaction: {}", e)))?;
+        Self::insert_chunk_rows(&mut tx, &file_path_str, file_hash, chunks).await?;
+        tx.commit().await.map_err(|e| {
+            KbError::database(format!(
+                "Failed to commit chunks for file {}: {}",
+                file_path_str, e
+            ))
+        })?;
+
+        debug!(
+            "Inserted {} chunks for file: {}",
+            chunks.len(),
+            file_path_str
+        );
+        Ok(())
+    }
+
+    async fn update_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        let file_path_str = file_path.to_string_lossy();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| KbError::database(format!("Failed to begin transaction: {}", e)))?;
+
+        sqlx::query("DELETE FROM documents WHERE file_path = ?")
+            .bind(file_path_str.as_ref())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to delete old chunks for file {}: {}",
+                    file_path_str, e
+                ))
+            })?;
+        Self::insert_chunk_rows(&mut tx, &file_path_str, file_hash, chunks).await?;
+
+        tx.commit().await.map_err(|e| {
+            KbError::database(format!(
+                "Failed to commit transaction for file {}: {}",
+                file_path_str, e
+            ))
+        })?;
+
+        debug!(
+            "Updated file: {} with {} chunks",
+            file_path_str,
+            chunks.len()
+        );
+        Ok(())
+    }
+
+    async fn cleanup_deleted_files(&self, deleted_files: &[String]) -> Result<u64> {
+        let mut total_deleted = 0;
+        for file_path in deleted_files {
+            total_deleted += self.delete_chunks_for_file(Path::new(file_path)).await?;
+        }
+
+        if !deleted_files.is_empty() {
+            info!(
+                "Cleanup complete: deleted {} total chunks from {} files",
+                total_deleted,
+                deleted_files.len()
+            );
+        }
+        Ok(total_deleted)
+    }
+
+    async fn vacuum(&self, deleted_files: &[String]) -> Result<CleanupStats> {
+        let failed = |e: sqlx::Error| KbError::database(format!("Failed to clean up: {}", e));
+        let mut tx = self.pool.begin().await.map_err(failed)?;
+
+        let stored: Vec<String> = sqlx::query_scalar(
+            "SELECT file_path FROM documents UNION SELECT file_path FROM semantic_chunks",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(failed)?;
+        let orphaned = orphaned_files(&stored, deleted_files);
+
+        let mut stats = CleanupStats {
+            files_removed: orphaned.len(),
+            ..Default::default()
+        };
+        for path in &orphaned {
+            for table in ["documents", "semantic_chunks"] {
+                let bytes: Vec<i64> = sqlx::query_scalar(&format!(
+                    "DELETE FROM {} WHERE file_path = ? \
+                     RETURNING length(CAST(content AS BLOB)) + length(embedding)",
+                    table
+                ))
+                .bind(path)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(failed)?;
+                stats.chunks_removed += bytes.len();
+                stats.bytes_reclaimed += bytes.iter().sum::<i64>() as u64;
+            }
+        }
+        tx.commit().await.map_err(failed)?;
+
+        // VACUUM rewrites the file without the freed pages; it cannot run
+        // inside a transaction
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(failed)?;
+
+        info!(
+            "Vacuum complete: removed {} chunks ({} bytes) from {} files",
+            stats.chunks_removed, stats.bytes_reclaimed, stats.files_removed
+        );
+        Ok(stats)
+    }
+
+    async fn store_semantic_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[SemanticChunk],
+    ) -> Result<()> {
+        let file_path_str = file_path.to_string_lossy();
+
+        for chunk in chunks {
+            let embedding: Vec<u8> = chunk
+                .embedding
+                .iter()
+                .flat_map(|f| f.to_le_bytes())
+                .collect();
+
+            sqlx::query(
+                "INSERT INTO semantic_chunks \
+                 (file_path, file_hash, chunk_index, total_chunks, start_sentence, end_sentence, \
+                  content, embedding, avg_similarity, source_file, title, category, keywords, \
+                  word_count, char_count, similarity_metric, overlap_sentences) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(file_path_str.as_ref())
+            .bind(file_hash)
+            .bind(chunk.chunk_index as i64)
+            .bind(chunk.total_chunks as i64)
+            .bind(chunk.start_sentence as i64)
+            .bind(chunk.end_sentence as i64)
+            .bind(&chunk.content)
+            .bind(embedding)
+            .bind(chunk.avg_similarity)
+            .bind(&chunk.metadata.source_file)
+            .bind(chunk.metadata.title.as_deref())
+            .bind(chunk.metadata.category.as_deref())
+            .bind(Json(&chunk.metadata.keywords))
+            .bind(chunk.metadata.word_count as i64)
+            .bind(chunk.metadata.char_count as i64)
+            .bind(chunk.similarity_metric.to_string())
+            .bind(chunk.metadata.overlap_sentences as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to insert semantic chunk {} for file {}: {}",
+                    chunk.chunk_index, file_path_str, e
+                ))
+            })?;
+        }
+
+        debug!(
+            "Stored {} semantic chunks for file: {}",
+            chunks.len(),
+            file_path_str
+        );
+        Ok(())
+    }
+
+    async fn delete_semantic_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        let file_path_str = file_path.to_string_lossy();
+        let result = sqlx::query("DELETE FROM semantic_chunks WHERE file_path = ?")
+            .bind(file_path_str.as_ref())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to delete semantic chunks for file {}: {}",
+                    file_path_str, e
+                ))
+            })?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_semantic_chunks_for_file(&self, file_path: &Path) -> Result<Vec<SemanticChunk>> {
+        let file_path_str = file_path.to_string_lossy();
+        let rows = sqlx::query(
+            "SELECT chunk_index, total_chunks, start_sentence, end_sentence, content, embedding, \
+                    avg_similarity, source_file, title, category, keywords, word_count, \
+                    char_count, similarity_metric, overlap_sentences \
+             FROM semantic_chunks WHERE file_path = ? ORDER BY chunk_index ASC",
+        )
+        .bind(file_path_str.as_ref())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            KbError::database(format!(
+                "Failed to retrieve semantic chunks for file {}: {}",
+                file_path_str, e
+            ))
+        })?;
+
+        rows.iter().map(semantic_chunk_from_row).collect()
+    }
+
+    async fn create_saved_search(&self, search: NewSavedSearch) -> Result<SavedSearchRecord> {
+        let now = chrono::Utc::now();
+        let row = sqlx::query(&format!(
+            "INSERT INTO saved_searches \
+             (user_id, name, description, search_request, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?) RETURNING {}",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(&search.user_id)
+        .bind(&search.name)
+        .bind(&search.description)
+        .bind(&search.search_request)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to insert saved search: {}", e)))?;
+
+        saved_search_from_row(&row)
+    }
+
+    async fn list_saved_searches(&self, user_id: &str) -> Result<Vec<SavedSearchRecord>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM saved_searches WHERE user_id = ? ORDER BY created_at DESC, id DESC",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to list saved searches: {}", e)))?;
+
+        rows.iter().map(saved_search_from_row).collect()
+    }
+
+    async fn get_saved_search(&self, user_id: &str, id: i32) -> Result<Option<SavedSearchRecord>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM saved_searches WHERE id = ? AND user_id = ?",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to get saved search {}: {}", id, e)))?;
+
+        row.as_ref().map(saved_search_from_row).transpose()
+    }
+
+    async fn update_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+        changes: SavedSearchChanges,
+    ) -> Result<Option<SavedSearchRecord>> {
+        let row = sqlx::query(&format!(
+            "UPDATE saved_searches SET \
+             name = COALESCE(?, name), \
+             description = COALESCE(?, description), \
+             search_request = COALESCE(?, search_request), \
+             updated_at = ? \
+             WHERE id = ? AND user_id = ? RETURNING {}",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(&changes.name)
+        .bind(&changes.description)
+        .bind(&changes.search_request)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to update saved search {}: {}", id, e)))?;
+
+        row.as_ref().map(saved_search_from_row).transpose()
+    }
+
+    async fn delete_saved_search(&self, user_id: &str, id: i32) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!("Failed to delete saved search {}: {}", id, e))
+            })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Columns read back for a saved search
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, description, search_request, created_at, updated_at";
+
+/// Read column `name` of `row`
+fn get<'r, T>(row: &'r SqliteRow, name: &str) -> Result<T>
+where
+    T: sqlx::Decode<'r, sqlx::Sqlite> + sqlx::Type<sqlx::Sqlite>,
+{
+    row.try_get(name)
+        .map_err(|e| KbError::database(format!("Failed to get {}: {}", name, e)))
+}
+
+/// Build a saved search from a row with [`SAVED_SEARCH_COLUMNS`]
+fn saved_search_from_row(row: &SqliteRow) -> Result<SavedSearchRecord> {
+    Ok(SavedSearchRecord {
+        id: get(row, "id")?,
+        user_id: get(row, "user_id")?,
+        name: get(row, "name")?,
+        description: get(row, "description")?,
+        search_request: get(row, "search_request")?,
+        created_at: get(row, "created_at")?,
+        updated_at: get(row, "updated_at")?,
+    })
+}
+
+/// Build a semantic chunk from a `semantic_chunks` row
+fn semantic_chunk_from_row(row: &SqliteRow) -> Result<SemanticChunk> {
+    let embedding: Vec<u8> = get(row, "embedding")?;
+    if !embedding.len().is_multiple_of(4) {
+        return Err(KbError::database(format!(
+            "Invalid embedding byte length: {} (must be multiple of 4)",
+            embedding.len()
+        )));
+    }
+    let keywords: Json<Vec<String>> = get(row, "keywords")?;
+    let similarity_metric: String = get(row, "similarity_metric")?;
+    let count = |name: &str| get::<i64>(row, name).map(|value| value as usize);
+
+    Ok(SemanticChunk {
+        content: get(row, "content")?,
+        chunk_index: count("chunk_index")?,
+        total_chunks: count("total_chunks")?,
+        start_sentence: count("start_sentence")?,
+        end_sentence: count("end_sentence")?,
+        avg_similarity: get(row, "avg_similarity")?,
+        metadata: ChunkMetadata {
+            source_file: get::<Option<String>>(row, "source_file")?.unwrap_or_default(),
+            title: get(row, "title")?,
+            category: get(row, "category")?,
+            keywords: keywords.0,
+            word_count: count("word_count")?,
+            char_count: count("char_count")?,
+            overlap_sentences: count("overlap_sentences")?,
+            breadcrumbs: Vec::new(),
+        },
+        embedding: embedding
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().expect("chunk is exactly 4 bytes")))
+            .collect(),
+        similarity_metric: similarity_metric
+            .parse()
+            .map_err(|e| KbError::database(format!("Invalid similarity_metric: {}", e)))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kb::loader::{IncrementalLoader, LoaderConfig};
+    use std::sync::Arc;
+
+    fn doc_chunk(id: &str) -> DocumentChunk {
+        DocumentChunk::new(
+            id.to_string(),
+            format!("content of {}", id),
+            vec![0.1, 0.2],
+            serde_json::json!({ "id": id }),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_migrations_apply_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kb.db");
+
+        let store = SqliteKbStore::open(&path).await.unwrap();
+        assert_eq!(store.schema_version().await.unwrap(), MIGRATIONS.len());
+        drop(store);
+
+        let reopened = SqliteKbStore::open(&path).await.unwrap();
+        assert_eq!(reopened.schema_version().await.unwrap(), MIGRATIONS.len());
+    }
+
+    #[tokio::test]
+    async fn test_file_chunks_and_metadata() {
+        let store = SqliteKbStore::open_in_memory().await.unwrap();
+        let a = Path::new("docs/a.md");
+        store
+            .insert_file_chunks(a, "hash1", &[doc_chunk("0"), doc_chunk("1")])
+            .await
+            .unwrap();
+        store
+            .record_file_metadata(
+                a,
+                FileMetadata {
+                    size: 10,
+                    modified_ns: 42,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.query_existing_files().await.unwrap()["docs/a.md"],
+            "hash1"
+        );
+        assert_eq!(
+            store.query_file_metadata().await.unwrap()["docs/a.md"].size,
+            10
+        );
+
+        // Rewriting the chunks clears the recorded metadata
+        store
+            .update_file_chunks(a, "hash2", &[doc_chunk("0")])
+            .await
+            .unwrap();
+        assert_eq!(
+            store.query_existing_files().await.unwrap()["docs/a.md"],
+            "hash2"
+        );
+        assert!(store.query_file_metadata().await.unwrap().is_empty());
+
+        assert_eq!(
+            store
+                .cleanup_deleted_files(&["docs/a.md".to_string()])
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(store.query_existing_files().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_shrinks_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("kb.db");
+        let missing = dir.path().join("missing.md");
+        let store = SqliteKbStore::open(&db_path).await.unwrap();
+
+        let big = DocumentChunk::new(
+            "0".to_string(),
+            "x".repeat(200_000),
+            vec![0.5; 4],
+            serde_json::json!({}),
+        );
+        store
+            .insert_file_chunks(&missing, "hash", &[big, doc_chunk("1")])
+            .await
+            .unwrap();
+        let size_before = std::fs::metadata(&db_path).unwrap().len();
+
+        let stats = store.vacuum(&[]).await.unwrap();
+        assert_eq!(stats.files_removed, 1);
+        assert_eq!(stats.chunks_removed, 2);
+        assert_eq!(stats.bytes_reclaimed, 200_000 + 16 + 12 + 8);
+        assert!(store.query_existing_files().await.unwrap().is_empty());
+        assert!(std::fs::metadata(&db_path).unwrap().len() < size_before);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_chunks_roundtrip() {
+        let store = SqliteKbStore::open_in_memory().await.unwrap();
+        let path = Path::new("docs/a.md");
+        let mut metadata = ChunkMetadata::new("docs/a.md".to_string(), "some content");
+        metadata.keywords = vec!["rust".to_string()];
+        let chunks: Vec<SemanticChunk> = [1, 0]
+            .into_iter()
+            .map(|index| {
+                let mut chunk = SemanticChunk::new(
+                    format!("chunk {}", index),
+                    index,
+                    2,
+                    0,
+                    1,
+                    0.5,
+                    metadata.clone(),
+                );
+                chunk.embedding = vec![0.25, -1.0];
+                chunk
+            })
+            .collect();
+
+        store
+            .store_semantic_chunks(path, "hash", &chunks)
+            .await
+            .unwrap();
+        let stored = store.get_semantic_chunks_for_file(path).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].chunk_index, 0);
+        assert_eq!(stored[0].embedding, vec![0.25, -1.0]);
+        assert_eq!(stored[0].metadata.keywords, vec!["rust"]);
+
+        // Mirrors the (file_path, chunk_index) unique constraint
+        assert!(store
+            .store_semantic_chunks(path, "hash", &chunks[..1])
+            .await
+            .is_err());
+        assert_eq!(
+            store.delete_semantic_chunks_for_file(path).await.unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches() {
+        let store = SqliteKbStore::open_in_memory().await.unwrap();
+        let saved = store
+            .create_saved_search(NewSavedSearch {
+                user_id: "alice".to_string(),
+                name: "Async docs".to_string(),
+                description: None,
+                search_request: serde_json::json!({ "query": "rust async" }),
+            })
+            .await
+            .unwrap();
+
+        let changes = SavedSearchChanges {
+            name: Some("Async Rust".to_string()),
+            ..Default::default()
+        };
+        let updated = store
+            .update_saved_search("alice", saved.id, changes)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.name, "Async Rust");
+        assert_eq!(updated.search_request["query"], "rust async");
+
+        assert!(store
+            .get_saved_search("bob", saved.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(store.list_saved_searches("alice").await.unwrap().len(), 1);
+        assert!(!store.delete_saved_search("bob", saved.id).await.unwrap());
+        assert!(store.delete_saved_search("alice", saved.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_second_load_skips_unchanged_files() {
+        let docs = tempfile::tempdir().unwrap();
+        std::fs::write(docs.path().join("a.md"), "First paragraph.\n\nSecond one.").unwrap();
+        std::fs::write(docs.path().join("b.md"), "Only paragraph.").unwrap();
+        let paths = vec![docs.path().to_string_lossy().to_string()];
+        let db = tempfile::tempdir().unwrap();
+        let db_path = db.path().join("kb.db");
+
+        let store = SqliteKbStore::open(&db_path).await.unwrap();
+        let stats = IncrementalLoader::with_backend(Arc::new(store), LoaderConfig::default())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        assert_eq!(stats.files_added, 2);
+
+        // A new process opening the same file sees the stored hashes
+        let store = SqliteKbStore::open(&db_path).await.unwrap();
+        let stats = IncrementalLoader::with_backend(Arc::new(store), LoaderConfig::default())
+            .unwrap()
+            .load(&paths)
+            .await
+            .unwrap();
+        assert_eq!(stats.files_skipped, 2);
+        assert_eq!(stats.files_to_process(), 0);
+        assert_eq!(stats.chunks_inserted, 0);
+        assert_eq!(stats.hashes_reused, 2);
+    }
+}
@@ -4,8 +4,13 @@
 //! file metadata, including file paths and hashes for incremental loading.
 //! It also supports storing and retrieving semantic chunks.
 
+use crate::kb::backend::{orphaned_files, CleanupStats, KbBackend};
 use crate::kb::error::{KbError, Result};
+use crate::kb::hash::FileMetadata;
+use crate::kb::saved_search::{NewSavedSearch, SavedSearchChanges, SavedSearchRecord};
 use crate::semantic::types::{ChunkMetadata, SemanticChunk};
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
 use std::collections::HashMap;
 use std::path::Path;
@@ -47,6 +52,19 @@ impl KbStore {
         Self { pool }
     }
 
+    /// Connect to the database at a `postgresql://` URL
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the database cannot be reached
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPool::connect(url)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to connect to database: {}", e)))?;
+
+        Ok(Self::new(pool))
+    }
+
     /// Query existing files from database
     ///
     /// Retrieves all files currently stored in the knowledge base
@@ -105,6 +123,93 @@ impl KbStore {
         Ok(files)
     }
 
+    /// Query the recorded size and modification time of stored files
+    ///
+    /// Only files whose chunk rows all carry metadata are returned, so a
+    /// file with rows written after its metadata was recorded falls back to
+    /// hashing.
+    ///
+    /// # Returns
+    ///
+    /// Returns a HashMap mapping file paths to their recorded metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if query fails
+    pub async fn query_file_metadata(&self) -> Result<HashMap<String, FileMetadata>> {
+        debug!("Querying file metadata from database");
+
+        let query = r#"
+            SELECT file_path, MIN(file_size) AS file_size, MIN(file_mtime_ns) AS file_mtime_ns
+            FROM documents
+            GROUP BY file_path
+            HAVING COUNT(*) = COUNT(file_size)
+               AND COUNT(*) = COUNT(file_mtime_ns)
+               AND MIN(file_size) = MAX(file_size)
+               AND MIN(file_mtime_ns) = MAX(file_mtime_ns)
+        "#;
+
+        let rows = sqlx::query(query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to query file metadata: {}", e)))?;
+
+        let mut files = HashMap::new();
+        for row in rows {
+            let file_path: String = row
+                .try_get("file_path")
+                .map_err(|e| KbError::database(format!("Failed to get file_path: {}", e)))?;
+            let size: i64 = row
+                .try_get("file_size")
+                .map_err(|e| KbError::database(format!("Failed to get file_size: {}", e)))?;
+            let modified_ns: i64 = row
+                .try_get("file_mtime_ns")
+                .map_err(|e| KbError::database(format!("Failed to get file_mtime_ns: {}", e)))?;
+
+            files.insert(
+                file_path,
+                FileMetadata {
+                    size: size as u64,
+                    modified_ns,
+                },
+            );
+        }
+
+        debug!("Retrieved metadata for {} files", files.len());
+        Ok(files)
+    }
+
+    /// Record the size and modification time of a stored file
+    ///
+    /// Updates every chunk row of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the update fails
+    pub async fn record_file_metadata(
+        &self,
+        file_path: &Path,
+        metadata: FileMetadata,
+    ) -> Result<()> {
+        let file_path_str = file_path.to_string_lossy();
+
+        sqlx::query("UPDATE documents SET file_size = $1, file_mtime_ns = $2 WHERE file_path = $3")
+            .bind(metadata.size as i64)
+            .bind(metadata.modified_ns)
+            .bind(file_path_str.as_ref())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!(
+                    "Failed to record metadata for file {}: {}",
+                    file_path_str, e
+                ))
+            })?;
+
+        debug!("Recorded metadata for file: {}", file_path_str);
+        Ok(())
+    }
+
     /// Delete chunks for a specific file
     ///
     /// Removes all chunks associated with the given file path from the documents table.
@@ -464,6 +569,97 @@ impl KbStore {
         Ok(total_deleted)
     }
 
+    /// Remove chunks of orphaned files and vacuum the chunk tables
+    ///
+    /// A stored file is orphaned if it is listed in `deleted_files` or no
+    /// longer exists on disk. Its rows in documents and semantic_chunks are
+    /// deleted in one transaction; `VACUUM` then runs outside it, since
+    /// PostgreSQL does not allow it in a transaction block.
+    ///
+    /// # Arguments
+    ///
+    /// * `deleted_files` - File paths categorized as deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the transaction fails; nothing is
+    /// removed in that case
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sqlx::PgPool;
+    /// use xze_core::kb::store::KbStore;
+    ///
+    /// # async fn example() -> xze_core::kb::error::Result<()> {
+    /// # let pool = PgPool::connect("postgresql://localhost/xze").await
+    /// #     .map_err(|e| xze_core::kb::error::KbError::database(e.to_string()))?;
+    /// let store = KbStore::new(pool);
+    /// let stats = store.vacuum(&[]).await?;
+    /// println!("Removed {} orphaned files", stats.files_removed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vacuum(&self, deleted_files: &[String]) -> Result<CleanupStats> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            KbError::database(format!("Failed to begin cleanup transaction: {}", e))
+        })?;
+
+        let stored: Vec<String> = sqlx::query_scalar(
+            "SELECT file_path FROM documents UNION SELECT file_path FROM semantic_chunks",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to query stored files: {}", e)))?;
+
+        let orphaned = orphaned_files(&stored, deleted_files);
+        let mut stats = CleanupStats {
+            files_removed: orphaned.len(),
+            ..Default::default()
+        };
+
+        for table in ["documents", "semantic_chunks"] {
+            let row = sqlx::query(&format!(
+                "WITH removed AS ( \
+                     DELETE FROM {} WHERE file_path = ANY($1) \
+                     RETURNING octet_length(content) + octet_length(embedding) AS bytes \
+                 ) \
+                 SELECT COUNT(*) AS chunks, COALESCE(SUM(bytes), 0)::BIGINT AS bytes FROM removed",
+                table
+            ))
+            .bind(&orphaned)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                KbError::database(format!("Failed to remove orphaned {}: {}", table, e))
+            })?;
+
+            let chunks: i64 = row
+                .try_get("chunks")
+                .map_err(|e| KbError::database(format!("Failed to get chunks: {}", e)))?;
+            let bytes: i64 = row
+                .try_get("bytes")
+                .map_err(|e| KbError::database(format!("Failed to get bytes: {}", e)))?;
+            stats.chunks_removed += chunks as usize;
+            stats.bytes_reclaimed += bytes as u64;
+        }
+
+        tx.commit().await.map_err(|e| {
+            KbError::database(format!("Failed to commit cleanup transaction: {}", e))
+        })?;
+
+        sqlx::query("VACUUM documents, semantic_chunks")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| KbError::database(format!("Failed to vacuum chunk tables: {}", e)))?;
+
+        info!(
+            "Vacuum complete: removed {} chunks ({} bytes) from {} files",
+            stats.chunks_removed, stats.bytes_reclaimed, stats.files_removed
+        );
+        Ok(stats)
+    }
+
     /// Store semantic chunks for a file
     ///
     /// Inserts semantic chunks into the semantic_chunks table.
@@ -531,8 +727,8 @@ impl KbStore {
                 INSERT INTO semantic_chunks
                 (file_path, file_hash, chunk_index, total_chunks, start_sentence, end_sentence,
                  content, embedding, avg_similarity, source_file, title, category, keywords,
-                 word_count, char_count)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                 word_count, char_count, similarity_metric, overlap_sentences)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
                 "#,
             )
             .bind(file_path_str.as_ref())
@@ -550,6 +746,8 @@ impl KbStore {
             .bind(&keywords)
             .bind(chunk.metadata.word_count as i32)
             .bind(chunk.metadata.char_count as i32)
+            .bind(chunk.similarity_metric.to_string())
+            .bind(chunk.metadata.overlap_sentences as i32)
             .execute(&self.pool)
             .await
             .map_err(|e| {
@@ -680,7 +878,7 @@ impl KbStore {
         let query = r#"
             SELECT chunk_index, total_chunks, start_sentence, end_sentence,
                    content, embedding, avg_similarity, source_file, title, category,
-                   keywords, word_count, char_count
+                   keywords, word_count, char_count, similarity_metric, overlap_sentences
             FROM semantic_chunks
             WHERE file_path = $1
             ORDER BY chunk_index ASC
@@ -739,8 +937,17 @@ impl KbStore {
             let char_count: i32 = row
                 .try_get("char_count")
                 .map_err(|e| KbError::database(format!("Failed to get char_count: {}", e)))?;
+            let similarity_metric: String = row.try_get("similarity_metric").map_err(|e| {
+                KbError::database(format!("Failed to get similarity_metric: {}", e))
+            })?;
+            let overlap_sentences: i32 = row.try_get("overlap_sentences").map_err(|e| {
+                KbError::database(format!("Failed to get overlap_sentences: {}", e))
+            })?;
 
             let embedding = self.bytes_to_embedding(&embedding_bytes)?;
+            let similarity_metric = similarity_metric
+                .parse()
+                .map_err(|e| KbError::database(format!("Invalid similarity_metric: {}", e)))?;
 
             let metadata = ChunkMetadata {
                 source_file,
@@ -749,6 +956,8 @@ impl KbStore {
                 keywords,
                 word_count: word_count as usize,
                 char_count: char_count as usize,
+                overlap_sentences: overlap_sentences as usize,
+                breadcrumbs: Vec::new(),
             };
 
             let chunk = SemanticChunk {
@@ -760,6 +969,7 @@ impl KbStore {
                 avg_similarity: avg_similarity as f64,
                 metadata,
                 embedding,
+                similarity_metric,
             };
 
             chunks.push(chunk);
@@ -774,6 +984,151 @@ impl KbStore {
         Ok(chunks)
     }
 
+    /// Store a saved search in the saved_searches table
+    ///
+    /// The database assigns the id and both timestamps.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the insert fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sqlx::PgPool;
+    /// use xze_core::kb::saved_search::NewSavedSearch;
+    /// use xze_core::kb::store::KbStore;
+    ///
+    /// # async fn example() -> xze_core::kb::error::Result<()> {
+    /// # let pool = PgPool::connect("postgresql://localhost/xze").await
+    /// #     .map_err(|e| xze_core::kb::error::KbError::database(e.to_string()))?;
+    /// let store = KbStore::new(pool);
+    /// let saved = store
+    ///     .create_saved_search(NewSavedSearch {
+    ///         user_id: "alice".to_string(),
+    ///         name: "Async docs".to_string(),
+    ///         description: None,
+    ///         search_request: serde_json::json!({ "query": "rust async" }),
+    ///     })
+    ///     .await?;
+    /// println!("Saved search {}", saved.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_saved_search(&self, search: NewSavedSearch) -> Result<SavedSearchRecord> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO saved_searches (user_id, name, description, search_request) \
+             VALUES ($1, $2, $3, $4) RETURNING {}",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(&search.user_id)
+        .bind(&search.name)
+        .bind(&search.description)
+        .bind(&search.search_request)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to insert saved search: {}", e)))?;
+
+        let saved = saved_search_from_row(&row)?;
+        debug!(
+            "Created saved search {} for user {}",
+            saved.id, saved.user_id
+        );
+        Ok(saved)
+    }
+
+    /// List a user's saved searches, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the query fails
+    pub async fn list_saved_searches(&self, user_id: &str) -> Result<Vec<SavedSearchRecord>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM saved_searches WHERE user_id = $1 ORDER BY created_at DESC, id DESC",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to list saved searches: {}", e)))?;
+
+        rows.iter().map(saved_search_from_row).collect()
+    }
+
+    /// Get a saved search if it belongs to `user_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the query fails
+    pub async fn get_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+    ) -> Result<Option<SavedSearchRecord>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM saved_searches WHERE id = $1 AND user_id = $2",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to get saved search {}: {}", id, e)))?;
+
+        row.as_ref().map(saved_search_from_row).transpose()
+    }
+
+    /// Update a user's saved search, leaving `None` fields unchanged
+    ///
+    /// The `update_saved_searches_updated_at` trigger sets `updated_at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the update fails
+    pub async fn update_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+        changes: SavedSearchChanges,
+    ) -> Result<Option<SavedSearchRecord>> {
+        let row = sqlx::query(&format!(
+            "UPDATE saved_searches SET \
+             name = COALESCE($3, name), \
+             description = COALESCE($4, description), \
+             search_request = COALESCE($5, search_request) \
+             WHERE id = $1 AND user_id = $2 RETURNING {}",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(&changes.name)
+        .bind(&changes.description)
+        .bind(&changes.search_request)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| KbError::database(format!("Failed to update saved search {}: {}", id, e)))?;
+
+        row.as_ref().map(saved_search_from_row).transpose()
+    }
+
+    /// Delete a user's saved search, returning whether it existed
+    ///
+    /// # Errors
+    ///
+    /// Returns `KbError::Database` if the delete fails
+    pub async fn delete_saved_search(&self, user_id: &str, id: i32) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                KbError::database(format!("Failed to delete saved search {}: {}", id, e))
+            })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Convert embedding vector to bytes for database storage
     ///
     /// Converts the f32 vector to a byte array that can be stored
@@ -810,6 +1165,111 @@ impl KbStore {
     }
 }
 
+/// Columns read back for a saved search
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, description, search_request, created_at, updated_at";
+
+/// Build a saved search from a row with [`SAVED_SEARCH_COLUMNS`]
+fn saved_search_from_row(row: &PgRow) -> Result<SavedSearchRecord> {
+    let column = |e: sqlx::Error| KbError::database(format!("Failed to read saved search: {}", e));
+    Ok(SavedSearchRecord {
+        id: row.try_get("id").map_err(column)?,
+        user_id: row.try_get("user_id").map_err(column)?,
+        name: row.try_get("name").map_err(column)?,
+        description: row.try_get("description").map_err(column)?,
+        search_request: row.try_get("search_request").map_err(column)?,
+        created_at: row.try_get("created_at").map_err(column)?,
+        updated_at: row.try_get("updated_at").map_err(column)?,
+    })
+}
+
+#[async_trait]
+impl KbBackend for KbStore {
+    async fn query_existing_files(&self) -> Result<HashMap<String, String>> {
+        KbStore::query_existing_files(self).await
+    }
+
+    async fn query_file_metadata(&self) -> Result<HashMap<String, FileMetadata>> {
+        KbStore::query_file_metadata(self).await
+    }
+
+    async fn record_file_metadata(&self, file_path: &Path, metadata: FileMetadata) -> Result<()> {
+        KbStore::record_file_metadata(self, file_path, metadata).await
+    }
+
+    async fn delete_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        KbStore::delete_chunks_for_file(self, file_path).await
+    }
+
+    async fn insert_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        KbStore::insert_file_chunks(self, file_path, file_hash, chunks).await
+    }
+
+    async fn update_file_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[DocumentChunk],
+    ) -> Result<()> {
+        KbStore::update_file_chunks(self, file_path, file_hash, chunks).await
+    }
+
+    async fn cleanup_deleted_files(&self, deleted_files: &[String]) -> Result<u64> {
+        KbStore::cleanup_deleted_files(self, deleted_files).await
+    }
+
+    async fn vacuum(&self, deleted_files: &[String]) -> Result<CleanupStats> {
+        KbStore::vacuum(self, deleted_files).await
+    }
+
+    async fn store_semantic_chunks(
+        &self,
+        file_path: &Path,
+        file_hash: &str,
+        chunks: &[SemanticChunk],
+    ) -> Result<()> {
+        KbStore::store_semantic_chunks(self, file_path, file_hash, chunks).await
+    }
+
+    async fn delete_semantic_chunks_for_file(&self, file_path: &Path) -> Result<u64> {
+        KbStore::delete_semantic_chunks_for_file(self, file_path).await
+    }
+
+    async fn get_semantic_chunks_for_file(&self, file_path: &Path) -> Result<Vec<SemanticChunk>> {
+        KbStore::get_semantic_chunks_for_file(self, file_path).await
+    }
+
+    async fn create_saved_search(&self, search: NewSavedSearch) -> Result<SavedSearchRecord> {
+        KbStore::create_saved_search(self, search).await
+    }
+
+    async fn list_saved_searches(&self, user_id: &str) -> Result<Vec<SavedSearchRecord>> {
+        KbStore::list_saved_searches(self, user_id).await
+    }
+
+    async fn get_saved_search(&self, user_id: &str, id: i32) -> Result<Option<SavedSearchRecord>> {
+        KbStore::get_saved_search(self, user_id, id).await
+    }
+
+    async fn update_saved_search(
+        &self,
+        user_id: &str,
+        id: i32,
+        changes: SavedSearchChanges,
+    ) -> Result<Option<SavedSearchRecord>> {
+        KbStore::update_saved_search(self, user_id, id, changes).await
+    }
+
+    async fn delete_saved_search(&self, user_id: &str, id: i32) -> Result<bool> {
+        KbStore::delete_saved_search(self, user_id, id).await
+    }
+}
+
 /// Represents a document chunk to be stored in the database
 ///
 /// Each chunk contains the actual content, its embedding vector for
@@ -867,7 +1327,7 @@ impl DocumentChunk {
     ///
     /// Converts the f32 vector to a byte array that can be stored
     /// in PostgreSQL as a bytea column.
-    fn embedding_as_bytes(&self) -> Vec<u8> {
+    pub(crate) fn embedding_as_bytes(&self) -> Vec<u8> {
         self.embedding
             .iter()
             .flat_map(|f| f.to_le_bytes())
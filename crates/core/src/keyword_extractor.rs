@@ -20,7 +20,8 @@
 //! 3. **Fallback Strategy**: Frequency-based extraction
 //!    - Activates when LLM is unavailable or fails
 //!    - Uses TF-IDF style scoring
-//!    - Filters common stop words
+//!    - Filters common stop words for the configured language (English,
+//!      Spanish, French or German) plus any configured extra words
 //!
 //! # Examples
 //!
@@ -43,12 +44,14 @@
 use crate::{
     ai::OllamaClient,
     error::{Result, XzeError},
+    search::{normalize::Language, synonyms::SynonymMap},
 };
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroUsize,
+    path::PathBuf,
     sync::Arc,
 };
 use tokio::sync::Mutex;
@@ -98,6 +101,26 @@ pub struct KeywordExtractorConfig {
 
     /// Enable metrics collection
     pub metrics_enabled: bool,
+
+    /// YAML file mapping canonical terms to their synonyms
+    /// (`term: [synonyms]`); synonyms in extracted keywords are replaced
+    /// by their canonical term. No synonyms when unset.
+    #[serde(default)]
+    pub synonyms_path: Option<PathBuf>,
+
+    /// Language of the documents, as an ISO 639-1 code (`en`, `es`, `fr`
+    /// or `de`); selects the stop word set. Unknown codes fall back to
+    /// English.
+    #[serde(default = "default_language")]
+    pub language: String,
+
+    /// Additional words never extracted as keywords
+    #[serde(default)]
+    pub extra_stopwords: Vec<String>,
+}
+
+fn default_language() -> String {
+    Language::English.code().to_string()
 }
 
 impl Default for KeywordExtractorConfig {
@@ -126,6 +149,9 @@ impl Default for KeywordExtractorConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(true),
+            synonyms_path: None,
+            language: default_language(),
+            extra_stopwords: Vec::new(),
         }
     }
 }
@@ -180,6 +206,7 @@ impl KeywordExtractorConfig {
 ///     technical: vec!["Cargo.toml".to_string(), "dependencies".to_string()],
 ///     extraction_method: "llm".to_string(),
 ///     confidence: Some(0.92),
+///     stopword_language: Some("en".to_string()),
 /// };
 ///
 /// assert_eq!(keywords.all_keywords().len(), 4);
@@ -199,6 +226,10 @@ pub struct ExtractedKeywords {
     /// Confidence score (0.0 to 1.0) if available
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f32>,
+
+    /// Language code of the stop word set applied, such as `en`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopword_language: Option<String>,
 }
 
 impl ExtractedKeywords {
@@ -214,6 +245,7 @@ impl ExtractedKeywords {
             technical,
             extraction_method,
             confidence,
+            stopword_language: None,
         }
     }
 
@@ -335,9 +367,96 @@ impl ExtractionMetrics {
     }
 }
 
+/// Split content into lowercase words
+///
+/// Words are runs of alphanumeric characters, `_` and `-`, so identifiers
+/// such as `search_index` and `kebab-case` stay whole.
+pub(crate) fn tokenize(content: &str) -> Vec<String> {
+    content
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Check if a lowercase word is a common English stop word
+pub(crate) fn is_stop_word(word: &str) -> bool {
+    stop_words(Language::English).contains(&word)
+}
+
+/// Bundled stop words for `language`
+pub(crate) fn stop_words(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => &[
+            "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with",
+            "by", "from", "as", "is", "was", "are", "were", "be", "been", "being", "have", "has",
+            "had", "do", "does", "did", "will", "would", "could", "should", "may", "might", "must",
+            "can", "this", "that", "these", "those", "it", "its", "they", "them", "their", "we",
+            "our", "you", "your", "he", "she", "him", "her", "his",
+        ],
+        Language::Spanish => &[
+            "el", "la", "los", "las", "un", "una", "unos", "unas", "de", "del", "al", "y", "o",
+            "pero", "en", "con", "por", "para", "sin", "sobre", "entre", "como", "que", "es",
+            "son", "fue", "ser", "está", "están", "estar", "ha", "han", "hay", "se", "su", "sus",
+            "lo", "le", "les", "este", "esta", "estos", "estas", "ese", "esa", "eso", "mi", "tu",
+            "nosotros", "ellos", "ellas", "muy", "más", "también", "no", "cuando", "donde",
+            "puede",
+        ],
+        Language::French => &[
+            "le", "la", "les", "un", "une", "des", "du", "de", "et", "ou", "mais", "dans", "en",
+            "sur", "pour", "par", "avec", "sans", "est", "sont", "être", "a", "ont", "avoir", "ce",
+            "cette", "ces", "il", "elle", "ils", "elles", "nous", "vous", "je", "on", "qui", "que",
+            "ne", "pas", "plus", "se", "son", "sa", "ses", "leur", "au", "aux", "comme", "si",
+            "tout",
+        ],
+        Language::German => &[
+            "der", "die", "das", "den", "dem", "des", "ein", "eine", "einer", "eines", "einem",
+            "einen", "und", "oder", "aber", "in", "im", "an", "am", "auf", "aus", "bei", "mit",
+            "nach", "von", "vom", "zu", "zum", "zur", "für", "über", "unter", "ist", "sind", "war",
+            "waren", "sein", "wird", "werden", "hat", "haben", "kann", "können", "nicht", "auch",
+            "als", "wie", "wenn", "dass", "es", "sie", "er", "wir", "ihr", "ich", "du", "sich",
+            "dieser", "diese", "dieses", "noch", "nur", "so",
+        ],
+    }
+}
+
+/// Stop words of the configured language plus the configured extra words
+#[derive(Debug, Clone)]
+struct StopwordSet {
+    language: Language,
+    words: HashSet<String>,
+}
+
+impl StopwordSet {
+    /// Resolve the set for `config`, using English for unknown languages
+    fn new(config: &KeywordExtractorConfig) -> Self {
+        let language = config.language.parse().unwrap_or_else(|e| {
+            warn!("{}; using English stop words", e);
+            Language::English
+        });
+
+        let mut words: HashSet<String> = stop_words(language)
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        words.extend(
+            config
+                .extra_stopwords
+                .iter()
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty()),
+        );
+
+        Self { language, words }
+    }
+}
+
 pub struct KeywordExtractor {
     config: KeywordExtractorConfig,
     client: OllamaClient,
+    synonyms: SynonymMap,
+    stopwords: StopwordSet,
     cache: Arc<Mutex<LruCache<String, ExtractedKeywords>>>,
     metrics: Arc<Mutex<ExtractionMetrics>>,
 }
@@ -347,8 +466,8 @@ impl KeywordExtractor {
     ///
     /// # Errors
     ///
-    /// Returns an error if the cache size is invalid or the Ollama client
-    /// cannot be initialized.
+    /// Returns an error if the cache size is invalid, the Ollama client
+    /// cannot be initialized, or the synonyms file cannot be loaded.
     ///
     /// # Examples
     ///
@@ -370,10 +489,14 @@ impl KeywordExtractor {
             config.ollama_base_url.clone(),
             std::time::Duration::from_secs(config.timeout_seconds),
         );
+        let synonyms = SynonymMap::load_optional(config.synonyms_path.as_deref())?;
+        let stopwords = StopwordSet::new(&config);
 
         Ok(Self {
             config,
             client,
+            synonyms,
+            stopwords,
             cache: Arc::new(Mutex::new(LruCache::new(cache_size))),
             metrics: Arc::new(Mutex::new(ExtractionMetrics::default())),
         })
@@ -601,6 +724,7 @@ JSON response:"#,
                 num_predict: Some(500),
                 top_k: None,
                 top_p: None,
+                stop: Vec::new(),
             }),
         };
 
@@ -610,15 +734,7 @@ JSON response:"#,
     /// Parse the LLM response into structured keywords
     fn parse_llm_response(&self, response: &str) -> Result<ExtractedKeywords> {
         // Try to find JSON in the response (handle markdown code blocks)
-        let json_str = if let Some(start) = response.find('{') {
-            if let Some(end) = response.rfind('}') {
-                &response[start..=end]
-            } else {
-                response
-            }
-        } else {
-            response
-        };
+        let json_str = crate::ai::json::extract_json(response);
 
         let parsed: LlmKeywordResponse = serde_json::from_str(json_str).map_err(|e| {
             XzeError::ai(format!(
@@ -642,18 +758,22 @@ JSON response:"#,
             return Err(XzeError::ai("LLM returned no valid keywords".to_string()));
         }
 
-        Ok(ExtractedKeywords::new(
+        Ok(self.with_stopword_language(ExtractedKeywords::new(
             descriptive,
             technical,
             "llm".to_string(),
             parsed.confidence,
-        ))
+        )))
     }
 
     /// Extract keywords using frequency-based analysis (fallback)
     #[doc(hidden)]
     pub fn extract_with_frequency(&self, content: &str) -> Result<ExtractedKeywords> {
-        let words = self.tokenize(content);
+        let words: Vec<String> = self
+            .tokenize(content)
+            .iter()
+            .map(|word| self.synonyms.canonical(word))
+            .collect();
         let frequencies = self.calculate_frequencies(&words);
 
         // Filter stop words and score
@@ -685,23 +805,24 @@ JSON response:"#,
             }
         }
 
-        Ok(ExtractedKeywords::new(
+        Ok(self.with_stopword_language(ExtractedKeywords::new(
             descriptive,
             technical,
             "frequency".to_string(),
             Some(0.6), // Lower confidence for fallback method
-        ))
+        )))
+    }
+
+    /// Record the stop word set applied to `keywords`
+    fn with_stopword_language(&self, mut keywords: ExtractedKeywords) -> ExtractedKeywords {
+        keywords.stopword_language = Some(self.stopwords.language.code().to_string());
+        keywords
     }
 
     /// Tokenize content into words
     #[doc(hidden)]
     pub fn tokenize(&self, content: &str) -> Vec<String> {
-        content
-            .to_lowercase()
-            .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-')
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string())
-            .collect()
+        tokenize(content)
     }
 
     /// Calculate word frequencies
@@ -714,16 +835,9 @@ JSON response:"#,
         frequencies
     }
 
-    /// Check if a word is a stop word
+    /// Check if a word is in the configured stop word set
     fn is_stop_word(&self, word: &str) -> bool {
-        const STOP_WORDS: &[&str] = &[
-            "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with",
-            "by", "from", "as", "is", "was", "are", "were", "be", "been", "being", "have", "has",
-            "had", "do", "does", "did", "will", "would", "could", "should", "may", "might", "must",
-            "can", "this", "that", "these", "those", "it", "its", "they", "them", "their", "we",
-            "our", "you", "your", "he", "she", "him", "her", "his",
-        ];
-        STOP_WORDS.contains(&word)
+        self.stopwords.words.contains(word)
     }
 
     /// Check if a word appears to be a technical term
@@ -742,15 +856,20 @@ JSON response:"#,
             || word.len() > 12
     }
 
-    /// Clean and deduplicate keywords
+    /// Clean and deduplicate keywords, dropping stop words and replacing
+    /// synonyms with their canonical term
     #[doc(hidden)]
     pub fn clean_keywords(&self, keywords: Vec<String>, max_count: usize) -> Vec<String> {
         let mut seen = HashSet::new();
         let mut cleaned = Vec::new();
 
         for keyword in keywords {
-            let normalized = keyword.trim().to_lowercase();
-            if !normalized.is_empty() && normalized.len() > 1 && seen.insert(normalized.clone()) {
+            let normalized = self.synonyms.canonical(keyword.trim());
+            if !normalized.is_empty()
+                && normalized.len() > 1
+                && !self.is_stop_word(&normalized)
+                && seen.insert(normalized.clone())
+            {
                 cleaned.push(normalized);
                 if cleaned.len() >= max_count {
                     break;
@@ -1052,6 +1171,90 @@ mod tests {
         assert!(!keywords.is_llm_extracted());
     }
 
+    #[test]
+    fn test_extraction_uses_canonical_synonyms() {
+        let synonyms = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(synonyms.path(), "kubernetes: [k8s, kube]\n").unwrap();
+        let config = KeywordExtractorConfig {
+            synonyms_path: Some(synonyms.path().to_path_buf()),
+            ..Default::default()
+        };
+        let extractor = KeywordExtractor::new(config).unwrap();
+
+        let keywords = extractor
+            .extract_with_frequency("Deploy to k8s. Scale k8s pods with kube tooling.")
+            .unwrap();
+        let all = keywords.all_keywords();
+        assert!(all.contains(&"kubernetes".to_string()));
+        assert!(!all.contains(&"k8s".to_string()));
+        assert!(!all.contains(&"kube".to_string()));
+
+        let cleaned =
+            extractor.clean_keywords(vec!["K8s".to_string(), "kubernetes".to_string()], 5);
+        assert_eq!(cleaned, vec!["kubernetes"]);
+
+        let missing = KeywordExtractorConfig {
+            synonyms_path: Some("/nonexistent/synonyms.yaml".into()),
+            ..Default::default()
+        };
+        assert!(KeywordExtractor::new(missing).is_err());
+    }
+
+    #[test]
+    fn test_frequency_extraction_uses_language_stopwords() {
+        let content = "Configuración de los servidores para la aplicación. \
+                       Los servidores usan una configuración para cada entorno.";
+
+        let english = KeywordExtractor::new(KeywordExtractorConfig::default()).unwrap();
+        let keywords = english.extract_with_frequency(content).unwrap();
+        assert!(keywords.all_keywords().contains(&"los".to_string()));
+        assert_eq!(keywords.stopword_language.as_deref(), Some("en"));
+
+        let config = KeywordExtractorConfig {
+            language: "es".to_string(),
+            extra_stopwords: vec!["Entorno".to_string()],
+            ..Default::default()
+        };
+        let spanish = KeywordExtractor::new(config).unwrap();
+        let keywords = spanish.extract_with_frequency(content).unwrap();
+        let all = keywords.all_keywords();
+        assert!(all.contains(&"servidores".to_string()));
+        for stopword in ["los", "para", "una", "entorno"] {
+            assert!(!all.contains(&stopword.to_string()), "{}", stopword);
+        }
+        assert_eq!(keywords.stopword_language.as_deref(), Some("es"));
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_english() {
+        let config = KeywordExtractorConfig {
+            language: "xx".to_string(),
+            ..Default::default()
+        };
+        let extractor = KeywordExtractor::new(config).unwrap();
+
+        assert!(extractor.is_stop_word("the"));
+        let keywords = extractor
+            .extract_with_frequency("The deployment pipeline builds the release artifacts")
+            .unwrap();
+        assert_eq!(keywords.stopword_language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_german_stopwords() {
+        let config = KeywordExtractorConfig {
+            language: "de".to_string(),
+            ..Default::default()
+        };
+        let extractor = KeywordExtractor::new(config).unwrap();
+
+        assert!(extractor.is_stop_word("und"));
+        assert!(extractor.is_stop_word("für"));
+        assert!(!extractor.is_stop_word("the"));
+        let cleaned = extractor.clean_keywords(vec!["die".to_string(), "Server".to_string()], 5);
+        assert_eq!(cleaned, vec!["server"]);
+    }
+
     #[test]
     fn test_build_extraction_prompt() {
         let config = KeywordExtractorConfig::default();
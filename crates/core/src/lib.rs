@@ -8,6 +8,7 @@ pub mod ab_testing;
 pub mod ai;
 pub mod change_detector;
 pub mod config;
+pub mod diagnostics;
 pub mod document_enrichment;
 pub mod documentation;
 pub mod error;
@@ -21,7 +22,10 @@ pub mod prompt_templates;
 pub mod quality_validator;
 pub mod repository;
 pub mod search;
+pub mod self_test;
 pub mod semantic;
+#[cfg(feature = "otlp")]
+pub mod telemetry;
 pub mod types;
 pub mod watcher;
 
@@ -36,7 +40,8 @@ pub use document_enrichment::{DocumentEnricher, EnrichmentConfig, EnrichmentStat
 pub use error::{Result, XzeError};
 pub use kb::{
     calculate_content_hash, calculate_file_hash, CategorizedFiles, FileCategorizer, FileCategory,
-    IncrementalLoader, KbError, KbStore, LoadStats, LoaderConfig,
+    InMemoryKbStore, IncrementalLoader, KbBackend, KbBackendKind, KbError, KbStore, LoadStats,
+    LoaderConfig,
 };
 pub use keyword_extractor::{ExtractedKeywords, KeywordExtractor, KeywordExtractorConfig};
 pub use monitoring::{Alert, AlertLevel, MetricsCollector, MetricsSnapshot};
@@ -72,41 +77,60 @@ pub fn init_logging() -> Result<()> {
 }
 
 /// Initialize logging with custom configuration
+///
+/// `format` is `json`, `text` (or `pretty`) or `compact`. Append `+otlp`,
+/// as in `json+otlp`, to also export spans to the OpenTelemetry collector
+/// at `OTEL_EXPORTER_OTLP_ENDPOINT`, or use `otlp` alone to export spans
+/// without writing them to stdout. OTLP export needs the `otlp` feature.
+///
+/// # Errors
+///
+/// Returns a validation error for an unknown format, or if OTLP export is
+/// requested without the `otlp` feature or a configured endpoint
 pub fn init_logging_with_config(level: &str, format: &str) -> Result<()> {
-    use tracing_subscriber::{fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt};
-
-    let env_filter = tracing_subscriber::EnvFilter::new(level);
-
-    let registry = tracing_subscriber::registry().with(env_filter);
-
-    match format {
-        "json" => {
-            registry
-                .with(tracing_subscriber::fmt::layer().json())
-                .init();
-        }
-        "text" | "pretty" => {
-            registry
-                .with(
-                    tracing_subscriber::fmt::layer()
-                        .pretty()
-                        .with_span_events(FmtSpan::CLOSE),
-                )
-                .init();
-        }
-        "compact" => {
-            registry
-                .with(tracing_subscriber::fmt::layer().compact())
-                .init();
-        }
-        _ => {
+    use tracing_subscriber::{
+        fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, Layer, Registry,
+    };
+
+    let (stdout_format, otlp) = match format.strip_suffix("+otlp") {
+        Some(stdout_format) => (Some(stdout_format), true),
+        None if format == "otlp" => (None, true),
+        None => (Some(format), false),
+    };
+
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> = Vec::new();
+    match stdout_format {
+        Some("json") => layers.push(tracing_subscriber::fmt::layer().json().boxed()),
+        Some("text" | "pretty") => layers.push(
+            tracing_subscriber::fmt::layer()
+                .pretty()
+                .with_span_events(FmtSpan::CLOSE)
+                .boxed(),
+        ),
+        Some("compact") => layers.push(tracing_subscriber::fmt::layer().compact().boxed()),
+        Some(_) => {
             return Err(XzeError::validation(format!(
                 "Unknown log format: {}",
                 format
             )));
         }
+        None => {}
+    }
+
+    if otlp {
+        #[cfg(feature = "otlp")]
+        layers.push(telemetry::otlp_layer()?.boxed());
+        #[cfg(not(feature = "otlp"))]
+        return Err(XzeError::validation(
+            "OTLP log format requires xze-core to be built with the otlp feature",
+        ));
     }
 
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(tracing_subscriber::EnvFilter::new(level))
+        .init();
+
     Ok(())
 }
 
@@ -169,6 +193,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_logging_rejects_unknown_format() {
+        assert!(matches!(
+            init_logging_with_config("info", "xml+otlp"),
+            Err(XzeError::Validation { .. })
+        ));
+        #[cfg(not(feature = "otlp"))]
+        assert!(matches!(
+            init_logging_with_config("info", "json+otlp"),
+            Err(XzeError::Validation { .. })
+        ));
+    }
+
     #[test]
     fn test_logging_init() {
         // Test that logging can be initialized without panicking
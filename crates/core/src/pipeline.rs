@@ -2,10 +2,11 @@
 
 use crate::{
     ai::AIAnalysisService,
+    documentation::GeneratorConfig,
     error::{Result, XzeError},
     git::GitOperations,
     repository::{Repository, RepositoryManager},
-    types::{JobId, JobStatus, RepositoryId},
+    types::{DiátaxisCategory, JobId, JobStatus, RepositoryId},
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc, time::Instant};
@@ -341,6 +342,8 @@ impl PipelineExecutor {
     ) -> Result<()> {
         info!("Generating documentation for {}", repository.name());
 
+        let presets = GeneratorConfig::default();
+
         // Generate different types of documentation
         let tasks = vec![
             ("tutorial", "Getting Started"),
@@ -354,7 +357,11 @@ impl PipelineExecutor {
                 "tutorial" => {
                     if let Ok(_content) = self
                         .ai_service
-                        .generate_tutorial(&repository.structure, title)
+                        .generate_tutorial(
+                            &repository.structure,
+                            title,
+                            presets.stop_sequences_for(&DiátaxisCategory::Tutorial),
+                        )
                         .await
                     {
                         let filename = format!(
@@ -368,7 +375,10 @@ impl PipelineExecutor {
                 "reference" => {
                     if let Ok(_content) = self
                         .ai_service
-                        .generate_api_documentation(&repository.structure)
+                        .generate_api_documentation(
+                            &repository.structure,
+                            presets.stop_sequences_for(&DiátaxisCategory::Reference),
+                        )
                         .await
                     {
                         let filename = "docs/api_reference.md".to_string();
@@ -379,7 +389,11 @@ impl PipelineExecutor {
                 "howto" => {
                     if let Ok(_content) = self
                         .ai_service
-                        .generate_howto(&repository.structure, title)
+                        .generate_howto(
+                            &repository.structure,
+                            title,
+                            presets.stop_sequences_for(&DiátaxisCategory::HowTo),
+                        )
                         .await
                     {
                         let filename =
@@ -391,7 +405,11 @@ impl PipelineExecutor {
                 "explanation" => {
                     if let Ok(_content) = self
                         .ai_service
-                        .generate_explanation(&repository.structure, title)
+                        .generate_explanation(
+                            &repository.structure,
+                            title,
+                            presets.stop_sequences_for(&DiátaxisCategory::Explanation),
+                        )
                         .await
                     {
                         let filename = format!(
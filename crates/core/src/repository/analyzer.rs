@@ -3,8 +3,9 @@
 use crate::{
     error::{Result, XzeError},
     repository::{
-        CodeStructure, ConfigFile, ConfigFormat, Field, Function, Module, Parameter,
-        TypeDefinition, TypeKind, Visibility,
+        call_graph, rust_ast, AnalysisWarning, CodeStructure, ConfigFile, ConfigFormat, Field,
+        Function, Module, Parameter, TypeDefinition, TypeKind, Visibility, WarningKind,
+        WarningSeverity,
     },
     types::ProgrammingLanguage,
 };
@@ -45,10 +46,27 @@ impl AnalyzerFactory {
             ProgrammingLanguage::JavaScript => Box::new(JavaScriptAnalyzer::new()),
             ProgrammingLanguage::TypeScript => Box::new(TypeScriptAnalyzer::new()),
             ProgrammingLanguage::Java => Box::new(JavaAnalyzer::new()),
+            ProgrammingLanguage::CSharp => Box::new(CSharpAnalyzer::new()),
+            ProgrammingLanguage::Ruby => Box::new(RubyAnalyzer::new()),
             _ => Box::new(GenericAnalyzer::new()),
         }
     }
 
+    /// Get the name of the analyzer used for the given language
+    pub fn analyzer_name(language: &ProgrammingLanguage) -> &'static str {
+        match language {
+            ProgrammingLanguage::Rust => "rust",
+            ProgrammingLanguage::Go => "go",
+            ProgrammingLanguage::Python => "python",
+            ProgrammingLanguage::JavaScript => "javascript",
+            ProgrammingLanguage::TypeScript => "typescript",
+            ProgrammingLanguage::Java => "java",
+            ProgrammingLanguage::CSharp => "csharp",
+            ProgrammingLanguage::Ruby => "ruby",
+            _ => "generic",
+        }
+    }
+
     /// Auto-detect and create analyzer for a repository
     pub fn auto_detect_analyzer(
         repo_path: &Path,
@@ -100,6 +118,16 @@ impl AnalyzerFactory {
                                 .entry(ProgrammingLanguage::Java)
                                 .or_insert(0) += 10;
                         }
+                        "Gemfile" | "Gemfile.lock" => {
+                            *language_counts
+                                .entry(ProgrammingLanguage::Ruby)
+                                .or_insert(0) += 10;
+                        }
+                        name if name.ends_with(".gemspec") => {
+                            *language_counts
+                                .entry(ProgrammingLanguage::Ruby)
+                                .or_insert(0) += 10;
+                        }
                         _ => {}
                     }
                 }
@@ -116,12 +144,26 @@ impl AnalyzerFactory {
 }
 
 /// Rust language analyzer
+///
+/// By default files are scanned line by line. [`with_ast_parsing`] parses
+/// them with `syn` instead, see [`rust_ast`].
+///
+/// [`with_ast_parsing`]: RustAnalyzer::with_ast_parsing
 #[derive(Debug, Default)]
-pub struct RustAnalyzer;
+pub struct RustAnalyzer {
+    ast_parsing: bool,
+}
 
 impl RustAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Parse files into syntax trees, falling back to line-based parsing
+    /// for files that do not parse
+    pub fn with_ast_parsing(mut self) -> Self {
+        self.ast_parsing = true;
+        self
     }
 
     fn extract_rust_doc_comment(content: &str, line_start: usize) -> Option<String> {
@@ -155,6 +197,8 @@ impl RustAnalyzer {
 impl LanguageAnalyzer for RustAnalyzer {
     fn analyze(&self, repo_path: &Path) -> Result<CodeStructure> {
         let mut structure = CodeStructure::new();
+        // Types may be defined in another file than their `impl` blocks
+        let mut methods = Vec::new();
 
         // Find all Rust files
         for entry in WalkDir::new(repo_path) {
@@ -162,15 +206,25 @@ impl LanguageAnalyzer for RustAnalyzer {
             let path = entry.path();
 
             if path.extension().and_then(|e| e.to_str()) == Some("rs") {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    self.parse_rust_file(path, &content, &mut structure)?;
+                if let Some(content) = structure.read_source(path) {
+                    if self.ast_parsing {
+                        self.parse_rust_ast(path, &content, &mut structure, &mut methods)?;
+                    } else {
+                        self.parse_rust_file(path, &content, &mut structure, &mut methods)?;
+                    }
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::Rust);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::Rust);
                 }
             }
         }
 
+        structure.attach_methods(methods);
+
         // Look for Cargo.toml and other config files
         self.parse_cargo_files(repo_path, &mut structure)?;
 
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -180,13 +234,53 @@ impl LanguageAnalyzer for RustAnalyzer {
 }
 
 impl RustAnalyzer {
+    fn parse_rust_ast(
+        &self,
+        file_path: &Path,
+        content: &str,
+        structure: &mut CodeStructure,
+        methods: &mut Vec<(String, Function)>,
+    ) -> Result<()> {
+        match rust_ast::parse_file(file_path, content) {
+            Ok(items) => {
+                structure.modules.extend(items.modules);
+                structure.functions.extend(items.functions);
+                structure.types.extend(items.types);
+                methods.extend(items.methods);
+                call_graph::record_calls(content, &items.declarations, &mut structure.call_graph);
+                Ok(())
+            }
+            Err(e) => {
+                let start = e.span().start();
+                structure.warnings.push(AnalysisWarning {
+                    severity: WarningSeverity::Info,
+                    kind: WarningKind::UnparsableFile,
+                    path: Some(file_path.to_path_buf()),
+                    message: format!(
+                        "Analyzed with line-based parsing: {} at line {}",
+                        e, start.line
+                    ),
+                });
+                self.parse_rust_file(file_path, content, structure, methods)
+            }
+        }
+    }
+
+    /// Parse one file line by line, collecting the methods of `impl`
+    /// blocks with the name of their type into `methods`
     fn parse_rust_file(
         &self,
         file_path: &Path,
         content: &str,
         structure: &mut CodeStructure,
+        methods: &mut Vec<(String, Function)>,
     ) -> Result<()> {
         let lines: Vec<&str> = content.lines().collect();
+        let mut declarations = Vec::new();
+        // Type of the `impl` block being read, the brace depth of its
+        // declaration and whether its body has opened
+        let mut current_impl: Option<(String, usize, bool)> = None;
+        let mut depth = 0usize;
 
         for (line_num, line) in lines.iter().enumerate() {
             let trimmed = line.trim();
@@ -207,8 +301,19 @@ impl RustAnalyzer {
                 });
             }
 
+            if let Some(type_name) = Self::extract_impl_target(trimmed) {
+                current_impl = Some((type_name, depth, false));
+            }
+
             // Parse functions
             if let Some(function) = self.extract_function(trimmed, content, line_num) {
+                declarations.push((line_num, function.name.clone()));
+                if let Some((type_name, impl_depth, true)) = &current_impl {
+                    // Only direct members, not functions nested in a method
+                    if depth == impl_depth + 1 {
+                        methods.push((type_name.clone(), function.clone()));
+                    }
+                }
                 structure.functions.push(function);
             }
 
@@ -216,11 +321,58 @@ impl RustAnalyzer {
             if let Some(type_def) = self.extract_type_definition(trimmed, content, line_num) {
                 structure.types.push(type_def);
             }
+
+            if !trimmed.starts_with("//") {
+                depth += trimmed.matches('{').count();
+                depth = depth.saturating_sub(trimmed.matches('}').count());
+            }
+            if let Some((_, impl_depth, opened)) = &mut current_impl {
+                if depth > *impl_depth {
+                    *opened = true;
+                } else if *opened {
+                    current_impl = None;
+                }
+            }
         }
 
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
         Ok(())
     }
 
+    /// Name of the type an `impl` block declares methods for, without its
+    /// path or generic arguments
+    fn extract_impl_target(line: &str) -> Option<String> {
+        let rest = line.strip_prefix("unsafe ").unwrap_or(line);
+        let rest = rest.strip_prefix("impl")?;
+
+        // Skip the block's own generic parameters, e.g. `impl<T: Clone>`
+        let rest = if rest.starts_with('<') {
+            let mut nesting = 0;
+            let end = rest.char_indices().find_map(|(i, c)| {
+                match c {
+                    '<' => nesting += 1,
+                    '>' => nesting -= 1,
+                    _ => {}
+                }
+                (nesting == 0).then_some(i + 1)
+            })?;
+            &rest[end..]
+        } else if rest.starts_with(' ') {
+            rest
+        } else {
+            return None;
+        };
+
+        let target = rest.rsplit_once(" for ").map_or(rest, |(_, target)| target);
+        let path = target
+            .trim()
+            .split(['<', ' ', '{'])
+            .next()
+            .unwrap_or_default();
+        let name = path.rsplit("::").next().unwrap_or(path);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
     fn extract_module_name(&self, line: &str) -> Option<String> {
         if line.starts_with("mod ") || line.starts_with("pub mod ") {
             let parts: Vec<&str> = line.split_whitespace().collect();
@@ -432,6 +584,14 @@ impl RustAnalyzer {
         None
     }
 
+    /// Name following `keyword` in a declaration such as `pub struct Name {`
+    fn declared_name(line: &str, keyword: &str) -> Option<String> {
+        let mut parts = line.split_whitespace();
+        parts.find(|part| *part == keyword)?;
+        let name = parts.next()?.split(['<', '(', '{', ';']).next()?;
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
     fn extract_struct(&self, line: &str, content: &str, line_num: usize) -> Option<TypeDefinition> {
         let visibility = if line.starts_with("pub") {
             Visibility::Public
@@ -439,9 +599,7 @@ impl RustAnalyzer {
             Visibility::Private
         };
 
-        // Extract struct name
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        let name = parts.get(1)?.trim_end_matches(['{', ';']).to_string();
+        let name = Self::declared_name(line, "struct")?;
 
         // Parse struct fields
         let fields = self.parse_struct_fields(content, line_num);
@@ -452,6 +610,7 @@ impl RustAnalyzer {
             documentation: Self::extract_rust_doc_comment(content, line_num),
             fields,
             visibility,
+            methods: Vec::new(),
         })
     }
 
@@ -462,8 +621,7 @@ impl RustAnalyzer {
             Visibility::Private
         };
 
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        let name = parts.get(1)?.trim_end_matches(['{', ';']).to_string();
+        let name = Self::declared_name(line, "enum")?;
 
         // Parse enum variants
         let fields = self.parse_enum_variants(content, line_num);
@@ -474,6 +632,7 @@ impl RustAnalyzer {
             documentation: Self::extract_rust_doc_comment(content, line_num),
             fields,
             visibility,
+            methods: Vec::new(),
         })
     }
 
@@ -484,8 +643,7 @@ impl RustAnalyzer {
             Visibility::Private
         };
 
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        let name = parts.get(1)?.trim_end_matches(['{', ';']).to_string();
+        let name = Self::declared_name(line, "trait")?;
 
         Some(TypeDefinition {
             name,
@@ -493,6 +651,7 @@ impl RustAnalyzer {
             documentation: Self::extract_rust_doc_comment(content, line_num),
             fields: Vec::new(),
             visibility,
+            methods: Vec::new(),
         })
     }
 
@@ -636,7 +795,7 @@ impl RustAnalyzer {
     fn parse_cargo_files(&self, repo_path: &Path, structure: &mut CodeStructure) -> Result<()> {
         let cargo_toml = repo_path.join("Cargo.toml");
         if cargo_toml.exists() {
-            if let Ok(content) = std::fs::read_to_string(&cargo_toml) {
+            if let Some(content) = structure.read_source(&cargo_toml) {
                 structure.configs.push(ConfigFile {
                     path: cargo_toml,
                     format: ConfigFormat::Toml,
@@ -647,7 +806,7 @@ impl RustAnalyzer {
 
         let cargo_lock = repo_path.join("Cargo.lock");
         if cargo_lock.exists() {
-            if let Ok(content) = std::fs::read_to_string(&cargo_lock) {
+            if let Some(content) = structure.read_source(&cargo_lock) {
                 structure.configs.push(ConfigFile {
                     path: cargo_lock,
                     format: ConfigFormat::Toml,
@@ -679,13 +838,17 @@ impl LanguageAnalyzer for GoAnalyzer {
             let path = entry.path();
 
             if path.extension().and_then(|e| e.to_str()) == Some("go") {
-                if let Ok(content) = std::fs::read_to_string(path) {
+                if let Some(content) = structure.read_source(path) {
                     self.parse_go_file(path, &content, &mut structure)?;
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::Go);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::Go);
                 }
             }
         }
 
         self.parse_go_mod(repo_path, &mut structure)?;
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -701,13 +864,16 @@ impl GoAnalyzer {
         content: &str,
         structure: &mut CodeStructure,
     ) -> Result<()> {
+        let mut declarations = Vec::new();
+
         // Simple Go parsing - extract functions, types, etc.
-        for line in content.lines() {
+        for (line_num, line) in content.lines().enumerate() {
             let trimmed = line.trim();
 
             // Parse functions
             if trimmed.starts_with("func ") {
                 if let Some(function) = self.extract_go_function(trimmed) {
+                    declarations.push((line_num, function.name.clone()));
                     structure.functions.push(function);
                 }
             }
@@ -720,6 +886,7 @@ impl GoAnalyzer {
             }
         }
 
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
         Ok(())
     }
 
@@ -780,6 +947,7 @@ impl GoAnalyzer {
                 documentation: None,
                 fields: Vec::new(),
                 visibility,
+                methods: Vec::new(),
             })
         } else {
             None
@@ -789,7 +957,7 @@ impl GoAnalyzer {
     fn parse_go_mod(&self, repo_path: &Path, structure: &mut CodeStructure) -> Result<()> {
         let go_mod = repo_path.join("go.mod");
         if go_mod.exists() {
-            if let Ok(content) = std::fs::read_to_string(&go_mod) {
+            if let Some(content) = structure.read_source(&go_mod) {
                 structure.configs.push(ConfigFile {
                     path: go_mod,
                     format: ConfigFormat::Toml, // go.mod is similar to TOML
@@ -820,13 +988,17 @@ impl LanguageAnalyzer for PythonAnalyzer {
             let path = entry.path();
 
             if path.extension().and_then(|e| e.to_str()) == Some("py") {
-                if let Ok(content) = std::fs::read_to_string(path) {
+                if let Some(content) = structure.read_source(path) {
                     self.parse_python_file(path, &content, &mut structure)?;
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::Python);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::Python);
                 }
             }
         }
 
         self.parse_python_configs(repo_path, &mut structure)?;
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -843,6 +1015,7 @@ impl PythonAnalyzer {
         structure: &mut CodeStructure,
     ) -> Result<()> {
         let lines: Vec<&str> = content.lines().collect();
+        let mut declarations = Vec::new();
         let mut i = 0;
 
         while i < lines.len() {
@@ -851,6 +1024,7 @@ impl PythonAnalyzer {
             // Parse functions
             if line.starts_with("def ") {
                 if let Some(function) = self.extract_python_function(line, &lines, i) {
+                    declarations.push((i, function.name.clone()));
                     structure.functions.push(function);
                 }
             }
@@ -865,6 +1039,7 @@ impl PythonAnalyzer {
             i += 1;
         }
 
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
         Ok(())
     }
 
@@ -930,6 +1105,7 @@ impl PythonAnalyzer {
             documentation,
             fields: Vec::new(), // TODO: Parse class attributes
             visibility,
+            methods: Vec::new(),
         })
     }
 
@@ -993,7 +1169,7 @@ impl PythonAnalyzer {
         // Parse requirements.txt
         let requirements = repo_path.join("requirements.txt");
         if requirements.exists() {
-            if let Ok(content) = std::fs::read_to_string(&requirements) {
+            if let Some(content) = structure.read_source(&requirements) {
                 structure.configs.push(ConfigFile {
                     path: requirements,
                     format: ConfigFormat::Env, // Plain text format
@@ -1005,7 +1181,7 @@ impl PythonAnalyzer {
         // Parse pyproject.toml
         let pyproject = repo_path.join("pyproject.toml");
         if pyproject.exists() {
-            if let Ok(content) = std::fs::read_to_string(&pyproject) {
+            if let Some(content) = structure.read_source(&pyproject) {
                 structure.configs.push(ConfigFile {
                     path: pyproject,
                     format: ConfigFormat::Toml,
@@ -1038,14 +1214,26 @@ impl LanguageAnalyzer for JavaScriptAnalyzer {
 
             if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
                 if matches!(ext, "js" | "mjs" | "cjs") {
-                    if let Ok(content) = std::fs::read_to_string(path) {
+                    if let Some(content) = structure.read_source(path) {
                         self.parse_js_file(path, &content, &mut structure)?;
+                        structure.record_annotations(
+                            path,
+                            &content,
+                            &ProgrammingLanguage::JavaScript,
+                        );
+                        structure.record_config_keys(
+                            path,
+                            &content,
+                            &ProgrammingLanguage::JavaScript,
+                        );
                     }
                 }
             }
         }
 
         self.parse_js_configs(repo_path, &mut structure)?;
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -1061,8 +1249,10 @@ impl JavaScriptAnalyzer {
         content: &str,
         structure: &mut CodeStructure,
     ) -> Result<()> {
+        let mut declarations = Vec::new();
+
         // Simple JavaScript parsing
-        for line in content.lines() {
+        for (line_num, line) in content.lines().enumerate() {
             let trimmed = line.trim();
 
             // Parse functions
@@ -1071,6 +1261,7 @@ impl JavaScriptAnalyzer {
                 || trimmed.contains("=> ")
             {
                 if let Some(function) = self.extract_js_function(trimmed) {
+                    declarations.push((line_num, function.name.clone()));
                     structure.functions.push(function);
                 }
             }
@@ -1083,6 +1274,7 @@ impl JavaScriptAnalyzer {
             }
         }
 
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
         Ok(())
     }
 
@@ -1127,13 +1319,14 @@ impl JavaScriptAnalyzer {
             documentation: None,
             fields: Vec::new(),
             visibility: Visibility::Public,
+            methods: Vec::new(),
         })
     }
 
     fn parse_js_configs(&self, repo_path: &Path, structure: &mut CodeStructure) -> Result<()> {
         let package_json = repo_path.join("package.json");
         if package_json.exists() {
-            if let Ok(content) = std::fs::read_to_string(&package_json) {
+            if let Some(content) = structure.read_source(&package_json) {
                 structure.configs.push(ConfigFile {
                     path: package_json,
                     format: ConfigFormat::Json,
@@ -1176,13 +1369,25 @@ impl LanguageAnalyzer for TypeScriptAnalyzer {
 
             if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
                 if matches!(ext, "ts" | "tsx") {
-                    if let Ok(content) = std::fs::read_to_string(path) {
+                    if let Some(content) = structure.read_source(path) {
                         self.parse_ts_file(path, &content, &mut structure)?;
+                        structure.record_annotations(
+                            path,
+                            &content,
+                            &ProgrammingLanguage::TypeScript,
+                        );
+                        structure.record_config_keys(
+                            path,
+                            &content,
+                            &ProgrammingLanguage::TypeScript,
+                        );
                     }
                 }
             }
         }
 
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -1237,6 +1442,7 @@ impl TypeScriptAnalyzer {
             documentation: None,
             fields: Vec::new(),
             visibility: Visibility::Public,
+            methods: Vec::new(),
         })
     }
 
@@ -1252,6 +1458,7 @@ impl TypeScriptAnalyzer {
             documentation: None,
             fields: Vec::new(),
             visibility: Visibility::Public,
+            methods: Vec::new(),
         })
     }
 }
@@ -1275,12 +1482,16 @@ impl LanguageAnalyzer for JavaAnalyzer {
             let path = entry.path();
 
             if path.extension().and_then(|e| e.to_str()) == Some("java") {
-                if let Ok(content) = std::fs::read_to_string(path) {
+                if let Some(content) = structure.read_source(path) {
                     self.parse_java_file(path, &content, &mut structure)?;
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::Java);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::Java);
                 }
             }
         }
 
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
         Ok(structure)
     }
 
@@ -1296,12 +1507,15 @@ impl JavaAnalyzer {
         content: &str,
         structure: &mut CodeStructure,
     ) -> Result<()> {
-        for line in content.lines() {
+        let mut declarations = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
             let trimmed = line.trim();
 
             // Parse methods
             if self.is_java_method(trimmed) {
                 if let Some(method) = self.extract_java_method(trimmed) {
+                    declarations.push((line_num, method.name.clone()));
                     structure.functions.push(method);
                 }
             }
@@ -1314,6 +1528,7 @@ impl JavaAnalyzer {
             }
         }
 
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
         Ok(())
     }
 
@@ -1398,8 +1613,593 @@ impl JavaAnalyzer {
             documentation: None,
             fields: Vec::new(),
             visibility,
+            methods: Vec::new(),
+        })
+    }
+}
+
+/// C# language analyzer
+///
+/// Files are scanned line by line. Namespaces become modules, and members
+/// are only taken from lines directly inside a class, interface, struct or
+/// record body, so calls inside method bodies are not mistaken for methods.
+#[derive(Debug, Default)]
+pub struct CSharpAnalyzer;
+
+/// Modifiers that may precede a C# type or member declaration
+const CSHARP_MODIFIERS: &[&str] = &[
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "async",
+    "virtual",
+    "override",
+    "abstract",
+    "sealed",
+    "extern",
+    "partial",
+    "unsafe",
+    "new",
+    "readonly",
+];
+
+/// Statement keywords that are followed by parentheses but never declare a method
+const CSHARP_STATEMENTS: &[&str] = &[
+    "if", "else", "for", "foreach", "while", "do", "switch", "case", "return", "throw", "using",
+    "lock", "catch", "fixed", "checked", "await", "yield", "var", "nameof", "typeof", "sizeof",
+];
+
+/// A type whose body is being scanned
+struct CSharpScope {
+    kind: TypeKind,
+    /// Brace depth of the line declaring the type
+    depth: usize,
+    opened: bool,
+}
+
+impl CSharpAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LanguageAnalyzer for CSharpAnalyzer {
+    fn analyze(&self, repo_path: &Path) -> Result<CodeStructure> {
+        let mut structure = CodeStructure::new();
+
+        for entry in WalkDir::new(repo_path) {
+            let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
+            let path = entry.path();
+
+            if path.extension().and_then(|e| e.to_str()) == Some("cs") {
+                if let Some(content) = structure.read_source(path) {
+                    self.parse_csharp_file(path, &content, &mut structure)?;
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::CSharp);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::CSharp);
+                }
+            }
+        }
+
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
+        Ok(structure)
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        vec!["cs"]
+    }
+}
+
+impl CSharpAnalyzer {
+    fn parse_csharp_file(
+        &self,
+        file_path: &Path,
+        content: &str,
+        structure: &mut CodeStructure,
+    ) -> Result<()> {
+        let mut declarations = Vec::new();
+        let mut scopes: Vec<CSharpScope> = Vec::new();
+        let mut doc_lines: Vec<String> = Vec::new();
+        let mut depth = 0usize;
+
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if let Some(doc) = trimmed.strip_prefix("///") {
+                let text = Self::strip_xml_tags(doc);
+                if !text.is_empty() {
+                    doc_lines.push(text);
+                }
+                continue;
+            }
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                continue;
+            }
+            // Attributes sit between the doc comment and the declaration
+            if trimmed.starts_with('[') {
+                continue;
+            }
+
+            let documentation = if doc_lines.is_empty() {
+                None
+            } else {
+                Some(doc_lines.join("\n"))
+            };
+            doc_lines.clear();
+
+            if let Some(name) = Self::extract_csharp_namespace(trimmed) {
+                structure.modules.push(Module {
+                    name,
+                    path: file_path.to_path_buf(),
+                    documentation,
+                    visibility: Visibility::Public,
+                });
+            } else if let Some(mut type_def) = self.extract_csharp_type(trimmed) {
+                type_def.documentation = documentation;
+                // Positional records without a body end with a semicolon
+                if !trimmed.ends_with(';') {
+                    scopes.push(CSharpScope {
+                        kind: type_def.kind,
+                        depth,
+                        opened: false,
+                    });
+                }
+                structure.types.push(type_def);
+            } else if let Some(scope) = scopes
+                .last()
+                .filter(|scope| scope.opened && depth == scope.depth + 1)
+            {
+                if let Some(mut method) = self.extract_csharp_method(trimmed, scope.kind) {
+                    method.documentation = documentation;
+                    declarations.push((line_num, method.name.clone()));
+                    structure.functions.push(method);
+                }
+            }
+
+            let (opens, closes) = Self::count_braces(trimmed);
+            depth = (depth + opens).saturating_sub(closes);
+            if let Some(scope) = scopes.last_mut() {
+                if depth > scope.depth {
+                    scope.opened = true;
+                }
+            }
+            while scopes
+                .last()
+                .is_some_and(|scope| scope.opened && depth <= scope.depth)
+            {
+                scopes.pop();
+            }
+        }
+
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
+        Ok(())
+    }
+
+    fn extract_csharp_namespace(line: &str) -> Option<String> {
+        let rest = line.strip_prefix("namespace ")?;
+        let name = rest.trim_end_matches(['{', ';']).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    fn extract_csharp_type(&self, line: &str) -> Option<TypeDefinition> {
+        let head = line.split(['(', ':', '{', '<', ';']).next()?;
+        let words: Vec<&str> = head.split_whitespace().collect();
+        let keyword_index = words.iter().position(|word| {
+            matches!(*word, "class" | "interface" | "struct" | "enum" | "record")
+        })?;
+        if !words[..keyword_index]
+            .iter()
+            .all(|word| CSHARP_MODIFIERS.contains(word))
+        {
+            return None;
+        }
+
+        let mut name_index = keyword_index + 1;
+        let kind = match words[keyword_index] {
+            "interface" => TypeKind::Interface,
+            "struct" => TypeKind::Struct,
+            "enum" => TypeKind::Enum,
+            "record" if words.get(name_index) == Some(&"struct") => {
+                name_index += 1;
+                TypeKind::Struct
+            }
+            "record" if words.get(name_index) == Some(&"class") => {
+                name_index += 1;
+                TypeKind::Class
+            }
+            _ => TypeKind::Class,
+        };
+        let name = words.get(name_index)?.to_string();
+
+        Some(TypeDefinition {
+            name,
+            kind,
+            documentation: None,
+            fields: Vec::new(),
+            visibility: Self::csharp_visibility(&words[..keyword_index], Visibility::Private),
+            methods: Vec::new(),
+        })
+    }
+
+    fn extract_csharp_method(&self, line: &str, owner: TypeKind) -> Option<Function> {
+        let paren = line.find('(')?;
+        let head = &line[..paren];
+        if head.contains('=') || head.contains('.') {
+            return None;
+        }
+
+        // Strip generic parameters from the name, e.g. `Map<T>`
+        let head = match head.trim_end().strip_suffix('>') {
+            Some(generic) => &generic[..generic.rfind('<')?],
+            None => head,
+        };
+        let words: Vec<&str> = head.split_whitespace().collect();
+        let (name, before) = words.split_last()?;
+        if before.is_empty()
+            || CSHARP_STATEMENTS.contains(&words[0])
+            || !name.chars().next()?.is_alphabetic()
+        {
+            return None;
+        }
+
+        let modifier_count = before
+            .iter()
+            .take_while(|word| CSHARP_MODIFIERS.contains(word))
+            .count();
+        let (modifiers, return_words) = before.split_at(modifier_count);
+        let is_expression_bodied = line.contains("=>");
+        let is_declaration_only = owner == TypeKind::Interface
+            || modifiers
+                .iter()
+                .any(|word| matches!(*word, "abstract" | "extern" | "partial"));
+        if line.ends_with(';') && !is_expression_bodied && !is_declaration_only {
+            return None;
+        }
+
+        let default_visibility = if owner == TypeKind::Interface {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+        // Constructors have no return type
+        let return_type = Some(return_words.join(" "))
+            .filter(|return_type| !return_type.is_empty() && return_type != "void");
+
+        let signature = line
+            .split("=>")
+            .next()
+            .unwrap_or(line)
+            .trim_end_matches(['{', ';'])
+            .trim()
+            .to_string();
+
+        Some(Function {
+            name: name.to_string(),
+            signature,
+            documentation: None,
+            parameters: Self::parse_csharp_parameters(&line[paren + 1..]),
+            return_type,
+            visibility: Self::csharp_visibility(modifiers, default_visibility),
+            is_async: modifiers.contains(&"async"),
+        })
+    }
+
+    /// Parse parameters from the text after a method's opening parenthesis
+    fn parse_csharp_parameters(after_paren: &str) -> Vec<Parameter> {
+        let mut parameters = Vec::new();
+        let mut current = String::new();
+        let mut nesting = 0usize;
+
+        for c in after_paren.chars() {
+            match c {
+                '<' | '(' | '[' => nesting += 1,
+                '>' | ']' => nesting = nesting.saturating_sub(1),
+                ')' if nesting == 0 => break,
+                ')' => nesting -= 1,
+                ',' if nesting == 0 => {
+                    parameters.extend(Self::parse_csharp_parameter(&current));
+                    current.clear();
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        parameters.extend(Self::parse_csharp_parameter(&current));
+        parameters
+    }
+
+    fn parse_csharp_parameter(text: &str) -> Option<Parameter> {
+        let (declaration, default_value) = match text.split_once('=') {
+            Some((declaration, value)) => (declaration, Some(value.trim().to_string())),
+            None => (text, None),
+        };
+        let words: Vec<&str> = declaration
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "this" | "ref" | "out" | "in" | "params"))
+            .collect();
+        let (name, type_words) = words.split_last()?;
+        if type_words.is_empty() {
+            return None;
+        }
+
+        Some(Parameter {
+            name: name.to_string(),
+            type_annotation: type_words.join(" "),
+            default_value,
+        })
+    }
+
+    fn csharp_visibility(modifiers: &[&str], default: Visibility) -> Visibility {
+        if modifiers.contains(&"public") {
+            Visibility::Public
+        } else if modifiers.contains(&"protected") {
+            Visibility::Protected
+        } else if modifiers.contains(&"private") || modifiers.contains(&"internal") {
+            Visibility::Private
+        } else {
+            default
+        }
+    }
+
+    /// Remove XML tags such as `<summary>` from a doc comment line
+    fn strip_xml_tags(line: &str) -> String {
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in line.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// Count braces outside string literals and line comments
+    fn count_braces(line: &str) -> (usize, usize) {
+        let mut opens = 0;
+        let mut closes = 0;
+        let mut quote = None;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(_), '\\') => {
+                    chars.next();
+                }
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '/') if chars.peek() == Some(&'/') => break,
+                (None, '{') => opens += 1,
+                (None, '}') => closes += 1,
+                _ => {}
+            }
+        }
+
+        (opens, closes)
+    }
+}
+
+/// Ruby language analyzer
+///
+/// Modules become [`Module`]s and classes become class types, with
+/// `attr_accessor`, `attr_reader` and `attr_writer` names as their fields.
+/// `private` and `protected` on their own line apply to the methods after
+/// them in the same class. Documentation is the `#` comment block right
+/// above a definition.
+#[derive(Debug, Default)]
+pub struct RubyAnalyzer;
+
+impl RubyAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LanguageAnalyzer for RubyAnalyzer {
+    fn analyze(&self, repo_path: &Path) -> Result<CodeStructure> {
+        let mut structure = CodeStructure::new();
+
+        for entry in WalkDir::new(repo_path) {
+            let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
+            let path = entry.path();
+
+            if path.extension().and_then(|e| e.to_str()) == Some("rb") {
+                if let Some(content) = structure.read_source(path) {
+                    self.parse_ruby_file(path, &content, &mut structure)?;
+                    structure.record_annotations(path, &content, &ProgrammingLanguage::Ruby);
+                    structure.record_config_keys(path, &content, &ProgrammingLanguage::Ruby);
+                }
+            }
+        }
+
+        self.parse_gemfile(repo_path, &mut structure)?;
+        structure.scan_config_files(repo_path);
+        structure.resolve_call_graph();
+        Ok(structure)
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        vec!["rb"]
+    }
+}
+
+impl RubyAnalyzer {
+    fn parse_ruby_file(
+        &self,
+        file_path: &Path,
+        content: &str,
+        structure: &mut CodeStructure,
+    ) -> Result<()> {
+        let mut declarations = Vec::new();
+        let mut doc_lines: Vec<&str> = Vec::new();
+        // Index in `structure.types` of the class being read
+        let mut current_class: Option<usize> = None;
+        let mut section_visibility = Visibility::Public;
+
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                if !comment.starts_with('!') {
+                    doc_lines.push(comment.trim());
+                }
+                continue;
+            }
+
+            let documentation = if doc_lines.is_empty() {
+                None
+            } else {
+                Some(doc_lines.join("\n"))
+            };
+            doc_lines.clear();
+
+            let mut words = trimmed.split_whitespace();
+            match words.next() {
+                Some("module") => {
+                    if let Some(name) = words.next() {
+                        structure.modules.push(Module {
+                            name: name.trim_end_matches(';').to_string(),
+                            path: file_path.to_path_buf(),
+                            documentation,
+                            visibility: Visibility::Public,
+                        });
+                    }
+                }
+                // `class << self` opens the singleton class, not a new type
+                Some("class") if words.clone().next() != Some("<<") => {
+                    if let Some(name) = words.next() {
+                        structure.types.push(TypeDefinition {
+                            name: name.trim_end_matches(';').to_string(),
+                            kind: TypeKind::Class,
+                            documentation,
+                            fields: Vec::new(),
+                            visibility: Visibility::Public,
+                            methods: Vec::new(),
+                        });
+                        current_class = Some(structure.types.len() - 1);
+                        section_visibility = Visibility::Public;
+                    }
+                }
+                Some("private") if trimmed == "private" => {
+                    section_visibility = Visibility::Private;
+                }
+                Some("protected") if trimmed == "protected" => {
+                    section_visibility = Visibility::Protected;
+                }
+                Some("public") if trimmed == "public" => {
+                    section_visibility = Visibility::Public;
+                }
+                Some(keyword @ ("attr_accessor" | "attr_reader" | "attr_writer")) => {
+                    if let Some(class) = current_class {
+                        let fields = &mut structure.types[class].fields;
+                        for name in trimmed[keyword.len()..].split(',') {
+                            let name = name.trim().trim_start_matches(':');
+                            if !name.is_empty() {
+                                fields.push(Field {
+                                    name: name.to_string(),
+                                    type_annotation: keyword.to_string(),
+                                    documentation: documentation.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(mut method) = self.extract_ruby_method(trimmed, section_visibility)
+                    {
+                        method.documentation = documentation;
+                        declarations.push((line_num, method.name.clone()));
+                        structure.functions.push(method);
+                    }
+                }
+            }
+        }
+
+        call_graph::record_calls(content, &declarations, &mut structure.call_graph);
+        Ok(())
+    }
+
+    fn extract_ruby_method(&self, line: &str, section_visibility: Visibility) -> Option<Function> {
+        // `private def helper` marks a single method
+        let (visibility, definition) = if let Some(rest) = line.strip_prefix("private def ") {
+            (Visibility::Private, rest)
+        } else if let Some(rest) = line.strip_prefix("protected def ") {
+            (Visibility::Protected, rest)
+        } else {
+            (section_visibility, line.strip_prefix("def ")?)
+        };
+
+        let definition = definition.trim();
+        let name_end = definition
+            .find(|c: char| c == '(' || c.is_whitespace())
+            .unwrap_or(definition.len());
+        let name = definition[..name_end].trim_start_matches("self.");
+        if name.is_empty() {
+            return None;
+        }
+
+        let rest = definition[name_end..].trim_start();
+        let parameter_list = match rest.strip_prefix('(') {
+            Some(inner) => inner.split(')').next().unwrap_or(inner),
+            // Endless methods such as `def name = value` take no parameters
+            None if rest.starts_with('=') => "",
+            None => rest.split(';').next().unwrap_or(rest),
+        };
+
+        Some(Function {
+            name: name.to_string(),
+            signature: line.to_string(),
+            documentation: None,
+            parameters: Self::parse_ruby_parameters(parameter_list),
+            return_type: None,
+            visibility,
+            is_async: false,
         })
     }
+
+    fn parse_ruby_parameters(list: &str) -> Vec<Parameter> {
+        list.split(',')
+            .filter_map(|parameter| {
+                let parameter = parameter.trim();
+                let (name, default_value) = if let Some((name, value)) = parameter.split_once('=') {
+                    (name, Some(value.trim().to_string()))
+                } else if let Some((name, value)) = parameter.split_once(':') {
+                    // Keyword arguments, with an optional default
+                    let value = value.trim();
+                    (name, (!value.is_empty()).then(|| value.to_string()))
+                } else {
+                    (parameter, None)
+                };
+
+                let name = name.trim().trim_start_matches(['*', '&']);
+                (!name.is_empty()).then(|| Parameter {
+                    name: name.to_string(),
+                    type_annotation: String::new(),
+                    default_value,
+                })
+            })
+            .collect()
+    }
+
+    fn parse_gemfile(&self, repo_path: &Path, structure: &mut CodeStructure) -> Result<()> {
+        let gemfile = repo_path.join("Gemfile");
+        if gemfile.exists() {
+            if let Some(content) = structure.read_source(&gemfile) {
+                structure.configs.push(ConfigFile {
+                    path: gemfile,
+                    format: ConfigFormat::Env, // Ruby DSL, kept as plain text
+                    content,
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Generic analyzer for unsupported languages
@@ -1422,12 +2222,13 @@ impl LanguageAnalyzer for GenericAnalyzer {
             let path = entry.path();
 
             if path.is_file() {
-                if let Some(config_file) = self.try_parse_config_file(path)? {
+                if let Some(config_file) = self.try_parse_config_file(path, &mut structure)? {
                     structure.configs.push(config_file);
                 }
             }
         }
 
+        structure.scan_config_files(repo_path);
         Ok(structure)
     }
 
@@ -1437,10 +2238,14 @@ impl LanguageAnalyzer for GenericAnalyzer {
 }
 
 impl GenericAnalyzer {
-    fn try_parse_config_file(&self, path: &Path) -> Result<Option<ConfigFile>> {
+    fn try_parse_config_file(
+        &self,
+        path: &Path,
+        structure: &mut CodeStructure,
+    ) -> Result<Option<ConfigFile>> {
         if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
             if let Some(format) = ConfigFormat::from_extension(extension) {
-                if let Ok(content) = std::fs::read_to_string(path) {
+                if let Some(content) = structure.read_source(path) {
                     return Ok(Some(ConfigFile {
                         path: path.to_path_buf(),
                         format,
@@ -1454,7 +2259,7 @@ impl GenericAnalyzer {
         if let Some("Dockerfile" | "Makefile" | "README" | "LICENSE") =
             path.file_name().and_then(|n| n.to_str())
         {
-            if let Ok(content) = std::fs::read_to_string(path) {
+            if let Some(content) = structure.read_source(path) {
                 return Ok(Some(ConfigFile {
                     path: path.to_path_buf(),
                     format: ConfigFormat::Env,
@@ -1524,6 +2329,219 @@ impl TestStruct {
         let main_fn = structure.functions.iter().find(|f| f.name == "main");
         assert!(main_fn.is_some());
         assert_eq!(main_fn.unwrap().visibility, Visibility::Public);
+
+        // Public types are named after the keyword, not by token position
+        assert_eq!(structure.types[0].name, "TestStruct");
+    }
+
+    #[test]
+    fn test_rust_analyzer_impl_methods() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("types.rs"),
+            "pub struct Cache<K> {\n    entries: Vec<K>,\n}\n\npub fn new() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("cache.rs"),
+            r#"
+impl<K: Clone> crate::types::Cache<K> {
+    pub fn new() -> Self {
+        fn helper() {}
+        Self { entries: Vec::new() }
+    }
+}
+
+impl<K> fmt::Debug for Cache<K>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+fn free() {}
+"#,
+        )
+        .unwrap();
+
+        for analyzer in [RustAnalyzer::new(), RustAnalyzer::new().with_ast_parsing()] {
+            let structure = analyzer.analyze(temp_dir.path()).unwrap();
+
+            let cache = structure.types.iter().find(|t| t.name == "Cache").unwrap();
+            let methods: Vec<_> = cache.methods.iter().map(|m| m.name.as_str()).collect();
+            assert_eq!(methods, vec!["new", "fmt"]);
+
+            let free: Vec<_> = structure
+                .free_functions()
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect();
+            assert!(free.contains(&"free"));
+            // The free `new` in types.rs stays, the method does not
+            assert_eq!(free.iter().filter(|name| **name == "new").count(), 1);
+            assert!(!free.contains(&"fmt"));
+        }
+    }
+
+    #[test]
+    fn test_rust_analyzer_call_graph() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("lib.rs"),
+            r#"
+pub fn run() {
+    let config = load_config();
+    validate(&config);
+}
+
+fn load_config() -> String {
+    String::new()
+}
+
+fn validate(config: &str) {
+    println!("{}", config.len());
+}
+"#,
+        )
+        .unwrap();
+
+        let structure = RustAnalyzer::new().analyze(temp_dir.path()).unwrap();
+
+        assert_eq!(structure.callees_of("run"), vec!["load_config", "validate"]);
+        assert_eq!(structure.callers_of("validate"), vec!["run"]);
+        assert!(structure.callees_of("validate").is_empty());
+    }
+
+    #[test]
+    fn test_rust_analyzer_ast_parsing() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("lib.rs"),
+            r#"
+pub struct Server;
+
+impl Server {
+    /// Start serving
+    pub fn start(&self) {
+        let banner = "fn banner()";
+        self.bind(banner);
+    }
+
+    fn bind(&self, address: &str) {}
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("broken.rs"),
+            "pub fn incomplete(value: u32 {\n",
+        )
+        .unwrap();
+
+        let structure = RustAnalyzer::new()
+            .with_ast_parsing()
+            .analyze(temp_dir.path())
+            .unwrap();
+
+        let mut names: Vec<_> = structure
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["bind", "incomplete", "start"]);
+        assert_eq!(structure.callees_of("start"), vec!["bind"]);
+
+        let start = structure
+            .functions
+            .iter()
+            .find(|f| f.name == "start")
+            .unwrap();
+        assert_eq!(start.signature, "pub fn start(&self)");
+        assert_eq!(start.documentation.as_deref(), Some("Start serving"));
+
+        assert_eq!(structure.warnings.len(), 1);
+        assert_eq!(structure.warnings[0].kind, WarningKind::UnparsableFile);
+        assert_eq!(
+            structure.warnings[0].path.as_deref(),
+            Some(temp_dir.path().join("broken.rs").as_path())
+        );
+    }
+
+    #[test]
+    fn test_csharp_analyzer() {
+        let analyzer = AnalyzerFactory::create_analyzer(&ProgrammingLanguage::CSharp);
+        assert_eq!(analyzer.supported_extensions(), vec!["cs"]);
+
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/csharp");
+        let structure = analyzer.analyze(&fixtures).unwrap();
+
+        assert_eq!(structure.modules.len(), 1);
+        assert_eq!(structure.modules[0].name, "Acme.Greetings");
+
+        let kinds: Vec<_> = structure
+            .types
+            .iter()
+            .map(|t| (t.name.as_str(), t.kind, t.visibility))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Greeter", TypeKind::Class, Visibility::Public),
+                ("IGreeter", TypeKind::Interface, Visibility::Public),
+                ("Counter", TypeKind::Struct, Visibility::Private),
+                ("Tone", TypeKind::Enum, Visibility::Public),
+                ("Greeting", TypeKind::Class, Visibility::Public),
+            ]
+        );
+        assert_eq!(
+            structure.types[0].documentation.as_deref(),
+            Some("Formats greetings for users.")
+        );
+
+        let names: Vec<_> = structure
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Greeter",
+                "Greet",
+                "GreetAsync",
+                "Format",
+                "Greet",
+                "Increment"
+            ]
+        );
+
+        let greet = &structure.functions[1];
+        assert_eq!(greet.signature, "public string Greet(string name)");
+        assert_eq!(
+            greet.documentation.as_deref(),
+            Some("Greet a single user.\nThe user to greet.")
+        );
+        assert_eq!(greet.return_type.as_deref(), Some("string"));
+        assert_eq!(greet.parameters[0].name, "name");
+        assert_eq!(greet.parameters[0].type_annotation, "string");
+
+        let constructor = &structure.functions[0];
+        assert_eq!(constructor.return_type, None);
+        assert_eq!(
+            constructor.parameters[0].default_value.as_deref(),
+            Some("\"Hello\"")
+        );
+        assert!(structure.functions[2].is_async);
+        assert_eq!(
+            structure.functions[2].return_type.as_deref(),
+            Some("Task<string>")
+        );
+        assert_eq!(structure.functions[3].visibility, Visibility::Private);
+        assert_eq!(structure.functions[4].visibility, Visibility::Public);
+        assert_eq!(structure.callees_of("Greet"), vec!["Format"]);
     }
 
     #[test]
@@ -1571,6 +2589,113 @@ class TestClass:
         assert_eq!(private_fn.unwrap().visibility, Visibility::Private);
     }
 
+    #[test]
+    fn test_ruby_analyzer() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("billing.rb"),
+            r#"# frozen_string_literal: true
+
+# Billing helpers
+module Billing
+  # An invoice sent to a customer
+  class Invoice < Record
+    # Amount in cents
+    attr_accessor :amount, :currency
+    attr_reader :id
+
+    # Build an invoice
+    def initialize(amount, currency = "USD", due: nil)
+      @amount = amount
+      @currency = currency
+      validate()
+    end
+
+    def self.load(id) = find(id)
+
+    private
+
+    def validate
+      raise "def fake" if amount.negative?
+    end
+  end
+end
+"#,
+        )
+        .unwrap();
+
+        let analyzer = AnalyzerFactory::create_analyzer(&ProgrammingLanguage::Ruby);
+        assert_eq!(analyzer.supported_extensions(), vec!["rb"]);
+        let structure = analyzer.analyze(temp_dir.path()).unwrap();
+
+        assert_eq!(structure.modules.len(), 1);
+        assert_eq!(structure.modules[0].name, "Billing");
+        assert_eq!(
+            structure.modules[0].documentation.as_deref(),
+            Some("Billing helpers")
+        );
+
+        let invoice = &structure.types[0];
+        assert_eq!(invoice.name, "Invoice");
+        assert_eq!(invoice.kind, TypeKind::Class);
+        assert_eq!(
+            invoice.documentation.as_deref(),
+            Some("An invoice sent to a customer")
+        );
+        let fields: Vec<_> = invoice.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["amount", "currency", "id"]);
+        assert_eq!(invoice.fields[0].type_annotation, "attr_accessor");
+        assert_eq!(
+            invoice.fields[0].documentation.as_deref(),
+            Some("Amount in cents")
+        );
+
+        let names: Vec<_> = structure
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["initialize", "load", "validate"]);
+
+        let initialize = &structure.functions[0];
+        assert_eq!(
+            initialize.documentation.as_deref(),
+            Some("Build an invoice")
+        );
+        let parameters: Vec<_> = initialize
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![
+                ("amount", None),
+                ("currency", Some("\"USD\"")),
+                ("due", Some("nil")),
+            ]
+        );
+        assert_eq!(structure.functions[1].visibility, Visibility::Public);
+        assert_eq!(structure.functions[2].visibility, Visibility::Private);
+        assert_eq!(structure.callees_of("initialize"), vec!["validate"]);
+    }
+
+    #[test]
+    fn test_language_detection_gemfile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("app.rb"), "puts 'hello'").unwrap();
+        fs::write(
+            temp_dir.path().join("Gemfile"),
+            "source 'https://rubygems.org'",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("a.py"), "").unwrap();
+        fs::write(temp_dir.path().join("b.py"), "").unwrap();
+
+        let (detected_lang, _) = AnalyzerFactory::auto_detect_analyzer(temp_dir.path()).unwrap();
+        assert_eq!(detected_lang, ProgrammingLanguage::Ruby);
+    }
+
     #[test]
     fn test_language_detection() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,508 @@
+//! TODO, FIXME and deprecation markers found in source files
+//!
+//! Analyzers scan each source file for markers that documentation and
+//! quality reports should surface:
+//!
+//! - `TODO` and `FIXME` in comments;
+//! - deprecations in the language's own form: `#[deprecated]` in Rust,
+//!   `@deprecated` in JSDoc and Javadoc, `@Deprecated` in Java, the
+//!   `@deprecated` decorator in Python and `Deprecated:` comments in Go.
+//!
+//! Scanning is line based like the analyzers themselves. Comment detection
+//! is heuristic, so a marker inside a string literal that looks like a
+//! comment may be reported. Deprecations record the name of the item they
+//! apply to, taken from the next declaration line.
+
+use super::CodeStructure;
+use crate::types::ProgrammingLanguage;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Keywords followed by the name of the item they declare
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "mod",
+    "const",
+    "static",
+    "union",
+    "class",
+    "interface",
+    "def",
+    "func",
+    "function",
+    "let",
+    "var",
+];
+
+/// Kind of marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationKind {
+    /// Work left to do
+    Todo,
+    /// Known problem to fix
+    Fixme,
+    /// Item that should no longer be used
+    Deprecated,
+}
+
+impl fmt::Display for AnnotationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnnotationKind::Todo => write!(f, "todo"),
+            AnnotationKind::Fixme => write!(f, "fixme"),
+            AnnotationKind::Deprecated => write!(f, "deprecated"),
+        }
+    }
+}
+
+/// A marker found in a source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeAnnotation {
+    pub kind: AnnotationKind,
+    /// Text following the marker, or the deprecation note; may be empty
+    pub text: String,
+    pub path: PathBuf,
+    /// One-based line of the marker
+    pub line: usize,
+    /// Name of the deprecated item, when it could be determined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+}
+
+/// Number of markers of each kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnnotationCounts {
+    pub todo: usize,
+    pub fixme: usize,
+    pub deprecated: usize,
+}
+
+impl CodeStructure {
+    /// Scan one source file for markers and record them
+    pub fn record_annotations(
+        &mut self,
+        path: &Path,
+        content: &str,
+        language: &ProgrammingLanguage,
+    ) {
+        self.annotations
+            .extend(extract_annotations(path, content, language));
+    }
+
+    /// Count the recorded markers by kind
+    pub fn annotation_counts(&self) -> AnnotationCounts {
+        let mut counts = AnnotationCounts::default();
+        for annotation in &self.annotations {
+            match annotation.kind {
+                AnnotationKind::Todo => counts.todo += 1,
+                AnnotationKind::Fixme => counts.fixme += 1,
+                AnnotationKind::Deprecated => counts.deprecated += 1,
+            }
+        }
+        counts
+    }
+
+    /// Get the deprecation markers
+    pub fn deprecations(&self) -> impl Iterator<Item = &CodeAnnotation> {
+        self.annotations
+            .iter()
+            .filter(|a| a.kind == AnnotationKind::Deprecated)
+    }
+}
+
+/// Find the markers in one source file
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use xze_core::repository::{extract_annotations, AnnotationKind};
+/// use xze_core::types::ProgrammingLanguage;
+///
+/// let content = "// TODO: cache this\n#[deprecated(note = \"use run\")]\npub fn start() {}\n";
+/// let found = extract_annotations(Path::new("lib.rs"), content, &ProgrammingLanguage::Rust);
+///
+/// assert_eq!(found[0].kind, AnnotationKind::Todo);
+/// assert_eq!(found[0].text, "cache this");
+/// assert_eq!(found[1].item.as_deref(), Some("start"));
+/// assert_eq!(found[1].text, "use run");
+/// ```
+pub fn extract_annotations(
+    path: &Path,
+    content: &str,
+    language: &ProgrammingLanguage,
+) -> Vec<CodeAnnotation> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut annotations: Vec<CodeAnnotation> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let comment = comment_text(line, language);
+
+        let deprecation = match comment {
+            Some(comment) => comment_deprecation(comment, language),
+            None => attribute_deprecation(trimmed, language),
+        };
+        if let Some(text) = deprecation {
+            annotations.push(CodeAnnotation {
+                kind: AnnotationKind::Deprecated,
+                text,
+                path: path.to_path_buf(),
+                line: index + 1,
+                item: next_declaration(&lines, index + 1),
+            });
+            continue;
+        }
+
+        if let Some((kind, text)) = comment.and_then(task_marker) {
+            annotations.push(CodeAnnotation {
+                kind,
+                text,
+                path: path.to_path_buf(),
+                line: index + 1,
+                item: None,
+            });
+        }
+    }
+
+    merge_deprecations(annotations)
+}
+
+/// Comment part of a line, without the comment marker
+pub(super) fn comment_text<'a>(line: &'a str, language: &ProgrammingLanguage) -> Option<&'a str> {
+    let trimmed = line.trim_start();
+    if matches!(
+        language,
+        ProgrammingLanguage::Python | ProgrammingLanguage::Ruby
+    ) {
+        return line.find('#').map(|i| &line[i + 1..]);
+    }
+
+    if let Some(rest) = trimmed.strip_prefix("/*") {
+        return Some(rest.trim_start_matches('*'));
+    }
+    if let Some(rest) = trimmed.strip_prefix('*') {
+        if !rest.starts_with('/') {
+            return Some(rest);
+        }
+    }
+
+    // `//` after whitespace or at the start, so `http://` is not a comment
+    line.match_indices("//")
+        .find(|(i, _)| *i == 0 || line[..*i].ends_with(char::is_whitespace))
+        .map(|(i, _)| line[i + 2..].trim_start_matches(['/', '!']))
+}
+
+/// Deprecation note in a comment, such as `@deprecated` or Go's
+/// `Deprecated:`
+fn comment_deprecation(comment: &str, language: &ProgrammingLanguage) -> Option<String> {
+    let comment = comment.trim().trim_end_matches("*/").trim();
+    if let Some(index) = comment.find("@deprecated") {
+        return Some(clean_text(&comment[index + "@deprecated".len()..]));
+    }
+    if *language == ProgrammingLanguage::Go {
+        if let Some(rest) = comment.strip_prefix("Deprecated:") {
+            return Some(clean_text(rest));
+        }
+    }
+    None
+}
+
+/// Deprecation attribute, annotation or decorator outside comments
+fn attribute_deprecation(trimmed: &str, language: &ProgrammingLanguage) -> Option<String> {
+    let is_deprecation = match language {
+        ProgrammingLanguage::Rust => trimmed.starts_with("#[deprecated"),
+        ProgrammingLanguage::Java => trimmed.starts_with("@Deprecated"),
+        ProgrammingLanguage::Python => {
+            let decorator = trimmed.split('(').next().unwrap_or_default();
+            decorator == "@deprecated"
+                || (decorator.starts_with('@') && decorator.ends_with(".deprecated"))
+        }
+        _ => false,
+    };
+    // Prefer Rust's `note = "..."` over other quoted arguments like `since`
+    let arguments = trimmed.find("note").map_or(trimmed, |i| &trimmed[i..]);
+    is_deprecation.then(|| first_quoted(arguments).unwrap_or_default())
+}
+
+/// `TODO` or `FIXME` marker in a comment, with the text after it
+fn task_marker(comment: &str) -> Option<(AnnotationKind, String)> {
+    [
+        ("TODO", AnnotationKind::Todo),
+        ("FIXME", AnnotationKind::Fixme),
+    ]
+    .into_iter()
+    .filter_map(|(marker, kind)| {
+        comment
+            .match_indices(marker)
+            .find(|(start, _)| {
+                let before = comment[..*start].chars().next_back();
+                let after = comment[start + marker.len()..].chars().next();
+                !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+            })
+            .map(|(start, _)| (start, kind, marker.len()))
+    })
+    .min_by_key(|(start, _, _)| *start)
+    .map(|(start, kind, len)| {
+        let mut rest = comment[start + len..].trim_start();
+        // Skip an owner such as `TODO(alice):`
+        if rest.starts_with('(') {
+            if let Some(end) = rest.find(')') {
+                rest = &rest[end + 1..];
+            }
+        }
+        (kind, clean_text(rest.trim_end().trim_end_matches("*/")))
+    })
+}
+
+/// Name declared by the first code line at or after `start`
+fn next_declaration(lines: &[&str], start: usize) -> Option<String> {
+    let line = lines.iter().skip(start).map(|l| l.trim()).find(|l| {
+        !l.is_empty()
+            && !l.starts_with("//")
+            && !l.starts_with("/*")
+            && !l.starts_with('*')
+            && !l.starts_with('#')
+            && !l.starts_with('@')
+    })?;
+    declared_name(line)
+}
+
+/// Name declared on a line, by keyword or as the identifier before `(`
+fn declared_name(line: &str) -> Option<String> {
+    // Go methods put the receiver before the name: `func (s *Server) Run()`
+    let line = match line.strip_prefix("func (") {
+        Some(rest) => rest.split_once(')').map(|(_, rest)| rest).unwrap_or(rest),
+        None => line,
+    };
+
+    let tokens: Vec<&str> = line
+        .split(|c: char| !is_identifier_char(c))
+        .filter(|t| !t.is_empty())
+        .collect();
+    if let Some(position) = tokens.iter().position(|t| DECLARATION_KEYWORDS.contains(t)) {
+        if let Some(name) = tokens.get(position + 1) {
+            return Some(name.to_string());
+        }
+    }
+    if line.trim_start().starts_with("func ") {
+        return tokens.get(1).map(|t| t.to_string());
+    }
+
+    let head = line.split('(').next().filter(|_| line.contains('('))?;
+    head.split(|c: char| !is_identifier_char(c))
+        .rfind(|t| !t.is_empty())
+        .map(str::to_string)
+}
+
+/// Keep one deprecation per item, preferring one with a note
+///
+/// Java items often carry both a Javadoc `@deprecated` tag and a
+/// `@Deprecated` annotation.
+fn merge_deprecations(annotations: Vec<CodeAnnotation>) -> Vec<CodeAnnotation> {
+    let mut merged: Vec<CodeAnnotation> = Vec::with_capacity(annotations.len());
+    for annotation in annotations {
+        let duplicate = merged.iter_mut().rev().find(|existing| {
+            annotation.kind == AnnotationKind::Deprecated
+                && existing.kind == AnnotationKind::Deprecated
+                && annotation.item.is_some()
+                && existing.item == annotation.item
+        });
+        match duplicate {
+            Some(existing) => {
+                if existing.text.is_empty() {
+                    existing.text = annotation.text;
+                }
+            }
+            None => merged.push(annotation),
+        }
+    }
+    merged
+}
+
+fn first_quoted(text: &str) -> Option<String> {
+    let start = text.find(['"', '\''])?;
+    let quote = text[start..].chars().next()?;
+    let rest = &text[start + 1..];
+    rest.find(quote).map(|end| rest[..end].to_string())
+}
+
+fn clean_text(text: &str) -> String {
+    text.trim()
+        .trim_start_matches([':', '-'])
+        .trim()
+        .to_string()
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(content: &str, language: ProgrammingLanguage) -> Vec<CodeAnnotation> {
+        extract_annotations(Path::new("src/file"), content, &language)
+    }
+
+    fn summary(annotations: &[CodeAnnotation]) -> Vec<(AnnotationKind, &str, Option<&str>)> {
+        annotations
+            .iter()
+            .map(|a| (a.kind, a.text.as_str(), a.item.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_task_markers_in_comments() {
+        let content = "\
+// TODO: handle retries
+let url = \"http://example.com\"; // FIXME(bob) - leaks a socket
+/* TODO reuse the buffer */
+let todo_list = TODOS; // not a marker
+";
+        let found = extract(content, ProgrammingLanguage::Rust);
+
+        assert_eq!(
+            summary(&found),
+            vec![
+                (AnnotationKind::Todo, "handle retries", None),
+                (AnnotationKind::Fixme, "leaks a socket", None),
+                (AnnotationKind::Todo, "reuse the buffer", None),
+            ]
+        );
+        assert_eq!(found[1].line, 2);
+    }
+
+    #[test]
+    fn test_python_markers() {
+        let content = "\
+# TODO: drop python 3.8
+@deprecated(\"Use load_all\")
+def load(path):
+    pass
+";
+        let found = extract(content, ProgrammingLanguage::Python);
+
+        assert_eq!(
+            summary(&found),
+            vec![
+                (AnnotationKind::Todo, "drop python 3.8", None),
+                (AnnotationKind::Deprecated, "Use load_all", Some("load")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_deprecated_attribute() {
+        let content = "\
+/// Old entry point
+#[deprecated(since = \"0.2\", note = \"use Client::connect\")]
+#[inline]
+pub fn connect() {}
+";
+        let found = extract(content, ProgrammingLanguage::Rust);
+
+        assert_eq!(
+            summary(&found),
+            vec![(
+                AnnotationKind::Deprecated,
+                "use Client::connect",
+                Some("connect")
+            )]
+        );
+        assert_eq!(found[0].line, 2);
+    }
+
+    #[test]
+    fn test_jsdoc_deprecated_tag() {
+        let content = "\
+/**
+ * Fetch a user.
+ * @deprecated Use fetchAccount instead.
+ */
+export function fetchUser(id) {}
+";
+        let found = extract(content, ProgrammingLanguage::JavaScript);
+
+        assert_eq!(
+            summary(&found),
+            vec![(
+                AnnotationKind::Deprecated,
+                "Use fetchAccount instead.",
+                Some("fetchUser")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_java_javadoc_and_annotation_are_merged() {
+        let content = "\
+/**
+ * @deprecated use {@link #send()}
+ */
+@Deprecated
+public void post(String body) {}
+
+@Deprecated
+public class LegacyClient {}
+";
+        let found = extract(content, ProgrammingLanguage::Java);
+
+        assert_eq!(
+            summary(&found),
+            vec![
+                (
+                    AnnotationKind::Deprecated,
+                    "use {@link #send()}",
+                    Some("post")
+                ),
+                (AnnotationKind::Deprecated, "", Some("LegacyClient")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_deprecated_comment() {
+        let content = "\
+// Dial connects to the server.
+//
+// Deprecated: use DialContext.
+func (c *Client) Dial(addr string) error {
+";
+        let found = extract(content, ProgrammingLanguage::Go);
+
+        assert_eq!(
+            summary(&found),
+            vec![(AnnotationKind::Deprecated, "use DialContext.", Some("Dial"))]
+        );
+    }
+
+    #[test]
+    fn test_annotation_counts() {
+        let mut structure = CodeStructure::new();
+        structure.record_annotations(
+            Path::new("a.rs"),
+            "// TODO one\n// TODO two\n// FIXME three\n#[deprecated]\nfn old() {}\n",
+            &ProgrammingLanguage::Rust,
+        );
+
+        assert_eq!(
+            structure.annotation_counts(),
+            AnnotationCounts {
+                todo: 2,
+                fixme: 1,
+                deprecated: 1
+            }
+        );
+        assert_eq!(structure.deprecations().count(), 1);
+    }
+}
@@ -0,0 +1,224 @@
+//! Best-effort intra-repository call graph extraction
+//!
+//! Analyzers are line based, so call resolution here is heuristic: every
+//! identifier immediately followed by `(` inside a function body is treated
+//! as a call candidate, and candidates are kept only when they name a
+//! function found elsewhere in the same [`CodeStructure`]. Functions are
+//! identified by bare name, so overloads, methods on different types and
+//! same-named functions in different modules are merged. A function body is
+//! approximated as the lines between its declaration and the next function
+//! declaration in the same file.
+
+use super::CodeStructure;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Words that look like calls (`if (`, `while (`) but never are
+const CALL_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "match", "switch", "return", "catch", "fn", "func", "def", "function",
+    "loop", "sizeof", "typeof", "new", "await", "yield", "elif", "assert",
+];
+
+/// Record raw call candidates for the functions declared in one file
+///
+/// `declarations` holds the zero-based line index and name of every function
+/// declared in `content`, in file order. Candidates are merged into
+/// `call_graph` and later filtered by [`CodeStructure::resolve_call_graph`].
+pub fn record_calls(
+    content: &str,
+    declarations: &[(usize, String)],
+    call_graph: &mut HashMap<String, Vec<String>>,
+) {
+    if declarations.is_empty() {
+        return;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (index, (start, caller)) in declarations.iter().enumerate() {
+        let end = declarations
+            .get(index + 1)
+            .map(|(next, _)| *next)
+            .unwrap_or(lines.len());
+
+        let callees = call_graph.entry(caller.clone()).or_default();
+
+        // Skip the declaration line itself so a function does not call itself
+        for line in lines.iter().take(end).skip(start + 1) {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*') {
+                continue;
+            }
+
+            for candidate in call_candidates(trimmed) {
+                if !callees.contains(&candidate) {
+                    callees.push(candidate);
+                }
+            }
+        }
+    }
+}
+
+/// Extract identifiers that are immediately followed by an opening paren
+fn call_candidates(line: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut current = String::new();
+
+    for ch in line.chars() {
+        if ch.is_alphanumeric() || ch == '_' {
+            current.push(ch);
+            continue;
+        }
+
+        if ch == '(' && is_call_name(&current) {
+            candidates.push(current.clone());
+        }
+        current.clear();
+    }
+
+    candidates
+}
+
+fn is_call_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().next().is_some_and(|c| c.is_ascii_digit())
+        && !CALL_KEYWORDS.contains(&name)
+}
+
+impl CodeStructure {
+    /// Keep only call edges that point at functions known to this structure
+    ///
+    /// Edges are deduplicated, sorted and self-references are dropped.
+    /// Functions without any resolved callees are removed from the graph.
+    /// Names shared by several functions are recorded as an info warning.
+    pub fn resolve_call_graph(&mut self) {
+        let known: HashSet<&str> = self.functions.iter().map(|f| f.name.as_str()).collect();
+
+        let mut resolved = HashMap::new();
+        for (caller, callees) in self.call_graph.drain() {
+            let callees: BTreeSet<String> = callees
+                .into_iter()
+                .filter(|callee| *callee != caller && known.contains(callee.as_str()))
+                .collect();
+            if !callees.is_empty() {
+                resolved.insert(caller, callees.into_iter().collect());
+            }
+        }
+
+        self.call_graph = resolved;
+        self.warn_ambiguous_names();
+    }
+
+    /// Get the functions that call `name`, sorted by name
+    pub fn callers_of(&self, name: &str) -> Vec<String> {
+        let mut callers: Vec<String> = self
+            .call_graph
+            .iter()
+            .filter(|(_, callees)| callees.iter().any(|c| c == name))
+            .map(|(caller, _)| caller.clone())
+            .collect();
+        callers.sort();
+        callers
+    }
+
+    /// Get the functions that `name` calls, sorted by name
+    pub fn callees_of(&self, name: &str) -> Vec<String> {
+        self.call_graph.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Get every function that directly or indirectly calls one of `names`
+    ///
+    /// Used to propagate change impact through the call graph. The changed
+    /// functions themselves are not included unless they are reached through
+    /// a cycle.
+    pub fn transitive_callers<S: AsRef<str>>(&self, names: &[S]) -> Vec<String> {
+        let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (caller, callees) in &self.call_graph {
+            for callee in callees {
+                reverse
+                    .entry(callee.as_str())
+                    .or_default()
+                    .push(caller.as_str());
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        let mut queue: VecDeque<&str> = names.iter().map(|n| n.as_ref()).collect();
+        while let Some(name) = queue.pop_front() {
+            for caller in reverse.get(name).into_iter().flatten() {
+                if seen.insert(caller.to_string()) {
+                    queue.push_back(caller);
+                }
+            }
+        }
+
+        seen.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, Visibility};
+
+    fn function(name: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    fn structure_with(names: &[&str], content: &str, decls: &[(usize, &str)]) -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.functions = names.iter().map(|n| function(n)).collect();
+        let decls: Vec<(usize, String)> = decls.iter().map(|(l, n)| (*l, n.to_string())).collect();
+        record_calls(content, &decls, &mut structure.call_graph);
+        structure.resolve_call_graph();
+        structure
+    }
+
+    #[test]
+    fn test_call_candidates() {
+        assert_eq!(
+            call_candidates("let x = parse(load(path)?);"),
+            vec!["parse", "load"]
+        );
+        assert_eq!(call_candidates("if (ready) { self.run() }"), vec!["run"]);
+        assert!(call_candidates("let x = 1;").is_empty());
+    }
+
+    #[test]
+    fn test_resolves_known_functions_only() {
+        let content = "fn a() {\n    b();\n    println!(\"x\");\n    unknown();\n}\nfn b() {\n    a();\n    b();\n}\n";
+        let structure = structure_with(&["a", "b"], content, &[(0, "a"), (5, "b")]);
+
+        assert_eq!(structure.callees_of("a"), vec!["b"]);
+        assert_eq!(structure.callees_of("b"), vec!["a"]);
+        assert_eq!(structure.callers_of("b"), vec!["a"]);
+        assert!(structure.callees_of("missing").is_empty());
+    }
+
+    #[test]
+    fn test_transitive_callers() {
+        let content = "fn top() {\n    mid();\n}\nfn mid() {\n    leaf();\n}\nfn leaf() {\n}\nfn other() {\n}\n";
+        let structure = structure_with(
+            &["top", "mid", "leaf", "other"],
+            content,
+            &[(0, "top"), (3, "mid"), (6, "leaf"), (8, "other")],
+        );
+
+        assert_eq!(structure.transitive_callers(&["leaf"]), vec!["mid", "top"]);
+        assert!(structure.transitive_callers(&["other"]).is_empty());
+    }
+
+    #[test]
+    fn test_comments_are_ignored() {
+        let content = "def a():\n    # b()\n    return 1\ndef b():\n    pass\n";
+        let structure = structure_with(&["a", "b"], content, &[(0, "a"), (3, "b")]);
+        assert!(structure.callees_of("a").is_empty());
+    }
+}
@@ -0,0 +1,851 @@
+//! Environment variables and configuration keys a project reads
+//!
+//! Two sources feed [`CodeStructure::config_keys`]:
+//!
+//! - source files, scanned for environment lookups such as
+//!   `std::env::var("X")`, `os.environ["X"]`, `process.env.X`,
+//!   `os.Getenv("X")` and `System.getenv("X")`;
+//! - configuration files (`.env`, YAML, TOML and JSON), whose keys are
+//!   flattened to dotted paths such as `server.port`.
+//!
+//! Each reference records where it was found, a default when one is visible
+//! on the same line, and a description taken from a trailing comment or the
+//! comment lines directly above. Scanning is line based like the analyzers,
+//! so lookups built from computed names are not found.
+
+use super::annotations::comment_text;
+use super::{CodeStructure, ConfigFormat, Repository};
+use crate::{error::Result, types::ProgrammingLanguage};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// Package manifests and lockfiles, which describe the build rather than
+/// the service's configuration
+const MANIFEST_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "tsconfig.json",
+    "go.mod",
+    "go.sum",
+    "pyproject.toml",
+    "Pipfile.lock",
+    "poetry.lock",
+];
+
+/// Directories whose files are never treated as service configuration
+const SKIPPED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    "tests",
+    "test",
+    "testdata",
+    "fixtures",
+];
+
+/// Kind of configuration reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigKeyKind {
+    /// Environment variable
+    EnvVar,
+    /// Key in a configuration file
+    ConfigKey,
+}
+
+impl fmt::Display for ConfigKeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigKeyKind::EnvVar => write!(f, "env_var"),
+            ConfigKeyKind::ConfigKey => write!(f, "config_key"),
+        }
+    }
+}
+
+/// A configuration value the project reads or defines
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigKey {
+    /// Variable name, or dotted key path for configuration files
+    pub name: String,
+    pub kind: ConfigKeyKind,
+    pub path: PathBuf,
+    /// One-based line of the reference
+    pub line: usize,
+    /// Default or example value, when visible
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Nearby comment describing the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl CodeStructure {
+    /// Scan one source file for environment lookups and record them
+    pub fn record_config_keys(
+        &mut self,
+        path: &Path,
+        content: &str,
+        language: &ProgrammingLanguage,
+    ) {
+        self.config_keys
+            .extend(extract_env_vars(path, content, language));
+    }
+
+    /// Scan the configuration files under `repo_path` and record their keys
+    ///
+    /// Manifests, lockfiles, hidden directories and build, vendor and test
+    /// directories are skipped.
+    pub fn scan_config_files(&mut self, repo_path: &Path) {
+        let entries = WalkDir::new(repo_path)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_skipped_dir(entry))
+            .filter_map(|entry| entry.ok());
+
+        for entry in entries {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(format) = config_file_format(entry.path()) else {
+                continue;
+            };
+            if let Some(content) = self.read_source(entry.path()) {
+                self.config_keys
+                    .extend(extract_config_file_keys(entry.path(), &content, format));
+            }
+        }
+    }
+
+    /// Recorded keys merged by kind and name, sorted
+    ///
+    /// A value referenced in several places is reported once, at its first
+    /// location; the default and description come from the first reference
+    /// that has them.
+    pub fn merged_config_keys(&self) -> Vec<ConfigKey> {
+        let mut merged: BTreeMap<(ConfigKeyKind, &str), ConfigKey> = BTreeMap::new();
+        for key in &self.config_keys {
+            merged
+                .entry((key.kind, key.name.as_str()))
+                .and_modify(|existing| {
+                    if existing.default.is_none() {
+                        existing.default = key.default.clone();
+                    }
+                    if existing.description.is_none() {
+                        existing.description = key.description.clone();
+                    }
+                })
+                .or_insert_with(|| key.clone());
+        }
+        merged.into_values().collect()
+    }
+}
+
+impl Repository {
+    /// Keys defined in the repository's configuration files
+    ///
+    /// Reads the files from [`get_config_files`](Repository::get_config_files)
+    /// that hold service configuration, skipping manifests and lockfiles.
+    pub fn config_file_keys(&self) -> Result<Vec<ConfigKey>> {
+        let mut keys = Vec::new();
+        for path in self.get_config_files()? {
+            let relative = path.strip_prefix(&self.local_path).unwrap_or(&path);
+            let skipped = relative.parent().is_some_and(|dir| {
+                dir.components().any(|c| {
+                    let name = c.as_os_str().to_string_lossy();
+                    name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref())
+                })
+            });
+            let Some(format) = config_file_format(&path).filter(|_| !skipped) else {
+                continue;
+            };
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                keys.extend(extract_config_file_keys(&path, &content, format));
+            }
+        }
+        Ok(keys)
+    }
+}
+
+/// Format of a file that holds service configuration, or `None` for
+/// manifests, lockfiles and other files
+fn config_file_format(path: &Path) -> Option<ConfigFormat> {
+    let name = path.file_name()?.to_str()?;
+    if MANIFEST_FILES.contains(&name) {
+        return None;
+    }
+    if name == ".env" || name.starts_with(".env.") {
+        return Some(ConfigFormat::Env);
+    }
+    ConfigFormat::from_extension(path.extension()?.to_str()?)
+}
+
+fn is_skipped_dir(entry: &walkdir::DirEntry) -> bool {
+    let name = entry.file_name().to_string_lossy();
+    entry.file_type().is_dir() && (name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()))
+}
+
+/// Environment lookup pattern for a language
+///
+/// `lookup` captures the variable name in group 1 or 2; `default` is
+/// matched against the text right after the lookup and captures the
+/// default value in group 1.
+struct EnvLookup {
+    lookup: Regex,
+    default: Option<Regex>,
+}
+
+impl EnvLookup {
+    fn new(lookup: &str, default: Option<&str>) -> Self {
+        Self {
+            lookup: Regex::new(lookup).expect("valid environment lookup pattern"),
+            default: default.map(|d| Regex::new(d).expect("valid default pattern")),
+        }
+    }
+}
+
+static RUST_LOOKUP: Lazy<EnvLookup> = Lazy::new(|| {
+    EnvLookup::new(
+        r#"\b(?:std::)?env::var(?:_os)?\(\s*"([^"]+)"\s*\)|\b(?:option_)?env!\(\s*"([^"]+)""#,
+        Some(r#"^\s*\.unwrap_or(?:_else)?\(\s*(?:\|[^|]*\|\s*)?"([^"]*)""#),
+    )
+});
+
+static PYTHON_LOOKUP: Lazy<EnvLookup> = Lazy::new(|| {
+    EnvLookup::new(
+        r#"\bos\.environ\[\s*["']([^"']+)["']\s*\]|\bos\.(?:environ\.get|getenv)\(\s*["']([^"']+)["']"#,
+        Some(r#"^\s*,\s*(?:default\s*=\s*)?("[^"]*"|'[^']*'|[\w.\-]+)"#),
+    )
+});
+
+static JS_LOOKUP: Lazy<EnvLookup> = Lazy::new(|| {
+    EnvLookup::new(
+        r#"\bprocess\.env\.([A-Za-z_]\w*)|\bprocess\.env\[\s*["'`]([^"'`]+)["'`]\s*\]"#,
+        Some(r#"^\s*(?:\|\||\?\?)\s*("[^"]*"|'[^']*'|`[^`]*`|[\w.\-]+)"#),
+    )
+});
+
+static GO_LOOKUP: Lazy<EnvLookup> =
+    Lazy::new(|| EnvLookup::new(r#"\bos\.(?:Getenv|LookupEnv)\(\s*"([^"]+)"\s*\)"#, None));
+
+static JAVA_LOOKUP: Lazy<EnvLookup> =
+    Lazy::new(|| EnvLookup::new(r#"\bSystem\.getenv\(\s*"([^"]+)"\s*\)"#, None));
+
+fn env_lookup(language: &ProgrammingLanguage) -> Option<&'static EnvLookup> {
+    match language {
+        ProgrammingLanguage::Rust => Some(&RUST_LOOKUP),
+        ProgrammingLanguage::Python => Some(&PYTHON_LOOKUP),
+        ProgrammingLanguage::JavaScript | ProgrammingLanguage::TypeScript => Some(&JS_LOOKUP),
+        ProgrammingLanguage::Go => Some(&GO_LOOKUP),
+        ProgrammingLanguage::Java => Some(&JAVA_LOOKUP),
+        _ => None,
+    }
+}
+
+/// Find the environment variables read in one source file
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use xze_core::repository::extract_env_vars;
+/// use xze_core::types::ProgrammingLanguage;
+///
+/// let content = "// Where to listen\nlet addr = env::var(\"BIND_ADDR\").unwrap_or(\"0.0.0.0:80\".into());\n";
+/// let found = extract_env_vars(Path::new("main.rs"), content, &ProgrammingLanguage::Rust);
+///
+/// assert_eq!(found[0].name, "BIND_ADDR");
+/// assert_eq!(found[0].default.as_deref(), Some("0.0.0.0:80"));
+/// assert_eq!(found[0].description.as_deref(), Some("Where to listen"));
+/// ```
+pub fn extract_env_vars(
+    path: &Path,
+    content: &str,
+    language: &ProgrammingLanguage,
+) -> Vec<ConfigKey> {
+    let Some(patterns) = env_lookup(language) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut keys = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        if is_comment_line(line, language) {
+            continue;
+        }
+
+        for captures in patterns.lookup.captures_iter(line) {
+            let Some(name) = captures.get(1).or_else(|| captures.get(2)) else {
+                continue;
+            };
+            let end = captures.get(0).map_or(line.len(), |m| m.end());
+            let default = patterns
+                .default
+                .as_ref()
+                .and_then(|d| d.captures(&line[end..]))
+                .and_then(|c| c.get(1))
+                .map(|m| unquote(m.as_str()));
+
+            keys.push(ConfigKey {
+                name: name.as_str().to_string(),
+                kind: ConfigKeyKind::EnvVar,
+                path: path.to_path_buf(),
+                line: index + 1,
+                default,
+                description: trailing_comment(line, language)
+                    .or_else(|| preceding_comment(&lines, index, language)),
+            });
+        }
+    }
+
+    keys
+}
+
+/// Find the keys defined in one configuration file
+///
+/// `.env` files yield environment variables with their values as defaults.
+/// YAML, TOML and JSON files yield dotted key paths for their scalar
+/// values and arrays; nested tables and mappings are descended into.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use xze_core::repository::{extract_config_file_keys, ConfigFormat};
+///
+/// let content = "server:\n  # Port to listen on\n  port: 8080\n";
+/// let found = extract_config_file_keys(Path::new("config.yaml"), content, ConfigFormat::Yaml);
+///
+/// assert_eq!(found[0].name, "server.port");
+/// assert_eq!(found[0].default.as_deref(), Some("8080"));
+/// assert_eq!(found[0].description.as_deref(), Some("Port to listen on"));
+/// ```
+pub fn extract_config_file_keys(
+    path: &Path,
+    content: &str,
+    format: ConfigFormat,
+) -> Vec<ConfigKey> {
+    match format {
+        ConfigFormat::Env => dotenv_keys(path, content),
+        ConfigFormat::Toml => toml_keys(path, content),
+        ConfigFormat::Yaml => yaml_keys(path, content),
+        ConfigFormat::Json => json_keys(path, content),
+    }
+}
+
+fn dotenv_keys(path: &Path, content: &str) -> Vec<ConfigKey> {
+    let mut keys = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            continue;
+        }
+
+        let assignment = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        if let Some((name, value)) = assignment.split_once('=') {
+            let name = name.trim();
+            if is_env_name(name) {
+                let (value, trailing) = split_value_comment(value);
+                keys.push(ConfigKey {
+                    name: name.to_string(),
+                    kind: ConfigKeyKind::EnvVar,
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                    default: non_empty(unquote(value)),
+                    description: trailing.or_else(|| join_comments(&comments)),
+                });
+            }
+        }
+        comments.clear();
+    }
+
+    keys
+}
+
+fn toml_keys(path: &Path, content: &str) -> Vec<ConfigKey> {
+    let mut keys = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    let mut table: Vec<String> = Vec::new();
+    // Closing delimiter of a multi-line value being skipped
+    let mut skip_until: Option<&str> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(end) = skip_until {
+            if trimmed.contains(end) {
+                skip_until = None;
+            }
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            continue;
+        }
+
+        if trimmed.starts_with('[') {
+            let header = trimmed.trim_start_matches('[');
+            let header = header.split(']').next().unwrap_or_default();
+            table = header.split('.').map(|s| unquote(s.trim())).collect();
+        } else if let Some((key, value)) = trimmed.split_once('=') {
+            let (value, trailing) = split_value_comment(value);
+            let default = if value.starts_with("\"\"\"") || value.starts_with("'''") {
+                let delimiter = &value[..3];
+                if !value[3..].contains(delimiter) {
+                    skip_until = Some(if delimiter == "\"\"\"" {
+                        "\"\"\""
+                    } else {
+                        "'''"
+                    });
+                }
+                None
+            } else if value == "[" || (value.starts_with('[') && !value.ends_with(']')) {
+                skip_until = Some("]");
+                None
+            } else {
+                non_empty(unquote(value))
+            };
+
+            let key = key.split('.').map(|s| unquote(s.trim()));
+            keys.push(ConfigKey {
+                name: table
+                    .iter()
+                    .cloned()
+                    .chain(key)
+                    .collect::<Vec<_>>()
+                    .join("."),
+                kind: ConfigKeyKind::ConfigKey,
+                path: path.to_path_buf(),
+                line: index + 1,
+                default,
+                description: trailing.or_else(|| join_comments(&comments)),
+            });
+        }
+        comments.clear();
+    }
+
+    keys
+}
+
+fn yaml_keys(path: &Path, content: &str) -> Vec<ConfigKey> {
+    let mut keys = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    // Enclosing mappings as (indent, key)
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    // Lines indented deeper than this belong to a list or block scalar
+    let mut skip_deeper_than: Option<usize> = None;
+    // Key with no inline value, reported if a sequence follows it
+    let mut open_key: Option<ConfigKey> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        if trimmed.is_empty() {
+            comments.clear();
+            continue;
+        }
+        if let Some(limit) = skip_deeper_than {
+            if indent > limit {
+                continue;
+            }
+            skip_deeper_than = None;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            continue;
+        }
+        if trimmed.starts_with("---") || trimmed.starts_with("...") {
+            parents.clear();
+            comments.clear();
+            continue;
+        }
+        if trimmed.starts_with('-') {
+            // Sequences are reported through their parent key
+            keys.extend(open_key.take());
+            skip_deeper_than = Some(indent);
+            comments.clear();
+            continue;
+        }
+        open_key = None;
+
+        let Some((key, value)) = split_yaml_key(trimmed) else {
+            comments.clear();
+            continue;
+        };
+        while parents.last().is_some_and(|(i, _)| *i >= indent) {
+            parents.pop();
+        }
+
+        let (value, trailing) = split_value_comment(value);
+        let name = parents
+            .iter()
+            .map(|(_, k)| k.as_str())
+            .chain(std::iter::once(key.as_str()))
+            .collect::<Vec<_>>()
+            .join(".");
+        let mut entry = ConfigKey {
+            name,
+            kind: ConfigKeyKind::ConfigKey,
+            path: path.to_path_buf(),
+            line: index + 1,
+            default: None,
+            description: trailing.or_else(|| join_comments(&comments)),
+        };
+        comments.clear();
+
+        if value.is_empty() {
+            parents.push((indent, key));
+            open_key = Some(entry);
+            continue;
+        }
+        if value.starts_with(['|', '>']) {
+            skip_deeper_than = Some(indent);
+        } else {
+            entry.default = non_empty(unquote(value));
+        }
+        keys.push(entry);
+    }
+
+    keys
+}
+
+/// Key and value of a YAML mapping entry such as `port: 8080`
+fn split_yaml_key(trimmed: &str) -> Option<(String, &str)> {
+    let colon = trimmed
+        .match_indices(':')
+        .map(|(i, _)| i)
+        .find(|&i| trimmed[i + 1..].is_empty() || trimmed[i + 1..].starts_with(' '))?;
+    let key = unquote(trimmed[..colon].trim());
+    (!key.is_empty()).then(|| (key, trimmed[colon + 1..].trim()))
+}
+
+fn json_keys(path: &Path, content: &str) -> Vec<ConfigKey> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut keys = Vec::new();
+    flatten_json(&value, &mut Vec::new(), &mut |name, leaf_key, value| {
+        let quoted = format!("\"{}\"", leaf_key);
+        let line = lines
+            .iter()
+            .position(|l| {
+                l.find(&quoted)
+                    .is_some_and(|i| l[i + quoted.len()..].trim_start().starts_with(':'))
+            })
+            .map_or(1, |i| i + 1);
+        let default = match value {
+            serde_json::Value::String(s) => non_empty(s.clone()),
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => Some(value.to_string()),
+            _ => None,
+        };
+        keys.push(ConfigKey {
+            name,
+            kind: ConfigKeyKind::ConfigKey,
+            path: path.to_path_buf(),
+            line,
+            default,
+            description: None,
+        });
+    });
+
+    keys.sort_by_key(|key| key.line);
+    keys
+}
+
+fn flatten_json<F>(value: &serde_json::Value, prefix: &mut Vec<String>, emit: &mut F)
+where
+    F: FnMut(String, &str, &serde_json::Value),
+{
+    let serde_json::Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        prefix.push(key.clone());
+        if child.is_object() {
+            flatten_json(child, prefix, emit);
+        } else {
+            emit(prefix.join("."), key, child);
+        }
+        prefix.pop();
+    }
+}
+
+fn is_comment_line(line: &str, language: &ProgrammingLanguage) -> bool {
+    let trimmed = line.trim_start();
+    match language {
+        ProgrammingLanguage::Python | ProgrammingLanguage::Ruby => trimmed.starts_with('#'),
+        _ => trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*'),
+    }
+}
+
+/// Comment after code on the same line
+fn trailing_comment(line: &str, language: &ProgrammingLanguage) -> Option<String> {
+    comment_text(line, language)
+        .and_then(|c| non_empty(c.trim().trim_end_matches("*/").trim().to_string()))
+}
+
+/// Comment lines directly above `index`, joined
+fn preceding_comment(
+    lines: &[&str],
+    index: usize,
+    language: &ProgrammingLanguage,
+) -> Option<String> {
+    let mut comments: Vec<String> = lines[..index]
+        .iter()
+        .rev()
+        .take_while(|line| is_comment_line(line, language))
+        .filter_map(|line| comment_text(line, language))
+        .map(|c| c.trim().trim_end_matches("*/").trim().to_string())
+        .collect();
+    comments.reverse();
+    join_comments(&comments)
+}
+
+fn join_comments(comments: &[String]) -> Option<String> {
+    non_empty(
+        comments
+            .iter()
+            .filter(|c| !c.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Split a value from a trailing `#` comment, respecting quotes
+fn split_value_comment(value: &str) -> (&str, Option<String>) {
+    let value = value.trim();
+    let search_from = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].find(quote).map_or(value.len(), |i| i + 2),
+        _ => 0,
+    };
+    let comment_start = value[search_from..]
+        .match_indices('#')
+        .map(|(i, _)| i + search_from)
+        .find(|&i| i == 0 || value[..i].ends_with(char::is_whitespace));
+    match comment_start {
+        Some(i) => (
+            value[..i].trim_end(),
+            non_empty(value[i + 1..].trim().to_string()),
+        ),
+        None => (value, None),
+    }
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\'', '`'] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn is_env_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(keys: &[ConfigKey]) -> Vec<&str> {
+        keys.iter().map(|k| k.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_env_lookups_per_language() {
+        let rust = "\
+let url = std::env::var(\"DATABASE_URL\")?;
+let port = env::var(\"PORT\").unwrap_or_else(|_| \"8080\".to_string()); // HTTP port
+// env::var(\"COMMENTED_OUT\")
+const VERSION: &str = env!(\"CARGO_PKG_VERSION\");
+";
+        let found = extract_env_vars(Path::new("main.rs"), rust, &ProgrammingLanguage::Rust);
+        assert_eq!(
+            names(&found),
+            vec!["DATABASE_URL", "PORT", "CARGO_PKG_VERSION"]
+        );
+        assert_eq!(found[1].default.as_deref(), Some("8080"));
+        assert_eq!(found[1].description.as_deref(), Some("HTTP port"));
+        assert_eq!(found[1].line, 2);
+
+        let python = "\
+# Secret used to sign sessions
+key = os.environ[\"SECRET_KEY\"]
+debug = os.getenv('DEBUG', 'false')
+level = os.environ.get(\"LOG_LEVEL\", default=\"info\")
+";
+        let found = extract_env_vars(Path::new("app.py"), python, &ProgrammingLanguage::Python);
+        assert_eq!(names(&found), vec!["SECRET_KEY", "DEBUG", "LOG_LEVEL"]);
+        assert_eq!(
+            found[0].description.as_deref(),
+            Some("Secret used to sign sessions")
+        );
+        assert_eq!(found[1].default.as_deref(), Some("false"));
+        assert_eq!(found[2].default.as_deref(), Some("info"));
+
+        let js = "const host = process.env.HOST || 'localhost';\nconst key = process.env['API_KEY'] ?? \"\";\n";
+        let found = extract_env_vars(Path::new("app.js"), js, &ProgrammingLanguage::JavaScript);
+        assert_eq!(names(&found), vec!["HOST", "API_KEY"]);
+        assert_eq!(found[0].default.as_deref(), Some("localhost"));
+        assert_eq!(found[1].default.as_deref(), Some(""));
+
+        let go = "addr := os.Getenv(\"ADDR\")\nif v, ok := os.LookupEnv(\"TOKEN\"); ok {}\n";
+        let found = extract_env_vars(Path::new("main.go"), go, &ProgrammingLanguage::Go);
+        assert_eq!(names(&found), vec!["ADDR", "TOKEN"]);
+
+        let java = "String home = System.getenv(\"APP_HOME\");\n";
+        let found = extract_env_vars(Path::new("App.java"), java, &ProgrammingLanguage::Java);
+        assert_eq!(names(&found), vec!["APP_HOME"]);
+    }
+
+    #[test]
+    fn test_dotenv_keys() {
+        let content = "\
+# Connection string
+DATABASE_URL=postgres://localhost/app
+export LOG_LEVEL=\"debug\" # verbose logs
+EMPTY=
+
+not a variable
+";
+        let found = extract_config_file_keys(Path::new(".env"), content, ConfigFormat::Env);
+        assert_eq!(names(&found), vec!["DATABASE_URL", "LOG_LEVEL", "EMPTY"]);
+        assert!(found.iter().all(|k| k.kind == ConfigKeyKind::EnvVar));
+        assert_eq!(
+            found[0].default.as_deref(),
+            Some("postgres://localhost/app")
+        );
+        assert_eq!(found[0].description.as_deref(), Some("Connection string"));
+        assert_eq!(found[1].default.as_deref(), Some("debug"));
+        assert_eq!(found[1].description.as_deref(), Some("verbose logs"));
+        assert_eq!(found[2].default, None);
+    }
+
+    #[test]
+    fn test_toml_keys() {
+        let content = "\
+title = \"app\"
+
+[server]
+# Listen port
+port = 8080
+hosts = [
+  \"a\",
+]
+
+[database.pool]
+max = 10 # connections
+";
+        let found = extract_config_file_keys(Path::new("config.toml"), content, ConfigFormat::Toml);
+        assert_eq!(
+            names(&found),
+            vec!["title", "server.port", "server.hosts", "database.pool.max"]
+        );
+        assert_eq!(found[1].description.as_deref(), Some("Listen port"));
+        assert_eq!(found[2].default, None);
+        assert_eq!(found[3].default.as_deref(), Some("10"));
+        assert_eq!(found[3].description.as_deref(), Some("connections"));
+        assert_eq!(found[3].line, 11);
+    }
+
+    #[test]
+    fn test_yaml_keys() {
+        let content = "\
+server:
+  host: \"0.0.0.0\"
+  tls:
+    enabled: false # off in development
+  allowed:
+    - a
+    - b
+  banner: |
+    welcome: not a key
+logging:
+  level: info
+";
+        let found = extract_config_file_keys(Path::new("app.yaml"), content, ConfigFormat::Yaml);
+        assert_eq!(
+            names(&found),
+            vec![
+                "server.host",
+                "server.tls.enabled",
+                "server.allowed",
+                "server.banner",
+                "logging.level"
+            ]
+        );
+        assert_eq!(found[0].default.as_deref(), Some("0.0.0.0"));
+        assert_eq!(found[1].description.as_deref(), Some("off in development"));
+        assert_eq!(found[2].default, None);
+        assert_eq!(found[3].default, None);
+    }
+
+    #[test]
+    fn test_json_keys() {
+        let content = "{\n  \"server\": {\n    \"port\": 8080\n  },\n  \"name\": \"app\",\n  \"tags\": [\"a\"]\n}\n";
+        let found = extract_config_file_keys(Path::new("config.json"), content, ConfigFormat::Json);
+        assert_eq!(names(&found), vec!["server.port", "name", "tags"]);
+        assert_eq!(found[0].line, 3);
+        assert_eq!(found[0].default.as_deref(), Some("8080"));
+        assert_eq!(found[2].default, None);
+        assert!(
+            extract_config_file_keys(Path::new("bad.json"), "{", ConfigFormat::Json).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_scan_config_files_skips_manifests_and_test_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(dir.path().join(".env.example"), "PORT=80\n").unwrap();
+        std::fs::write(dir.path().join("settings.yaml"), "mode: fast\n").unwrap();
+        std::fs::create_dir(dir.path().join("tests")).unwrap();
+        std::fs::write(dir.path().join("tests/fixture.json"), "{\"a\": 1}").unwrap();
+
+        let mut structure = CodeStructure::new();
+        structure.scan_config_files(dir.path());
+
+        let mut found = names(&structure.config_keys);
+        found.sort();
+        assert_eq!(found, vec!["PORT", "mode"]);
+    }
+
+    #[test]
+    fn test_merged_config_keys() {
+        let mut structure = CodeStructure::new();
+        structure.record_config_keys(
+            Path::new("a.rs"),
+            "let a = env::var(\"PORT\");\nlet b = env::var(\"PORT\").unwrap_or(\"80\");\nlet c = env::var(\"HOST\");\n",
+            &ProgrammingLanguage::Rust,
+        );
+
+        let merged = structure.merged_config_keys();
+        assert_eq!(names(&merged), vec!["HOST", "PORT"]);
+        assert_eq!(merged[1].line, 1);
+        assert_eq!(merged[1].default.as_deref(), Some("80"));
+    }
+}
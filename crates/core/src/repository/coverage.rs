@@ -0,0 +1,272 @@
+//! Documentation coverage measured against a per-language policy
+//!
+//! Conventions differ on what must be documented: Go only expects comments
+//! on exported identifiers, while some teams require docs on every public
+//! item down to struct fields. A [`CoveragePolicy`] lists the item kinds
+//! that require documentation for each language, and
+//! [`CodeStructure::coverage_report`] counts coverage and gaps against it.
+//! Items of kinds not listed are ignored entirely, so they neither raise nor
+//! lower the score.
+
+use super::{CodeStructure, Visibility};
+use crate::types::ProgrammingLanguage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of item that may require documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocItemKind {
+    /// Modules, packages and files
+    Module,
+    /// Public (exported) functions
+    PublicFunction,
+    /// Private and protected functions
+    PrivateFunction,
+    /// Public (exported) types
+    PublicType,
+    /// Private and protected types
+    PrivateType,
+    /// Fields of public types
+    Field,
+}
+
+impl fmt::Display for DocItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocItemKind::Module => write!(f, "module"),
+            DocItemKind::PublicFunction => write!(f, "public function"),
+            DocItemKind::PrivateFunction => write!(f, "private function"),
+            DocItemKind::PublicType => write!(f, "public type"),
+            DocItemKind::PrivateType => write!(f, "private type"),
+            DocItemKind::Field => write!(f, "field"),
+        }
+    }
+}
+
+/// Which item kinds require documentation, per language
+///
+/// Language keys are matched case-insensitively with the same aliases as
+/// `--language` (`rs`, `py`, `ts`, ...). Languages without an entry use
+/// `default`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoveragePolicy {
+    /// Required kinds for languages without an override
+    #[serde(default = "default_required")]
+    pub default: Vec<DocItemKind>,
+    /// Required kinds by language name
+    #[serde(default)]
+    pub languages: HashMap<String, Vec<DocItemKind>>,
+}
+
+impl Default for CoveragePolicy {
+    fn default() -> Self {
+        Self {
+            default: default_required(),
+            languages: HashMap::new(),
+        }
+    }
+}
+
+fn default_required() -> Vec<DocItemKind> {
+    vec![
+        DocItemKind::PublicFunction,
+        DocItemKind::PublicType,
+        DocItemKind::Module,
+    ]
+}
+
+impl CoveragePolicy {
+    /// Get the item kinds that require documentation in `language`
+    pub fn required_for(&self, language: &ProgrammingLanguage) -> &[DocItemKind] {
+        self.languages
+            .iter()
+            .find(|(name, _)| ProgrammingLanguage::from(name.as_str()) == *language)
+            .map(|(_, kinds)| kinds.as_slice())
+            .unwrap_or(&self.default)
+    }
+}
+
+/// An item that requires documentation but has none
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocGap {
+    /// Kind of the undocumented item
+    pub kind: DocItemKind,
+    /// Item name; modules are reported by path and fields as `Type.field`
+    pub name: String,
+}
+
+/// Documentation coverage of a structure under a policy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Items that require documentation
+    pub required: usize,
+    /// Required items that are documented
+    pub documented: usize,
+    /// Required items that are not documented, in structure order
+    pub gaps: Vec<DocGap>,
+}
+
+impl CoverageReport {
+    /// Documented fraction of required items (0.0 to 1.0)
+    ///
+    /// A structure with no required items has a coverage of 0.0.
+    pub fn coverage(&self) -> f32 {
+        if self.required == 0 {
+            return 0.0;
+        }
+        self.documented as f32 / self.required as f32
+    }
+
+    fn record(&mut self, kind: DocItemKind, name: String, documentation: &Option<String>) {
+        self.required += 1;
+        if documentation.is_some() {
+            self.documented += 1;
+        } else {
+            self.gaps.push(DocGap { kind, name });
+        }
+    }
+}
+
+impl CodeStructure {
+    /// Measure documentation coverage for the given required item kinds
+    pub fn coverage_report(&self, required: &[DocItemKind]) -> CoverageReport {
+        let mut report = CoverageReport::default();
+
+        if required.contains(&DocItemKind::Module) {
+            for module in &self.modules {
+                report.record(
+                    DocItemKind::Module,
+                    module.path.display().to_string(),
+                    &module.documentation,
+                );
+            }
+        }
+
+        for function in &self.functions {
+            let kind = match function.visibility {
+                Visibility::Public => DocItemKind::PublicFunction,
+                _ => DocItemKind::PrivateFunction,
+            };
+            if required.contains(&kind) {
+                report.record(kind, function.name.clone(), &function.documentation);
+            }
+        }
+
+        for type_def in &self.types {
+            let kind = match type_def.visibility {
+                Visibility::Public => DocItemKind::PublicType,
+                _ => DocItemKind::PrivateType,
+            };
+            if required.contains(&kind) {
+                report.record(kind, type_def.name.clone(), &type_def.documentation);
+            }
+
+            if type_def.visibility == Visibility::Public && required.contains(&DocItemKind::Field) {
+                for field in &type_def.fields {
+                    report.record(
+                        DocItemKind::Field,
+                        format!("{}.{}", type_def.name, field.name),
+                        &field.documentation,
+                    );
+                }
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Field, Function, Module, TypeDefinition, TypeKind};
+    use std::path::PathBuf;
+
+    fn function(name: &str, visibility: Visibility, documented: bool) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: documented.then(|| "Docs".to_string()),
+            parameters: vec![],
+            return_type: None,
+            visibility,
+            is_async: false,
+        }
+    }
+
+    fn structure() -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.modules.push(Module {
+            name: "lib".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+            documentation: Some("Crate docs".to_string()),
+            visibility: Visibility::Public,
+        });
+        structure.functions = vec![
+            function("run", Visibility::Public, true),
+            function("load", Visibility::Public, false),
+            function("helper", Visibility::Private, false),
+        ];
+        structure.types.push(TypeDefinition {
+            name: "Config".to_string(),
+            kind: TypeKind::Struct,
+            documentation: Some("Config docs".to_string()),
+            fields: vec![Field {
+                name: "path".to_string(),
+                type_annotation: "PathBuf".to_string(),
+                documentation: None,
+            }],
+            visibility: Visibility::Public,
+            methods: Vec::new(),
+        });
+        structure
+    }
+
+    #[test]
+    fn test_default_policy_ignores_private_items_and_fields() {
+        let report = structure().coverage_report(&CoveragePolicy::default().default);
+
+        assert_eq!(report.required, 4);
+        assert_eq!(report.documented, 3);
+        assert_eq!(report.coverage(), 0.75);
+        assert_eq!(
+            report.gaps,
+            vec![DocGap {
+                kind: DocItemKind::PublicFunction,
+                name: "load".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_fields_are_reported_with_type_name() {
+        let report = structure().coverage_report(&[DocItemKind::Field]);
+
+        assert_eq!(report.required, 1);
+        assert_eq!(report.gaps[0].name, "Config.path");
+    }
+
+    #[test]
+    fn test_required_for_uses_language_aliases() {
+        let policy: CoveragePolicy =
+            serde_yaml::from_str("languages:\n  go: [public-function]\n").unwrap();
+
+        assert_eq!(
+            policy.required_for(&ProgrammingLanguage::Go),
+            &[DocItemKind::PublicFunction]
+        );
+        assert_eq!(
+            policy.required_for(&ProgrammingLanguage::Rust),
+            CoveragePolicy::default().default.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_empty_report_has_zero_coverage() {
+        let report = CodeStructure::new().coverage_report(&[DocItemKind::Module]);
+        assert_eq!(report.coverage(), 0.0);
+        assert!(report.gaps.is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Structural diff between two analyses of a repository
+//!
+//! Items are matched by kind and bare name, the same identity the call graph
+//! uses. A matched item is reported as modified when its rendered summary
+//! (signature, fields, documentation) differs between the two structures.
+
+use super::{CodeStructure, Function, Module, TypeDefinition, Visibility};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Kind of item compared by [`CodeStructure::diff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffItemKind {
+    Module,
+    Function,
+    Type,
+}
+
+impl fmt::Display for DiffItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffItemKind::Module => write!(f, "module"),
+            DiffItemKind::Function => write!(f, "function"),
+            DiffItemKind::Type => write!(f, "type"),
+        }
+    }
+}
+
+/// How an item changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl fmt::Display for DiffChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffChange::Added => write!(f, "added"),
+            DiffChange::Removed => write!(f, "removed"),
+            DiffChange::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// A single changed item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemDiff {
+    pub kind: DiffItemKind,
+    pub name: String,
+    pub change: DiffChange,
+    /// Summary of the item before the change
+    pub before: Option<String>,
+    /// Summary of the item after the change
+    pub after: Option<String>,
+}
+
+/// Items that differ between two code structures
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructureDiff {
+    /// Changed items, sorted by kind and name
+    pub items: Vec<ItemDiff>,
+}
+
+impl StructureDiff {
+    /// Check if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of changed items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Names of the changed items
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|item| item.name.as_str())
+    }
+
+    /// Render the changes as plain text for a prompt
+    pub fn to_prompt(&self) -> String {
+        let mut out = String::new();
+        for item in &self.items {
+            out.push_str(&format!(
+                "- {} {} `{}`\n",
+                item.change, item.kind, item.name
+            ));
+            if let Some(before) = &item.before {
+                out.push_str(&format!("  before: {}\n", before));
+            }
+            if let Some(after) = &item.after {
+                out.push_str(&format!("  after: {}\n", after));
+            }
+        }
+        out
+    }
+}
+
+impl CodeStructure {
+    /// Compute the items that changed from `self` to `new`
+    pub fn diff(&self, new: &CodeStructure) -> StructureDiff {
+        let before = summaries(self);
+        let after = summaries(new);
+
+        let mut items = Vec::new();
+        for (key, old) in &before {
+            match after.get(key) {
+                None => items.push(item(key, DiffChange::Removed, Some(old), None)),
+                Some(new) if new != old => {
+                    items.push(item(key, DiffChange::Modified, Some(old), Some(new)))
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, new) in &after {
+            if !before.contains_key(key) {
+                items.push(item(key, DiffChange::Added, None, Some(new)));
+            }
+        }
+
+        items.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+        StructureDiff { items }
+    }
+}
+
+fn item(
+    key: &(DiffItemKind, String),
+    change: DiffChange,
+    before: Option<&String>,
+    after: Option<&String>,
+) -> ItemDiff {
+    ItemDiff {
+        kind: key.0,
+        name: key.1.clone(),
+        change,
+        before: before.cloned(),
+        after: after.cloned(),
+    }
+}
+
+/// One-line summary of every item, keyed by kind and name
+///
+/// Duplicate names keep the last summary, so same-named items merge the
+/// way they do in the call graph.
+fn summaries(structure: &CodeStructure) -> BTreeMap<(DiffItemKind, String), String> {
+    let mut map = BTreeMap::new();
+    for module in &structure.modules {
+        map.insert(
+            (DiffItemKind::Module, module.name.clone()),
+            module_summary(module),
+        );
+    }
+    for function in &structure.functions {
+        map.insert(
+            (DiffItemKind::Function, function.name.clone()),
+            function_summary(function),
+        );
+    }
+    for type_def in &structure.types {
+        map.insert(
+            (DiffItemKind::Type, type_def.name.clone()),
+            type_summary(type_def),
+        );
+    }
+    map
+}
+
+fn module_summary(module: &Module) -> String {
+    with_docs(module.path.display().to_string(), &module.documentation)
+}
+
+/// Analyzers record the declaration line as the signature, which already
+/// carries visibility and `async`
+fn function_summary(function: &Function) -> String {
+    with_docs(
+        function.signature.trim().to_string(),
+        &function.documentation,
+    )
+}
+
+fn type_summary(type_def: &TypeDefinition) -> String {
+    let fields: Vec<String> = type_def
+        .fields
+        .iter()
+        .map(|f| format!("{}: {}", f.name, f.type_annotation))
+        .collect();
+    with_docs(
+        format!(
+            "{}{} {} {{ {} }}",
+            visibility_prefix(type_def.visibility),
+            format!("{:?}", type_def.kind).to_lowercase(),
+            type_def.name,
+            fields.join(", ")
+        ),
+        &type_def.documentation,
+    )
+}
+
+fn visibility_prefix(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "pub ",
+        Visibility::Private => "",
+        Visibility::Protected => "protected ",
+    }
+}
+
+fn with_docs(summary: String, documentation: &Option<String>) -> String {
+    match documentation {
+        Some(docs) => format!(
+            "{} -- {}",
+            summary,
+            docs.lines().next().unwrap_or("").trim()
+        ),
+        None => summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(name: &str, signature: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: signature.to_string(),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    fn structure(functions: Vec<Function>) -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.functions = functions;
+        structure
+    }
+
+    #[test]
+    fn test_identical_structures_have_empty_diff() {
+        let old = structure(vec![function("run", "fn run()")]);
+        assert!(old.diff(&old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_modified() {
+        let old = structure(vec![
+            function("run", "fn run()"),
+            function("load", "fn load()"),
+        ]);
+        let new = structure(vec![
+            function("run", "fn run(force: bool)"),
+            function("save", "fn save()"),
+        ]);
+
+        let diff = old.diff(&new);
+        let changes: Vec<(&str, DiffChange)> = diff
+            .items
+            .iter()
+            .map(|item| (item.name.as_str(), item.change))
+            .collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                ("load", DiffChange::Removed),
+                ("run", DiffChange::Modified),
+                ("save", DiffChange::Added),
+            ]
+        );
+        assert_eq!(diff.items[1].after.as_deref(), Some("fn run(force: bool)"));
+    }
+
+    #[test]
+    fn test_to_prompt_lists_changes() {
+        let old = structure(vec![]);
+        let new = structure(vec![function("run", "fn run()")]);
+
+        let prompt = old.diff(&new).to_prompt();
+        assert_eq!(prompt, "- added function `run`\n  after: fn run()\n");
+    }
+}
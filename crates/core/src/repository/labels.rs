@@ -0,0 +1,246 @@
+//! Repository tags and groups
+//!
+//! Repositories in the configuration can carry a `group` and a list of
+//! `tags` so that large fleets can be listed and summarized by team, domain
+//! or criticality rather than as one flat list:
+//!
+//! ```yaml
+//! repositories:
+//!   - name: billing-api
+//!     url: https://github.com/example/billing-api
+//!     group: payments
+//!     tags: [critical, team-ledger]
+//! ```
+//!
+//! Groups and tags share one syntax: 1 to 64 lowercase ASCII letters,
+//! digits, `-`, `_` or `.`, starting with a letter or digit. Labels are
+//! trimmed and lowercased before they are checked, and repeated tags are
+//! dropped.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::repository::labels::{normalize_tags, RepositoryFilter};
+//!
+//! let tags = normalize_tags(&["Critical".to_string(), "critical".to_string()]).unwrap();
+//! assert_eq!(tags, vec!["critical"]);
+//!
+//! let filter = RepositoryFilter {
+//!     group: Some("payments".to_string()),
+//!     tag: None,
+//! };
+//! assert!(filter.matches(Some("payments"), &tags));
+//! assert!(!filter.matches(None, &tags));
+//!
+//! assert!(normalize_tags(&["has space".to_string()]).is_err());
+//! ```
+
+use super::RepositoryMetadata;
+use crate::error::{Result, XzeError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Maximum length of a group or tag
+pub const MAX_LABEL_LEN: usize = 64;
+
+/// Trim and lowercase `label`, then check its syntax
+///
+/// `kind` names the label in the error message, such as `tag` or `group`.
+///
+/// # Errors
+///
+/// Returns a validation error if the label is empty, too long or contains
+/// characters other than letters, digits, `-`, `_` and `.`
+pub fn normalize_label(label: &str, kind: &str) -> Result<String> {
+    let label = label.trim().to_ascii_lowercase();
+    let valid = label.len() <= MAX_LABEL_LEN
+        && label
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric())
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(XzeError::validation(format!(
+            "Invalid repository {} '{}': use up to {} lowercase letters, digits, '-', '_' or '.', starting with a letter or digit",
+            kind, label, MAX_LABEL_LEN
+        )));
+    }
+    Ok(label)
+}
+
+/// Normalize each tag and drop repeats, keeping the first occurrence
+///
+/// # Errors
+///
+/// Returns a validation error for the first invalid tag
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = normalize_label(tag, "tag")?;
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Selects repositories by group and tag
+///
+/// Unset criteria match every repository; set criteria must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryFilter {
+    /// Group the repository must belong to
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Tag the repository must carry
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl RepositoryFilter {
+    /// Normalize the criteria so they compare equal to stored labels
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a criterion is not a valid label
+    pub fn normalized(self) -> Result<Self> {
+        Ok(Self {
+            group: self
+                .group
+                .map(|g| normalize_label(&g, "group"))
+                .transpose()?,
+            tag: self.tag.map(|t| normalize_label(&t, "tag")).transpose()?,
+        })
+    }
+
+    /// Whether a repository with this group and these tags is selected
+    pub fn matches(&self, group: Option<&str>, tags: &[String]) -> bool {
+        self.group.as_deref().is_none_or(|g| group == Some(g))
+            && self.tag.as_ref().is_none_or(|t| tags.contains(t))
+    }
+}
+
+/// Totals for the repositories of one group
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupStats {
+    /// Number of repositories in the group
+    pub repositories: usize,
+    /// Source files across the group
+    pub files: usize,
+    /// Lines of code across the group
+    pub lines_of_code: usize,
+    /// Mean documentation coverage of the group's repositories (0.0 to 1.0)
+    pub doc_coverage: f32,
+}
+
+/// Summarize repositories by group
+///
+/// Repositories without a group are left out.
+pub fn stats_by_group<'a>(
+    metadata: impl IntoIterator<Item = &'a RepositoryMetadata>,
+) -> BTreeMap<String, GroupStats> {
+    let mut groups: BTreeMap<String, GroupStats> = BTreeMap::new();
+    for metadata in metadata {
+        let Some(group) = &metadata.group else {
+            continue;
+        };
+        let stats = groups.entry(group.clone()).or_default();
+        stats.repositories += 1;
+        stats.files += metadata.file_count;
+        stats.lines_of_code += metadata.lines_of_code;
+        stats.doc_coverage += metadata.doc_coverage;
+    }
+    for stats in groups.values_mut() {
+        stats.doc_coverage /= stats.repositories as f32;
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RepositoryId;
+
+    #[test]
+    fn test_normalize_tags_lowercases_and_dedupes() {
+        let tags = ["Team-Ledger", " critical ", "team-ledger", "v1.2"].map(String::from);
+        assert_eq!(
+            normalize_tags(&tags).unwrap(),
+            vec!["team-ledger", "critical", "v1.2"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_labels_are_rejected() {
+        let long = "a".repeat(MAX_LABEL_LEN + 1);
+        for label in [
+            "",
+            "  ",
+            "-leading",
+            "has space",
+            "slash/tag",
+            "ünicode",
+            &long,
+        ] {
+            assert!(normalize_label(label, "tag").is_err(), "{:?}", label);
+        }
+        let err = normalize_label("a b", "group").unwrap_err();
+        assert!(err.to_string().contains("group 'a b'"), "{}", err);
+        assert!(normalize_label(&"a".repeat(MAX_LABEL_LEN), "tag").is_ok());
+    }
+
+    #[test]
+    fn test_filter_matches_all_criteria() {
+        let tags = vec!["critical".to_string()];
+        assert!(RepositoryFilter::default().matches(None, &[]));
+
+        let filter = RepositoryFilter {
+            group: Some("Payments".to_string()),
+            tag: Some("critical".to_string()),
+        }
+        .normalized()
+        .unwrap();
+        assert!(filter.matches(Some("payments"), &tags));
+        assert!(!filter.matches(Some("payments"), &[]));
+        assert!(!filter.matches(Some("search"), &tags));
+
+        let invalid = RepositoryFilter {
+            group: Some("a b".to_string()),
+            tag: None,
+        };
+        assert!(invalid.normalized().is_err());
+    }
+
+    #[test]
+    fn test_stats_by_group() {
+        let metadata = |name: &str, group: Option<&str>, coverage: f32| {
+            let mut metadata = RepositoryMetadata::new(RepositoryId::from(name));
+            metadata.group = group.map(String::from);
+            metadata.doc_coverage = coverage;
+            metadata.file_count = 10;
+            metadata.lines_of_code = 100;
+            metadata
+        };
+        let repos = [
+            metadata("a", Some("payments"), 0.5),
+            metadata("b", Some("payments"), 1.0),
+            metadata("c", Some("search"), 0.2),
+            metadata("d", None, 0.0),
+        ];
+
+        let groups = stats_by_group(&repos);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups["payments"],
+            GroupStats {
+                repositories: 2,
+                files: 20,
+                lines_of_code: 200,
+                doc_coverage: 0.75,
+            }
+        );
+        assert_eq!(groups["search"].repositories, 1);
+    }
+}
@@ -3,19 +3,33 @@
 use crate::{
     config::{RepositoryConfig, XzeConfig},
     error::{Result, XzeError},
-    repository::{ChangeAnalysis, Repository},
+    repository::{
+        labels, ChangeAnalysis, GroupStats, Repository, RepositoryFilter, RepositoryMetadata,
+    },
     types::{ProgrammingLanguage, RepositoryId},
 };
+use chrono::Utc;
 use git2::{Cred, FetchOptions, Oid, RemoteCallbacks, Repository as GitRepository};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+/// Directory under the cache directory holding persisted repository metadata
+const METADATA_DIR: &str = ".metadata";
+
+/// Get the commit checked out in the git working tree at `path`
+pub fn head_commit(path: &Path) -> Result<String> {
+    let git_repo = GitRepository::open(path).map_err(XzeError::Git)?;
+    let head = git_repo.head().map_err(XzeError::Git)?;
+    let commit = head.peel_to_commit().map_err(XzeError::Git)?;
+    Ok(commit.id().to_string())
+}
+
 /// Repository manager handles cloning, updating, and managing repositories
 #[derive(Debug)]
 pub struct RepositoryManager {
@@ -98,13 +112,86 @@ impl RepositoryManager {
 
         // Create repository instance
         let mut repo = Repository::new(repo_id, config.url.to_string(), local_path, language);
+        repo.metadata.group = config.normalized_group()?;
+        repo.metadata.tags = config.normalized_tags()?;
 
         // Update metadata
         self.update_repository_metadata(&mut repo).await?;
 
+        // Restore the last analyzed commit from previous runs
+        if let Some(stored) = self.load_metadata(&repo.id)? {
+            repo.metadata.last_commit = stored.last_commit;
+            repo.metadata.last_analyzed = stored.last_analyzed;
+        }
+
         Ok(repo)
     }
 
+    /// Get the commit currently checked out in a repository
+    pub fn head_commit(&self, repo: &Repository) -> Result<String> {
+        head_commit(&repo.local_path)
+    }
+
+    /// Record a successful analysis of `commit`
+    ///
+    /// Persists `last_commit` and `last_analyzed` under the cache directory so
+    /// later runs can skip repositories whose HEAD has not moved. Call this
+    /// only after analysis and generation have succeeded.
+    pub async fn mark_analyzed(&self, repo_id: &RepositoryId, commit: &str) -> Result<()> {
+        let mut metadata = self
+            .load_metadata(repo_id)?
+            .unwrap_or_else(|| RepositoryMetadata::new(repo_id.clone()));
+        metadata.last_commit = Some(commit.to_string());
+        metadata.last_analyzed = Some(Utc::now());
+
+        {
+            let mut repositories = self.repositories.write().await;
+            if let Some(repo) = repositories.get_mut(repo_id) {
+                metadata.language = repo.metadata.language.clone();
+                metadata.file_count = repo.metadata.file_count;
+                metadata.lines_of_code = repo.metadata.lines_of_code;
+                repo.metadata.last_commit = metadata.last_commit.clone();
+                repo.metadata.last_analyzed = metadata.last_analyzed;
+            }
+        }
+
+        self.save_metadata(&metadata)
+    }
+
+    /// Path of the persisted metadata file for a repository
+    fn metadata_path(&self, repo_id: &RepositoryId) -> PathBuf {
+        self.cache_dir
+            .join(METADATA_DIR)
+            .join(format!("{}.json", repo_id))
+    }
+
+    /// Load persisted metadata, if any
+    fn load_metadata(&self, repo_id: &RepositoryId) -> Result<Option<RepositoryMetadata>> {
+        let path = self.metadata_path(repo_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            XzeError::filesystem(format!("Failed to read metadata {:?}: {}", path, e))
+        })?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Persist metadata for a repository
+    fn save_metadata(&self, metadata: &RepositoryMetadata) -> Result<()> {
+        let path = self.metadata_path(&metadata.name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                XzeError::filesystem(format!("Failed to create metadata directory: {}", e))
+            })?;
+        }
+
+        std::fs::write(&path, serde_json::to_string_pretty(metadata)?).map_err(|e| {
+            XzeError::filesystem(format!("Failed to write metadata {:?}: {}", path, e))
+        })
+    }
+
     /// Clone a repository
     async fn clone_repository(&self, config: &RepositoryConfig, local_path: &Path) -> Result<()> {
         info!("Cloning repository {} to {:?}", config.url, local_path);
@@ -164,6 +251,19 @@ impl RepositoryManager {
                 .fetch(&[] as &[&str], Some(&mut fetch_options), None)
                 .map_err(XzeError::Git)?;
 
+            // Move the cached checkout to the fetched branch head so HEAD
+            // reflects the remote state
+            let head = git_repo.head().map_err(XzeError::Git)?;
+            if let Some(branch) = head.shorthand() {
+                let remote_ref = format!("refs/remotes/origin/{}", branch);
+                if let Ok(target) = git_repo.refname_to_id(&remote_ref) {
+                    let object = git_repo.find_object(target, None)?;
+                    git_repo
+                        .reset(&object, git2::ResetType::Hard, None)
+                        .map_err(XzeError::Git)?;
+                }
+            }
+
             Ok(())
         })
         .await
@@ -257,7 +357,12 @@ impl RepositoryManager {
             let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
             let path = entry.path();
 
-            if path.is_file() && !repo.should_ignore_path(path) {
+            // Match ignore rules against the repository-relative path so the
+            // clone location itself never causes files to be ignored
+            let relative = repo
+                .relative_path(path)
+                .unwrap_or_else(|| path.to_path_buf());
+            if path.is_file() && !repo.should_ignore_path(&relative) {
                 file_count += 1;
 
                 // Count lines for source files
@@ -273,15 +378,6 @@ impl RepositoryManager {
         repo.metadata.update_stats(file_count, lines_of_code);
         repo.metadata.language = Some(repo.language.clone());
 
-        // Get current commit hash
-        if let Ok(git_repo) = GitRepository::open(&repo.local_path) {
-            if let Ok(head) = git_repo.head() {
-                if let Some(oid) = head.target() {
-                    repo.metadata.last_commit = Some(oid.to_string());
-                }
-            }
-        }
-
         Ok(())
     }
 
@@ -296,6 +392,7 @@ impl RepositoryManager {
                 ProgrammingLanguage::TypeScript => matches!(extension, "ts" | "tsx"),
                 ProgrammingLanguage::Java => matches!(extension, "java"),
                 ProgrammingLanguage::CSharp => matches!(extension, "cs"),
+                ProgrammingLanguage::Ruby => matches!(extension, "rb"),
                 ProgrammingLanguage::Cpp => matches!(extension, "cpp" | "cc" | "cxx"),
                 ProgrammingLanguage::C => matches!(extension, "c"),
                 _ => false,
@@ -311,6 +408,16 @@ impl RepositoryManager {
         repositories.values().cloned().collect()
     }
 
+    /// Get the cached repositories selected by `filter`
+    pub async fn find_repositories(&self, filter: &RepositoryFilter) -> Vec<Repository> {
+        let repositories = self.repositories.read().await;
+        repositories
+            .values()
+            .filter(|r| filter.matches(r.metadata.group.as_deref(), &r.metadata.tags))
+            .cloned()
+            .collect()
+    }
+
     /// Remove a repository from cache
     pub async fn remove_repository(&self, repo_id: &RepositoryId) -> Result<()> {
         let mut repositories = self.repositories.write().await;
@@ -374,6 +481,7 @@ impl RepositoryManager {
             total_files,
             total_lines_of_code: total_lines,
             language_distribution,
+            groups: labels::stats_by_group(repositories.values().map(|r| &r.metadata)),
             cache_size_mb: self.calculate_cache_size().unwrap_or(0),
         }
     }
@@ -465,6 +573,8 @@ pub struct RepositoryManagerStats {
     pub total_files: usize,
     pub total_lines_of_code: usize,
     pub language_distribution: HashMap<ProgrammingLanguage, usize>,
+    /// Totals and mean coverage for each repository group
+    pub groups: BTreeMap<String, GroupStats>,
     pub cache_size_mb: usize,
 }
 
@@ -518,6 +628,53 @@ mod tests {
         assert!(all_repos.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_find_repositories_and_group_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager =
+            RepositoryManager::new(temp_dir.path().to_path_buf(), XzeConfig::default()).unwrap();
+
+        let add = |name: &str, group: Option<&str>, tags: &[&str], coverage: f32| {
+            let mut repo = Repository::new(
+                RepositoryId::from(name),
+                format!("https://github.com/example/{}", name),
+                temp_dir.path().join(name),
+                ProgrammingLanguage::Rust,
+            );
+            repo.metadata.group = group.map(String::from);
+            repo.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+            repo.metadata.doc_coverage = coverage;
+            (repo.id.clone(), repo)
+        };
+        manager.repositories.write().await.extend([
+            add("billing", Some("payments"), &["critical"], 0.8),
+            add("ledger", Some("payments"), &[], 0.4),
+            add("search", None, &["critical"], 0.1),
+        ]);
+
+        let filter = |group: Option<&str>, tag: Option<&str>| RepositoryFilter {
+            group: group.map(String::from),
+            tag: tag.map(String::from),
+        };
+        assert_eq!(
+            manager
+                .find_repositories(&filter(Some("payments"), None))
+                .await
+                .len(),
+            2
+        );
+        let critical = manager
+            .find_repositories(&filter(Some("payments"), Some("critical")))
+            .await;
+        assert_eq!(critical.len(), 1);
+        assert_eq!(critical[0].id, RepositoryId::from("billing"));
+
+        let stats = manager.get_stats().await;
+        assert_eq!(stats.groups.len(), 1);
+        assert_eq!(stats.groups["payments"].repositories, 2);
+        assert!((stats.groups["payments"].doc_coverage - 0.6).abs() < 1e-6);
+    }
+
     #[test]
     fn test_source_file_detection() {
         let temp_dir = TempDir::new().unwrap();
@@ -529,4 +686,47 @@ mod tests {
         assert!(manager.is_source_file(Path::new("main.py"), &ProgrammingLanguage::Python));
         assert!(!manager.is_source_file(Path::new("README.md"), &ProgrammingLanguage::Rust));
     }
+
+    #[tokio::test]
+    async fn test_mark_analyzed_persists_last_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_id = RepositoryId::from("service");
+
+        let manager =
+            RepositoryManager::new(temp_dir.path().to_path_buf(), XzeConfig::default()).unwrap();
+        assert!(manager.load_metadata(&repo_id).unwrap().is_none());
+        manager.mark_analyzed(&repo_id, "abc123").await.unwrap();
+
+        // A fresh manager over the same cache sees the recorded commit
+        let manager =
+            RepositoryManager::new(temp_dir.path().to_path_buf(), XzeConfig::default()).unwrap();
+        let metadata = manager.load_metadata(&repo_id).unwrap().unwrap();
+        assert!(metadata.is_up_to_date("abc123"));
+        assert!(!metadata.is_up_to_date("def456"));
+        assert!(metadata.last_analyzed.is_some());
+    }
+
+    #[test]
+    fn test_head_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager =
+            RepositoryManager::new(temp_dir.path().to_path_buf(), XzeConfig::default()).unwrap();
+
+        let repo_dir = temp_dir.path().join("service");
+        let git_repo = GitRepository::init(&repo_dir).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = git_repo.index().unwrap().write_tree().unwrap();
+        let tree = git_repo.find_tree(tree_id).unwrap();
+        let oid = git_repo
+            .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+
+        let repo = Repository::new(
+            RepositoryId::from("service"),
+            "https://example.com/service.git".to_string(),
+            repo_dir,
+            ProgrammingLanguage::Rust,
+        );
+        assert_eq!(manager.head_commit(&repo).unwrap(), oid.to_string());
+    }
 }
@@ -2,7 +2,9 @@
 
 use crate::{
     error::{Result, XzeError},
-    types::{ChangeSeverity, ChangeType, FileChange, ProgrammingLanguage, RepositoryId},
+    types::{
+        ChangeSeverity, ChangeType, DiátaxisCategory, FileChange, ProgrammingLanguage, RepositoryId,
+    },
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -14,13 +16,37 @@ use std::{
 use walkdir::WalkDir;
 
 pub mod analyzer;
+pub mod annotations;
+pub mod call_graph;
+pub mod config_keys;
+pub mod coverage;
+pub mod diff;
+pub mod labels;
 pub mod manager;
+pub mod outcome;
 pub mod parser;
+pub mod redact;
+pub mod rust_ast;
+pub mod schema;
+pub mod snapshot;
+pub mod staged;
+pub mod stats;
+pub mod warnings;
 
 // Re-export commonly used types
 pub use analyzer::LanguageAnalyzer;
-pub use manager::RepositoryManager;
+pub use annotations::{extract_annotations, AnnotationCounts, AnnotationKind, CodeAnnotation};
+pub use config_keys::{extract_config_file_keys, extract_env_vars, ConfigKey, ConfigKeyKind};
+pub use coverage::{CoveragePolicy, CoverageReport, DocGap, DocItemKind};
+pub use diff::{DiffChange, DiffItemKind, ItemDiff, StructureDiff};
+pub use labels::{GroupStats, RepositoryFilter};
+pub use manager::{head_commit, RepositoryManager};
+pub use outcome::{analyze_repository, AnalysisOutcome, EmptyAnalysis, EmptyReason};
 pub use parser::CodeParser;
+pub use redact::PathRedaction;
+pub use schema::ANALYSIS_SCHEMA_VERSION;
+pub use stats::{LanguageStats, RepositoryStats, HIGH_TODO_DENSITY};
+pub use warnings::{AnalysisWarning, WarningKind, WarningSeverity};
 
 // Import struct definitions
 mod r#struct;
@@ -72,50 +98,27 @@ impl Repository {
     }
 
     /// Update the structure with new analysis
+    ///
+    /// Documentation coverage is measured with the default policy.
     pub fn update_structure(&mut self, structure: CodeStructure) {
+        self.update_structure_with_policy(structure, &CoveragePolicy::default());
+    }
+
+    /// Update the structure, measuring coverage with `policy`
+    pub fn update_structure_with_policy(
+        &mut self,
+        structure: CodeStructure,
+        policy: &CoveragePolicy,
+    ) {
         self.structure = structure;
         self.metadata.last_analyzed = Some(Utc::now());
-        self.update_doc_coverage();
+        self.metadata.doc_coverage = self.coverage_report(policy).coverage();
     }
 
-    /// Calculate documentation coverage based on structure
-    fn update_doc_coverage(&mut self) {
-        let total_items = self.structure.item_count();
-        if total_items == 0 {
-            self.metadata.doc_coverage = 0.0;
-            return;
-        }
-
-        let documented_items = self.count_documented_items();
-        self.metadata.doc_coverage = documented_items as f32 / total_items as f32;
-    }
-
-    /// Count items that have documentation
-    fn count_documented_items(&self) -> usize {
-        let mut count = 0;
-
-        count += self
-            .structure
-            .modules
-            .iter()
-            .filter(|m| m.documentation.is_some())
-            .count();
-
-        count += self
-            .structure
-            .functions
-            .iter()
-            .filter(|f| f.documentation.is_some())
-            .count();
-
-        count += self
-            .structure
-            .types
-            .iter()
-            .filter(|t| t.documentation.is_some())
-            .count();
-
-        count
+    /// Measure documentation coverage of the current structure
+    pub fn coverage_report(&self, policy: &CoveragePolicy) -> CoverageReport {
+        self.structure
+            .coverage_report(policy.required_for(&self.language))
     }
 
     /// Get files matching specific patterns
@@ -155,6 +158,7 @@ impl Repository {
             ProgrammingLanguage::TypeScript => vec!["**/*.ts", "**/*.tsx"],
             ProgrammingLanguage::Java => vec!["**/*.java"],
             ProgrammingLanguage::CSharp => vec!["**/*.cs"],
+            ProgrammingLanguage::Ruby => vec!["**/*.rb"],
             ProgrammingLanguage::Cpp => vec!["**/*.cpp", "**/*.cc", "**/*.cxx"],
             ProgrammingLanguage::C => vec!["**/*.c"],
             _ => vec!["**/*"],
@@ -219,12 +223,22 @@ impl Repository {
             .map(|p| p.to_path_buf())
     }
 
+    /// Rewrite the paths in the analyzed structure relative to the
+    /// repository root
+    pub fn redact_paths(&mut self, mode: PathRedaction) {
+        let root = self.local_path.clone();
+        self.structure.redact_paths(&root, mode);
+    }
+
     /// Check if a path should be ignored
+    ///
+    /// Directory names are matched against whole path components, so
+    /// `target/debug/x` is ignored while `src/targeting.rs` is not. Both `/`
+    /// and `\` are treated as separators regardless of platform, and file
+    /// patterns are matched against the file name only.
     pub fn should_ignore_path(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-
         // Common directories to ignore
-        let ignore_dirs = [
+        const IGNORE_DIRS: [&str; 13] = [
             "target",
             "build",
             "dist",
@@ -240,14 +254,8 @@ impl Repository {
             "third_party",
         ];
 
-        for ignore_dir in &ignore_dirs {
-            if path_str.contains(ignore_dir) {
-                return true;
-            }
-        }
-
         // Common file patterns to ignore
-        let ignore_patterns = [
+        const IGNORE_PATTERNS: [&str; 10] = [
             "*.log",
             "*.tmp",
             "*.temp",
@@ -260,15 +268,25 @@ impl Repository {
             "*.min.css",
         ];
 
-        for pattern in &ignore_patterns {
-            if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
-                if glob_pattern.matches(&path_str) {
-                    return true;
-                }
-            }
+        let path_str = path.to_string_lossy();
+        let components: Vec<&str> = path_str
+            .split(['/', '\\'])
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect();
+
+        if components.iter().any(|c| IGNORE_DIRS.contains(c)) {
+            return true;
         }
 
-        false
+        let Some(file_name) = components.last() else {
+            return false;
+        };
+
+        IGNORE_PATTERNS.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .map(|p| p.matches(file_name))
+                .unwrap_or(false)
+        })
     }
 }
 
@@ -291,6 +309,12 @@ pub struct RepositoryMetadata {
     pub lines_of_code: usize,
     /// Custom metadata
     pub custom: HashMap<String, String>,
+    /// Group the repository belongs to, from its configuration
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Tags from the repository configuration
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl RepositoryMetadata {
@@ -305,6 +329,8 @@ impl RepositoryMetadata {
             file_count: 0,
             lines_of_code: 0,
             custom: HashMap::new(),
+            group: None,
+            tags: Vec::new(),
         }
     }
 
@@ -314,6 +340,11 @@ impl RepositoryMetadata {
         self.lines_of_code = lines_of_code;
     }
 
+    /// Check whether `head` is the commit that was last analyzed
+    pub fn is_up_to_date(&self, head: &str) -> bool {
+        self.last_commit.as_deref() == Some(head)
+    }
+
     /// Set custom metadata
     pub fn set_custom(&mut self, key: String, value: String) {
         self.custom.insert(key, value);
@@ -479,6 +510,38 @@ impl ChangeAnalysis {
         })
     }
 
+    /// Documentation categories the changes make stale
+    ///
+    /// Major changes affect every category. API and configuration changes
+    /// affect the reference and how-to guides, and any other source change
+    /// the reference. Entries of [`affected_docs`](Self::affected_docs) that
+    /// name a category add it. Patch-level changes to other files, such as
+    /// documentation typos, affect nothing.
+    ///
+    /// Categories are returned in [`DiátaxisCategory::ALL`] order.
+    pub fn affected_categories(&self) -> Vec<DiátaxisCategory> {
+        let mut affected: Vec<DiátaxisCategory> = self
+            .affected_docs
+            .iter()
+            .filter_map(|doc| doc.parse().ok())
+            .collect();
+
+        if self.change_significance == ChangeSeverity::Major {
+            affected.extend(DiátaxisCategory::ALL);
+        }
+        if self.affects_api() || self.affects_config() {
+            affected.extend([DiátaxisCategory::Reference, DiátaxisCategory::HowTo]);
+        }
+        if self.change_significance == ChangeSeverity::Minor {
+            affected.push(DiátaxisCategory::Reference);
+        }
+
+        DiátaxisCategory::ALL
+            .into_iter()
+            .filter(|category| affected.contains(category))
+            .collect()
+    }
+
     /// Get total lines changed
     pub fn total_lines_changed(&self) -> usize {
         self.changed_files
@@ -521,6 +584,43 @@ mod tests {
         assert!(!repo.exists()); // Directory is empty
     }
 
+    #[test]
+    fn test_update_structure_uses_language_policy() {
+        let mut repo = Repository::new(
+            RepositoryId::from("service"),
+            String::new(),
+            PathBuf::from("service"),
+            ProgrammingLanguage::Go,
+        );
+        let mut structure = CodeStructure::new();
+        structure.functions.push(Function {
+            name: "helper".to_string(),
+            signature: "func helper()".to_string(),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Private,
+            is_async: false,
+        });
+        structure.modules.push(Module {
+            name: "main".to_string(),
+            path: PathBuf::from("main.go"),
+            documentation: Some("Package main".to_string()),
+            visibility: Visibility::Public,
+        });
+
+        let mut policy = CoveragePolicy::default();
+        repo.update_structure_with_policy(structure.clone(), &policy);
+        assert_eq!(repo.metadata.doc_coverage, 1.0);
+
+        policy.languages.insert(
+            "go".to_string(),
+            vec![DocItemKind::Module, DocItemKind::PrivateFunction],
+        );
+        repo.update_structure_with_policy(structure, &policy);
+        assert_eq!(repo.metadata.doc_coverage, 0.5);
+    }
+
     #[test]
     fn test_documentation_analysis() {
         let mut analysis = DocumentationAnalysis::new();
@@ -552,6 +652,42 @@ mod tests {
 
         assert_eq!(analysis.change_significance, ChangeSeverity::Major);
         assert!(analysis.affects_config());
+        assert_eq!(analysis.affected_categories(), DiátaxisCategory::ALL);
+    }
+
+    #[test]
+    fn test_change_analysis_affected_categories() {
+        let change = |path: &str| FileChange {
+            path: path.to_string(),
+            change_type: ChangeType::Modified,
+            lines_added: 1,
+            lines_removed: 1,
+        };
+
+        let mut analysis = ChangeAnalysis::new();
+        analysis.add_file_change(change("docs/guide.md"));
+        assert!(analysis.affected_categories().is_empty());
+
+        analysis.affected_docs.push("explanation".to_string());
+        analysis.affected_docs.push("changelog".to_string());
+        assert_eq!(
+            analysis.affected_categories(),
+            vec![DiátaxisCategory::Explanation]
+        );
+
+        let mut analysis = ChangeAnalysis::new();
+        analysis.add_file_change(change("config/server.yaml"));
+        assert_eq!(
+            analysis.affected_categories(),
+            vec![DiátaxisCategory::Reference, DiátaxisCategory::HowTo]
+        );
+
+        let mut analysis = ChangeAnalysis::new();
+        analysis.add_file_change(change("src/parser.rs"));
+        assert_eq!(
+            analysis.affected_categories(),
+            vec![DiátaxisCategory::Reference]
+        );
     }
 
     #[test]
@@ -607,4 +743,40 @@ mod tests {
         assert!(repo.should_ignore_path(Path::new("file.log")));
         assert!(!repo.should_ignore_path(Path::new("src/main.rs")));
     }
+
+    #[test]
+    fn test_should_ignore_path_matches_components_not_substrings() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::new(
+            RepositoryId::from("test"),
+            "test".to_string(),
+            temp_dir.path().to_path_buf(),
+            ProgrammingLanguage::Rust,
+        );
+
+        assert!(!repo.should_ignore_path(Path::new("src/targeting.rs")));
+        assert!(!repo.should_ignore_path(Path::new("src/my_target.rs")));
+        assert!(!repo.should_ignore_path(Path::new("targeting/lib.rs")));
+        assert!(!repo.should_ignore_path(Path::new("src/build_info.rs")));
+        assert!(repo.should_ignore_path(Path::new("./target/debug/x")));
+        assert!(repo.should_ignore_path(Path::new("crates/core/target")));
+        assert!(repo.should_ignore_path(Path::new("logs/app.log")));
+    }
+
+    #[test]
+    fn test_should_ignore_path_windows_separators() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::new(
+            RepositoryId::from("test"),
+            "test".to_string(),
+            temp_dir.path().to_path_buf(),
+            ProgrammingLanguage::Rust,
+        );
+
+        assert!(repo.should_ignore_path(Path::new(r"target\debug\x.exe")));
+        assert!(repo.should_ignore_path(Path::new(r"C:\repo\node_modules\pkg\index.js")));
+        assert!(repo.should_ignore_path(Path::new(r"src\generated\bundle.min.js")));
+        assert!(!repo.should_ignore_path(Path::new(r"src\targeting.rs")));
+        assert!(!repo.should_ignore_path(Path::new(r"C:\repo\src\main.rs")));
+    }
 }
@@ -0,0 +1,268 @@
+//! Detecting repositories with nothing to document
+//!
+//! An empty checkout, a docs-only directory or a mistyped path analyzes to an
+//! empty [`CodeStructure`]. Generating documentation from it wastes model
+//! calls on vacuous output, so [`analyze_repository`] reports such
+//! directories as [`AnalysisOutcome::Empty`] instead, with the reason and
+//! the languages that were seen:
+//!
+//! - [`EmptyReason::NoSupportedSource`]: no file is in a language with a
+//!   dedicated analyzer (Rust, Go, Python, JavaScript, TypeScript, Java, C#
+//!   or Ruby)
+//! - [`EmptyReason::NoItems`]: source files exist but contain no modules,
+//!   functions or types
+//!
+//! Configuration files alone do not count as source. Hidden directories,
+//! such as `.git`, are not searched.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::repository::outcome::{analyze_repository, AnalysisOutcome, EmptyReason};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! std::fs::write(dir.path().join("README.md"), "# Notes\n").unwrap();
+//!
+//! match analyze_repository(dir.path(), None).unwrap() {
+//!     AnalysisOutcome::Empty(empty) => {
+//!         assert_eq!(empty.reason, EmptyReason::NoSupportedSource);
+//!         assert_eq!(
+//!             empty.to_string(),
+//!             "no supported source files found; detected languages: Markdown"
+//!         );
+//!     }
+//!     AnalysisOutcome::Analyzed { .. } => unreachable!(),
+//! }
+//! ```
+
+use super::{analyzer::AnalyzerFactory, CodeStructure};
+use crate::{
+    error::{Result, XzeError},
+    types::ProgrammingLanguage,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::Path};
+use walkdir::WalkDir;
+
+/// Why a repository has nothing to document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyReason {
+    /// No file is in a language with a dedicated analyzer
+    NoSupportedSource,
+    /// Source files contain no modules, functions or types
+    NoItems,
+}
+
+impl fmt::Display for EmptyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            EmptyReason::NoSupportedSource => "no supported source files found",
+            EmptyReason::NoItems => "no modules, functions or types found",
+        };
+        f.pad(reason)
+    }
+}
+
+/// Details of a repository with nothing to document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmptyAnalysis {
+    /// Why there is nothing to document
+    pub reason: EmptyReason,
+    /// Languages of the files found, by extension, in name order
+    pub detected_languages: Vec<ProgrammingLanguage>,
+    /// Number of files found
+    pub files: usize,
+}
+
+impl fmt::Display for EmptyAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let languages = if self.detected_languages.is_empty() {
+            "none".to_string()
+        } else {
+            self.detected_languages
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(f, "{}; detected languages: {}", self.reason, languages)
+    }
+}
+
+/// Result of analyzing a repository
+#[derive(Debug, Clone)]
+pub enum AnalysisOutcome {
+    /// Source was found and analyzed
+    Analyzed {
+        /// Language the analyzer was chosen for
+        language: ProgrammingLanguage,
+        /// The analyzed structure
+        structure: CodeStructure,
+    },
+    /// Nothing to document
+    Empty(EmptyAnalysis),
+}
+
+impl AnalysisOutcome {
+    /// The analyzed language and structure
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error describing the empty repository
+    pub fn into_analyzed(self) -> Result<(ProgrammingLanguage, CodeStructure)> {
+        match self {
+            AnalysisOutcome::Analyzed {
+                language,
+                structure,
+            } => Ok((language, structure)),
+            AnalysisOutcome::Empty(empty) => Err(XzeError::validation(format!(
+                "Nothing to analyze: {}",
+                empty
+            ))),
+        }
+    }
+}
+
+/// Analyze `repo_path`, reporting directories with nothing to document
+///
+/// Uses `language` when given and the detected primary language otherwise.
+/// The analyzer only runs when supported source files are present.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be walked or the analyzer fails
+pub fn analyze_repository(
+    repo_path: &Path,
+    language: Option<ProgrammingLanguage>,
+) -> Result<AnalysisOutcome> {
+    let mut files = 0;
+    let mut source_files = 0;
+    let mut detected_languages: Vec<ProgrammingLanguage> = Vec::new();
+    let entries = WalkDir::new(repo_path)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'));
+    for entry in entries {
+        let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        files += 1;
+        let Some(extension) = entry.path().extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        let detected = ProgrammingLanguage::from(extension);
+        if matches!(detected, ProgrammingLanguage::Unknown(_)) {
+            continue;
+        }
+        if AnalyzerFactory::analyzer_name(&detected) != "generic" {
+            source_files += 1;
+        }
+        if !detected_languages.contains(&detected) {
+            detected_languages.push(detected);
+        }
+    }
+    detected_languages.sort_by_key(|l| l.to_string());
+
+    let empty = |reason| {
+        Ok(AnalysisOutcome::Empty(EmptyAnalysis {
+            reason,
+            detected_languages: detected_languages.clone(),
+            files,
+        }))
+    };
+    if source_files == 0 {
+        return empty(EmptyReason::NoSupportedSource);
+    }
+
+    let (language, analyzer) = match language {
+        Some(language) => {
+            let analyzer = AnalyzerFactory::create_analyzer(&language);
+            (language, analyzer)
+        }
+        None => AnalyzerFactory::auto_detect_analyzer(repo_path)?,
+    };
+    let structure = analyzer.analyze(repo_path)?;
+    if structure.modules.is_empty() && structure.functions.is_empty() && structure.types.is_empty()
+    {
+        return empty(EmptyReason::NoItems);
+    }
+
+    Ok(AnalysisOutcome::Analyzed {
+        language,
+        structure,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn expect_empty(path: &Path, language: Option<ProgrammingLanguage>) -> EmptyAnalysis {
+        match analyze_repository(path, language).unwrap() {
+            AnalysisOutcome::Empty(empty) => empty,
+            AnalysisOutcome::Analyzed { structure, .. } => {
+                panic!("expected empty, found {} items", structure.item_count())
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".git/objects")).unwrap();
+        fs::write(dir.path().join(".git/objects/main.rs"), "fn main() {}").unwrap();
+
+        let empty = expect_empty(dir.path(), None);
+        assert_eq!(empty.reason, EmptyReason::NoSupportedSource);
+        assert_eq!(empty.files, 0);
+        assert_eq!(
+            empty.to_string(),
+            "no supported source files found; detected languages: none"
+        );
+        let err = AnalysisOutcome::Empty(empty).into_analyzed().unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_docs_only_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/guide.md"), "# Guide\n").unwrap();
+        fs::write(dir.path().join("mkdocs.yml"), "site_name: x\n").unwrap();
+
+        let empty = expect_empty(dir.path(), None);
+        assert_eq!(empty.reason, EmptyReason::NoSupportedSource);
+        assert_eq!(empty.files, 2);
+        assert_eq!(
+            empty.detected_languages,
+            [ProgrammingLanguage::Markdown, ProgrammingLanguage::Yaml]
+        );
+    }
+
+    #[test]
+    fn test_source_without_items() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("empty.py"), "# nothing here\n").unwrap();
+
+        let empty = expect_empty(dir.path(), Some(ProgrammingLanguage::Python));
+        assert_eq!(empty.reason, EmptyReason::NoItems);
+        let json = serde_json::to_value(&empty).unwrap();
+        assert_eq!(json["reason"], "no_items");
+    }
+
+    #[test]
+    fn test_source_is_analyzed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub fn run() {}\n").unwrap();
+
+        let (language, structure) = analyze_repository(dir.path(), None)
+            .unwrap()
+            .into_analyzed()
+            .unwrap();
+        assert_eq!(language, ProgrammingLanguage::Rust);
+        assert_eq!(structure.functions[0].name, "run");
+    }
+}
@@ -0,0 +1,290 @@
+//! Path redaction for published analysis output
+//!
+//! Analyzers record the paths they read as given, which for a checked-out
+//! repository means absolute paths such as `/home/alice/src/billing/src/lib.rs`.
+//! Those leak the host layout and user names when analysis output is served
+//! or shared. Redaction rewrites every path in a [`CodeStructure`] relative
+//! to the repository root:
+//!
+//! - [`PathRedaction::None`] keeps paths as they are
+//! - [`PathRedaction::Relative`] makes them repository-relative, `src/lib.rs`
+//! - [`PathRedaction::Hashed`] also prefixes a short hash of the root,
+//!   `3f2a9c1d/src/lib.rs`, so output from several checkouts stays
+//!   distinguishable without naming them
+//!
+//! Paths outside the root are reduced to their file name. Root prefixes
+//! embedded in warning messages are rewritten the same way. Functions and
+//! types carry no paths and are left unchanged.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::{Path, PathBuf};
+//! use xze_core::repository::{redact::PathRedaction, CodeStructure, Module, Visibility};
+//!
+//! let mut structure = CodeStructure::new();
+//! structure.modules.push(Module {
+//!     name: "lib".to_string(),
+//!     path: PathBuf::from("/home/alice/billing/src/lib.rs"),
+//!     documentation: None,
+//!     visibility: Visibility::Public,
+//! });
+//!
+//! structure.redact_paths(Path::new("/home/alice/billing"), PathRedaction::Relative);
+//! assert_eq!(structure.modules[0].path, PathBuf::from("src/lib.rs"));
+//! ```
+
+use super::CodeStructure;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Number of hex characters of the root hash used by [`PathRedaction::Hashed`]
+pub const ROOT_HASH_LEN: usize = 8;
+
+/// How paths in analysis output are rewritten
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathRedaction {
+    /// Keep paths as the analyzer recorded them
+    #[default]
+    None,
+    /// Make paths relative to the repository root
+    Relative,
+    /// Make paths relative and prefix them with a hash of the root
+    Hashed,
+}
+
+impl fmt::Display for PathRedaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PathRedaction::None => "none",
+            PathRedaction::Relative => "relative",
+            PathRedaction::Hashed => "hashed",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for PathRedaction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(PathRedaction::None),
+            "relative" => Ok(PathRedaction::Relative),
+            "hashed" => Ok(PathRedaction::Hashed),
+            other => Err(format!(
+                "Unknown path redaction '{}': expected none, relative or hashed",
+                other
+            )),
+        }
+    }
+}
+
+impl PathRedaction {
+    /// How to name the repository root itself, for example as the source
+    /// of statistics
+    ///
+    /// The root is kept as is, reduced to its directory name or replaced by
+    /// its hash.
+    pub fn root_label(self, root: &Path) -> String {
+        match self {
+            PathRedaction::None => root.display().to_string(),
+            PathRedaction::Relative => root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| ".".to_string()),
+            PathRedaction::Hashed => root_hash(root),
+        }
+    }
+}
+
+/// Short, stable hash identifying a repository root
+pub fn root_hash(root: &Path) -> String {
+    let digest = Sha256::digest(root.to_string_lossy().as_bytes());
+    format!("{:x}", digest)[..ROOT_HASH_LEN].to_string()
+}
+
+/// Rewrites paths under one repository root
+#[derive(Debug, Clone)]
+pub struct PathRedactor {
+    root: PathBuf,
+    mode: PathRedaction,
+    prefix: Option<String>,
+}
+
+impl PathRedactor {
+    /// Create a redactor for paths under `root`
+    pub fn new(root: &Path, mode: PathRedaction) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            mode,
+            prefix: (mode == PathRedaction::Hashed).then(|| root_hash(root)),
+        }
+    }
+
+    /// Rewrite one path
+    ///
+    /// Relative paths are taken to be relative to the root already.
+    pub fn redact(&self, path: &Path) -> PathBuf {
+        if self.mode == PathRedaction::None {
+            return path.to_path_buf();
+        }
+        let relative = match path.strip_prefix(&self.root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) if path.is_relative() => path.to_path_buf(),
+            Err(_) => path.file_name().map(PathBuf::from).unwrap_or_default(),
+        };
+        match &self.prefix {
+            Some(prefix) => Path::new(prefix).join(relative),
+            None => relative,
+        }
+    }
+
+    /// Replace the root where it appears in free text, such as a message
+    pub fn redact_text(&self, text: &str) -> String {
+        if self.mode == PathRedaction::None {
+            return text.to_string();
+        }
+        let root = self.root.to_string_lossy();
+        let root = root.trim_end_matches(['/', '\\']);
+        if root.is_empty() {
+            return text.to_string();
+        }
+        let replacement = self.prefix.as_deref().unwrap_or(".");
+        text.replace(root, replacement)
+    }
+}
+
+impl CodeStructure {
+    /// Rewrite every path in the structure relative to `root`
+    ///
+    /// Covers modules, configuration files, warnings, annotations and
+    /// configuration keys. See the [module documentation](self) for the
+    /// available modes.
+    pub fn redact_paths(&mut self, root: &Path, mode: PathRedaction) {
+        if mode == PathRedaction::None {
+            return;
+        }
+        let redactor = PathRedactor::new(root, mode);
+        for module in &mut self.modules {
+            module.path = redactor.redact(&module.path);
+        }
+        for config in &mut self.configs {
+            config.path = redactor.redact(&config.path);
+        }
+        for warning in &mut self.warnings {
+            warning.path = warning.path.as_deref().map(|p| redactor.redact(p));
+            warning.message = redactor.redact_text(&warning.message);
+        }
+        for annotation in &mut self.annotations {
+            annotation.path = redactor.redact(&annotation.path);
+        }
+        for key in &mut self.config_keys {
+            key.path = redactor.redact(&key.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{
+        AnalysisWarning, ConfigFile, ConfigFormat, Module, Visibility, WarningKind, WarningSeverity,
+    };
+
+    fn structure(root: &Path) -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.modules.push(Module {
+            name: "lib".to_string(),
+            path: root.join("src/lib.rs"),
+            documentation: None,
+            visibility: Visibility::Public,
+        });
+        structure.configs.push(ConfigFile {
+            path: root.join("Cargo.toml"),
+            format: ConfigFormat::Toml,
+            content: String::new(),
+        });
+        structure.warnings.push(AnalysisWarning {
+            severity: WarningSeverity::Warning,
+            kind: WarningKind::UnreadableFile,
+            path: Some(root.join("src/bad.rs")),
+            message: format!("Could not read {}", root.join("src/bad.rs").display()),
+        });
+        structure
+    }
+
+    #[test]
+    fn test_relative_redaction() {
+        let root = Path::new("/home/alice/billing");
+        let mut structure = structure(root);
+        structure.redact_paths(root, PathRedaction::Relative);
+
+        assert_eq!(structure.modules[0].path, PathBuf::from("src/lib.rs"));
+        assert_eq!(structure.configs[0].path, PathBuf::from("Cargo.toml"));
+        assert_eq!(
+            structure.warnings[0].path,
+            Some(PathBuf::from("src/bad.rs"))
+        );
+        assert_eq!(structure.warnings[0].message, "Could not read ./src/bad.rs");
+        let json = serde_json::to_string(&structure).unwrap();
+        assert!(!json.contains("alice"), "{}", json);
+    }
+
+    #[test]
+    fn test_hashed_redaction() {
+        let root = Path::new("/home/alice/billing");
+        let mut structure = structure(root);
+        structure.redact_paths(root, PathRedaction::Hashed);
+
+        let hash = root_hash(root);
+        assert_eq!(hash.len(), ROOT_HASH_LEN);
+        assert_eq!(
+            structure.modules[0].path,
+            Path::new(&hash).join("src/lib.rs")
+        );
+        assert_ne!(hash, root_hash(Path::new("/home/bob/billing")));
+    }
+
+    #[test]
+    fn test_paths_outside_root_keep_file_name() {
+        let redactor = PathRedactor::new(Path::new("/repo"), PathRedaction::Relative);
+        assert_eq!(
+            redactor.redact(Path::new("/etc/secret/app.toml")),
+            PathBuf::from("app.toml")
+        );
+        assert_eq!(
+            redactor.redact(Path::new("src/main.rs")),
+            PathBuf::from("src/main.rs")
+        );
+    }
+
+    #[test]
+    fn test_none_keeps_paths() {
+        let root = Path::new("/home/alice/billing");
+        let mut structure = structure(root);
+        structure.redact_paths(root, PathRedaction::None);
+        assert_eq!(structure.modules[0].path, root.join("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("Hashed".parse(), Ok(PathRedaction::Hashed));
+        assert!("absolute".parse::<PathRedaction>().is_err());
+        assert_eq!(PathRedaction::Relative.to_string(), "relative");
+    }
+
+    #[test]
+    fn test_root_label() {
+        let root = Path::new("/home/alice/billing");
+        assert_eq!(PathRedaction::None.root_label(root), "/home/alice/billing");
+        assert_eq!(PathRedaction::Relative.root_label(root), "billing");
+        assert_eq!(PathRedaction::Hashed.root_label(root), root_hash(root));
+    }
+}
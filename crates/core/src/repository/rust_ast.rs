@@ -0,0 +1,539 @@
+//! Syntax-tree extraction of Rust items
+//!
+//! [`RustAnalyzer::with_ast_parsing`](super::analyzer::RustAnalyzer::with_ast_parsing)
+//! parses each file with `syn` instead of scanning it line by line. The
+//! syntax tree finds methods in `impl` blocks, trait method signatures,
+//! declarations spread over several lines and items behind attributes, and
+//! it is not misled by `fn` inside strings or comments.
+//!
+//! The extracted items have the same shape as the line-based parser's:
+//!
+//! - functions and methods are named without their type or module
+//! - signatures are the declaration up to the body, on one line
+//! - documentation comes from `///` comments and `#[doc]` attributes
+//! - enum variants are fields with the type annotation `variant`
+//! - methods of trait implementations are public, trait methods share the
+//!   trait's visibility
+//! - methods of `impl` blocks are also listed in [`RustItems::methods`]
+//!   with the name of their type
+//!
+//! Items in inline modules are included. Visibility other than plain `pub`,
+//! such as `pub(crate)`, is private.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::Path;
+//! use xze_core::repository::rust_ast::parse_file;
+//!
+//! let source = r#"
+//! /// A counter
+//! pub struct Counter {
+//!     count: u32,
+//! }
+//!
+//! impl Counter {
+//!     /// Add one
+//!     pub fn increment(&mut self) -> u32 {
+//!         let label = "fn fake()";
+//!         self.count += 1;
+//!         self.count
+//!     }
+//! }
+//! "#;
+//!
+//! let items = parse_file(Path::new("src/counter.rs"), source).unwrap();
+//! assert_eq!(items.types[0].name, "Counter");
+//! assert_eq!(items.functions.len(), 1);
+//! assert_eq!(items.functions[0].signature, "pub fn increment(&mut self) -> u32");
+//! assert_eq!(items.functions[0].documentation.as_deref(), Some("Add one"));
+//! ```
+
+use super::{Field, Function, Module, Parameter, TypeDefinition, TypeKind, Visibility};
+use proc_macro2::LineColumn;
+use std::path::Path;
+use syn::{
+    spanned::Spanned, Attribute, Expr, ExprLit, Fields, FnArg, ImplItem, Item, Lit, Meta, Pat,
+    ReturnType, Signature, TraitItem, Type,
+};
+
+/// Items extracted from one Rust source file
+#[derive(Debug, Clone, Default)]
+pub struct RustItems {
+    pub modules: Vec<Module>,
+    pub functions: Vec<Function>,
+    pub types: Vec<TypeDefinition>,
+    /// Methods of `impl` blocks with the name of their type; they are also
+    /// in `functions`
+    pub methods: Vec<(String, Function)>,
+    /// Zero-based line and name of each function, in line order, for
+    /// [`call_graph::record_calls`](super::call_graph::record_calls)
+    pub declarations: Vec<(usize, String)>,
+}
+
+/// Parse `content` and extract its modules, functions and types
+///
+/// # Errors
+///
+/// Returns the parse error if `content` is not valid Rust
+pub fn parse_file(path: &Path, content: &str) -> syn::Result<RustItems> {
+    let file = syn::parse_file(content)?;
+    let mut extractor = Extractor {
+        path,
+        lines: content.lines().collect(),
+        items: RustItems::default(),
+    };
+    extractor.items(&file.items);
+
+    let mut items = extractor.items;
+    items.declarations.sort_by_key(|(line, _)| *line);
+    Ok(items)
+}
+
+struct Extractor<'a> {
+    path: &'a Path,
+    lines: Vec<&'a str>,
+    items: RustItems,
+}
+
+impl Extractor<'_> {
+    fn items(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Mod(module) => {
+                    self.items.modules.push(Module {
+                        name: module.ident.to_string(),
+                        path: self.path.to_path_buf(),
+                        documentation: documentation(&module.attrs),
+                        visibility: visibility(&module.vis),
+                    });
+                    if let Some((_, content)) = &module.content {
+                        self.items(content);
+                    }
+                }
+                Item::Fn(function) => {
+                    let start = self.start_with_vis(&function.vis, &function.sig);
+                    self.function(
+                        start,
+                        &function.sig,
+                        &function.attrs,
+                        visibility(&function.vis),
+                    );
+                }
+                Item::Impl(block) => {
+                    let owner = match &*block.self_ty {
+                        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+                        _ => None,
+                    };
+                    for item in &block.items {
+                        if let ImplItem::Fn(method) = item {
+                            let visibility = if block.trait_.is_some() {
+                                Visibility::Public
+                            } else {
+                                visibility(&method.vis)
+                            };
+                            let start = self.start_with_vis(&method.vis, &method.sig);
+                            self.function(start, &method.sig, &method.attrs, visibility);
+                            if let (Some(owner), Some(function)) =
+                                (&owner, self.items.functions.last())
+                            {
+                                self.items.methods.push((owner.clone(), function.clone()));
+                            }
+                        }
+                    }
+                }
+                Item::Trait(definition) => {
+                    let trait_visibility = visibility(&definition.vis);
+                    self.items.types.push(TypeDefinition {
+                        name: definition.ident.to_string(),
+                        kind: TypeKind::Trait,
+                        documentation: documentation(&definition.attrs),
+                        fields: Vec::new(),
+                        visibility: trait_visibility,
+                        methods: Vec::new(),
+                    });
+                    for item in &definition.items {
+                        if let TraitItem::Fn(method) = item {
+                            let start = method.sig.span().start();
+                            self.function(start, &method.sig, &method.attrs, trait_visibility);
+                        }
+                    }
+                }
+                Item::Struct(definition) => {
+                    let fields = self.fields(&definition.fields);
+                    self.items.types.push(TypeDefinition {
+                        name: definition.ident.to_string(),
+                        kind: TypeKind::Struct,
+                        documentation: documentation(&definition.attrs),
+                        fields,
+                        visibility: visibility(&definition.vis),
+                        methods: Vec::new(),
+                    });
+                }
+                Item::Enum(definition) => {
+                    let fields = definition
+                        .variants
+                        .iter()
+                        .map(|variant| Field {
+                            name: variant.ident.to_string(),
+                            type_annotation: "variant".to_string(),
+                            documentation: documentation(&variant.attrs),
+                        })
+                        .collect();
+                    self.items.types.push(TypeDefinition {
+                        name: definition.ident.to_string(),
+                        kind: TypeKind::Enum,
+                        documentation: documentation(&definition.attrs),
+                        fields,
+                        visibility: visibility(&definition.vis),
+                        methods: Vec::new(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn function(
+        &mut self,
+        start: LineColumn,
+        sig: &Signature,
+        attrs: &[Attribute],
+        visibility: Visibility,
+    ) {
+        let name = sig.ident.to_string();
+        let parameters = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(typed) => Some(Parameter {
+                    name: match &*typed.pat {
+                        Pat::Ident(pat) => pat.ident.to_string(),
+                        pattern => self.source(pattern),
+                    },
+                    type_annotation: self.source(&*typed.ty),
+                    default_value: None,
+                }),
+                FnArg::Receiver(_) => None,
+            })
+            .collect();
+        let return_type = match &sig.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some(self.source(&**ty)),
+        };
+
+        let line = sig.ident.span().start().line.saturating_sub(1);
+        self.items.declarations.push((line, name.clone()));
+        self.items.functions.push(Function {
+            name,
+            signature: self.text(start, sig.span().end()),
+            documentation: documentation(attrs),
+            parameters,
+            return_type,
+            visibility,
+            is_async: sig.asyncness.is_some(),
+        });
+    }
+
+    fn fields(&self, fields: &Fields) -> Vec<Field> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| Field {
+                name: field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| index.to_string(), ToString::to_string),
+                type_annotation: self.source(&field.ty),
+                documentation: documentation(&field.attrs),
+            })
+            .collect()
+    }
+
+    /// Where a declaration starts, including its visibility
+    fn start_with_vis(&self, vis: &syn::Visibility, sig: &Signature) -> LineColumn {
+        match vis {
+            syn::Visibility::Inherited => sig.span().start(),
+            vis => vis.span().start(),
+        }
+    }
+
+    /// Source text of a syntax node on one line
+    fn source(&self, node: &impl Spanned) -> String {
+        let span = node.span();
+        self.text(span.start(), span.end())
+    }
+
+    /// Source text between two positions, with whitespace collapsed
+    fn text(&self, start: LineColumn, end: LineColumn) -> String {
+        let mut text = String::new();
+        for line_number in start.line..=end.line {
+            let Some(line) = self.lines.get(line_number.wrapping_sub(1)) else {
+                break;
+            };
+            let from = if line_number == start.line {
+                start.column
+            } else {
+                0
+            };
+            let chars = line.chars().skip(from);
+            if line_number == end.line {
+                text.extend(chars.take(end.column.saturating_sub(from)));
+            } else {
+                text.extend(chars);
+                text.push(' ');
+            }
+        }
+
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace("( ", "(")
+            .replace(" )", ")")
+            .replace(",)", ")")
+            .replace("< ", "<")
+            .replace(",>", ">")
+            .replace(", >", ">")
+    }
+}
+
+/// Documentation from `///` comments and `#[doc = "..."]` attributes
+fn documentation(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(pair) => match &pair.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(text),
+                    ..
+                }) => Some(text.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|text| {
+            text.lines()
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn visibility(vis: &syn::Visibility) -> Visibility {
+    match vis {
+        syn::Visibility::Public(_) => Visibility::Public,
+        _ => Visibility::Private,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> RustItems {
+        parse_file(Path::new("src/lib.rs"), source).unwrap()
+    }
+
+    fn function<'a>(items: &'a RustItems, name: &str) -> &'a Function {
+        items
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+            .unwrap_or_else(|| panic!("no function {}", name))
+    }
+
+    #[test]
+    fn test_impl_and_trait_methods() {
+        let items = parse(
+            r#"
+/// Things that can be shown
+pub trait Render {
+    /// Render to a string
+    fn render(&self, width: usize) -> String;
+}
+
+struct Page;
+
+impl Page {
+    pub async fn load(path: &str) -> Self {
+        Page
+    }
+
+    fn helper(&self) {}
+}
+
+impl Render for Page {
+    fn render(&self, width: usize) -> String {
+        String::new()
+    }
+}
+"#,
+        );
+
+        let names: Vec<_> = items.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["render", "load", "helper", "render"]);
+
+        let declared = &items.functions[0];
+        assert_eq!(
+            declared.signature,
+            "fn render(&self, width: usize) -> String"
+        );
+        assert_eq!(
+            declared.documentation.as_deref(),
+            Some("Render to a string")
+        );
+        assert_eq!(declared.visibility, Visibility::Public);
+        assert_eq!(declared.parameters.len(), 1);
+        assert_eq!(declared.parameters[0].name, "width");
+        assert_eq!(declared.parameters[0].type_annotation, "usize");
+
+        let load = function(&items, "load");
+        assert!(load.is_async);
+        assert_eq!(load.signature, "pub async fn load(path: &str) -> Self");
+        assert_eq!(load.return_type.as_deref(), Some("Self"));
+        assert_eq!(function(&items, "helper").visibility, Visibility::Private);
+        assert_eq!(items.functions[3].visibility, Visibility::Public);
+
+        let methods: Vec<_> = items
+            .methods
+            .iter()
+            .map(|(owner, method)| (owner.as_str(), method.name.as_str()))
+            .collect();
+        assert_eq!(
+            methods,
+            vec![("Page", "load"), ("Page", "helper"), ("Page", "render")]
+        );
+
+        assert_eq!(items.types[0].kind, TypeKind::Trait);
+        assert_eq!(
+            items.types[0].documentation.as_deref(),
+            Some("Things that can be shown")
+        );
+    }
+
+    #[test]
+    fn test_multi_line_generics_and_attributes() {
+        let items = parse(
+            r#"
+#[doc = "Merge two maps"]
+#[doc = "keeping the first value"]
+#[inline]
+pub fn merge<
+    K: Eq + std::hash::Hash,
+    V,
+>(
+    mut left: HashMap<K, V>,
+    right: HashMap<K, V>,
+) -> HashMap<K, V>
+where
+    V: Clone,
+{
+    left
+}
+"#,
+        );
+
+        let merge = function(&items, "merge");
+        assert_eq!(
+            merge.signature,
+            "pub fn merge<K: Eq + std::hash::Hash, V>(mut left: HashMap<K, V>, \
+             right: HashMap<K, V>) -> HashMap<K, V> where V: Clone,"
+        );
+        assert_eq!(
+            merge.documentation.as_deref(),
+            Some("Merge two maps\nkeeping the first value")
+        );
+        let names: Vec<_> = merge.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["left", "right"]);
+        assert_eq!(merge.return_type.as_deref(), Some("HashMap<K, V>"));
+        assert_eq!(items.declarations, vec![(4, "merge".to_string())]);
+    }
+
+    #[test]
+    fn test_fn_in_strings_and_comments_is_ignored() {
+        let items = parse(
+            r#"
+// fn commented_out() {}
+/* fn in_block_comment() {} */
+fn real() {
+    let text = "fn not_a_function() {}";
+    println!("{}", text);
+}
+"#,
+        );
+
+        let names: Vec<_> = items.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["real"]);
+    }
+
+    #[test]
+    fn test_types_and_modules() {
+        let items = parse(
+            r#"
+/// Network settings
+pub mod net {
+    /// An address
+    #[derive(Debug)]
+    pub struct Address {
+        /// Host name
+        pub host: String,
+        port: u16,
+    }
+
+    pub(crate) struct Pair(u8, Vec<u8>);
+
+    pub enum Scheme {
+        /// Plain text
+        Http,
+        Https { port: u16 },
+    }
+}
+"#,
+        );
+
+        assert_eq!(items.modules.len(), 1);
+        assert_eq!(items.modules[0].name, "net");
+        assert_eq!(
+            items.modules[0].documentation.as_deref(),
+            Some("Network settings")
+        );
+
+        let address = &items.types[0];
+        assert_eq!(address.name, "Address");
+        assert_eq!(address.documentation.as_deref(), Some("An address"));
+        assert_eq!(address.fields[0].name, "host");
+        assert_eq!(address.fields[0].type_annotation, "String");
+        assert_eq!(
+            address.fields[0].documentation.as_deref(),
+            Some("Host name")
+        );
+        assert_eq!(address.fields[1].name, "port");
+
+        let pair = &items.types[1];
+        assert_eq!(pair.visibility, Visibility::Private);
+        assert_eq!(pair.fields[0].name, "0");
+        assert_eq!(pair.fields[1].type_annotation, "Vec<u8>");
+
+        let scheme = &items.types[2];
+        assert_eq!(scheme.kind, TypeKind::Enum);
+        let variants: Vec<_> = scheme.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(variants, vec!["Http", "Https"]);
+        assert_eq!(scheme.fields[0].type_annotation, "variant");
+        assert_eq!(
+            scheme.fields[0].documentation.as_deref(),
+            Some("Plain text")
+        );
+    }
+
+    #[test]
+    fn test_invalid_source_is_an_error() {
+        assert!(parse_file(Path::new("src/lib.rs"), "fn broken( {").is_err());
+    }
+}
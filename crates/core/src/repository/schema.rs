@@ -0,0 +1,234 @@
+//! Versioned serialization of analysis output
+//!
+//! Serialized [`CodeStructure`]s carry a top-level `schema_version` and the
+//! `xze_version` that wrote them, next to the structure's own fields:
+//!
+//! ```text
+//! {"schema_version": 2, "xze_version": "0.1.0", "modules": [...], "functions": [...], ...}
+//! ```
+//!
+//! Compatibility policy:
+//!
+//! - [`ANALYSIS_SCHEMA_VERSION`] is bumped whenever a field of the output is
+//!   added, removed, renamed or changes meaning.
+//! - Each bump adds a migration from the previous version, so output from
+//!   any older version still loads.
+//! - Output from a newer version than this build supports is rejected
+//!   rather than misread.
+//! - Output without `schema_version` predates versioning and is read as
+//!   version 1.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::repository::CodeStructure;
+//!
+//! let json = CodeStructure::new().to_json().unwrap();
+//! assert!(json.contains("\"schema_version\": 2"));
+//!
+//! let structure = CodeStructure::from_json(&json).unwrap();
+//! assert!(structure.is_empty());
+//!
+//! assert!(CodeStructure::from_json(r#"{"schema_version": 99}"#).is_err());
+//! ```
+
+use super::CodeStructure;
+use crate::error::{Result, XzeError};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Current schema version of serialized analysis output
+pub const ANALYSIS_SCHEMA_VERSION: u32 = 2;
+
+/// Upgrades an output object from one schema version to the next
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migrations in order; entry `i` upgrades version `i + 1` to `i + 2`
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+
+/// Version 2 added only the version fields themselves
+fn migrate_v1_to_v2(_output: &mut Map<String, Value>) {}
+
+#[derive(Serialize)]
+struct AnalysisOutput<'a> {
+    schema_version: u32,
+    xze_version: &'a str,
+    #[serde(flatten)]
+    structure: &'a CodeStructure,
+}
+
+impl CodeStructure {
+    fn versioned(&self) -> AnalysisOutput<'_> {
+        AnalysisOutput {
+            schema_version: ANALYSIS_SCHEMA_VERSION,
+            xze_version: crate::VERSION,
+            structure: self,
+        }
+    }
+
+    /// Serialize as pretty-printed JSON with the schema and xze versions
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.versioned())?)
+    }
+
+    /// Serialize as YAML with the schema and xze versions
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(&self.versioned())?)
+    }
+
+    /// Parse JSON analysis output, migrating it from older schema versions
+    ///
+    /// # Errors
+    ///
+    /// Returns an unsupported error for output from a newer schema version,
+    /// a validation error for an invalid `schema_version`, and a JSON error
+    /// if the output does not match the schema
+    pub fn from_json(json: &str) -> Result<Self> {
+        from_output(serde_json::from_str(json)?, MIGRATIONS)
+    }
+
+    /// Parse YAML analysis output, migrating it from older schema versions
+    ///
+    /// # Errors
+    ///
+    /// As for [`from_json`](Self::from_json), with a YAML error for
+    /// malformed input
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        from_output(serde_yaml::from_str(yaml)?, MIGRATIONS)
+    }
+}
+
+fn from_output(output: Value, migrations: &[Migration]) -> Result<CodeStructure> {
+    let Value::Object(mut output) = output else {
+        return Err(XzeError::validation("Analysis output must be an object"));
+    };
+    let supported = migrations.len() + 1;
+
+    let version = match output.remove("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| usize::try_from(v).ok())
+            .filter(|&v| v >= 1)
+            .ok_or_else(|| {
+                XzeError::validation(format!("Invalid analysis schema_version {}", version))
+            })?,
+    };
+    if version > supported {
+        return Err(XzeError::unsupported(format!(
+            "Analysis schema version {} is newer than supported version {}; upgrade xze to read it",
+            version, supported
+        )));
+    }
+    output.remove("xze_version");
+
+    for migrate in &migrations[version - 1..] {
+        migrate(&mut output);
+    }
+    Ok(serde_json::from_value(Value::Object(output))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, Visibility};
+
+    fn structure() -> CodeStructure {
+        let mut structure = CodeStructure::new();
+        structure.functions.push(Function {
+            name: "run".to_string(),
+            signature: "pub fn run()".to_string(),
+            documentation: Some("Runs".to_string()),
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        });
+        structure
+    }
+
+    fn fields(structure: &CodeStructure) -> Value {
+        serde_json::to_value(structure).unwrap()
+    }
+
+    #[test]
+    fn test_migrations_cover_current_version() {
+        assert_eq!(MIGRATIONS.len() + 1, ANALYSIS_SCHEMA_VERSION as usize);
+    }
+
+    #[test]
+    fn test_round_trip_with_version_fields() {
+        let json = structure().to_json().unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], ANALYSIS_SCHEMA_VERSION);
+        assert_eq!(value["xze_version"], crate::VERSION);
+        assert_eq!(value["functions"][0]["name"], "run");
+        assert_eq!(
+            fields(&CodeStructure::from_json(&json).unwrap()),
+            fields(&structure())
+        );
+
+        let yaml = structure().to_yaml().unwrap();
+        assert!(yaml.starts_with("schema_version: 2\n"));
+        assert_eq!(
+            fields(&CodeStructure::from_yaml(&yaml).unwrap()),
+            fields(&structure())
+        );
+    }
+
+    #[test]
+    fn test_unversioned_output_is_read_as_version_1() {
+        let json = serde_json::to_string(&structure()).unwrap();
+        assert!(!json.contains("schema_version"));
+        assert_eq!(
+            fields(&CodeStructure::from_json(&json).unwrap()),
+            fields(&structure())
+        );
+    }
+
+    #[test]
+    fn test_rejects_newer_and_invalid_versions() {
+        let err = CodeStructure::from_json(r#"{"schema_version": 3}"#).unwrap_err();
+        assert!(
+            matches!(err, XzeError::UnsupportedOperation { .. }),
+            "{}",
+            err
+        );
+        for json in [
+            r#"{"schema_version": 0}"#,
+            r#"{"schema_version": "2"}"#,
+            "[]",
+        ] {
+            assert!(CodeStructure::from_json(json).is_err(), "{}", json);
+        }
+    }
+
+    /// Version 3 of a hypothetical schema renames `fns` to `functions`
+    fn rename_fns(output: &mut Map<String, Value>) {
+        if let Some(fns) = output.remove("fns") {
+            output.insert("functions".to_string(), fns);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_across_version_bump() {
+        let migrations: &[Migration] = &[migrate_v1_to_v2, rename_fns];
+
+        // Output written at version 2 in the old shape
+        let mut old = serde_json::to_value(structure().versioned()).unwrap();
+        let functions = old.as_object_mut().unwrap().remove("functions").unwrap();
+        old["fns"] = functions;
+
+        let migrated = from_output(old, migrations).unwrap();
+        assert_eq!(fields(&migrated), fields(&structure()));
+
+        // Output at the new version needs no migration
+        let mut current = serde_json::to_value(structure().versioned()).unwrap();
+        current["schema_version"] = 3.into();
+        let parsed = from_output(current.clone(), migrations).unwrap();
+        assert_eq!(fields(&parsed), fields(&structure()));
+
+        // A build that predates the bump rejects it
+        assert!(from_output(current, MIGRATIONS).is_err());
+    }
+}
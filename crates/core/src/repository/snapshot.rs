@@ -0,0 +1,426 @@
+//! Repository snapshots for reproducible analysis
+//!
+//! A snapshot captures the exact inputs to a repository analysis: the
+//! resolved file list with contents and SHA-256 hashes, the detected
+//! language, the analyzer used, the xze version, and optionally the
+//! configuration in effect. Snapshots are serialized as a portable JSON
+//! manifest so an analysis can be re-run without access to the original
+//! repository.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use xze_core::repository::snapshot::RepositorySnapshot;
+//!
+//! # fn example() -> xze_core::Result<()> {
+//! let snapshot = RepositorySnapshot::capture(Path::new("./my-repo"), None, None)?;
+//! snapshot.to_file(Path::new("snapshot.json"))?;
+//!
+//! let restored = RepositorySnapshot::from_file(Path::new("snapshot.json"))?;
+//! let structure = restored.analyze()?;
+//! println!("Found {} items", structure.item_count());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    config::XzeConfig,
+    error::{Result, XzeError},
+    kb::hash::calculate_content_hash,
+    repository::{analyzer::AnalyzerFactory, CodeStructure},
+    types::ProgrammingLanguage,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info, warn};
+use walkdir::WalkDir;
+
+/// Current snapshot manifest format version
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Files larger than this are left out of snapshots
+pub const MAX_SNAPSHOT_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Directories never included in a snapshot
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    ".svn",
+    "target",
+    "node_modules",
+    "__pycache__",
+    ".pytest_cache",
+    ".venv",
+];
+
+/// A single file recorded in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path relative to the repository root, using `/` separators
+    pub path: String,
+    /// SHA-256 hash of the file content
+    pub hash: String,
+    /// File size in bytes
+    pub size: u64,
+    /// File content
+    pub content: String,
+}
+
+/// Portable manifest of the inputs to a repository analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositorySnapshot {
+    /// Manifest format version
+    pub format_version: u32,
+    /// Version of xze that produced the snapshot
+    pub xze_version: String,
+    /// When the snapshot was captured
+    pub created_at: DateTime<Utc>,
+    /// Original repository location, for reference only
+    pub source: String,
+    /// Language used for analysis
+    pub language: ProgrammingLanguage,
+    /// Name of the analyzer used for the language
+    pub analyzer: String,
+    /// Configuration in effect, with credentials removed
+    pub config: Option<XzeConfig>,
+    /// Files in the repository, sorted by path
+    pub files: Vec<SnapshotFile>,
+}
+
+impl RepositorySnapshot {
+    /// Capture a snapshot of a local repository
+    ///
+    /// Walks the repository, skipping VCS and build directories, binary files
+    /// and files larger than [`MAX_SNAPSHOT_FILE_SIZE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `repo_path` - Path to the repository root
+    /// * `language` - Language override; auto-detected when `None`
+    /// * `config` - Configuration to record; credentials are stripped
+    ///
+    /// # Returns
+    ///
+    /// Returns the captured snapshot
+    ///
+    /// # Errors
+    ///
+    /// Returns `XzeError::NotFound` if the path does not exist, or
+    /// `XzeError::FileSystem` if the repository cannot be walked
+    pub fn capture(
+        repo_path: &Path,
+        language: Option<ProgrammingLanguage>,
+        config: Option<&XzeConfig>,
+    ) -> Result<Self> {
+        if !repo_path.is_dir() {
+            return Err(XzeError::not_found(format!(
+                "Repository path does not exist: {}",
+                repo_path.display()
+            )));
+        }
+
+        let language = match language {
+            Some(language) => language,
+            None => AnalyzerFactory::auto_detect_analyzer(repo_path)?.0,
+        };
+
+        let mut files = Vec::new();
+        let walker = WalkDir::new(repo_path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+            });
+
+        for entry in walker {
+            let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let size = entry
+                .metadata()
+                .map_err(|e| XzeError::filesystem(format!("Metadata error: {}", e)))?
+                .len();
+            if size > MAX_SNAPSHOT_FILE_SIZE {
+                debug!("Skipping large file: {}", entry.path().display());
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(entry.path()) {
+                Ok(content) => content,
+                Err(_) => {
+                    debug!("Skipping non-UTF-8 file: {}", entry.path().display());
+                    continue;
+                }
+            };
+
+            let relative = entry
+                .path()
+                .strip_prefix(repo_path)
+                .map_err(|e| XzeError::filesystem(format!("Failed to relativize path: {}", e)))?;
+            let path = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            files.push(SnapshotFile {
+                path,
+                hash: calculate_content_hash(&content),
+                size,
+                content,
+            });
+        }
+
+        let config = config.cloned().map(|mut config| {
+            config.documentation_repo.credentials = None;
+            for repo in &mut config.repositories {
+                repo.credentials = None;
+            }
+            config
+        });
+
+        info!(
+            "Captured snapshot of {} with {} files",
+            repo_path.display(),
+            files.len()
+        );
+
+        Ok(Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            xze_version: crate::VERSION.to_string(),
+            created_at: Utc::now(),
+            source: repo_path.display().to_string(),
+            analyzer: AnalyzerFactory::analyzer_name(&language).to_string(),
+            language,
+            config,
+            files,
+        })
+    }
+
+    /// Load a snapshot manifest from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if the
+    /// manifest format version is newer than this build supports
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let snapshot: Self = serde_json::from_str(&content)?;
+
+        if snapshot.format_version > SNAPSHOT_FORMAT_VERSION {
+            return Err(XzeError::unsupported(format!(
+                "Snapshot format version {} is newer than supported version {}",
+                snapshot.format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot manifest as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Check that every recorded hash matches its recorded content
+    ///
+    /// # Errors
+    ///
+    /// Returns `XzeError::Validation` naming the first file whose content
+    /// does not match its hash
+    pub fn verify(&self) -> Result<()> {
+        for file in &self.files {
+            if calculate_content_hash(&file.content) != file.hash {
+                return Err(XzeError::validation(format!(
+                    "Snapshot content for {} does not match its recorded hash",
+                    file.path
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the recorded files into a directory
+    ///
+    /// # Errors
+    ///
+    /// Returns `XzeError::Validation` if a recorded path would escape the
+    /// target directory, or a filesystem error if writing fails
+    pub fn restore(&self, target: &Path) -> Result<()> {
+        for file in &self.files {
+            let relative = PathBuf::from(&file.path);
+            if relative
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_)))
+            {
+                return Err(XzeError::validation(format!(
+                    "Snapshot contains unsafe path: {}",
+                    file.path
+                )));
+            }
+
+            let destination = target.join(relative);
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&destination, &file.content)?;
+        }
+        Ok(())
+    }
+
+    /// Re-run analysis against the recorded files
+    ///
+    /// Verifies the manifest, restores the files into a temporary directory
+    /// and analyzes them with the analyzer for the recorded language. File
+    /// paths in the result are relative to that temporary directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if verification, restoring or analysis fails
+    pub fn analyze(&self) -> Result<CodeStructure> {
+        self.verify()?;
+
+        if self.xze_version != crate::VERSION {
+            warn!(
+                "Snapshot was created by xze {} but this is xze {}; results may differ",
+                self.xze_version,
+                crate::VERSION
+            );
+        }
+
+        let workdir = tempfile::tempdir()?;
+        self.restore(workdir.path())?;
+
+        let analyzer = AnalyzerFactory::create_analyzer(&self.language);
+        analyzer.analyze(workdir.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "/// Adds numbers\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("target/debug/build.log"), "build output").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_capture_records_files_and_metadata() {
+        let repo = sample_repo();
+        let snapshot = RepositorySnapshot::capture(repo.path(), None, None).unwrap();
+
+        assert_eq!(snapshot.format_version, SNAPSHOT_FORMAT_VERSION);
+        assert_eq!(snapshot.xze_version, crate::VERSION);
+        assert_eq!(snapshot.language, ProgrammingLanguage::Rust);
+        assert_eq!(snapshot.analyzer, "rust");
+
+        let paths: Vec<&str> = snapshot.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["Cargo.toml", "src/lib.rs"]);
+        assert!(snapshot.verify().is_ok());
+    }
+
+    #[test]
+    fn test_roundtrip_and_analyze_matches_original() {
+        let repo = sample_repo();
+        let snapshot = RepositorySnapshot::capture(repo.path(), None, None).unwrap();
+
+        let manifest = TempDir::new().unwrap();
+        let manifest_path = manifest.path().join("snapshot.json");
+        snapshot.to_file(&manifest_path).unwrap();
+        let loaded = RepositorySnapshot::from_file(&manifest_path).unwrap();
+
+        let original = AnalyzerFactory::create_analyzer(&ProgrammingLanguage::Rust)
+            .analyze(repo.path())
+            .unwrap();
+        let replayed = loaded.analyze().unwrap();
+
+        let names = |s: &CodeStructure| {
+            let mut names: Vec<String> = s.functions.iter().map(|f| f.name.clone()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&original), names(&replayed));
+        assert_eq!(original.configs.len(), replayed.configs.len());
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let repo = sample_repo();
+        let mut snapshot = RepositorySnapshot::capture(repo.path(), None, None).unwrap();
+        snapshot.files[0].content.push_str("tampered");
+
+        assert!(snapshot.verify().is_err());
+        assert!(snapshot.analyze().is_err());
+    }
+
+    #[test]
+    fn test_restore_rejects_unsafe_paths() {
+        let repo = sample_repo();
+        let mut snapshot = RepositorySnapshot::capture(repo.path(), None, None).unwrap();
+        snapshot.files[0].path = "../escape.txt".to_string();
+
+        let target = TempDir::new().unwrap();
+        assert!(snapshot.restore(target.path()).is_err());
+    }
+
+    #[test]
+    fn test_capture_strips_credentials() {
+        let repo = sample_repo();
+        let mut config = XzeConfig::default();
+        config.repositories.push(crate::config::RepositoryConfig {
+            name: "demo".to_string(),
+            url: url::Url::parse("https://example.com/demo.git").unwrap(),
+            language: None,
+            watch_branches: vec![],
+            local_path: None,
+            credentials: Some(crate::config::GitCredentials {
+                auth: crate::config::GitAuth::UserPass {
+                    username: "user".to_string(),
+                    password: "secret".to_string(),
+                },
+            }),
+            custom: Default::default(),
+            group: None,
+            tags: vec![],
+            auto_update: true,
+            exclude_dirs: vec![],
+            exclude_patterns: vec![],
+        });
+
+        let snapshot = RepositorySnapshot::capture(repo.path(), None, Some(&config)).unwrap();
+        let recorded = snapshot.config.unwrap();
+        assert!(recorded.repositories[0].credentials.is_none());
+    }
+
+    #[test]
+    fn test_capture_missing_path() {
+        let result = RepositorySnapshot::capture(Path::new("/nonexistent/repo"), None, None);
+        assert!(result.is_err());
+    }
+}
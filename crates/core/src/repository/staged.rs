@@ -0,0 +1,382 @@
+//! Documentation check for staged files
+//!
+//! Backs `xze analyze --staged`, which is meant to run as a pre-commit hook.
+//! Only files staged in the git index are analyzed, using the staged
+//! content rather than the working tree, so the check sees exactly what is
+//! about to be committed. There is no repository walk and no AI call.
+//!
+//! Gaps are reported with the line of the undocumented declaration. The
+//! analyzers do not record positions, so the line is found by searching the
+//! file for the declaration; when that fails the gap is reported on line 1.
+
+use super::{analyzer::AnalyzerFactory, CoveragePolicy, DocItemKind};
+use crate::error::{Result, XzeError};
+use crate::git::{CredentialStore, GitOperations};
+use crate::types::ProgrammingLanguage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Keywords that introduce a named declaration in the supported languages
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "union",
+    "class",
+    "interface",
+    "def",
+    "func",
+    "function",
+];
+
+/// Visibility and other modifiers that may precede a declaration
+const MODIFIERS: &[&str] = &[
+    "pub",
+    "crate",
+    "super",
+    "export",
+    "public",
+    "private",
+    "protected",
+    "static",
+    "final",
+    "abstract",
+    "async",
+    "const",
+    "unsafe",
+    "readonly",
+];
+
+/// An undocumented item in a staged file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagedGap {
+    /// Path of the file, relative to the repository root
+    pub path: PathBuf,
+    /// 1-based line of the declaration
+    pub line: usize,
+    /// Kind of the undocumented item
+    pub kind: DocItemKind,
+    /// Item name; fields are reported as `Type.field`
+    pub name: String,
+}
+
+impl fmt::Display for StagedGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: undocumented {} `{}`",
+            self.path.display(),
+            self.line,
+            self.kind,
+            self.name
+        )
+    }
+}
+
+/// Result of checking the staged files of a repository
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagedReport {
+    /// Staged files that were analyzed
+    pub files_checked: usize,
+    /// Staged files skipped because no analyzer handles them
+    pub files_skipped: usize,
+    /// Undocumented items, by file and then line
+    pub gaps: Vec<StagedGap>,
+}
+
+impl StagedReport {
+    /// Whether the staged files have no documentation gaps
+    pub fn is_clean(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// Check the files staged in the repository at `repo_path`
+///
+/// Each staged file is analyzed on its own with the analyzer for its
+/// extension, and gaps are measured against `policy` for that language.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened, its status or
+/// index cannot be read, or analysis of a staged file fails
+pub fn check_staged(repo_path: &Path, policy: &CoveragePolicy) -> Result<StagedReport> {
+    let git = GitOperations::new(CredentialStore::new());
+    let repo = git.open(repo_path)?;
+    let index = repo.index().map_err(XzeError::Git)?;
+
+    let mut report = StagedReport::default();
+    for path in git.get_staged_files(&repo)? {
+        let language = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(ProgrammingLanguage::from)
+            .filter(|l| !matches!(l, ProgrammingLanguage::Unknown(_)));
+        let (Some(language), Some(entry)) = (language, index.get_path(&path, 0)) else {
+            report.files_skipped += 1;
+            continue;
+        };
+
+        let blob = repo.find_blob(entry.id).map_err(XzeError::Git)?;
+        let Ok(content) = std::str::from_utf8(blob.content()) else {
+            report.files_skipped += 1;
+            continue;
+        };
+
+        report
+            .gaps
+            .extend(check_file(&path, content, &language, policy)?);
+        report.files_checked += 1;
+    }
+
+    Ok(report)
+}
+
+/// Find documentation gaps in one file's content
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be staged for analysis or the
+/// analyzer fails
+pub fn check_file(
+    path: &Path,
+    content: &str,
+    language: &ProgrammingLanguage,
+    policy: &CoveragePolicy,
+) -> Result<Vec<StagedGap>> {
+    // Analyzers work on directories, so the file is analyzed alone in one
+    let workdir = tempfile::tempdir()?;
+    let file_name = path.file_name().unwrap_or(path.as_os_str());
+    std::fs::write(workdir.path().join(file_name), content)?;
+
+    let structure = AnalyzerFactory::create_analyzer(language).analyze(workdir.path())?;
+    let coverage = structure.coverage_report(policy.required_for(language));
+
+    let lines: Vec<&str> = content.lines().collect();
+    // Modules are reported by name at their `mod` line, or at the top of
+    // the file in languages where the file is the module
+    let module_lines = module_declaration_lines(&lines);
+    let mut undocumented_modules = structure
+        .modules
+        .iter()
+        .enumerate()
+        .filter(|(_, module)| module.documentation.is_none())
+        .map(|(i, module)| (module_lines.get(i).copied(), module.name.clone()));
+    let mut locator = DeclarationLocator::new(&lines);
+
+    let mut gaps: Vec<StagedGap> = coverage
+        .gaps
+        .into_iter()
+        .map(|gap| {
+            let (line, name) = match gap.kind {
+                DocItemKind::Module => undocumented_modules.next().unwrap_or((None, gap.name)),
+                DocItemKind::Field => (locator.field(&gap.name), gap.name),
+                _ => (locator.declaration(&gap.name), gap.name),
+            };
+            StagedGap {
+                path: path.to_path_buf(),
+                line: line.unwrap_or(0) + 1,
+                kind: gap.kind,
+                name,
+            }
+        })
+        .collect();
+    gaps.sort_by_key(|gap| gap.line);
+
+    Ok(gaps)
+}
+
+/// 0-based lines of Rust `mod` declarations, in file order
+fn module_declaration_lines(lines: &[&str]) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim_start();
+            trimmed.starts_with("mod ") || trimmed.starts_with("pub mod ")
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Finds declaration lines, moving past earlier matches so that repeated
+/// names resolve to successive declarations
+struct DeclarationLocator<'a> {
+    lines: &'a [&'a str],
+    next_from: HashMap<String, usize>,
+}
+
+impl<'a> DeclarationLocator<'a> {
+    fn new(lines: &'a [&'a str]) -> Self {
+        Self {
+            lines,
+            next_from: HashMap::new(),
+        }
+    }
+
+    /// Next line declaring `name`
+    fn declaration(&mut self, name: &str) -> Option<usize> {
+        let from = self.next_from.get(name).copied().unwrap_or(0);
+        let line = self.locate(name, from)?;
+        self.next_from.insert(name.to_string(), line + 1);
+        Some(line)
+    }
+
+    /// Next line declaring the field `Type.field`, searched after the
+    /// first declaration of the type
+    fn field(&mut self, name: &str) -> Option<usize> {
+        let (type_name, field) = name.split_once('.')?;
+        let from = match self.next_from.get(name) {
+            Some(&from) => from,
+            None => self.locate(type_name, 0)?,
+        };
+        let line = self.find(from, |tokens| tokens.first() == Some(&field))?;
+        self.next_from.insert(name.to_string(), line + 1);
+        Some(line)
+    }
+
+    /// First line from `from` declaring `name`, preferring a line where a
+    /// declaration keyword precedes it
+    fn locate(&self, name: &str, from: usize) -> Option<usize> {
+        self.find(from, |tokens| {
+            tokens
+                .iter()
+                .position(|t| *t == name)
+                .is_some_and(|i| tokens[..i].iter().any(|t| is_keyword(t)))
+        })
+        .or_else(|| self.find(from, |tokens| tokens.contains(&name)))
+    }
+
+    fn find(&self, from: usize, matches: impl Fn(&[&str]) -> bool) -> Option<usize> {
+        (from..self.lines.len()).find(|&i| {
+            let line = self.lines[i].trim_start();
+            !is_comment(line) && matches(&identifiers(line))
+        })
+    }
+}
+
+fn identifiers(line: &str) -> Vec<&str> {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|t| !t.is_empty() && !MODIFIERS.contains(t))
+        .collect()
+}
+
+fn is_keyword(token: &str) -> bool {
+    DECLARATION_KEYWORDS.contains(&token)
+}
+
+fn is_comment(line: &str) -> bool {
+    ["//", "#", "/*", "*"].iter().any(|p| line.starts_with(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = "\
+//! Crate docs
+
+/// Documented
+pub fn run() {}
+
+pub fn load() {}
+
+/// Config docs
+pub struct Config {
+    pub path: String,
+}
+
+fn helper() {}
+
+pub enum Mode { Fast }
+";
+
+    #[test]
+    fn test_check_file_reports_declaration_lines() {
+        let gaps = check_file(
+            Path::new("src/lib.rs"),
+            SOURCE,
+            &ProgrammingLanguage::Rust,
+            &CoveragePolicy::default(),
+        )
+        .unwrap();
+
+        let lines: Vec<String> = gaps.iter().map(|gap| gap.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "src/lib.rs:6: undocumented public function `load`",
+                "src/lib.rs:15: undocumented public type `Mode`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locator_handles_fields_modules_and_repeats() {
+        let lines: Vec<&str> = SOURCE.lines().collect();
+        let mut locator = DeclarationLocator::new(&lines);
+        // Doc comments mentioning a name are not declarations
+        assert_eq!(locator.declaration("Config"), Some(8));
+        assert_eq!(locator.field("Config.path"), Some(9));
+        assert_eq!(locator.field("Config.path"), None);
+
+        let overloads = ["class A {", "  void a() {}", "  void a(int x) {}"];
+        let mut locator = DeclarationLocator::new(&overloads);
+        assert_eq!(locator.declaration("a"), Some(1));
+        assert_eq!(locator.declaration("a"), Some(2));
+
+        let modules = ["/// Documented", "pub mod a;", "mod b;"];
+        assert_eq!(module_declaration_lines(&modules), vec![1, 2]);
+    }
+
+    fn commit_all(repo: &git2::Repository) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_check_staged_uses_index_content() {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("committed.rs"), "pub fn old() {}\n").unwrap();
+        commit_all(&repo);
+
+        // Staged: a new file with a gap, and a non-source file
+        std::fs::write(dir.path().join("new.rs"), "pub fn fresh() {}\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "text").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("new.rs")).unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+
+        // Unstaged edits are ignored, both to staged and committed files
+        std::fs::write(dir.path().join("new.rs"), "/// Docs\npub fn fresh() {}\n").unwrap();
+        std::fs::write(dir.path().join("committed.rs"), "pub fn other() {}\n").unwrap();
+
+        let report = check_staged(dir.path(), &CoveragePolicy::default()).unwrap();
+        assert_eq!(report.files_checked, 1);
+        assert_eq!(report.files_skipped, 1);
+        assert_eq!(
+            report.gaps,
+            vec![StagedGap {
+                path: PathBuf::from("new.rs"),
+                line: 1,
+                kind: DocItemKind::PublicFunction,
+                name: "fresh".to_string(),
+            }]
+        );
+        assert!(!report.is_clean());
+    }
+}
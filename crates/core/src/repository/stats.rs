@@ -0,0 +1,277 @@
+//! Aggregate repository statistics
+//!
+//! [`RepositoryStats`] summarizes an analysis as counts only: files, lines,
+//! a per-language breakdown and documentation coverage. It is a small,
+//! fixed-size alternative to serializing the full [`CodeStructure`], which
+//! grows with every item in the repository.
+
+use super::{
+    snapshot::RepositorySnapshot, AnnotationCounts, CodeStructure, CoverageReport, DocItemKind,
+    Repository,
+};
+use crate::{
+    error::{Result, XzeError},
+    types::ProgrammingLanguage,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+use walkdir::WalkDir;
+
+/// TODO and FIXME markers per thousand lines above which a repository is
+/// flagged
+pub const HIGH_TODO_DENSITY: f32 = 10.0;
+
+/// File and line counts for one language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageStats {
+    /// Number of source files
+    pub files: usize,
+    /// Lines in those files
+    pub lines: usize,
+}
+
+/// Summary statistics for an analyzed repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepositoryStats {
+    /// Repository path or snapshot source
+    pub source: String,
+    /// Language used for analysis
+    pub language: ProgrammingLanguage,
+    /// Number of files, excluding ignored paths
+    pub file_count: usize,
+    /// Lines in source files of the analysis language
+    pub lines_of_code: usize,
+    /// File and line counts by language name
+    pub languages: BTreeMap<String, LanguageStats>,
+    /// Number of modules found by the analyzer
+    pub modules: usize,
+    /// Number of functions found by the analyzer
+    pub functions: usize,
+    /// Number of types found by the analyzer
+    pub types: usize,
+    /// Documentation coverage under the active policy (0.0 to 1.0)
+    pub doc_coverage: f32,
+    /// Public functions and types without documentation
+    pub undocumented_public_items: usize,
+    /// Number of analysis warnings, such as skipped files
+    pub warnings: usize,
+    /// TODO, FIXME and deprecation markers
+    #[serde(default)]
+    pub annotations: AnnotationCounts,
+}
+
+impl RepositoryStats {
+    /// Summarize `structure` and its coverage, with no files counted yet
+    pub fn from_structure(
+        source: String,
+        language: ProgrammingLanguage,
+        structure: &CodeStructure,
+        coverage: &CoverageReport,
+    ) -> Self {
+        let undocumented_public_items = structure
+            .coverage_report(&[DocItemKind::PublicFunction, DocItemKind::PublicType])
+            .gaps
+            .len();
+
+        Self {
+            source,
+            language,
+            file_count: 0,
+            lines_of_code: 0,
+            languages: BTreeMap::new(),
+            modules: structure.modules.len(),
+            functions: structure.functions.len(),
+            types: structure.types.len(),
+            doc_coverage: coverage.coverage(),
+            undocumented_public_items,
+            warnings: structure.warnings.len(),
+            annotations: structure.annotation_counts(),
+        }
+    }
+
+    /// TODO and FIXME markers per thousand lines of code
+    ///
+    /// Returns 0.0 when no lines have been counted.
+    pub fn todo_density(&self) -> f32 {
+        if self.lines_of_code == 0 {
+            return 0.0;
+        }
+        (self.annotations.todo + self.annotations.fixme) as f32 * 1000.0 / self.lines_of_code as f32
+    }
+
+    /// Whether TODO density exceeds [`HIGH_TODO_DENSITY`]
+    pub fn has_high_todo_density(&self) -> bool {
+        self.todo_density() > HIGH_TODO_DENSITY
+    }
+
+    /// Count a file, adding its lines when its language is recognized
+    ///
+    /// Lines only count toward `lines_of_code` for the analysis language,
+    /// matching `RepositoryMetadata::lines_of_code`.
+    ///
+    /// `content` is `None` for files that could not be read as text.
+    pub fn add_file(&mut self, path: &Path, content: Option<&str>) {
+        self.file_count += 1;
+
+        let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+            return;
+        };
+        let language = ProgrammingLanguage::from(extension);
+        if matches!(language, ProgrammingLanguage::Unknown(_)) {
+            return;
+        }
+
+        let lines = content.map(|c| c.lines().count()).unwrap_or(0);
+        let entry = self.languages.entry(language.to_string()).or_default();
+        entry.files += 1;
+        entry.lines += lines;
+        if language == self.language {
+            self.lines_of_code += lines;
+        }
+    }
+}
+
+impl Repository {
+    /// Collect statistics for the current structure
+    ///
+    /// Walks the checkout, skipping paths matched by
+    /// [`Repository::should_ignore_path`].
+    pub fn stats(&self, coverage: &CoverageReport) -> Result<RepositoryStats> {
+        let mut stats = RepositoryStats::from_structure(
+            self.local_path.display().to_string(),
+            self.language.clone(),
+            &self.structure,
+            coverage,
+        );
+
+        for entry in WalkDir::new(&self.local_path) {
+            let entry = entry.map_err(|e| XzeError::filesystem(format!("Walk error: {}", e)))?;
+            let path = entry.path();
+            let relative = self
+                .relative_path(path)
+                .unwrap_or_else(|| path.to_path_buf());
+            if path.is_file() && !self.should_ignore_path(&relative) {
+                let content = std::fs::read_to_string(path).ok();
+                stats.add_file(path, content.as_deref());
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+impl RepositorySnapshot {
+    /// Collect statistics for `structure` from the recorded files
+    pub fn stats(&self, structure: &CodeStructure, coverage: &CoverageReport) -> RepositoryStats {
+        let mut stats = RepositoryStats::from_structure(
+            self.source.clone(),
+            self.language.clone(),
+            structure,
+            coverage,
+        );
+        for file in &self.files {
+            stats.add_file(Path::new(&file.path), Some(&file.content));
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, Visibility};
+    use crate::types::RepositoryId;
+    use tempfile::TempDir;
+
+    fn function(name: &str, visibility: Visibility, documented: bool) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: documented.then(|| "Docs".to_string()),
+            parameters: vec![],
+            return_type: None,
+            visibility,
+            is_async: false,
+        }
+    }
+
+    #[test]
+    fn test_from_structure_counts_undocumented_public_items() {
+        let mut structure = CodeStructure::new();
+        structure.functions = vec![
+            function("run", Visibility::Public, true),
+            function("load", Visibility::Public, false),
+            function("helper", Visibility::Private, false),
+        ];
+        let coverage = structure.coverage_report(&[DocItemKind::PublicFunction]);
+
+        let stats = RepositoryStats::from_structure(
+            "repo".to_string(),
+            ProgrammingLanguage::Rust,
+            &structure,
+            &coverage,
+        );
+
+        assert_eq!(stats.functions, 3);
+        assert_eq!(stats.undocumented_public_items, 1);
+        assert_eq!(stats.doc_coverage, 0.5);
+    }
+
+    #[test]
+    fn test_todo_density() {
+        let mut structure = CodeStructure::new();
+        structure.record_annotations(
+            Path::new("src/lib.rs"),
+            "// TODO: a\n// FIXME: b\n#[deprecated]\nfn old() {}\n",
+            &ProgrammingLanguage::Rust,
+        );
+
+        let mut stats = RepositoryStats::from_structure(
+            "repo".to_string(),
+            ProgrammingLanguage::Rust,
+            &structure,
+            &CoverageReport::default(),
+        );
+        assert_eq!(stats.annotations.deprecated, 1);
+        assert_eq!(stats.todo_density(), 0.0);
+
+        stats.lines_of_code = 100;
+        assert_eq!(stats.todo_density(), 20.0);
+        assert!(stats.has_high_todo_density());
+
+        stats.lines_of_code = 1000;
+        assert!(!stats.has_high_todo_density());
+    }
+
+    #[test]
+    fn test_repository_stats_walks_files_by_language() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(root.join("build.py"), "print(1)\n").unwrap();
+        std::fs::write(root.join("README.md"), "# Readme\n").unwrap();
+        std::fs::write(root.join("target/out.rs"), "fn ignored() {}\n").unwrap();
+
+        let repo = Repository::new(
+            RepositoryId::from("repo"),
+            String::new(),
+            root.to_path_buf(),
+            ProgrammingLanguage::Rust,
+        );
+        let stats = repo.stats(&CoverageReport::default()).unwrap();
+
+        assert_eq!(stats.file_count, 3);
+        assert_eq!(stats.lines_of_code, 2);
+        assert_eq!(
+            stats.languages.get("Rust"),
+            Some(&LanguageStats { files: 1, lines: 2 })
+        );
+        assert_eq!(
+            stats.languages.get("Python"),
+            Some(&LanguageStats { files: 1, lines: 1 })
+        );
+        assert_eq!(stats.languages.len(), 3);
+    }
+}
@@ -1,7 +1,11 @@
 //! Code structure representations
 
+use super::{AnalysisWarning, CodeAnnotation, ConfigKey};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 /// Complete code structure of a repository
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -10,6 +14,18 @@ pub struct CodeStructure {
     pub functions: Vec<Function>,
     pub types: Vec<TypeDefinition>,
     pub configs: Vec<ConfigFile>,
+    /// Best-effort call graph: function name to the names of functions it calls
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub call_graph: HashMap<String, Vec<String>>,
+    /// Inputs that were skipped or only partially understood
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalysisWarning>,
+    /// TODO, FIXME and deprecation markers found in source files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<CodeAnnotation>,
+    /// Environment variables and configuration keys the project reads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_keys: Vec<ConfigKey>,
 }
 
 impl CodeStructure {
@@ -34,6 +50,33 @@ impl CodeStructure {
             .filter(|f| f.visibility == Visibility::Public)
             .collect()
     }
+
+    /// Attach methods to the types they are declared on
+    ///
+    /// Each method is paired with the name of its type. The first type with
+    /// that name receives it; methods of types outside the structure, such
+    /// as trait implementations for foreign types, stay free functions.
+    pub fn attach_methods(&mut self, methods: Vec<(String, Function)>) {
+        for (type_name, method) in methods {
+            if let Some(owner) = self.types.iter_mut().find(|t| t.name == type_name) {
+                owner.methods.push(method);
+            }
+        }
+    }
+
+    /// Get the functions that are not methods of a type in the structure
+    pub fn free_functions(&self) -> Vec<&Function> {
+        let methods: HashSet<(&str, &str)> = self
+            .types
+            .iter()
+            .flat_map(|t| &t.methods)
+            .map(|m| (m.name.as_str(), m.signature.as_str()))
+            .collect();
+        self.functions
+            .iter()
+            .filter(|f| !methods.contains(&(f.name.as_str(), f.signature.as_str())))
+            .collect()
+    }
 }
 
 /// Module representation
@@ -73,6 +116,9 @@ pub struct TypeDefinition {
     pub documentation: Option<String>,
     pub fields: Vec<Field>,
     pub visibility: Visibility,
+    /// Methods from `impl` blocks, which are also in [`CodeStructure::functions`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<Function>,
 }
 
 /// Kind of type definition
@@ -184,6 +230,62 @@ mod tests {
         assert_eq!(structure.public_functions().len(), 1);
     }
 
+    #[test]
+    fn test_attach_methods() {
+        let function = |name: &str, signature: &str| Function {
+            name: name.to_string(),
+            signature: signature.to_string(),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        };
+        let mut structure = CodeStructure::new();
+        structure.types.push(TypeDefinition {
+            name: "Server".to_string(),
+            kind: TypeKind::Struct,
+            documentation: None,
+            fields: vec![],
+            visibility: Visibility::Public,
+            methods: vec![],
+        });
+        let start = function("new", "pub fn new() -> Server");
+        let foreign = function("new", "pub fn new() -> Client");
+        structure.functions = vec![
+            start.clone(),
+            foreign.clone(),
+            function("main", "fn main()"),
+        ];
+
+        structure.attach_methods(vec![
+            ("Server".to_string(), start),
+            ("Client".to_string(), foreign),
+        ]);
+
+        assert_eq!(structure.types[0].methods.len(), 1);
+        assert_eq!(
+            structure.types[0].methods[0].signature,
+            "pub fn new() -> Server"
+        );
+        let free: Vec<_> = structure
+            .free_functions()
+            .iter()
+            .map(|f| f.signature.as_str())
+            .collect();
+        assert_eq!(free, vec!["pub fn new() -> Client", "fn main()"]);
+        assert_eq!(structure.item_count(), 4);
+        assert_eq!(structure.public_functions().len(), 3);
+    }
+
+    #[test]
+    fn test_type_without_methods_deserializes() {
+        let json = r#"{"name":"Unit","kind":"struct","documentation":null,"fields":[],"visibility":"public"}"#;
+        let type_def: TypeDefinition = serde_json::from_str(json).unwrap();
+        assert!(type_def.methods.is_empty());
+        assert_eq!(serde_json::to_string(&type_def).unwrap(), json);
+    }
+
     #[test]
     fn test_config_format() {
         assert_eq!(
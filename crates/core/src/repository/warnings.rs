@@ -0,0 +1,238 @@
+//! Non-fatal problems found during analysis
+//!
+//! Analyzers skip inputs they cannot use rather than failing the whole run.
+//! Each skip or ambiguity is recorded as an [`AnalysisWarning`] on the
+//! [`CodeStructure`], so API and CLI consumers can tell when a result is
+//! partial instead of relying on log output.
+
+use super::CodeStructure;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Names of ambiguous functions quoted in a warning message
+const MAX_NAMED_AMBIGUITIES: usize = 5;
+
+/// How serious an analysis warning is, in increasing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningSeverity {
+    /// Results are complete but may be imprecise
+    Info,
+    /// Part of the repository was skipped
+    Warning,
+}
+
+impl fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningSeverity::Info => write!(f, "info"),
+            WarningSeverity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// What kind of problem a warning describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WarningKind {
+    /// A file could not be read and was skipped
+    UnreadableFile,
+    /// A file is not valid UTF-8 and was skipped
+    UndecodableFile,
+    /// Several functions share a name, so their call graph entries merge
+    AmbiguousName,
+    /// A file did not parse and was analyzed with line-based heuristics
+    UnparsableFile,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningKind::UnreadableFile => write!(f, "unreadable-file"),
+            WarningKind::UndecodableFile => write!(f, "undecodable-file"),
+            WarningKind::AmbiguousName => write!(f, "ambiguous-name"),
+            WarningKind::UnparsableFile => write!(f, "unparsable-file"),
+        }
+    }
+}
+
+/// A problem encountered during analysis that did not stop it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisWarning {
+    pub severity: WarningSeverity,
+    pub kind: WarningKind,
+    /// File the warning is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+impl fmt::Display for AnalysisWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.severity, self.message)?;
+        if let Some(path) = &self.path {
+            write!(f, " ({})", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl CodeStructure {
+    /// Read a file for analysis
+    ///
+    /// Returns `None` and records a warning when the file cannot be read or
+    /// is not valid UTF-8.
+    pub fn read_source(&mut self, path: &Path) -> Option<String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                debug!("Skipping {}: {}", path.display(), e);
+                let (kind, message) = if e.kind() == io::ErrorKind::InvalidData {
+                    (
+                        WarningKind::UndecodableFile,
+                        "Skipped file that is not valid UTF-8".to_string(),
+                    )
+                } else {
+                    (
+                        WarningKind::UnreadableFile,
+                        format!("Skipped unreadable file: {}", e),
+                    )
+                };
+                self.warnings.push(AnalysisWarning {
+                    severity: WarningSeverity::Warning,
+                    kind,
+                    path: Some(path.to_path_buf()),
+                    message,
+                });
+                None
+            }
+        }
+    }
+
+    /// Get the warnings at or above `severity`
+    pub fn warnings_at_least(
+        &self,
+        severity: WarningSeverity,
+    ) -> impl Iterator<Item = &AnalysisWarning> {
+        self.warnings.iter().filter(move |w| w.severity >= severity)
+    }
+
+    /// Record a warning if several functions share a name
+    pub(crate) fn warn_ambiguous_names(&mut self) {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for function in &self.functions {
+            *counts.entry(function.name.as_str()).or_insert(0) += 1;
+        }
+
+        let mut duplicated: Vec<&str> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(name, _)| name)
+            .collect();
+        if duplicated.is_empty() {
+            return;
+        }
+        duplicated.sort_unstable();
+
+        let mut examples = duplicated
+            .iter()
+            .take(MAX_NAMED_AMBIGUITIES)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if duplicated.len() > MAX_NAMED_AMBIGUITIES {
+            examples.push_str(", ...");
+        }
+        let message = format!(
+            "{} function names are defined more than once ({}); their call graph \
+             entries are merged",
+            duplicated.len(),
+            examples
+        );
+
+        self.warnings.push(AnalysisWarning {
+            severity: WarningSeverity::Info,
+            kind: WarningKind::AmbiguousName,
+            path: None,
+            message,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{Function, Visibility};
+    use tempfile::TempDir;
+
+    fn function(name: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            signature: format!("fn {}()", name),
+            documentation: None,
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+        }
+    }
+
+    #[test]
+    fn test_read_source_records_skipped_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let binary = temp_dir.path().join("blob.rs");
+        std::fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+        let missing = temp_dir.path().join("missing.rs");
+
+        let mut structure = CodeStructure::new();
+        assert!(structure.read_source(&binary).is_none());
+        assert!(structure.read_source(&missing).is_none());
+
+        let kinds: Vec<WarningKind> = structure.warnings.iter().map(|w| w.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![WarningKind::UndecodableFile, WarningKind::UnreadableFile]
+        );
+        assert_eq!(
+            structure.warnings[0].path.as_deref(),
+            Some(binary.as_path())
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_names_are_reported_as_info() {
+        let mut structure = CodeStructure::new();
+        structure.functions = vec![function("new"), function("run"), function("new")];
+        structure.warn_ambiguous_names();
+
+        assert_eq!(structure.warnings.len(), 1);
+        assert_eq!(structure.warnings[0].kind, WarningKind::AmbiguousName);
+        assert!(structure.warnings[0].message.contains("(new)"));
+        assert_eq!(
+            structure
+                .warnings_at_least(WarningSeverity::Warning)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_warning_serialization() {
+        let warning = AnalysisWarning {
+            severity: WarningSeverity::Warning,
+            kind: WarningKind::UndecodableFile,
+            path: Some(PathBuf::from("src/blob.rs")),
+            message: "Skipped file that is not valid UTF-8".to_string(),
+        };
+
+        let json = serde_json::to_value(&warning).unwrap();
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["kind"], "undecodable-file");
+        assert_eq!(json["path"], "src/blob.rs");
+    }
+}
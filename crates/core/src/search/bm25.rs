@@ -0,0 +1,196 @@
+//! BM25 lexical scoring of search candidates
+//!
+//! Embedding similarity misses exact keyword matches such as identifiers
+//! and error messages. [`Bm25`] scores documents by the query terms they
+//! contain, weighted by how rare each term is across the scored documents
+//! and damped for long documents. Text is split with the keyword extractor
+//! tokenization and stop words are ignored.
+//!
+//! Statistics are computed over the documents given to [`Bm25::new`], so
+//! scores are only comparable within one candidate set.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::search::bm25::Bm25;
+//!
+//! let bm25 = Bm25::new(["Install with cargo", "Configure the logging format"]);
+//! let scores = bm25.normalized_scores("logging format");
+//!
+//! assert_eq!(scores[0], 0.0);
+//! assert_eq!(scores[1], 1.0);
+//! ```
+
+use crate::keyword_extractor::{is_stop_word, tokenize};
+use std::collections::HashMap;
+
+/// Term frequency saturation
+pub const K1: f32 = 1.2;
+
+/// Document length normalization
+pub const B: f32 = 0.75;
+
+/// BM25 statistics for a fixed set of documents
+#[derive(Debug, Clone, Default)]
+pub struct Bm25 {
+    documents: Vec<HashMap<String, usize>>,
+    lengths: Vec<usize>,
+    document_frequency: HashMap<String, usize>,
+    avg_length: f32,
+}
+
+impl Bm25 {
+    /// Build statistics for `documents`, scored by their position
+    pub fn new<I, S>(documents: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut bm25 = Self::default();
+
+        for document in documents {
+            let terms = terms(document.as_ref());
+            let mut frequencies = HashMap::new();
+            for term in &terms {
+                *frequencies.entry(term.clone()).or_insert(0) += 1;
+            }
+            for term in frequencies.keys() {
+                *bm25.document_frequency.entry(term.clone()).or_insert(0) += 1;
+            }
+            bm25.lengths.push(terms.len());
+            bm25.documents.push(frequencies);
+        }
+
+        if !bm25.lengths.is_empty() {
+            bm25.avg_length = bm25.lengths.iter().sum::<usize>() as f32 / bm25.lengths.len() as f32;
+        }
+        bm25
+    }
+
+    /// Number of documents scored
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Whether there are no documents
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Raw BM25 score of every document for `query`, in document order
+    ///
+    /// Documents sharing no terms with the query score 0.0.
+    pub fn scores(&self, query: &str) -> Vec<f32> {
+        let mut query_terms = terms(query);
+        query_terms.sort();
+        query_terms.dedup();
+
+        let count = self.documents.len() as f32;
+        let idf: Vec<(String, f32)> = query_terms
+            .into_iter()
+            .filter_map(|term| {
+                let df = *self.document_frequency.get(&term)? as f32;
+                Some((term, ((count - df + 0.5) / (df + 0.5) + 1.0).ln()))
+            })
+            .collect();
+
+        self.documents
+            .iter()
+            .zip(&self.lengths)
+            .map(|(frequencies, &length)| {
+                let length_norm = if self.avg_length > 0.0 {
+                    1.0 - B + B * length as f32 / self.avg_length
+                } else {
+                    1.0
+                };
+                idf.iter()
+                    .filter_map(|(term, idf)| {
+                        let tf = *frequencies.get(term)? as f32;
+                        Some(idf * tf * (K1 + 1.0) / (tf + K1 * length_norm))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// BM25 scores divided by the best score, so they fall in 0.0 to 1.0
+    ///
+    /// All scores are 0.0 when no document matches.
+    pub fn normalized_scores(&self, query: &str) -> Vec<f32> {
+        let scores = self.scores(query);
+        let max = scores.iter().copied().fold(0.0f32, f32::max);
+        if max <= 0.0 {
+            return vec![0.0; scores.len()];
+        }
+        scores.into_iter().map(|score| score / max).collect()
+    }
+}
+
+/// Tokens of `text` that count towards BM25
+fn terms(text: &str) -> Vec<String> {
+    tokenize(text)
+        .into_iter()
+        .filter(|word| !is_stop_word(word))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_favor_matching_documents() {
+        let bm25 = Bm25::new([
+            "Configure the search_index with cargo",
+            "Install the application",
+            "Notes about the weather",
+        ]);
+
+        let scores = bm25.scores("search_index");
+        assert!(scores[0] > 0.0);
+        assert_eq!(scores[1], 0.0);
+        assert_eq!(scores[2], 0.0);
+    }
+
+    #[test]
+    fn test_rare_terms_weigh_more() {
+        let bm25 = Bm25::new([
+            "rust tokio runtime",
+            "rust async guide",
+            "rust error handling",
+        ]);
+
+        let scores = bm25.scores("rust tokio");
+        assert!(scores[0] > scores[1]);
+        assert_eq!(scores[1], scores[2]);
+    }
+
+    #[test]
+    fn test_shorter_documents_score_higher() {
+        let bm25 = Bm25::new([
+            "logging",
+            "logging plus many other unrelated words padding this document out",
+        ]);
+
+        let scores = bm25.scores("logging");
+        assert!(scores[0] > scores[1]);
+    }
+
+    #[test]
+    fn test_stop_words_are_ignored() {
+        let bm25 = Bm25::new(["the cat", "the dog"]);
+        assert_eq!(bm25.scores("the"), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_normalized_scores() {
+        let bm25 = Bm25::new(["alpha beta", "alpha", "gamma"]);
+
+        let scores = bm25.normalized_scores("beta");
+        assert_eq!(scores, vec![1.0, 0.0, 0.0]);
+        assert_eq!(bm25.normalized_scores("missing"), vec![0.0; 3]);
+        assert!(Bm25::new(Vec::<String>::new())
+            .normalized_scores("query")
+            .is_empty());
+    }
+}
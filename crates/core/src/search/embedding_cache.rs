@@ -4,6 +4,12 @@
 //! regenerating embeddings for frequently searched queries. This can
 //! reduce search latency by 50-100ms per query.
 //!
+//! With [`EmbeddingCache::with_persistence`] the cache is also kept in a
+//! file, loaded when the cache is created and rewritten periodically, so a
+//! restarted server does not embed every query again. Keys are hashed with
+//! [`calculate_content_hash`] before they are cached, so raw queries are
+//! never written to disk.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -25,18 +31,50 @@
 //! # }
 //! ```
 
+use crate::kb::hash::calculate_content_hash;
+use crate::XzeError;
 use moka::future::Cache;
+use moka::policy::EvictionPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tracing::{debug, trace};
+use std::time::Duration;
+use tracing::{debug, trace, warn};
+
+/// How often a persistent cache writes new entries to its file
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Version of the cache file format
+const CACHE_FILE_VERSION: u32 = 1;
 
 /// Query embedding cache with LRU eviction
 ///
 /// Caches query embeddings to avoid regenerating them for frequently
 /// searched queries. Uses an LRU eviction policy to maintain a fixed
 /// size and TTL-based expiration.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct EmbeddingCache {
     cache: Cache<String, Arc<Vec<f32>>>,
+    persistence: Option<Arc<Persistence>>,
+}
+
+/// File backing a persistent cache
+#[derive(Debug)]
+struct Persistence {
+    path: PathBuf,
+    /// Whether entries changed since the last write
+    dirty: AtomicBool,
+    /// Serializes writes of the file
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+/// On-disk form of the cache, keyed by hashed query
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, Vec<f32>>,
 }
 
 impl EmbeddingCache {
@@ -58,18 +96,17 @@ impl EmbeddingCache {
     /// let cache = EmbeddingCache::new(1000);
     /// ```
     pub fn new(capacity: u64) -> Self {
-        let cache = Cache::builder()
-            .max_capacity(capacity)
-            .time_to_live(std::time::Duration::from_secs(3600)) // 1 hour TTL
-            .time_to_idle(std::time::Duration::from_secs(1800)) // 30 min idle
-            .build();
+        let cache = Self::build(capacity, 3600, 1800); // 1 hour TTL, 30 min idle
 
         debug!(
             "Created embedding cache with capacity {} and 1h TTL",
             capacity
         );
 
-        Self { cache }
+        Self {
+            cache,
+            persistence: None,
+        }
     }
 
     /// Create a new cache with custom TTL and idle timeout
@@ -93,18 +130,126 @@ impl EmbeddingCache {
     /// let cache = EmbeddingCache::with_ttl(1000, 7200, 3600);
     /// ```
     pub fn with_ttl(capacity: u64, ttl_seconds: u64, idle_seconds: u64) -> Self {
-        let cache = Cache::builder()
-            .max_capacity(capacity)
-            .time_to_live(std::time::Duration::from_secs(ttl_seconds))
-            .time_to_idle(std::time::Duration::from_secs(idle_seconds))
-            .build();
+        let cache = Self::build(capacity, ttl_seconds, idle_seconds);
 
         debug!(
             "Created embedding cache with capacity {}, TTL {}s, idle {}s",
             capacity, ttl_seconds, idle_seconds
         );
 
-        Self { cache }
+        Self {
+            cache,
+            persistence: None,
+        }
+    }
+
+    fn build(capacity: u64, ttl_seconds: u64, idle_seconds: u64) -> Cache<String, Arc<Vec<f32>>> {
+        Cache::builder()
+            .max_capacity(capacity)
+            .eviction_policy(EvictionPolicy::lru())
+            .time_to_live(Duration::from_secs(ttl_seconds))
+            .time_to_idle(Duration::from_secs(idle_seconds))
+            .build()
+    }
+
+    /// Keep the cache in the file at `path`
+    ///
+    /// Loads the entries already in the file, up to the cache capacity, and
+    /// writes new entries back every [`DEFAULT_FLUSH_INTERVAL`]. A missing
+    /// file starts an empty cache; an unreadable or corrupted one is ignored
+    /// with a warning and replaced on the next write. Must be called within
+    /// a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xze_core::search::embedding_cache::EmbeddingCache;
+    ///
+    /// # async fn example() -> xze_core::Result<()> {
+    /// let cache = EmbeddingCache::new(1000)
+    ///     .with_persistence("/var/cache/xze/embeddings.json")
+    ///     .await;
+    /// cache.insert("rust error handling", vec![0.1, 0.2]).await;
+    /// cache.flush().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_persistence(self, path: impl Into<PathBuf>) -> Self {
+        self.with_persistence_every(path, DEFAULT_FLUSH_INTERVAL)
+            .await
+    }
+
+    /// Keep the cache in the file at `path`, writing it every `interval`
+    ///
+    /// See [`with_persistence`](Self::with_persistence).
+    pub async fn with_persistence_every(
+        mut self,
+        path: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> Self {
+        let path = path.into();
+        let entries = load_entries(&path).await;
+        debug!(
+            "Loaded {} cached embeddings from {}",
+            entries.len(),
+            path.display()
+        );
+        for (key, embedding) in entries {
+            self.cache.insert(key, Arc::new(embedding)).await;
+        }
+        self.cache.run_pending_tasks().await;
+
+        let persistence = Arc::new(Persistence {
+            path,
+            dirty: AtomicBool::new(false),
+            write_lock: tokio::sync::Mutex::new(()),
+        });
+
+        // The task stops once every handle to this cache is dropped
+        let weak = Arc::downgrade(&persistence);
+        let cache = self.cache.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(persistence) = weak.upgrade() else {
+                    break;
+                };
+                if let Err(e) = persistence.flush(&cache).await {
+                    warn!("Failed to write embedding cache: {}", e);
+                }
+            }
+        });
+
+        self.persistence = Some(persistence);
+        self
+    }
+
+    /// Path of the file backing the cache, if it is persistent
+    pub fn persistence_path(&self) -> Option<&Path> {
+        self.persistence.as_ref().map(|p| p.path.as_path())
+    }
+
+    /// Write entries changed since the last write to the cache file
+    ///
+    /// Does nothing for an in-memory cache or when nothing changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache file cannot be written
+    pub async fn flush(&self) -> crate::Result<()> {
+        match &self.persistence {
+            Some(persistence) => persistence.flush(&self.cache).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Mark entries as changed so the next flush writes them
+    fn touch(&self) {
+        if let Some(persistence) = &self.persistence {
+            persistence.dirty.store(true, Ordering::Relaxed);
+        }
     }
 
     /// Insert a query embedding into the cache
@@ -133,7 +278,10 @@ impl EmbeddingCache {
             query_str,
             embedding.len()
         );
-        self.cache.insert(query_str, Arc::new(embedding)).await;
+        self.cache
+            .insert(calculate_content_hash(&query_str), Arc::new(embedding))
+            .await;
+        self.touch();
     }
 
     /// Retrieve a cached embedding for a query
@@ -163,7 +311,7 @@ impl EmbeddingCache {
     /// # }
     /// ```
     pub async fn get(&self, query: &str) -> Option<Arc<Vec<f32>>> {
-        let result = self.cache.get(query).await;
+        let result = self.cache.get(&calculate_content_hash(query)).await;
         if result.is_some() {
             trace!("Cache HIT for query '{}'", query);
         } else {
@@ -190,7 +338,8 @@ impl EmbeddingCache {
     /// ```
     pub async fn invalidate(&self, query: &str) {
         debug!("Invalidating cache entry for query '{}'", query);
-        self.cache.invalidate(query).await;
+        self.cache.invalidate(&calculate_content_hash(query)).await;
+        self.touch();
     }
 
     /// Clear all entries from the cache
@@ -210,6 +359,7 @@ impl EmbeddingCache {
         self.cache.invalidate_all();
         // Run pending tasks to ensure invalidation completes
         self.cache.run_pending_tasks().await;
+        self.touch();
     }
 
     /// Get the current number of entries in the cache
@@ -287,13 +437,103 @@ impl EmbeddingCache {
 
         // Cache the result
         self.cache
-            .insert(query.to_string(), arc_embedding.clone())
+            .insert(calculate_content_hash(query), arc_embedding.clone())
             .await;
+        self.touch();
 
         Ok(arc_embedding)
     }
 }
 
+impl Persistence {
+    /// Write `cache` to the file if it changed since the last write
+    ///
+    /// Writes a temporary file and renames it over the cache file, so a
+    /// crash mid-write leaves the previous file intact.
+    async fn flush(&self, cache: &Cache<String, Arc<Vec<f32>>>) -> crate::Result<()> {
+        let _guard = self.write_lock.lock().await;
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        cache.run_pending_tasks().await;
+        let file = CacheFile {
+            version: CACHE_FILE_VERSION,
+            entries: cache
+                .iter()
+                .map(|(key, embedding)| (key.as_ref().clone(), embedding.as_ref().clone()))
+                .collect(),
+        };
+
+        let result = write_file(&self.path, &file).await;
+        if result.is_err() {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+async fn write_file(path: &Path, file: &CacheFile) -> crate::Result<()> {
+    let bytes = serde_json::to_vec(file)?;
+    let tmp = path.with_extension("tmp");
+    let write = async {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, path).await
+    };
+    write.await.map_err(|e| {
+        XzeError::filesystem(format!(
+            "Failed to write embedding cache {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    debug!(
+        "Wrote {} cached embeddings to {}",
+        file.entries.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Entries of the cache file at `path`; empty if it is missing or corrupted
+async fn load_entries(path: &Path) -> HashMap<String, Vec<f32>> {
+    let bytes = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            warn!(
+                "Ignoring unreadable embedding cache {}: {}",
+                path.display(),
+                e
+            );
+            return HashMap::new();
+        }
+    };
+
+    match serde_json::from_slice::<CacheFile>(&bytes) {
+        Ok(file) if file.version == CACHE_FILE_VERSION => file.entries,
+        Ok(file) => {
+            warn!(
+                "Ignoring embedding cache {} with unsupported version {}",
+                path.display(),
+                file.version
+            );
+            HashMap::new()
+        }
+        Err(e) => {
+            warn!(
+                "Ignoring corrupted embedding cache {}: {}",
+                path.display(),
+                e
+            );
+            HashMap::new()
+        }
+    }
+}
+
 impl Default for EmbeddingCache {
     /// Create a default cache with capacity of 1000 entries
     fn default() -> Self {
@@ -491,4 +731,86 @@ mod tests {
         assert!(cached.is_some());
         assert_eq!(*cached.unwrap(), embedding2);
     }
+
+    #[tokio::test]
+    async fn test_embedding_cache_evicts_least_recently_used() {
+        let cache = EmbeddingCache::new(2);
+
+        cache.insert("first", vec![0.1]).await;
+        cache.insert("second", vec![0.2]).await;
+        cache.cache.run_pending_tasks().await;
+        assert!(cache.get("first").await.is_some());
+
+        cache.insert("third", vec![0.3]).await;
+        cache.cache.run_pending_tasks().await;
+
+        assert_eq!(cache.entry_count(), 2);
+        assert!(cache.get("first").await.is_some());
+        assert!(cache.get("second").await.is_none());
+        assert!(cache.get("third").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("embeddings.json");
+
+        let cache = EmbeddingCache::new(100).with_persistence(&path).await;
+        assert_eq!(cache.persistence_path(), Some(path.as_path()));
+        cache.insert("rust error handling", vec![0.1, 0.2]).await;
+        cache.flush().await.unwrap();
+        drop(cache);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("rust error handling"));
+        assert!(contents.contains(&calculate_content_hash("rust error handling")));
+
+        let restarted = EmbeddingCache::new(100).with_persistence(&path).await;
+        assert_eq!(
+            *restarted.get("rust error handling").await.unwrap(),
+            vec![0.1, 0.2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache_flushes_periodically() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cache").join("embeddings.json");
+
+        let cache = EmbeddingCache::new(100)
+            .with_persistence_every(&path, Duration::from_millis(20))
+            .await;
+        cache.insert("query", vec![0.5]).await;
+
+        for _ in 0..50 {
+            if path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_cache_file_is_ignored() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("embeddings.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        let cache = EmbeddingCache::new(100).with_persistence(&path).await;
+        assert_eq!(cache.entry_count(), 0);
+
+        cache.insert("query", vec![0.5]).await;
+        cache.flush().await.unwrap();
+        let restarted = EmbeddingCache::new(100).with_persistence(&path).await;
+        assert!(restarted.get("query").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_flush_is_noop() {
+        let cache = EmbeddingCache::new(100);
+        cache.insert("query", vec![0.5]).await;
+        assert!(cache.flush().await.is_ok());
+        assert!(cache.persistence_path().is_none());
+    }
 }
@@ -0,0 +1,642 @@
+//! In-memory search index with incremental updates
+//!
+//! [`SearchIndex`] holds chunk embeddings by source file so search can run
+//! without reading the whole chunk table on every query. Files are added,
+//! replaced and removed one at a time with [`SearchIndex::upsert`] and
+//! [`SearchIndex::remove`]; the KB loader calls these for each file it adds,
+//! updates or deletes when built with
+//! [`IncrementalLoader::with_search_index`](crate::kb::loader::IncrementalLoader::with_search_index).
+//!
+//! Updates are copy-on-write: each change builds a new [`IndexSnapshot`] and
+//! swaps it in, so a search runs against one snapshot from start to finish
+//! and never sees a half-applied update.
+//!
+//! Every upsert and removal is also published as an [`IndexChange`] to
+//! receivers from [`SearchIndex::subscribe`], so caches built on top of the
+//! index can drop results the change may have made stale.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::kb::store::DocumentChunk;
+//! use xze_core::search::index::{IndexedDocument, SearchIndex};
+//! use xze_core::semantic::search::SearchConfig;
+//!
+//! let index = SearchIndex::new();
+//! index.upsert(IndexedDocument::new(
+//!     "docs/install.md",
+//!     vec![DocumentChunk::new(
+//!         "chunk_0".to_string(),
+//!         "Install with cargo".to_string(),
+//!         vec![1.0, 0.0],
+//!         serde_json::json!({}),
+//!     )],
+//! ));
+//!
+//! let results = index.search(&[1.0, 0.0], &SearchConfig::default()).unwrap();
+//! assert_eq!(results[0].source_file, "docs/install.md");
+//!
+//! index.remove("docs/install.md");
+//! assert_eq!(index.stats().chunks, 0);
+//! ```
+
+use crate::kb::store::DocumentChunk;
+use crate::semantic::search::{
+    check_metric, rank_chunks, ChunkSearchResult, SearchConfig, SearchError,
+};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+#[cfg(feature = "metrics")]
+use once_cell::sync::Lazy;
+
+#[cfg(feature = "metrics")]
+static INDEX_GAUGES: Lazy<(
+    prometheus::IntGauge,
+    prometheus::IntGauge,
+    prometheus::IntGauge,
+)> = Lazy::new(|| {
+    let documents = prometheus::IntGauge::new(
+        "xze_search_index_documents",
+        "Source files in the search index",
+    )
+    .expect("Failed to create search index document gauge");
+    let chunks = prometheus::IntGauge::new("xze_search_index_chunks", "Chunks in the search index")
+        .expect("Failed to create search index chunk gauge");
+    let updated = prometheus::IntGauge::new(
+        "xze_search_index_last_update_timestamp_seconds",
+        "Unix time of the last search index update",
+    )
+    .expect("Failed to create search index update gauge");
+    for gauge in [&documents, &chunks, &updated] {
+        prometheus::default_registry()
+            .register(Box::new(gauge.clone()))
+            .expect("Failed to register search index gauge");
+    }
+    (documents, chunks, updated)
+});
+
+/// All chunks of one source file
+#[derive(Debug, Clone)]
+pub struct IndexedDocument {
+    /// Source file path, the document's identifier in the index
+    pub path: String,
+    /// Chunks in document order
+    pub chunks: Vec<DocumentChunk>,
+}
+
+impl IndexedDocument {
+    /// Create a document from its path and chunks
+    pub fn new(path: impl Into<String>, chunks: Vec<DocumentChunk>) -> Self {
+        Self {
+            path: path.into(),
+            chunks,
+        }
+    }
+}
+
+/// A chunk with the numeric id assigned by the index
+#[derive(Debug)]
+struct IndexedChunk {
+    id: i64,
+    chunk: DocumentChunk,
+}
+
+/// Changes buffered for each subscriber before it starts lagging
+const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// A document added, replaced or removed from the index
+///
+/// `categories` and `repositories` come from the `category` and
+/// `repository` chunk metadata of both the old and the new version, so they
+/// cover every query filter the document matched before or after the
+/// change. They are empty when the chunks carry no such metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexChange {
+    /// Source file path
+    pub path: String,
+    /// Categories of the affected chunks
+    pub categories: BTreeSet<String>,
+    /// Repositories of the affected chunks
+    pub repositories: BTreeSet<String>,
+}
+
+impl IndexChange {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record<'a>(&mut self, chunks: impl IntoIterator<Item = &'a DocumentChunk>) {
+        for chunk in chunks {
+            let metadata_str = |key: &str| chunk.metadata.get(key).and_then(|v| v.as_str());
+            if let Some(category) = metadata_str("category") {
+                self.categories.insert(category.to_string());
+            }
+            if let Some(repository) = metadata_str("repository") {
+                self.repositories.insert(repository.to_string());
+            }
+        }
+    }
+}
+
+/// Size and freshness of a search index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexStats {
+    /// Number of source files
+    pub documents: usize,
+    /// Number of chunks across all files
+    pub chunks: usize,
+    /// Time of the last upsert or removal, if any
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+/// Immutable view of the index at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct IndexSnapshot {
+    documents: BTreeMap<String, Arc<Vec<IndexedChunk>>>,
+    chunks: usize,
+    last_updated: Option<DateTime<Utc>>,
+}
+
+impl IndexSnapshot {
+    /// Size and freshness of this snapshot
+    pub fn stats(&self) -> IndexStats {
+        IndexStats {
+            documents: self.documents.len(),
+            chunks: self.chunks,
+            last_updated: self.last_updated,
+        }
+    }
+
+    /// Whether `path` is indexed
+    pub fn contains(&self, path: &str) -> bool {
+        self.documents.contains_key(path)
+    }
+
+    /// Rank chunks by similarity to `query_embedding`
+    ///
+    /// Applies the same filters and limits as
+    /// [`search_with_chunks`](crate::semantic::search::search_with_chunks).
+    /// `title`, `category` and the `language` matched by
+    /// `config.language_filter` come from the chunk metadata; sentence
+    /// ranges are not tracked and are reported as `(0, 0)`. `config.alpha`
+    /// is ignored; use [`IndexSnapshot::search_hybrid`] to blend in BM25.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::MetricMismatch` if a chunk's `similarity_metric`
+    /// metadata names a different metric than `config`, and
+    /// `SearchError::SimilarityCalculation` if a chunk embedding cannot be
+    /// compared with the query, for example on a dimension mismatch.
+    pub fn search(
+        &self,
+        query_embedding: &[f32],
+        config: &SearchConfig,
+    ) -> Result<Vec<ChunkSearchResult>, SearchError> {
+        let candidates = self.candidates(Some(query_embedding), config)?;
+        Ok(rank_chunks(candidates, None, config))
+    }
+
+    /// Rank chunks by the hybrid score of `query` configured by `config.alpha`
+    ///
+    /// Without a `query_embedding`, chunks are ranked by BM25 alone. With
+    /// `config.alpha` unset this ranks like [`IndexSnapshot::search`].
+    ///
+    /// # Errors
+    ///
+    /// See [`IndexSnapshot::search`]; stored metrics are only checked when a
+    /// `query_embedding` is given.
+    pub fn search_hybrid(
+        &self,
+        query: &str,
+        query_embedding: Option<&[f32]>,
+        config: &SearchConfig,
+    ) -> Result<Vec<ChunkSearchResult>, SearchError> {
+        let candidates = self.candidates(query_embedding, config)?;
+        Ok(rank_chunks(candidates, Some(query), config))
+    }
+
+    /// Chunks passing the filters in `config`, scored by `query_embedding`
+    fn candidates(
+        &self,
+        query_embedding: Option<&[f32]>,
+        config: &SearchConfig,
+    ) -> Result<Vec<ChunkSearchResult>, SearchError> {
+        let mut results = Vec::new();
+
+        for (path, chunks) in &self.documents {
+            for (chunk_index, indexed) in chunks.iter().enumerate() {
+                let metadata_str = |key: &str| {
+                    indexed
+                        .chunk
+                        .metadata
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                };
+                let category = metadata_str("category");
+                if config.category_filter.is_some() && category != config.category_filter {
+                    continue;
+                }
+                if let Some(language) = &config.language_filter {
+                    let matches = metadata_str("language")
+                        .is_some_and(|found| found.eq_ignore_ascii_case(language));
+                    if !matches {
+                        continue;
+                    }
+                }
+
+                let vector_score = match query_embedding {
+                    Some(query_embedding) => {
+                        check_metric(
+                            metadata_str("similarity_metric").as_deref(),
+                            config.similarity_metric,
+                        )?;
+                        Some(
+                            config
+                                .similarity_metric
+                                .similarity(query_embedding, &indexed.chunk.embedding)?,
+                        )
+                    }
+                    None => None,
+                };
+                results.push(ChunkSearchResult {
+                    id: indexed.id,
+                    source_file: path.clone(),
+                    content: indexed.chunk.content.clone(),
+                    similarity: vector_score.unwrap_or(0.0),
+                    chunk_index: chunk_index as i32,
+                    total_chunks: chunks.len() as i32,
+                    title: metadata_str("title"),
+                    category,
+                    sentence_range: (0, 0),
+                    avg_chunk_similarity: 0.0,
+                    vector_score,
+                    lexical_score: None,
+                });
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Search index that can be updated one file at a time
+#[derive(Debug)]
+pub struct SearchIndex {
+    snapshot: RwLock<Arc<IndexSnapshot>>,
+    next_id: AtomicI64,
+    changes: broadcast::Sender<IndexChange>,
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self {
+            snapshot: RwLock::default(),
+            next_id: AtomicI64::default(),
+            changes: broadcast::channel(CHANGE_CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl SearchIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive a change for every later upsert and removal
+    ///
+    /// A receiver that falls more than 256 changes behind gets
+    /// [`broadcast::error::RecvError::Lagged`] and should treat everything
+    /// derived from the index as stale.
+    pub fn subscribe(&self) -> broadcast::Receiver<IndexChange> {
+        self.changes.subscribe()
+    }
+
+    /// Current snapshot of the index
+    ///
+    /// The snapshot is unaffected by later updates.
+    pub fn snapshot(&self) -> Arc<IndexSnapshot> {
+        self.snapshot.read().clone()
+    }
+
+    /// Size and freshness of the index
+    pub fn stats(&self) -> IndexStats {
+        self.snapshot().stats()
+    }
+
+    /// Add a document, replacing any document with the same path
+    ///
+    /// A document without chunks removes the path instead.
+    pub fn upsert(&self, doc: IndexedDocument) {
+        if doc.chunks.is_empty() {
+            self.remove(&doc.path);
+            return;
+        }
+
+        let mut change = IndexChange::new(&doc.path);
+        change.record(&doc.chunks);
+
+        let chunks: Vec<IndexedChunk> = doc
+            .chunks
+            .into_iter()
+            .map(|chunk| IndexedChunk {
+                id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+                chunk,
+            })
+            .collect();
+        debug!("Indexing {} chunks for {}", chunks.len(), doc.path);
+
+        self.update(|next| {
+            let added = chunks.len();
+            if let Some(old) = next.documents.insert(doc.path, Arc::new(chunks)) {
+                change.record(old.iter().map(|indexed| &indexed.chunk));
+                next.chunks -= old.len();
+            }
+            next.chunks += added;
+            true
+        });
+        self.publish(change);
+    }
+
+    /// Remove the document at `path`
+    ///
+    /// Returns `true` if the path was indexed.
+    pub fn remove(&self, path: &str) -> bool {
+        let mut change = IndexChange::new(path);
+        let removed = self.update(|next| match next.documents.remove(path) {
+            Some(old) => {
+                debug!("Removed {} chunks for {} from index", old.len(), path);
+                change.record(old.iter().map(|indexed| &indexed.chunk));
+                next.chunks -= old.len();
+                true
+            }
+            None => false,
+        });
+        if removed {
+            self.publish(change);
+        }
+        removed
+    }
+
+    /// Rank chunks in the current snapshot by similarity to `query_embedding`
+    ///
+    /// # Errors
+    ///
+    /// See [`IndexSnapshot::search`].
+    pub fn search(
+        &self,
+        query_embedding: &[f32],
+        config: &SearchConfig,
+    ) -> Result<Vec<ChunkSearchResult>, SearchError> {
+        self.snapshot().search(query_embedding, config)
+    }
+
+    /// Rank chunks in the current snapshot by the hybrid score of `query`
+    ///
+    /// # Errors
+    ///
+    /// See [`IndexSnapshot::search_hybrid`].
+    pub fn search_hybrid(
+        &self,
+        query: &str,
+        query_embedding: Option<&[f32]>,
+        config: &SearchConfig,
+    ) -> Result<Vec<ChunkSearchResult>, SearchError> {
+        self.snapshot()
+            .search_hybrid(query, query_embedding, config)
+    }
+
+    /// Notify subscribers; having none is not an error
+    fn publish(&self, change: IndexChange) {
+        let _ = self.changes.send(change);
+    }
+
+    /// Apply `change` to a copy of the snapshot and publish it if it
+    /// reports a change
+    fn update<F>(&self, change: F) -> bool
+    where
+        F: FnOnce(&mut IndexSnapshot) -> bool,
+    {
+        let mut current = self.snapshot.write();
+        let mut next = IndexSnapshot::clone(&current);
+        if !change(&mut next) {
+            return false;
+        }
+        next.last_updated = Some(Utc::now());
+
+        #[cfg(feature = "metrics")]
+        {
+            let (documents, chunks, updated) = &*INDEX_GAUGES;
+            documents.set(next.documents.len() as i64);
+            chunks.set(next.chunks as i64);
+            updated.set(Utc::now().timestamp());
+        }
+
+        *current = Arc::new(next);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::similarity::SimilarityMetric;
+
+    fn chunk(content: &str, embedding: Vec<f32>) -> DocumentChunk {
+        DocumentChunk::new(
+            content.to_string(),
+            content.to_string(),
+            embedding,
+            serde_json::json!({ "category": "tutorial" }),
+        )
+    }
+
+    #[test]
+    fn test_upsert_replaces_and_remove_deletes() {
+        let index = SearchIndex::new();
+        assert_eq!(index.stats(), IndexStats::default());
+
+        index.upsert(IndexedDocument::new(
+            "a.md",
+            vec![chunk("one", vec![1.0, 0.0]), chunk("two", vec![0.0, 1.0])],
+        ));
+        index.upsert(IndexedDocument::new(
+            "b.md",
+            vec![chunk("three", vec![1.0, 1.0])],
+        ));
+        assert_eq!(index.stats().documents, 2);
+        assert_eq!(index.stats().chunks, 3);
+        assert!(index.stats().last_updated.is_some());
+
+        index.upsert(IndexedDocument::new(
+            "a.md",
+            vec![chunk("new", vec![1.0, 0.0])],
+        ));
+        assert_eq!(index.stats().chunks, 2);
+
+        assert!(index.remove("a.md"));
+        assert!(!index.remove("a.md"));
+        assert_eq!(index.stats().documents, 1);
+        assert_eq!(index.stats().chunks, 1);
+    }
+
+    #[test]
+    fn test_search_ranks_and_filters() {
+        let index = SearchIndex::new();
+        index.upsert(IndexedDocument::new(
+            "a.md",
+            vec![
+                chunk("exact", vec![1.0, 0.0]),
+                chunk("orthogonal", vec![0.0, 1.0]),
+            ],
+        ));
+        index.upsert(IndexedDocument::new(
+            "b.md",
+            vec![chunk("close", vec![1.0, 0.2])],
+        ));
+
+        let config = SearchConfig {
+            min_similarity: 0.5,
+            ..Default::default()
+        };
+        let results = index.search(&[1.0, 0.0], &config).unwrap();
+        let contents: Vec<&str> = results.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["exact", "close"]);
+        assert_eq!(results[0].total_chunks, 2);
+        assert_eq!(results[0].category.as_deref(), Some("tutorial"));
+
+        let config = SearchConfig {
+            category_filter: Some("reference".to_string()),
+            ..Default::default()
+        };
+        assert!(index.search(&[1.0, 0.0], &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_filters_by_language() {
+        let index = SearchIndex::new();
+        let tagged = |content: &str, language: &str| {
+            DocumentChunk::new(
+                content.to_string(),
+                content.to_string(),
+                vec![1.0, 0.0],
+                serde_json::json!({ "language": language }),
+            )
+        };
+        index.upsert(IndexedDocument::new(
+            "en.md",
+            vec![tagged("english", "eng")],
+        ));
+        index.upsert(IndexedDocument::new("de.md", vec![tagged("german", "deu")]));
+        index.upsert(IndexedDocument::new(
+            "code.md",
+            vec![chunk("untagged", vec![1.0, 0.0])],
+        ));
+
+        let config = SearchConfig {
+            language_filter: Some("DEU".to_string()),
+            ..Default::default()
+        };
+        let results = index.search(&[1.0, 0.0], &config).unwrap();
+        let contents: Vec<&str> = results.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["german"]);
+
+        let all = index.search(&[1.0, 0.0], &SearchConfig::default()).unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn test_search_uses_configured_metric() {
+        let index = SearchIndex::new();
+        let dot = |content: &str, embedding: Vec<f32>| {
+            DocumentChunk::new(
+                content.to_string(),
+                content.to_string(),
+                embedding,
+                serde_json::json!({ "similarity_metric": "dot_product" }),
+            )
+        };
+        index.upsert(IndexedDocument::new(
+            "a.md",
+            vec![dot("aligned", vec![1.0, 0.0]), dot("long", vec![3.0, 3.0])],
+        ));
+
+        // Chunks recorded for dot product cannot be ranked with cosine
+        assert!(matches!(
+            index.search(&[1.0, 0.0], &SearchConfig::default()),
+            Err(SearchError::MetricMismatch { .. })
+        ));
+
+        let config = SearchConfig {
+            similarity_metric: SimilarityMetric::DotProduct,
+            ..Default::default()
+        };
+        let results = index.search(&[1.0, 0.0], &config).unwrap();
+        let contents: Vec<&str> = results.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["long", "aligned"]);
+        assert_eq!(results[0].similarity, 3.0);
+    }
+
+    #[test]
+    fn test_snapshot_is_isolated_from_later_updates() {
+        let index = SearchIndex::new();
+        index.upsert(IndexedDocument::new("a.md", vec![chunk("one", vec![1.0])]));
+
+        let snapshot = index.snapshot();
+        index.remove("a.md");
+
+        assert!(snapshot.contains("a.md"));
+        assert!(!index.snapshot().contains("a.md"));
+        assert_eq!(
+            snapshot
+                .search(&[1.0], &SearchConfig::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_changes_are_published() {
+        let index = SearchIndex::new();
+        let mut changes = index.subscribe();
+
+        let mut reference = chunk("ref", vec![1.0]);
+        reference.metadata = serde_json::json!({ "category": "reference", "repository": "xze" });
+        index.upsert(IndexedDocument::new("a.md", vec![reference]));
+        index.upsert(IndexedDocument::new("a.md", vec![chunk("tut", vec![1.0])]));
+        assert!(!index.remove("missing.md"));
+        index.remove("a.md");
+
+        let change = changes.try_recv().unwrap();
+        assert_eq!(change.path, "a.md");
+        assert_eq!(change.categories, BTreeSet::from(["reference".to_string()]));
+        assert_eq!(change.repositories, BTreeSet::from(["xze".to_string()]));
+
+        // A replacement reports both the old and the new categories
+        let change = changes.try_recv().unwrap();
+        assert_eq!(
+            change.categories,
+            BTreeSet::from(["reference".to_string(), "tutorial".to_string()])
+        );
+
+        let change = changes.try_recv().unwrap();
+        assert_eq!(change.categories, BTreeSet::from(["tutorial".to_string()]));
+        assert!(changes.try_recv().is_err());
+    }
+}
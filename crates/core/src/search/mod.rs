@@ -4,6 +4,11 @@
 //! - Query embedding caching for performance optimization
 //! - Database query optimization
 //! - Pagination support
+//! - Text normalization and query language detection
+//! - Relevance reranking of retrieved candidates
+//! - BM25 lexical scoring for hybrid ranking
+//! - Synonym expansion shared with keyword extraction
+//! - An in-memory index updated file by file from KB loads
 //!
 //! # Examples
 //!
@@ -26,6 +31,16 @@
 //! # }
 //! ```
 
+pub mod bm25;
 pub mod embedding_cache;
+pub mod index;
+pub mod normalize;
+pub mod rerank;
+pub mod synonyms;
 
+pub use bm25::Bm25;
 pub use embedding_cache::EmbeddingCache;
+pub use index::{IndexChange, IndexSnapshot, IndexStats, IndexedDocument, SearchIndex};
+pub use normalize::{detect_language, Language, NormalizerConfig, TextNormalizer};
+pub use rerank::{RelevanceScorer, RerankCandidate, Reranker, MAX_RERANK_TOP_N};
+pub use synonyms::SynonymMap;
@@ -0,0 +1,553 @@
+//! Text normalization and query language detection for search
+//!
+//! This module provides a single normalization pipeline that must be applied
+//! to both indexed content and search queries so that both sides are compared
+//! in the same form. The pipeline runs, in order:
+//!
+//! 1. Unicode NFKC normalization (compatibility forms such as ligatures)
+//! 2. Lowercasing
+//! 3. Optional diacritic folding (`café` becomes `cafe`)
+//! 4. Tokenization on non-alphanumeric boundaries
+//! 5. Optional per-language light stemming (`running` becomes `run`)
+//!
+//! Language detection is a lightweight stopword heuristic intended for
+//! analytics and stemmer selection, not a general-purpose classifier.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::search::normalize::{NormalizerConfig, TextNormalizer};
+//!
+//! let normalizer = TextNormalizer::new(NormalizerConfig {
+//!     fold_diacritics: true,
+//!     stemming: true,
+//!     ..Default::default()
+//! });
+//!
+//! assert!(normalizer.matches("Café", "the cafe on the corner"));
+//! assert!(normalizer.matches("run", "Running the tests"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Languages supported by stemming and detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English
+    #[default]
+    English,
+    /// Spanish
+    Spanish,
+    /// French
+    French,
+    /// German
+    German,
+}
+
+impl Language {
+    /// All supported languages
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+    ];
+
+    /// ISO 639-1 code for the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+        }
+    }
+
+    fn stopwords(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => &[
+                "the", "and", "is", "are", "of", "to", "in", "how", "what", "with", "for", "a",
+                "an", "this", "that", "do", "does", "i", "my",
+            ],
+            Language::Spanish => &[
+                "el", "la", "los", "las", "de", "que", "y", "en", "un", "una", "por", "para",
+                "con", "como", "es", "del", "se", "mi",
+            ],
+            Language::French => &[
+                "le", "la", "les", "de", "des", "et", "est", "un", "une", "du", "pour", "avec",
+                "comment", "dans", "que", "je", "mon", "sur",
+            ],
+            Language::German => &[
+                "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "wie", "für",
+                "auf", "den", "dem", "ich", "zu", "von",
+            ],
+        }
+    }
+
+    fn marker_chars(&self) -> &'static [char] {
+        match self {
+            Language::English => &[],
+            Language::Spanish => &['ñ', '¿', '¡', 'á', 'í', 'ó', 'ú'],
+            Language::French => &['ç', 'œ', 'è', 'ê', 'à', 'â', 'î', 'ô', 'û'],
+            Language::German => &['ß', 'ä', 'ö', 'ü'],
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "es" | "spanish" => Ok(Language::Spanish),
+            "fr" | "french" => Ok(Language::French),
+            "de" | "german" => Ok(Language::German),
+            other => Err(format!("Unsupported language: {}", other)),
+        }
+    }
+}
+
+/// Configuration for the text normalization pipeline
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::search::normalize::NormalizerConfig;
+///
+/// let config = NormalizerConfig::default();
+/// assert!(config.lowercase);
+/// assert!(config.unicode_nfkc);
+/// assert!(!config.fold_diacritics);
+/// assert!(!config.stemming);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizerConfig {
+    /// Lowercase all text
+    #[serde(
+        default = "default_true",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub lowercase: bool,
+    /// Apply Unicode NFKC normalization
+    #[serde(
+        default = "default_true",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub unicode_nfkc: bool,
+    /// Strip diacritics (accents) from letters
+    #[serde(default, deserialize_with = "crate::config::deserialize_scalar")]
+    pub fold_diacritics: bool,
+    /// Apply light stemming for the configured language
+    #[serde(default, deserialize_with = "crate::config::deserialize_scalar")]
+    pub stemming: bool,
+    /// Language used for stemming when no language is detected
+    #[serde(default)]
+    pub language: Language,
+    /// Detect the language of each text and stem with that language
+    #[serde(default, deserialize_with = "crate::config::deserialize_scalar")]
+    pub detect_language: bool,
+}
+
+impl Default for NormalizerConfig {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            unicode_nfkc: true,
+            fold_diacritics: false,
+            stemming: false,
+            language: Language::English,
+            detect_language: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Normalizes text for indexing and querying
+///
+/// The same normalizer instance (or an identical configuration) must be used
+/// for content and queries, otherwise matching becomes inconsistent.
+#[derive(Debug, Clone, Default)]
+pub struct TextNormalizer {
+    config: NormalizerConfig,
+}
+
+impl TextNormalizer {
+    /// Create a new normalizer
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Normalization configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns a new TextNormalizer instance
+    pub fn new(config: NormalizerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the normalizer configuration
+    pub fn config(&self) -> &NormalizerConfig {
+        &self.config
+    }
+
+    /// Normalize text into a space-separated string of tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to normalize
+    ///
+    /// # Returns
+    ///
+    /// Returns the normalized tokens joined by single spaces
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::search::normalize::{NormalizerConfig, TextNormalizer};
+    ///
+    /// let normalizer = TextNormalizer::new(NormalizerConfig::default());
+    /// assert_eq!(normalizer.normalize("Hello,  WORLD!"), "hello world");
+    /// ```
+    pub fn normalize(&self, text: &str) -> String {
+        self.tokens(text).join(" ")
+    }
+
+    /// Normalize text into tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to tokenize
+    ///
+    /// # Returns
+    ///
+    /// Returns normalized tokens in input order
+    pub fn tokens(&self, text: &str) -> Vec<String> {
+        let language = if self.config.detect_language {
+            detect_language(text).unwrap_or(self.config.language)
+        } else {
+            self.config.language
+        };
+
+        let mut normalized: String = if self.config.unicode_nfkc {
+            text.nfkc().collect()
+        } else {
+            text.to_string()
+        };
+
+        if self.config.lowercase {
+            normalized = normalized.to_lowercase();
+        }
+
+        if self.config.fold_diacritics {
+            normalized = fold_diacritics(&normalized);
+        }
+
+        normalized
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                if self.config.stemming {
+                    stem(token, language)
+                } else {
+                    token.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Check whether every query token appears in the content
+    ///
+    /// Both sides are normalized with this normalizer's configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search query
+    /// * `content` - Content to match against
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the query has at least one token and all of its
+    /// tokens occur in the content
+    pub fn matches(&self, query: &str, content: &str) -> bool {
+        let query_tokens = self.tokens(query);
+        if query_tokens.is_empty() {
+            return false;
+        }
+
+        let content_tokens: HashSet<String> = self.tokens(content).into_iter().collect();
+        query_tokens
+            .iter()
+            .all(|token| content_tokens.contains(token))
+    }
+}
+
+/// Remove diacritics from text
+///
+/// Decomposes characters and drops combining marks, and expands a few
+/// letters that have no decomposition (for example `ß` becomes `ss`).
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::search::normalize::fold_diacritics;
+///
+/// assert_eq!(fold_diacritics("café crème"), "cafe creme");
+/// assert_eq!(fold_diacritics("straße"), "strasse");
+/// ```
+pub fn fold_diacritics(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.nfd() {
+        if is_combining_mark(c) {
+            continue;
+        }
+        match c {
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'ø' => folded.push('o'),
+            'Ø' => folded.push('O'),
+            'đ' => folded.push('d'),
+            'ł' => folded.push('l'),
+            _ => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Detect the language of a short text
+///
+/// Scores each supported language by stopword hits and language-specific
+/// characters. Returns `None` when there is no signal or the top two
+/// languages tie.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::search::normalize::{detect_language, Language};
+///
+/// assert_eq!(detect_language("how to configure the server"), Some(Language::English));
+/// assert_eq!(detect_language("cómo configurar el servidor"), Some(Language::Spanish));
+/// assert_eq!(detect_language("kubernetes"), None);
+/// ```
+pub fn detect_language(text: &str) -> Option<Language> {
+    let lowered = text.to_lowercase();
+    let tokens: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut scores: Vec<(Language, usize)> = Language::ALL
+        .iter()
+        .map(|lang| {
+            let stopword_hits = tokens
+                .iter()
+                .filter(|t| lang.stopwords().contains(t))
+                .count();
+            let marker_hits = lowered
+                .chars()
+                .filter(|c| lang.marker_chars().contains(c))
+                .count();
+            (*lang, stopword_hits * 2 + marker_hits)
+        })
+        .collect();
+
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    match (scores.first(), scores.get(1)) {
+        (Some((lang, best)), Some((_, second))) if *best > 0 && best > second => Some(*lang),
+        _ => None,
+    }
+}
+
+/// Apply light suffix stemming to a single lowercase token
+///
+/// The stemmers are intentionally conservative: they strip common
+/// inflectional suffixes only and never touch tokens shorter than four
+/// characters.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::search::normalize::{stem, Language};
+///
+/// assert_eq!(stem("running", Language::English), "run");
+/// assert_eq!(stem("libraries", Language::English), "library");
+/// assert_eq!(stem("servidores", Language::Spanish), "servidor");
+/// ```
+pub fn stem(token: &str, language: Language) -> String {
+    if token.chars().count() < 4 || token.chars().any(|c| c.is_ascii_digit()) {
+        return token.to_string();
+    }
+
+    match language {
+        Language::English => stem_english(token),
+        Language::Spanish => strip_first_suffix(token, &["mente", "es", "s"], 4),
+        Language::French => strip_first_suffix(token, &["ement", "es", "s", "x"], 4),
+        Language::German => strip_first_suffix(token, &["ungen", "en", "er", "e", "s"], 4),
+    }
+}
+
+fn strip_first_suffix(token: &str, suffixes: &[&str], min_stem: usize) -> String {
+    for suffix in suffixes {
+        if let Some(stem) = token.strip_suffix(suffix) {
+            if stem.chars().count() >= min_stem {
+                return stem.to_string();
+            }
+        }
+    }
+    token.to_string()
+}
+
+fn stem_english(token: &str) -> String {
+    let mut word = token.to_string();
+
+    // Plurals
+    if let Some(stem) = word.strip_suffix("sses") {
+        word = format!("{}ss", stem);
+    } else if let Some(stem) = word.strip_suffix("ies") {
+        if stem.len() > 1 {
+            word = format!("{}y", stem);
+        }
+    } else if word.ends_with('s')
+        && !word.ends_with("ss")
+        && !word.ends_with("us")
+        && !word.ends_with("is")
+    {
+        word.pop();
+    }
+
+    // Progressive and past tense
+    for suffix in ["ing", "ed"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.len() >= 3 && stem.chars().any(is_vowel) {
+                word = undouble(stem);
+                break;
+            }
+        }
+    }
+
+    // Adverbs
+    if let Some(stem) = word.strip_suffix("ly") {
+        if stem.len() >= 4 {
+            word = stem.to_string();
+        }
+    }
+
+    word
+}
+
+fn undouble(stem: &str) -> String {
+    let chars: Vec<char> = stem.chars().collect();
+    let n = chars.len();
+    if n >= 2
+        && chars[n - 1] == chars[n - 2]
+        && !is_vowel(chars[n - 1])
+        && !matches!(chars[n - 1], 'l' | 's' | 'z')
+    {
+        chars[..n - 1].iter().collect()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalizer(fold: bool, stemming: bool) -> TextNormalizer {
+        TextNormalizer::new(NormalizerConfig {
+            fold_diacritics: fold,
+            stemming,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_diacritic_folding_matches_when_enabled() {
+        assert!(normalizer(true, false).matches("café", "best cafe in town"));
+        assert!(normalizer(true, false).matches("cafe", "best café in town"));
+        assert!(!normalizer(false, false).matches("café", "best cafe in town"));
+    }
+
+    #[test]
+    fn test_stemming_unifies_running_and_run() {
+        let n = normalizer(false, true);
+        assert_eq!(n.normalize("running"), n.normalize("run"));
+        assert!(n.matches("running", "how to run the tests"));
+        assert!(!normalizer(false, false).matches("running", "how to run the tests"));
+    }
+
+    #[test]
+    fn test_nfkc_and_lowercase() {
+        let n = TextNormalizer::default();
+        // U+FB01 LATIN SMALL LIGATURE FI
+        assert_eq!(n.normalize("\u{FB01}le NAME"), "file name");
+    }
+
+    #[test]
+    fn test_english_stemmer() {
+        assert_eq!(stem("runs", Language::English), "run");
+        assert_eq!(stem("stopped", Language::English), "stop");
+        assert_eq!(stem("classes", Language::English), "class");
+        assert_eq!(stem("status", Language::English), "status");
+        assert_eq!(stem("quickly", Language::English), "quick");
+        assert_eq!(stem("falling", Language::English), "fall");
+    }
+
+    #[test]
+    fn test_stemming_uses_detected_language() {
+        let n = TextNormalizer::new(NormalizerConfig {
+            stemming: true,
+            detect_language: true,
+            ..Default::default()
+        });
+        assert_eq!(n.normalize("los servidores"), "los servidor");
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("comment configurer le serveur"),
+            Some(Language::French)
+        );
+        assert_eq!(
+            detect_language("wie ist der Server konfiguriert"),
+            Some(Language::German)
+        );
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn test_language_parse_and_display() {
+        assert_eq!("de".parse::<Language>().unwrap(), Language::German);
+        assert_eq!(Language::Spanish.to_string(), "es");
+        assert!("xx".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_empty_query_never_matches() {
+        assert!(!TextNormalizer::default().matches("  !! ", "anything"));
+    }
+}
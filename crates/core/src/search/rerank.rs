@@ -0,0 +1,388 @@
+//! Relevance reranking of search candidates
+//!
+//! Vector search ranks candidates by embedding similarity alone. A rerank
+//! stage scores each (query, document) pair directly with a
+//! [`RelevanceScorer`], usually a relevance prompt sent through the AI
+//! service, and re-sorts the top candidates by that score. Scoring is
+//! expensive, so the number of candidates is capped at
+//! [`MAX_RERANK_TOP_N`] and scores are cached per (query, document) pair;
+//! the cache key includes a hash of the document text, so an edited
+//! document reloaded under the same id is scored again.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use xze_core::ai::AIAnalysisService;
+//! use xze_core::config::ModelConfig;
+//! use xze_core::search::rerank::{RerankCandidate, Reranker};
+//!
+//! # async fn example() -> xze_core::Result<()> {
+//! let service = AIAnalysisService::new(
+//!     "http://localhost:11434".to_string(),
+//!     ModelConfig::default(),
+//! );
+//! let reranker = Reranker::new(Arc::new(service));
+//!
+//! let documents = vec!["Install with cargo", "Configure logging"];
+//! let ranked = reranker
+//!     .rerank("how do I install it", documents, 10, |doc| RerankCandidate {
+//!         id: doc.to_string(),
+//!         text: doc.to_string(),
+//!     })
+//!     .await?;
+//!
+//! for (doc, score) in ranked {
+//!     println!("{}: {:?}", doc, score);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    ai::AIAnalysisService,
+    error::{Result, XzeError},
+    kb::hash::calculate_content_hash,
+};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tracing::debug;
+
+/// Maximum number of candidates scored per query
+pub const MAX_RERANK_TOP_N: usize = 50;
+
+/// Characters of each document included in the relevance prompt
+const MAX_DOCUMENT_CHARS: usize = 2000;
+
+/// Default number of cached (query, document) scores
+const DEFAULT_CACHE_CAPACITY: u64 = 10_000;
+
+/// Default lifetime of a cached score
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// Reranking settings for the search API
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::search::rerank::RerankConfig;
+///
+/// let config: RerankConfig = serde_yaml::from_str("model: llama3\n").unwrap();
+/// assert_eq!(config.max_top_n, 50);
+/// assert_eq!(config.cache_ttl_secs, 3600);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RerankConfig {
+    /// Model that rates relevance
+    pub model: String,
+    /// Most candidates a request may rerank, at most [`MAX_RERANK_TOP_N`]
+    #[serde(
+        default = "default_max_top_n",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub max_top_n: usize,
+    /// Seconds a cached score is kept
+    #[serde(
+        default = "default_cache_ttl_secs",
+        deserialize_with = "crate::config::deserialize_scalar"
+    )]
+    pub cache_ttl_secs: u64,
+}
+
+fn default_max_top_n() -> usize {
+    MAX_RERANK_TOP_N
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    DEFAULT_CACHE_TTL.as_secs()
+}
+
+/// Scores how relevant a document is to a query
+#[async_trait]
+pub trait RelevanceScorer: Send + Sync {
+    /// Relevance of `document` to `query`, from 0.0 to 1.0
+    async fn score(&self, query: &str, document: &str) -> Result<f32>;
+}
+
+#[async_trait]
+impl RelevanceScorer for AIAnalysisService {
+    async fn score(&self, query: &str, document: &str) -> Result<f32> {
+        let response = self
+            .generate_text(&relevance_prompt(query, document))
+            .await?;
+        parse_relevance(&response)
+    }
+}
+
+/// Prompt asking the model to rate relevance from 0 to 10
+pub fn relevance_prompt(query: &str, document: &str) -> String {
+    let document: String = document.chars().take(MAX_DOCUMENT_CHARS).collect();
+    format!(
+        "Rate how well the document answers the search query on a scale from 0 \
+         (unrelated) to 10 (answers it fully). Reply with the number only.\n\n\
+         Query: {}\n\nDocument:\n{}\n\nRating:",
+        query.trim(),
+        document.trim()
+    )
+}
+
+/// Parse a 0 to 10 rating from a model response into a 0.0 to 1.0 score
+///
+/// # Errors
+///
+/// Returns an AI error if the response contains no number or the number
+/// is outside 0 to 10.
+pub fn parse_relevance(response: &str) -> Result<f32> {
+    let rating = response
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|token| token.trim_matches('.').parse::<f32>().ok())
+        .ok_or_else(|| {
+            XzeError::ai(format!(
+                "Relevance response contains no rating: {}",
+                response.trim()
+            ))
+        })?;
+
+    if !(0.0..=10.0).contains(&rating) {
+        return Err(XzeError::ai(format!(
+            "Relevance rating out of range: {}",
+            rating
+        )));
+    }
+    Ok(rating / 10.0)
+}
+
+/// A candidate to rerank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RerankCandidate {
+    /// Stable document identifier, used as part of the cache key
+    pub id: String,
+    /// Text shown to the scorer; its hash is part of the cache key
+    pub text: String,
+}
+
+/// Re-sorts search candidates by a relevance score
+#[derive(Clone)]
+pub struct Reranker {
+    scorer: Arc<dyn RelevanceScorer>,
+    /// Scores keyed by query, document id and document text hash
+    cache: Cache<(String, String, String), f32>,
+    max_top_n: usize,
+}
+
+impl std::fmt::Debug for Reranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reranker")
+            .field("cached_scores", &self.cache.entry_count())
+            .field("max_top_n", &self.max_top_n)
+            .finish()
+    }
+}
+
+impl Reranker {
+    /// Create a reranker with the default score cache
+    pub fn new(scorer: Arc<dyn RelevanceScorer>) -> Self {
+        Self::with_cache(scorer, DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL)
+    }
+
+    /// Create a reranker caching up to `capacity` scores for `ttl`
+    pub fn with_cache(scorer: Arc<dyn RelevanceScorer>, capacity: u64, ttl: Duration) -> Self {
+        let cache = Cache::builder()
+            .max_capacity(capacity)
+            .time_to_live(ttl)
+            .build();
+        Self {
+            scorer,
+            cache,
+            max_top_n: MAX_RERANK_TOP_N,
+        }
+    }
+
+    /// Create a reranker with the cap and cache lifetime from `config`
+    pub fn from_config(scorer: Arc<dyn RelevanceScorer>, config: &RerankConfig) -> Self {
+        Self::with_cache(
+            scorer,
+            DEFAULT_CACHE_CAPACITY,
+            Duration::from_secs(config.cache_ttl_secs),
+        )
+        .with_max_top_n(config.max_top_n)
+    }
+
+    /// Score at most `max_top_n` candidates per query, capped at
+    /// [`MAX_RERANK_TOP_N`]
+    pub fn with_max_top_n(mut self, max_top_n: usize) -> Self {
+        self.max_top_n = max_top_n.min(MAX_RERANK_TOP_N);
+        self
+    }
+
+    /// Score one candidate, using the cache when possible
+    ///
+    /// # Errors
+    ///
+    /// Returns the scorer's error; failed scores are not cached.
+    pub async fn score(&self, query: &str, candidate: &RerankCandidate) -> Result<f32> {
+        let key = (
+            query.to_string(),
+            candidate.id.clone(),
+            calculate_content_hash(&candidate.text),
+        );
+        if let Some(score) = self.cache.get(&key).await {
+            return Ok(score);
+        }
+
+        let score = self.scorer.score(query, &candidate.text).await?;
+        self.cache.insert(key, score).await;
+        Ok(score)
+    }
+
+    /// Rerank the first `top_n` items by relevance to `query`
+    ///
+    /// `items` must already be in retrieval order. The first `top_n` items,
+    /// capped at the reranker's maximum, are scored concurrently and sorted by
+    /// score, highest first; ties keep their retrieval order. The remaining
+    /// items follow unchanged with no score.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first scoring error.
+    pub async fn rerank<T, F>(
+        &self,
+        query: &str,
+        items: Vec<T>,
+        top_n: usize,
+        candidate: F,
+    ) -> Result<Vec<(T, Option<f32>)>>
+    where
+        F: Fn(&T) -> RerankCandidate,
+    {
+        let top_n = top_n.min(self.max_top_n).min(items.len());
+        let mut items = items.into_iter();
+        let head: Vec<T> = items.by_ref().take(top_n).collect();
+
+        let candidates: Vec<RerankCandidate> = head.iter().map(&candidate).collect();
+        let scores = try_join_all(candidates.iter().map(|c| self.score(query, c))).await?;
+        debug!("Reranked {} candidates for query '{}'", top_n, query);
+
+        let mut ranked: Vec<(T, Option<f32>)> = head
+            .into_iter()
+            .zip(scores)
+            .map(|(item, score)| (item, Some(score)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.extend(items.map(|item| (item, None)));
+        Ok(ranked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Scores documents by their length and counts calls
+    #[derive(Default)]
+    struct LengthScorer {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RelevanceScorer for LengthScorer {
+        async fn score(&self, _query: &str, document: &str) -> Result<f32> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(document.len() as f32 / 100.0)
+        }
+    }
+
+    fn candidate(doc: &&str) -> RerankCandidate {
+        RerankCandidate {
+            id: doc.to_string(),
+            text: doc.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_relevance() {
+        assert_eq!(parse_relevance("8").unwrap(), 0.8);
+        assert_eq!(parse_relevance("Rating: 7.5/10").unwrap(), 0.75);
+        assert_eq!(parse_relevance(" 10.\n").unwrap(), 1.0);
+        assert!(parse_relevance("very relevant").is_err());
+        assert!(parse_relevance("42").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rerank_sorts_top_n_and_keeps_the_rest() {
+        let reranker = Reranker::new(Arc::new(LengthScorer::default()));
+        let items = vec!["a", "ccc", "bb", "dddd"];
+
+        let ranked = reranker.rerank("query", items, 3, candidate).await.unwrap();
+
+        let order: Vec<&str> = ranked.iter().map(|(doc, _)| *doc).collect();
+        assert_eq!(order, vec!["ccc", "bb", "a", "dddd"]);
+        assert_eq!(ranked[0].1, Some(0.03));
+        assert_eq!(ranked[3].1, None);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_respects_configured_cap() {
+        let config = RerankConfig {
+            model: "llama3".to_string(),
+            max_top_n: 2,
+            cache_ttl_secs: 60,
+        };
+        let reranker = Reranker::from_config(Arc::new(LengthScorer::default()), &config);
+
+        let ranked = reranker
+            .rerank("query", vec!["a", "ccc", "bb"], 10, candidate)
+            .await
+            .unwrap();
+
+        let order: Vec<&str> = ranked.iter().map(|(doc, _)| *doc).collect();
+        assert_eq!(order, vec!["ccc", "a", "bb"]);
+        assert_eq!(ranked[2].1, None);
+    }
+
+    #[tokio::test]
+    async fn test_scores_are_cached_per_query_and_document() {
+        let scorer = Arc::new(LengthScorer::default());
+        let reranker = Reranker::new(scorer.clone());
+        let items = vec!["a", "bb"];
+
+        reranker
+            .rerank("query", items.clone(), 10, candidate)
+            .await
+            .unwrap();
+        reranker
+            .rerank("query", items.clone(), 10, candidate)
+            .await
+            .unwrap();
+        assert_eq!(scorer.calls.load(Ordering::SeqCst), 2);
+
+        reranker
+            .rerank("other query", items, 10, candidate)
+            .await
+            .unwrap();
+        assert_eq!(scorer.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_edited_document_is_scored_again() {
+        let scorer = Arc::new(LengthScorer::default());
+        let reranker = Reranker::new(scorer.clone());
+        let original = RerankCandidate {
+            id: "doc-1".to_string(),
+            text: "short".to_string(),
+        };
+        let edited = RerankCandidate {
+            text: "a much longer revision".to_string(),
+            ..original.clone()
+        };
+
+        assert_eq!(reranker.score("query", &original).await.unwrap(), 0.05);
+        assert_eq!(reranker.score("query", &original).await.unwrap(), 0.05);
+        assert_eq!(reranker.score("query", &edited).await.unwrap(), 0.22);
+        assert_eq!(scorer.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,208 @@
+//! Synonym map shared by keyword extraction and search
+//!
+//! Maps each canonical term to the synonyms that mean the same thing, such
+//! as `kubernetes: [k8s, kube]`. Keyword extraction replaces synonyms with
+//! their canonical term so documents are indexed under one form, and search
+//! expands query terms to every form so a query for `k8s` also matches
+//! documents that say `kubernetes`.
+//!
+//! Terms are single words as split by the keyword extractor and compared
+//! case-insensitively.
+//!
+//! # File Format
+//!
+//! ```yaml
+//! kubernetes: [k8s, kube]
+//! postgresql: [postgres, pg]
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::search::synonyms::SynonymMap;
+//!
+//! let synonyms = SynonymMap::from_yaml("kubernetes: [k8s, kube]").unwrap();
+//!
+//! assert_eq!(synonyms.canonical("K8s"), "kubernetes");
+//! assert_eq!(synonyms.expand_query("deploy k8s"), "deploy k8s kubernetes kube");
+//! ```
+
+use crate::keyword_extractor::tokenize;
+use crate::{Result, XzeError};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Canonical terms and their synonyms
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SynonymMap {
+    /// Canonical term of every known term, including canonical terms
+    canonical: HashMap<String, String>,
+    /// Every form of each canonical term, canonical first
+    forms: HashMap<String, Vec<String>>,
+}
+
+impl SynonymMap {
+    /// Build a map from canonical terms and their synonyms
+    ///
+    /// A synonym listed under several terms belongs to the first one.
+    pub fn new<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Vec<S>)>,
+        S: AsRef<str>,
+    {
+        let mut map = Self::default();
+
+        for (term, synonyms) in entries {
+            let term = term.as_ref().trim().to_lowercase();
+            if term.is_empty() {
+                continue;
+            }
+            map.canonical.entry(term.clone()).or_insert(term.clone());
+            let forms = map.forms.entry(term.clone()).or_insert(vec![term.clone()]);
+
+            for synonym in synonyms {
+                let synonym = synonym.as_ref().trim().to_lowercase();
+                if synonym.is_empty() || map.canonical.contains_key(&synonym) {
+                    continue;
+                }
+                map.canonical.insert(synonym.clone(), term.clone());
+                forms.push(synonym);
+            }
+        }
+
+        map
+    }
+
+    /// Parse a `term: [synonyms]` YAML mapping
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML is not a mapping of terms to lists
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let entries: BTreeMap<String, Vec<String>> = serde_yaml::from_str(yaml)?;
+        Ok(Self::new(entries))
+    }
+
+    /// Load a `term: [synonyms]` YAML file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: &Path) -> Result<Self> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| {
+            XzeError::filesystem(format!(
+                "Failed to read synonyms from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_yaml(&yaml)
+    }
+
+    /// Load the file at `path`, or an empty map when no path is set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is set and cannot be read or parsed
+    pub fn load_optional(path: Option<&Path>) -> Result<Self> {
+        path.map_or_else(|| Ok(Self::default()), Self::load)
+    }
+
+    /// Number of canonical terms
+    pub fn len(&self) -> usize {
+        self.forms.len()
+    }
+
+    /// Whether the map has no terms
+    pub fn is_empty(&self) -> bool {
+        self.forms.is_empty()
+    }
+
+    /// Canonical form of `term`, lowercased; unknown terms are returned as is
+    pub fn canonical(&self, term: &str) -> String {
+        let lower = term.to_lowercase();
+        self.canonical.get(&lower).cloned().unwrap_or(lower)
+    }
+
+    /// Every form of `term`, canonical first; just `term` if unknown
+    pub fn forms(&self, term: &str) -> Vec<String> {
+        match self.forms.get(&self.canonical(term)) {
+            Some(forms) => forms.clone(),
+            None => vec![term.to_lowercase()],
+        }
+    }
+
+    /// Append the other forms of each query word to `query`
+    ///
+    /// Returns `query` unchanged when no word has synonyms.
+    pub fn expand_query(&self, query: &str) -> String {
+        let mut expanded = query.to_string();
+        let mut seen: Vec<String> = tokenize(query);
+
+        for word in tokenize(query) {
+            for form in self.forms(&word) {
+                if !seen.contains(&form) {
+                    expanded.push(' ');
+                    expanded.push_str(&form);
+                    seen.push(form);
+                }
+            }
+        }
+        expanded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_canonical_and_forms() {
+        let synonyms = SynonymMap::new([("Kubernetes", vec!["k8s", "KUBE"])]);
+
+        assert_eq!(synonyms.canonical("k8s"), "kubernetes");
+        assert_eq!(synonyms.canonical("kube"), "kubernetes");
+        assert_eq!(synonyms.canonical("kubernetes"), "kubernetes");
+        assert_eq!(synonyms.canonical("Docker"), "docker");
+        assert_eq!(synonyms.forms("kube"), vec!["kubernetes", "k8s", "kube"]);
+        assert_eq!(synonyms.forms("docker"), vec!["docker"]);
+    }
+
+    #[test]
+    fn test_synonym_belongs_to_first_term() {
+        let synonyms = SynonymMap::new([("postgresql", vec!["pg"]), ("pgbouncer", vec!["pg"])]);
+        assert_eq!(synonyms.canonical("pg"), "postgresql");
+        assert_eq!(synonyms.forms("pgbouncer"), vec!["pgbouncer"]);
+    }
+
+    #[test]
+    fn test_expand_query() {
+        let synonyms = SynonymMap::new([("kubernetes", vec!["k8s"])]);
+
+        assert_eq!(synonyms.expand_query("k8s pods"), "k8s pods kubernetes");
+        assert_eq!(synonyms.expand_query("kubernetes k8s"), "kubernetes k8s");
+        assert_eq!(synonyms.expand_query("docker"), "docker");
+    }
+
+    #[test]
+    fn test_load_yaml_file() {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "kubernetes: [k8s]\npostgresql:\n  - postgres\n",
+        )
+        .unwrap();
+
+        let synonyms = SynonymMap::load(file.path()).unwrap();
+        assert_eq!(synonyms.len(), 2);
+        assert_eq!(synonyms.canonical("postgres"), "postgresql");
+
+        assert!(SynonymMap::load_optional(None).unwrap().is_empty());
+        assert!(SynonymMap::from_yaml("kubernetes: k8s").is_err());
+        assert!(SynonymMap::load(Path::new("/nonexistent/synonyms.yaml")).is_err());
+    }
+}
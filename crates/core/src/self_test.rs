@@ -0,0 +1,392 @@
+//! End-to-end self-test
+//!
+//! Backs `xze doctor`. Where `xze health` only probes components, the
+//! self-test drives a tiny repository through the real pipeline:
+//!
+//! 1. **Setup** writes a Rust crate with one documented and one
+//!    undocumented public function to a temporary directory.
+//! 2. **Analyze** runs the Rust analyzer and checks that both functions are
+//!    found and only the undocumented one is reported as a gap.
+//! 3. **Generate** checks that the primary model is installed, asks the
+//!    configured Ollama models for a short reference document and checks
+//!    that the output is not empty.
+//! 4. **Validate** runs the documentation validator on that document.
+//!
+//! Each stage is timed. A stage that fails skips the stages after it, so
+//! the first failure points at the part of the setup to fix.
+
+use crate::{
+    ai::{model_selection::is_installed, AIAnalysisService, OllamaClient},
+    config::{OllamaConfig, XzeConfig},
+    documentation::validator::{DiátaxisValidator, DocumentationValidator, ValidatorConfig},
+    error::{Result, XzeError},
+    repository::{analyzer::AnalyzerFactory, CodeStructure, DocItemKind},
+    types::{DiátaxisCategory, ProgrammingLanguage},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Output limit for the generated document, to keep the test short
+pub const GENERATION_MAX_TOKENS: usize = 256;
+
+/// Source of the self-test repository
+const SOURCE: &str = "\
+//! Arithmetic helpers
+
+/// Add two numbers
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn subtract(a: i32, b: i32) -> i32 {
+    a - b
+}
+";
+
+/// A step of the self-test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Create the temporary repository
+    Setup,
+    /// Analyze the repository
+    Analyze,
+    /// Generate a document with the configured models
+    Generate,
+    /// Validate the generated document
+    Validate,
+}
+
+impl Stage {
+    /// All stages, in the order they run
+    pub const ALL: [Stage; 4] = [
+        Stage::Setup,
+        Stage::Analyze,
+        Stage::Generate,
+        Stage::Validate,
+    ];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Setup => "setup",
+            Stage::Analyze => "analyze",
+            Stage::Generate => "generate",
+            Stage::Validate => "validate",
+        };
+        f.pad(name)
+    }
+}
+
+/// How a stage ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    /// The stage's checks held
+    Passed,
+    /// The stage failed or its checks did not hold
+    Failed,
+    /// The stage did not run because an earlier one failed
+    Skipped,
+}
+
+/// Outcome of one stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageResult {
+    /// Stage that ran
+    pub stage: Stage,
+    /// How it ended
+    pub status: StageStatus,
+    /// Time taken in milliseconds
+    pub duration_ms: u64,
+    /// What was checked, or why the stage failed
+    pub detail: String,
+}
+
+/// Outcome of a self-test run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// One result per stage, in order
+    pub stages: Vec<StageResult>,
+}
+
+impl SelfTestReport {
+    /// Whether every stage passed
+    pub fn passed(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| stage.status == StageStatus::Passed)
+    }
+
+    /// The first stage that failed
+    pub fn first_failure(&self) -> Option<&StageResult> {
+        self.stages
+            .iter()
+            .find(|stage| stage.status == StageStatus::Failed)
+    }
+
+    /// Time taken by all stages in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        self.stages.iter().map(|stage| stage.duration_ms).sum()
+    }
+
+    /// Time `stage`, recording its result, and return its output if it
+    /// passed
+    async fn run_stage<T>(
+        &mut self,
+        stage: Stage,
+        run: impl std::future::Future<Output = Result<(T, String)>>,
+    ) -> Option<T> {
+        let started = Instant::now();
+        let outcome = run.await;
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let (output, status, detail) = match outcome {
+            Ok((output, detail)) => (Some(output), StageStatus::Passed, detail),
+            Err(e) => (None, StageStatus::Failed, e.to_string()),
+        };
+        self.stages.push(StageResult {
+            stage,
+            status,
+            duration_ms,
+            detail,
+        });
+        output
+    }
+
+    /// Record the stages that have not run as skipped
+    fn skip_remaining(&mut self) {
+        for stage in Stage::ALL.into_iter().skip(self.stages.len()) {
+            self.stages.push(StageResult {
+                stage,
+                status: StageStatus::Skipped,
+                duration_ms: 0,
+                detail: "Skipped after an earlier failure".to_string(),
+            });
+        }
+    }
+}
+
+/// Run the self-test against the Ollama server and models in `config`
+///
+/// Generation is tried once per model, without the configured retries.
+pub async fn run(config: &XzeConfig) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+    run_stages(&mut report, &config.ollama).await;
+    report.skip_remaining();
+    report
+}
+
+/// Run the stages in order, stopping at the first failure
+async fn run_stages(report: &mut SelfTestReport, ollama: &OllamaConfig) -> Option<()> {
+    let workdir = report.run_stage(Stage::Setup, async { setup() }).await?;
+    let structure = report
+        .run_stage(Stage::Analyze, async { analyze(workdir.path()) })
+        .await?;
+    let content = report
+        .run_stage(Stage::Generate, generate(ollama, &structure))
+        .await?;
+    report.run_stage(Stage::Validate, validate(&content)).await
+}
+
+fn setup() -> Result<(tempfile::TempDir, String)> {
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir(dir.path().join("src"))?;
+    std::fs::write(dir.path().join("src/lib.rs"), SOURCE)?;
+    let detail = format!("Wrote a sample crate to {}", dir.path().display());
+    Ok((dir, detail))
+}
+
+fn analyze(path: &Path) -> Result<(CodeStructure, String)> {
+    let structure = AnalyzerFactory::create_analyzer(&ProgrammingLanguage::Rust).analyze(path)?;
+
+    let mut functions: Vec<&str> = structure
+        .functions
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    functions.sort_unstable();
+    if functions != ["add", "subtract"] {
+        return Err(XzeError::validation(format!(
+            "Expected functions add and subtract, found {:?}",
+            functions
+        )));
+    }
+
+    let gaps: Vec<String> = structure
+        .coverage_report(&[DocItemKind::PublicFunction])
+        .gaps
+        .into_iter()
+        .map(|gap| gap.name)
+        .collect();
+    if gaps != ["subtract"] {
+        return Err(XzeError::validation(format!(
+            "Expected only subtract to be undocumented, found {:?}",
+            gaps
+        )));
+    }
+
+    Ok((structure, "Found 2 functions, 1 undocumented".to_string()))
+}
+
+async fn generate(ollama: &OllamaConfig, structure: &CodeStructure) -> Result<(String, String)> {
+    // Listing the models first separates an unreachable server or a
+    // missing model from a failed generation
+    let client = OllamaClient::with_timeout(
+        ollama.url.as_str().trim_end_matches('/').to_string(),
+        Duration::from_secs(ollama.timeout_seconds),
+    );
+    let installed = client.list_models().await?;
+    let model = &ollama.models.primary;
+    if !is_installed(&installed, model) {
+        return Err(XzeError::ai(format!(
+            "Model {} is not installed on {}; run `ollama pull {}`",
+            model, ollama.url, model
+        )));
+    }
+
+    let service = AIAnalysisService::from_config(ollama).with_retry_attempts(1);
+    let result = service
+        .generate_documentation(
+            &DiátaxisCategory::Reference,
+            structure,
+            "",
+            &[],
+            Some(GENERATION_MAX_TOKENS),
+        )
+        .await?;
+    if result.summary.trim().is_empty() {
+        return Err(XzeError::ai("The model returned an empty document"));
+    }
+
+    let detail = format!(
+        "Generated {} characters with {} (confidence {:.2})",
+        result.summary.len(),
+        model,
+        result.confidence
+    );
+    Ok((result.summary, detail))
+}
+
+async fn validate(content: &str) -> Result<((), String)> {
+    let validator = DiátaxisValidator::new(ValidatorConfig::default());
+    let result = validator
+        .validate_document(Path::new("reference/self_test.md"), content)
+        .await?;
+    if !result.is_valid() {
+        let errors: Vec<&str> = result
+            .issues_by_severity(crate::documentation::validator::IssueSeverity::Error)
+            .into_iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        return Err(XzeError::validation(format!(
+            "Generated document failed validation: {}",
+            errors.join("; ")
+        )));
+    }
+
+    Ok(((), format!("Validation score {:.2}", result.score)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    const DOCUMENT: &str = "# Arithmetic API\\n\\n## add\\n\\nAdds two numbers and returns the sum.\\n\\n```rust\\nlet sum = add(1, 2);\\n```\\n\\n## subtract\\n\\nSubtracts the second number from the first.\\n";
+
+    fn config(url: &str) -> XzeConfig {
+        let mut config = XzeConfig::default();
+        config.ollama.url = Url::parse(url).unwrap();
+        config.ollama.models.primary = "llama3".to_string();
+        config
+    }
+
+    async fn server_with_models(models: &str) -> mockito::ServerGuard {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/tags")
+            .with_body(format!(r#"{{"models":[{}]}}"#, models))
+            .create_async()
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_all_stages_pass() {
+        let mut server = server_with_models(
+            r#"{"name":"llama3:latest","size":1,"digest":"a","modified_at":""}"#,
+        )
+        .await;
+        server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJsonString(format!(
+                r#"{{"model":"llama3","options":{{"num_predict":{}}}}}"#,
+                GENERATION_MAX_TOKENS
+            )))
+            .with_body(format!(
+                "{{\"response\":\"{}\",\"done\":true,\"done_reason\":\"stop\"}}\n",
+                DOCUMENT
+            ))
+            .create_async()
+            .await;
+
+        let report = run(&config(&server.url())).await;
+        assert!(report.passed(), "{:#?}", report);
+        let stages: Vec<Stage> = report.stages.iter().map(|s| s.stage).collect();
+        assert_eq!(stages, Stage::ALL);
+        assert_eq!(report.stages[1].detail, "Found 2 functions, 1 undocumented");
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_fails_generation() {
+        let server = server_with_models("").await;
+
+        let report = run(&config(&server.url())).await;
+        let failure = report.first_failure().unwrap();
+        assert_eq!(failure.stage, Stage::Generate);
+        assert!(
+            failure.detail.contains("ollama pull llama3"),
+            "{}",
+            failure.detail
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failure_skips_later_stages() {
+        // Nothing listens on port 1, so the model check fails
+        let report = run(&config("http://127.0.0.1:1")).await;
+
+        assert!(!report.passed());
+        let statuses: Vec<StageStatus> = report.stages.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                StageStatus::Passed,
+                StageStatus::Passed,
+                StageStatus::Failed,
+                StageStatus::Skipped,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = SelfTestReport {
+            stages: vec![StageResult {
+                stage: Stage::Setup,
+                status: StageStatus::Passed,
+                duration_ms: 3,
+                detail: "ok".to_string(),
+            }],
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["stages"][0]["stage"], "setup");
+        assert_eq!(json["stages"][0]["status"], "passed");
+        assert_eq!(report.duration_ms(), 3);
+    }
+}
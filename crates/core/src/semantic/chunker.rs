@@ -5,18 +5,22 @@
 //!
 //! # Overview
 //!
-//! The semantic chunker uses sentence embeddings and cosine similarity to detect
-//! topical boundaries in documents. It groups consecutive sentences with high
+//! The semantic chunker uses sentence embeddings and a configurable similarity
+//! metric (cosine by default) to detect topical boundaries in documents. It groups consecutive sentences with high
 //! similarity into chunks while respecting configurable constraints.
 //!
 //! # Architecture
 //!
 //! The chunking process involves:
-//! 1. Split document into sentences (preserving code blocks)
+//! 1. Split document into sentences (preserving code blocks), or with
+//!    [`SplitterKind::Markdown`] into heading sections that are chunked
+//!    separately
 //! 2. Generate embeddings for each sentence
 //! 3. Calculate pairwise similarity between consecutive sentences
-//! 4. Detect boundaries using percentile-based thresholding
-//! 5. Create chunks from sentence groups
+//! 4. Detect boundaries where similarity drops below a static threshold, or
+//!    below a percentile of the document's own similarities
+//! 5. Create chunks from sentence groups, optionally repeating the last
+//!    sentences of each chunk at the start of the next
 //!
 //! # Examples
 //!
@@ -41,9 +45,11 @@
 //! ```
 
 use crate::ai::OllamaClient;
+use crate::semantic::strategy::{document_title, ChunkStrategy};
 use crate::semantic::{
-    calculate_percentile, generate_embeddings_batch, pairwise_similarities, ChunkMetadata,
-    EmbeddingError, SemanticChunk, SentenceSplitter, SimilarityError,
+    calculate_percentile, collect_embeddings, pairwise_similarities_with, stream_embeddings,
+    ChunkMetadata, EmbeddingBatchConfig, EmbeddingError, MarkdownSplitter, SemanticChunk,
+    SentenceSplitter, SimilarityError, SimilarityMetric, SplitterKind,
 };
 use thiserror::Error;
 use tracing::{debug, info, warn};
@@ -73,10 +79,20 @@ use tracing::{debug, info, warn};
 ///     ..Default::default()
 /// };
 /// assert!(custom.validate().is_ok());
+///
+/// // Cut below the 25th percentile of each document's similarities
+/// let dynamic = ChunkerConfig {
+///     similarity_percentile: Some(0.25),
+///     ..Default::default()
+/// };
+/// assert!(dynamic.validate().is_ok());
 /// ```
 #[derive(Debug, Clone)]
 pub struct ChunkerConfig {
     /// Minimum similarity score to keep sentences in the same chunk (0.0-1.0)
+    ///
+    /// Higher values cut more often and yield smaller chunks. Ignored when
+    /// `similarity_percentile` is set.
     pub similarity_threshold: f32,
 
     /// Minimum number of sentences per chunk
@@ -85,8 +101,16 @@ pub struct ChunkerConfig {
     /// Maximum number of sentences per chunk
     pub max_chunk_sentences: usize,
 
-    /// Percentile to use for dynamic threshold calculation (0.0-1.0)
-    pub similarity_percentile: f32,
+    /// Number of sentences from the end of each chunk repeated at the start
+    /// of the next one; 0 disables overlap
+    pub overlap_sentences: usize,
+
+    /// Percentile of the document's adjacent-sentence similarities used as
+    /// the threshold instead of `similarity_threshold` (0.0-1.0)
+    ///
+    /// Adapts the cut points to each document, so documents with uniformly
+    /// high or low similarity are still split.
+    pub similarity_percentile: Option<f32>,
 
     /// Minimum sentence length in characters to avoid fragments
     pub min_sentence_length: usize,
@@ -94,8 +118,18 @@ pub struct ChunkerConfig {
     /// Batch size for embedding generation
     pub embedding_batch_size: usize,
 
+    /// Number of embedding batches requested at the same time
+    pub max_concurrent_embedding_batches: usize,
+
     /// Model name for embedding generation
     pub model_name: String,
+
+    /// Metric used to compare sentence embeddings; should match the metric
+    /// the embedding model was trained for
+    pub similarity_metric: SimilarityMetric,
+
+    /// How documents are split into sentences before chunking
+    pub splitter: SplitterKind,
 }
 
 impl Default for ChunkerConfig {
@@ -104,10 +138,14 @@ impl Default for ChunkerConfig {
             similarity_threshold: 0.7,
             min_chunk_sentences: 3,
             max_chunk_sentences: 30,
-            similarity_percentile: 0.5,
+            overlap_sentences: 0,
+            similarity_percentile: None,
             min_sentence_length: 10,
             embedding_batch_size: 32,
+            max_concurrent_embedding_batches: 4,
             model_name: "nomic-embed-text".to_string(),
+            similarity_metric: SimilarityMetric::default(),
+            splitter: SplitterKind::default(),
         }
     }
 }
@@ -138,7 +176,8 @@ impl ChunkerConfig {
     /// Preset configuration optimized for narrative text
     ///
     /// Uses lower similarity threshold and smaller chunks to respect
-    /// narrative flow and paragraph boundaries.
+    /// narrative flow and paragraph boundaries, with the threshold taken
+    /// from the 40th percentile of each document's similarities.
     ///
     /// # Examples
     ///
@@ -153,7 +192,7 @@ impl ChunkerConfig {
         Self {
             similarity_threshold: 0.65,
             max_chunk_sentences: 20,
-            similarity_percentile: 0.4,
+            similarity_percentile: Some(0.4),
             ..Default::default()
         }
     }
@@ -167,8 +206,10 @@ impl ChunkerConfig {
     /// - similarity_percentile not in [0.0, 1.0]
     /// - min_chunk_sentences is 0
     /// - max_chunk_sentences less than min_chunk_sentences
+    /// - overlap_sentences not less than max_chunk_sentences
     /// - min_sentence_length is 0
     /// - embedding_batch_size is 0
+    /// - max_concurrent_embedding_batches is 0
     /// - model_name is empty
     ///
     /// # Examples
@@ -192,7 +233,10 @@ impl ChunkerConfig {
             ));
         }
 
-        if !(0.0..=1.0).contains(&self.similarity_percentile) {
+        if self
+            .similarity_percentile
+            .is_some_and(|percentile| !(0.0..=1.0).contains(&percentile))
+        {
             return Err(ChunkingError::InvalidConfiguration(
                 "similarity_percentile must be between 0.0 and 1.0".to_string(),
             ));
@@ -210,6 +254,12 @@ impl ChunkerConfig {
             ));
         }
 
+        if self.overlap_sentences >= self.max_chunk_sentences {
+            return Err(ChunkingError::InvalidConfiguration(
+                "overlap_sentences must be less than max_chunk_sentences".to_string(),
+            ));
+        }
+
         if self.min_sentence_length == 0 {
             return Err(ChunkingError::InvalidConfiguration(
                 "min_sentence_length must be greater than 0".to_string(),
@@ -222,6 +272,12 @@ impl ChunkerConfig {
             ));
         }
 
+        if self.max_concurrent_embedding_batches == 0 {
+            return Err(ChunkingError::InvalidConfiguration(
+                "max_concurrent_embedding_batches must be greater than 0".to_string(),
+            ));
+        }
+
         if self.model_name.is_empty() {
             return Err(ChunkingError::InvalidConfiguration(
                 "model_name cannot be empty".to_string(),
@@ -263,6 +319,7 @@ pub struct SemanticChunker {
     config: ChunkerConfig,
     ollama_client: OllamaClient,
     sentence_splitter: SentenceSplitter,
+    markdown_splitter: MarkdownSplitter,
 }
 
 impl SemanticChunker {
@@ -288,10 +345,12 @@ impl SemanticChunker {
     /// ```
     pub fn new(config: ChunkerConfig, ollama_client: OllamaClient) -> Self {
         let sentence_splitter = SentenceSplitter::new(config.min_sentence_length);
+        let markdown_splitter = MarkdownSplitter::new(config.min_sentence_length);
         Self {
             config,
             ollama_client,
             sentence_splitter,
+            markdown_splitter,
         }
     }
 
@@ -340,8 +399,20 @@ impl SemanticChunker {
         // Validate configuration
         self.config.validate()?;
 
-        // Split into sentences
-        let sentences = self.sentence_splitter.split(text);
+        // Split into sentences, grouped by section with their breadcrumbs
+        let sections: Vec<(Vec<String>, Vec<String>)> = match self.config.splitter {
+            SplitterKind::Sentence => vec![(Vec::new(), self.sentence_splitter.split(text))],
+            SplitterKind::Markdown => self
+                .markdown_splitter
+                .sections(text)
+                .into_iter()
+                .map(|section| (section.breadcrumbs, section.units))
+                .collect(),
+        };
+        let sentences: Vec<String> = sections
+            .iter()
+            .flat_map(|(_, units)| units.iter().cloned())
+            .collect();
 
         if sentences.is_empty() {
             return Err(ChunkingError::EmptyDocument);
@@ -353,38 +424,137 @@ impl SemanticChunker {
         );
 
         // Generate embeddings for all sentences
-        let embeddings = generate_embeddings_batch(
+        let batches = stream_embeddings(
             &self.ollama_client,
             &self.config.model_name,
             &sentences,
-            self.config.embedding_batch_size,
+            EmbeddingBatchConfig {
+                batch_size: self.config.embedding_batch_size,
+                max_concurrent_batches: self.config.max_concurrent_embedding_batches,
+            },
         )
-        .await
         .map_err(ChunkingError::EmbeddingGeneration)?;
+        let embeddings = collect_embeddings(batches)
+            .await
+            .map_err(ChunkingError::EmbeddingGeneration)?;
 
         debug!("Generated embeddings for {} sentences", embeddings.len());
 
-        // Calculate pairwise similarities
-        let similarities =
-            pairwise_similarities(&embeddings).map_err(ChunkingError::SimilarityCalculation)?;
+        // Use default metadata if none provided
+        let metadata = metadata.unwrap_or_else(|| ChunkMetadata::new("unknown".to_string(), ""));
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+
+        // Chunk each section on its own so no chunk spans two sections
+        for (breadcrumbs, units) in sections {
+            let end = offset + units.len();
+            let section_embeddings = &embeddings[offset..end];
+
+            // Calculate pairwise similarities
+            let similarities =
+                pairwise_similarities_with(section_embeddings, self.config.similarity_metric)
+                    .map_err(ChunkingError::SimilarityCalculation)?;
 
-        // Detect chunk boundaries
-        let boundaries = self.detect_boundaries(&similarities);
+            // Detect chunk boundaries
+            let boundaries = self.detect_boundaries(&similarities);
 
-        debug!("Detected {} chunk boundaries", boundaries.len());
+            debug!("Detected {} chunk boundaries", boundaries.len());
+
+            // Markdown sections below the minimum size still get a chunk
+            let mut ranges = self.chunk_ranges(units.len(), &boundaries);
+            if ranges.is_empty() && self.config.splitter == SplitterKind::Markdown {
+                ranges.push((0, units.len()));
+            }
 
-        // Create chunks from boundaries
-        let chunks = self.create_chunks(&sentences, &embeddings, &boundaries, metadata);
+            let section_metadata = ChunkMetadata {
+                breadcrumbs,
+                ..metadata.clone()
+            };
+            for mut chunk in
+                self.build_chunks(&units, section_embeddings, &ranges, section_metadata)
+            {
+                chunk.chunk_index = chunks.len();
+                chunk.start_sentence += offset;
+                chunk.end_sentence += offset;
+                chunks.push(chunk);
+            }
+            offset = end;
+        }
+
+        let total_chunks = chunks.len();
+        for chunk in &mut chunks {
+            chunk.total_chunks = total_chunks;
+        }
 
         info!("Created {} semantic chunks", chunks.len());
 
         Ok(chunks)
     }
 
+    /// Chunks one document with its own configuration
+    ///
+    /// Lets callers tune granularity per document, such as a higher
+    /// similarity threshold for dense reference pages, while reusing this
+    /// chunker's Ollama client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] under the same conditions as
+    /// [`chunk_document`](Self::chunk_document), validating `config`
+    /// instead of the chunker's configuration
+    pub async fn chunk_document_with_config(
+        &self,
+        text: &str,
+        metadata: Option<ChunkMetadata>,
+        config: &ChunkerConfig,
+    ) -> Result<Vec<SemanticChunk>, ChunkingError> {
+        Self::new(config.clone(), self.ollama_client.clone())
+            .chunk_document(text, metadata)
+            .await
+    }
+
+    /// Chunks a document with the given strategy
+    ///
+    /// `ChunkStrategy::Semantic` runs [`chunk_document`](Self::chunk_document)
+    /// and returns chunks with embeddings. The other strategies need no
+    /// embedding model and return chunks without embeddings. Either way each
+    /// chunk's metadata describes that chunk's own content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] under the same conditions as
+    /// [`chunk_document`](Self::chunk_document) and
+    /// [`ChunkStrategy::chunk`]
+    pub async fn chunk_with_strategy(
+        &self,
+        strategy: ChunkStrategy,
+        text: &str,
+        source_file: &str,
+    ) -> Result<Vec<SemanticChunk>, ChunkingError> {
+        if !strategy.requires_embeddings() {
+            return strategy.chunk(text, source_file);
+        }
+
+        let mut chunks = self.chunk_document(text, None).await?;
+        let title = document_title(text);
+        for chunk in &mut chunks {
+            // Counts, overlap and breadcrumbs already describe the chunk
+            chunk.metadata.source_file = source_file.to_string();
+            chunk.metadata.title = chunk
+                .metadata
+                .breadcrumbs
+                .last()
+                .cloned()
+                .or_else(|| title.clone());
+        }
+        Ok(chunks)
+    }
+
     /// Detects chunk boundaries based on similarity scores
     ///
-    /// Uses percentile-based thresholding to identify where to split chunks.
-    /// A boundary is placed where similarity drops below the threshold.
+    /// A boundary is placed where similarity drops below the threshold: the
+    /// configured percentile of `similarities` when set, otherwise the static
+    /// similarity threshold.
     ///
     /// # Arguments
     ///
@@ -398,14 +568,24 @@ impl SemanticChunker {
             return vec![];
         }
 
-        // Calculate dynamic threshold based on percentile
-        let threshold = calculate_percentile(similarities, self.config.similarity_percentile)
-            .min(self.config.similarity_threshold);
-
-        debug!(
-            "Using similarity threshold: {:.3} (percentile: {:.2})",
-            threshold, self.config.similarity_percentile
-        );
+        let threshold = match self.config.similarity_percentile {
+            // Calculate dynamic threshold based on percentile
+            Some(percentile) => {
+                let threshold = calculate_percentile(similarities, percentile);
+                debug!(
+                    "Using similarity threshold: {:.3} (percentile: {:.2})",
+                    threshold, percentile
+                );
+                threshold
+            }
+            None => {
+                debug!(
+                    "Using similarity threshold: {:.3}",
+                    self.config.similarity_threshold
+                );
+                self.config.similarity_threshold
+            }
+        };
 
         let mut boundaries = vec![0]; // Start with first sentence
 
@@ -421,7 +601,9 @@ impl SemanticChunker {
     /// Creates chunks from sentences and boundary positions
     ///
     /// Groups consecutive sentences between boundaries into chunks, respecting
-    /// min/max chunk size constraints.
+    /// min/max chunk size constraints. With `overlap_sentences` set, each chunk
+    /// also starts with the last sentences of the previous chunk; see
+    /// [`overlap_len`](Self::overlap_len).
     ///
     /// # Arguments
     ///
@@ -433,6 +615,7 @@ impl SemanticChunker {
     /// # Returns
     ///
     /// Returns a vector of [`SemanticChunk`] instances with embeddings.
+    #[cfg(test)]
     fn create_chunks(
         &self,
         sentences: &[String],
@@ -440,130 +623,131 @@ impl SemanticChunker {
         boundaries: &[usize],
         metadata: Option<ChunkMetadata>,
     ) -> Vec<SemanticChunk> {
-        let mut chunks = Vec::new();
-        let mut current_start = 0;
+        let ranges = self.chunk_ranges(sentences.len(), boundaries);
+        let metadata = metadata.unwrap_or_else(|| ChunkMetadata::new("unknown".to_string(), ""));
+        self.build_chunks(sentences, embeddings, &ranges, metadata)
+    }
 
-        // Use default metadata if none provided
-        let default_metadata = ChunkMetadata::new("unknown".to_string(), "");
-        let chunk_metadata = metadata.unwrap_or(default_metadata);
+    /// Sentence ranges owned by each chunk, excluding overlap
+    ///
+    /// Groups the `count` sentences between boundaries, dropping or merging
+    /// groups below `min_chunk_sentences` and splitting groups above
+    /// `max_chunk_sentences`.
+    fn chunk_ranges(&self, count: usize, boundaries: &[usize]) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut current_start = 0;
 
         for &boundary in boundaries.iter().skip(1) {
-            let chunk_sentences = &sentences[current_start..boundary];
+            let chunk_len = boundary - current_start;
 
             // Enforce minimum chunk size
-            if chunk_sentences.len() < self.config.min_chunk_sentences {
+            if chunk_len < self.config.min_chunk_sentences {
                 continue;
             }
 
             // Enforce maximum chunk size by splitting if needed
-            if chunk_sentences.len() > self.config.max_chunk_sentences {
-                // Split into smaller chunks
+            if chunk_len > self.config.max_chunk_sentences {
                 let mut sub_start = current_start;
                 while sub_start < boundary {
                     let sub_end = (sub_start + self.config.max_chunk_sentences).min(boundary);
-                    let sub_sentences = &sentences[sub_start..sub_end];
-
-                    if sub_sentences.len() >= self.config.min_chunk_sentences {
-                        let avg_similarity =
-                            self.calculate_chunk_similarity(embeddings, sub_start, sub_end) as f64;
-
-                        let content = sub_sentences.join(" ");
-
-                        // Compute chunk embedding as average of sentence embeddings
-                        let chunk_embedding =
-                            self.compute_chunk_embedding(embeddings, sub_start, sub_end);
-
-                        let mut chunk = SemanticChunk::new(
-                            content,
-                            chunks.len(),
-                            0, // Total chunks updated later
-                            sub_start,
-                            sub_end - 1,
-                            avg_similarity,
-                            chunk_metadata.clone(),
-                        );
-                        chunk.embedding = chunk_embedding;
-                        chunks.push(chunk);
+                    if sub_end - sub_start >= self.config.min_chunk_sentences {
+                        ranges.push((sub_start, sub_end));
                     }
-
                     sub_start = sub_end;
                 }
             } else {
-                // Create normal chunk
-                let avg_similarity =
-                    self.calculate_chunk_similarity(embeddings, current_start, boundary) as f64;
-
-                let content = chunk_sentences.join(" ");
-
-                // Compute chunk embedding as average of sentence embeddings
-                let chunk_embedding =
-                    self.compute_chunk_embedding(embeddings, current_start, boundary);
-
-                let mut chunk = SemanticChunk::new(
-                    content,
-                    chunks.len(),
-                    0, // Total chunks updated later
-                    current_start,
-                    boundary - 1,
-                    avg_similarity,
-                    chunk_metadata.clone(),
-                );
-                chunk.embedding = chunk_embedding;
-                chunks.push(chunk);
+                ranges.push((current_start, boundary));
             }
 
             current_start = boundary;
         }
 
         // Handle remaining sentences
-        if current_start < sentences.len() {
-            let remaining_sentences = &sentences[current_start..];
-            if remaining_sentences.len() >= self.config.min_chunk_sentences {
-                let avg_similarity =
-                    self.calculate_chunk_similarity(embeddings, current_start, sentences.len())
-                        as f64;
-
-                let content = remaining_sentences.join(" ");
-
-                // Compute chunk embedding as average of sentence embeddings
-                let chunk_embedding =
-                    self.compute_chunk_embedding(embeddings, current_start, sentences.len());
-
-                let mut chunk = SemanticChunk::new(
-                    content,
-                    chunks.len(),
-                    0,
-                    current_start,
-                    sentences.len() - 1,
-                    avg_similarity,
-                    chunk_metadata.clone(),
-                );
-                chunk.embedding = chunk_embedding;
-                chunks.push(chunk);
-            } else if !chunks.is_empty() {
+        if current_start < count {
+            let remaining = count - current_start;
+            if remaining >= self.config.min_chunk_sentences {
+                ranges.push((current_start, count));
+            } else if let Some(last) = ranges.last_mut() {
                 // Merge with last chunk if too small
-                warn!(
-                    "Merging {} remaining sentences with last chunk",
-                    remaining_sentences.len()
-                );
-                if let Some(last_chunk) = chunks.last_mut() {
-                    let merged_content =
-                        format!("{} {}", last_chunk.content, remaining_sentences.join(" "));
-                    last_chunk.content = merged_content;
-                    last_chunk.end_sentence = sentences.len() - 1;
-                }
+                warn!("Merging {} remaining sentences with last chunk", remaining);
+                last.1 = count;
             }
         }
 
-        // Update total_chunks for all chunks
-        let total_chunks = chunks.len();
-        for chunk in &mut chunks {
-            chunk.total_chunks = total_chunks;
+        ranges
+    }
+
+    /// Builds one chunk per range, prefixed with overlap from the previous one
+    ///
+    /// Word and character counts in each chunk's metadata are recomputed
+    /// from its content.
+    fn build_chunks(
+        &self,
+        sentences: &[String],
+        embeddings: &[Vec<f32>],
+        ranges: &[(usize, usize)],
+        chunk_metadata: ChunkMetadata,
+    ) -> Vec<SemanticChunk> {
+        let total_chunks = ranges.len();
+        let mut chunks = Vec::with_capacity(total_chunks);
+
+        for (index, &(start, end)) in ranges.iter().enumerate() {
+            let overlap = match index.checked_sub(1).map(|i| ranges[i]) {
+                Some(previous) if previous.1 == start => {
+                    self.overlap_len(sentences, previous.0, start)
+                }
+                _ => 0,
+            };
+            let content_start = start - overlap;
+
+            let content = sentences[content_start..end].join(" ");
+            let avg_similarity =
+                self.calculate_chunk_similarity(embeddings, content_start, end) as f64;
+
+            // Counts describe this chunk's content, overlap included
+            let mut metadata = chunk_metadata.clone();
+            metadata.word_count = content.split_whitespace().count();
+            metadata.char_count = content.chars().count();
+            metadata.overlap_sentences = overlap;
+
+            let mut chunk = SemanticChunk::new(
+                content,
+                index,
+                total_chunks,
+                content_start,
+                end - 1,
+                avg_similarity,
+                metadata,
+            );
+            // Compute chunk embedding as average of sentence embeddings
+            chunk.embedding = self.compute_chunk_embedding(embeddings, content_start, end);
+            chunk.similarity_metric = self.config.similarity_metric;
+            chunks.push(chunk);
         }
 
         chunks
     }
 
+    /// Number of sentences before `start` to repeat at the start of a chunk
+    ///
+    /// Takes up to `overlap_sentences` from the previous chunk, which owns
+    /// `previous_start..start`. The overlap is shortened until it holds whole
+    /// fenced code blocks, so a block is never cut at the overlap boundary.
+    fn overlap_len(&self, sentences: &[String], previous_start: usize, start: usize) -> usize {
+        let max = self.config.overlap_sentences.min(start - previous_start);
+        let mut fences = 0;
+        let mut overlap = 0;
+
+        for len in 1..=max {
+            fences += sentences[start - len].matches("```").count();
+            if fences % 2 == 0 {
+                overlap = len;
+            }
+        }
+
+        overlap
+    }
+
     /// Computes chunk embedding by averaging sentence embeddings
     ///
     /// Creates a single embedding vector for the chunk by computing the mean
@@ -616,7 +800,7 @@ impl SemanticChunker {
 
     /// Calculates average similarity within a chunk
     ///
-    /// Computes the mean cosine similarity between consecutive sentence pairs
+    /// Computes the mean similarity between consecutive sentence pairs
     /// within the specified range.
     ///
     /// # Arguments
@@ -634,7 +818,9 @@ impl SemanticChunker {
         }
 
         let chunk_embeddings = &embeddings[start..end];
-        let similarities = pairwise_similarities(chunk_embeddings).unwrap_or_default();
+        let similarities =
+            pairwise_similarities_with(chunk_embeddings, self.config.similarity_metric)
+                .unwrap_or_default();
 
         if similarities.is_empty() {
             return 1.0;
@@ -713,7 +899,7 @@ mod tests {
     #[test]
     fn test_chunker_config_validation_invalid_percentile() {
         let config = ChunkerConfig {
-            similarity_percentile: -0.1,
+            similarity_percentile: Some(-0.1),
             ..Default::default()
         };
         assert!(config.validate().is_err());
@@ -738,6 +924,16 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_chunker_config_validation_overlap_not_less_than_max() {
+        let config = ChunkerConfig {
+            max_chunk_sentences: 5,
+            overlap_sentences: 5,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_chunker_config_validation_zero_batch_size() {
         let config = ChunkerConfig {
@@ -747,6 +943,15 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_chunker_config_validation_zero_concurrent_batches() {
+        let config = ChunkerConfig {
+            max_concurrent_embedding_batches: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_chunker_config_validation_empty_model_name() {
         let config = ChunkerConfig {
@@ -771,7 +976,7 @@ mod tests {
         let ollama_client = OllamaClient::new("http://localhost:11434".to_string());
         let config = ChunkerConfig {
             similarity_threshold: 0.5,
-            similarity_percentile: 0.5,
+            similarity_percentile: Some(0.5),
             ..Default::default()
         };
         let chunker = SemanticChunker::new(config, ollama_client);
@@ -809,4 +1014,184 @@ mod tests {
         let similarity = chunker.calculate_chunk_similarity(&embeddings, 0, 3);
         assert_eq!(similarity, 1.0); // All identical vectors
     }
+
+    fn overlap_chunker(overlap_sentences: usize) -> SemanticChunker {
+        let ollama_client = OllamaClient::new("http://localhost:11434".to_string());
+        let config = ChunkerConfig {
+            min_chunk_sentences: 2,
+            overlap_sentences,
+            ..Default::default()
+        };
+        SemanticChunker::new(config, ollama_client)
+    }
+
+    #[test]
+    fn test_create_chunks_with_overlap() {
+        let chunker = overlap_chunker(1);
+        let sentences: Vec<String> = [
+            "First sentence here.",
+            "Second sentence follows.",
+            "Third sentence starts a topic.",
+            "Fourth one continues it.",
+            "Fifth closes the document nicely.",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let embeddings = vec![vec![1.0, 0.0]; sentences.len()];
+        let metadata = ChunkMetadata::new("doc.md".to_string(), &sentences.join(" "));
+
+        let chunks = chunker.create_chunks(&sentences, &embeddings, &[0, 2], Some(metadata));
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].metadata.overlap_sentences, 0);
+        assert_eq!(chunks[1].metadata.overlap_sentences, 1);
+        assert_eq!(chunks[1].start_sentence, chunks[0].end_sentence);
+        assert!(chunks[0].content.ends_with("Second sentence follows."));
+        assert!(chunks[1].content.starts_with("Second sentence follows."));
+        for chunk in &chunks {
+            assert_eq!(
+                chunk.metadata.word_count,
+                chunk.content.split_whitespace().count()
+            );
+            assert_eq!(chunk.metadata.char_count, chunk.content.chars().count());
+        }
+        assert_eq!(chunks[1].metadata.word_count, 17);
+    }
+
+    #[test]
+    fn test_create_chunks_without_overlap() {
+        let chunker = overlap_chunker(0);
+        let sentences: Vec<String> = (0..4).map(|i| format!("Sentence number {}.", i)).collect();
+        let embeddings = vec![vec![1.0, 0.0]; sentences.len()];
+
+        let chunks = chunker.create_chunks(&sentences, &embeddings, &[0, 2], None);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].start_sentence, 2);
+        assert_eq!(chunks[1].metadata.overlap_sentences, 0);
+        assert_eq!(chunks[1].content, "Sentence number 2. Sentence number 3.");
+    }
+
+    #[test]
+    fn test_overlap_keeps_code_blocks_whole() {
+        let chunker = overlap_chunker(1);
+        let sentences: Vec<String> = [
+            "Intro sentence here.",
+            "Run this:\n```sh",
+            "cargo build\n```",
+            "Then start the server.",
+            "It listens on port 3000.",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let embeddings = vec![vec![1.0, 0.0]; sentences.len()];
+
+        // The last sentence of the first chunk closes a code block opened
+        // in the sentence before it, so one sentence cannot be repeated
+        let chunks = chunker.create_chunks(&sentences, &embeddings, &[0, 3], None);
+        assert_eq!(chunks[1].metadata.overlap_sentences, 0);
+        assert_eq!(chunks[1].start_sentence, 3);
+
+        let chunker = overlap_chunker(2);
+        let chunks = chunker.create_chunks(&sentences, &embeddings, &[0, 3], None);
+        assert_eq!(chunks[1].metadata.overlap_sentences, 2);
+        assert!(chunks[1]
+            .content
+            .starts_with("Run this:\n```sh cargo build\n```"));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_splitter_chunks_each_section() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/embeddings")
+            .with_body(r#"{"embedding":[1.0,0.0,0.0]}"#)
+            .create_async()
+            .await;
+        let config = ChunkerConfig {
+            splitter: SplitterKind::Markdown,
+            ..Default::default()
+        };
+        let chunker = SemanticChunker::new(config, OllamaClient::new(server.url()));
+        let text = "# Guide\n\nThe guide explains setup. It is short.\n\n\
+            ## Install\n\nRun this command:\n\n```sh\nmake install. Then test.\n```\n\n\
+            ## Usage\n\nStart the server afterwards.";
+
+        let chunks = chunker
+            .chunk_with_strategy(ChunkStrategy::Semantic, text, "docs/guide.md")
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].metadata.breadcrumbs, vec!["Guide"]);
+        assert_eq!(
+            chunks[1].metadata.breadcrumb().as_deref(),
+            Some("Guide > Install")
+        );
+        assert_eq!(chunks[1].metadata.title.as_deref(), Some("Install"));
+        assert!(chunks[1]
+            .content
+            .ends_with("```sh\nmake install. Then test.\n```"));
+        assert_eq!((chunks[1].start_sentence, chunks[1].end_sentence), (2, 3));
+        assert_eq!(chunks[2].content, "Start the server afterwards.");
+        assert!(chunks.iter().all(|c| c.total_chunks == 3));
+    }
+
+    /// Unit embeddings whose adjacent similarities are `0.95, 0.6, 0.95,
+    /// 0.3, 0.95, 0.7, 0.95`
+    fn graded_embeddings() -> Vec<Vec<f32>> {
+        let mut angle: f32 = 0.0;
+        let mut embeddings = vec![vec![1.0, 0.0]];
+        for similarity in [0.95f32, 0.6, 0.95, 0.3, 0.95, 0.7, 0.95] {
+            angle += similarity.acos();
+            embeddings.push(vec![angle.cos(), angle.sin()]);
+        }
+        embeddings
+    }
+
+    fn chunk_graded(similarity_threshold: f32, similarity_percentile: Option<f32>) -> Vec<usize> {
+        let ollama_client = OllamaClient::new("http://localhost:11434".to_string());
+        let config = ChunkerConfig {
+            similarity_threshold,
+            similarity_percentile,
+            min_chunk_sentences: 1,
+            ..Default::default()
+        };
+        let chunker = SemanticChunker::new(config, ollama_client);
+        let embeddings = graded_embeddings();
+        let sentences: Vec<String> = (0..embeddings.len())
+            .map(|i| format!("Sentence number {}.", i))
+            .collect();
+        let similarities =
+            pairwise_similarities_with(&embeddings, SimilarityMetric::Cosine).unwrap();
+
+        let boundaries = chunker.detect_boundaries(&similarities);
+        chunker
+            .create_chunks(&sentences, &embeddings, &boundaries, None)
+            .iter()
+            .map(|chunk| chunk.sentence_count())
+            .collect()
+    }
+
+    #[test]
+    fn test_lower_threshold_yields_fewer_larger_chunks() {
+        let high = chunk_graded(0.8, None);
+        let low = chunk_graded(0.5, None);
+
+        assert_eq!(high, vec![2, 2, 2, 2]);
+        assert_eq!(low, vec![4, 4]);
+        assert!(low.len() < high.len());
+        assert!(low.iter().max() > high.iter().max());
+    }
+
+    #[test]
+    fn test_percentile_overrides_static_threshold() {
+        // The 20th percentile of the similarities falls on 0.6, so only the
+        // 0.3 drop is below it whatever the static threshold
+        assert_eq!(chunk_graded(0.99, Some(0.2)), vec![4, 4]);
+        assert_eq!(chunk_graded(0.1, Some(0.2)), vec![4, 4]);
+        assert_eq!(chunk_graded(0.1, None), vec![8]);
+    }
 }
@@ -3,9 +3,18 @@
 //! This module provides functions for generating embeddings using the Ollama API.
 //! It supports both single and batch embedding generation with error handling
 //! and retry logic.
+//!
+//! For large inputs, [`stream_embeddings`] yields embeddings one batch at a
+//! time instead of collecting every vector. At most
+//! [`EmbeddingBatchConfig::max_concurrent_batches`] batches are requested
+//! at once and batches are yielded in input order, so no more than
+//! `batch_size * max_concurrent_batches` vectors are held before the caller
+//! takes them. Each batch carries a [`BatchProgress`].
 
-use crate::ai::client::{EmbedRequest, OllamaClient};
+use crate::ai::client::OllamaClient;
 use crate::error::XzeError;
+use futures::{future, stream, Stream, StreamExt};
+use std::future::Future;
 use thiserror::Error;
 use tracing::{debug, warn};
 
@@ -27,6 +36,88 @@ pub enum EmbeddingError {
     /// Invalid model specified
     #[error("Invalid model specified: {0}")]
     InvalidModel(String),
+
+    /// Invalid batch size or concurrency
+    #[error("Invalid embedding batch configuration: {0}")]
+    InvalidBatchConfig(String),
+}
+
+/// Limits for batched embedding generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddingBatchConfig {
+    /// Number of texts per batch
+    pub batch_size: usize,
+    /// Number of batches requested at the same time
+    pub max_concurrent_batches: usize,
+}
+
+impl Default for EmbeddingBatchConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            max_concurrent_batches: 4,
+        }
+    }
+}
+
+impl EmbeddingBatchConfig {
+    /// Maximum number of embeddings held before the caller takes them
+    pub fn max_buffered_embeddings(&self) -> usize {
+        self.batch_size * self.max_concurrent_batches
+    }
+
+    /// Check that both limits are at least 1
+    ///
+    /// # Errors
+    ///
+    /// Returns `EmbeddingError::InvalidBatchConfig` if either limit is 0
+    pub fn validate(&self) -> std::result::Result<(), EmbeddingError> {
+        if self.batch_size == 0 {
+            return Err(EmbeddingError::InvalidBatchConfig(
+                "batch_size must be greater than 0".to_string(),
+            ));
+        }
+        if self.max_concurrent_batches == 0 {
+            return Err(EmbeddingError::InvalidBatchConfig(
+                "max_concurrent_batches must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Progress through a batched embedding run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Batches yielded so far, including this one
+    pub batches_completed: usize,
+    /// Batches in the run
+    pub total_batches: usize,
+    /// Texts embedded so far, including this batch
+    pub texts_completed: usize,
+    /// Texts in the run
+    pub total_texts: usize,
+}
+
+impl BatchProgress {
+    /// Share of texts embedded (0.0 to 1.0)
+    pub fn fraction(&self) -> f32 {
+        if self.total_texts == 0 {
+            return 1.0;
+        }
+        self.texts_completed as f32 / self.total_texts as f32
+    }
+}
+
+/// Embeddings for one batch of consecutive texts
+#[derive(Debug, Clone)]
+pub struct EmbeddingBatch {
+    /// Position of the first text of the batch in the input
+    pub offset: usize,
+    /// One embedding per text, in input order
+    pub embeddings: Vec<Vec<f32>>,
+    /// Progress after this batch
+    pub progress: BatchProgress,
 }
 
 impl From<XzeError> for EmbeddingError {
@@ -38,7 +129,9 @@ impl From<XzeError> for EmbeddingError {
 /// Generates embeddings for a single text using Ollama
 ///
 /// This function sends a request to the Ollama API to generate an embedding
-/// vector for the provided text using the specified model.
+/// vector for the provided text using the specified model. It goes through
+/// [`OllamaClient::embeddings`], so a client with an embedding cache only
+/// asks Ollama once per model and text.
 ///
 /// # Arguments
 ///
@@ -83,12 +176,7 @@ pub async fn generate_embeddings(
 
     debug!("Generating embedding for text of length {}", text.len());
 
-    let request = EmbedRequest {
-        model: model.to_string(),
-        prompt: text.to_string(),
-    };
-
-    let embedding = client.embed(request).await?;
+    let embedding = client.embeddings(model, text).await?;
 
     debug!("Generated embedding with dimension {}", embedding.len());
 
@@ -145,59 +233,155 @@ pub async fn generate_embeddings_batch(
     texts: &[String],
     batch_size: usize,
 ) -> std::result::Result<Vec<Vec<f32>>, EmbeddingError> {
-    if texts.is_empty() {
-        return Ok(Vec::new());
+    let config = EmbeddingBatchConfig {
+        batch_size,
+        max_concurrent_batches: 1,
+    };
+    collect_embeddings(stream_embeddings(client, model, texts, config)?).await
+}
+
+/// Streams embeddings for multiple texts, one batch at a time
+///
+/// Batches are requested up to `config.max_concurrent_batches` at a time
+/// and yielded in input order; see the [module documentation](self) for the
+/// memory bound. The stream ends after the first error. Dropping the stream
+/// cancels the outstanding requests.
+///
+/// # Errors
+///
+/// Returns `EmbeddingError::InvalidBatchConfig` if `config` is invalid. The
+/// stream yields the errors of [`generate_embeddings`], and
+/// `EmbeddingError::DimensionMismatch` if a batch's embeddings differ in
+/// size from the first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use xze_core::ai::client::OllamaClient;
+/// use xze_core::semantic::embeddings::{stream_embeddings, EmbeddingBatchConfig};
+///
+/// # async fn example(texts: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = OllamaClient::new("http://localhost:11434".to_string());
+/// let config = EmbeddingBatchConfig {
+///     batch_size: 64,
+///     max_concurrent_batches: 4,
+/// };
+///
+/// let mut batches = Box::pin(stream_embeddings(&client, "nomic-embed-text", &texts, config)?);
+/// while let Some(batch) = batches.next().await {
+///     let batch = batch?;
+///     println!("{:.0}% embedded", batch.progress.fraction() * 100.0);
+///     // Store batch.embeddings, then let them drop
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn stream_embeddings<'a>(
+    client: &'a OllamaClient,
+    model: &'a str,
+    texts: &'a [String],
+    config: EmbeddingBatchConfig,
+) -> std::result::Result<
+    impl Stream<Item = std::result::Result<EmbeddingBatch, EmbeddingError>> + 'a,
+    EmbeddingError,
+> {
+    batch_stream(texts, config, move |text| {
+        generate_embeddings(client, model, text)
+    })
+}
+
+/// Collect every batch of `batches` into one vector per text
+pub async fn collect_embeddings(
+    batches: impl Stream<Item = std::result::Result<EmbeddingBatch, EmbeddingError>>,
+) -> std::result::Result<Vec<Vec<f32>>, EmbeddingError> {
+    let mut batches = std::pin::pin!(batches);
+    let mut all_embeddings = Vec::new();
+    while let Some(batch) = batches.next().await {
+        all_embeddings.extend(batch?.embeddings);
     }
+    Ok(all_embeddings)
+}
+
+/// Batch `texts` and embed each with `embed`, yielding batches in order
+fn batch_stream<'a, F, Fut>(
+    texts: &'a [String],
+    config: EmbeddingBatchConfig,
+    embed: F,
+) -> std::result::Result<
+    impl Stream<Item = std::result::Result<EmbeddingBatch, EmbeddingError>> + 'a,
+    EmbeddingError,
+>
+where
+    F: Fn(&'a str) -> Fut + Clone + 'a,
+    Fut: Future<Output = std::result::Result<Vec<f32>, EmbeddingError>> + 'a,
+{
+    config.validate()?;
 
+    let total_texts = texts.len();
+    let total_batches = total_texts.div_ceil(config.batch_size);
     debug!(
-        "Generating embeddings for {} texts in batches of {}",
-        texts.len(),
-        batch_size
+        "Generating embeddings for {} texts in {} batches of {} ({} at a time)",
+        total_texts, total_batches, config.batch_size, config.max_concurrent_batches
     );
 
-    let mut all_embeddings = Vec::with_capacity(texts.len());
-    let mut expected_dimension: Option<usize> = None;
-
-    for (batch_idx, chunk) in texts.chunks(batch_size).enumerate() {
-        debug!(
-            "Processing batch {}/{} ({} texts)",
-            batch_idx + 1,
-            texts.len().div_ceil(batch_size),
-            chunk.len()
-        );
-
-        for text in chunk {
-            match generate_embeddings(client, model, text).await {
-                Ok(embedding) => {
-                    // Verify dimension consistency
-                    if let Some(expected) = expected_dimension {
-                        if embedding.len() != expected {
-                            return Err(EmbeddingError::DimensionMismatch {
-                                expected,
-                                actual: embedding.len(),
-                            });
-                        }
-                    } else {
-                        expected_dimension = Some(embedding.len());
-                    }
-
-                    all_embeddings.push(embedding);
-                }
-                Err(e) => {
-                    warn!("Failed to generate embedding for text: {}", e);
-                    return Err(e);
+    let batches = stream::iter(texts.chunks(config.batch_size).enumerate())
+        .map(move |(index, chunk)| {
+            let embed = embed.clone();
+            async move {
+                let mut embeddings = Vec::with_capacity(chunk.len());
+                for text in chunk {
+                    embeddings.push(embed(text).await.inspect_err(|e| {
+                        warn!("Failed to generate embedding for text: {}", e);
+                    })?);
                 }
+                Ok((index * config.batch_size, embeddings))
             }
-        }
-    }
+        })
+        .buffered(config.max_concurrent_batches);
 
-    debug!(
-        "Successfully generated {} embeddings with dimension {}",
-        all_embeddings.len(),
-        expected_dimension.unwrap_or(0)
-    );
-
-    Ok(all_embeddings)
+    // Check dimensions and count progress in input order, ending the stream
+    // after the first error
+    let mut expected_dimension: Option<usize> = None;
+    let mut progress = BatchProgress {
+        batches_completed: 0,
+        total_batches,
+        texts_completed: 0,
+        total_texts,
+    };
+    let mut failed = false;
+    Ok(batches.scan((), move |_, result| {
+        if failed {
+            return future::ready(None);
+        }
+        let batch = result.and_then(|(offset, embeddings): (usize, Vec<Vec<f32>>)| {
+            for embedding in &embeddings {
+                let expected = *expected_dimension.get_or_insert(embedding.len());
+                if embedding.len() != expected {
+                    return Err(EmbeddingError::DimensionMismatch {
+                        expected,
+                        actual: embedding.len(),
+                    });
+                }
+            }
+            progress.batches_completed += 1;
+            progress.texts_completed += embeddings.len();
+            debug!(
+                "Processed batch {}/{} ({}/{} texts)",
+                progress.batches_completed,
+                progress.total_batches,
+                progress.texts_completed,
+                progress.total_texts
+            );
+            Ok(EmbeddingBatch {
+                offset,
+                embeddings,
+                progress,
+            })
+        });
+        failed = batch.is_err();
+        future::ready(Some(batch))
+    }))
 }
 
 #[cfg(test)]
@@ -233,6 +417,148 @@ mod tests {
         assert!(err.to_string().contains("unknown-model"));
     }
 
-    // Note: Integration tests that require a running Ollama instance
-    // are in the integration test directory
+    #[test]
+    fn test_batch_config_validation() {
+        assert!(EmbeddingBatchConfig::default().validate().is_ok());
+        for config in [
+            EmbeddingBatchConfig {
+                batch_size: 0,
+                max_concurrent_batches: 1,
+            },
+            EmbeddingBatchConfig {
+                batch_size: 1,
+                max_concurrent_batches: 0,
+            },
+        ] {
+            assert!(matches!(
+                config.validate(),
+                Err(EmbeddingError::InvalidBatchConfig(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_preserves_order_and_reports_progress() {
+        let texts: Vec<String> = (0..7).map(|i| i.to_string()).collect();
+        let config = EmbeddingBatchConfig {
+            batch_size: 3,
+            max_concurrent_batches: 2,
+        };
+        let embed = |text: &str| {
+            let value: f32 = text.parse().unwrap();
+            async move {
+                // Later texts finish first
+                tokio::time::sleep(std::time::Duration::from_millis(10 - value as u64)).await;
+                Ok(vec![value, 0.0])
+            }
+        };
+
+        let batches: Vec<EmbeddingBatch> = batch_stream(&texts, config, embed)
+            .unwrap()
+            .map(|batch| batch.unwrap())
+            .collect()
+            .await;
+
+        let offsets: Vec<usize> = batches.iter().map(|b| b.offset).collect();
+        assert_eq!(offsets, [0, 3, 6]);
+        let values: Vec<f32> = batches
+            .iter()
+            .flat_map(|b| b.embeddings.iter().map(|e| e[0]))
+            .collect();
+        assert_eq!(values, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(
+            batches[1].progress,
+            BatchProgress {
+                batches_completed: 2,
+                total_batches: 3,
+                texts_completed: 6,
+                total_texts: 7,
+            }
+        );
+        assert_eq!(batches[2].progress.fraction(), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_memory_stays_bounded_for_large_input() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let texts: Vec<String> = vec!["text".to_string(); 20_000];
+        let config = EmbeddingBatchConfig {
+            batch_size: 50,
+            max_concurrent_batches: 4,
+        };
+        let produced = Arc::new(AtomicUsize::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let embed = {
+            let (produced, in_flight, max_in_flight) =
+                (produced.clone(), in_flight.clone(), max_in_flight.clone());
+            move |_: &str| {
+                let (produced, in_flight, max_in_flight) =
+                    (produced.clone(), in_flight.clone(), max_in_flight.clone());
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(now, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    produced.fetch_add(1, Ordering::SeqCst);
+                    Ok(vec![0.0; 768])
+                }
+            }
+        };
+
+        let mut batches = std::pin::pin!(batch_stream(&texts, config, embed).unwrap());
+        let mut consumed = 0;
+        let mut max_buffered = 0;
+        while let Some(batch) = batches.next().await {
+            let batch = batch.unwrap();
+            // Embeddings created but not yet handed to the caller
+            let buffered = produced.load(Ordering::SeqCst) - consumed;
+            max_buffered = max_buffered.max(buffered);
+            consumed += batch.embeddings.len();
+        }
+
+        assert_eq!(consumed, texts.len());
+        assert!(
+            max_buffered <= config.max_buffered_embeddings(),
+            "{} embeddings buffered",
+            max_buffered
+        );
+        assert!(max_in_flight.load(Ordering::SeqCst) <= config.max_concurrent_batches);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_dimension_mismatch() {
+        let texts: Vec<String> = (0..4).map(|i| i.to_string()).collect();
+        let config = EmbeddingBatchConfig {
+            batch_size: 1,
+            max_concurrent_batches: 1,
+        };
+        let embed = |text: &str| {
+            let len = if text == "1" { 3 } else { 2 };
+            async move { Ok(vec![0.0; len]) }
+        };
+
+        let results: Vec<_> = batch_stream(&texts, config, embed).unwrap().collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            results[1],
+            Err(EmbeddingError::DimensionMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected_up_front() {
+        let client = OllamaClient::new("http://localhost:11434".to_string());
+        let config = EmbeddingBatchConfig {
+            batch_size: 0,
+            max_concurrent_batches: 1,
+        };
+        assert!(stream_embeddings(&client, "nomic-embed-text", &[], config).is_err());
+    }
 }
@@ -0,0 +1,244 @@
+//! Markdown-aware splitting for semantic chunking
+//!
+//! [`SentenceSplitter`] sees a document as one run of sentences, so a chunk
+//! can start in one section and end in an unrelated one. [`MarkdownSplitter`]
+//! first cuts the document at ATX headings (`#` to `######`) and only then
+//! splits each section into sentences, recording the heading path of every
+//! section as breadcrumbs such as `["Guide", "Install"]`.
+//!
+//! Fenced code blocks (```` ``` ```` or `~~~`) and tables (runs of lines
+//! starting with `|`) are kept whole as single units, so the chunker never
+//! splits them across chunks. Heading lines themselves are not units; their
+//! text lives in the breadcrumbs.
+//!
+//! # Examples
+//!
+//! ```
+//! use xze_core::semantic::markdown::MarkdownSplitter;
+//!
+//! let text = "# Guide\n\nIntro sentence here.\n\n## Install\n\n```sh\nmake install\n```";
+//! let sections = MarkdownSplitter::new(10).sections(text);
+//!
+//! assert_eq!(sections.len(), 2);
+//! assert_eq!(sections[1].breadcrumbs, vec!["Guide", "Install"]);
+//! assert_eq!(sections[1].units, vec!["```sh\nmake install\n```"]);
+//! ```
+
+use super::splitter::SentenceSplitter;
+use super::strategy::heading_text;
+
+/// Units of one heading section and the headings above it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownSection {
+    /// Heading texts from the outermost heading down to this section's own;
+    /// empty for text before the first heading
+    pub breadcrumbs: Vec<String>,
+
+    /// Sentences, code blocks and tables in document order
+    pub units: Vec<String>,
+}
+
+/// Splits Markdown into heading sections of sentences, code blocks and tables
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownSplitter {
+    sentence_splitter: SentenceSplitter,
+}
+
+impl MarkdownSplitter {
+    /// Creates a splitter dropping prose sentences shorter than
+    /// `min_sentence_length` characters
+    ///
+    /// Code blocks and tables are kept whatever their length.
+    pub fn new(min_sentence_length: usize) -> Self {
+        Self {
+            sentence_splitter: SentenceSplitter::new(min_sentence_length),
+        }
+    }
+
+    /// Returns the minimum sentence length
+    pub fn min_sentence_length(&self) -> usize {
+        self.sentence_splitter.min_sentence_length()
+    }
+
+    /// Splits `text` into heading sections
+    ///
+    /// Sections without any units, such as a heading directly followed by a
+    /// subheading, are left out.
+    pub fn sections(&self, text: &str) -> Vec<MarkdownSection> {
+        let mut sections = Vec::new();
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut current = SectionBuilder::default();
+        let mut fence: Option<&str> = None;
+
+        for line in text.lines() {
+            let trimmed = line.trim_start();
+
+            if let Some(marker) = fence {
+                current.block.push(line);
+                if trimmed.starts_with(marker) {
+                    current.flush_block();
+                    fence = None;
+                }
+                continue;
+            }
+
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                current.flush(&self.sentence_splitter);
+                current.block.push(line);
+                fence = Some(marker);
+            } else if let Some(heading) = heading_text(trimmed) {
+                current.flush(&self.sentence_splitter);
+                sections.extend(current.finish(&headings));
+
+                let level = trimmed.chars().take_while(|&c| c == '#').count();
+                while headings.last().is_some_and(|(l, _)| *l >= level) {
+                    headings.pop();
+                }
+                headings.push((level, heading));
+            } else if trimmed.starts_with('|') {
+                current.flush_paragraph(&self.sentence_splitter);
+                current.block.push(line);
+            } else {
+                current.flush_block();
+                current.paragraph.push(line);
+            }
+        }
+
+        // An unclosed fence runs to the end of the document
+        current.flush_block();
+        current.flush(&self.sentence_splitter);
+        sections.extend(current.finish(&headings));
+        sections
+    }
+
+    /// Splits `text` into units of every section in document order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::semantic::markdown::MarkdownSplitter;
+    ///
+    /// let text = "# A\n\nFirst sentence here.\n\n| k | v |\n|---|---|\n| a | 1 |";
+    /// let units = MarkdownSplitter::new(10).split(text);
+    ///
+    /// assert_eq!(units, vec!["First sentence here.", "| k | v |\n|---|---|\n| a | 1 |"]);
+    /// ```
+    pub fn split(&self, text: &str) -> Vec<String> {
+        self.sections(text)
+            .into_iter()
+            .flat_map(|section| section.units)
+            .collect()
+    }
+}
+
+/// Lines and units of the section being read
+#[derive(Default)]
+struct SectionBuilder<'a> {
+    units: Vec<String>,
+    paragraph: Vec<&'a str>,
+    block: Vec<&'a str>,
+}
+
+impl SectionBuilder<'_> {
+    fn flush_paragraph(&mut self, splitter: &SentenceSplitter) {
+        if !self.paragraph.is_empty() {
+            self.units
+                .extend(splitter.split(&self.paragraph.join("\n")));
+            self.paragraph.clear();
+        }
+    }
+
+    fn flush_block(&mut self) {
+        if !self.block.is_empty() {
+            self.units.push(self.block.join("\n").trim().to_string());
+            self.block.clear();
+        }
+    }
+
+    fn flush(&mut self, splitter: &SentenceSplitter) {
+        self.flush_paragraph(splitter);
+        self.flush_block();
+    }
+
+    /// Takes the finished section, if it has any units
+    fn finish(&mut self, headings: &[(usize, String)]) -> Option<MarkdownSection> {
+        if self.units.is_empty() {
+            return None;
+        }
+        Some(MarkdownSection {
+            breadcrumbs: headings.iter().map(|(_, text)| text.clone()).collect(),
+            units: std::mem::take(&mut self.units),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "Preamble sentence before headings.\n\n\
+        # Guide\n\nThe guide starts here. It has two sentences.\n\n\
+        ## Install\n\nRun the installer first.\n\n\
+        ```sh\n# not a heading\nmake install. Then run it.\n```\n\n\
+        ### Linux\n\n| distro | package |\n|--------|---------|\n| Debian | xze.deb |\n\n\
+        ## Usage\n\nCall the binary afterwards.";
+
+    #[test]
+    fn test_sections_follow_heading_hierarchy() {
+        let sections = MarkdownSplitter::new(10).sections(DOC);
+        let breadcrumbs: Vec<Vec<String>> =
+            sections.iter().map(|s| s.breadcrumbs.clone()).collect();
+
+        assert_eq!(
+            breadcrumbs,
+            vec![
+                vec![],
+                vec!["Guide".to_string()],
+                vec!["Guide".to_string(), "Install".to_string()],
+                vec![
+                    "Guide".to_string(),
+                    "Install".to_string(),
+                    "Linux".to_string()
+                ],
+                vec!["Guide".to_string(), "Usage".to_string()],
+            ]
+        );
+        assert_eq!(sections[1].units.len(), 2);
+    }
+
+    #[test]
+    fn test_code_blocks_and_tables_stay_whole() {
+        let sections = MarkdownSplitter::new(10).sections(DOC);
+
+        assert_eq!(
+            sections[2].units,
+            vec![
+                "Run the installer first.",
+                "```sh\n# not a heading\nmake install. Then run it.\n```",
+            ]
+        );
+        assert_eq!(
+            sections[3].units,
+            vec!["| distro | package |\n|--------|---------|\n| Debian | xze.deb |"]
+        );
+    }
+
+    #[test]
+    fn test_tilde_fence_and_unclosed_fence() {
+        let splitter = MarkdownSplitter::new(10);
+
+        let units = splitter.split("~~~\n``` inside\n~~~\nAfter the fence ends.");
+        assert_eq!(units, vec!["~~~\n``` inside\n~~~", "After the fence ends."]);
+
+        let units = splitter.split("# A\n\n```rust\nfn main() {}\n# B");
+        assert_eq!(units, vec!["```rust\nfn main() {}\n# B"]);
+    }
+
+    #[test]
+    fn test_empty_sections_are_skipped() {
+        let sections = MarkdownSplitter::new(10).sections("# A\n## B\n\nOnly this sentence.");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].breadcrumbs, vec!["A", "B"]);
+        assert!(MarkdownSplitter::default().sections("").is_empty());
+    }
+}
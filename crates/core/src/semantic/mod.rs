@@ -15,6 +15,8 @@
 //!
 //! - [`types`] - Core data structures (SemanticChunk, ChunkMetadata)
 //! - [`splitter`] - Sentence splitting with code preservation
+//! - [`markdown`] - Heading-aware splitting that keeps code blocks and tables whole
+//! - [`strategy`] - Selectable chunking strategies and when to use each
 //!
 //! # Examples
 //!
@@ -39,17 +41,26 @@
 
 pub mod chunker;
 pub mod embeddings;
+pub mod markdown;
 pub mod search;
 pub mod similarity;
 pub mod splitter;
+pub mod strategy;
 pub mod types;
 
 // Re-export commonly used types
 pub use chunker::{ChunkerConfig, ChunkingError, SemanticChunker};
-pub use embeddings::{generate_embeddings, generate_embeddings_batch, EmbeddingError};
+pub use embeddings::{
+    collect_embeddings, generate_embeddings, generate_embeddings_batch, stream_embeddings,
+    BatchProgress, EmbeddingBatch, EmbeddingBatchConfig, EmbeddingError,
+};
+pub use markdown::{MarkdownSection, MarkdownSplitter};
 pub use search::{search_with_chunks, ChunkSearchResult, SearchConfig, SearchError};
 pub use similarity::{
-    calculate_percentile, cosine_similarity, pairwise_similarities, SimilarityError,
+    calculate_percentile, cosine_similarity, cosine_similarity_scalar, dot_product,
+    euclidean_similarity, pairwise_similarities, pairwise_similarities_with, simd_enabled,
+    SimilarityError, SimilarityMetric,
 };
-pub use splitter::SentenceSplitter;
+pub use splitter::{SentenceSplitter, SplitterKind};
+pub use strategy::ChunkStrategy;
 pub use types::{ChunkMetadata, SemanticChunk};
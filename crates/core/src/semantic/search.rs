@@ -8,7 +8,9 @@
 //! The search functionality:
 //! - Generates embeddings for search queries
 //! - Compares query embeddings against stored chunk embeddings
-//! - Ranks results by cosine similarity
+//! - Ranks results by the configured similarity metric (cosine by default)
+//! - Optionally blends in BM25 keyword scores, falling back to BM25 alone
+//!   when the query cannot be embedded
 //! - Returns rich metadata about matching chunks
 //!
 //! # Examples
@@ -37,11 +39,16 @@
 //! ```
 
 use crate::ai::client::OllamaClient;
+use crate::search::bm25::Bm25;
+use crate::search::index::SearchIndex;
+use crate::search::normalize::TextNormalizer;
+use crate::search::synonyms::SynonymMap;
 use crate::semantic::embeddings::{generate_embeddings, EmbeddingError};
-use crate::semantic::similarity::{cosine_similarity, SimilarityError};
+use crate::semantic::similarity::{SimilarityError, SimilarityMetric};
 use sqlx::{PgPool, Row};
+use std::sync::Arc;
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Search result containing chunk information and similarity score
 ///
@@ -53,13 +60,15 @@ use tracing::{debug, info};
 /// - `id` - Unique database identifier for the chunk
 /// - `source_file` - Path to the source file containing the chunk
 /// - `content` - Text content of the chunk
-/// - `similarity` - Cosine similarity score (0.0 to 1.0) between query and chunk
+/// - `similarity` - Score between query and chunk under the configured metric
 /// - `chunk_index` - Zero-based index of this chunk within the document
 /// - `total_chunks` - Total number of chunks in the source document
 /// - `title` - Optional document title from metadata
 /// - `category` - Optional document category (Diataxis framework)
 /// - `sentence_range` - Range of sentence indices (start, end) within the document
 /// - `avg_chunk_similarity` - Average similarity between sentences in the chunk
+/// - `vector_score` - Embedding similarity, if the query was embedded
+/// - `lexical_score` - Normalized BM25 score, if hybrid ranking was used
 #[derive(Debug, Clone, PartialEq)]
 pub struct ChunkSearchResult {
     /// Unique database identifier
@@ -82,6 +91,11 @@ pub struct ChunkSearchResult {
     pub sentence_range: (i32, i32),
     /// Average chunk similarity
     pub avg_chunk_similarity: f32,
+    /// Embedding similarity, if the query was embedded
+    pub vector_score: Option<f32>,
+    /// BM25 score relative to the best match (0.0 to 1.0), if hybrid
+    /// ranking was used
+    pub lexical_score: Option<f32>,
 }
 
 /// Configuration for semantic search
@@ -92,14 +106,21 @@ pub struct ChunkSearchResult {
 ///
 /// ```
 /// use xze_core::semantic::search::SearchConfig;
+/// use xze_core::semantic::similarity::SimilarityMetric;
 ///
 /// let config = SearchConfig::default();
 /// assert_eq!(config.max_results, 10);
+/// assert_eq!(config.similarity_metric, SimilarityMetric::Cosine);
 ///
 /// let custom = SearchConfig {
 ///     max_results: 20,
 ///     min_similarity: 0.5,
 ///     category_filter: Some("tutorial".to_string()),
+///     language_filter: Some("eng".to_string()),
+///     similarity_metric: SimilarityMetric::DotProduct,
+///     alpha: Some(0.7),
+///     synonyms: None,
+///     normalizer: None,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -110,6 +131,30 @@ pub struct SearchConfig {
     pub min_similarity: f32,
     /// Optional category filter
     pub category_filter: Option<String>,
+    /// Optional document language filter, an ISO 639-3 code such as `eng`
+    ///
+    /// Matches the `language` the loader records in chunk metadata, so it is
+    /// only supported by indexed search.
+    pub language_filter: Option<String>,
+    /// Metric used to rank chunks; must match the metric recorded with the
+    /// stored embeddings
+    pub similarity_metric: SimilarityMetric,
+    /// Weight of embedding similarity in hybrid ranking (0.0 to 1.0)
+    ///
+    /// When set, `similarity` becomes `alpha` times the embedding similarity
+    /// plus `1 - alpha` times the BM25 score of the chunk title and content,
+    /// and search falls back to BM25 alone if the query cannot be embedded.
+    /// `0.0` ranks by keywords without contacting Ollama. `None` ranks by
+    /// embedding similarity only.
+    pub alpha: Option<f32>,
+    /// Synonyms added to the query terms before BM25 scoring, so a query
+    /// for `k8s` matches chunks that say `kubernetes`; only used when
+    /// `alpha` is set
+    pub synonyms: Option<Arc<SynonymMap>>,
+    /// Normalizer applied to chunk text and the query before BM25 scoring,
+    /// so `Café` matches `cafe` and, with stemming, `running` matches
+    /// `run`; only used when `alpha` is set
+    pub normalizer: Option<Arc<TextNormalizer>>,
 }
 
 impl Default for SearchConfig {
@@ -118,6 +163,11 @@ impl Default for SearchConfig {
             max_results: 10,
             min_similarity: 0.0,
             category_filter: None,
+            language_filter: None,
+            similarity_metric: SimilarityMetric::default(),
+            alpha: None,
+            synonyms: None,
+            normalizer: None,
         }
     }
 }
@@ -148,13 +198,48 @@ pub enum SearchError {
     /// Invalid configuration
     #[error("Invalid search configuration: {0}")]
     InvalidConfig(String),
+
+    /// Stored embeddings were recorded for a different metric
+    #[error("Chunks were embedded for {stored} similarity, but {requested} was requested")]
+    MetricMismatch {
+        /// Metric recorded with the stored chunk
+        stored: SimilarityMetric,
+        /// Metric in the search configuration
+        requested: SimilarityMetric,
+    },
+}
+
+/// Check that a stored chunk can be ranked with the requested metric
+///
+/// Chunks stored without a metric predate the setting and were compared
+/// with cosine similarity.
+///
+/// # Errors
+///
+/// Returns `SearchError::MetricMismatch` if the metrics differ, and
+/// `SearchError::InvalidConfig` if the stored metric is not recognized
+pub(crate) fn check_metric(
+    stored: Option<&str>,
+    requested: SimilarityMetric,
+) -> Result<(), SearchError> {
+    let stored = match stored {
+        Some(name) => name.parse().map_err(|e: SimilarityError| {
+            SearchError::InvalidConfig(format!("Stored chunk has {}", e))
+        })?,
+        None => SimilarityMetric::Cosine,
+    };
+    if stored != requested {
+        return Err(SearchError::MetricMismatch { stored, requested });
+    }
+    Ok(())
 }
 
 /// Search for document chunks semantically similar to a query
 ///
 /// This function generates an embedding for the query text, retrieves all
 /// chunks from the database, calculates similarity scores, and returns
-/// the top matching results.
+/// the top matching results. With `config.alpha` set, scores are blended
+/// with BM25 over the retrieved chunks as described on [`SearchConfig`].
 ///
 /// # Arguments
 ///
@@ -171,8 +256,10 @@ pub enum SearchError {
 ///
 /// Returns `SearchError` if:
 /// - Query is empty
-/// - Embedding generation fails
+/// - A language filter is set, since chunk tables do not record languages
+/// - Embedding generation fails and `config.alpha` is unset
 /// - Database query fails
+/// - A chunk was stored for a different similarity metric
 /// - Similarity calculation fails
 ///
 /// # Examples
@@ -187,6 +274,7 @@ pub enum SearchError {
 ///     max_results: 5,
 ///     min_similarity: 0.3,
 ///     category_filter: Some("how_to".to_string()),
+///     ..Default::default()
 /// };
 ///
 /// let results = search_with_chunks(
@@ -208,34 +296,13 @@ pub async fn search_with_chunks(
     ollama_url: &str,
     config: &SearchConfig,
 ) -> Result<Vec<ChunkSearchResult>, SearchError> {
-    // Validate input
-    if query.trim().is_empty() {
-        return Err(SearchError::EmptyQuery);
-    }
-
-    if config.max_results == 0 {
-        return Err(SearchError::InvalidConfig(
-            "max_results must be greater than 0".to_string(),
-        ));
-    }
-
-    if config.min_similarity < 0.0 || config.min_similarity > 1.0 {
+    if config.language_filter.is_some() {
         return Err(SearchError::InvalidConfig(
-            "min_similarity must be between 0.0 and 1.0".to_string(),
+            "language_filter requires indexed search; semantic_chunks does not record languages"
+                .to_string(),
         ));
     }
-
-    info!(
-        "Searching for: '{}' (max: {}, min_sim: {})",
-        query, config.max_results, config.min_similarity
-    );
-
-    // Create Ollama client
-    let client = OllamaClient::new(ollama_url.to_string());
-
-    // Generate embedding for the query
-    debug!("Generating embedding for query");
-    let query_embedding = generate_embeddings(&client, "nomic-embed-text", query).await?;
+    let query_embedding = embed_query(query, ollama_url, config).await?;
 
     // Build SQL query with optional category filter
     let sql = if let Some(ref _category) = config.category_filter {
@@ -251,7 +318,8 @@ pub async fn search_with_chunks(
                 category,
                 start_sentence,
                 end_sentence,
-                avg_similarity
+                avg_similarity,
+                similarity_metric
             FROM semantic_chunks
             WHERE category = $1
             ORDER BY created_at DESC
@@ -269,7 +337,8 @@ pub async fn search_with_chunks(
                 category,
                 start_sentence,
                 end_sentence,
-                avg_similarity
+                avg_similarity,
+                similarity_metric
             FROM semantic_chunks
             ORDER BY created_at DESC
         "#
@@ -302,10 +371,6 @@ pub async fn search_with_chunks(
             .try_get("content")
             .map_err(|e| SearchError::Database(format!("Failed to get content: {}", e)))?;
 
-        let embedding_bytes: Vec<u8> = row
-            .try_get("embedding")
-            .map_err(|e| SearchError::Database(format!("Failed to get embedding: {}", e)))?;
-
         let chunk_index: i32 = row
             .try_get("chunk_index")
             .map_err(|e| SearchError::Database(format!("Failed to get chunk_index: {}", e)))?;
@@ -329,42 +394,178 @@ pub async fn search_with_chunks(
             .try_get("avg_similarity")
             .map_err(|e| SearchError::Database(format!("Failed to get avg_similarity: {}", e)))?;
 
-        // Parse embedding from byte array
-        let chunk_embedding = parse_embedding(&embedding_bytes)?;
+        let vector_score = match query_embedding {
+            Some(ref query_embedding) => {
+                let stored_metric: Option<String> = row.try_get("similarity_metric").ok();
+                check_metric(stored_metric.as_deref(), config.similarity_metric)?;
+
+                // Parse embedding from byte array
+                let embedding_bytes: Vec<u8> = row.try_get("embedding").map_err(|e| {
+                    SearchError::Database(format!("Failed to get embedding: {}", e))
+                })?;
+                let chunk_embedding = parse_embedding(&embedding_bytes)?;
+
+                Some(
+                    config
+                        .similarity_metric
+                        .similarity(query_embedding, &chunk_embedding)?,
+                )
+            }
+            None => None,
+        };
 
-        // Calculate cosine similarity
-        let similarity = cosine_similarity(&query_embedding, &chunk_embedding)?;
+        results.push(ChunkSearchResult {
+            id,
+            source_file: file_path,
+            content,
+            similarity: vector_score.unwrap_or(0.0),
+            chunk_index,
+            total_chunks,
+            title,
+            category,
+            sentence_range: (start_sentence, end_sentence),
+            avg_chunk_similarity: avg_similarity,
+            vector_score,
+            lexical_score: None,
+        });
+    }
 
-        // Filter by minimum similarity
-        if similarity >= config.min_similarity {
-            results.push(ChunkSearchResult {
-                id,
-                source_file: file_path,
-                content,
-                similarity,
-                chunk_index,
-                total_chunks,
-                title,
-                category,
-                sentence_range: (start_sentence, end_sentence),
-                avg_chunk_similarity: avg_similarity,
-            });
+    let results = rank_chunks(results, Some(query), config);
+    info!("Returning {} search results", results.len());
+
+    Ok(results)
+}
+
+/// Search a [`SearchIndex`] instead of the database
+///
+/// Validates the request and embeds the query exactly like
+/// [`search_with_chunks`], then ranks the chunks in the index's current
+/// snapshot. Results reflect every file upserted into the index so far.
+///
+/// # Errors
+///
+/// Returns `SearchError` if:
+/// - Query is empty or the configuration is invalid
+/// - Embedding generation fails and `config.alpha` is unset
+/// - Similarity calculation fails
+pub async fn search_with_index(
+    index: &SearchIndex,
+    query: &str,
+    ollama_url: &str,
+    config: &SearchConfig,
+) -> Result<Vec<ChunkSearchResult>, SearchError> {
+    let query_embedding = embed_query(query, ollama_url, config).await?;
+
+    let results = index.search_hybrid(query, query_embedding.as_deref(), config)?;
+    info!("Returning {} indexed search results", results.len());
+
+    Ok(results)
+}
+
+/// Blend, filter, sort and truncate scored chunks
+///
+/// With a `query` and `config.alpha` set, each chunk's `similarity` becomes
+/// its hybrid score, using BM25 alone for chunks without a `vector_score`.
+/// The query is expanded with `config.synonyms` for BM25 only, then the
+/// query and chunk text are normalized with `config.normalizer`.
+/// BM25 statistics come from `results`, so pass every candidate before
+/// any limit is applied.
+pub(crate) fn rank_chunks(
+    mut results: Vec<ChunkSearchResult>,
+    query: Option<&str>,
+    config: &SearchConfig,
+) -> Vec<ChunkSearchResult> {
+    if let (Some(query), Some(alpha)) = (query, config.alpha) {
+        let normalize = |text: String| match &config.normalizer {
+            Some(normalizer) => normalizer.normalize(&text),
+            None => text,
+        };
+        let bm25 = Bm25::new(results.iter().map(|r| {
+            normalize(match &r.title {
+                Some(title) => format!("{}\n{}", title, r.content),
+                None => r.content.clone(),
+            })
+        }));
+        let query = normalize(match &config.synonyms {
+            Some(synonyms) => synonyms.expand_query(query),
+            None => query.to_string(),
+        });
+        for (result, lexical) in results.iter_mut().zip(bm25.normalized_scores(&query)) {
+            result.similarity = match result.vector_score {
+                Some(vector) => alpha * vector + (1.0 - alpha) * lexical,
+                None => lexical,
+            };
+            result.lexical_score = Some(lexical);
         }
     }
 
-    // Sort by similarity (descending)
+    results.retain(|r| r.similarity >= config.min_similarity);
     results.sort_by(|a, b| {
         b.similarity
             .partial_cmp(&a.similarity)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
-
-    // Limit results
     results.truncate(config.max_results);
+    results
+}
 
-    info!("Returning {} search results", results.len());
+/// Validate a search request and generate the query embedding
+///
+/// Returns `None` when hybrid ranking should use BM25 alone: when
+/// `config.alpha` is 0.0, or when it is set and embedding fails.
+async fn embed_query(
+    query: &str,
+    ollama_url: &str,
+    config: &SearchConfig,
+) -> Result<Option<Vec<f32>>, SearchError> {
+    // Validate input
+    if query.trim().is_empty() {
+        return Err(SearchError::EmptyQuery);
+    }
 
-    Ok(results)
+    if config.max_results == 0 {
+        return Err(SearchError::InvalidConfig(
+            "max_results must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.min_similarity < 0.0 || config.min_similarity > 1.0 {
+        return Err(SearchError::InvalidConfig(
+            "min_similarity must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if let Some(alpha) = config.alpha {
+        if !(0.0..=1.0).contains(&alpha) {
+            return Err(SearchError::InvalidConfig(
+                "alpha must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+    }
+
+    info!(
+        "Searching for: '{}' (max: {}, min_sim: {}, metric: {}, alpha: {:?})",
+        query, config.max_results, config.min_similarity, config.similarity_metric, config.alpha
+    );
+
+    if config.alpha == Some(0.0) {
+        debug!("Skipping query embedding for lexical ranking");
+        return Ok(None);
+    }
+
+    // Reuse pooled connections across searches
+    let client = OllamaClient::shared(ollama_url);
+
+    // Generate embedding for the query
+    debug!("Generating embedding for query");
+    match generate_embeddings(&client, "nomic-embed-text", query).await {
+        Ok(embedding) => Ok(Some(embedding)),
+        Err(e) if config.alpha.is_some() => {
+            warn!("Query embedding failed, ranking by BM25 only: {}", e);
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
 }
 
 /// Parse embedding vector from byte array
@@ -404,12 +605,31 @@ fn parse_embedding(bytes: &[u8]) -> Result<Vec<f32>, SearchError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_metric() {
+        assert!(check_metric(None, SimilarityMetric::Cosine).is_ok());
+        assert!(check_metric(Some("dot_product"), SimilarityMetric::DotProduct).is_ok());
+        assert!(matches!(
+            check_metric(None, SimilarityMetric::Euclidean),
+            Err(SearchError::MetricMismatch {
+                stored: SimilarityMetric::Cosine,
+                requested: SimilarityMetric::Euclidean,
+            })
+        ));
+        assert!(matches!(
+            check_metric(Some("manhattan"), SimilarityMetric::Cosine),
+            Err(SearchError::InvalidConfig(_))
+        ));
+    }
+
     #[test]
     fn test_search_config_default() {
         let config = SearchConfig::default();
         assert_eq!(config.max_results, 10);
         assert_eq!(config.min_similarity, 0.0);
         assert!(config.category_filter.is_none());
+        assert!(config.language_filter.is_none());
+        assert_eq!(config.similarity_metric, SimilarityMetric::Cosine);
     }
 
     #[test]
@@ -418,6 +638,11 @@ mod tests {
             max_results: 20,
             min_similarity: 0.5,
             category_filter: Some("tutorial".to_string()),
+            language_filter: None,
+            similarity_metric: SimilarityMetric::Euclidean,
+            alpha: Some(0.5),
+            synonyms: None,
+            normalizer: None,
         };
         assert_eq!(config.max_results, 20);
         assert_eq!(config.min_similarity, 0.5);
@@ -471,6 +696,8 @@ mod tests {
             category: Some("tutorial".to_string()),
             sentence_range: (0, 3),
             avg_chunk_similarity: 0.85,
+            vector_score: Some(0.95),
+            lexical_score: None,
         };
 
         assert_eq!(result.id, 1);
@@ -498,4 +725,211 @@ mod tests {
         let error = SearchError::InvalidConfig("test".to_string());
         assert_eq!(error.to_string(), "Invalid search configuration: test");
     }
+
+    #[tokio::test]
+    async fn test_search_with_index_validates_before_embedding() {
+        let index = SearchIndex::new();
+
+        let result =
+            search_with_index(&index, "  ", "http://localhost:1", &SearchConfig::default()).await;
+        assert!(matches!(result, Err(SearchError::EmptyQuery)));
+
+        let config = SearchConfig {
+            max_results: 0,
+            ..Default::default()
+        };
+        let result = search_with_index(&index, "query", "http://localhost:1", &config).await;
+        assert!(matches!(result, Err(SearchError::InvalidConfig(_))));
+    }
+
+    fn scored(content: &str, vector_score: Option<f32>) -> ChunkSearchResult {
+        ChunkSearchResult {
+            id: 0,
+            source_file: format!("{}.md", content),
+            content: content.to_string(),
+            similarity: vector_score.unwrap_or(0.0),
+            chunk_index: 0,
+            total_chunks: 1,
+            title: None,
+            category: None,
+            sentence_range: (0, 0),
+            avg_chunk_similarity: 0.0,
+            vector_score,
+            lexical_score: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_chunks_blends_scores() {
+        let results = vec![
+            scored("configure the retry_policy option", Some(0.6)),
+            scored("general configuration overview", Some(0.8)),
+        ];
+
+        // Embedding similarity alone prefers the overview
+        let config = SearchConfig::default();
+        let ranked = rank_chunks(results.clone(), Some("retry_policy"), &config);
+        assert_eq!(ranked[0].content, "general configuration overview");
+        assert!(ranked[0].lexical_score.is_none());
+
+        // An exact keyword match wins once BM25 carries weight
+        let config = SearchConfig {
+            alpha: Some(0.5),
+            ..Default::default()
+        };
+        let ranked = rank_chunks(results, Some("retry_policy"), &config);
+        assert_eq!(ranked[0].content, "configure the retry_policy option");
+        assert_eq!(ranked[0].lexical_score, Some(1.0));
+        assert_eq!(ranked[0].vector_score, Some(0.6));
+        assert!((ranked[0].similarity - 0.8).abs() < 1e-6);
+        assert!((ranked[1].similarity - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rank_chunks_lexical_only_without_embeddings() {
+        let results = vec![scored("install guide", None), scored("logging setup", None)];
+        let config = SearchConfig {
+            alpha: Some(0.7),
+            min_similarity: 0.5,
+            ..Default::default()
+        };
+
+        let ranked = rank_chunks(results, Some("logging"), &config);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].content, "logging setup");
+        assert_eq!(ranked[0].similarity, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_search_with_index_falls_back_to_lexical() {
+        use crate::kb::store::DocumentChunk;
+        use crate::search::index::IndexedDocument;
+
+        let index = SearchIndex::new();
+        for (path, content) in [
+            ("install.md", "Install with cargo"),
+            ("log.md", "Logging levels"),
+        ] {
+            index.upsert(IndexedDocument::new(
+                path,
+                vec![DocumentChunk::new(
+                    "chunk_0".to_string(),
+                    content.to_string(),
+                    vec![1.0, 0.0],
+                    serde_json::json!({}),
+                )],
+            ));
+        }
+
+        // Ollama is unreachable, so only hybrid search succeeds
+        let unreachable = "http://127.0.0.1:1";
+        let result =
+            search_with_index(&index, "logging", unreachable, &SearchConfig::default()).await;
+        assert!(matches!(result, Err(SearchError::EmbeddingGeneration(_))));
+
+        let config = SearchConfig {
+            alpha: Some(0.5),
+            ..Default::default()
+        };
+        let results = search_with_index(&index, "logging", unreachable, &config)
+            .await
+            .unwrap();
+        assert_eq!(results[0].source_file, "log.md");
+        assert!(results[0].vector_score.is_none());
+        assert_eq!(results[0].lexical_score, Some(1.0));
+
+        let config = SearchConfig {
+            alpha: Some(1.5),
+            ..Default::default()
+        };
+        let result = search_with_index(&index, "logging", unreachable, &config).await;
+        assert!(matches!(result, Err(SearchError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_expands_query_synonyms() {
+        use crate::kb::store::DocumentChunk;
+        use crate::search::index::IndexedDocument;
+
+        let index = SearchIndex::new();
+        index.upsert(IndexedDocument::new(
+            "deploy.md",
+            vec![DocumentChunk::new(
+                "chunk_0".to_string(),
+                "Deploy the service to a kubernetes cluster".to_string(),
+                vec![1.0, 0.0],
+                serde_json::json!({}),
+            )],
+        ));
+
+        let lexical = SearchConfig {
+            alpha: Some(0.0),
+            min_similarity: 0.1,
+            ..Default::default()
+        };
+        let ollama = "http://127.0.0.1:1";
+        let results = search_with_index(&index, "k8s", ollama, &lexical)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+
+        let config = SearchConfig {
+            synonyms: Some(Arc::new(SynonymMap::new([("kubernetes", vec!["k8s"])]))),
+            ..lexical
+        };
+        let results = search_with_index(&index, "k8s", ollama, &config)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source_file, "deploy.md");
+    }
+    #[tokio::test]
+    async fn test_search_normalizes_loaded_documents() {
+        use crate::kb::backend::KbBackendKind;
+        use crate::kb::loader::{IncrementalLoader, LoaderConfig};
+        use crate::search::normalize::NormalizerConfig;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("cafe.md"), "Meet at the Café downtown.").unwrap();
+        std::fs::write(dir.path().join("tests.md"), "Running the tests nightly.").unwrap();
+
+        let index = Arc::new(SearchIndex::new());
+        let config = LoaderConfig {
+            backend: KbBackendKind::Memory,
+            ..Default::default()
+        };
+        IncrementalLoader::new(None, config)
+            .unwrap()
+            .with_search_index(index.clone())
+            .load(&[dir.path().to_string_lossy().to_string()])
+            .await
+            .unwrap();
+
+        let lexical = SearchConfig {
+            alpha: Some(0.0),
+            min_similarity: 0.1,
+            ..Default::default()
+        };
+        let ollama = "http://127.0.0.1:1";
+        let results = search_with_index(&index, "cafe", ollama, &lexical)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+
+        let config = SearchConfig {
+            normalizer: Some(Arc::new(TextNormalizer::new(NormalizerConfig {
+                fold_diacritics: true,
+                stemming: true,
+                ..Default::default()
+            }))),
+            ..lexical
+        };
+        for (query, expected) in [("cafe", "cafe.md"), ("run", "tests.md")] {
+            let results = search_with_index(&index, query, ollama, &config)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1, "query {}", query);
+            assert!(results[0].source_file.ends_with(expected));
+        }
+    }
 }
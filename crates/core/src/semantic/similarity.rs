@@ -3,7 +3,19 @@
 //! This module provides functions for calculating similarity between embeddings,
 //! including cosine similarity, pairwise similarity calculations, and percentile
 //! computations for threshold determination.
+//!
+//! Embedding models are trained for a particular metric, so the metric is
+//! configurable through [`SimilarityMetric`]. Every metric returns a score
+//! where higher means more similar, so ranking code does not depend on the
+//! metric in use.
+//!
+//! With the `simd` feature enabled, the dot product and magnitude loops use
+//! AVX and FMA instructions on x86_64 CPUs that support them. Support is
+//! detected at runtime; other CPUs use the scalar loop. The two paths agree
+//! within floating-point rounding, since only the summation order differs.
 
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
 /// Errors that can occur during similarity calculations
@@ -22,6 +34,161 @@ pub enum SimilarityError {
     InvalidValue(String),
 }
 
+/// Metric used to compare embeddings
+///
+/// | Metric | Score | Use with |
+/// |--------|-------|----------|
+/// | `Cosine` | Cosine of the angle, -1.0 to 1.0 | Most sentence-embedding models (default) |
+/// | `DotProduct` | Raw dot product, unbounded | Models trained for maximum inner product search |
+/// | `Euclidean` | `1 / (1 + distance)`, 0.0 to 1.0 | Models trained for L2 distance |
+///
+/// For normalized embeddings all three rank results the same way.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::similarity::SimilarityMetric;
+///
+/// let a = [3.0, 4.0];
+/// assert!((SimilarityMetric::Cosine.similarity(&a, &[6.0, 8.0]).unwrap() - 1.0).abs() < 1e-6);
+/// assert_eq!(SimilarityMetric::DotProduct.similarity(&a, &[1.0, 1.0]).unwrap(), 7.0);
+/// assert_eq!(SimilarityMetric::Euclidean.similarity(&a, &[0.0, 0.0]).unwrap(), 1.0 / 6.0);
+/// assert_eq!("dot".parse::<SimilarityMetric>().unwrap(), SimilarityMetric::DotProduct);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityMetric {
+    /// Cosine similarity (default)
+    #[default]
+    Cosine,
+    /// Dot product
+    DotProduct,
+    /// Euclidean (L2) distance, mapped to a similarity
+    Euclidean,
+}
+
+impl SimilarityMetric {
+    /// All supported metrics
+    pub const ALL: [SimilarityMetric; 3] = [
+        SimilarityMetric::Cosine,
+        SimilarityMetric::DotProduct,
+        SimilarityMetric::Euclidean,
+    ];
+
+    /// Score two vectors; higher is more similar
+    ///
+    /// # Errors
+    ///
+    /// Returns `SimilarityError::DimensionMismatch` if the vectors differ in
+    /// length or are empty, `SimilarityError::ZeroVector` for a zero vector
+    /// under `Cosine`, and `SimilarityError::InvalidValue` for NaN or
+    /// infinite results
+    pub fn similarity(&self, a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
+        match self {
+            SimilarityMetric::Cosine => cosine_similarity(a, b),
+            SimilarityMetric::DotProduct => dot_product(a, b),
+            SimilarityMetric::Euclidean => euclidean_similarity(a, b),
+        }
+    }
+}
+
+impl fmt::Display for SimilarityMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimilarityMetric::Cosine => write!(f, "cosine"),
+            SimilarityMetric::DotProduct => write!(f, "dot_product"),
+            SimilarityMetric::Euclidean => write!(f, "euclidean"),
+        }
+    }
+}
+
+impl std::str::FromStr for SimilarityMetric {
+    type Err = SimilarityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "cosine" => Ok(SimilarityMetric::Cosine),
+            "dot_product" | "dot" | "inner_product" => Ok(SimilarityMetric::DotProduct),
+            "euclidean" | "l2" => Ok(SimilarityMetric::Euclidean),
+            other => Err(SimilarityError::InvalidValue(format!(
+                "Unknown similarity metric: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Calculates the dot product of two vectors
+///
+/// # Errors
+///
+/// Returns the same errors as [`cosine_similarity`], except that zero
+/// vectors are allowed and score 0.0
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::similarity::dot_product;
+///
+/// assert_eq!(dot_product(&[1.0, 2.0], &[3.0, 4.0]).unwrap(), 11.0);
+/// ```
+pub fn dot_product(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
+    check_dimensions(a, b)?;
+    let (dot_product, _, _) = dot_and_norms(a, b);
+    check_finite(dot_product)
+}
+
+/// Calculates a similarity from the Euclidean distance between two vectors
+///
+/// Returns `1 / (1 + distance)`: 1.0 for identical vectors, approaching 0.0
+/// as they move apart.
+///
+/// # Errors
+///
+/// Returns the same errors as [`dot_product`]
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::similarity::euclidean_similarity;
+///
+/// assert_eq!(euclidean_similarity(&[1.0, 1.0], &[1.0, 1.0]).unwrap(), 1.0);
+/// assert_eq!(euclidean_similarity(&[0.0, 0.0], &[3.0, 4.0]).unwrap(), 1.0 / 6.0);
+/// ```
+pub fn euclidean_similarity(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
+    check_dimensions(a, b)?;
+    // |a - b|^2 = |a|^2 + |b|^2 - 2ab loses precision for nearby vectors,
+    // so the distance is summed directly
+    let squared: f32 = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum();
+    check_finite(1.0 / (1.0 + squared.sqrt()))
+}
+
+fn check_dimensions(a: &[f32], b: &[f32]) -> Result<(), SimilarityError> {
+    if a.len() != b.len() {
+        return Err(SimilarityError::DimensionMismatch {
+            expected: a.len(),
+            actual: b.len(),
+        });
+    }
+    if a.is_empty() {
+        return Err(SimilarityError::DimensionMismatch {
+            expected: 0,
+            actual: 0,
+        });
+    }
+    Ok(())
+}
+
+fn check_finite(value: f32) -> Result<f32, SimilarityError> {
+    if value.is_nan() {
+        return Err(SimilarityError::InvalidValue("NaN".to_string()));
+    }
+    if value.is_infinite() {
+        return Err(SimilarityError::InvalidValue("Infinite".to_string()));
+    }
+    Ok(value)
+}
+
 /// Calculates the cosine similarity between two vectors
 ///
 /// Cosine similarity measures the cosine of the angle between two vectors,
@@ -54,32 +221,48 @@ pub enum SimilarityError {
 /// assert!((similarity - 1.0).abs() < 0.0001);
 /// ```
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
-    // Check dimensions match
-    if a.len() != b.len() {
-        return Err(SimilarityError::DimensionMismatch {
-            expected: a.len(),
-            actual: b.len(),
-        });
-    }
+    cosine_with(a, b, dot_and_norms)
+}
 
-    // Check for empty vectors
-    if a.is_empty() {
-        return Err(SimilarityError::DimensionMismatch {
-            expected: 0,
-            actual: 0,
-        });
+/// Calculates the cosine similarity using only the scalar loop
+///
+/// This is the reference for the accelerated path of [`cosine_similarity`]
+/// and returns the same errors.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::similarity::cosine_similarity_scalar;
+///
+/// let similarity = cosine_similarity_scalar(&[1.0, 0.0], &[0.0, 1.0]).unwrap();
+/// assert!(similarity.abs() < 0.0001);
+/// ```
+pub fn cosine_similarity_scalar(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
+    cosine_with(a, b, scalar_dot_and_norms)
+}
+
+/// Returns true if [`cosine_similarity`] uses SIMD instructions
+///
+/// This requires the `simd` feature and an x86_64 CPU with AVX and FMA.
+pub fn simd_enabled() -> bool {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        simd::is_available()
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        false
     }
+}
 
-    // Calculate dot product and magnitudes
-    let mut dot_product = 0.0;
-    let mut magnitude_a = 0.0;
-    let mut magnitude_b = 0.0;
+/// Computes the dot product and both squared magnitudes of two slices
+type Kernel = fn(&[f32], &[f32]) -> (f32, f32, f32);
 
-    for i in 0..a.len() {
-        dot_product += a[i] * b[i];
-        magnitude_a += a[i] * a[i];
-        magnitude_b += b[i] * b[i];
-    }
+fn cosine_with(a: &[f32], b: &[f32], kernel: Kernel) -> Result<f32, SimilarityError> {
+    check_dimensions(a, b)?;
+
+    // Calculate dot product and magnitudes
+    let (dot_product, magnitude_a, magnitude_b) = kernel(a, b);
 
     // Check for zero vectors
     if magnitude_a == 0.0 || magnitude_b == 0.0 {
@@ -87,17 +270,83 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
     }
 
     // Calculate cosine similarity
-    let similarity = dot_product / (magnitude_a.sqrt() * magnitude_b.sqrt());
+    check_finite(dot_product / (magnitude_a.sqrt() * magnitude_b.sqrt()))
+}
 
-    // Check for invalid values
-    if similarity.is_nan() {
-        return Err(SimilarityError::InvalidValue("NaN".to_string()));
+/// Dot product and squared magnitudes, using SIMD when available
+fn dot_and_norms(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if simd::is_available() {
+        // SAFETY: AVX and FMA support was detected at runtime
+        return unsafe { simd::dot_and_norms(a, b) };
     }
-    if similarity.is_infinite() {
-        return Err(SimilarityError::InvalidValue("Infinite".to_string()));
+    scalar_dot_and_norms(a, b)
+}
+
+/// Dot product and squared magnitudes of equal-length slices
+fn scalar_dot_and_norms(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    let mut dot_product = 0.0;
+    let mut magnitude_a = 0.0;
+    let mut magnitude_b = 0.0;
+
+    for (x, y) in a.iter().zip(b) {
+        dot_product += x * y;
+        magnitude_a += x * x;
+        magnitude_b += y * y;
     }
 
-    Ok(similarity)
+    (dot_product, magnitude_a, magnitude_b)
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd {
+    use std::arch::x86_64::{
+        __m256, _mm256_fmadd_ps, _mm256_loadu_ps, _mm256_setzero_ps, _mm256_storeu_ps,
+    };
+
+    /// Floats per AVX register
+    const LANES: usize = 8;
+
+    pub(super) fn is_available() -> bool {
+        std::arch::is_x86_feature_detected!("avx") && std::arch::is_x86_feature_detected!("fma")
+    }
+
+    /// AVX/FMA version of `scalar_dot_and_norms`
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX and FMA, and `a` and `b` must have the same
+    /// length.
+    #[target_feature(enable = "avx,fma")]
+    pub(super) unsafe fn dot_and_norms(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+        let mut dot_product = _mm256_setzero_ps();
+        let mut magnitude_a = _mm256_setzero_ps();
+        let mut magnitude_b = _mm256_setzero_ps();
+
+        let chunks = a.len() / LANES;
+        for i in 0..chunks {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i * LANES));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i * LANES));
+            dot_product = _mm256_fmadd_ps(x, y, dot_product);
+            magnitude_a = _mm256_fmadd_ps(x, x, magnitude_a);
+            magnitude_b = _mm256_fmadd_ps(y, y, magnitude_b);
+        }
+
+        let tail = chunks * LANES;
+        let (tail_dot, tail_a, tail_b) = super::scalar_dot_and_norms(&a[tail..], &b[tail..]);
+        (
+            horizontal_sum(dot_product) + tail_dot,
+            horizontal_sum(magnitude_a) + tail_a,
+            horizontal_sum(magnitude_b) + tail_b,
+        )
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn horizontal_sum(v: __m256) -> f32 {
+        let mut lanes = [0.0f32; LANES];
+        _mm256_storeu_ps(lanes.as_mut_ptr(), v);
+        lanes.iter().sum()
+    }
 }
 
 /// Calculates pairwise similarities between consecutive embeddings
@@ -132,18 +381,23 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Result<f32, SimilarityError> {
 /// assert_eq!(similarities.len(), 2);
 /// ```
 pub fn pairwise_similarities(embeddings: &[Vec<f32>]) -> Result<Vec<f32>, SimilarityError> {
-    if embeddings.len() < 2 {
-        return Ok(Vec::new());
-    }
-
-    let mut similarities = Vec::with_capacity(embeddings.len() - 1);
-
-    for i in 0..embeddings.len() - 1 {
-        let sim = cosine_similarity(&embeddings[i], &embeddings[i + 1])?;
-        similarities.push(sim);
-    }
+    pairwise_similarities_with(embeddings, SimilarityMetric::Cosine)
+}
 
-    Ok(similarities)
+/// Calculates pairwise similarities between consecutive embeddings with
+/// the given metric
+///
+/// # Errors
+///
+/// Returns `SimilarityError` if any pairwise similarity calculation fails
+pub fn pairwise_similarities_with(
+    embeddings: &[Vec<f32>],
+    metric: SimilarityMetric,
+) -> Result<Vec<f32>, SimilarityError> {
+    embeddings
+        .windows(2)
+        .map(|pair| metric.similarity(&pair[0], &pair[1]))
+        .collect()
 }
 
 /// Calculates a percentile value from a sorted or unsorted collection
@@ -296,6 +550,29 @@ mod tests {
         assert!((similarities[1] - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_cosine_similarity_matches_scalar() {
+        // Lengths around the SIMD width exercise the remainder loop
+        for len in [1, 7, 8, 9, 31, 384, 1001] {
+            let a: Vec<f32> = (0..len)
+                .map(|i| ((i * 7 % 13) as f32 - 6.0) / 3.0)
+                .collect();
+            let b: Vec<f32> = (0..len)
+                .map(|i| ((i * 5 % 11) as f32 - 4.5) / 2.0)
+                .collect();
+
+            let fast = cosine_similarity(&a, &b).unwrap();
+            let scalar = cosine_similarity_scalar(&a, &b).unwrap();
+            assert!(
+                (fast - scalar).abs() < 1e-5,
+                "len {}: {} vs {}",
+                len,
+                fast,
+                scalar
+            );
+        }
+    }
+
     #[test]
     fn test_calculate_percentile_median() {
         let values = vec![1.0, 2.0, 3.0, 4.0, 5.0];
@@ -351,4 +628,118 @@ mod tests {
         let values = vec![1.0, 2.0, 3.0];
         calculate_percentile(&values, 1.5);
     }
+
+    fn rank(metric: SimilarityMetric, query: &[f32], candidates: &[Vec<f32>]) -> Vec<usize> {
+        let mut scored: Vec<(usize, f32)> = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, metric.similarity(query, c).unwrap()))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored.into_iter().map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn test_metrics_rank_known_vectors() {
+        let query = [1.0, 0.0];
+        // Same direction but long, nearby at an angle, and far at an angle
+        let candidates = vec![vec![10.0, 0.0], vec![0.9, 0.3], vec![3.0, 3.0]];
+
+        assert_eq!(
+            rank(SimilarityMetric::Cosine, &query, &candidates),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            rank(SimilarityMetric::DotProduct, &query, &candidates),
+            vec![0, 2, 1]
+        );
+        assert_eq!(
+            rank(SimilarityMetric::Euclidean, &query, &candidates),
+            vec![1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_metrics_agree_on_normalized_vectors() {
+        let query = [0.6, 0.8];
+        let candidates = vec![vec![1.0, 0.0], vec![0.8, 0.6], vec![0.0, 1.0]];
+
+        let expected = rank(SimilarityMetric::Cosine, &query, &candidates);
+        for metric in SimilarityMetric::ALL {
+            assert_eq!(rank(metric, &query, &candidates), expected, "{}", metric);
+        }
+    }
+
+    #[test]
+    fn test_metric_scores() {
+        let a = [1.0, 2.0, 2.0];
+        let b = [1.0, 2.0, 2.0];
+        assert!((SimilarityMetric::Cosine.similarity(&a, &b).unwrap() - 1.0).abs() < 1e-6);
+        assert_eq!(
+            SimilarityMetric::DotProduct.similarity(&a, &b).unwrap(),
+            9.0
+        );
+        assert_eq!(SimilarityMetric::Euclidean.similarity(&a, &b).unwrap(), 1.0);
+
+        // Zero vectors only fail under cosine
+        let zero = [0.0, 0.0, 0.0];
+        assert!(SimilarityMetric::Cosine.similarity(&a, &zero).is_err());
+        assert_eq!(
+            SimilarityMetric::DotProduct.similarity(&a, &zero).unwrap(),
+            0.0
+        );
+        assert_eq!(
+            SimilarityMetric::Euclidean.similarity(&a, &zero).unwrap(),
+            0.25
+        );
+
+        for metric in SimilarityMetric::ALL {
+            assert!(matches!(
+                metric.similarity(&a, &[1.0]),
+                Err(SimilarityError::DimensionMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_metric_parse_and_display() {
+        for metric in SimilarityMetric::ALL {
+            assert_eq!(
+                metric.to_string().parse::<SimilarityMetric>().unwrap(),
+                metric
+            );
+        }
+        assert_eq!(
+            "Dot-Product".parse::<SimilarityMetric>().unwrap(),
+            SimilarityMetric::DotProduct
+        );
+        assert_eq!(
+            "l2".parse::<SimilarityMetric>().unwrap(),
+            SimilarityMetric::Euclidean
+        );
+        assert!("manhattan".parse::<SimilarityMetric>().is_err());
+        assert_eq!(SimilarityMetric::default(), SimilarityMetric::Cosine);
+        assert_eq!(
+            serde_json::to_string(&SimilarityMetric::DotProduct).unwrap(),
+            "\"dot_product\""
+        );
+    }
+
+    #[test]
+    fn test_pairwise_similarities_with_metric() {
+        let embeddings = vec![vec![1.0, 0.0], vec![2.0, 0.0], vec![2.0, 1.0]];
+        assert_eq!(
+            pairwise_similarities_with(&embeddings, SimilarityMetric::DotProduct).unwrap(),
+            vec![2.0, 4.0]
+        );
+        assert_eq!(
+            pairwise_similarities_with(&embeddings, SimilarityMetric::Euclidean).unwrap(),
+            vec![0.5, 0.5]
+        );
+        assert!(
+            pairwise_similarities_with(&embeddings[..1], SimilarityMetric::Euclidean)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
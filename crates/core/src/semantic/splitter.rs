@@ -3,9 +3,12 @@
 //! This module provides tools for splitting text into sentences while preserving
 //! code blocks and handling common abbreviations correctly.
 
+use super::chunker::ChunkingError;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Common abbreviations that should not trigger sentence boundaries
 static ABBREVIATIONS: Lazy<Vec<&'static str>> = Lazy::new(|| {
@@ -247,6 +250,51 @@ impl Default for SentenceSplitter {
     }
 }
 
+/// Which splitter turns a document into sentences for semantic chunking
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::splitter::SplitterKind;
+///
+/// assert_eq!(SplitterKind::default(), SplitterKind::Sentence);
+/// assert_eq!("markdown".parse::<SplitterKind>().unwrap(), SplitterKind::Markdown);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitterKind {
+    /// [`SentenceSplitter`] over the whole document (default)
+    #[default]
+    Sentence,
+    /// [`MarkdownSplitter`](super::markdown::MarkdownSplitter), chunking each
+    /// heading section on its own
+    Markdown,
+}
+
+impl fmt::Display for SplitterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitterKind::Sentence => write!(f, "sentence"),
+            SplitterKind::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl std::str::FromStr for SplitterKind {
+    type Err = ChunkingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sentence" => Ok(SplitterKind::Sentence),
+            "markdown" | "md" => Ok(SplitterKind::Markdown),
+            other => Err(ChunkingError::InvalidConfiguration(format!(
+                "Unknown splitter: {}",
+                other
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
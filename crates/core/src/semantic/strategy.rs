@@ -0,0 +1,476 @@
+//! Selectable chunking strategies
+//!
+//! Different corpora and embedding models favour different chunk shapes, so
+//! the strategy is configurable per load. Every strategy produces
+//! [`SemanticChunk`]s whose [`ChunkMetadata`] is filled in the same way: the
+//! source file, the nearest heading as the title, and word and character
+//! counts of the chunk content.
+//!
+//! # Choosing a strategy
+//!
+//! | Strategy | Suits | Tradeoff |
+//! |----------|-------|----------|
+//! | [`ChunkStrategy::Sentence`] | Prose, mixed or unknown content | Balanced default |
+//! | [`ChunkStrategy::FixedToken`] | Embedding models with a fixed context window, logs, transcripts | Predictable size; may cut mid-sentence |
+//! | [`ChunkStrategy::Semantic`] | Long narrative documents that drift between topics | Best precision; needs an embedding model |
+//! | [`ChunkStrategy::Markdown`] | Structured Markdown such as reference docs and how-to guides | Whole sections give high recall; large sections dilute precision |
+
+use super::chunker::ChunkingError;
+use super::splitter::SentenceSplitter;
+use super::types::{ChunkMetadata, SemanticChunk};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Sentences grouped into one chunk by the sentence strategy
+pub const SENTENCES_PER_CHUNK: usize = 5;
+
+/// Default window size, in tokens, for the fixed-token strategy
+pub const DEFAULT_TOKEN_WINDOW: usize = 256;
+
+/// Default overlap, in tokens, between fixed-token windows
+pub const DEFAULT_TOKEN_OVERLAP: usize = 32;
+
+/// Minimum sentence length passed to the sentence splitter
+const MIN_SENTENCE_LENGTH: usize = 10;
+
+/// How documents are split into chunks
+///
+/// `start_sentence` and `end_sentence` on the produced chunks index the
+/// strategy's own units: sentences for `Sentence` and `Semantic`, tokens for
+/// `FixedToken` and sections for `Markdown`. Only `Semantic` measures
+/// `avg_similarity`; the other strategies report `0.0`.
+///
+/// # Examples
+///
+/// ```
+/// use xze_core::semantic::strategy::ChunkStrategy;
+///
+/// assert_eq!(ChunkStrategy::default(), ChunkStrategy::Sentence);
+/// assert_eq!(
+///     "fixed-token:128:16".parse::<ChunkStrategy>().unwrap(),
+///     ChunkStrategy::FixedToken { size: 128, overlap: 16 }
+/// );
+///
+/// let chunks = ChunkStrategy::Markdown
+///     .chunk("# Intro\n\nHello there.\n\n## Usage\n\nRun it.", "docs/guide.md")
+///     .unwrap();
+/// assert_eq!(chunks.len(), 2);
+/// assert_eq!(chunks[1].metadata.title.as_deref(), Some("Usage"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// Groups consecutive sentences (default)
+    #[default]
+    Sentence,
+    /// Sliding windows of whitespace-separated tokens
+    FixedToken {
+        /// Tokens per chunk
+        size: usize,
+        /// Tokens shared with the previous chunk
+        overlap: usize,
+    },
+    /// Splits where similarity between sentence embeddings drops
+    ///
+    /// Requires an embedding model; see [`SemanticChunker`](super::SemanticChunker).
+    Semantic,
+    /// One chunk per Markdown heading section
+    Markdown,
+}
+
+impl ChunkStrategy {
+    /// Validate strategy parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChunkingError::InvalidConfiguration` if a fixed-token window
+    /// is empty or its overlap is not smaller than the window
+    pub fn validate(&self) -> Result<(), ChunkingError> {
+        if let ChunkStrategy::FixedToken { size, overlap } = *self {
+            if size == 0 {
+                return Err(ChunkingError::InvalidConfiguration(
+                    "fixed-token size must be greater than 0".to_string(),
+                ));
+            }
+            if overlap >= size {
+                return Err(ChunkingError::InvalidConfiguration(format!(
+                    "fixed-token overlap ({}) must be smaller than size ({})",
+                    overlap, size
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the strategy needs sentence embeddings to chunk
+    pub fn requires_embeddings(&self) -> bool {
+        matches!(self, ChunkStrategy::Semantic)
+    }
+
+    /// Split `text` into chunks without embeddings
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Document content
+    /// * `source_file` - Path recorded in each chunk's metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChunkingError::InvalidConfiguration` for invalid parameters
+    /// or for `Semantic`, which must go through
+    /// [`SemanticChunker::chunk_with_strategy`](super::SemanticChunker::chunk_with_strategy),
+    /// and `ChunkingError::EmptyDocument` if the text has no content
+    pub fn chunk(
+        &self,
+        text: &str,
+        source_file: &str,
+    ) -> Result<Vec<SemanticChunk>, ChunkingError> {
+        self.validate()?;
+        if text.trim().is_empty() {
+            return Err(ChunkingError::EmptyDocument);
+        }
+
+        let pieces = match *self {
+            ChunkStrategy::Sentence => sentence_pieces(text),
+            ChunkStrategy::FixedToken { size, overlap } => token_pieces(text, size, overlap),
+            ChunkStrategy::Markdown => markdown_pieces(text),
+            ChunkStrategy::Semantic => {
+                return Err(ChunkingError::InvalidConfiguration(
+                    "semantic chunking requires an embedding model".to_string(),
+                ))
+            }
+        };
+
+        if pieces.is_empty() {
+            return Err(ChunkingError::EmptyDocument);
+        }
+        Ok(build_chunks(pieces, text, source_file))
+    }
+}
+
+impl fmt::Display for ChunkStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkStrategy::Sentence => write!(f, "sentence"),
+            ChunkStrategy::FixedToken { size, overlap } => {
+                write!(f, "fixed-token:{}:{}", size, overlap)
+            }
+            ChunkStrategy::Semantic => write!(f, "semantic"),
+            ChunkStrategy::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl std::str::FromStr for ChunkStrategy {
+    type Err = ChunkingError;
+
+    /// Parse `sentence`, `semantic`, `markdown`, `fixed-token`, or
+    /// `fixed-token:SIZE[:OVERLAP]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+        let name = parts
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+            .replace('_', "-");
+        let mut number = |default: usize| -> Result<usize, ChunkingError> {
+            match parts.next() {
+                Some(n) => n.trim().parse().map_err(|_| {
+                    ChunkingError::InvalidConfiguration(format!("Invalid token count: {}", n))
+                }),
+                None => Ok(default),
+            }
+        };
+
+        let strategy = match name.as_str() {
+            "sentence" => ChunkStrategy::Sentence,
+            "semantic" => ChunkStrategy::Semantic,
+            "markdown" => ChunkStrategy::Markdown,
+            "fixed-token" => {
+                let size = number(DEFAULT_TOKEN_WINDOW)?;
+                let overlap = number(DEFAULT_TOKEN_OVERLAP.min(size / 2))?;
+                ChunkStrategy::FixedToken { size, overlap }
+            }
+            other => {
+                return Err(ChunkingError::InvalidConfiguration(format!(
+                    "Unknown chunk strategy: {}",
+                    other
+                )))
+            }
+        };
+        strategy.validate()?;
+        Ok(strategy)
+    }
+}
+
+/// Chunk content with its unit range and the heading it falls under
+struct Piece {
+    content: String,
+    start: usize,
+    end: usize,
+    heading: Option<String>,
+}
+
+fn sentence_pieces(text: &str) -> Vec<Piece> {
+    let sentences = SentenceSplitter::new(MIN_SENTENCE_LENGTH).split(text);
+    sentences
+        .chunks(SENTENCES_PER_CHUNK)
+        .enumerate()
+        .map(|(i, group)| {
+            let start = i * SENTENCES_PER_CHUNK;
+            Piece {
+                content: group.join(" "),
+                start,
+                end: start + group.len() - 1,
+                heading: None,
+            }
+        })
+        .collect()
+}
+
+fn token_pieces(text: &str, size: usize, overlap: usize) -> Vec<Piece> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let step = size - overlap;
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    while start < tokens.len() {
+        let end = (start + size).min(tokens.len());
+        pieces.push(Piece {
+            content: tokens[start..end].join(" "),
+            start,
+            end: end - 1,
+            heading: None,
+        });
+        if end == tokens.len() {
+            break;
+        }
+        start += step;
+    }
+    pieces
+}
+
+fn markdown_pieces(text: &str) -> Vec<Piece> {
+    let mut sections: Vec<(Option<String>, Vec<&str>)> = vec![(None, Vec::new())];
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(heading) = heading_text(trimmed) {
+                sections.push((Some(heading), Vec::new()));
+            }
+        }
+        if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(heading, lines)| (heading, lines.join("\n").trim().to_string()))
+        .filter(|(_, content)| !content.is_empty())
+        .enumerate()
+        .map(|(i, (heading, content))| Piece {
+            content,
+            start: i,
+            end: i,
+            heading,
+        })
+        .collect()
+}
+
+/// Text of an ATX heading line, or `None` if the line is not a heading
+pub(crate) fn heading_text(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim().to_string())
+}
+
+/// First Markdown heading outside fenced code, used as the document title
+pub(crate) fn document_title(text: &str) -> Option<String> {
+    let mut in_fence = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(heading) = heading_text(trimmed) {
+                return Some(heading);
+            }
+        }
+    }
+    None
+}
+
+fn build_chunks(pieces: Vec<Piece>, text: &str, source_file: &str) -> Vec<SemanticChunk> {
+    let document_title = document_title(text);
+    let total = pieces.len();
+
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(index, piece)| {
+            let title = piece.heading.or_else(|| document_title.clone());
+            let metadata = ChunkMetadata::with_details(
+                source_file.to_string(),
+                title,
+                None,
+                Vec::new(),
+                &piece.content,
+            );
+            SemanticChunk::new(
+                piece.content,
+                index,
+                total,
+                piece.start,
+                piece.end,
+                0.0,
+                metadata,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: &str = "Preamble text before any heading.\n\n\
+        # Guide\n\nIntro paragraph.\n\n\
+        ## Install\n\nRun the installer.\n\n```sh\n# not a heading\nmake install\n```\n\n\
+        ## Usage\n\nCall the binary.";
+
+    #[test]
+    fn test_default_and_parse() {
+        assert_eq!(ChunkStrategy::default(), ChunkStrategy::Sentence);
+        assert_eq!(
+            "fixed-token".parse::<ChunkStrategy>().unwrap(),
+            ChunkStrategy::FixedToken {
+                size: DEFAULT_TOKEN_WINDOW,
+                overlap: DEFAULT_TOKEN_OVERLAP
+            }
+        );
+        for strategy in [
+            ChunkStrategy::Sentence,
+            ChunkStrategy::Semantic,
+            ChunkStrategy::Markdown,
+            ChunkStrategy::FixedToken {
+                size: 64,
+                overlap: 8,
+            },
+        ] {
+            assert_eq!(
+                strategy.to_string().parse::<ChunkStrategy>().unwrap(),
+                strategy
+            );
+        }
+        assert!("fixed-token:8:8".parse::<ChunkStrategy>().is_err());
+        assert!("paragraph".parse::<ChunkStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_serde_representation() {
+        let json = serde_json::to_value(ChunkStrategy::FixedToken {
+            size: 10,
+            overlap: 2,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "fixed_token", "size": 10, "overlap": 2 })
+        );
+        let parsed: ChunkStrategy =
+            serde_json::from_value(serde_json::json!({ "type": "markdown" })).unwrap();
+        assert_eq!(parsed, ChunkStrategy::Markdown);
+    }
+
+    #[test]
+    fn test_sentence_strategy_groups_sentences() {
+        let text = (1..=7)
+            .map(|i| format!("This is sentence number {}.", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let chunks = ChunkStrategy::Sentence.chunk(&text, "a.md").unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].sentence_count(), SENTENCES_PER_CHUNK);
+        assert_eq!((chunks[1].start_sentence, chunks[1].end_sentence), (5, 6));
+        assert!(chunks.iter().all(|c| c.total_chunks == 2));
+    }
+
+    #[test]
+    fn test_fixed_token_windows_overlap() {
+        let text = (0..10)
+            .map(|i| format!("t{}", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let chunks = ChunkStrategy::FixedToken {
+            size: 4,
+            overlap: 1,
+        }
+        .chunk(&text, "a.txt")
+        .unwrap();
+
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["t0 t1 t2 t3", "t3 t4 t5 t6", "t6 t7 t8 t9"]);
+        assert!(chunks.iter().all(|c| c.metadata.word_count <= 4));
+    }
+
+    #[test]
+    fn test_markdown_keeps_sections_together() {
+        let chunks = ChunkStrategy::Markdown.chunk(MARKDOWN, "guide.md").unwrap();
+
+        let titles: Vec<Option<&str>> =
+            chunks.iter().map(|c| c.metadata.title.as_deref()).collect();
+        assert_eq!(
+            titles,
+            vec![Some("Guide"), Some("Guide"), Some("Install"), Some("Usage")]
+        );
+        assert!(chunks[2].content.contains("make install"));
+        assert!(chunks[2].content.starts_with("## Install"));
+    }
+
+    #[test]
+    fn test_metadata_is_consistent_across_strategies() {
+        for strategy in [
+            ChunkStrategy::Sentence,
+            ChunkStrategy::Markdown,
+            ChunkStrategy::FixedToken {
+                size: 5,
+                overlap: 0,
+            },
+        ] {
+            for chunk in strategy.chunk(MARKDOWN, "guide.md").unwrap() {
+                assert_eq!(chunk.metadata.source_file, "guide.md");
+                assert_eq!(
+                    chunk.metadata.word_count,
+                    chunk.content.split_whitespace().count()
+                );
+                assert_eq!(chunk.metadata.char_count, chunk.content.chars().count());
+                assert!(chunk.metadata.title.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_semantic_and_empty_are_rejected() {
+        assert!(ChunkStrategy::Semantic.requires_embeddings());
+        assert!(matches!(
+            ChunkStrategy::Semantic.chunk("Some text here.", "a.md"),
+            Err(ChunkingError::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            ChunkStrategy::Sentence.chunk("   ", "a.md"),
+            Err(ChunkingError::EmptyDocument)
+        ));
+    }
+}
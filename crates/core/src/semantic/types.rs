@@ -3,6 +3,7 @@
 //! This module defines the fundamental data structures used in semantic chunking,
 //! including chunks, metadata, and associated utility functions.
 
+use super::similarity::SimilarityMetric;
 use serde::{Deserialize, Serialize};
 
 /// Represents a semantically coherent chunk of text
@@ -23,6 +24,8 @@ use serde::{Deserialize, Serialize};
 ///     keywords: vec!["setup".to_string(), "installation".to_string()],
 ///     word_count: 150,
 ///     char_count: 890,
+///     overlap_sentences: 0,
+///     breadcrumbs: Vec::new(),
 /// };
 ///
 /// let chunk = SemanticChunk::new(
@@ -64,6 +67,10 @@ pub struct SemanticChunk {
     /// Embedding vector for semantic similarity search
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub embedding: Vec<f32>,
+
+    /// Metric the embedding is meant to be compared with
+    #[serde(default)]
+    pub similarity_metric: SimilarityMetric,
 }
 
 /// Metadata associated with a semantic chunk
@@ -83,6 +90,8 @@ pub struct SemanticChunk {
 ///     keywords: vec!["entry".to_string(), "initialization".to_string()],
 ///     word_count: 85,
 ///     char_count: 512,
+///     overlap_sentences: 0,
+///     breadcrumbs: Vec::new(),
 /// };
 ///
 /// assert_eq!(metadata.word_count, 85);
@@ -107,6 +116,18 @@ pub struct ChunkMetadata {
 
     /// Number of characters in the chunk
     pub char_count: usize,
+
+    /// Number of leading sentences repeated from the previous chunk
+    ///
+    /// Readers combining consecutive chunks should skip this many sentences
+    /// to avoid duplicated text.
+    #[serde(default)]
+    pub overlap_sentences: usize,
+
+    /// Headings above the chunk, outermost first, when split by Markdown
+    /// structure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breadcrumbs: Vec<String>,
 }
 
 impl SemanticChunk {
@@ -134,6 +155,8 @@ impl SemanticChunk {
     ///     keywords: vec![],
     ///     word_count: 10,
     ///     char_count: 50,
+    ///     overlap_sentences: 0,
+    ///     breadcrumbs: Vec::new(),
     /// };
     ///
     /// let chunk = SemanticChunk::new(
@@ -166,6 +189,7 @@ impl SemanticChunk {
             avg_similarity,
             metadata,
             embedding: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
         }
     }
 
@@ -187,6 +211,8 @@ impl SemanticChunk {
     ///     keywords: vec![],
     ///     word_count: 10,
     ///     char_count: 50,
+    ///     overlap_sentences: 0,
+    ///     breadcrumbs: Vec::new(),
     /// };
     ///
     /// let mut chunk = SemanticChunk::new(
@@ -222,6 +248,8 @@ impl SemanticChunk {
     ///     keywords: vec![],
     ///     word_count: 10,
     ///     char_count: 50,
+    ///     overlap_sentences: 0,
+    ///     breadcrumbs: Vec::new(),
     /// };
     ///
     /// let chunk = SemanticChunk::new(
@@ -274,6 +302,8 @@ impl ChunkMetadata {
             keywords: Vec::new(),
             word_count,
             char_count,
+            overlap_sentences: 0,
+            breadcrumbs: Vec::new(),
         }
     }
 
@@ -320,6 +350,29 @@ impl ChunkMetadata {
             keywords,
             word_count,
             char_count,
+            overlap_sentences: 0,
+            breadcrumbs: Vec::new(),
+        }
+    }
+
+    /// Breadcrumbs joined as `H1 > H2`, or `None` without breadcrumbs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::semantic::types::ChunkMetadata;
+    ///
+    /// let mut metadata = ChunkMetadata::new("docs/guide.md".to_string(), "Run it.");
+    /// assert_eq!(metadata.breadcrumb(), None);
+    ///
+    /// metadata.breadcrumbs = vec!["Guide".to_string(), "Install".to_string()];
+    /// assert_eq!(metadata.breadcrumb().as_deref(), Some("Guide > Install"));
+    /// ```
+    pub fn breadcrumb(&self) -> Option<String> {
+        if self.breadcrumbs.is_empty() {
+            None
+        } else {
+            Some(self.breadcrumbs.join(" > "))
         }
     }
 }
@@ -0,0 +1,93 @@
+//! OpenTelemetry trace export
+//!
+//! Exports `tracing` spans over OTLP/HTTP so AI generation, git operations
+//! and HTTP handlers can be correlated with the services around them. The
+//! collector is taken from `OTEL_EXPORTER_OTLP_ENDPOINT`, with the other
+//! standard `OTEL_EXPORTER_OTLP_*` variables applied by the exporter.
+//!
+//! Spans are exported in batches; call [`shutdown_tracing`] before exiting
+//! so the last batch is not lost.
+
+use crate::{Result, XzeError};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, OTEL_EXPORTER_OTLP_ENDPOINT};
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use std::sync::OnceLock;
+use tracing_subscriber::{registry::LookupSpan, Layer};
+
+/// Provider installed by [`otlp_layer`], kept to flush it on shutdown
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Collector endpoint from `OTEL_EXPORTER_OTLP_ENDPOINT`
+///
+/// # Errors
+///
+/// Returns a validation error if the variable is unset or empty
+pub fn otlp_endpoint() -> Result<String> {
+    std::env::var(OTEL_EXPORTER_OTLP_ENDPOINT)
+        .ok()
+        .filter(|endpoint| !endpoint.trim().is_empty())
+        .ok_or_else(|| {
+            XzeError::validation(format!(
+                "OTLP log format requires {} to be set",
+                OTEL_EXPORTER_OTLP_ENDPOINT
+            ))
+        })
+}
+
+/// Build a layer exporting spans to the configured OTLP collector
+///
+/// The tracer provider is also installed as the global OpenTelemetry
+/// provider.
+///
+/// # Errors
+///
+/// Returns a validation error if no endpoint is configured or the
+/// exporter cannot be built
+pub fn otlp_layer<S>() -> Result<impl Layer<S>>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    otlp_endpoint()?;
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .build()
+        .map_err(|e| XzeError::validation(format!("Failed to build OTLP exporter: {}", e)))?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(crate::NAME).build())
+        .build();
+
+    let tracer = provider.tracer(crate::NAME);
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let _ = TRACER_PROVIDER.set(provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Export buffered spans and stop the OTLP exporter
+///
+/// Does nothing unless OTLP export was initialized.
+pub fn shutdown_tracing() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to shut down OTLP exporter: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_otlp_requires_endpoint() {
+        if std::env::var_os(OTEL_EXPORTER_OTLP_ENDPOINT).is_some() {
+            return;
+        }
+        let err = otlp_endpoint().unwrap_err();
+        assert!(matches!(err, XzeError::Validation { .. }));
+        assert!(err.to_string().contains(OTEL_EXPORTER_OTLP_ENDPOINT));
+    }
+}
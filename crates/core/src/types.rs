@@ -15,6 +15,7 @@ pub enum ProgrammingLanguage {
     TypeScript,
     Java,
     CSharp,
+    Ruby,
     Cpp,
     C,
     Shell,
@@ -36,6 +37,7 @@ impl From<&str> for ProgrammingLanguage {
             "typescript" | "ts" => Self::TypeScript,
             "java" => Self::Java,
             "csharp" | "cs" => Self::CSharp,
+            "ruby" | "rb" => Self::Ruby,
             "cpp" | "c++" => Self::Cpp,
             "c" => Self::C,
             "shell" | "bash" | "sh" => Self::Shell,
@@ -59,6 +61,7 @@ impl std::fmt::Display for ProgrammingLanguage {
             Self::TypeScript => write!(f, "TypeScript"),
             Self::Java => write!(f, "Java"),
             Self::CSharp => write!(f, "C#"),
+            Self::Ruby => write!(f, "Ruby"),
             Self::Cpp => write!(f, "C++"),
             Self::C => write!(f, "C"),
             Self::Shell => write!(f, "Shell"),
@@ -96,6 +99,62 @@ impl std::fmt::Display for DiátaxisCategory {
     }
 }
 
+impl DiátaxisCategory {
+    /// All categories in generation order
+    pub const ALL: [DiátaxisCategory; 4] = [
+        Self::Reference,
+        Self::HowTo,
+        Self::Tutorial,
+        Self::Explanation,
+    ];
+
+    /// Parse a list of category names, rejecting unknown names
+    ///
+    /// Duplicates are dropped while preserving the order of first
+    /// appearance. An empty list selects every category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_core::types::DiátaxisCategory;
+    ///
+    /// let categories = DiátaxisCategory::parse_list(&["reference", "how-to"]).unwrap();
+    /// assert_eq!(categories, vec![DiátaxisCategory::Reference, DiátaxisCategory::HowTo]);
+    /// assert!(DiátaxisCategory::parse_list(&["recipes"]).is_err());
+    /// ```
+    pub fn parse_list<S: AsRef<str>>(names: &[S]) -> crate::Result<Vec<Self>> {
+        if names.is_empty() {
+            return Ok(Self::ALL.to_vec());
+        }
+
+        let mut categories = Vec::with_capacity(names.len());
+        for name in names {
+            let category: Self = name.as_ref().parse()?;
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        Ok(categories)
+    }
+}
+
+impl std::str::FromStr for DiátaxisCategory {
+    type Err = crate::error::XzeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tutorial" | "tutorials" => Ok(Self::Tutorial),
+            "how-to" | "howto" | "how_to" | "how-tos" | "howtos" => Ok(Self::HowTo),
+            "reference" => Ok(Self::Reference),
+            "explanation" | "explanations" => Ok(Self::Explanation),
+            other => Err(crate::error::XzeError::validation(format!(
+                "Unknown documentation category '{}': expected one of tutorial, how-to, reference, explanation",
+                other
+            ))),
+        }
+    }
+}
+
 /// Job execution status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JobStatus {
@@ -303,6 +362,7 @@ mod tests {
         assert_eq!(ProgrammingLanguage::from("rust"), ProgrammingLanguage::Rust);
         assert_eq!(ProgrammingLanguage::from("rs"), ProgrammingLanguage::Rust);
         assert_eq!(ProgrammingLanguage::from("go"), ProgrammingLanguage::Go);
+        assert_eq!(ProgrammingLanguage::from("rb"), ProgrammingLanguage::Ruby);
         assert_eq!(
             ProgrammingLanguage::from("python"),
             ProgrammingLanguage::Python
@@ -313,6 +373,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diataxis_category_from_str() {
+        assert_eq!(
+            "Reference".parse::<DiátaxisCategory>().unwrap(),
+            DiátaxisCategory::Reference
+        );
+        assert_eq!(
+            "how-to".parse::<DiátaxisCategory>().unwrap(),
+            DiátaxisCategory::HowTo
+        );
+        assert_eq!(
+            "howto".parse::<DiátaxisCategory>().unwrap(),
+            DiátaxisCategory::HowTo
+        );
+        assert!("cookbook".parse::<DiátaxisCategory>().is_err());
+    }
+
+    #[test]
+    fn test_diataxis_category_parse_list() {
+        let none: [&str; 0] = [];
+        assert_eq!(
+            DiátaxisCategory::parse_list(&none).unwrap(),
+            DiátaxisCategory::ALL.to_vec()
+        );
+        assert_eq!(
+            DiátaxisCategory::parse_list(&["tutorial", "reference", "tutorial"]).unwrap(),
+            vec![DiátaxisCategory::Tutorial, DiátaxisCategory::Reference]
+        );
+        assert!(DiátaxisCategory::parse_list(&["reference", "bogus"]).is_err());
+    }
+
     #[test]
     fn test_job_id_generation() {
         let id1 = JobId::new();
@@ -0,0 +1,57 @@
+using System;
+using System.Collections.Generic;
+
+namespace Acme.Greetings
+{
+    /// <summary>
+    /// Formats greetings for users.
+    /// </summary>
+    public class Greeter : IGreeter
+    {
+        private readonly string _prefix;
+
+        public Greeter(string prefix = "Hello")
+        {
+            _prefix = prefix;
+        }
+
+        /// <summary>Greet a single user.</summary>
+        /// <param name="name">The user to greet.</param>
+        [Obsolete("Use GreetAsync")]
+        public string Greet(string name)
+        {
+            if (string.IsNullOrEmpty(name))
+            {
+                return Format("stranger");
+            }
+            return Format(name);
+        }
+
+        public async Task<string> GreetAsync(string name) => await Task.FromResult(Greet(name));
+
+        private string Format(string name)
+        {
+            var text = "void Fake() { }";
+            return $"{_prefix}, {name}!";
+        }
+    }
+
+    /// <summary>Something that can greet.</summary>
+    public interface IGreeter
+    {
+        string Greet(string name);
+    }
+
+    internal struct Counter
+    {
+        public int Increment(ref int value, int step = 1) => value += step;
+    }
+
+    public enum Tone
+    {
+        Formal,
+        Casual,
+    }
+
+    public record Greeting(string Name, string Text);
+}
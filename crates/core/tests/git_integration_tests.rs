@@ -13,10 +13,13 @@
 //! cargo test --package xze-core --test git_integration_tests -- --skip remote
 //! ```
 
+use chrono::Duration;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
-use xze_core::git::{ChangeType, CredentialStore, GitOperations};
+use xze_core::change_detector::{ChangeDetector, ChangeDetectorConfig};
+use xze_core::config::{SigningConfig, SigningFormat};
+use xze_core::git::{ChangeType, CloneOptions, CredentialStore, GitOperations};
 use xze_core::Result;
 
 /// Helper to create a test repository with initial commit
@@ -64,6 +67,17 @@ fn create_test_files(repo_path: &Path, files: &[(&str, &str)]) -> Result<()> {
     Ok(())
 }
 
+/// Helper to clone `origin` into `path` with a configured test user
+fn clone_test_repo(origin: &Path, path: &Path) -> Result<git2::Repository> {
+    let repo = git2::Repository::clone(origin.to_str().unwrap(), path)?;
+    {
+        let mut config = repo.config()?;
+        config.set_str("user.name", "Clone User")?;
+        config.set_str("user.email", "clone@example.com")?;
+    }
+    Ok(repo)
+}
+
 #[test]
 fn test_init_repository() -> Result<()> {
     let temp_dir = TempDir::new()?;
@@ -156,6 +170,45 @@ fn test_list_branches() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_list_branches_includes_remote_branches() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source_path = temp_dir.path().join("source");
+    let remote_path = temp_dir.path().join("remote.git");
+    let clone_path = temp_dir.path().join("clone");
+
+    create_test_repo(&source_path)?;
+    let remote = git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(source_path.to_str().unwrap(), &remote_path)?;
+    let head_commit = remote.head()?.peel_to_commit()?;
+    remote.branch("remote-only", &head_commit, false)?;
+
+    let repo = clone_test_repo(&remote_path, &clone_path)?;
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let default_branch = git_ops.current_branch(&repo)?;
+
+    let local = git_ops.list_branches(&repo, true)?;
+    assert_eq!(local.len(), 1);
+    assert_eq!(local[0].name, default_branch);
+    assert!(!local[0].is_remote);
+    assert_eq!(
+        local[0].upstream.as_deref(),
+        Some(format!("origin/{}", default_branch).as_str())
+    );
+
+    let all = git_ops.list_branches(&repo, false)?;
+    let names: Vec<_> = all.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec![default_branch.as_str(), "origin/remote-only"]);
+
+    let remote_branch = &all[1];
+    assert!(remote_branch.is_remote);
+    assert!(!remote_branch.is_current);
+    assert_eq!(remote_branch.commit_hash, head_commit.id().to_string());
+
+    Ok(())
+}
+
 #[test]
 fn test_delete_branch() -> Result<()> {
     let temp_dir = TempDir::new()?;
@@ -302,6 +355,531 @@ fn test_diff_analysis_between_commits() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_diff_analysis_per_file_stats() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let initial_commit = git_ops.get_last_commit_hash(&repo)?;
+
+    let large: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+    create_test_files(&repo_path, &[("large.txt", &large), ("small.txt", "one\n")])?;
+    fs::write(repo_path.join("README.md"), "# Test Repository\n")?;
+
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Change files by different amounts")?;
+
+    let diff = git_ops.diff_analysis(&repo, Some(&initial_commit), Some("HEAD"))?;
+    let stats = |path: &str| {
+        let change = diff
+            .changes
+            .iter()
+            .find(|c| c.path == Path::new(path))
+            .unwrap_or_else(|| panic!("no change for {}", path));
+        (change.additions, change.deletions)
+    };
+
+    assert_eq!(stats("large.txt"), (100, 0));
+    assert_eq!(stats("small.txt"), (1, 0));
+    assert_eq!(stats("README.md"), (0, 2));
+    assert_eq!(
+        diff.insertions,
+        diff.changes.iter().map(|c| c.additions).sum::<usize>()
+    );
+    assert_eq!(
+        diff.deletions,
+        diff.changes.iter().map(|c| c.deletions).sum::<usize>()
+    );
+    assert_eq!((diff.insertions, diff.deletions), (101, 2));
+
+    Ok(())
+}
+
+#[test]
+fn test_pull_merges_divergent_history() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let origin_path = temp_dir.path().join("origin");
+    let local_path = temp_dir.path().join("local");
+
+    create_test_repo(&origin_path)?;
+    clone_test_repo(&origin_path, &local_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let origin = git_ops.open(&origin_path)?;
+    let local = git_ops.open(&local_path)?;
+
+    create_test_files(&origin_path, &[("upstream.txt", "From upstream\n")])?;
+    git_ops.stage_all(&origin)?;
+    git_ops.commit(&origin, "Upstream change")?;
+
+    create_test_files(&local_path, &[("local.txt", "From local\n")])?;
+    git_ops.stage_all(&local)?;
+    git_ops.commit(&local, "Local change")?;
+
+    git_ops.pull(&local)?;
+
+    let head = local.head()?.peel_to_commit()?;
+    assert_eq!(head.parent_count(), 2);
+    assert!(local_path.join("upstream.txt").exists());
+    assert!(local_path.join("local.txt").exists());
+    assert_eq!(local.state(), git2::RepositoryState::Clean);
+    assert!(!git_ops.has_changes(&local)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_pull_reports_merge_conflicts() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let origin_path = temp_dir.path().join("origin");
+    let local_path = temp_dir.path().join("local");
+
+    create_test_repo(&origin_path)?;
+    clone_test_repo(&origin_path, &local_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let origin = git_ops.open(&origin_path)?;
+    let local = git_ops.open(&local_path)?;
+
+    fs::write(origin_path.join("README.md"), "# Upstream title\n")?;
+    git_ops.stage_all(&origin)?;
+    git_ops.commit(&origin, "Upstream README")?;
+
+    fs::write(local_path.join("README.md"), "# Local title\n")?;
+    git_ops.stage_all(&local)?;
+    git_ops.commit(&local, "Local README")?;
+
+    let error = git_ops.pull(&local).unwrap_err();
+    assert!(error.to_string().contains("README.md"));
+    assert_eq!(
+        git_ops.get_conflicts(&local)?,
+        vec![Path::new("README.md").to_path_buf()]
+    );
+    assert_eq!(local.state(), git2::RepositoryState::Merge);
+
+    Ok(())
+}
+
+#[test]
+fn test_clone_single_branch() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let origin_path = temp_dir.path().join("origin");
+    let clone_path = temp_dir.path().join("clone");
+
+    create_test_repo(&origin_path)?;
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let origin = git_ops.open(&origin_path)?;
+    git_ops.create_branch(&origin, "docs", false)?;
+    git_ops.create_branch(&origin, "other", false)?;
+
+    let options = CloneOptions {
+        branch: Some("docs".to_string()),
+        single_branch: true,
+        ..CloneOptions::default()
+    };
+    let url = format!("file://{}", origin_path.display());
+    let repo = tokio_test::block_on(git_ops.clone_with_options(&url, &clone_path, &options))?;
+
+    assert_eq!(git_ops.current_branch(&repo)?, "docs");
+    assert!(repo.find_reference("refs/remotes/origin/docs").is_ok());
+    assert!(repo.find_reference("refs/remotes/origin/other").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_clone_rejects_invalid_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let dest = temp_dir.path().join("clone");
+
+    let zero_depth = CloneOptions {
+        depth: Some(0),
+        ..CloneOptions::default()
+    };
+    assert!(tokio_test::block_on(git_ops.clone_with_options(
+        "https://example.com/repo.git",
+        &dest,
+        &zero_depth
+    ))
+    .is_err());
+
+    let no_branch = CloneOptions {
+        single_branch: true,
+        ..CloneOptions::default()
+    };
+    assert!(tokio_test::block_on(git_ops.clone_with_options(
+        "https://example.com/repo.git",
+        &dest,
+        &no_branch
+    ))
+    .is_err());
+    assert!(!dest.exists());
+}
+
+#[test]
+fn test_blame_file_attributes_lines_to_commits() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let initial_commit = git_ops.get_last_commit_hash(&repo)?;
+
+    fs::write(
+        repo_path.join("README.md"),
+        "# Test Repository\n\nUpdated content\n",
+    )?;
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Update README")?;
+    let update_commit = git_ops.get_last_commit_hash(&repo)?;
+
+    let blame = git_ops.blame_file(&repo, Path::new("README.md"))?;
+
+    assert_eq!(blame.len(), 3);
+    assert_eq!(
+        blame.iter().map(|line| line.line).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(blame[0].commit_hash, initial_commit);
+    assert_eq!(blame[1].commit_hash, initial_commit);
+    assert_eq!(blame[2].commit_hash, update_commit);
+    assert!(blame.iter().all(|line| line.author == "Test User"));
+
+    Ok(())
+}
+
+#[test]
+fn test_blame_file_skips_untracked_and_binary_files() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+
+    fs::write(
+        repo_path.join("logo.png"),
+        [0x89, b'P', b'N', b'G', 0, 0, 1, 0],
+    )?;
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Add logo")?;
+    create_test_files(&repo_path, &[("notes.txt", "not committed\n")])?;
+
+    assert!(git_ops.blame_file(&repo, Path::new("logo.png"))?.is_empty());
+    assert!(git_ops
+        .blame_file(&repo, Path::new("notes.txt"))?
+        .is_empty());
+    assert!(git_ops
+        .blame_file(&repo, Path::new("missing.rs"))?
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_documentation_staleness_from_blame() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let detector = ChangeDetector::new(
+        GitOperations::new(CredentialStore::new()),
+        ChangeDetectorConfig::default(),
+    );
+    let sources = vec![PathBuf::from("README.md"), PathBuf::from("untracked.rs")];
+    let changed_at = detector
+        .latest_source_change(&repo_path, Path::new("README.md"))?
+        .expect("README.md has blame");
+
+    assert!(detector.is_documentation_stale(
+        &repo_path,
+        &sources,
+        changed_at - Duration::hours(1)
+    )?);
+    assert!(!detector.is_documentation_stale(&repo_path, &sources, changed_at)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_log_returns_newest_first_and_respects_bounds() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+
+    for i in 1..=3 {
+        create_test_files(&repo_path, &[("file.txt", &format!("version {}\n", i))])?;
+        git_ops.stage_all(&repo)?;
+        git_ops.commit(&repo, &format!("Commit {}", i))?;
+    }
+
+    let history = git_ops.log(&repo, None, None)?;
+    assert_eq!(history.len(), 4);
+    assert_eq!(history[0].hash, git_ops.get_last_commit_hash(&repo)?);
+    assert_eq!(history[0].message, "Commit 3");
+    assert_eq!(history[3].message, "Initial commit");
+    assert_eq!(history[0].author, "Test User");
+
+    let limited = git_ops.log(&repo, Some(2), None)?;
+    assert_eq!(
+        limited
+            .iter()
+            .map(|c| c.message.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Commit 3", "Commit 2"]
+    );
+
+    let future = history[0].timestamp + Duration::hours(1);
+    assert!(git_ops.log(&repo, None, Some(future))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_log_path_filters_to_file_history() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+
+    create_test_files(&repo_path, &[("src/lib.rs", "pub fn a() {}\n")])?;
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Add lib")?;
+
+    create_test_files(&repo_path, &[("other.txt", "unrelated\n")])?;
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Add other file")?;
+
+    create_test_files(&repo_path, &[("src/lib.rs", "pub fn b() {}\n")])?;
+    git_ops.stage_all(&repo)?;
+    git_ops.commit(&repo, "Change lib")?;
+
+    let history = git_ops.log_path(&repo, Path::new("src/lib.rs"), None, None)?;
+    assert_eq!(
+        history
+            .iter()
+            .map(|c| c.message.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Change lib", "Add lib"]
+    );
+
+    let latest = git_ops.log_path(&repo, Path::new("src/lib.rs"), Some(1), None)?;
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].message, "Change lib");
+
+    assert!(git_ops
+        .log_path(&repo, Path::new("missing.rs"), None, None)?
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_commit_signed_with_ssh_key() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+    let key_path = temp_dir.path().join("signing_key");
+
+    let keygen = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key_path)
+        .status();
+    if !keygen.is_ok_and(|status| status.success()) {
+        eprintln!("ssh-keygen unavailable, skipping signed commit test");
+        return Ok(());
+    }
+
+    create_test_repo(&repo_path)?;
+
+    let signing = SigningConfig {
+        format: SigningFormat::Ssh,
+        key: key_path.display().to_string(),
+        program: None,
+    };
+    let git_ops = GitOperations::new(CredentialStore::new()).with_signing(Some(signing));
+    let repo = git_ops.open(&repo_path)?;
+    let parent = git_ops.get_last_commit_hash(&repo)?;
+
+    create_test_files(&repo_path, &[("docs/index.md", "# Docs\n")])?;
+    git_ops.stage_all(&repo)?;
+    let oid = git_ops.commit(&repo, "Signed documentation update")?;
+
+    let (signature, signed_data) = repo.extract_signature(&oid, None)?;
+    let signature = signature.as_str().unwrap_or_default();
+    assert!(signature.starts_with("-----BEGIN SSH SIGNATURE-----"));
+    assert!(signed_data
+        .as_str()
+        .unwrap_or_default()
+        .contains("Signed documentation update"));
+
+    assert_eq!(git_ops.get_last_commit_hash(&repo)?, oid.to_string());
+    let commit = repo.find_commit(oid)?;
+    assert_eq!(commit.parent_id(0)?.to_string(), parent);
+    assert!(git_ops.is_clean(&repo)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_commit_signed_reports_signer_failure() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let head = git_ops.get_last_commit_hash(&repo)?;
+
+    let signing = SigningConfig {
+        format: SigningFormat::Gpg,
+        key: "missing-key".to_string(),
+        program: Some("false".to_string()),
+    };
+    assert!(git_ops
+        .commit_signed(&repo, "Should not be created", &signing)
+        .is_err());
+    assert_eq!(git_ops.get_last_commit_hash(&repo)?, head);
+
+    Ok(())
+}
+
+#[test]
+fn test_cherry_pick_applies_commit() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let main_branch = git_ops.current_branch(&repo)?;
+
+    git_ops.create_branch(&repo, "docs-fix", true)?;
+    create_test_files(&repo_path, &[("docs/guide.md", "# Fixed guide\n")])?;
+    git_ops.stage_all(&repo)?;
+    let fix = git_ops.commit(&repo, "Fix guide typo")?;
+
+    git_ops.checkout_branch(&repo, &main_branch)?;
+    assert!(!repo_path.join("docs/guide.md").exists());
+
+    let oid = git_ops.cherry_pick(&repo, &fix.to_string())?;
+
+    assert_eq!(
+        fs::read_to_string(repo_path.join("docs/guide.md"))?,
+        "# Fixed guide\n"
+    );
+    let commit = repo.find_commit(oid)?;
+    let message = commit.message().unwrap_or_default();
+    assert!(message.starts_with("Fix guide typo"));
+    assert!(message.contains(&format!("cherry picked from commit {}", fix)));
+    assert_eq!(commit.author().name(), Some("Test User"));
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    assert!(git_ops.is_clean(&repo)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cherry_pick_reports_conflicts() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let main_branch = git_ops.current_branch(&repo)?;
+
+    git_ops.create_branch(&repo, "docs-fix", true)?;
+    fs::write(repo_path.join("README.md"), "# Branch version\n")?;
+    git_ops.stage_all(&repo)?;
+    let fix = git_ops.commit(&repo, "Rewrite README on branch")?;
+
+    git_ops.checkout_branch(&repo, &main_branch)?;
+    fs::write(repo_path.join("README.md"), "# Main version\n")?;
+    git_ops.stage_all(&repo)?;
+    let head = git_ops.commit(&repo, "Rewrite README on main")?;
+
+    let err = git_ops
+        .cherry_pick(&repo, &fix.to_string())
+        .expect_err("cherry-pick should conflict");
+
+    assert!(err.to_string().contains("README.md"));
+    assert!(git_ops.has_conflicts(&repo)?);
+    assert_eq!(
+        git_ops.get_conflicts(&repo)?,
+        vec![PathBuf::from("README.md")]
+    );
+    assert_eq!(git_ops.get_last_commit_hash(&repo)?, head.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_revert_undoes_commit() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+
+    create_test_files(&repo_path, &[("docs/draft.md", "# Draft\n")])?;
+    git_ops.stage_all(&repo)?;
+    let draft = git_ops.commit(&repo, "Add draft page")?;
+
+    let oid = git_ops.revert(&repo, "HEAD")?;
+
+    assert!(!repo_path.join("docs/draft.md").exists());
+    let commit = repo.find_commit(oid)?;
+    assert_eq!(commit.summary(), Some("Revert \"Add draft page\""));
+    assert!(commit
+        .message()
+        .unwrap_or_default()
+        .contains(&format!("This reverts commit {}.", draft)));
+    assert!(git_ops.is_clean(&repo)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cherry_pick_refuses_dirty_tree() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("test-repo");
+
+    create_test_repo(&repo_path)?;
+
+    let git_ops = GitOperations::new(CredentialStore::new());
+    let repo = git_ops.open(&repo_path)?;
+    let head = git_ops.get_last_commit_hash(&repo)?;
+
+    fs::write(repo_path.join("README.md"), "uncommitted\n")?;
+
+    assert!(git_ops.cherry_pick(&repo, &head).is_err());
+    assert!(git_ops.revert(&repo, &head).is_err());
+    assert_eq!(git_ops.get_last_commit_hash(&repo)?, head);
+
+    Ok(())
+}
+
 #[test]
 fn test_diff_working_directory() -> Result<()> {
     let temp_dir = TempDir::new()?;
@@ -432,6 +432,8 @@ async fn test_chunk_metadata_preservation() {
             keywords: vec!["existing".to_string()],
             word_count: 28,
             char_count: 180,
+            overlap_sentences: 0,
+            breadcrumbs: Vec::new(),
         },
     );
 
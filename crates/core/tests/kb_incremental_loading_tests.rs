@@ -10,9 +10,13 @@ use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
 use xze_core::kb::{
+    backend::KbBackendKind,
+    encoding::EncodingMode,
     error::Result,
+    hash::HashAlgorithm,
     loader::{IncrementalLoader, LoaderConfig},
 };
+use xze_core::semantic::ChunkStrategy;
 
 /// Test database URL - uses environment variable or default
 fn test_db_url() -> String {
@@ -136,9 +140,18 @@ async fn test_resume_after_full_load() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -156,9 +169,18 @@ async fn test_resume_after_full_load() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_resume)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_resume)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -202,9 +224,18 @@ async fn test_update_modified_files() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -232,9 +263,18 @@ async fn test_update_modified_files() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_update)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_update)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -283,9 +323,18 @@ async fn test_cleanup_deleted_files() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -306,9 +355,18 @@ async fn test_cleanup_deleted_files() -> Result<()> {
         cleanup: true,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_cleanup)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_cleanup)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -357,9 +415,18 @@ async fn test_dry_run_mode() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -377,9 +444,18 @@ async fn test_dry_run_mode() -> Result<()> {
         cleanup: false,
         dry_run: true,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_dry_run)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_dry_run)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -403,9 +479,18 @@ async fn test_dry_run_mode() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_real)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_real)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -447,9 +532,18 @@ async fn test_force_full_reload() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -463,9 +557,18 @@ async fn test_force_full_reload() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: true,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_force)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_force)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -505,9 +608,18 @@ async fn test_mixed_scenario_add_update_delete() -> Result<()> {
         cleanup: false,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
@@ -533,9 +645,18 @@ async fn test_mixed_scenario_add_update_delete() -> Result<()> {
         cleanup: true,
         dry_run: false,
         force: false,
+        backend: KbBackendKind::Postgres,
+        hash_algorithm: HashAlgorithm::Sha256,
+        verify_hashes: false,
+        chunk_strategy: ChunkStrategy::Sentence,
+        encoding: EncodingMode::Detect,
+        concurrency: 1,
+        max_failures: 0,
+        checkpoint_path: None,
+        checkpoint_interval: 10,
     };
 
-    let loader = IncrementalLoader::new(pool.clone(), config_mixed)?;
+    let loader = IncrementalLoader::new(Some(pool.clone()), config_mixed)?;
     let stats = loader
         .load(&[base_path.to_string_lossy().to_string()])
         .await?;
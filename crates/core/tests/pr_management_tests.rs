@@ -49,9 +49,14 @@ fn test_platform_detection() {
         GitPlatform::GitLab
     );
 
-    // Unknown platforms
     assert_eq!(
         GitPlatform::detect("https://bitbucket.org/owner/repo"),
+        GitPlatform::Bitbucket
+    );
+
+    // Unknown platforms
+    assert_eq!(
+        GitPlatform::detect("https://git.example.org/owner/repo"),
         GitPlatform::Unknown
     );
 }
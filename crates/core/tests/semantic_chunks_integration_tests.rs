@@ -48,6 +48,8 @@ fn create_test_chunk(
         keywords: vec!["test".to_string(), "example".to_string()],
         word_count: content.split_whitespace().count(),
         char_count: content.chars().count(),
+        overlap_sentences: 0,
+        breadcrumbs: Vec::new(),
     };
 
     let mut chunk = SemanticChunk::new(
@@ -231,6 +233,8 @@ async fn test_store_chunks_with_empty_optional_fields() -> Result<()> {
         keywords: vec![],
         word_count: 5,
         char_count: 20,
+        overlap_sentences: 0,
+        breadcrumbs: Vec::new(),
     };
 
     let mut chunk = SemanticChunk::new(
@@ -4,6 +4,7 @@
 //! This includes Ollama client, file system abstractions, and other
 //! infrastructure-level services.
 
+use xze_core::ai::HttpPoolConfig;
 use xze_core::{Result, XzeError};
 
 pub mod cache;
@@ -27,6 +28,8 @@ pub struct InfraConfig {
     pub cache_dir: std::path::PathBuf,
     pub max_cache_size_mb: usize,
     pub log_level: String,
+    /// Connection pool settings for the Ollama HTTP client
+    pub http_pool: HttpPoolConfig,
 }
 
 impl Default for InfraConfig {
@@ -37,6 +40,7 @@ impl Default for InfraConfig {
             cache_dir: std::env::temp_dir().join("xze-cache"),
             max_cache_size_mb: 1024,
             log_level: "info".to_string(),
+            http_pool: HttpPoolConfig::default(),
         }
     }
 }
@@ -58,21 +62,43 @@ pub async fn init_infrastructure(config: InfraConfig) -> Result<()> {
     Ok(())
 }
 
+/// Timeout for the Ollama request made by [`health_check`]
+const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Health check for infrastructure components
+///
+/// The cache directory counts as accessible only if a file can be written
+/// to it, and Ollama only if it lists its models within five seconds.
 pub async fn health_check(config: &InfraConfig) -> Result<HealthStatus> {
     let mut status = HealthStatus::new();
 
-    // Check cache directory
-    if config.cache_dir.exists() && config.cache_dir.is_dir() {
-        status.cache_accessible = true;
-    }
-
-    // TODO: Check Ollama connectivity
-    status.ollama_accessible = false; // Placeholder
+    status.cache_accessible = cache_dir_writable(&config.cache_dir);
+
+    let ollama = OllamaClient::new(OllamaConfig {
+        timeout: HEALTH_CHECK_TIMEOUT,
+        ..OllamaConfig::from(config)
+    });
+    status.ollama_accessible = match ollama.list_models().await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::warn!("Ollama at {} is not accessible: {}", config.ollama_url, e);
+            false
+        }
+    };
 
     Ok(status)
 }
 
+/// Whether a temporary file can be written to `dir`
+fn cache_dir_writable(dir: &std::path::Path) -> bool {
+    use std::io::Write;
+
+    dir.is_dir()
+        && tempfile::NamedTempFile::new_in(dir)
+            .and_then(|mut file| file.write_all(b"xze health check"))
+            .is_ok()
+}
+
 /// Health status for infrastructure components
 #[derive(Debug, Clone)]
 pub struct HealthStatus {
@@ -90,9 +116,29 @@ impl HealthStatus {
         }
     }
 
+    /// Whether the components needed to keep running are accessible
+    ///
+    /// Ollama is not required: without it the server still answers
+    /// requests that need no generation, so see [`is_degraded`](Self::is_degraded).
     pub fn is_healthy(&self) -> bool {
         self.cache_accessible && self.filesystem_accessible
     }
+
+    /// Whether the server is running but Ollama is down
+    pub fn is_degraded(&self) -> bool {
+        self.is_healthy() && !self.ollama_accessible
+    }
+
+    /// `"healthy"`, `"degraded"` or `"unhealthy"`
+    pub fn status(&self) -> &'static str {
+        if !self.is_healthy() {
+            "unhealthy"
+        } else if self.is_degraded() {
+            "degraded"
+        } else {
+            "healthy"
+        }
+    }
 }
 
 impl Default for HealthStatus {
@@ -112,6 +158,7 @@ mod tests {
         assert_eq!(config.ollama_url, "http://localhost:11434");
         assert_eq!(config.ollama_timeout_seconds, 300);
         assert_eq!(config.max_cache_size_mb, 1024);
+        assert_eq!(config.http_pool, HttpPoolConfig::default());
     }
 
     #[tokio::test]
@@ -138,6 +185,57 @@ mod tests {
         assert!(status.filesystem_accessible);
     }
 
+    #[tokio::test]
+    async fn test_health_check_reaches_ollama() {
+        let mut server = mockito::Server::new_async().await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .with_body(r#"{"models":[]}"#)
+            .create_async()
+            .await;
+        let temp_dir = TempDir::new().unwrap();
+        let config = InfraConfig {
+            ollama_url: server.url(),
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let status = health_check(&config).await.unwrap();
+        tags.assert_async().await;
+        assert!(status.ollama_accessible);
+        assert_eq!(status.status(), "healthy");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_degraded_without_ollama() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/tags")
+            .with_status(500)
+            .create_async()
+            .await;
+        let temp_dir = TempDir::new().unwrap();
+        let config = InfraConfig {
+            ollama_url: server.url(),
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let status = health_check(&config).await.unwrap();
+        assert!(!status.ollama_accessible);
+        assert!(status.is_healthy());
+        assert!(status.is_degraded());
+        assert_eq!(status.status(), "degraded");
+
+        let missing = InfraConfig {
+            cache_dir: temp_dir.path().join("missing"),
+            ..config
+        };
+        let status = health_check(&missing).await.unwrap();
+        assert!(!status.cache_accessible);
+        assert_eq!(status.status(), "unhealthy");
+    }
+
     #[test]
     fn test_health_status() {
         let status = HealthStatus::new();
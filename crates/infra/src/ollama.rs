@@ -1,7 +1,9 @@
 //! Ollama client implementation for XZe infrastructure
 
+use crate::InfraConfig;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use xze_core::ai::client::{build_http_client, HttpPoolConfig};
 use xze_core::{Result, XzeError};
 
 /// Ollama client configuration
@@ -10,6 +12,7 @@ pub struct OllamaConfig {
     pub base_url: String,
     pub timeout: Duration,
     pub model: String,
+    pub pool: HttpPoolConfig,
 }
 
 impl Default for OllamaConfig {
@@ -18,6 +21,18 @@ impl Default for OllamaConfig {
             base_url: "http://localhost:11434".to_string(),
             timeout: Duration::from_secs(300),
             model: "llama2".to_string(),
+            pool: HttpPoolConfig::default(),
+        }
+    }
+}
+
+impl From<&InfraConfig> for OllamaConfig {
+    fn from(config: &InfraConfig) -> Self {
+        Self {
+            base_url: config.ollama_url.clone(),
+            timeout: Duration::from_secs(config.ollama_timeout_seconds),
+            pool: config.http_pool.clone(),
+            ..Self::default()
         }
     }
 }
@@ -38,10 +53,7 @@ impl Default for OllamaClient {
 impl OllamaClient {
     /// Create a new Ollama client
     pub fn new(config: OllamaConfig) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(config.timeout)
-            .build()
-            .unwrap();
+        let client = build_http_client(config.timeout, &config.pool).unwrap();
 
         Self { config, client }
     }
@@ -163,6 +175,22 @@ mod tests {
         assert_eq!(config.model, "llama2");
     }
 
+    #[test]
+    fn test_ollama_config_from_infra() {
+        let infra = InfraConfig {
+            ollama_timeout_seconds: 30,
+            http_pool: HttpPoolConfig {
+                max_idle_per_host: 4,
+                ..HttpPoolConfig::default()
+            },
+            ..InfraConfig::default()
+        };
+
+        let config = OllamaConfig::from(&infra);
+        assert_eq!(config.timeout, Duration::from_secs(30));
+        assert_eq!(config.pool.max_idle_per_host, 4);
+    }
+
     #[test]
     fn test_ollama_client_creation() {
         let config = OllamaConfig::default();
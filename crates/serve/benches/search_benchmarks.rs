@@ -52,6 +52,11 @@ fn benchmark_request_validation(c: &mut Criterion) {
             include_snippets: Some(true),
             highlight_terms: Some(true),
             group_by: Some("category".to_string()),
+            dedup_by: None,
+            rerank_top_n: None,
+            explain: None,
+            sort: None,
+            cursor: None,
         }),
         aggregations: Some(AggregationRequest {
             by_category: Some(true),
@@ -101,6 +106,11 @@ fn benchmark_serialization(c: &mut Criterion) {
             include_snippets: Some(true),
             highlight_terms: Some(true),
             group_by: Some("category".to_string()),
+            dedup_by: None,
+            rerank_top_n: None,
+            explain: None,
+            sort: None,
+            cursor: None,
         }),
         aggregations: Some(AggregationRequest {
             by_category: Some(true),
@@ -283,6 +293,11 @@ fn benchmark_options_validation(c: &mut Criterion) {
         include_snippets: Some(true),
         highlight_terms: Some(true),
         group_by: Some("category".to_string()),
+        dedup_by: None,
+        rerank_top_n: None,
+        explain: None,
+        sort: None,
+        cursor: None,
     };
 
     group.bench_function("custom", |b| {
@@ -299,6 +314,11 @@ fn benchmark_options_validation(c: &mut Criterion) {
         include_snippets: Some(true),
         highlight_terms: Some(true),
         group_by: Some("similarity".to_string()),
+        dedup_by: None,
+        rerank_top_n: None,
+        explain: None,
+        sort: None,
+        cursor: None,
     };
 
     group.bench_function("maximum", |b| {
@@ -483,6 +503,11 @@ fn benchmark_full_validation_pipeline(c: &mut Criterion) {
             include_snippets: Some(true),
             highlight_terms: Some(true),
             group_by: Some("category".to_string()),
+            dedup_by: None,
+            rerank_top_n: None,
+            explain: None,
+            sort: None,
+            cursor: None,
         }),
         aggregations: Some(AggregationRequest {
             by_category: Some(true),
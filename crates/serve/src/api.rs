@@ -18,6 +18,138 @@ use std::collections::HashMap;
 /// API version
 pub const API_VERSION: &str = "v1";
 
+/// A group of endpoints that can be switched off
+///
+/// Disabled groups are not mounted, so their paths return 404.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointGroup {
+    /// Repository analysis and call graphs, which run analyzers on demand
+    Analyze,
+    /// Search under `/api/search`, `/api/v1/search` and `/search`
+    Search,
+    /// The `/api/ws/search` WebSocket
+    Websocket,
+    /// Search analytics under `/api/search/analytics`
+    Analytics,
+    /// Administrative status endpoints under `/api/v1/admin`
+    Admin,
+}
+
+impl EndpointGroup {
+    /// Every group, in declaration order
+    pub const ALL: [EndpointGroup; 5] = [
+        EndpointGroup::Analyze,
+        EndpointGroup::Search,
+        EndpointGroup::Websocket,
+        EndpointGroup::Analytics,
+        EndpointGroup::Admin,
+    ];
+}
+
+impl std::fmt::Display for EndpointGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            EndpointGroup::Analyze => "analyze",
+            EndpointGroup::Search => "search",
+            EndpointGroup::Websocket => "websocket",
+            EndpointGroup::Analytics => "analytics",
+            EndpointGroup::Admin => "admin",
+        };
+        f.pad(name)
+    }
+}
+
+impl std::str::FromStr for EndpointGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        EndpointGroup::ALL
+            .into_iter()
+            .find(|group| group.to_string() == name)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown endpoint group '{}': expected analyze, search, websocket, analytics \
+                     or admin",
+                    name
+                )
+            })
+    }
+}
+
+/// Which endpoint groups the server mounts
+///
+/// Every group is enabled by default. Health, version, repository and
+/// documentation endpoints are always mounted.
+///
+/// # Examples
+///
+/// ```
+/// use xze_serve::api::{EndpointGroup, EndpointToggles};
+///
+/// // A read-only search server
+/// let toggles = EndpointToggles {
+///     enable_analyze: false,
+///     enable_admin: false,
+///     ..EndpointToggles::default()
+/// };
+/// assert!(toggles.is_enabled(EndpointGroup::Search));
+/// assert!(!toggles.is_enabled(EndpointGroup::Analyze));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EndpointToggles {
+    /// Mount `POST /analyze`, `POST /repositories/:id/analyze`,
+    /// `GET /repositories/:id/annotations` and `POST /call-graph`
+    pub enable_analyze: bool,
+    /// Mount the search endpoints
+    pub enable_search: bool,
+    /// Mount the search WebSocket
+    pub enable_websocket: bool,
+    /// Mount the search analytics endpoints
+    pub enable_analytics: bool,
+    /// Mount the admin endpoints
+    pub enable_admin: bool,
+}
+
+impl Default for EndpointToggles {
+    fn default() -> Self {
+        Self {
+            enable_analyze: true,
+            enable_search: true,
+            enable_websocket: true,
+            enable_analytics: true,
+            enable_admin: true,
+        }
+    }
+}
+
+impl EndpointToggles {
+    /// Whether `group` is mounted
+    pub fn is_enabled(&self, group: EndpointGroup) -> bool {
+        match group {
+            EndpointGroup::Analyze => self.enable_analyze,
+            EndpointGroup::Search => self.enable_search,
+            EndpointGroup::Websocket => self.enable_websocket,
+            EndpointGroup::Analytics => self.enable_analytics,
+            EndpointGroup::Admin => self.enable_admin,
+        }
+    }
+
+    /// Enable or disable `group`
+    pub fn set(&mut self, group: EndpointGroup, enabled: bool) {
+        let toggle = match group {
+            EndpointGroup::Analyze => &mut self.enable_analyze,
+            EndpointGroup::Search => &mut self.enable_search,
+            EndpointGroup::Websocket => &mut self.enable_websocket,
+            EndpointGroup::Analytics => &mut self.enable_analytics,
+            EndpointGroup::Admin => &mut self.enable_admin,
+        };
+        *toggle = enabled;
+    }
+}
+
 /// API routes configuration
 ///
 /// Creates the complete router with both v1 and legacy endpoints.
@@ -40,20 +172,32 @@ pub const API_VERSION: &str = "v1";
 /// // let router = create_routes().with_state(state);
 /// ```
 pub fn create_routes() -> Router<crate::handlers::AppState> {
+    create_routes_with(&EndpointToggles::default())
+}
+
+/// API routes with only the enabled endpoint groups mounted
+///
+/// Applies `endpoints` to both the v1 and legacy routes.
+pub fn create_routes_with(endpoints: &EndpointToggles) -> Router<crate::handlers::AppState> {
     // Create v1 routes nested under /api/v1
-    let v1_routes = v1::create_v1_routes();
+    let v1_routes = v1::create_v1_routes_with(endpoints);
 
     // Create legacy routes (deprecated but maintained for backward compatibility)
-    let legacy_routes = Router::new()
+    let mut legacy_routes = Router::new()
         .route("/health", get(health_check))
         .route("/version", get(get_version))
-        .route("/search", get(crate::handlers::handle_search))
-        .route("/analyze", post(analyze_repository))
         .route("/repositories", get(list_repositories))
         .route("/repositories/:id", get(get_repository))
-        .route("/repositories/:id/analyze", post(analyze_repository_by_id))
         .route("/documentation", get(list_documentation))
         .route("/documentation/:id", get(get_documentation));
+    if endpoints.enable_search {
+        legacy_routes = legacy_routes.route("/search", get(crate::handlers::handle_search));
+    }
+    if endpoints.enable_analyze {
+        legacy_routes = legacy_routes
+            .route("/analyze", post(analyze_repository))
+            .route("/repositories/:id/analyze", post(analyze_repository_by_id));
+    }
 
     // Combine routes: v1 under /api/v1, legacy at root
     Router::new()
@@ -293,6 +437,20 @@ mod tests {
         let _v1_routes = v1::create_v1_routes();
     }
 
+    #[test]
+    fn test_endpoint_toggles() {
+        let mut toggles = EndpointToggles::default();
+        assert!(EndpointGroup::ALL.iter().all(|g| toggles.is_enabled(*g)));
+
+        toggles.set("Analyze".parse().unwrap(), false);
+        assert!(!toggles.enable_analyze);
+        assert_eq!(EndpointGroup::Websocket.to_string(), "websocket");
+        assert!("generate".parse::<EndpointGroup>().is_err());
+
+        let parsed: EndpointToggles = serde_json::from_str(r#"{"enable_admin": false}"#).unwrap();
+        assert!(!parsed.enable_admin && parsed.enable_search);
+    }
+
     #[test]
     fn test_create_routes_includes_v1_and_legacy() {
         // Test that router creation includes both v1 and legacy routes
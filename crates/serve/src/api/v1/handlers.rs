@@ -13,7 +13,26 @@ use std::collections::HashMap;
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
 
+use std::path::PathBuf;
+use std::sync::Arc;
+use xze_core::config::{ModelConfig, RepositoryConfig};
+use xze_core::documentation::versions::{DocumentDiff, SectionChange, SectionChangeKind};
+use xze_core::documentation::{
+    build_sink, DocumentationService, GenerationReport, GenerationStatus, OutputSinkKind,
+};
+use xze_core::kb::{KbBackend, KbStore};
+use xze_core::repository::analyzer::AnalyzerFactory;
+use xze_core::repository::redact::PathRedactor;
+use xze_core::repository::{AnalysisOutcome, Repository, RepositoryFilter};
+use xze_core::repository::{AnalysisWarning, AnnotationCounts, AnnotationKind, CodeAnnotation};
+use xze_core::types::{DiátaxisCategory, ProgrammingLanguage};
+
+use xze_core::ai::{select_models, AIAnalysisService, ModelSelection, OllamaClient};
+use xze_core::XzeError;
+
+use crate::cache::{analyze_cached, AnalysisCacheStatus};
 use crate::handlers::AppState;
+use crate::search::handlers::ErrorResponse;
 
 /// Health check endpoint for API v1
 ///
@@ -87,7 +106,11 @@ pub async fn get_version(State(_state): State<AppState>) -> impl IntoResponse {
 
 /// Analyze repository endpoint
 ///
-/// Queues a repository for AI-powered documentation analysis
+/// Generates documentation for the selected Diátaxis categories when the
+/// repository is a local checkout: the `local_path` of the configured
+/// repository with that URL, or a directory given as a path or `file://`
+/// URL. The documents are delivered to the configured output sink. Other
+/// repositories are queued for AI-powered documentation analysis.
 ///
 /// # Arguments
 ///
@@ -95,14 +118,16 @@ pub async fn get_version(State(_state): State<AppState>) -> impl IntoResponse {
 ///
 /// # Returns
 ///
-/// Returns a job ID and status for tracking the analysis
+/// Returns the generated documents for a local checkout, with `207` if
+/// some categories failed and `502` if all did, otherwise a job ID and
+/// status for tracking the analysis
 ///
 /// # Examples
 ///
 /// ```
 /// // POST /api/v1/analyze
-/// // Body: {"repository_url":"https://github.com/user/repo","branch":"main"}
-/// // Returns: {"job_id":"...","status":"queued","message":"..."}
+/// // Body: {"repository_url":"https://github.com/user/repo","branch":"main","model":"llama3:70b"}
+/// // Returns: {"job_id":"...","status":"queued","message":"...","model":"llama3:70b","fallback_models":[...]}
 /// ```
 #[cfg_attr(
     feature = "openapi",
@@ -112,27 +137,223 @@ pub async fn get_version(State(_state): State<AppState>) -> impl IntoResponse {
         tag = "analysis",
         request_body = AnalyzeRequest,
         responses(
-            (status = 200, description = "Analysis job queued successfully", body = AnalyzeResponse),
-            (status = 400, description = "Invalid request parameters"),
+            (status = 200, description = "Documentation generated, or analysis job queued", body = AnalyzeResponse),
+            (status = 207, description = "Some documentation categories failed to generate", body = AnalyzeResponse),
+            (status = 400, description = "Invalid request parameters, model not installed or nothing to analyze", body = ErrorResponse),
+            (status = 500, description = "Repository analysis or document delivery failed", body = ErrorResponse),
+            (status = 502, description = "Failed to list installed models, or no documentation category generated", body = ErrorResponse),
         )
     )
 )]
 pub async fn analyze_repository(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(request): Json<AnalyzeRequest>,
 ) -> impl IntoResponse {
-    // TODO: Implement actual repository analysis
-    // This should queue a job in the background processing system
-    Json(AnalyzeResponse {
+    let categories =
+        match DiátaxisCategory::parse_list(request.docs.as_deref().unwrap_or_default()) {
+            Ok(categories) => categories,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: "Invalid documentation category".to_string(),
+                        details: Some(e.to_string()),
+                    }),
+                )
+                    .into_response();
+            }
+        };
+
+    let models = match select_models(
+        &OllamaClient::shared(&state.ollama_url),
+        &state.config.models,
+        request.model.as_deref(),
+        request.fallback_models.as_deref(),
+    )
+    .await
+    {
+        Ok(models) => models,
+        Err(e) => return model_error_response(e),
+    };
+
+    let Some(path) = local_checkout(&state.config.repositories, &request.repository_url) else {
+        // TODO: Implement actual repository analysis
+        // This should queue a job in the background processing system
+        let categories = categories
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Json(AnalyzeResponse {
+            job_id: uuid::Uuid::new_v4().to_string(),
+            status: "queued".to_string(),
+            message: format!(
+                "Analysis queued for repository: {} ({})",
+                request.repository_url, categories
+            ),
+            model: models.model,
+            fallback_models: models.fallback_models,
+            generation: None,
+        })
+        .into_response();
+    };
+
+    let report = match generate_documentation(&state, &path, &request, &categories, &models).await {
+        Ok(report) => report,
+        Err(e) => {
+            let status = match e {
+                XzeError::Validation { .. } => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (
+                status,
+                Json(ErrorResponse {
+                    error: "Documentation generation failed".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let outcome = report.status();
+    let status = match outcome {
+        GenerationStatus::Complete => "completed",
+        GenerationStatus::Partial => "partial",
+        GenerationStatus::Failed => "failed",
+    };
+    let response = Json(AnalyzeResponse {
         job_id: uuid::Uuid::new_v4().to_string(),
-        status: "queued".to_string(),
-        message: format!("Analysis queued for repository: {}", request.repository_url),
+        status: status.to_string(),
+        message: format!(
+            "Generated {} documents for repository: {} ({} categories failed)",
+            report.documents.len(),
+            request.repository_url,
+            report.failures.len()
+        ),
+        model: models.model,
+        fallback_models: models.fallback_models,
+        generation: Some(report),
+    });
+    let code = StatusCode::from_u16(outcome.http_status()).unwrap_or(StatusCode::BAD_GATEWAY);
+    (code, response).into_response()
+}
+
+/// Local checkout named by `repository_url`
+///
+/// The `local_path` of the configured repository with that URL, or the
+/// directory a `file://` URL or plain path names.
+fn local_checkout(repositories: &[RepositoryConfig], repository_url: &str) -> Option<PathBuf> {
+    let configured = repositories
+        .iter()
+        .find(|r| r.url.as_str().trim_end_matches('/') == repository_url.trim_end_matches('/'))
+        .and_then(|r| r.local_path.clone());
+    let path = match configured {
+        Some(path) => path,
+        None if repository_url.contains("://") => {
+            PathBuf::from(repository_url.strip_prefix("file://")?)
+        }
+        None => PathBuf::from(repository_url),
+    };
+    path.is_dir().then_some(path)
+}
+
+/// Analyze a local checkout and deliver the selected documentation to the
+/// configured output sink
+async fn generate_documentation(
+    state: &AppState,
+    path: &std::path::Path,
+    request: &AnalyzeRequest,
+    categories: &[DiátaxisCategory],
+    models: &ModelSelection,
+) -> xze_core::Result<GenerationReport> {
+    let language = request.language.as_deref().map(ProgrammingLanguage::from);
+    let root = path.to_path_buf();
+    let outcome = tokio::task::spawn_blocking(move || {
+        xze_core::repository::analyze_repository(&root, language)
     })
+    .await
+    .map_err(|e| XzeError::repository(format!("Analysis task failed: {}", e)))??;
+    let (language, structure) = match outcome {
+        AnalysisOutcome::Analyzed {
+            language,
+            structure,
+        } => (language, structure),
+        AnalysisOutcome::Empty(empty) => {
+            return Err(XzeError::validation(format!(
+                "Nothing to analyze in {}: {}",
+                request.repository_url, empty
+            )))
+        }
+    };
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| request.repository_url.clone());
+    let mut repo = Repository::new(
+        name.into(),
+        request.repository_url.clone(),
+        path.to_path_buf(),
+        language,
+    );
+    repo.update_structure(structure);
+    repo.redact_paths(state.config.path_redaction);
+
+    let service = match &state.documentation {
+        Some(service) => service.clone(),
+        None => Arc::new(documentation_service(state, models)?),
+    };
+    let kb: Option<Arc<dyn KbBackend>> = match state.config.output.sink {
+        OutputSinkKind::Kb => Some(Arc::new(KbStore::new(state.database_pool.clone()))),
+        _ => None,
+    };
+    let sink = build_sink(&state.config.output, kb)?;
+    service
+        .generate_to_sink(&repo, categories, sink.as_ref())
+        .await
+}
+
+/// Documentation service that generates with the selected models
+fn documentation_service(
+    state: &AppState,
+    models: &ModelSelection,
+) -> xze_core::Result<DocumentationService> {
+    let model_config = ModelConfig {
+        primary: models.model.clone(),
+        fallback: models.fallback_models.clone(),
+        ..state.config.models.clone()
+    };
+    let ai_service = Arc::new(AIAnalysisService::new(
+        state.ollama_url.clone(),
+        model_config,
+    ));
+    DocumentationService::with_defaults(ai_service).with_generation_config(&state.config.generation)
+}
+
+/// Map a model selection failure to a response
+///
+/// An unknown or empty model name is the caller's mistake and gets a 400;
+/// failing to reach the AI server to check the names gets a 502.
+fn model_error_response(error: XzeError) -> axum::response::Response {
+    let (status, error_text) = match error {
+        XzeError::Validation { .. } => (StatusCode::BAD_REQUEST, "Model not available"),
+        _ => (StatusCode::BAD_GATEWAY, "Failed to list installed models"),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: error_text.to_string(),
+            details: Some(error.to_string()),
+        }),
+    )
+        .into_response()
 }
 
 /// List repositories endpoint
 ///
-/// Returns a paginated list of repositories
+/// Returns a paginated list of the configured repositories, optionally
+/// narrowed to one group or tag
 ///
 /// # Arguments
 ///
@@ -140,12 +361,13 @@ pub async fn analyze_repository(
 ///
 /// # Returns
 ///
-/// Returns a list of repositories with pagination metadata
+/// Returns a list of repositories with pagination metadata, or 400 if the
+/// group or tag is not a valid label
 ///
 /// # Examples
 ///
 /// ```
-/// // GET /api/v1/repositories?page=1&per_page=10
+/// // GET /api/v1/repositories?page=1&per_page=10&group=payments&tag=critical
 /// // Returns: {"repositories":[...],"total":0,"page":1,"per_page":10}
 /// ```
 #[cfg_attr(
@@ -157,26 +379,80 @@ pub async fn analyze_repository(
         params(
             ("page" = Option<usize>, Query, description = "Page number (default: 1)"),
             ("per_page" = Option<usize>, Query, description = "Items per page (default: 10, max: 100)"),
+            ("group" = Option<String>, Query, description = "Only repositories in this group"),
+            ("tag" = Option<String>, Query, description = "Only repositories with this tag"),
         ),
         responses(
             (status = 200, description = "List of repositories", body = RepositoryListResponse),
+            (status = 400, description = "Invalid group or tag"),
         )
     )
 )]
 pub async fn list_repositories(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
-    // TODO: Implement actual repository listing from database
-    Json(RepositoryListResponse {
-        repositories: vec![],
-        total: 0,
-        page: params.get("page").and_then(|p| p.parse().ok()).unwrap_or(1),
-        per_page: params
-            .get("per_page")
-            .and_then(|p| p.parse().ok())
-            .unwrap_or(10),
-    })
+    let filter = RepositoryFilter {
+        group: params.get("group").cloned(),
+        tag: params.get("tag").cloned(),
+    };
+    let filter = match filter.normalized() {
+        Ok(filter) => filter,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid repository filter".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    let page = params
+        .get("page")
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(1usize)
+        .max(1);
+    let per_page = params
+        .get("per_page")
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(10usize)
+        .clamp(1, 100);
+
+    Json(list_configured_repositories(
+        &state.config.repositories,
+        &filter,
+        page,
+        per_page,
+    ))
+    .into_response()
+}
+
+/// One page of the configured repositories selected by `filter`
+fn list_configured_repositories(
+    repositories: &[RepositoryConfig],
+    filter: &RepositoryFilter,
+    page: usize,
+    per_page: usize,
+) -> RepositoryListResponse {
+    let matching: Vec<RepositoryInfo> = repositories
+        .iter()
+        .map(RepositoryInfo::from)
+        .filter(|info| filter.matches(info.group.as_deref(), &info.tags))
+        .collect();
+
+    RepositoryListResponse {
+        total: matching.len(),
+        repositories: matching
+            .into_iter()
+            .skip((page - 1).saturating_mul(per_page))
+            .take(per_page)
+            .collect(),
+        page,
+        per_page,
+    }
 }
 
 /// Get repository by ID
@@ -254,14 +530,285 @@ pub async fn get_repository(
     )
 )]
 pub async fn analyze_repository_by_id(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
+    let models = ModelSelection::from_config(&state.config.models);
+
     // TODO: Implement repository analysis by ID
     Json(AnalyzeResponse {
         job_id: uuid::Uuid::new_v4().to_string(),
         status: "queued".to_string(),
         message: format!("Analysis queued for repository ID: {}", id),
+        model: models.model,
+        fallback_models: models.fallback_models,
+        generation: None,
+    })
+}
+
+/// Call graph endpoint
+///
+/// Analyzes a local repository and returns its best-effort call graph.
+/// When `function` is given, the callers and callees of that function are
+/// returned as well.
+///
+/// # Arguments
+///
+/// * `request` - Repository path, optional language and function name
+///
+/// # Returns
+///
+/// Returns the call graph or 400 if the path cannot be analyzed
+///
+/// # Examples
+///
+/// ```
+/// // POST /api/v1/call-graph
+/// // Body: {"path":"/srv/repos/xze","function":"generate_all"}
+/// // Returns: {"call_graph":{...},"callers":[...],"callees":[...]}
+/// ```
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/call-graph",
+        tag = "analysis",
+        request_body = CallGraphRequest,
+        responses(
+            (status = 200, description = "Call graph of the repository", body = CallGraphResponse),
+            (status = 400, description = "Invalid repository path or language"),
+        )
+    )
+)]
+pub async fn get_call_graph(
+    State(state): State<AppState>,
+    Json(request): Json<CallGraphRequest>,
+) -> impl IntoResponse {
+    let bad_request = |error: &str, details: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: Some(details),
+            }),
+        )
+            .into_response()
+    };
+
+    let path = std::path::PathBuf::from(&request.path);
+    if !path.is_dir() {
+        return bad_request(
+            "Invalid repository path",
+            format!("{} is not a directory", request.path),
+        );
+    }
+
+    // Cached analyses use the auto-detected analyzer, so an explicit
+    // language always bypasses the cache
+    let analysis = match request.language.clone() {
+        Some(language) => {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || {
+                AnalyzerFactory::create_analyzer(&ProgrammingLanguage::from(language.as_str()))
+                    .analyze(&path)
+                    .map(Arc::new)
+            })
+            .await
+        }
+        None => Ok(analyze_cached(&state.analysis_cache, &path).await),
+    };
+
+    let structure = match analysis {
+        Ok(Ok(structure)) => structure,
+        Ok(Err(e)) => return bad_request("Repository analysis failed", e.to_string()),
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Repository analysis failed".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    let (callers, callees) = match &request.function {
+        Some(function) => (
+            Some(structure.callers_of(function)),
+            Some(structure.callees_of(function)),
+        ),
+        None => (None, None),
+    };
+
+    let redactor = PathRedactor::new(&path, state.config.path_redaction);
+    Json(CallGraphResponse {
+        call_graph: structure.call_graph.clone(),
+        callers,
+        callees,
+        warnings: structure
+            .warnings
+            .iter()
+            .map(|warning| AnalysisWarningInfo::redacted(warning, &redactor))
+            .collect(),
+    })
+    .into_response()
+}
+
+/// Repository annotations endpoint
+///
+/// Returns the TODO, FIXME and deprecation markers found in a repository.
+/// Repositories are looked up by directory name among those configured for
+/// the analysis cache warmer, and their analysis is served from the cache
+/// when it is current.
+///
+/// # Arguments
+///
+/// * `id` - Directory name of a configured repository
+/// * `kind` - Optional filter: `todo`, `fixme` or `deprecated`
+///
+/// # Returns
+///
+/// Returns the markers and their counts, or 404 if the repository is not
+/// configured
+///
+/// # Examples
+///
+/// ```
+/// // GET /api/v1/repositories/xze/annotations?kind=deprecated
+/// // Returns: {"repository":"xze","counts":{...},"annotations":[...]}
+/// ```
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/repositories/{id}/annotations",
+        tag = "repositories",
+        params(
+            ("id" = String, Path, description = "Directory name of a configured repository"),
+            ("kind" = Option<String>, Query, description = "Only return `todo`, `fixme` or `deprecated` markers"),
+        ),
+        responses(
+            (status = 200, description = "Markers found in the repository", body = AnnotationsResponse),
+            (status = 400, description = "Invalid annotation kind"),
+            (status = 404, description = "Repository not configured"),
+        )
+    )
+)]
+pub async fn get_repository_annotations(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    let error = |status: StatusCode, error: &str, details: String| {
+        (
+            status,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: Some(details),
+            }),
+        )
+            .into_response()
+    };
+
+    let kind = match params.get("kind").map(|k| parse_annotation_kind(k)) {
+        None => None,
+        Some(Some(kind)) => Some(kind),
+        Some(None) => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                "Invalid annotation kind",
+                "kind must be one of todo, fixme, deprecated".to_string(),
+            )
+        }
+    };
+
+    let Some(path) = configured_repository(&state.config.warmer.repositories, &id) else {
+        return error(
+            StatusCode::NOT_FOUND,
+            "Repository not found",
+            format!("No configured repository is named {}", id),
+        );
+    };
+
+    let structure = match analyze_cached(&state.analysis_cache, &path).await {
+        Ok(structure) => structure,
+        Err(e) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Repository analysis failed",
+                e.to_string(),
+            )
+        }
+    };
+
+    let redactor = PathRedactor::new(&path, state.config.path_redaction);
+    Json(AnnotationsResponse {
+        repository: id,
+        counts: structure.annotation_counts(),
+        annotations: structure
+            .annotations
+            .iter()
+            .filter(|a| kind.is_none_or(|kind| a.kind == kind))
+            .map(|annotation| AnnotationInfo::redacted(annotation, &redactor))
+            .collect(),
+    })
+    .into_response()
+}
+
+/// Configured repository whose directory name is `id`
+fn configured_repository(
+    repositories: &[std::path::PathBuf],
+    id: &str,
+) -> Option<std::path::PathBuf> {
+    repositories
+        .iter()
+        .find(|path| path.file_name().and_then(|n| n.to_str()) == Some(id))
+        .cloned()
+}
+
+fn parse_annotation_kind(kind: &str) -> Option<AnnotationKind> {
+    match kind.to_lowercase().as_str() {
+        "todo" => Some(AnnotationKind::Todo),
+        "fixme" => Some(AnnotationKind::Fixme),
+        "deprecated" => Some(AnnotationKind::Deprecated),
+        _ => None,
+    }
+}
+
+/// Analysis cache warmer status endpoint
+///
+/// Reports, for each repository the warmer is configured with, the commit
+/// of its cached analysis and when that analysis was stored.
+///
+/// # Returns
+///
+/// Returns the warmer settings and per-repository cache state
+///
+/// # Examples
+///
+/// ```
+/// // GET /api/v1/admin/warmer
+/// // Returns: {"enabled":true,"interval_secs":3600,"repositories":[...]}
+/// ```
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/admin/warmer",
+        tag = "admin",
+        responses(
+            (status = 200, description = "Analysis cache warmer status", body = WarmerStatusResponse),
+        )
+    )
+)]
+pub async fn get_warmer_status(State(state): State<AppState>) -> Json<WarmerStatusResponse> {
+    let warmer = &state.config.warmer;
+
+    Json(WarmerStatusResponse {
+        enabled: warmer.enabled,
+        interval_secs: warmer.interval_secs,
+        repositories: state.analysis_cache.status(&warmer.repositories).await,
     })
 }
 
@@ -339,6 +886,84 @@ pub async fn get_documentation(
     StatusCode::NOT_FOUND
 }
 
+/// Compare two versions of a document
+///
+/// Returns the sections added, removed or changed between the versions,
+/// the change in word count and confidence, and a unified diff. Versions are
+/// identified by content hash or a unique prefix of at least 7 characters.
+///
+/// # Arguments
+///
+/// * `id` - Documentation ID
+/// * `from` - Hash of the older version
+/// * `to` - Hash of the newer version
+///
+/// # Returns
+///
+/// Returns the diff, 400 if a hash is missing, too short or ambiguous, or
+/// 404 if the document or a version does not exist
+///
+/// # Examples
+///
+/// ```
+/// // GET /api/v1/documentation/api-reference/diff?from=3f2a9c1&to=8b41d07
+/// // Returns: {"id":"api-reference","sections":[...],"word_count_delta":12,...}
+/// ```
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/documentation/{id}/diff",
+        tag = "documentation",
+        params(
+            ("id" = String, Path, description = "Documentation unique identifier"),
+            ("from" = String, Query, description = "Hash or hash prefix of the older version"),
+            ("to" = String, Query, description = "Hash or hash prefix of the newer version"),
+        ),
+        responses(
+            (status = 200, description = "Differences between the versions", body = DocumentationDiffResponse),
+            (status = 400, description = "Missing, short or ambiguous version hash"),
+            (status = 404, description = "Documentation or version not found"),
+        )
+    )
+)]
+pub async fn get_documentation_diff(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    let error = |status: StatusCode, error: &str, details: String| {
+        (
+            status,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: Some(details),
+            }),
+        )
+            .into_response()
+    };
+
+    let (Some(from), Some(to)) = (params.get("from"), params.get("to")) else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "Missing version hash",
+            "Both from and to are required".to_string(),
+        );
+    };
+
+    match state.document_versions.read().await.diff(&id, from, to) {
+        Ok(diff) => Json(DocumentationDiffResponse::new(id, diff)).into_response(),
+        Err(e @ XzeError::NotFound { .. }) => {
+            error(StatusCode::NOT_FOUND, "Version not found", e.to_string())
+        }
+        Err(e) => error(
+            StatusCode::BAD_REQUEST,
+            "Invalid version hash",
+            e.to_string(),
+        ),
+    }
+}
+
 // Response types
 
 /// Health check response
@@ -391,7 +1016,7 @@ pub struct AnalyzeResponse {
         schema(example = "550e8400-e29b-41d4-a716-446655440000")
     )]
     pub job_id: String,
-    /// Job status (queued, processing, completed, failed)
+    /// Job status (queued, processing, completed, partial, failed)
     #[cfg_attr(feature = "openapi", schema(example = "queued"))]
     pub status: String,
     /// Human-readable message
@@ -400,6 +1025,17 @@ pub struct AnalyzeResponse {
         schema(example = "Analysis queued for repository")
     )]
     pub message: String,
+    /// Model the analysis uses
+    #[cfg_attr(feature = "openapi", schema(example = "llama3:70b"))]
+    pub model: String,
+    /// Models tried in order if the primary model fails
+    #[cfg_attr(feature = "openapi", schema(example = json!(["codellama"])))]
+    pub fallback_models: Vec<String>,
+    /// Generated documents and the categories that failed, when the
+    /// repository is a local checkout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<Object>))]
+    pub generation: Option<GenerationReport>,
 }
 
 /// Repository list response
@@ -440,6 +1076,34 @@ pub struct RepositoryInfo {
     pub language: String,
     /// Last analysis timestamp
     pub last_analyzed: Option<chrono::DateTime<chrono::Utc>>,
+    /// Group the repository belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = "payments"))]
+    pub group: Option<String>,
+    /// Repository tags
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["critical"])))]
+    pub tags: Vec<String>,
+}
+
+impl From<&RepositoryConfig> for RepositoryInfo {
+    /// Labels that fail validation are kept as written, so that a listing
+    /// never hides a repository
+    fn from(config: &RepositoryConfig) -> Self {
+        Self {
+            id: config.name.clone(),
+            name: config.name.clone(),
+            url: config.url.to_string(),
+            language: config.language.clone().unwrap_or_default(),
+            last_analyzed: None,
+            group: config
+                .normalized_group()
+                .unwrap_or_else(|_| config.group.clone()),
+            tags: config
+                .normalized_tags()
+                .unwrap_or_else(|_| config.tags.clone()),
+        }
+    }
 }
 
 /// Documentation list response
@@ -493,11 +1157,228 @@ pub struct AnalyzeRequest {
     /// Primary language (auto-detected if not provided)
     #[cfg_attr(feature = "openapi", schema(example = "rust"))]
     pub language: Option<String>,
+    /// Documentation categories to generate (all four if not provided)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["reference", "how-to"])))]
+    pub docs: Option<Vec<String>>,
+    /// Model to use for this request instead of the server default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = "llama3:70b"))]
+    pub model: Option<String>,
+    /// Fallback models to use for this request instead of the server default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["codellama"])))]
+    pub fallback_models: Option<Vec<String>>,
+}
+
+/// Call graph request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CallGraphRequest {
+    /// Local path of the repository to analyze
+    #[cfg_attr(feature = "openapi", schema(example = "/srv/repos/xze"))]
+    pub path: String,
+    /// Primary language (auto-detected if not provided)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "rust"))]
+    pub language: Option<String>,
+    /// Function whose callers and callees should be returned
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "generate_all"))]
+    pub function: Option<String>,
+}
+
+/// Call graph response
+///
+/// Name resolution is heuristic: functions are matched by bare name only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CallGraphResponse {
+    /// Function name to the names of functions it calls
+    pub call_graph: HashMap<String, Vec<String>>,
+    /// Functions calling the requested function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callers: Option<Vec<String>>,
+    /// Functions called by the requested function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callees: Option<Vec<String>>,
+    /// Problems found during analysis, such as skipped files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalysisWarningInfo>,
+}
+
+/// Non-fatal analysis warning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AnalysisWarningInfo {
+    /// Severity, `info` or `warning`
+    pub severity: String,
+    /// Warning kind, such as `unreadable-file`
+    pub kind: String,
+    /// File the warning is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl AnalysisWarningInfo {
+    /// Convert a warning, rewriting its path and message with `redactor`
+    pub fn redacted(warning: &AnalysisWarning, redactor: &PathRedactor) -> Self {
+        Self {
+            path: warning
+                .path
+                .as_deref()
+                .map(|path| redactor.redact(path).display().to_string()),
+            message: redactor.redact_text(&warning.message),
+            ..Self::from(warning)
+        }
+    }
+}
+
+impl From<&AnalysisWarning> for AnalysisWarningInfo {
+    fn from(warning: &AnalysisWarning) -> Self {
+        Self {
+            severity: warning.severity.to_string(),
+            kind: warning.kind.to_string(),
+            path: warning.path.as_ref().map(|path| path.display().to_string()),
+            message: warning.message.clone(),
+        }
+    }
+}
+
+/// Repository annotations response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AnnotationsResponse {
+    /// Repository directory name
+    pub repository: String,
+    /// Number of markers of each kind, before filtering
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub counts: AnnotationCounts,
+    /// Markers in file order
+    pub annotations: Vec<AnnotationInfo>,
+}
+
+/// TODO, FIXME or deprecation marker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AnnotationInfo {
+    /// Marker kind: `todo`, `fixme` or `deprecated`
+    pub kind: String,
+    /// Text after the marker, or the deprecation note
+    pub text: String,
+    /// File containing the marker
+    pub path: String,
+    /// One-based line number
+    pub line: usize,
+    /// Name of the deprecated item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+}
+
+impl AnnotationInfo {
+    /// Convert a marker, rewriting its path with `redactor`
+    pub fn redacted(annotation: &CodeAnnotation, redactor: &PathRedactor) -> Self {
+        Self {
+            path: redactor.redact(&annotation.path).display().to_string(),
+            ..Self::from(annotation)
+        }
+    }
+}
+
+impl From<&CodeAnnotation> for AnnotationInfo {
+    fn from(annotation: &CodeAnnotation) -> Self {
+        Self {
+            kind: annotation.kind.to_string(),
+            text: annotation.text.clone(),
+            path: annotation.path.display().to_string(),
+            line: annotation.line,
+            item: annotation.item.clone(),
+        }
+    }
+}
+
+/// Differences between two versions of a document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DocumentationDiffResponse {
+    /// Documentation ID
+    pub id: String,
+    /// Full hash of the older version
+    pub from: String,
+    /// Full hash of the newer version
+    pub to: String,
+    /// Sections that differ
+    pub sections: Vec<SectionChangeInfo>,
+    /// Word count of the newer version minus the older
+    pub word_count_delta: i64,
+    /// Confidence of the newer version minus the older, when both were
+    /// scored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_delta: Option<f32>,
+    /// Unified diff of the content
+    pub unified_diff: String,
+}
+
+impl DocumentationDiffResponse {
+    fn new(id: String, diff: DocumentDiff) -> Self {
+        Self {
+            id,
+            from: diff.from,
+            to: diff.to,
+            sections: diff.sections.iter().map(Into::into).collect(),
+            word_count_delta: diff.word_count_delta,
+            confidence_delta: diff.confidence_delta,
+            unified_diff: diff.unified_diff,
+        }
+    }
+}
+
+/// A section that differs between two document versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SectionChangeInfo {
+    /// Section heading; omitted for the text before the first heading
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// `added`, `removed` or `changed`
+    pub change: String,
+}
+
+impl From<&SectionChange> for SectionChangeInfo {
+    fn from(change: &SectionChange) -> Self {
+        let kind = match change.kind {
+            SectionChangeKind::Added => "added",
+            SectionChangeKind::Removed => "removed",
+            SectionChangeKind::Changed => "changed",
+        };
+        Self {
+            heading: change.heading.clone(),
+            change: kind.to_string(),
+        }
+    }
+}
+
+/// Analysis cache warmer status response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WarmerStatusResponse {
+    /// Whether the background warmer is running
+    pub enabled: bool,
+    /// Seconds between warming passes
+    #[cfg_attr(feature = "openapi", schema(example = 3600))]
+    pub interval_secs: u64,
+    /// Cache state of each configured repository
+    pub repositories: Vec<AnalysisCacheStatus>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use xze_core::documentation::{
+        DiátaxisValidator, Document, DocumentationGenerator, OutputConfig, ValidatorConfig,
+    };
 
     #[test]
     fn test_health_response_serialization() {
@@ -538,17 +1419,205 @@ mod tests {
         assert_eq!(request.language, Some("rust".to_string()));
     }
 
+    #[test]
+    fn test_analyze_request_docs_selector() {
+        let json =
+            r#"{"repository_url":"https://github.com/test/repo","docs":["reference","how-to"]}"#;
+        let request: AnalyzeRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.docs,
+            Some(vec!["reference".to_string(), "how-to".to_string()])
+        );
+
+        let json = r#"{"repository_url":"https://github.com/test/repo"}"#;
+        let request: AnalyzeRequest = serde_json::from_str(json).unwrap();
+        assert!(request.docs.is_none());
+    }
+
+    #[test]
+    fn test_analyze_request_model_overrides() {
+        let json = r#"{"repository_url":"https://github.com/test/repo","model":"llama3:70b","fallback_models":["codellama"]}"#;
+        let request: AnalyzeRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.model.as_deref(), Some("llama3:70b"));
+        assert_eq!(request.fallback_models, Some(vec!["codellama".to_string()]));
+
+        let json = r#"{"repository_url":"https://github.com/test/repo"}"#;
+        let request: AnalyzeRequest = serde_json::from_str(json).unwrap();
+        assert!(request.model.is_none());
+        assert!(request.fallback_models.is_none());
+    }
+
+    #[test]
+    fn test_model_error_response_status() {
+        let response = model_error_response(XzeError::validation("Models not installed: x"));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = model_error_response(XzeError::network("connection refused"));
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test]
+    fn test_call_graph_request_deserialization() {
+        let json = r#"{"path":"/tmp/repo","function":"run"}"#;
+        let request: CallGraphRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.path, "/tmp/repo");
+        assert!(request.language.is_none());
+        assert_eq!(request.function, Some("run".to_string()));
+    }
+
+    #[test]
+    fn test_call_graph_response_omits_missing_function() {
+        let response = CallGraphResponse {
+            call_graph: HashMap::from([("run".to_string(), vec!["load".to_string()])]),
+            callers: None,
+            callees: None,
+            warnings: vec![],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"run\":[\"load\"]"));
+        assert!(!json.contains("callers"));
+        assert!(!json.contains("warnings"));
+    }
+
+    #[test]
+    fn test_analysis_warning_info_from_core_warning() {
+        let warning = AnalysisWarning {
+            severity: xze_core::repository::WarningSeverity::Warning,
+            kind: xze_core::repository::WarningKind::UnreadableFile,
+            path: Some(std::path::PathBuf::from("src/lib.rs")),
+            message: "Skipped unreadable file".to_string(),
+        };
+
+        let info = AnalysisWarningInfo::from(&warning);
+        assert_eq!(info.severity, "warning");
+        assert_eq!(info.kind, "unreadable-file");
+        assert_eq!(info.path.as_deref(), Some("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_annotation_info_from_annotation() {
+        let annotation = CodeAnnotation {
+            kind: AnnotationKind::Deprecated,
+            text: "use connect".to_string(),
+            path: std::path::PathBuf::from("src/client.rs"),
+            line: 12,
+            item: Some("dial".to_string()),
+        };
+
+        let json = serde_json::to_value(AnnotationInfo::from(&annotation)).unwrap();
+        assert_eq!(json["kind"], "deprecated");
+        assert_eq!(json["path"], "src/client.rs");
+        assert_eq!(json["item"], "dial");
+    }
+
+    #[test]
+    fn test_documentation_diff_response() {
+        use xze_core::documentation::{Document, DocumentVersionStore};
+
+        let document = |content: &str| {
+            Document::new(
+                DiátaxisCategory::Reference,
+                "API".to_string(),
+                content.to_string(),
+                std::path::PathBuf::from("reference/api.md"),
+            )
+        };
+        let mut store = DocumentVersionStore::new();
+        let from = store.record("api", document("# API\n\n## load\n\nLoads.\n"));
+        let to = store.record("api", document("# API\n\n## save\n\nSaves.\n"));
+
+        let diff = store.diff("api", &from[..7], &to[..7]).unwrap();
+        let json =
+            serde_json::to_value(DocumentationDiffResponse::new("api".to_string(), diff)).unwrap();
+        assert_eq!(json["from"], from);
+        assert_eq!(json["sections"][0]["heading"], "save");
+        assert_eq!(json["sections"][0]["change"], "added");
+        assert_eq!(json["sections"][1]["change"], "removed");
+        assert!(json.get("confidence_delta").is_none());
+        assert!(json["unified_diff"].as_str().unwrap().contains("+## save"));
+    }
+
+    #[test]
+    fn test_redacted_infos_hide_repository_root() {
+        let redactor = PathRedactor::new(
+            std::path::Path::new("/srv/repos/xze"),
+            xze_core::repository::PathRedaction::Relative,
+        );
+        let warning = AnalysisWarning {
+            severity: xze_core::repository::WarningSeverity::Warning,
+            kind: xze_core::repository::WarningKind::UnreadableFile,
+            path: Some(std::path::PathBuf::from("/srv/repos/xze/src/lib.rs")),
+            message: "Skipped /srv/repos/xze/src/lib.rs".to_string(),
+        };
+        let annotation = CodeAnnotation {
+            kind: AnnotationKind::Todo,
+            text: "split".to_string(),
+            path: std::path::PathBuf::from("/srv/repos/xze/src/main.rs"),
+            line: 3,
+            item: None,
+        };
+
+        let info = AnalysisWarningInfo::redacted(&warning, &redactor);
+        assert_eq!(info.path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(info.message, "Skipped ./src/lib.rs");
+        assert_eq!(
+            AnnotationInfo::redacted(&annotation, &redactor).path,
+            "src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_configured_repository_and_kind_lookup() {
+        let repositories = vec![
+            std::path::PathBuf::from("/srv/repos/xze"),
+            std::path::PathBuf::from("/srv/repos/widgets"),
+        ];
+
+        assert_eq!(
+            configured_repository(&repositories, "widgets"),
+            Some(std::path::PathBuf::from("/srv/repos/widgets"))
+        );
+        assert_eq!(configured_repository(&repositories, "repos"), None);
+        assert_eq!(parse_annotation_kind("FIXME"), Some(AnnotationKind::Fixme));
+        assert_eq!(parse_annotation_kind("note"), None);
+    }
+
+    #[test]
+    fn test_warmer_status_response_serialization() {
+        let response = WarmerStatusResponse {
+            enabled: true,
+            interval_secs: 600,
+            repositories: vec![AnalysisCacheStatus {
+                repository: "/srv/repos/xze".to_string(),
+                commit: None,
+                last_warmed: None,
+            }],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"interval_secs\":600"));
+        assert!(json.contains("\"repository\":\"/srv/repos/xze\""));
+        assert!(json.contains("\"last_warmed\":null"));
+    }
+
     #[test]
     fn test_analyze_response_serialization() {
         let response = AnalyzeResponse {
             job_id: "job-123".to_string(),
             status: "queued".to_string(),
             message: "Analysis queued".to_string(),
+            model: "llama3:70b".to_string(),
+            fallback_models: vec!["codellama".to_string()],
+            generation: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("job-123"));
         assert!(json.contains("queued"));
+        assert!(json.contains("\"model\":\"llama3:70b\""));
+        assert!(json.contains("\"fallback_models\":[\"codellama\"]"));
+        assert!(!json.contains("generation"));
     }
 
     #[test]
@@ -559,12 +1628,63 @@ mod tests {
             url: "https://github.com/test/repo".to_string(),
             language: "rust".to_string(),
             last_analyzed: None,
+            group: None,
+            tags: vec![],
         };
 
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("repo-1"));
         assert!(json.contains("test-repo"));
         assert!(json.contains("rust"));
+        assert!(!json.contains("group"));
+    }
+
+    #[test]
+    fn test_list_configured_repositories_filters_and_pages() {
+        let repo = |name: &str, group: Option<&str>, tags: &[&str]| -> RepositoryConfig {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "url": format!("https://github.com/example/{}", name),
+                "language": "rust",
+                "watch_branches": [],
+                "local_path": null,
+                "credentials": null,
+                "group": group,
+                "tags": tags,
+            }))
+            .unwrap()
+        };
+        let repositories = vec![
+            repo("billing", Some("Payments"), &["critical", "Critical"]),
+            repo("ledger", Some("payments"), &[]),
+            repo("search", None, &["critical"]),
+        ];
+
+        let all = list_configured_repositories(&repositories, &RepositoryFilter::default(), 1, 2);
+        assert_eq!(all.total, 3);
+        assert_eq!(all.repositories.len(), 2);
+        assert_eq!(all.repositories[0].group.as_deref(), Some("payments"));
+        assert_eq!(all.repositories[0].tags, vec!["critical"]);
+
+        let filter = RepositoryFilter {
+            group: Some("payments".to_string()),
+            tag: None,
+        };
+        let payments = list_configured_repositories(&repositories, &filter, 2, 1);
+        assert_eq!(payments.total, 2);
+        assert_eq!(payments.repositories[0].name, "ledger");
+
+        let filter = RepositoryFilter {
+            group: None,
+            tag: Some("critical".to_string()),
+        };
+        let critical = list_configured_repositories(&repositories, &filter, 1, 10);
+        let names: Vec<&str> = critical
+            .repositories
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["billing", "search"]);
     }
 
     #[test]
@@ -597,4 +1717,213 @@ mod tests {
         assert_eq!(doc.category, "tutorial");
         assert_eq!(doc.repository_id, "repo-1");
     }
+
+    #[test]
+    fn test_local_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured: RepositoryConfig = serde_json::from_value(serde_json::json!({
+            "name": "billing",
+            "url": "https://github.com/example/billing",
+            "language": "rust",
+            "watch_branches": [],
+            "local_path": dir.path(),
+            "credentials": null,
+        }))
+        .unwrap();
+        let repositories = vec![configured];
+        let checkout = Some(dir.path().to_path_buf());
+
+        assert_eq!(
+            local_checkout(&repositories, "https://github.com/example/billing/"),
+            checkout
+        );
+        assert_eq!(
+            local_checkout(&[], &format!("file://{}", dir.path().display())),
+            checkout
+        );
+        assert_eq!(
+            local_checkout(&[], &dir.path().display().to_string()),
+            checkout
+        );
+        assert_eq!(
+            local_checkout(&repositories, "https://github.com/example/ledger"),
+            None
+        );
+        assert_eq!(local_checkout(&[], "/no/such/checkout"), None);
+    }
+
+    /// Writes one fixed document per category, failing for `failing`
+    struct StubGenerator {
+        failing: Option<DiátaxisCategory>,
+    }
+
+    impl StubGenerator {
+        fn document(
+            &self,
+            category: DiátaxisCategory,
+            repo: &Repository,
+        ) -> xze_core::Result<Document> {
+            if self.failing.as_ref() == Some(&category) {
+                return Err(XzeError::ai("model timed out"));
+            }
+            Ok(Document::new(
+                category.clone(),
+                format!("{} {}", repo.id, category),
+                format!("Documents {}.\n", repo.id),
+                PathBuf::from(format!("{}/index.md", category.to_string().to_lowercase())),
+            ))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DocumentationGenerator for StubGenerator {
+        async fn generate_reference(&self, repo: &Repository) -> xze_core::Result<Document> {
+            self.document(DiátaxisCategory::Reference, repo)
+        }
+
+        async fn generate_howto(
+            &self,
+            repo: &Repository,
+            _task: &str,
+        ) -> xze_core::Result<Document> {
+            self.document(DiátaxisCategory::HowTo, repo)
+        }
+
+        async fn generate_tutorial(
+            &self,
+            repo: &Repository,
+            _topic: &str,
+        ) -> xze_core::Result<Document> {
+            self.document(DiátaxisCategory::Tutorial, repo)
+        }
+
+        async fn generate_explanation(
+            &self,
+            repo: &Repository,
+            _concept: &str,
+        ) -> xze_core::Result<Document> {
+            self.document(DiátaxisCategory::Explanation, repo)
+        }
+    }
+
+    fn stub_documentation_state(
+        config: crate::ServerConfig,
+        failing: Option<DiátaxisCategory>,
+    ) -> AppState {
+        let ai_service = Arc::new(AIAnalysisService::new(
+            config.ollama_url.clone(),
+            config.models.clone(),
+        ));
+        let service = DocumentationService::new(
+            ai_service,
+            Box::new(StubGenerator { failing }),
+            Box::new(DiátaxisValidator::new(ValidatorConfig::default())),
+        );
+        let pool = sqlx::PgPool::connect_lazy("postgresql://localhost/xze_test").unwrap();
+        AppState::from_pool(config, pool).with_documentation(Arc::new(service))
+    }
+
+    fn local_repository() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "/// Adds two numbers\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_analyze_repository_writes_selected_docs_to_sink() {
+        let dir = local_repository();
+        let request = AnalyzeRequest {
+            repository_url: format!("file://{}", dir.path().display()),
+            branch: None,
+            language: None,
+            docs: Some(vec!["reference".to_string(), "tutorial".to_string()]),
+            model: None,
+            fallback_models: None,
+        };
+
+        let output = tempfile::tempdir().unwrap();
+        let config = crate::ServerConfig {
+            output: OutputConfig {
+                sink: OutputSinkKind::Filesystem,
+                output_dir: output.path().to_path_buf(),
+                ..OutputConfig::default()
+            },
+            ..crate::ServerConfig::default()
+        };
+        let state = stub_documentation_state(config, None);
+        let response = analyze_repository(State(state), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: AnalyzeResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.status, "completed");
+
+        let report = response.generation.unwrap();
+        let categories: Vec<DiátaxisCategory> = report
+            .documents
+            .iter()
+            .map(|d| d.category.clone())
+            .collect();
+        assert_eq!(
+            categories,
+            vec![DiátaxisCategory::Reference, DiátaxisCategory::Tutorial]
+        );
+        assert!(report.failures.is_empty());
+
+        let written = std::fs::read_to_string(output.path().join("tutorial/index.md")).unwrap();
+        assert_eq!(written, report.documents[1].content);
+        assert!(output.path().join("reference/index.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_repository_status_follows_generation_outcome() {
+        let dir = local_repository();
+        let output = tempfile::tempdir().unwrap();
+        let config = crate::ServerConfig {
+            output: OutputConfig {
+                output_dir: output.path().to_path_buf(),
+                ..OutputConfig::default()
+            },
+            ..crate::ServerConfig::default()
+        };
+        let analyze = |docs: &[&str]| {
+            let state = stub_documentation_state(config.clone(), Some(DiátaxisCategory::Tutorial));
+            let request = AnalyzeRequest {
+                repository_url: dir.path().display().to_string(),
+                branch: None,
+                language: None,
+                docs: Some(docs.iter().map(|d| d.to_string()).collect()),
+                model: None,
+                fallback_models: None,
+            };
+            async move {
+                let response = analyze_repository(State(state), Json(request))
+                    .await
+                    .into_response();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let response: AnalyzeResponse = serde_json::from_slice(&body).unwrap();
+                (status, response.status)
+            }
+        };
+
+        assert_eq!(
+            analyze(&["reference", "tutorial"]).await,
+            (StatusCode::MULTI_STATUS, "partial".to_string())
+        );
+        assert_eq!(
+            analyze(&["tutorial"]).await,
+            (StatusCode::BAD_GATEWAY, "failed".to_string())
+        );
+    }
 }
@@ -9,6 +9,7 @@ pub mod openapi;
 
 use axum::{routing::get, routing::post, Router};
 
+use crate::api::EndpointToggles;
 use crate::handlers::AppState;
 
 #[cfg(feature = "openapi")]
@@ -30,8 +31,11 @@ use utoipa_swagger_ui::SwaggerUi;
 /// - GET /repositories - List repositories
 /// - GET /repositories/:id - Get repository by ID
 /// - POST /repositories/:id/analyze - Analyze repository by ID
+/// - GET /repositories/:id/annotations - TODO, FIXME and deprecation markers
+/// - POST /call-graph - Call graph of a local repository
 /// - GET /documentation - List documentation
 /// - GET /documentation/:id - Get documentation by ID
+/// - GET /documentation/:id/diff - Compare two versions of a document
 /// - GET /search - Search documentation (from handlers module)
 ///
 /// # Examples
@@ -45,35 +49,48 @@ use utoipa_swagger_ui::SwaggerUi;
 /// // let router = create_v1_routes().with_state(state);
 /// ```
 pub fn create_v1_routes() -> Router<AppState> {
-    #[cfg(feature = "openapi")]
+    create_v1_routes_with(&EndpointToggles::default())
+}
+
+/// API v1 routes with only the enabled endpoint groups mounted
+///
+/// Disabled groups are left out of the router, so their paths return 404:
+/// - analyze: POST /analyze, POST /repositories/:id/analyze,
+///   GET /repositories/:id/annotations and POST /call-graph
+/// - search: GET /search
+/// - admin: GET /admin/warmer
+pub fn create_v1_routes_with(endpoints: &EndpointToggles) -> Router<AppState> {
     let mut router = Router::new()
         .route("/health", get(handlers::health_check))
         .route("/version", get(handlers::get_version))
-        .route("/analyze", post(handlers::analyze_repository))
         .route("/repositories", get(handlers::list_repositories))
         .route("/repositories/:id", get(handlers::get_repository))
-        .route(
-            "/repositories/:id/analyze",
-            post(handlers::analyze_repository_by_id),
-        )
         .route("/documentation", get(handlers::list_documentation))
         .route("/documentation/:id", get(handlers::get_documentation))
-        .route("/search", get(crate::handlers::handle_search));
-
-    #[cfg(not(feature = "openapi"))]
-    let router = Router::new()
-        .route("/health", get(handlers::health_check))
-        .route("/version", get(handlers::get_version))
-        .route("/analyze", post(handlers::analyze_repository))
-        .route("/repositories", get(handlers::list_repositories))
-        .route("/repositories/:id", get(handlers::get_repository))
         .route(
-            "/repositories/:id/analyze",
-            post(handlers::analyze_repository_by_id),
-        )
-        .route("/documentation", get(handlers::list_documentation))
-        .route("/documentation/:id", get(handlers::get_documentation))
-        .route("/search", get(crate::handlers::handle_search));
+            "/documentation/:id/diff",
+            get(handlers::get_documentation_diff),
+        );
+
+    if endpoints.enable_analyze {
+        router = router
+            .route("/analyze", post(handlers::analyze_repository))
+            .route(
+                "/repositories/:id/analyze",
+                post(handlers::analyze_repository_by_id),
+            )
+            .route(
+                "/repositories/:id/annotations",
+                get(handlers::get_repository_annotations),
+            )
+            .route("/call-graph", post(handlers::get_call_graph));
+    }
+    if endpoints.enable_admin {
+        router = router.route("/admin/warmer", get(handlers::get_warmer_status));
+    }
+    if endpoints.enable_search {
+        router = router.route("/search", get(crate::handlers::handle_search));
+    }
 
     // Add Swagger UI if openapi feature is enabled
     #[cfg(feature = "openapi")]
@@ -54,8 +54,12 @@ use super::handlers::*;
         super::handlers::list_repositories,
         super::handlers::get_repository,
         super::handlers::analyze_repository_by_id,
+        super::handlers::get_call_graph,
+        super::handlers::get_repository_annotations,
+        super::handlers::get_warmer_status,
         super::handlers::list_documentation,
         super::handlers::get_documentation,
+        super::handlers::get_documentation_diff,
         crate::handlers::handle_search,
     ),
     components(
@@ -65,10 +69,19 @@ use super::handlers::*;
             BuildInfo,
             AnalyzeResponse,
             AnalyzeRequest,
+            CallGraphRequest,
+            CallGraphResponse,
+            AnalysisWarningInfo,
+            AnnotationsResponse,
+            AnnotationInfo,
+            WarmerStatusResponse,
+            crate::cache::AnalysisCacheStatus,
             RepositoryListResponse,
             RepositoryInfo,
             DocumentationListResponse,
             DocumentationInfo,
+            DocumentationDiffResponse,
+            SectionChangeInfo,
             crate::handlers::SearchQueryParams,
             crate::handlers::SearchResponse,
             crate::handlers::SearchResultItem,
@@ -83,6 +96,7 @@ use super::handlers::*;
         (name = "repositories", description = "Repository management endpoints"),
         (name = "documentation", description = "Documentation retrieval endpoints"),
         (name = "search", description = "Semantic search endpoints"),
+        (name = "admin", description = "Server administration endpoints"),
     ),
     modifiers(&SecurityAddon)
 )]
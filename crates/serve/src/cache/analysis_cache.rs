@@ -0,0 +1,183 @@
+//! Repository analysis caching
+//!
+//! Analyzing a repository walks and parses every source file, which is too
+//! slow to repeat on each request. The cache keeps the latest
+//! [`CodeStructure`] per repository together with the commit it was built
+//! from; lookups only hit when the caller's commit matches, so a moved HEAD
+//! is never answered with a stale structure.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use xze_core::repository::CodeStructure;
+
+/// Analysis of a repository at a specific commit
+#[derive(Debug, Clone)]
+pub struct CachedAnalysis {
+    /// Commit the structure was built from
+    pub commit: String,
+    /// Analyzed code structure
+    pub structure: Arc<CodeStructure>,
+    /// When the analysis was stored
+    pub warmed_at: DateTime<Utc>,
+}
+
+/// Cache state of a single repository, as reported by the admin endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AnalysisCacheStatus {
+    /// Repository path
+    #[cfg_attr(feature = "openapi", schema(example = "/srv/repos/xze"))]
+    pub repository: String,
+    /// Commit of the cached analysis
+    pub commit: Option<String>,
+    /// When the cached analysis was stored
+    pub last_warmed: Option<DateTime<Utc>>,
+}
+
+/// In-memory cache of repository analyses keyed by repository path
+#[derive(Debug, Default)]
+pub struct AnalysisCache {
+    entries: RwLock<HashMap<PathBuf, CachedAnalysis>>,
+}
+
+/// Analysis cache shared between handlers and the warmer
+pub type SharedAnalysisCache = Arc<AnalysisCache>;
+
+impl AnalysisCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cached structure for `path` if it was built from `commit`
+    pub async fn get(&self, path: &Path, commit: &str) -> Option<Arc<CodeStructure>> {
+        self.entries
+            .read()
+            .await
+            .get(&cache_key(path))
+            .filter(|entry| entry.commit == commit)
+            .map(|entry| entry.structure.clone())
+    }
+
+    /// Get the commit of the cached analysis for `path`
+    pub async fn commit_of(&self, path: &Path) -> Option<String> {
+        self.entries
+            .read()
+            .await
+            .get(&cache_key(path))
+            .map(|entry| entry.commit.clone())
+    }
+
+    /// Store the analysis of `path` at `commit`, replacing any older entry
+    pub async fn insert(&self, path: &Path, commit: String, structure: Arc<CodeStructure>) {
+        self.entries.write().await.insert(
+            cache_key(path),
+            CachedAnalysis {
+                commit,
+                structure,
+                warmed_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Get the cache state of each path, in the order given
+    ///
+    /// Paths without a cached analysis are reported with empty fields.
+    pub async fn status(&self, paths: &[PathBuf]) -> Vec<AnalysisCacheStatus> {
+        let entries = self.entries.read().await;
+        paths
+            .iter()
+            .map(|path| {
+                let entry = entries.get(&cache_key(path));
+                AnalysisCacheStatus {
+                    repository: path.display().to_string(),
+                    commit: entry.map(|e| e.commit.clone()),
+                    last_warmed: entry.map(|e| e.warmed_at),
+                }
+            })
+            .collect()
+    }
+
+    /// Number of cached repositories
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Whether the cache is empty
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+/// Canonical form of a repository path so `./repo` and `/abs/repo` share an entry
+fn cache_key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_get_requires_matching_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::new();
+
+        cache
+            .insert(
+                temp_dir.path(),
+                "abc".to_string(),
+                Arc::new(CodeStructure::new()),
+            )
+            .await;
+
+        assert!(cache.get(temp_dir.path(), "abc").await.is_some());
+        assert!(cache.get(temp_dir.path(), "def").await.is_none());
+        assert_eq!(
+            cache.commit_of(temp_dir.path()).await.as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_equivalent_paths_share_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::new();
+        let dotted = temp_dir.path().join(".");
+
+        cache
+            .insert(&dotted, "abc".to_string(), Arc::new(CodeStructure::new()))
+            .await;
+
+        assert!(cache.get(temp_dir.path(), "abc").await.is_some());
+        assert_eq!(cache.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_missing_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::new();
+        cache
+            .insert(
+                temp_dir.path(),
+                "abc".to_string(),
+                Arc::new(CodeStructure::new()),
+            )
+            .await;
+
+        let missing = temp_dir.path().join("missing");
+        let status = cache
+            .status(&[temp_dir.path().to_path_buf(), missing])
+            .await;
+
+        assert_eq!(status[0].commit.as_deref(), Some("abc"));
+        assert!(status[0].last_warmed.is_some());
+        assert_eq!(status[1].commit, None);
+        assert_eq!(status[1].last_warmed, None);
+    }
+}
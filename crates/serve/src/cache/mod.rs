@@ -3,9 +3,13 @@
 //! Provides caching implementations for search results and other data
 //! to improve performance and reduce database load.
 
+pub mod analysis_cache;
 pub mod search_cache;
+pub mod warmer;
 
+pub use analysis_cache::{AnalysisCache, AnalysisCacheStatus, CachedAnalysis, SharedAnalysisCache};
 pub use search_cache::{
-    create_shared_cache, CachedSearchResponse, SearchCache, SearchCacheConfig, SearchCacheKey,
-    SharedSearchCache,
+    create_shared_cache, CacheScope, CachedSearchResponse, SearchCache, SearchCacheConfig,
+    SearchCacheKey, SearchCacheStats, SharedSearchCache,
 };
+pub use warmer::{analyze_cached, AnalysisWarmer, WarmReport, WarmerConfig};
@@ -2,12 +2,26 @@
 //!
 //! Provides in-memory caching for search results to improve performance
 //! and reduce database load.
+//!
+//! The cache is bounded by entry count with least-recently-used eviction.
+//! Entries expire after the configured TTL unless given their own with
+//! [`CachedSearchResponse::with_ttl`]. Each entry records the
+//! [`CacheScope`] of its query, so when a document changes only the entries
+//! whose filters could have matched it are dropped; see
+//! [`SearchCache::watch_index`].
 
+use crate::search::types::SearchFilters;
 use moka::future::Cache;
+use moka::notification::RemovalCause;
+use moka::policy::EvictionPolicy;
+use moka::Expiry;
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use xze_core::search::{IndexChange, SearchIndex};
 
 /// Cache key for search requests
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -102,6 +116,65 @@ impl SearchCacheKey {
     }
 }
 
+/// Documents a cached search could have matched
+///
+/// A `None` filter matches every value. Comparisons ignore ASCII case.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheScope {
+    /// Categories the query was restricted to
+    pub categories: Option<Vec<String>>,
+    /// Repositories the query was restricted to
+    pub repositories: Option<Vec<String>>,
+}
+
+impl CacheScope {
+    /// Creates the scope of a query with the given filters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_serve::cache::search_cache::CacheScope;
+    /// use xze_serve::search::types::SearchFilters;
+    ///
+    /// let filters = SearchFilters {
+    ///     categories: Some(vec!["tutorial".to_string()]),
+    ///     ..Default::default()
+    /// };
+    /// let scope = CacheScope::from_filters(Some(&filters));
+    /// assert_eq!(scope.categories, Some(vec!["tutorial".to_string()]));
+    /// assert_eq!(scope.repositories, None);
+    /// ```
+    pub fn from_filters(filters: Option<&SearchFilters>) -> Self {
+        Self {
+            categories: filters.and_then(|f| f.categories.clone()),
+            repositories: filters.and_then(|f| f.repositories.clone()),
+        }
+    }
+
+    /// Checks whether a query with this scope could have matched the
+    /// changed document
+    ///
+    /// Errs on the side of eviction: a change without category or
+    /// repository information matches every scope on that dimension.
+    pub fn may_match(&self, change: &IndexChange) -> bool {
+        fn overlaps<'a>(
+            filter: &Option<Vec<String>>,
+            values: impl ExactSizeIterator<Item = &'a String> + Clone,
+        ) -> bool {
+            match filter {
+                None => true,
+                Some(_) if values.len() == 0 => true,
+                Some(filter) => filter
+                    .iter()
+                    .any(|f| values.clone().any(|v| f.eq_ignore_ascii_case(v))),
+            }
+        }
+
+        overlaps(&self.categories, change.categories.iter())
+            && overlaps(&self.repositories, change.repositories.iter())
+    }
+}
+
 /// Cached search response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedSearchResponse {
@@ -113,6 +186,12 @@ pub struct CachedSearchResponse {
     pub total_results: usize,
     /// Cache timestamp
     pub cached_at: chrono::DateTime<chrono::Utc>,
+    /// Documents the query could have matched
+    #[serde(default)]
+    pub scope: CacheScope,
+    /// Time to live overriding the cache default, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<u64>,
 }
 
 impl CachedSearchResponse {
@@ -147,9 +226,34 @@ impl CachedSearchResponse {
             results,
             total_results,
             cached_at: chrono::Utc::now(),
+            scope: CacheScope::default(),
+            ttl_seconds: None,
         }
     }
 
+    /// Sets the documents the query could have matched
+    pub fn with_scope(mut self, scope: CacheScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Sets a time to live for this entry, overriding the cache default
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use xze_serve::cache::search_cache::CachedSearchResponse;
+    ///
+    /// let response = CachedSearchResponse::new("test".to_string(), "[]".to_string(), 0)
+    ///     .with_ttl(Duration::from_secs(60));
+    /// assert_eq!(response.ttl_seconds, Some(60));
+    /// ```
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl_seconds = Some(ttl.as_secs());
+        self
+    }
+
     /// Checks if the cached response is still fresh
     ///
     /// # Arguments
@@ -184,7 +288,7 @@ impl CachedSearchResponse {
 pub struct SearchCacheConfig {
     /// Maximum number of entries in cache
     pub max_capacity: u64,
-    /// Time to live for cache entries in seconds
+    /// Default time to live for cache entries in seconds
     pub ttl_seconds: u64,
     /// Time to idle for cache entries in seconds
     pub tti_seconds: u64,
@@ -275,10 +379,93 @@ impl SearchCacheConfig {
     }
 }
 
+/// Hit, miss and removal counts of a search cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCacheStats {
+    /// Lookups that found a live entry
+    pub hits: u64,
+    /// Lookups that found nothing
+    pub misses: u64,
+    /// Entries evicted to stay within capacity
+    pub evictions: u64,
+    /// Entries dropped when their TTL or idle time passed
+    pub expirations: u64,
+    /// Entries removed by invalidation
+    pub invalidations: u64,
+    /// Entries currently cached
+    pub entries: u64,
+}
+
+impl SearchCacheStats {
+    /// Fraction of lookups that were hits, or `0.0` before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+/// Expires entries after their own TTL or the configured default
+struct EntryExpiry {
+    default_ttl: Duration,
+}
+
+impl EntryExpiry {
+    fn ttl(&self, value: &CachedSearchResponse) -> Duration {
+        value
+            .ttl_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+impl Expiry<SearchCacheKey, CachedSearchResponse> for EntryExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &SearchCacheKey,
+        value: &CachedSearchResponse,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(self.ttl(value))
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &SearchCacheKey,
+        value: &CachedSearchResponse,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(self.ttl(value))
+    }
+}
+
 /// Search cache implementation
 pub struct SearchCache {
     cache: Cache<SearchCacheKey, CachedSearchResponse>,
     config: SearchCacheConfig,
+    counters: Arc<CacheCounters>,
+}
+
+impl std::fmt::Debug for SearchCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchCache")
+            .field("config", &self.config)
+            .field("entries", &self.cache.entry_count())
+            .finish()
+    }
 }
 
 impl SearchCache {
@@ -301,13 +488,32 @@ impl SearchCache {
     /// let cache = SearchCache::new(config);
     /// ```
     pub fn new(config: SearchCacheConfig) -> Self {
+        let counters = Arc::new(CacheCounters::default());
+        let listener_counters = counters.clone();
+
         let cache = Cache::builder()
             .max_capacity(config.max_capacity)
-            .time_to_live(Duration::from_secs(config.ttl_seconds))
+            .eviction_policy(EvictionPolicy::lru())
+            .expire_after(EntryExpiry {
+                default_ttl: Duration::from_secs(config.ttl_seconds),
+            })
             .time_to_idle(Duration::from_secs(config.tti_seconds))
+            .eviction_listener(move |_key, _value, cause| {
+                let counter = match cause {
+                    RemovalCause::Size => &listener_counters.evictions,
+                    RemovalCause::Expired => &listener_counters.expirations,
+                    RemovalCause::Explicit => &listener_counters.invalidations,
+                    RemovalCause::Replaced => return,
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+            })
             .build();
 
-        Self { cache, config }
+        Self {
+            cache,
+            config,
+            counters,
+        }
     }
 
     /// Gets a cached response
@@ -333,7 +539,20 @@ impl SearchCache {
     /// # });
     /// ```
     pub async fn get(&self, key: &SearchCacheKey) -> Option<CachedSearchResponse> {
-        self.cache.get(key).await
+        let response = self.cache.get(key).await;
+        let counter = if response.is_some() {
+            &self.counters.hits
+        } else {
+            &self.counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        crate::search::SEARCH_METRICS.record_cache_operation(if response.is_some() {
+            "hit"
+        } else {
+            "miss"
+        });
+        response
     }
 
     /// Stores a response in cache
@@ -397,8 +616,108 @@ impl SearchCache {
         self.cache.run_pending_tasks().await;
     }
 
+    /// Invalidates the entries whose query could have matched a changed
+    /// document
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - Document added, replaced or removed from the index
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of entries invalidated
+    pub async fn invalidate_for_change(&self, change: &IndexChange) -> usize {
+        let stale: Vec<Arc<SearchCacheKey>> = self
+            .cache
+            .iter()
+            .filter(|(_, response)| response.scope.may_match(change))
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in &stale {
+            self.cache.invalidate(key.as_ref()).await;
+        }
+        self.cache.run_pending_tasks().await;
+
+        tracing::debug!(
+            "Invalidated {} cached searches after change to {}",
+            stale.len(),
+            change.path
+        );
+        stale.len()
+    }
+
+    /// Invalidates affected entries whenever the search index changes
+    ///
+    /// Spawns a task that applies [`invalidate_for_change`] to every
+    /// change published by `index`. If the task falls behind and changes
+    /// are lost, the whole cache is cleared. The task ends when the index
+    /// is dropped.
+    ///
+    /// [`invalidate_for_change`]: SearchCache::invalidate_for_change
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use xze_core::search::SearchIndex;
+    /// use xze_serve::cache::search_cache::{create_shared_cache, SearchCacheConfig};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let index = Arc::new(SearchIndex::new());
+    /// let cache = create_shared_cache(SearchCacheConfig::default());
+    /// let _watcher = cache.clone().watch_index(&index);
+    /// # });
+    /// ```
+    pub fn watch_index(self: Arc<Self>, index: &SearchIndex) -> tokio::task::JoinHandle<()> {
+        let mut changes = index.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match changes.recv().await {
+                    Ok(change) => {
+                        self.invalidate_for_change(&change).await;
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("Search cache missed {} index changes; clearing it", missed);
+                        self.invalidate_all().await;
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Gets hit, miss and removal counts
+    ///
+    /// Removals are counted when the cache performs its pending
+    /// maintenance, so they can trail the operation that caused them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xze_serve::cache::search_cache::{SearchCache, SearchCacheConfig, SearchCacheKey};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let cache = SearchCache::new(SearchCacheConfig::default());
+    /// cache.get(&SearchCacheKey::from_query("test".to_string())).await;
+    /// assert_eq!(cache.statistics().misses, 1);
+    /// # });
+    /// ```
+    pub fn statistics(&self) -> SearchCacheStats {
+        SearchCacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            expirations: self.counters.expirations.load(Ordering::Relaxed),
+            invalidations: self.counters.invalidations.load(Ordering::Relaxed),
+            entries: self.cache.entry_count(),
+        }
+    }
+
     /// Gets cache statistics
     ///
+    /// See [`statistics`](SearchCache::statistics) for hit and miss counts.
+    ///
     /// # Returns
     ///
     /// Returns the number of entries in the cache
@@ -658,4 +977,139 @@ mod tests {
         let cloned = config.clone();
         assert_eq!(config.max_capacity, cloned.max_capacity);
     }
+
+    fn scoped_response(categories: Option<&[&str]>) -> CachedSearchResponse {
+        CachedSearchResponse::new("test".to_string(), "[]".to_string(), 0).with_scope(CacheScope {
+            categories: categories.map(|c| c.iter().map(|s| s.to_string()).collect()),
+            repositories: None,
+        })
+    }
+
+    fn change(categories: &[&str], repositories: &[&str]) -> IndexChange {
+        IndexChange {
+            path: "docs/a.md".to_string(),
+            categories: categories.iter().map(|s| s.to_string()).collect(),
+            repositories: repositories.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_cache_scope_may_match() {
+        let any = CacheScope::default();
+        assert!(any.may_match(&change(&["tutorial"], &["xze"])));
+
+        let tutorials = CacheScope {
+            categories: Some(vec!["Tutorial".to_string()]),
+            repositories: Some(vec!["xze".to_string()]),
+        };
+        assert!(tutorials.may_match(&change(&["tutorial"], &["xze"])));
+        assert!(!tutorials.may_match(&change(&["reference"], &["xze"])));
+        assert!(!tutorials.may_match(&change(&["tutorial"], &["other"])));
+        // Unknown metadata is treated as a possible match
+        assert!(tutorials.may_match(&change(&[], &[])));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_for_change_is_targeted() {
+        let cache = SearchCache::new(SearchCacheConfig::default());
+        let unfiltered = SearchCacheKey::from_query("any".to_string());
+        let tutorial = SearchCacheKey::from_query("tutorial".to_string());
+        let reference = SearchCacheKey::from_query("reference".to_string());
+        cache.set(unfiltered.clone(), scoped_response(None)).await;
+        cache
+            .set(tutorial.clone(), scoped_response(Some(&["tutorial"])))
+            .await;
+        cache
+            .set(reference.clone(), scoped_response(Some(&["reference"])))
+            .await;
+
+        let invalidated = cache
+            .invalidate_for_change(&change(&["tutorial"], &[]))
+            .await;
+
+        assert_eq!(invalidated, 2);
+        assert!(cache.get(&unfiltered).await.is_none());
+        assert!(cache.get(&tutorial).await.is_none());
+        assert!(cache.get(&reference).await.is_some());
+        assert_eq!(cache.statistics().invalidations, 2);
+    }
+
+    #[tokio::test]
+    async fn test_statistics_count_hits_misses_and_evictions() {
+        let cache = SearchCache::new(SearchCacheConfig::new(1, 3600, 1800));
+        let first = SearchCacheKey::from_query("first".to_string());
+        let second = SearchCacheKey::from_query("second".to_string());
+
+        cache.set(first.clone(), scoped_response(None)).await;
+        cache.cache.run_pending_tasks().await;
+        assert!(cache.get(&first).await.is_some());
+        cache.set(second.clone(), scoped_response(None)).await;
+        cache.cache.run_pending_tasks().await;
+
+        let stats = cache.statistics();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.entries, 1);
+
+        cache
+            .get(&SearchCacheKey::from_query("none".to_string()))
+            .await;
+        assert_eq!(cache.statistics().misses, 1);
+        assert!((cache.statistics().hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_per_entry_ttl_overrides_default() {
+        let cache = SearchCache::new(SearchCacheConfig::default());
+        let short = SearchCacheKey::from_query("short".to_string());
+        let long = SearchCacheKey::from_query("long".to_string());
+        cache
+            .set(
+                short.clone(),
+                scoped_response(None).with_ttl(Duration::from_secs(1)),
+            )
+            .await;
+        cache.set(long.clone(), scoped_response(None)).await;
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        assert!(cache.get(&short).await.is_none());
+        assert!(cache.get(&long).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_watch_index_invalidates_on_upsert() {
+        use xze_core::kb::store::DocumentChunk;
+        use xze_core::search::IndexedDocument;
+
+        let index = SearchIndex::new();
+        let cache = create_shared_cache(SearchCacheConfig::default());
+        let watcher = cache.clone().watch_index(&index);
+
+        let key = SearchCacheKey::from_query("tutorial".to_string());
+        cache
+            .set(key.clone(), scoped_response(Some(&["tutorial"])))
+            .await;
+
+        index.upsert(IndexedDocument::new(
+            "docs/a.md",
+            vec![DocumentChunk::new(
+                "chunk_0".to_string(),
+                "content".to_string(),
+                vec![1.0],
+                serde_json::json!({ "category": "tutorial" }),
+            )],
+        ));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.get(&key).await.is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("cache entry was not invalidated");
+
+        drop(index);
+        watcher.await.unwrap();
+    }
 }
@@ -0,0 +1,232 @@
+//! Background analysis cache warming
+//!
+//! The warmer analyzes the configured repositories on startup and then on a
+//! fixed interval, so the first request for a repository does not pay for a
+//! full analysis. Repositories whose HEAD still matches the cached commit are
+//! skipped, mirroring the CLI's incremental auto mode.
+
+use super::analysis_cache::SharedAnalysisCache;
+use futures_util::stream::{self, StreamExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use xze_core::repository::analyzer::AnalyzerFactory;
+use xze_core::repository::{head_commit, CodeStructure};
+use xze_core::{Result, XzeError};
+
+/// Cache warmer configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmerConfig {
+    /// Run the warmer when the server starts
+    pub enabled: bool,
+    /// Seconds between warming passes
+    pub interval_secs: u64,
+    /// Maximum repositories analyzed at once
+    pub concurrency: usize,
+    /// Local repository paths to keep warm
+    pub repositories: Vec<PathBuf>,
+}
+
+impl Default for WarmerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 3600,
+            concurrency: 2,
+            repositories: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of a single warming pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarmReport {
+    /// Repositories analyzed in this pass
+    pub warmed: usize,
+    /// Repositories skipped because HEAD matched the cached commit
+    pub up_to_date: usize,
+    /// Repositories that could not be analyzed
+    pub failed: usize,
+}
+
+enum WarmOutcome {
+    Warmed,
+    UpToDate,
+}
+
+/// Periodically pre-analyzes repositories into the analysis cache
+pub struct AnalysisWarmer {
+    config: WarmerConfig,
+    cache: SharedAnalysisCache,
+}
+
+impl AnalysisWarmer {
+    /// Creates a warmer filling `cache`
+    pub fn new(config: WarmerConfig, cache: SharedAnalysisCache) -> Self {
+        Self { config, cache }
+    }
+
+    /// Warm every configured repository once
+    ///
+    /// Failures are logged and counted; they never abort the pass.
+    pub async fn warm_once(&self) -> WarmReport {
+        let outcomes: Vec<(PathBuf, Result<WarmOutcome>)> =
+            stream::iter(self.config.repositories.clone())
+                .map(|path| {
+                    let cache = self.cache.clone();
+                    async move {
+                        let outcome = warm_repository(&cache, &path).await;
+                        (path, outcome)
+                    }
+                })
+                .buffer_unordered(self.config.concurrency.max(1))
+                .collect()
+                .await;
+
+        let mut report = WarmReport::default();
+        for (path, outcome) in outcomes {
+            match outcome {
+                Ok(WarmOutcome::Warmed) => report.warmed += 1,
+                Ok(WarmOutcome::UpToDate) => report.up_to_date += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to warm analysis of {}: {}", path.display(), e);
+                    report.failed += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            "Analysis cache warmed: {} analyzed, {} up to date, {} failed",
+            report.warmed,
+            report.up_to_date,
+            report.failed
+        );
+        report
+    }
+
+    /// Run a pass now and then every `interval_secs` until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        let interval = Duration::from_secs(self.config.interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                // The first tick completes immediately, warming on startup
+                ticker.tick().await;
+                self.warm_once().await;
+            }
+        })
+    }
+}
+
+async fn warm_repository(cache: &SharedAnalysisCache, path: &Path) -> Result<WarmOutcome> {
+    let commit = current_commit(path).await?;
+    if cache.commit_of(path).await.as_deref() == Some(commit.as_str()) {
+        tracing::debug!("{} is up to date at {}", path.display(), commit);
+        return Ok(WarmOutcome::UpToDate);
+    }
+
+    let structure = analyze_repository(path.to_path_buf()).await?;
+    cache.insert(path, commit, Arc::new(structure)).await;
+    Ok(WarmOutcome::Warmed)
+}
+
+/// Get the analysis of `path`, using the cache when HEAD has not moved
+///
+/// Directories that are not git repositories are analyzed without caching.
+pub async fn analyze_cached(
+    cache: &SharedAnalysisCache,
+    path: &Path,
+) -> Result<Arc<CodeStructure>> {
+    let Ok(commit) = current_commit(path).await else {
+        return analyze_repository(path.to_path_buf()).await.map(Arc::new);
+    };
+
+    if let Some(structure) = cache.get(path, &commit).await {
+        return Ok(structure);
+    }
+
+    let structure = Arc::new(analyze_repository(path.to_path_buf()).await?);
+    cache.insert(path, commit, structure.clone()).await;
+    Ok(structure)
+}
+
+async fn current_commit(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || head_commit(&path))
+        .await
+        .map_err(|e| XzeError::repository(format!("Commit lookup task failed: {}", e)))?
+}
+
+/// Analyze a repository with an auto-detected analyzer off the async runtime
+async fn analyze_repository(path: PathBuf) -> Result<CodeStructure> {
+    tokio::task::spawn_blocking(move || {
+        let (_, analyzer) = AnalyzerFactory::auto_detect_analyzer(&path)?;
+        analyzer.analyze(&path)
+    })
+    .await
+    .map_err(|e| XzeError::repository(format!("Analysis task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::analysis_cache::AnalysisCache;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_warmer_config_defaults() {
+        let config = WarmerConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.interval_secs, 3600);
+        assert_eq!(config.concurrency, 2);
+        assert!(config.repositories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_warm_once_counts_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Arc::new(AnalysisCache::new());
+        let warmer = AnalysisWarmer::new(
+            WarmerConfig {
+                enabled: true,
+                concurrency: 0,
+                repositories: vec![temp_dir.path().to_path_buf(), temp_dir.path().join("gone")],
+                ..WarmerConfig::default()
+            },
+            cache.clone(),
+        );
+
+        let report = warmer.warm_once().await;
+
+        assert_eq!(
+            report,
+            WarmReport {
+                warmed: 0,
+                up_to_date: 0,
+                failed: 2
+            }
+        );
+        assert!(cache.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_cached_skips_cache_outside_git() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"x\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "pub fn run() {}\n").unwrap();
+        let cache = Arc::new(AnalysisCache::new());
+
+        let structure = analyze_cached(&cache, temp_dir.path()).await.unwrap();
+
+        assert!(structure.functions.iter().any(|f| f.name == "run"));
+        assert!(cache.is_empty().await);
+    }
+}
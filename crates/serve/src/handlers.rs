@@ -8,6 +8,11 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cache::{create_shared_cache, AnalysisCache, SharedAnalysisCache, SharedSearchCache};
+use xze_core::documentation::{DocumentVersionStore, DocumentationService};
+use xze_core::search::{SearchIndex, SynonymMap, TextNormalizer};
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
@@ -18,6 +23,21 @@ pub struct AppState {
     pub ollama_url: String,
     pub database_pool: PgPool,
     pub config: crate::ServerConfig,
+    pub analysis_cache: SharedAnalysisCache,
+    /// Responses of the `/api/search` endpoints, invalidated when
+    /// `search_index` changes
+    pub search_cache: SharedSearchCache,
+    /// In-memory index searched instead of the database when set
+    pub search_index: Option<Arc<SearchIndex>>,
+    /// Generated document versions served by `/documentation/:id/diff`
+    pub document_versions: Arc<tokio::sync::RwLock<DocumentVersionStore>>,
+    /// Synonyms that search queries are expanded with
+    pub synonyms: Arc<SynonymMap>,
+    /// Normalizer applied to chunk text and queries before keyword scoring
+    pub normalizer: Arc<TextNormalizer>,
+    /// Service used by `/analyze` instead of one built per request from
+    /// the selected models
+    pub documentation: Option<Arc<DocumentationService>>,
 }
 
 impl AppState {
@@ -28,7 +48,14 @@ impl AppState {
         Ok(Self {
             ollama_url: config.ollama_url.clone(),
             database_pool,
+            search_cache: create_shared_cache(config.search_cache.clone()),
             config,
+            analysis_cache: Arc::new(AnalysisCache::new()),
+            search_index: None,
+            document_versions: Arc::default(),
+            synonyms: Arc::default(),
+            normalizer: Arc::default(),
+            documentation: None,
         })
     }
 
@@ -37,9 +64,54 @@ impl AppState {
         Self {
             ollama_url: config.ollama_url.clone(),
             database_pool: pool,
+            search_cache: create_shared_cache(config.search_cache.clone()),
             config,
+            analysis_cache: Arc::new(AnalysisCache::new()),
+            search_index: None,
+            document_versions: Arc::default(),
+            synonyms: Arc::default(),
+            normalizer: Arc::default(),
+            documentation: None,
         }
     }
+
+    /// Serve `/search` from an index kept current by the KB loader
+    ///
+    /// See `IncrementalLoader::with_search_index`.
+    pub fn with_search_index(mut self, index: Arc<SearchIndex>) -> Self {
+        self.search_index = Some(index);
+        self
+    }
+
+    /// Expand `/search` queries with `synonyms` before keyword scoring
+    pub fn with_synonyms(mut self, synonyms: Arc<SynonymMap>) -> Self {
+        self.synonyms = synonyms;
+        self
+    }
+
+    /// Normalize `/search` queries and chunk text with `normalizer` before
+    /// keyword scoring
+    pub fn with_normalizer(mut self, normalizer: Arc<TextNormalizer>) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Generate `/analyze` documentation with `service`, ignoring the
+    /// models a request selects
+    pub fn with_documentation(mut self, service: Arc<DocumentationService>) -> Self {
+        self.documentation = Some(service);
+        self
+    }
+
+    /// Serve document diffs from `store`, shared with whatever records
+    /// generated documents
+    pub fn with_document_versions(
+        mut self,
+        store: Arc<tokio::sync::RwLock<DocumentVersionStore>>,
+    ) -> Self {
+        self.document_versions = store;
+        self
+    }
 }
 
 /// Handler for repository analysis
@@ -146,6 +218,22 @@ pub async fn handle_generate_documentation(
         payload.repository_id
     );
 
+    let categories = match xze_core::types::DiátaxisCategory::parse_list(
+        payload.categories.as_deref().unwrap_or_default(),
+    ) {
+        Ok(categories) => categories,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(SearchErrorResponse {
+                    error: "Invalid documentation category".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+                .into_response();
+        }
+    };
+
     // TODO: Implement actual documentation generation
     let job_id = uuid::Uuid::new_v4().to_string();
 
@@ -153,15 +241,12 @@ pub async fn handle_generate_documentation(
         job_id,
         status: "queued".to_string(),
         message: "Documentation generation queued".to_string(),
-        categories: payload.categories.unwrap_or_else(|| {
-            vec![
-                "tutorial".to_string(),
-                "how-to".to_string(),
-                "reference".to_string(),
-                "explanation".to_string(),
-            ]
-        }),
+        categories: categories
+            .iter()
+            .map(|c| c.to_string().to_lowercase())
+            .collect(),
     })
+    .into_response()
 }
 
 /// Handler for getting documentation
@@ -317,6 +402,10 @@ pub struct ServiceStatus {
             ("max_results" = Option<usize>, Query, description = "Maximum number of results (default: 10)"),
             ("min_similarity" = Option<f32>, Query, description = "Minimum similarity threshold 0.0-1.0 (default: 0.0)"),
             ("category" = Option<String>, Query, description = "Filter by documentation category (tutorial, how-to, reference, explanation)"),
+            ("language" = Option<String>, Query, description = "Filter by document language as an ISO 639-3 code such as eng (indexed search only)"),
+            ("metric" = Option<String>, Query, description = "Similarity metric: cosine, dot_product or euclidean (default: cosine)"),
+            ("alpha" = Option<f32>, Query, description = "Weight of embedding similarity blended with BM25 keyword scores, 0.0-1.0; 0.0 ranks by keywords only (default: embedding similarity only)"),
+            ("explain" = Option<bool>, Query, description = "Include the embedding and BM25 scores of each result (default: false)"),
         ),
         responses(
             (status = 200, description = "Search results with similarity scores", body = SearchResponse),
@@ -330,14 +419,16 @@ pub async fn handle_search(
     State(state): State<AppState>,
     Query(params): Query<SearchQueryParams>,
 ) -> impl IntoResponse {
-    use xze_core::semantic::search::{search_with_chunks, SearchConfig};
+    use xze_core::semantic::search::{search_with_chunks, search_with_index, SearchConfig};
+    use xze_core::semantic::similarity::SimilarityMetric;
 
     tracing::info!(
-        "Search request: query='{}', max_results={}, min_similarity={}, category={:?}",
+        "Search request: query='{}', max_results={}, min_similarity={}, category={:?}, alpha={:?}",
         params.q,
         params.max_results.unwrap_or(10),
         params.min_similarity.unwrap_or(0.0),
-        params.category
+        params.category,
+        params.alpha
     );
 
     // Validate query
@@ -352,15 +443,46 @@ pub async fn handle_search(
             .into_response();
     }
 
+    let similarity_metric = match params
+        .metric
+        .as_deref()
+        .map(str::parse::<SimilarityMetric>)
+        .transpose()
+    {
+        Ok(metric) => metric.unwrap_or_default(),
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(SearchErrorResponse {
+                    error: "Invalid similarity metric".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+                .into_response();
+        }
+    };
+
     // Build search config
     let config = SearchConfig {
         max_results: params.max_results.unwrap_or(10),
         min_similarity: params.min_similarity.unwrap_or(0.0),
         category_filter: params.category.clone(),
+        language_filter: params.language.clone(),
+        similarity_metric,
+        alpha: params.alpha,
+        synonyms: Some(state.synonyms.clone()),
+        normalizer: Some(state.normalizer.clone()),
     };
+    let explain = params.explain.unwrap_or(false);
 
     // Perform search
-    match search_with_chunks(&state.database_pool, &params.q, &state.ollama_url, &config).await {
+    let results = match &state.search_index {
+        Some(index) => search_with_index(index, &params.q, &state.ollama_url, &config).await,
+        None => {
+            search_with_chunks(&state.database_pool, &params.q, &state.ollama_url, &config).await
+        }
+    };
+    match results {
         Ok(results) => {
             let total_results = results.len();
             let response = SearchResponse {
@@ -378,6 +500,8 @@ pub async fn handle_search(
                         category: r.category,
                         sentence_range: r.sentence_range,
                         avg_chunk_similarity: r.avg_chunk_similarity,
+                        vector_score: r.vector_score.filter(|_| explain),
+                        lexical_score: r.lexical_score.filter(|_| explain),
                     })
                     .collect(),
                 total_results,
@@ -385,6 +509,9 @@ pub async fn handle_search(
                     max_results: config.max_results,
                     min_similarity: config.min_similarity,
                     category_filter: config.category_filter,
+                    language_filter: config.language_filter,
+                    similarity_metric: config.similarity_metric.to_string(),
+                    alpha: config.alpha,
                 },
             };
 
@@ -400,6 +527,10 @@ pub async fn handle_search(
                 xze_core::semantic::search::SearchError::InvalidConfig(ref msg) => {
                     (StatusCode::BAD_REQUEST, msg.as_str())
                 }
+                xze_core::semantic::search::SearchError::MetricMismatch { .. } => (
+                    StatusCode::BAD_REQUEST,
+                    "Similarity metric does not match the stored embeddings",
+                ),
                 xze_core::semantic::search::SearchError::Database(ref db_err) => {
                     tracing::error!("Database error: {}", db_err);
                     (StatusCode::INTERNAL_SERVER_ERROR, "Database error occurred")
@@ -444,6 +575,18 @@ pub struct SearchQueryParams {
     /// Filter by documentation category
     #[cfg_attr(feature = "openapi", schema(example = "tutorial"))]
     pub category: Option<String>,
+    /// Filter by document language, an ISO 639-3 code such as `eng`
+    #[cfg_attr(feature = "openapi", schema(example = "eng"))]
+    pub language: Option<String>,
+    /// Similarity metric (cosine, dot_product or euclidean)
+    #[cfg_attr(feature = "openapi", schema(example = "cosine"))]
+    pub metric: Option<String>,
+    /// Weight of embedding similarity against BM25 keyword scores (0.0 to
+    /// 1.0); unset ranks by embedding similarity only
+    #[cfg_attr(feature = "openapi", schema(example = 0.7))]
+    pub alpha: Option<f32>,
+    /// Include the component scores of each result
+    pub explain: Option<bool>,
 }
 
 /// Search response with results
@@ -480,7 +623,8 @@ pub struct SearchResultItem {
         schema(example = "To implement authentication, first configure the JWT middleware...")
     )]
     pub content: String,
-    /// Similarity score (0.0 to 1.0, higher is better)
+    /// Similarity score (0.0 to 1.0, higher is better); the blended score
+    /// when `alpha` is set
     #[cfg_attr(feature = "openapi", schema(example = 0.87))]
     pub similarity: f32,
     /// Index of this chunk within the document
@@ -500,6 +644,14 @@ pub struct SearchResultItem {
     /// Average similarity across all chunks in document
     #[cfg_attr(feature = "openapi", schema(example = 0.82))]
     pub avg_chunk_similarity: f32,
+    /// Embedding similarity (if `explain` is set and the query was embedded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = 0.81))]
+    pub vector_score: Option<f32>,
+    /// BM25 score relative to the best match (if `explain` and `alpha` are set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = 1.0))]
+    pub lexical_score: Option<f32>,
 }
 
 /// Search configuration details
@@ -515,6 +667,15 @@ pub struct SearchConfigResponse {
     /// Category filter applied
     #[cfg_attr(feature = "openapi", schema(example = "tutorial"))]
     pub category_filter: Option<String>,
+    /// Language filter applied
+    #[cfg_attr(feature = "openapi", schema(example = "eng"))]
+    pub language_filter: Option<String>,
+    /// Similarity metric used for ranking
+    #[cfg_attr(feature = "openapi", schema(example = "cosine"))]
+    pub similarity_metric: String,
+    /// Weight of embedding similarity in hybrid ranking, if enabled
+    #[cfg_attr(feature = "openapi", schema(example = 0.7))]
+    pub alpha: Option<f32>,
 }
 
 /// Search error response
@@ -562,6 +723,54 @@ mod tests {
         assert!(json.contains("queued"));
     }
 
+    #[tokio::test]
+    async fn test_handle_search_normalizes_query_and_chunks() {
+        use xze_core::kb::store::DocumentChunk;
+        use xze_core::search::{IndexedDocument, NormalizerConfig};
+
+        let index = Arc::new(SearchIndex::new());
+        index.upsert(IndexedDocument::new(
+            "cafe.md",
+            vec![DocumentChunk::new(
+                "chunk_0".to_string(),
+                "Running the Café downtown".to_string(),
+                vec![1.0, 0.0],
+                serde_json::json!({}),
+            )],
+        ));
+        let pool = PgPool::connect_lazy("postgresql://localhost/xze_test").unwrap();
+        let state = AppState::from_pool(crate::ServerConfig::default(), pool)
+            .with_search_index(index)
+            .with_normalizer(Arc::new(TextNormalizer::new(NormalizerConfig {
+                fold_diacritics: true,
+                stemming: true,
+                ..Default::default()
+            })));
+
+        for query in ["cafe", "run"] {
+            let params = SearchQueryParams {
+                q: query.to_string(),
+                max_results: None,
+                min_similarity: Some(0.1),
+                category: None,
+                language: None,
+                metric: None,
+                alpha: Some(0.0),
+                explain: None,
+            };
+            let response = handle_search(State(state.clone()), Query(params))
+                .await
+                .into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json["total_results"], 1, "query {}", query);
+            assert_eq!(json["results"][0]["source_file"], "cafe.md");
+        }
+    }
+
     #[test]
     fn test_search_query_params_deserialization() {
         // Test basic query
@@ -570,6 +779,10 @@ mod tests {
             max_results: Some(10),
             min_similarity: Some(0.5),
             category: None,
+            language: None,
+            metric: None,
+            alpha: None,
+            explain: None,
         };
         assert_eq!(params.q, "test query");
         assert_eq!(params.max_results, Some(10));
@@ -582,6 +795,10 @@ mod tests {
             max_results: None,
             min_similarity: None,
             category: Some("tutorial".to_string()),
+            language: Some("eng".to_string()),
+            metric: Some("dot_product".to_string()),
+            alpha: Some(0.7),
+            explain: Some(true),
         };
         assert_eq!(params_with_category.category, Some("tutorial".to_string()));
     }
@@ -601,12 +818,17 @@ mod tests {
                 category: Some("tutorial".to_string()),
                 sentence_range: (0, 5),
                 avg_chunk_similarity: 0.85,
+                vector_score: None,
+                lexical_score: None,
             }],
             total_results: 1,
             config: SearchConfigResponse {
                 max_results: 10,
                 min_similarity: 0.0,
                 category_filter: None,
+                language_filter: None,
+                similarity_metric: "cosine".to_string(),
+                alpha: None,
             },
         };
 
@@ -615,6 +837,8 @@ mod tests {
         assert!(json.contains("docs/test.md"));
         assert!(json.contains("Test content"));
         assert!(json.contains("0.95"));
+        assert!(!json.contains("vector_score"));
+        assert!(!json.contains("lexical_score"));
     }
 
     #[test]
@@ -642,6 +866,8 @@ mod tests {
             category: Some("how_to".to_string()),
             sentence_range: (10, 20),
             avg_chunk_similarity: 0.75,
+            vector_score: Some(0.8),
+            lexical_score: Some(1.0),
         };
 
         assert_eq!(item.id, 42);
@@ -650,6 +876,9 @@ mod tests {
         assert_eq!(item.chunk_index, 2);
         assert_eq!(item.total_chunks, 5);
         assert_eq!(item.sentence_range, (10, 20));
+
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["lexical_score"], 1.0);
     }
 
     #[test]
@@ -658,6 +887,9 @@ mod tests {
             max_results: 20,
             min_similarity: 0.6,
             category_filter: Some("reference".to_string()),
+            language_filter: Some("deu".to_string()),
+            similarity_metric: "euclidean".to_string(),
+            alpha: Some(0.5),
         };
 
         assert_eq!(config.max_results, 20);
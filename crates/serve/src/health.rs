@@ -0,0 +1,273 @@
+//! Liveness and readiness probes
+//!
+//! `GET /livez` answers 200 whenever the process can serve a request, so an
+//! orchestrator only restarts instances that stopped responding.
+//! `GET /readyz` answers 200 only while the dependencies are reachable and
+//! 503 otherwise, listing the dependencies that failed:
+//!
+//! ```json
+//! {"status":"not_ready","failed":["ollama"],"dependencies":{"cache":true,"ollama":false,"kb_store":null}}
+//! ```
+//!
+//! The cache directory and Ollama are checked with
+//! [`xze_infra::health_check`], then the KB store database. Checks stop at
+//! the first failure, leaving later dependencies unchecked (`null`), and the
+//! result is reused for [`READINESS_TTL`] so frequent probes do not hit
+//! Ollama each time.
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use xze_infra::InfraConfig;
+
+/// How long a readiness result is reused
+pub const READINESS_TTL: Duration = Duration::from_secs(2);
+
+/// Timeout for the KB store query
+const KB_STORE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Reachability of each dependency; `None` when not checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    /// Cache directory is writable
+    pub cache: Option<bool>,
+    /// Ollama lists its models
+    pub ollama: Option<bool>,
+    /// KB store database answers queries
+    pub kb_store: Option<bool>,
+}
+
+/// Response of `GET /readyz`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    /// `ready` or `not_ready`
+    pub status: String,
+    /// Dependencies that failed
+    pub failed: Vec<String>,
+    /// Result of each check
+    pub dependencies: DependencyStatus,
+}
+
+impl ReadinessReport {
+    fn new(dependencies: DependencyStatus) -> Self {
+        let failed: Vec<String> = [
+            ("cache", dependencies.cache),
+            ("ollama", dependencies.ollama),
+            ("kb_store", dependencies.kb_store),
+        ]
+        .into_iter()
+        .filter(|(_, ok)| *ok == Some(false))
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+        Self {
+            status: if failed.is_empty() {
+                "ready"
+            } else {
+                "not_ready"
+            }
+            .to_string(),
+            failed,
+            dependencies,
+        }
+    }
+
+    /// Whether every dependency is reachable
+    pub fn is_ready(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Checks dependencies for `/readyz`, reusing recent results
+#[derive(Debug)]
+pub struct ReadinessCheck {
+    infra: InfraConfig,
+    pool: PgPool,
+    ttl: Duration,
+    last: Mutex<Option<(Instant, ReadinessReport)>>,
+}
+
+impl ReadinessCheck {
+    /// Check the infrastructure in `infra` and the KB store on `pool`
+    pub fn new(infra: InfraConfig, pool: PgPool) -> Self {
+        Self {
+            infra,
+            pool,
+            ttl: READINESS_TTL,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Set how long a result is reused
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The latest readiness result, checking again once it is older than the TTL
+    ///
+    /// Concurrent probes wait for a single check instead of each running one.
+    pub async fn report(&self) -> ReadinessReport {
+        let mut last = self.last.lock().await;
+        if let Some((checked_at, report)) = last.as_ref() {
+            if checked_at.elapsed() < self.ttl {
+                return report.clone();
+            }
+        }
+
+        let report = ReadinessReport::new(self.check().await);
+        *last = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    async fn check(&self) -> DependencyStatus {
+        let mut status = DependencyStatus {
+            cache: None,
+            ollama: None,
+            kb_store: None,
+        };
+
+        match xze_infra::health_check(&self.infra).await {
+            Ok(infra) => {
+                status.cache = Some(infra.cache_accessible);
+                if !infra.cache_accessible {
+                    return status;
+                }
+                status.ollama = Some(infra.ollama_accessible);
+                if !infra.ollama_accessible {
+                    return status;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Infrastructure health check failed: {}", e);
+                status.cache = Some(false);
+                return status;
+            }
+        }
+
+        let query = sqlx::query("SELECT 1").execute(&self.pool);
+        status.kb_store = Some(matches!(
+            tokio::time::timeout(KB_STORE_TIMEOUT, query).await,
+            Ok(Ok(_))
+        ));
+        status
+    }
+}
+
+/// Handles `GET /livez`
+pub async fn handle_livez() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "alive" }))
+}
+
+/// Handles `GET /readyz`
+pub async fn handle_readyz(
+    State(check): State<Arc<ReadinessCheck>>,
+) -> (StatusCode, Json<ReadinessReport>) {
+    let report = check.report().await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+/// Create the `/livez` and `/readyz` routes
+pub fn health_routes(check: Arc<ReadinessCheck>) -> Router {
+    Router::new()
+        .route("/livez", get(handle_livez))
+        .route("/readyz", get(handle_readyz))
+        .with_state(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn check(ollama_url: &str, cache_dir: &TempDir) -> Arc<ReadinessCheck> {
+        let infra = InfraConfig {
+            ollama_url: ollama_url.to_string(),
+            cache_dir: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let pool = PgPool::connect_lazy("postgresql://localhost/xze_test").unwrap();
+        Arc::new(ReadinessCheck::new(infra, pool))
+    }
+
+    async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_livez_is_always_ok() {
+        let cache_dir = TempDir::new().unwrap();
+        let app = health_routes(check("http://127.0.0.1:1", &cache_dir));
+
+        let (status, body) = get_json(app, "/livez").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "alive");
+    }
+
+    #[tokio::test]
+    async fn test_readyz_unavailable_when_ollama_unreachable() {
+        let cache_dir = TempDir::new().unwrap();
+        let app = health_routes(check("http://127.0.0.1:1", &cache_dir));
+
+        let (status, body) = get_json(app, "/readyz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "not_ready");
+        assert_eq!(body["failed"], serde_json::json!(["ollama"]));
+        assert_eq!(body["dependencies"]["cache"], true);
+        assert!(body["dependencies"]["kb_store"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_readiness_result_is_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let tags = server
+            .mock("GET", "/api/tags")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+        let cache_dir = TempDir::new().unwrap();
+        let check = check(&server.url(), &cache_dir);
+
+        assert!(!check.report().await.is_ready());
+        assert!(!check.report().await.is_ready());
+        tags.assert_async().await;
+    }
+
+    #[test]
+    fn test_report_lists_failed_dependencies() {
+        let report = ReadinessReport::new(DependencyStatus {
+            cache: Some(true),
+            ollama: Some(true),
+            kb_store: Some(false),
+        });
+        assert!(!report.is_ready());
+        assert_eq!(report.failed, vec!["kb_store"]);
+
+        let ready = ReadinessReport::new(DependencyStatus {
+            cache: Some(true),
+            ollama: Some(true),
+            kb_store: Some(true),
+        });
+        assert!(ready.is_ready());
+        assert_eq!(ready.status, "ready");
+    }
+}
@@ -2,14 +2,20 @@
 //!
 //! Web server interface for the XZe documentation pipeline tool.
 
+use std::path::PathBuf;
 use xze_core::Result;
 
 pub mod api;
 pub mod cache;
 pub mod handlers;
+pub mod health;
+pub mod load;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod middleware;
 pub mod search;
 pub mod server;
+pub mod tls;
 
 pub use handlers::*;
 pub use search::search_routes;
@@ -18,6 +24,18 @@ pub use server::*;
 /// Server version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Cargo features compiled into `xze-serve`
+pub fn compiled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "openapi") {
+        features.push("openapi");
+    }
+    if cfg!(feature = "metrics") {
+        features.push("metrics");
+    }
+    features
+}
+
 /// Default server configuration
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
@@ -27,6 +45,44 @@ pub struct ServerConfig {
     pub database_url: String,
     pub cors_enabled: bool,
     pub max_request_size: usize,
+    pub warmer: cache::WarmerConfig,
+    pub timeouts: middleware::TimeoutConfig,
+    /// Models used when a request does not choose its own
+    pub models: xze_core::config::ModelConfig,
+    /// Configured repositories, listed and filtered by `/repositories`
+    pub repositories: Vec<xze_core::config::RepositoryConfig>,
+    /// How paths in served analysis output are rewritten; repository
+    /// relative by default so host layouts are not exposed
+    pub path_redaction: xze_core::repository::PathRedaction,
+    /// PEM certificate chain; with `tls_key_path`, serve HTTPS
+    pub tls_cert_path: Option<PathBuf>,
+    /// PEM private key for `tls_cert_path`
+    pub tls_key_path: Option<PathBuf>,
+    /// Require a JWT bearer token on every endpoint except health checks
+    pub jwt: Option<middleware::JwtConfig>,
+    /// Endpoint groups to mount; all are enabled by default
+    pub endpoints: api::EndpointToggles,
+    /// Capacities `/metrics/load` scores the load against
+    pub load: load::LoadConfig,
+    /// YAML file of `term: [synonyms]` used to expand search queries; no
+    /// expansion when unset
+    pub synonyms_path: Option<PathBuf>,
+    /// Normalization applied to chunk text and queries before keyword
+    /// scoring
+    pub normalization: xze_core::search::NormalizerConfig,
+    /// Largest page size and offset the search endpoints accept
+    pub search_limits: search::SearchLimits,
+    /// Capacity and lifetime of cached `/api/search` responses
+    pub search_cache: cache::SearchCacheConfig,
+    /// Model reranking for searches that set `rerank_top_n`; such
+    /// searches are rejected when unset
+    pub rerank: Option<xze_core::search::rerank::RerankConfig>,
+    /// Secret scanning and granularity for documentation generated by
+    /// `/analyze`
+    pub generation: xze_core::config::GenerationConfig,
+    /// Where documentation generated by `/analyze` is delivered; the `kb`
+    /// sink writes to the server's database
+    pub output: xze_core::documentation::OutputConfig,
 }
 
 impl Default for ServerConfig {
@@ -38,17 +94,56 @@ impl Default for ServerConfig {
             database_url: "postgresql://localhost/xze".to_string(),
             cors_enabled: true,
             max_request_size: 10 * 1024 * 1024, // 10MB
+            warmer: cache::WarmerConfig::default(),
+            timeouts: middleware::TimeoutConfig::default(),
+            models: xze_core::config::ModelConfig::default(),
+            repositories: Vec::new(),
+            path_redaction: xze_core::repository::PathRedaction::Relative,
+            tls_cert_path: None,
+            tls_key_path: None,
+            jwt: None,
+            endpoints: api::EndpointToggles::default(),
+            load: load::LoadConfig::default(),
+            synonyms_path: None,
+            normalization: Default::default(),
+            search_limits: search::SearchLimits::default(),
+            search_cache: cache::SearchCacheConfig::default(),
+            rerank: None,
+            generation: Default::default(),
+            output: Default::default(),
         }
     }
 }
 
-/// Initialize the server
+/// Start the server and run until Ctrl+C or SIGTERM
+///
+/// Serves HTTPS when a TLS certificate and key are configured and plain
+/// HTTP otherwise. The TLS files are loaded and the address is bound before
+/// the database is connected, so a bad certificate or a port that is
+/// already in use fails fast.
+///
+/// # Errors
+///
+/// Returns a validation error if only one TLS path is set or a TLS file is
+/// invalid, `XzeError::Network` if the address cannot be bound or serving
+/// fails, and an error if the database cannot be reached
 pub async fn init_server(config: ServerConfig) -> Result<()> {
     tracing::info!("Initializing XZe server v{}", VERSION);
-    tracing::info!("Server will bind to {}:{}", config.host, config.port);
-
-    // TODO: Implement actual server initialization
-    Ok(())
+    let tls = tls::load(&config)?;
+    let listener = server::bind(&config).await?;
+    let server = XzeServer::new(config).await?;
+    match tls {
+        Some(tls) => {
+            tls::serve_tls(
+                listener,
+                server.into_router(),
+                tls,
+                server::shutdown_signal(),
+            )
+            .await
+        }
+        None => server::serve(listener, server.into_router(), server::shutdown_signal()).await,
+    }
 }
 
 #[cfg(test)]
@@ -61,11 +156,35 @@ mod tests {
         assert_eq!(config.host, "127.0.0.1");
         assert_eq!(config.port, 3000);
         assert!(config.cors_enabled);
+        assert!(config.tls_cert_path.is_none() && config.tls_key_path.is_none());
     }
 
     #[tokio::test]
-    async fn test_server_init() {
-        let config = ServerConfig::default();
-        assert!(init_server(config).await.is_ok());
+    async fn test_server_init_rejects_partial_tls() {
+        let config = ServerConfig {
+            tls_key_path: Some(PathBuf::from("key.pem")),
+            ..ServerConfig::default()
+        };
+        let err = init_server(config).await.unwrap_err();
+        assert!(
+            matches!(err, xze_core::XzeError::Validation { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_init_fails_on_port_in_use() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = ServerConfig {
+            port: taken.local_addr().unwrap().port(),
+            ..ServerConfig::default()
+        };
+        let err = init_server(config).await.unwrap_err();
+        assert!(
+            matches!(err, xze_core::XzeError::Network { .. }),
+            "{:?}",
+            err
+        );
     }
 }
@@ -0,0 +1,398 @@
+//! Load indicator for autoscalers
+//!
+//! `GET /metrics/load` reports how saturated this instance is as one score
+//! between 0 and 1, so an autoscaler can act on a single number instead of
+//! combining the Prometheus metrics itself.
+//!
+//! # Scoring
+//!
+//! Each component is a ratio clamped to `0.0..=1.0`:
+//!
+//! | Component | Ratio |
+//! |-----------|-------|
+//! | `inference_queue` | Ollama requests in flight / [`LoadConfig::inference_capacity`] |
+//! | `inference_budget` | share of the inference budget used, the larger of tokens and requests |
+//! | `search_qps` | search requests per second over [`LoadConfig::window`] / [`LoadConfig::target_search_qps`] |
+//! | `ollama_latency` | p99 latency of recent generate and embed calls / [`LoadConfig::target_latency`] |
+//!
+//! The saturation score is the largest component: an instance is as loaded
+//! as its busiest resource, and averaging would hide a saturated one behind
+//! idle ones. `inference_budget` is `null` when no budget is configured and
+//! `ollama_latency` is `null` before the first inference call; missing
+//! components do not count.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use xze_serve::load::{LoadConfig, LoadReport};
+//!
+//! let config = LoadConfig::default();
+//! // 2 of 4 inference slots busy, 9 searches/s against a target of 10
+//! let report = LoadReport::new(&config, 2, 9.0, Some(Duration::from_secs(1)), None);
+//! assert_eq!(report.components.inference_queue, 0.5);
+//! assert_eq!(report.saturation, 0.9);
+//! ```
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use xze_core::ai::{InferenceBudget, OllamaClient};
+
+/// Paths whose requests count towards the search rate
+pub(crate) const SEARCH_PATHS: &[&str] = &[
+    "/search",
+    "/api/search",
+    "/api/search/advanced",
+    "/api/v1/search",
+];
+
+/// Capacities the load components are measured against
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadConfig {
+    /// Concurrent inference requests Ollama serves without queueing
+    pub inference_capacity: usize,
+    /// Search requests per second one instance is sized for
+    pub target_search_qps: f64,
+    /// Highest acceptable p99 latency of inference calls
+    pub target_latency: Duration,
+    /// Window the search rate is averaged over, at least one second
+    pub window: Duration,
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        Self {
+            inference_capacity: 4,
+            target_search_qps: 10.0,
+            target_latency: Duration::from_secs(5),
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Normalized load components, each between 0 and 1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadComponents {
+    /// Inference requests in flight relative to capacity
+    pub inference_queue: f64,
+    /// Share of the inference budget used, if a budget is configured
+    pub inference_budget: Option<f64>,
+    /// Search rate relative to the target rate
+    pub search_qps: f64,
+    /// Ollama p99 latency relative to the target, once calls were made
+    pub ollama_latency: Option<f64>,
+}
+
+impl LoadComponents {
+    /// The largest component
+    pub fn saturation(&self) -> f64 {
+        [self.inference_budget, self.ollama_latency]
+            .into_iter()
+            .flatten()
+            .fold(self.inference_queue.max(self.search_qps), f64::max)
+    }
+}
+
+/// Response of `GET /metrics/load`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadReport {
+    /// Overall saturation between 0 (idle) and 1 (saturated)
+    pub saturation: f64,
+    /// Components the score is derived from
+    pub components: LoadComponents,
+    /// Inference requests in flight
+    pub inference_in_flight: usize,
+    /// Configured inference capacity
+    pub inference_capacity: usize,
+    /// Search requests per second over the window
+    pub search_qps: f64,
+    /// Configured target search rate
+    pub target_search_qps: f64,
+    /// p99 latency of recent inference calls in milliseconds
+    pub ollama_p99_latency_ms: Option<u64>,
+    /// Configured target latency in milliseconds
+    pub target_latency_ms: u64,
+}
+
+impl LoadReport {
+    /// Score raw measurements against `config`
+    ///
+    /// `budget_usage` is the used share of the inference budget, `None`
+    /// when no budget is configured.
+    pub fn new(
+        config: &LoadConfig,
+        inference_in_flight: usize,
+        search_qps: f64,
+        ollama_p99_latency: Option<Duration>,
+        budget_usage: Option<f64>,
+    ) -> Self {
+        let target_latency = config.target_latency.as_secs_f64();
+        let components = LoadComponents {
+            inference_queue: ratio(inference_in_flight as f64, config.inference_capacity as f64),
+            inference_budget: budget_usage.map(|usage| usage.clamp(0.0, 1.0)),
+            search_qps: ratio(search_qps, config.target_search_qps),
+            ollama_latency: ollama_p99_latency
+                .map(|latency| ratio(latency.as_secs_f64(), target_latency)),
+        };
+
+        Self {
+            saturation: components.saturation(),
+            components,
+            inference_in_flight,
+            inference_capacity: config.inference_capacity,
+            search_qps,
+            target_search_qps: config.target_search_qps,
+            ollama_p99_latency_ms: ollama_p99_latency.map(|latency| latency.as_millis() as u64),
+            target_latency_ms: config.target_latency.as_millis() as u64,
+        }
+    }
+}
+
+/// `value / capacity` clamped to 0..=1; a zero capacity is saturated by any load
+fn ratio(value: f64, capacity: f64) -> f64 {
+    if capacity > 0.0 {
+        (value / capacity).clamp(0.0, 1.0)
+    } else if value > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Used share of `budget`, the larger of its token and request limits
+fn budget_usage(budget: &InferenceBudget) -> Option<f64> {
+    let config = budget.config();
+    let snapshot = budget.snapshot();
+    let used = |remaining: Option<u64>, limit: Option<u64>| {
+        let limit = limit?.max(1) as f64;
+        Some(1.0 - remaining? as f64 / limit)
+    };
+    [
+        used(snapshot.remaining_tokens, config.tokens_per_minute),
+        used(snapshot.remaining_requests, config.requests_per_minute),
+    ]
+    .into_iter()
+    .flatten()
+    .reduce(f64::max)
+}
+
+/// Tracks the search rate and scores the load of this instance
+#[derive(Debug)]
+pub struct LoadMonitor {
+    config: LoadConfig,
+    client: OllamaClient,
+    searches: Mutex<VecDeque<Instant>>,
+}
+
+impl LoadMonitor {
+    /// Create a monitor measuring the inference calls of `client`
+    pub fn new(config: LoadConfig, client: OllamaClient) -> Self {
+        Self {
+            config,
+            client,
+            searches: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &LoadConfig {
+        &self.config
+    }
+
+    /// Count a search request
+    pub fn record_search(&self) {
+        let now = Instant::now();
+        let mut searches = self.searches.lock().unwrap();
+        searches.push_back(now);
+        prune(&mut searches, now, self.config.window);
+    }
+
+    /// Search requests per second over the window
+    pub fn search_qps(&self) -> f64 {
+        let mut searches = self.searches.lock().unwrap();
+        prune(&mut searches, Instant::now(), self.config.window);
+        searches.len() as f64 / self.config.window.as_secs_f64().max(1.0)
+    }
+
+    /// Score the current load
+    pub fn report(&self) -> LoadReport {
+        LoadReport::new(
+            &self.config,
+            self.client.pool_stats().active_requests,
+            self.search_qps(),
+            self.client.latency_percentile(0.99),
+            budget_usage(self.client.budget()),
+        )
+    }
+}
+
+/// Drop searches older than `window`
+fn prune(searches: &mut VecDeque<Instant>, now: Instant, window: Duration) {
+    while searches
+        .front()
+        .is_some_and(|at| now.duration_since(*at) > window)
+    {
+        searches.pop_front();
+    }
+}
+
+/// Handles `GET /metrics/load`
+pub async fn handle_load(State(monitor): State<Arc<LoadMonitor>>) -> Json<LoadReport> {
+    Json(monitor.report())
+}
+
+/// Create the `/metrics/load` route
+pub fn load_routes(monitor: Arc<LoadMonitor>) -> Router {
+    Router::new()
+        .route("/metrics/load", get(handle_load))
+        .with_state(monitor)
+}
+
+/// Count requests to the search endpoints in `monitor`
+pub async fn load_tracking_middleware(
+    monitor: Arc<LoadMonitor>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if SEARCH_PATHS.contains(&request.uri().path()) {
+        monitor.record_search();
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware};
+    use tower::ServiceExt;
+    use xze_core::ai::InferenceBudgetConfig;
+
+    fn monitor(config: LoadConfig) -> Arc<LoadMonitor> {
+        Arc::new(LoadMonitor::new(
+            config,
+            OllamaClient::new("http://localhost:11434".to_string()),
+        ))
+    }
+
+    #[test]
+    fn test_components_are_normalized() {
+        let config = LoadConfig::default();
+        let report = LoadReport::new(
+            &config,
+            8,
+            2.5,
+            Some(Duration::from_millis(1250)),
+            Some(0.4),
+        );
+
+        assert_eq!(report.components.inference_queue, 1.0);
+        assert_eq!(report.components.search_qps, 0.25);
+        assert_eq!(report.components.ollama_latency, Some(0.25));
+        assert_eq!(report.components.inference_budget, Some(0.4));
+        assert_eq!(report.saturation, 1.0);
+        assert_eq!(report.ollama_p99_latency_ms, Some(1250));
+        assert_eq!(report.target_latency_ms, 5000);
+    }
+
+    #[test]
+    fn test_saturation_is_the_busiest_component() {
+        let config = LoadConfig::default();
+        let idle = LoadReport::new(&config, 0, 0.0, None, None);
+        assert_eq!(idle.saturation, 0.0);
+        assert_eq!(idle.components.ollama_latency, None);
+
+        let slow = LoadReport::new(&config, 1, 1.0, Some(Duration::from_secs(4)), None);
+        assert_eq!(slow.saturation, 0.8);
+
+        let budget = LoadReport::new(&config, 1, 1.0, None, Some(0.6));
+        assert_eq!(budget.saturation, 0.6);
+    }
+
+    #[test]
+    fn test_zero_capacity_is_saturated_by_any_load() {
+        let config = LoadConfig {
+            inference_capacity: 0,
+            ..Default::default()
+        };
+        assert_eq!(LoadReport::new(&config, 0, 0.0, None, None).saturation, 0.0);
+        assert_eq!(LoadReport::new(&config, 1, 0.0, None, None).saturation, 1.0);
+    }
+
+    #[test]
+    fn test_budget_usage() {
+        assert_eq!(budget_usage(&InferenceBudget::unlimited()), None);
+
+        let budget = InferenceBudget::new(InferenceBudgetConfig {
+            requests_per_minute: Some(100),
+            ..Default::default()
+        });
+        assert!(budget_usage(&budget).unwrap() < 0.01);
+    }
+
+    #[test]
+    fn test_search_rate_uses_window() {
+        let monitor = monitor(LoadConfig {
+            window: Duration::from_secs(10),
+            ..Default::default()
+        });
+        for _ in 0..20 {
+            monitor.record_search();
+        }
+        assert_eq!(monitor.search_qps(), 2.0);
+
+        let mut searches = VecDeque::from([Instant::now()]);
+        prune(
+            &mut searches,
+            Instant::now() + Duration::from_secs(11),
+            Duration::from_secs(10),
+        );
+        assert!(searches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_endpoint_counts_searches() {
+        let monitor = monitor(LoadConfig {
+            window: Duration::from_secs(1),
+            ..Default::default()
+        });
+        let tracked = monitor.clone();
+        let app = Router::new()
+            .route("/api/search", get(|| async { "results" }))
+            .route("/health", get(|| async { "ok" }))
+            .merge(load_routes(monitor))
+            .layer(middleware::from_fn(move |req, next| {
+                load_tracking_middleware(tracked.clone(), req, next)
+            }));
+
+        for path in ["/api/search", "/api/search", "/health"] {
+            let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let request = Request::builder()
+            .uri("/metrics/load")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: LoadReport = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report.search_qps, 2.0);
+        assert_eq!(report.components.search_qps, 0.2);
+        assert_eq!(report.components.inference_budget, None);
+        assert_eq!(report.saturation, 0.2);
+    }
+}
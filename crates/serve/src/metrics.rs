@@ -0,0 +1,295 @@
+//! Prometheus metrics endpoint
+//!
+//! `GET /metrics` renders every metric in the default Prometheus registry in
+//! the text exposition format: the HTTP request metrics recorded here, the
+//! search metrics in [`SEARCH_METRICS`] and gauges refreshed on each scrape.
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `xze_http_requests_total` | counter | requests by method, route and status |
+//! | `xze_http_request_duration_seconds` | histogram | request latency by method and route |
+//! | `xze_search_duration_seconds` | histogram | search latency; use `histogram_quantile` for percentiles |
+//! | `xze_search_cache_hit_ratio` | gauge | search cache hits over lookups |
+//! | `xze_ollama_request_duration_seconds` | gauge | p50, p90 and p99 of recent generate and embed calls |
+//! | `xze_ollama_requests_in_flight` | gauge | Ollama requests currently in flight |
+//!
+//! Routes are labelled with their pattern, such as `/api/v1/documentation/:id`,
+//! so path parameters do not create a series per value; requests no route
+//! matched share the `unmatched` label.
+
+use crate::load::SEARCH_PATHS;
+use crate::search::SEARCH_METRICS;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_gauge, register_gauge_vec, register_histogram_vec, register_int_counter_vec,
+    register_int_gauge, Encoder, Gauge, GaugeVec, HistogramVec, IntCounterVec, IntGauge,
+    TextEncoder,
+};
+use std::time::Instant;
+use xze_core::ai::OllamaClient;
+
+/// Quantiles of recent Ollama latencies exported on each scrape
+const OLLAMA_QUANTILES: &[(f64, &str)] = &[(0.5, "0.5"), (0.9, "0.9"), (0.99, "0.99")];
+
+/// Global server metrics instance
+pub static SERVER_METRICS: Lazy<ServerMetrics> =
+    Lazy::new(|| ServerMetrics::new().expect("Failed to initialize server metrics"));
+
+/// Server-level metrics registered in the default registry
+pub struct ServerMetrics {
+    /// Requests by method, route and status
+    pub http_requests_total: IntCounterVec,
+    /// Request duration in seconds by method and route
+    pub http_request_duration_seconds: HistogramVec,
+    /// Latency quantiles of recent Ollama calls in seconds
+    pub ollama_request_duration_seconds: GaugeVec,
+    /// Ollama requests in flight
+    pub ollama_requests_in_flight: IntGauge,
+    /// Search cache hits over lookups
+    pub search_cache_hit_ratio: Gauge,
+}
+
+impl ServerMetrics {
+    /// Creates and registers the server metrics
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a metric with the same name is already registered
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let http_requests_total = register_int_counter_vec!(
+            "xze_http_requests_total",
+            "Total number of HTTP requests",
+            &["method", "route", "status"]
+        )?;
+
+        let http_request_duration_seconds = register_histogram_vec!(
+            "xze_http_request_duration_seconds",
+            "HTTP request duration in seconds",
+            &["method", "route"],
+            vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]
+        )?;
+
+        let ollama_request_duration_seconds = register_gauge_vec!(
+            "xze_ollama_request_duration_seconds",
+            "Latency quantiles of recent Ollama generate and embed calls in seconds",
+            &["quantile"]
+        )?;
+
+        let ollama_requests_in_flight = register_int_gauge!(
+            "xze_ollama_requests_in_flight",
+            "Ollama requests currently in flight"
+        )?;
+
+        let search_cache_hit_ratio = register_gauge!(
+            "xze_search_cache_hit_ratio",
+            "Search cache hits divided by lookups"
+        )?;
+
+        Ok(Self {
+            http_requests_total,
+            http_request_duration_seconds,
+            ollama_request_duration_seconds,
+            ollama_requests_in_flight,
+            search_cache_hit_ratio,
+        })
+    }
+
+    /// Records a finished HTTP request
+    pub fn record_request(&self, method: &str, route: &str, status: u16, duration: f64) {
+        self.http_requests_total
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_request_duration_seconds
+            .with_label_values(&[method, route])
+            .observe(duration);
+    }
+
+    /// Refreshes the gauges derived from `client` and the search counters
+    pub fn refresh(&self, client: &OllamaClient) {
+        for (quantile, label) in OLLAMA_QUANTILES {
+            let gauge = self
+                .ollama_request_duration_seconds
+                .with_label_values(&[label]);
+            match client.latency_percentile(*quantile) {
+                Some(latency) => gauge.set(latency.as_secs_f64()),
+                None => gauge.set(0.0),
+            }
+        }
+        self.ollama_requests_in_flight
+            .set(client.pool_stats().active_requests as i64);
+
+        let operations = &SEARCH_METRICS.cache_operations_total;
+        let hits = operations.with_label_values(&["hit"]).get();
+        let lookups = hits + operations.with_label_values(&["miss"]).get();
+        let ratio = if lookups == 0 {
+            0.0
+        } else {
+            hits as f64 / lookups as f64
+        };
+        self.search_cache_hit_ratio.set(ratio);
+    }
+}
+
+/// Renders the default registry in the Prometheus text format
+///
+/// Gauges derived from `client` are refreshed first.
+pub fn render(client: &OllamaClient) -> Result<String, prometheus::Error> {
+    SERVER_METRICS.refresh(client);
+    // Touch the search metrics so they are registered before the first search
+    Lazy::force(&SEARCH_METRICS);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+    String::from_utf8(buffer).map_err(|e| prometheus::Error::Msg(e.to_string()))
+}
+
+/// Handles `GET /metrics`
+pub async fn handle_metrics(State(client): State<OllamaClient>) -> Response {
+    match render(&client) {
+        Ok(body) => (
+            [(header::CONTENT_TYPE, TextEncoder::new().format_type())],
+            body,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to render metrics: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Create the `/metrics` route reporting the Ollama latencies of `client`
+pub fn metrics_routes(client: OllamaClient) -> Router {
+    Router::new()
+        .route("/metrics", get(handle_metrics))
+        .with_state(client)
+}
+
+/// Record the count and latency of every request
+///
+/// Requests to the search endpoints are also recorded in [`SEARCH_METRICS`].
+pub async fn metrics_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let duration = started.elapsed().as_secs_f64();
+
+    let status = response.status().as_u16();
+    SERVER_METRICS.record_request(&method, &route, status, duration);
+    if SEARCH_PATHS.contains(&path.as_str()) {
+        SEARCH_METRICS.record_request(&path, &method, &status.to_string());
+        SEARCH_METRICS.record_duration(&path, &method, duration);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware};
+    use tower::ServiceExt;
+
+    /// Check one line of the text exposition format
+    fn assert_valid_line(line: &str) {
+        if let Some(comment) = line.strip_prefix("# ") {
+            let mut parts = comment.splitn(3, ' ');
+            let keyword = parts.next().unwrap();
+            let name = parts.next().unwrap_or_default();
+            assert!(is_metric_name(name), "bad metric name in {:?}", line);
+            match keyword {
+                "HELP" => {}
+                "TYPE" => assert!(
+                    ["counter", "gauge", "histogram", "summary", "untyped"]
+                        .contains(&parts.next().unwrap_or_default()),
+                    "bad type in {:?}",
+                    line
+                ),
+                _ => panic!("unknown comment {:?}", line),
+            }
+            return;
+        }
+
+        let (series, value) = line.rsplit_once(' ').expect("sample without value");
+        assert!(
+            value.parse::<f64>().is_ok() || ["+Inf", "-Inf", "NaN"].contains(&value),
+            "bad value in {:?}",
+            line
+        );
+        let name = match series.split_once('{') {
+            Some((name, labels)) => {
+                let labels = labels.strip_suffix('}').expect("unclosed labels");
+                for label in labels.split("\",").filter(|l| !l.is_empty()) {
+                    let (key, value) = label.split_once("=\"").expect("label without value");
+                    assert!(is_metric_name(key), "bad label name in {:?}", line);
+                    assert!(!value.trim_end_matches('"').contains('"'), "{:?}", line);
+                }
+                name
+            }
+            None => series,
+        };
+        assert!(is_metric_name(name), "bad metric name in {:?}", line);
+    }
+
+    fn is_metric_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_renders_prometheus_text() {
+        let client = OllamaClient::new("http://localhost:11434".to_string());
+        let app = Router::new()
+            .route("/api/search", get(|| async { "results" }))
+            .route("/documents/:id", get(|| async { "document" }))
+            .merge(metrics_routes(client))
+            .layer(middleware::from_fn(metrics_middleware));
+
+        SEARCH_METRICS.record_cache_operation("hit");
+        for uri in ["/api/search", "/documents/1", "/documents/2", "/missing"] {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let request = Request::get("/metrics").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/plain; version=0.0.4"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            assert_valid_line(line);
+        }
+        assert!(text.contains(
+            r#"xze_http_requests_total{method="GET",route="/documents/:id",status="200"}"#
+        ));
+        assert!(text.contains(r#"route="unmatched",status="404""#));
+        assert!(text.contains("xze_search_duration_seconds_bucket"));
+        assert!(text.contains(r#"xze_ollama_request_duration_seconds{quantile="0.99"}"#));
+        assert!(text.contains("# TYPE xze_search_cache_hit_ratio gauge"));
+    }
+}
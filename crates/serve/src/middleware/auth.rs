@@ -0,0 +1,399 @@
+//! JWT bearer token authentication
+//!
+//! [`jwt_auth_middleware`] validates the `Bearer` token in the
+//! `Authorization` header against a [`JwtConfig`] and stores the caller as
+//! an [`AuthenticatedUser`] in the request extensions. Handlers take
+//! `AuthenticatedUser` as an extractor, or `Option<AuthenticatedUser>` when
+//! authentication is optional, instead of trusting a `user_id` sent by the
+//! client.
+//!
+//! Missing, malformed, expired and wrongly signed tokens are rejected with
+//! 401 Unauthorized. Health and metrics endpoints are not authenticated.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use axum::{middleware, routing::get, Router};
+//! use std::sync::Arc;
+//! use xze_serve::middleware::auth::{jwt_auth_middleware, AuthenticatedUser, JwtConfig};
+//!
+//! async fn whoami(user: AuthenticatedUser) -> String {
+//!     user.user_id
+//! }
+//!
+//! let config = Arc::new(JwtConfig::hs256("change-me").with_issuer("https://auth.example.com"));
+//! let app: Router = Router::new()
+//!     .route("/whoami", get(whoami))
+//!     .layer(middleware::from_fn(move |req, next| {
+//!         jwt_auth_middleware(config.clone(), req, next)
+//!     }));
+//! ```
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use jsonwebtoken::{decode, errors::ErrorKind, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+use xze_core::{Result, XzeError};
+
+/// How bearer tokens are verified
+#[derive(Clone)]
+pub struct JwtConfig {
+    key: DecodingKey,
+    /// Signing algorithms accepted in the token header
+    pub algorithms: Vec<Algorithm>,
+    /// Required `aud` claim, if any
+    pub audience: Option<String>,
+    /// Required `iss` claim, if any
+    pub issuer: Option<String>,
+    /// Clock skew allowed when checking `exp` and `nbf`, in seconds
+    pub leeway_secs: u64,
+}
+
+impl JwtConfig {
+    /// Verify HS256 tokens signed with a shared secret
+    pub fn hs256(secret: impl AsRef<[u8]>) -> Self {
+        Self::new(DecodingKey::from_secret(secret.as_ref()), Algorithm::HS256)
+    }
+
+    /// Verify RS256 tokens with a PEM-encoded RSA public key
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the key is not a PEM RSA public key
+    pub fn rs256_pem(public_key: impl AsRef<[u8]>) -> Result<Self> {
+        let key = DecodingKey::from_rsa_pem(public_key.as_ref())
+            .map_err(|e| XzeError::validation(format!("Invalid JWT RSA public key: {}", e)))?;
+        Ok(Self::new(key, Algorithm::RS256))
+    }
+
+    fn new(key: DecodingKey, algorithm: Algorithm) -> Self {
+        Self {
+            key,
+            algorithms: vec![algorithm],
+            audience: None,
+            issuer: None,
+            leeway_secs: 60,
+        }
+    }
+
+    /// Accept these algorithms; they must belong to the key's family
+    pub fn with_algorithms(mut self, algorithms: Vec<Algorithm>) -> Self {
+        self.algorithms = algorithms;
+        self
+    }
+
+    /// Require the `aud` claim to contain `audience`
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Require the `iss` claim to equal `issuer`
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Allow `leeway_secs` of clock skew
+    pub fn with_leeway(mut self, leeway_secs: u64) -> Self {
+        self.leeway_secs = leeway_secs;
+        self
+    }
+
+    /// Decode and verify `token`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `jsonwebtoken` error for a malformed, expired, wrongly
+    /// signed or otherwise invalid token
+    pub fn verify(&self, token: &str) -> jsonwebtoken::errors::Result<Claims> {
+        let mut validation = Validation::default();
+        validation.algorithms = self.algorithms.clone();
+        validation.leeway = self.leeway_secs;
+        validation.validate_aud = self.audience.is_some();
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+            validation.required_spec_claims.insert("aud".to_string());
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+            validation.required_spec_claims.insert("iss".to_string());
+        }
+        decode::<Claims>(token, &self.key, &validation).map(|data| data.claims)
+    }
+}
+
+impl fmt::Debug for JwtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtConfig")
+            .field("key", &"<redacted>")
+            .field("algorithms", &self.algorithms)
+            .field("audience", &self.audience)
+            .field("issuer", &self.issuer)
+            .field("leeway_secs", &self.leeway_secs)
+            .finish()
+    }
+}
+
+/// Claims read from a verified token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject; the user id
+    pub sub: String,
+    /// Expiry, in seconds since the Unix epoch
+    pub exp: u64,
+    /// Granted scopes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    /// Granted scopes as a space-separated OAuth `scope` string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// The caller identified by a verified token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthenticatedUser {
+    /// The token subject
+    pub user_id: String,
+    /// Scopes from both the `scopes` and `scope` claims
+    pub scopes: Vec<String>,
+}
+
+impl AuthenticatedUser {
+    /// Whether the token grants `scope`
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+impl From<Claims> for AuthenticatedUser {
+    fn from(claims: Claims) -> Self {
+        let mut scopes = claims.scopes;
+        for scope in claims.scope.iter().flat_map(|s| s.split_whitespace()) {
+            if !scopes.iter().any(|s| s == scope) {
+                scopes.push(scope.to_string());
+            }
+        }
+        Self {
+            user_id: claims.sub,
+            scopes,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// JWT authentication middleware
+///
+/// Verifies the bearer token and inserts the [`AuthenticatedUser`] into
+/// the request extensions.
+///
+/// # Errors
+///
+/// Returns 401 Unauthorized for a missing, malformed, expired or invalid
+/// token
+pub async fn jwt_auth_middleware(
+    config: Arc<JwtConfig>,
+    mut request: Request,
+    next: Next,
+) -> std::result::Result<Response, StatusCode> {
+    // Skip auth for health check endpoints
+    let path = request.uri().path();
+    if path == "/health"
+        || path == "/api/v1/health"
+        || path == "/metrics"
+        || path == "/metrics/load"
+        || path == "/livez"
+        || path == "/readyz"
+    {
+        return Ok(next.run(request).await);
+    }
+
+    let token = request
+        .headers()
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .ok_or_else(|| {
+            tracing::warn!("Missing or invalid authorization header");
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    let claims = config.verify(token.trim()).map_err(|e| {
+        match e.kind() {
+            ErrorKind::ExpiredSignature => tracing::warn!("Expired JWT"),
+            kind => tracing::warn!(?kind, "Invalid JWT"),
+        }
+        StatusCode::UNAUTHORIZED
+    })?;
+
+    tracing::debug!(user_id = %claims.sub, "JWT validated successfully");
+    request
+        .extensions_mut()
+        .insert(AuthenticatedUser::from(claims));
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-secret";
+
+    fn now() -> u64 {
+        chrono::Utc::now().timestamp() as u64
+    }
+
+    fn token(claims: serde_json::Value) -> String {
+        encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    async fn whoami(user: AuthenticatedUser) -> String {
+        format!("{} {}", user.user_id, user.scopes.join(","))
+    }
+
+    async fn call(
+        config: JwtConfig,
+        uri: &str,
+        authorization: Option<&str>,
+    ) -> (StatusCode, String) {
+        let config = Arc::new(config);
+        let app = Router::new()
+            .route("/whoami", get(whoami))
+            .route("/health", get(|| async { "ok" }))
+            .layer(middleware::from_fn(move |req, next| {
+                jwt_auth_middleware(config.clone(), req, next)
+            }));
+        let mut request = Request::get(uri);
+        if let Some(authorization) = authorization {
+            request = request.header("authorization", authorization);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_injects_user() {
+        let token = token(json!({
+            "sub": "alice",
+            "exp": now() + 600,
+            "scopes": ["search"],
+            "scope": "search saved_searches",
+        }));
+        let (status, body) = call(
+            JwtConfig::hs256(SECRET),
+            "/whoami",
+            Some(&format!("Bearer {}", token)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "alice search,saved_searches");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_malformed_and_expired_tokens() {
+        let expired = token(json!({"sub": "alice", "exp": now() - 3600}));
+        let forged = encode(
+            &Header::new(Algorithm::HS256),
+            &json!({"sub": "mallory", "exp": now() + 600}),
+            &EncodingKey::from_secret(b"other-secret"),
+        )
+        .unwrap();
+
+        for authorization in [
+            "Basic YWxpY2U6c2VjcmV0".to_string(),
+            "Bearer not.a.jwt".to_string(),
+            format!("Bearer {}", expired),
+            format!("Bearer {}", forged),
+        ] {
+            let (status, _) = call(JwtConfig::hs256(SECRET), "/whoami", Some(&authorization)).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", authorization);
+        }
+
+        let (status, _) = call(JwtConfig::hs256(SECRET), "/whoami", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = call(JwtConfig::hs256(SECRET), "/health", None).await;
+        assert_eq!((status, body.as_str()), (StatusCode::OK, "ok"));
+    }
+
+    #[tokio::test]
+    async fn test_checks_audience_and_issuer() {
+        let config = || {
+            JwtConfig::hs256(SECRET)
+                .with_audience("xze")
+                .with_issuer("https://auth.example.com")
+        };
+        let good = token(json!({
+            "sub": "alice",
+            "exp": now() + 600,
+            "aud": "xze",
+            "iss": "https://auth.example.com",
+        }));
+        let wrong_audience = token(json!({
+            "sub": "alice",
+            "exp": now() + 600,
+            "aud": "other",
+            "iss": "https://auth.example.com",
+        }));
+        let no_issuer = token(json!({"sub": "alice", "exp": now() + 600, "aud": "xze"}));
+
+        let (status, _) = call(config(), "/whoami", Some(&format!("Bearer {}", good))).await;
+        assert_eq!(status, StatusCode::OK);
+        for token in [wrong_audience, no_issuer] {
+            let (status, _) = call(config(), "/whoami", Some(&format!("Bearer {}", token))).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[test]
+    fn test_rs256_key_and_algorithm_family() {
+        assert!(matches!(
+            JwtConfig::rs256_pem("not a key"),
+            Err(XzeError::Validation { .. })
+        ));
+
+        let hs256 = token(json!({"sub": "alice", "exp": now() + 600}));
+        let config = JwtConfig::hs256(SECRET).with_algorithms(vec![Algorithm::RS256]);
+        assert_eq!(
+            config.verify(&hs256).unwrap_err().kind(),
+            &ErrorKind::InvalidAlgorithm
+        );
+        assert!(!format!("{:?}", JwtConfig::hs256(SECRET)).contains(SECRET));
+    }
+}
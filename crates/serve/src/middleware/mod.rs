@@ -1,11 +1,14 @@
 //! Middleware module for XZe serve crate
 //!
 //! Provides comprehensive middleware for rate limiting, security,
-//! authentication, and request processing.
+//! authentication, request timeouts, and request processing.
 
+pub mod auth;
 pub mod rate_limit;
 pub mod security;
+pub mod timeout;
 
+pub use auth::{jwt_auth_middleware, AuthenticatedUser, Claims, JwtConfig};
 pub use rate_limit::{
     api_key_middleware, create_rate_limiter, rate_limit_middleware, request_validation_middleware,
     RateLimitConfig, SharedRateLimiter,
@@ -13,3 +16,4 @@ pub use rate_limit::{
 pub use security::{
     cors_middleware, input_sanitization_middleware, security_headers_middleware, CorsConfig,
 };
+pub use timeout::{timeout_middleware, RouteTimeout, TimeoutConfig};
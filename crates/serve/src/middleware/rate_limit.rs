@@ -239,7 +239,13 @@ pub async fn api_key_middleware(
 ) -> Result<Response, StatusCode> {
     // Skip auth for health check endpoints
     let path = request.uri().path();
-    if path == "/health" || path == "/api/v1/health" || path == "/metrics" {
+    if path == "/health"
+        || path == "/api/v1/health"
+        || path == "/metrics"
+        || path == "/metrics/load"
+        || path == "/livez"
+        || path == "/readyz"
+    {
         return Ok(next.run(request).await);
     }
 
@@ -0,0 +1,316 @@
+//! Request timeout middleware
+//!
+//! Aborts handlers that run longer than their route's timeout and returns
+//! `504 Gateway Timeout`, so a stalled database or Ollama call cannot hold
+//! a connection indefinitely. Requests that finish but take longer than the
+//! slow-request threshold are logged. Streaming endpoints are exempt.
+
+use axum::{
+    extract::Request,
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Header carrying the request identifier
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Timeout for requests whose path matches a route pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteTimeout {
+    /// Path segment pattern, such as `/analyze`
+    pub pattern: String,
+    /// Time allowed for matching requests
+    pub timeout: Duration,
+}
+
+impl RouteTimeout {
+    /// Creates a route timeout
+    pub fn new(pattern: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            pattern: pattern.into(),
+            timeout,
+        }
+    }
+}
+
+/// Request timeout configuration
+///
+/// Patterns match whole path segments anywhere in the path, so `/analyze`
+/// matches `/analyze`, `/api/v1/analyze` and `/repositories/:id/analyze`,
+/// and `/search` matches `/api/v1/search` and `/api/search/advanced`. When
+/// several route patterns match, the longest one wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutConfig {
+    /// Apply timeouts at all
+    pub enabled: bool,
+    /// Timeout for requests that match no route pattern
+    pub default_timeout: Duration,
+    /// Per-route timeouts
+    pub routes: Vec<RouteTimeout>,
+    /// Patterns that are never timed out, such as streaming endpoints
+    pub exempt: Vec<String>,
+    /// Completed requests slower than this are logged
+    pub slow_request_threshold: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_timeout: Duration::from_secs(30),
+            routes: vec![
+                RouteTimeout::new("/analyze", Duration::from_secs(600)),
+                RouteTimeout::new("/search", Duration::from_secs(10)),
+            ],
+            exempt: vec!["/ws".to_string()],
+            slow_request_threshold: Duration::from_secs(5),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// Timeout for `path`, or `None` if the path is exempt or timeouts are
+    /// disabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use xze_serve::middleware::timeout::TimeoutConfig;
+    ///
+    /// let config = TimeoutConfig::default();
+    /// assert_eq!(config.timeout_for("/api/v1/search"), Some(Duration::from_secs(10)));
+    /// assert_eq!(config.timeout_for("/api/v1/health"), Some(Duration::from_secs(30)));
+    /// assert_eq!(config.timeout_for("/ws/search"), None);
+    /// ```
+    pub fn timeout_for(&self, path: &str) -> Option<Duration> {
+        if !self.enabled
+            || self
+                .exempt
+                .iter()
+                .any(|pattern| matches_segments(path, pattern))
+        {
+            return None;
+        }
+
+        let timeout = self
+            .routes
+            .iter()
+            .filter(|route| matches_segments(path, &route.pattern))
+            .max_by_key(|route| route.pattern.len())
+            .map(|route| route.timeout)
+            .unwrap_or(self.default_timeout);
+        Some(timeout)
+    }
+}
+
+/// Whether `pattern` occurs in `path` on segment boundaries
+fn matches_segments(path: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+
+    path.match_indices(pattern).any(|(start, _)| {
+        let before_ok = pattern.starts_with('/') || start == 0 || path[..start].ends_with('/');
+        let rest = &path[start + pattern.len()..];
+        before_ok && (rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// Request timeout middleware
+///
+/// Runs the rest of the stack under the route's timeout. On timeout the
+/// handler future is dropped, which cancels it, and a 504 is returned. The
+/// `x-request-id` header is reused when the client sends one and generated
+/// otherwise; it is logged with timed out and slow requests and echoed on
+/// 504 responses.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use axum::{Router, middleware};
+/// use xze_serve::middleware::timeout::{timeout_middleware, TimeoutConfig};
+///
+/// let config = Arc::new(TimeoutConfig::default());
+///
+/// let app: Router = Router::new()
+///     .layer(middleware::from_fn(move |req, next| {
+///         timeout_middleware(config.clone(), req, next)
+///     }));
+/// ```
+pub async fn timeout_middleware(
+    config: Arc<TimeoutConfig>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(timeout) = config.timeout_for(request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let request_id = match request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+    {
+        Some(id) => id.to_string(),
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                request.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+            id
+        }
+    };
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => {
+            let elapsed = start.elapsed();
+            if elapsed >= config.slow_request_threshold {
+                tracing::warn!(
+                    request_id = %request_id,
+                    method = %method,
+                    path = %path,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    "Slow request"
+                );
+            }
+            response
+        }
+        Err(_) => {
+            tracing::error!(
+                request_id = %request_id,
+                method = %method,
+                path = %path,
+                timeout_ms = timeout.as_millis() as u64,
+                "Request timed out; handler aborted"
+            );
+
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                [(REQUEST_ID_HEADER, request_id)],
+                "Request timed out",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(config: TimeoutConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route(
+                "/api/v1/search",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    "done"
+                }),
+            )
+            .route("/api/v1/health", get(|| async { "ok" }))
+            .route(
+                "/ws/search",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    "streamed"
+                }),
+            )
+            .layer(middleware::from_fn(move |req, next| {
+                timeout_middleware(config.clone(), req, next)
+            }))
+    }
+
+    fn short_timeouts() -> TimeoutConfig {
+        TimeoutConfig {
+            default_timeout: Duration::from_secs(5),
+            routes: vec![RouteTimeout::new("/search", Duration::from_millis(20))],
+            ..Default::default()
+        }
+    }
+
+    fn get_request(path: &str) -> Request {
+        Request::builder().uri(path).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_timeout_for_uses_longest_matching_route() {
+        let config = TimeoutConfig {
+            routes: vec![
+                RouteTimeout::new("/search", Duration::from_secs(10)),
+                RouteTimeout::new("/search/advanced", Duration::from_secs(20)),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.timeout_for("/api/search/advanced"),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(config.timeout_for("/search"), Some(Duration::from_secs(10)));
+        // Segment boundaries are respected
+        assert_eq!(
+            config.timeout_for("/api/v1/searches"),
+            Some(config.default_timeout)
+        );
+        assert_eq!(
+            TimeoutConfig::default().timeout_for("/repositories/a/analyze"),
+            Some(Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn test_timeout_for_disabled_and_exempt() {
+        let disabled = TimeoutConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(disabled.timeout_for("/api/v1/search"), None);
+        assert_eq!(TimeoutConfig::default().timeout_for("/ws/search"), None);
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_returns_gateway_timeout() {
+        let request = Request::builder()
+            .uri("/api/v1/search")
+            .header(REQUEST_ID_HEADER, "req-123")
+            .body(Body::empty())
+            .unwrap();
+
+        let start = Instant::now();
+        let response = app(short_timeouts()).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-123");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_fast_and_exempt_requests_pass_through() {
+        let response = app(short_timeouts())
+            .oneshot(get_request("/api/v1/health"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Slower than the search timeout, but streaming routes are exempt
+        let response = app(short_timeouts())
+            .oneshot(get_request("/ws/search"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -447,6 +447,7 @@ mod tests {
             result_count,
             execution_time_ms: 100,
             zero_results,
+            query_language: None,
             timestamp: Utc::now(),
         })
     }
@@ -111,6 +111,7 @@ impl AnalyticsCollector {
     ///     result_count: 10,
     ///     execution_time_ms: 150,
     ///     zero_results: false,
+    ///     query_language: None,
     ///     timestamp: Utc::now(),
     /// };
     ///
@@ -293,6 +294,7 @@ mod tests {
             result_count: 10,
             execution_time_ms: 100,
             zero_results: false,
+            query_language: None,
             timestamp: Utc::now(),
         }
     }
@@ -5,7 +5,8 @@
 use super::aggregator::AnalyticsAggregator;
 use super::collector::AnalyticsCollector;
 use super::types::{
-    AnalyticsReportRequest, AnalyticsReportResponse, TrackEventRequest, TrackEventResponse,
+    AnalyticsEvent, AnalyticsReportRequest, AnalyticsReportResponse, TrackEventRequest,
+    TrackEventResponse,
 };
 use axum::{
     extract::State,
@@ -96,8 +97,13 @@ pub async fn handle_track_event(
 ) -> Result<Json<TrackEventResponse>, AnalyticsError> {
     debug!("Tracking analytics event: {:?}", request.event);
 
+    let mut event = request.event;
+    if let AnalyticsEvent::SearchQuery(ref mut query_event) = event {
+        query_event.detect_query_language();
+    }
+
     // Track the event
-    state.collector.track(request.event).await;
+    state.collector.track(event).await;
 
     info!("Analytics event tracked successfully");
 
@@ -329,6 +335,7 @@ mod tests {
             result_count: 10,
             execution_time_ms: 150,
             zero_results: false,
+            query_language: None,
             timestamp: Utc::now(),
         })
     }
@@ -42,10 +42,26 @@ pub struct SearchQueryEvent {
     pub execution_time_ms: u64,
     /// Whether this was a zero-result query
     pub zero_results: bool,
+    /// Detected query language (ISO 639-1 code), if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_language: Option<String>,
     /// Timestamp
     pub timestamp: DateTime<Utc>,
 }
 
+impl SearchQueryEvent {
+    /// Fill in `query_language` by detecting the language of the query
+    ///
+    /// Leaves an existing value untouched and keeps `None` when the
+    /// language cannot be determined.
+    pub fn detect_query_language(&mut self) {
+        if self.query_language.is_none() {
+            self.query_language =
+                xze_core::search::detect_language(&self.query).map(|lang| lang.code().to_string());
+        }
+    }
+}
+
 /// Result click event
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ResultClickEvent {
@@ -246,6 +262,7 @@ mod tests {
             result_count: 42,
             execution_time_ms: 150,
             zero_results: false,
+            query_language: None,
             timestamp: Utc::now(),
         };
 
@@ -254,6 +271,29 @@ mod tests {
         assert_eq!(event, deserialized);
     }
 
+    #[test]
+    fn test_search_query_event_detects_language() {
+        let mut event = SearchQueryEvent {
+            query_id: "q1".to_string(),
+            session_id: "s1".to_string(),
+            user_id: None,
+            query: "comment configurer le serveur".to_string(),
+            query_type: QueryType::Simple,
+            filters: None,
+            result_count: 0,
+            execution_time_ms: 10,
+            zero_results: true,
+            query_language: None,
+            timestamp: Utc::now(),
+        };
+
+        event.detect_query_language();
+        assert_eq!(event.query_language.as_deref(), Some("fr"));
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""query_language":"fr""#));
+    }
+
     #[test]
     fn test_result_click_event_serialization() {
         let event = ResultClickEvent {
@@ -289,6 +329,7 @@ mod tests {
             result_count: 10,
             execution_time_ms: 100,
             zero_results: false,
+            query_language: None,
             timestamp: Utc::now(),
         };
 
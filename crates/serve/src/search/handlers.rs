@@ -20,12 +20,15 @@
 //! # }
 //! ```
 
+use crate::cache::{CacheScope, CachedSearchResponse, SearchCacheKey, SharedSearchCache};
+use crate::middleware::auth::AuthenticatedUser;
 use crate::search::types::{
-    AdvancedSearchRequest, AggregationResponse, CategoryCount, PaginationInfo, SearchError,
-    SearchResponse, SearchResult,
+    collapse_results, sort_results, AdvancedSearchRequest, AggregationResponse, CategoryCount,
+    PaginationInfo, ResultPage, ScoreExplanation, SearchError, SearchLimits, SearchResponse,
+    SearchResult,
 };
 use axum::{
-    extract::{Json, Query},
+    extract::{FromRef, Json, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::{get, post},
@@ -34,11 +37,16 @@ use axum::{
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{error, info, warn};
+use xze_core::search::rerank::{RerankCandidate, Reranker};
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
 
+/// Candidates fetched per requested result when `dedup_by` is enabled
+const DEDUP_OVERFETCH_FACTOR: usize = 3;
+
 /// Query parameters for GET search endpoint
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -65,6 +73,82 @@ fn default_limit() -> usize {
     20
 }
 
+/// Shared state for the search routes
+#[derive(Debug, Clone, Default)]
+pub struct SearchState {
+    /// Maximum page size and offset accepted by the handlers
+    pub limits: SearchLimits,
+
+    /// Reranker used when a request sets `rerank_top_n` (default: none,
+    /// which rejects such requests)
+    pub reranker: Option<Arc<Reranker>>,
+
+    /// Cache of successful responses (default: none)
+    pub cache: Option<SharedSearchCache>,
+}
+
+impl SearchState {
+    /// Creates search state with the given limits, no reranker and no cache
+    pub fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            reranker: None,
+            cache: None,
+        }
+    }
+
+    /// Serves repeated searches from `cache`
+    ///
+    /// Subscribe the cache to the search index with
+    /// [`SearchCache::watch_index`](crate::cache::SearchCache::watch_index)
+    /// so document changes evict the responses they affect.
+    pub fn with_cache(mut self, cache: SharedSearchCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the cached response for `key`, if any
+    async fn cached(&self, key: &SearchCacheKey) -> Option<SearchResponse> {
+        let cached = self.cache.as_ref()?.get(key).await?;
+        match serde_json::from_str(&cached.results) {
+            Ok(response) => Some(response),
+            Err(e) => {
+                warn!("Ignoring unreadable cached search response: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Caches `response` under `key`, scoped to the documents it could
+    /// have matched
+    async fn cache(&self, key: SearchCacheKey, scope: CacheScope, response: &SearchResponse) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        match serde_json::to_string(response) {
+            Ok(json) => {
+                let cached =
+                    CachedSearchResponse::new(response.query.clone(), json, response.total_results)
+                        .with_scope(scope);
+                cache.set(key, cached).await;
+            }
+            Err(e) => warn!("Failed to cache search response: {}", e),
+        }
+    }
+
+    /// Enables reranking with the given reranker
+    pub fn with_reranker(mut self, reranker: Arc<Reranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+}
+
+impl FromRef<SearchState> for SearchLimits {
+    fn from_ref(state: &SearchState) -> Self {
+        state.limits
+    }
+}
+
 /// Error response structure
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -81,6 +165,7 @@ impl IntoResponse for SearchError {
             SearchError::InvalidQuery(msg) => (StatusCode::BAD_REQUEST, msg),
             SearchError::InvalidFilter(msg) => (StatusCode::BAD_REQUEST, msg),
             SearchError::InvalidOptions(msg) => (StatusCode::BAD_REQUEST, msg),
+            SearchError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             SearchError::DatabaseError(msg) => {
                 error!("Database error during search: {}", msg);
                 (
@@ -106,7 +191,7 @@ impl IntoResponse for SearchError {
     }
 }
 
-/// Creates the search routes
+/// Creates the search routes with the default pagination limits
 ///
 /// # Examples
 ///
@@ -119,9 +204,62 @@ impl IntoResponse for SearchError {
 /// # }
 /// ```
 pub fn search_routes() -> Router {
+    search_routes_with_limits(SearchLimits::default())
+}
+
+/// Creates the search routes with custom pagination limits
+///
+/// # Arguments
+///
+/// * `limits` - Maximum page size and offset accepted by the handlers
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use xze_serve::search::handlers::search_routes_with_limits;
+/// use xze_serve::search::types::SearchLimits;
+///
+/// # async fn example() {
+/// let limits = SearchLimits {
+///     max_results: 50,
+///     max_offset: 1_000,
+/// };
+/// let app = Router::new().nest("/api", search_routes_with_limits(limits));
+/// # }
+/// ```
+pub fn search_routes_with_limits(limits: SearchLimits) -> Router {
+    search_routes_with_state(SearchState::new(limits))
+}
+
+/// Creates the search routes with custom limits and optional reranking
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use std::sync::Arc;
+/// use xze_core::ai::AIAnalysisService;
+/// use xze_core::config::ModelConfig;
+/// use xze_core::search::rerank::Reranker;
+/// use xze_serve::search::handlers::{search_routes_with_state, SearchState};
+/// use xze_serve::search::types::SearchLimits;
+///
+/// # async fn example() {
+/// let service = AIAnalysisService::new(
+///     "http://localhost:11434".to_string(),
+///     ModelConfig::default(),
+/// );
+/// let state = SearchState::new(SearchLimits::default())
+///     .with_reranker(Arc::new(Reranker::new(Arc::new(service))));
+/// let app = Router::new().nest("/api", search_routes_with_state(state));
+/// # }
+/// ```
+pub fn search_routes_with_state(state: SearchState) -> Router {
     Router::new()
         .route("/search", get(handle_search))
         .route("/search/advanced", post(handle_search_advanced))
+        .with_state(state)
 }
 
 /// GET search handler for simple queries
@@ -132,7 +270,8 @@ pub fn search_routes() -> Router {
 ///
 /// - `q`: Search query string (required)
 /// - `limit`: Maximum results to return (default: 20, max: 100)
-/// - `offset`: Offset for pagination (default: 0)
+/// - `offset`: Offset for pagination (default: 0, max: 10000); use
+///   cursor-based pagination beyond that
 /// - `category`: Filter by category (optional)
 /// - `repository`: Filter by repository (optional)
 ///
@@ -154,7 +293,7 @@ pub fn search_routes() -> Router {
     params(
         ("q" = String, Query, description = "Search query string"),
         ("limit" = Option<usize>, Query, description = "Maximum number of results (default: 20, max: 100)"),
-        ("offset" = Option<usize>, Query, description = "Offset for pagination (default: 0)"),
+        ("offset" = Option<usize>, Query, description = "Offset for pagination (default: 0, max: 10000)"),
         ("category" = Option<String>, Query, description = "Filter by documentation category"),
         ("repository" = Option<String>, Query, description = "Filter by repository name"),
     ),
@@ -165,11 +304,16 @@ pub fn search_routes() -> Router {
     )
 ))]
 pub async fn handle_search(
+    State(state): State<SearchState>,
+    user: Option<AuthenticatedUser>,
     Query(params): Query<SearchQuery>,
 ) -> Result<Json<SearchResponse>, SearchError> {
     info!(
-        "GET search request: query='{}', limit={}, offset={}",
-        params.q, params.limit, params.offset
+        "GET search request: query='{}', limit={}, offset={}, user={}",
+        params.q,
+        params.limit,
+        params.offset,
+        user.as_ref().map_or("anonymous", |u| u.user_id.as_str())
     );
 
     // Validate query
@@ -180,16 +324,30 @@ pub async fn handle_search(
         ));
     }
 
+    let limits = state.limits;
+
     // Validate limit
     if params.limit == 0 {
         return Err(SearchError::InvalidOptions(
             "Limit must be greater than 0".to_string(),
         ));
     }
-    if params.limit > 100 {
-        return Err(SearchError::InvalidOptions(
-            "Limit cannot exceed 100".to_string(),
-        ));
+    if params.limit > limits.max_results {
+        return Err(SearchError::InvalidOptions(format!(
+            "Limit cannot exceed {}",
+            limits.max_results
+        )));
+    }
+    limits.check_offset(params.offset)?;
+
+    let cache_key = SearchCacheKey::new(
+        params.q.clone(),
+        cache_part(&(&params.category, &params.repository)),
+        cache_part(&(params.limit, params.offset)),
+    );
+    if let Some(response) = state.cached(&cache_key).await {
+        info!("GET search served from cache for query '{}'", params.q);
+        return Ok(Json(response));
     }
 
     // Perform search (mock implementation for now)
@@ -211,6 +369,12 @@ pub async fn handle_search(
         total_results, params.q
     );
 
+    let scope = CacheScope {
+        categories: params.category.clone().map(|c| vec![c]),
+        repositories: params.repository.clone().map(|r| vec![r]),
+    };
+    state.cache(cache_key, scope, &response).await;
+
     Ok(Json(response))
 }
 
@@ -230,7 +394,24 @@ pub async fn handle_search(
 ///
 /// Returns `SearchError::InvalidQuery` if query is invalid
 /// Returns `SearchError::InvalidFilter` if filters are invalid
-/// Returns `SearchError::InvalidOptions` if options are invalid
+/// Returns `SearchError::InvalidOptions` if options are invalid, such as
+/// an unknown sort field, or if `rerank_top_n` is set and no reranker is
+/// configured
+///
+/// # Pagination
+///
+/// Pages are chosen by `options.offset` or by `options.cursor`, not both.
+/// Responses carry `pagination.cursor` when another page follows and
+/// `pagination.prev_cursor` when one precedes; passing either back as
+/// `options.cursor` continues from the result it points at, so results
+/// added or removed in between do not skip or repeat rows the way offsets
+/// do.
+///
+/// # Sorting
+///
+/// Results come back in relevance order unless `options.sort` is set.
+/// Sort clauses apply after filtering, collapsing and reranking, and any
+/// field other than `relevance` replaces the similarity-based order.
 ///
 /// # Examples
 ///
@@ -247,7 +428,10 @@ pub async fn handle_search(
 ///   "options": {
 ///     "max_results": 50,
 ///     "include_snippets": true,
-///     "highlight_terms": true
+///     "highlight_terms": true,
+///     "rerank_top_n": 20,
+///     "explain": true,
+///     "sort": [{ "field": "updated_at", "order": "desc" }]
 ///   },
 ///   "aggregations": {
 ///     "by_category": true
@@ -266,12 +450,13 @@ pub async fn handle_search(
     )
 ))]
 pub async fn handle_search_advanced(
+    State(state): State<SearchState>,
     Json(request): Json<AdvancedSearchRequest>,
 ) -> Result<Json<SearchResponse>, SearchError> {
     info!("POST advanced search request: query='{}'", request.query);
 
     // Validate request
-    request.validate()?;
+    request.validate_with_limits(&state.limits)?;
 
     // Extract options with defaults
     let max_results = request
@@ -300,8 +485,123 @@ pub async fn handle_search_advanced(
         max_results, offset, include_snippets, highlight_terms
     );
 
+    let dedup_by = request.options.as_ref().and_then(|o| o.dedup_by.clone());
+    let rerank_top_n = request.options.as_ref().and_then(|o| o.rerank_top_n);
+    let explain = request
+        .options
+        .as_ref()
+        .map(|o| o.get_explain())
+        .unwrap_or(false);
+
+    let sort = request
+        .options
+        .as_ref()
+        .and_then(|o| o.sort.clone())
+        .filter(|clauses| !clauses.is_empty());
+
+    let reranker = match (rerank_top_n, &state.reranker) {
+        (Some(_), None) => {
+            return Err(SearchError::InvalidOptions(
+                "rerank_top_n requires reranking to be enabled on the server".to_string(),
+            ))
+        }
+        (Some(top_n), Some(reranker)) => Some((top_n, reranker)),
+        (None, _) => None,
+    };
+
+    let cursor = request
+        .options
+        .as_ref()
+        .map(|o| o.get_cursor())
+        .transpose()?
+        .flatten();
+
+    let cache_key = SearchCacheKey::new(
+        request.query.clone(),
+        cache_part(&request.filters),
+        cache_part(&request),
+    );
+    if let Some(response) = state.cached(&cache_key).await {
+        info!(
+            "POST advanced search served from cache for query '{}'",
+            request.query
+        );
+        return Ok(Json(response));
+    }
+
     // Perform advanced search (mock implementation for now)
-    let results = perform_advanced_search(&request, max_results, offset).await?;
+    let page: ResultPage<(SearchResult, Option<f32>)> =
+        if dedup_by.is_some() || reranker.is_some() || sort.is_some() || cursor.is_some() {
+            // Collapse, rerank and sort before paginating so each page holds
+            // `max_results` distinct results in their final order; over-fetch
+            // to leave room for merged duplicates. The mock backend cannot
+            // seek to a cursor's key, so cursors are resolved in the window
+            // of candidates up to the deepest allowed offset.
+            let window = match cursor {
+                Some(_) => state.limits.max_offset + max_results + 1,
+                None => offset + max_results + 1,
+            };
+            let mut fetch = match dedup_by {
+                Some(_) => window * DEDUP_OVERFETCH_FACTOR,
+                None => window,
+            };
+            if let Some((top_n, _)) = reranker {
+                fetch = fetch.max(top_n);
+            }
+
+            let mut candidates = perform_advanced_search(&request, fetch, 0).await?;
+            if let Some(ref field) = dedup_by {
+                candidates = collapse_results(candidates, field);
+            }
+            let ranked = match reranker {
+                Some((top_n, reranker)) => {
+                    rerank_results(reranker, &request.query, candidates, top_n).await
+                }
+                None => candidates.into_iter().map(|r| (r, None)).collect(),
+            };
+            let ranked = sort_results(ranked, sort.as_deref().unwrap_or_default(), |(r, _)| r);
+            match cursor {
+                Some(ref cursor) => {
+                    ResultPage::after_cursor(ranked, cursor, max_results, |(r, _)| r)?
+                }
+                None => ResultPage::at(ranked, offset, max_results),
+            }
+        } else {
+            // Fetch one extra result to tell whether another page follows
+            let fetched = perform_advanced_search(&request, max_results + 1, offset)
+                .await?
+                .into_iter()
+                .map(|r| (r, None))
+                .collect();
+            ResultPage {
+                has_prev: offset > 0,
+                ..ResultPage::at(fetched, 0, max_results)
+            }
+        };
+
+    let next_cursor = match page.results.last() {
+        Some((last, _)) if page.has_more => Some(last.cursor(true)?),
+        _ => None,
+    };
+    let prev_cursor = match page.results.first() {
+        Some((first, _)) if page.has_prev => Some(first.cursor(false)?),
+        _ => None,
+    };
+    let has_more = page.has_more;
+    let results = page.results;
+
+    let results: Vec<SearchResult> = results
+        .into_iter()
+        .map(|(mut result, rerank_score)| {
+            if explain {
+                result.explanation = Some(ScoreExplanation {
+                    retrieval_score: result.similarity,
+                    rerank_score,
+                });
+            }
+            result
+        })
+        .collect();
 
     // Compute aggregations if requested
     let aggregations = if request.aggregations.is_some() {
@@ -311,7 +611,15 @@ pub async fn handle_search_advanced(
     };
 
     let total_results = results.len();
-    let pagination = PaginationInfo::new(offset, max_results, total_results);
+    let pagination = match cursor {
+        Some(_) => PaginationInfo::cursor_based(max_results, has_more, next_cursor, prev_cursor),
+        None => PaginationInfo {
+            has_more,
+            cursor: next_cursor,
+            prev_cursor,
+            ..PaginationInfo::new(offset, max_results, total_results)
+        },
+    };
 
     let response = SearchResponse {
         query: request.query.clone(),
@@ -326,9 +634,43 @@ pub async fn handle_search_advanced(
         total_results, request.query
     );
 
+    let scope = CacheScope::from_filters(request.filters.as_ref());
+    state.cache(cache_key, scope, &response).await;
+
     Ok(Json(response))
 }
 
+/// Serializes part of a request for a cache key
+fn cache_part<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Reranks retrieved results by model-judged relevance
+///
+/// Reranking only refines precision, so if scoring fails the results keep
+/// their retrieval order rather than failing the search.
+async fn rerank_results(
+    reranker: &Reranker,
+    query: &str,
+    results: Vec<SearchResult>,
+    top_n: usize,
+) -> Vec<(SearchResult, Option<f32>)> {
+    let fallback = results.clone();
+    match reranker
+        .rerank(query, results, top_n, |result| RerankCandidate {
+            id: result.id.clone(),
+            text: format!("{}\n\n{}", result.title, result.content),
+        })
+        .await
+    {
+        Ok(ranked) => ranked,
+        Err(e) => {
+            warn!("Reranking failed, keeping retrieval order: {}", e);
+            fallback.into_iter().map(|r| (r, None)).collect()
+        }
+    }
+}
+
 /// Performs a simple search (mock implementation)
 ///
 /// This is a placeholder that will be replaced with actual database queries
@@ -383,6 +725,8 @@ async fn perform_search(
             path: format!("docs/{}/document_{}.md", category, i + 1),
             tags: vec!["rust".to_string(), "documentation".to_string()],
             updated_at: Utc::now(),
+            collapsed_count: None,
+            explanation: None,
         });
     }
 
@@ -483,6 +827,8 @@ async fn perform_advanced_search(
             path: format!("docs/{}/advanced_{}.md", category, i + 1),
             tags: vec!["rust".to_string(), "advanced".to_string()],
             updated_at: Utc::now(),
+            collapsed_count: None,
+            explanation: None,
         });
     }
 
@@ -552,7 +898,10 @@ fn compute_aggregations(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::search::types::{AggregationRequest, SearchFilters, SearchOptions, SimilarityRange};
+    use crate::search::types::{
+        AggregationRequest, PaginationCursor, SearchFilters, SearchOptions, SimilarityRange,
+        SortClause, SortOrder,
+    };
 
     #[tokio::test]
     async fn test_handle_search_advanced_success() {
@@ -573,11 +922,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(false),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -596,7 +950,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_err());
     }
 
@@ -620,7 +974,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
     }
 
@@ -639,7 +993,7 @@ mod tests {
             }),
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -683,6 +1037,11 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(true),
                 group_by: Some("category".to_string()),
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: Some(AggregationRequest {
                 by_category: Some(true),
@@ -691,7 +1050,7 @@ mod tests {
             }),
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -719,7 +1078,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_err());
     }
 
@@ -740,7 +1099,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_err());
     }
 
@@ -757,11 +1116,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(false),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_err());
     }
 
@@ -778,11 +1142,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(false),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_err());
     }
 
@@ -799,11 +1168,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(false),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -824,11 +1198,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(true),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -849,7 +1228,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -879,7 +1258,7 @@ mod tests {
 
         // Note: This might fail validation due to max_results=0
         // This test verifies that aggregations can be requested without results
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         // The actual behavior depends on implementation
         assert!(result.is_ok() || result.is_err());
     }
@@ -942,7 +1321,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
     }
 
@@ -967,7 +1346,7 @@ mod tests {
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
     }
 
@@ -984,11 +1363,16 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(true),
                 group_by: None,
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -1013,11 +1397,16 @@ mod tests {
                 include_snippets: Some(false),
                 highlight_terms: Some(false),
                 group_by: Some("category".to_string()),
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
     }
 
@@ -1034,11 +1423,250 @@ mod tests {
                 include_snippets: Some(false),
                 highlight_terms: Some(false),
                 group_by: Some("repository".to_string()),
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: None,
         };
 
-        let result = handle_search_advanced(Json(request)).await;
+        let result = handle_search_advanced(State(SearchState::default()), Json(request)).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_dedup_by_repository() {
+        let request = AdvancedSearchRequest {
+            query: "documentation".to_string(),
+            multi_match: None,
+            bool_query: None,
+            filters: None,
+            options: Some(SearchOptions {
+                max_results: Some(5),
+                offset: Some(0),
+                include_snippets: Some(false),
+                highlight_terms: Some(false),
+                group_by: None,
+                dedup_by: Some("repository".to_string()),
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
+            }),
+            aggregations: None,
+        };
+
+        let response = handle_search_advanced(State(SearchState::default()), Json(request))
+            .await
+            .unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].repository, "xze");
+        assert!(response.results[0].collapsed_count.unwrap() > 0);
+    }
+
+    fn sorted_request(sort: Option<Vec<SortClause>>) -> AdvancedSearchRequest {
+        AdvancedSearchRequest {
+            query: "documentation".to_string(),
+            multi_match: None,
+            bool_query: None,
+            filters: None,
+            options: Some(SearchOptions {
+                max_results: Some(5),
+                sort,
+                ..Default::default()
+            }),
+            aggregations: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_defaults_to_relevance_order() {
+        let response =
+            handle_search_advanced(State(SearchState::default()), Json(sorted_request(None)))
+                .await
+                .unwrap();
+
+        let similarities: Vec<f32> = response
+            .results
+            .iter()
+            .map(|r| r.similarity.unwrap())
+            .collect();
+        assert_eq!(similarities.len(), 5);
+        assert!(similarities.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_sorts_before_paginating() {
+        let sort = vec![SortClause::new("relevance", SortOrder::Asc)];
+        let response = handle_search_advanced(
+            State(SearchState::default()),
+            Json(sorted_request(Some(sort))),
+        )
+        .await
+        .unwrap();
+
+        let similarities: Vec<f32> = response
+            .results
+            .iter()
+            .map(|r| r.similarity.unwrap())
+            .collect();
+        assert!(similarities.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_rejects_unknown_sort_field() {
+        let sort = vec![SortClause::new("popularity", SortOrder::Desc)];
+        let result = handle_search_advanced(
+            State(SearchState::default()),
+            Json(sorted_request(Some(sort))),
+        )
+        .await;
+
+        assert!(matches!(result, Err(SearchError::InvalidOptions(_))));
+    }
+
+    fn cursor_request(offset: Option<usize>, cursor: Option<String>) -> AdvancedSearchRequest {
+        AdvancedSearchRequest {
+            query: "documentation".to_string(),
+            multi_match: None,
+            bool_query: None,
+            filters: None,
+            options: Some(SearchOptions {
+                max_results: Some(3),
+                offset,
+                cursor,
+                ..Default::default()
+            }),
+            aggregations: None,
+        }
+    }
+
+    async fn search_page(request: AdvancedSearchRequest) -> SearchResponse {
+        handle_search_advanced(State(SearchState::default()), Json(request))
+            .await
+            .unwrap()
+            .0
+    }
+
+    fn ids(response: &SearchResponse) -> Vec<&str> {
+        response.results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_cursor_pagination() {
+        let first = search_page(cursor_request(None, None)).await;
+        assert_eq!(
+            ids(&first),
+            ["doc-advanced-0", "doc-advanced-1", "doc-advanced-2"]
+        );
+        assert!(first.pagination.has_more);
+        assert!(first.pagination.prev_cursor.is_none());
+        let next = first.pagination.cursor.clone().unwrap();
+
+        let second = search_page(cursor_request(None, Some(next))).await;
+        assert_eq!(
+            ids(&second),
+            ["doc-advanced-3", "doc-advanced-4", "doc-advanced-5"]
+        );
+        assert_eq!(second.pagination.offset, None);
+        assert!(second.pagination.has_more);
+        let prev = second.pagination.prev_cursor.clone().unwrap();
+
+        let back = search_page(cursor_request(None, Some(prev))).await;
+        assert_eq!(ids(&back), ids(&first));
+        assert!(back.pagination.prev_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_last_page_has_no_next_cursor() {
+        let mut cursor = None;
+        let mut seen = Vec::new();
+        loop {
+            let page = search_page(cursor_request(None, cursor)).await;
+            seen.extend(ids(&page).into_iter().map(str::to_string));
+            match page.pagination.cursor {
+                Some(next) => cursor = Some(next),
+                None => {
+                    assert!(!page.pagination.has_more);
+                    break;
+                }
+            }
+        }
+        assert_eq!(seen.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_rejects_offset_with_cursor() {
+        let cursor = PaginationCursor::new(1, None, true).encode().unwrap();
+        let both = handle_search_advanced(
+            State(SearchState::default()),
+            Json(cursor_request(Some(3), Some(cursor.clone()))),
+        )
+        .await;
+        assert!(matches!(both, Err(SearchError::InvalidOptions(_))));
+
+        let unknown = handle_search_advanced(
+            State(SearchState::default()),
+            Json(cursor_request(None, Some(cursor))),
+        )
+        .await;
+        assert!(matches!(unknown, Err(SearchError::InvalidOptions(_))));
+    }
+
+    /// Prefers the third mock document
+    struct ThirdDocumentScorer;
+
+    #[async_trait::async_trait]
+    impl xze_core::search::rerank::RelevanceScorer for ThirdDocumentScorer {
+        async fn score(&self, _query: &str, document: &str) -> xze_core::Result<f32> {
+            Ok(if document.contains("Document 3 ") {
+                0.9
+            } else {
+                0.1
+            })
+        }
+    }
+
+    fn rerank_request(explain: bool) -> AdvancedSearchRequest {
+        AdvancedSearchRequest {
+            query: "documentation".to_string(),
+            multi_match: None,
+            bool_query: None,
+            filters: None,
+            options: Some(SearchOptions {
+                max_results: Some(3),
+                rerank_top_n: Some(5),
+                explain: Some(explain),
+                ..Default::default()
+            }),
+            aggregations: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_rerank_reorders_and_explains() {
+        let state = SearchState::default()
+            .with_reranker(Arc::new(Reranker::new(Arc::new(ThirdDocumentScorer))));
+
+        let response = handle_search_advanced(State(state), Json(rerank_request(true)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.results.len(), 3);
+        assert_eq!(response.results[0].id, "doc-advanced-2");
+        let explanation = response.results[0].explanation.as_ref().unwrap();
+        assert_eq!(explanation.rerank_score, Some(0.9));
+        assert_eq!(explanation.retrieval_score, response.results[0].similarity);
+    }
+
+    #[tokio::test]
+    async fn test_handle_search_advanced_rerank_requires_reranker() {
+        let result =
+            handle_search_advanced(State(SearchState::default()), Json(rerank_request(false)))
+                .await;
+
+        assert!(matches!(result, Err(SearchError::InvalidOptions(_))));
+    }
 }
@@ -9,6 +9,7 @@
 //! The search module is organized into:
 //! - `types`: Request/response structures and error types
 //! - `handlers`: HTTP handlers for search endpoints
+//! - `saved`: Per-user saved search endpoints
 //!
 //! # Usage
 //!
@@ -55,6 +56,7 @@
 pub mod analytics;
 pub mod handlers;
 pub mod metrics;
+pub mod saved;
 pub mod types;
 pub mod websocket;
 
@@ -62,12 +64,17 @@ pub mod websocket;
 pub mod openapi;
 
 // Re-export commonly used types
-pub use handlers::{handle_search, handle_search_advanced, search_routes, SearchQuery};
+pub use handlers::{
+    handle_search, handle_search_advanced, search_routes, search_routes_with_limits,
+    search_routes_with_state, SearchQuery, SearchState,
+};
 pub use metrics::SearchMetrics;
+pub use saved::saved_search_routes;
 pub use types::{
     AdvancedSearchRequest, AggregationRequest, AggregationResponse, CategoryCount, DateCount,
-    DateRange, PaginationInfo, SearchError, SearchFilters, SearchOptions, SearchResponse,
-    SearchResult, SimilarityRange, SimilarityRangeCount,
+    DateRange, PaginationInfo, ScoreExplanation, SearchError, SearchFilters, SearchLimits,
+    SearchOptions, SearchResponse, SearchResult, SimilarityRange, SimilarityRangeCount, SortClause,
+    SortOrder,
 };
 
 #[cfg(feature = "openapi")]
@@ -44,6 +44,11 @@ use super::types::*;
     paths(
         super::handlers::handle_search,
         super::handlers::handle_search_advanced,
+        super::saved::handle_create_saved_search,
+        super::saved::handle_list_saved_searches,
+        super::saved::handle_get_saved_search,
+        super::saved::handle_update_saved_search,
+        super::saved::handle_delete_saved_search,
     ),
     components(
         schemas(
@@ -56,11 +61,18 @@ use super::types::*;
             DateRange,
             SearchResponse,
             SearchResult,
+            ScoreExplanation,
+            SortClause,
+            SortOrder,
             AggregationResponse,
             CategoryCount,
             SimilarityRangeCount,
             DateCount,
             PaginationInfo,
+            SavedSearch,
+            CreateSavedSearchRequest,
+            UpdateSavedSearchRequest,
+            SavedSearchListResponse,
             ErrorResponse,
         )
     ),
@@ -0,0 +1,466 @@
+//! Saved search endpoints
+//!
+//! Lets authenticated users save search requests and manage them:
+//!
+//! - `POST /search/saved` - Save a search
+//! - `GET /search/saved` - List the user's saved searches, newest first
+//! - `GET /search/saved/:id` - Get one saved search
+//! - `PUT /search/saved/:id` - Change the name, description or request
+//! - `DELETE /search/saved/:id` - Delete a saved search
+//!
+//! Searches are persisted through a [`KbBackend`], normally the PostgreSQL
+//! `KbStore`, and always belong to the [`AuthenticatedUser`] making the
+//! request. Requests without an authenticated user are rejected with 401,
+//! so these endpoints need JWT authentication to be configured. Another
+//! user's search id is reported as 404, the same as one that does not
+//! exist.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use axum::Router;
+//! use std::sync::Arc;
+//! use xze_core::kb::memory::InMemoryKbStore;
+//! use xze_serve::search::saved::saved_search_routes;
+//!
+//! # async fn example() {
+//! let app = Router::new().nest(
+//!     "/api",
+//!     saved_search_routes(Arc::new(InMemoryKbStore::new())),
+//! );
+//! # }
+//! ```
+
+use crate::middleware::auth::AuthenticatedUser;
+use crate::search::types::{
+    AdvancedSearchRequest, CreateSavedSearchRequest, SavedSearch, SavedSearchListResponse,
+    SearchError, UpdateSavedSearchRequest,
+};
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use tracing::info;
+use xze_core::kb::backend::KbBackend;
+use xze_core::kb::error::KbError;
+use xze_core::kb::saved_search::{NewSavedSearch, SavedSearchChanges, SavedSearchRecord};
+
+/// Store holding the saved searches
+pub type SavedSearchStore = Arc<dyn KbBackend>;
+
+/// Creates the saved search routes backed by `store`
+pub fn saved_search_routes(store: SavedSearchStore) -> Router {
+    Router::new()
+        .route(
+            "/search/saved",
+            get(handle_list_saved_searches).post(handle_create_saved_search),
+        )
+        .route(
+            "/search/saved/:id",
+            get(handle_get_saved_search)
+                .put(handle_update_saved_search)
+                .delete(handle_delete_saved_search),
+        )
+        .with_state(store)
+}
+
+/// Save a search for the authenticated user
+///
+/// # Errors
+///
+/// Returns `SearchError::InvalidQuery` if the request is invalid
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/search/saved",
+    tag = "search",
+    request_body = CreateSavedSearchRequest,
+    responses(
+        (status = 201, description = "Search saved", body = SavedSearch),
+        (status = 400, description = "Invalid saved search", body = crate::search::handlers::ErrorResponse),
+        (status = 401, description = "Not authenticated"),
+    )
+))]
+pub async fn handle_create_saved_search(
+    State(store): State<SavedSearchStore>,
+    user: AuthenticatedUser,
+    Json(request): Json<CreateSavedSearchRequest>,
+) -> Result<(StatusCode, Json<SavedSearch>), SearchError> {
+    request.validate()?;
+    let search = SavedSearch::for_user(user.user_id, request);
+    search.validate()?;
+
+    let record = store
+        .create_saved_search(NewSavedSearch {
+            user_id: search.user_id,
+            name: search.name,
+            description: search.description,
+            search_request: to_json(&search.search_request)?,
+        })
+        .await
+        .map_err(store_error)?;
+    info!(
+        "Created saved search {} for user {}",
+        record.id, record.user_id
+    );
+
+    Ok((StatusCode::CREATED, Json(from_record(record)?)))
+}
+
+/// List the authenticated user's saved searches, newest first
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/search/saved",
+    tag = "search",
+    responses(
+        (status = 200, description = "Saved searches of the user", body = SavedSearchListResponse),
+        (status = 401, description = "Not authenticated"),
+    )
+))]
+pub async fn handle_list_saved_searches(
+    State(store): State<SavedSearchStore>,
+    user: AuthenticatedUser,
+) -> Result<Json<SavedSearchListResponse>, SearchError> {
+    let searches = store
+        .list_saved_searches(&user.user_id)
+        .await
+        .map_err(store_error)?
+        .into_iter()
+        .map(from_record)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Json(SavedSearchListResponse {
+        total: searches.len(),
+        searches,
+    }))
+}
+
+/// Get one of the authenticated user's saved searches
+///
+/// # Errors
+///
+/// Returns `SearchError::NotFound` if the user has no search with this id
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/search/saved/{id}",
+    tag = "search",
+    params(("id" = i32, Path, description = "Saved search id")),
+    responses(
+        (status = 200, description = "The saved search", body = SavedSearch),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No such saved search", body = crate::search::handlers::ErrorResponse),
+    )
+))]
+pub async fn handle_get_saved_search(
+    State(store): State<SavedSearchStore>,
+    user: AuthenticatedUser,
+    Path(id): Path<i32>,
+) -> Result<Json<SavedSearch>, SearchError> {
+    let record = store
+        .get_saved_search(&user.user_id, id)
+        .await
+        .map_err(store_error)?
+        .ok_or_else(|| not_found(id))?;
+
+    Ok(Json(from_record(record)?))
+}
+
+/// Change one of the authenticated user's saved searches
+///
+/// Fields missing from the request are left unchanged.
+///
+/// # Errors
+///
+/// Returns `SearchError::InvalidQuery` if the request is invalid and
+/// `SearchError::NotFound` if the user has no search with this id
+#[cfg_attr(feature = "openapi", utoipa::path(
+    put,
+    path = "/search/saved/{id}",
+    tag = "search",
+    params(("id" = i32, Path, description = "Saved search id")),
+    request_body = UpdateSavedSearchRequest,
+    responses(
+        (status = 200, description = "The updated saved search", body = SavedSearch),
+        (status = 400, description = "Invalid update", body = crate::search::handlers::ErrorResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No such saved search", body = crate::search::handlers::ErrorResponse),
+    )
+))]
+pub async fn handle_update_saved_search(
+    State(store): State<SavedSearchStore>,
+    user: AuthenticatedUser,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateSavedSearchRequest>,
+) -> Result<Json<SavedSearch>, SearchError> {
+    request.validate()?;
+    let changes = SavedSearchChanges {
+        name: request.name,
+        description: request.description,
+        search_request: request.search_request.as_ref().map(to_json).transpose()?,
+    };
+
+    let record = store
+        .update_saved_search(&user.user_id, id, changes)
+        .await
+        .map_err(store_error)?
+        .ok_or_else(|| not_found(id))?;
+    info!("Updated saved search {} for user {}", id, user.user_id);
+
+    Ok(Json(from_record(record)?))
+}
+
+/// Delete one of the authenticated user's saved searches
+///
+/// # Errors
+///
+/// Returns `SearchError::NotFound` if the user has no search with this id
+#[cfg_attr(feature = "openapi", utoipa::path(
+    delete,
+    path = "/search/saved/{id}",
+    tag = "search",
+    params(("id" = i32, Path, description = "Saved search id")),
+    responses(
+        (status = 204, description = "Saved search deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No such saved search", body = crate::search::handlers::ErrorResponse),
+    )
+))]
+pub async fn handle_delete_saved_search(
+    State(store): State<SavedSearchStore>,
+    user: AuthenticatedUser,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, SearchError> {
+    if !store
+        .delete_saved_search(&user.user_id, id)
+        .await
+        .map_err(store_error)?
+    {
+        return Err(not_found(id));
+    }
+    info!("Deleted saved search {} for user {}", id, user.user_id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn to_json(request: &AdvancedSearchRequest) -> Result<serde_json::Value, SearchError> {
+    serde_json::to_value(request)
+        .map_err(|e| SearchError::InternalError(format!("Failed to encode search request: {}", e)))
+}
+
+fn from_record(record: SavedSearchRecord) -> Result<SavedSearch, SearchError> {
+    let search_request = serde_json::from_value(record.search_request).map_err(|e| {
+        SearchError::InternalError(format!(
+            "Stored saved search {} has an invalid search request: {}",
+            record.id, e
+        ))
+    })?;
+    Ok(SavedSearch {
+        id: Some(record.id),
+        user_id: record.user_id,
+        name: record.name,
+        description: record.description,
+        search_request,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+    })
+}
+
+fn store_error(error: KbError) -> SearchError {
+    SearchError::DatabaseError(error.to_string())
+}
+
+fn not_found(id: i32) -> SearchError {
+    SearchError::NotFound(format!("Saved search {} not found", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+    use xze_core::kb::memory::InMemoryKbStore;
+
+    fn app() -> Router {
+        saved_search_routes(Arc::new(InMemoryKbStore::new()))
+    }
+
+    async fn send(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        user: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(user) = user {
+            request = request.extension(AuthenticatedUser {
+                user_id: user.to_string(),
+                scopes: Vec::new(),
+            });
+        }
+        let request = match body {
+            Some(body) => request
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, json)
+    }
+
+    fn create(name: &str, query: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "description": null,
+            "search_request": { "query": query },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_crud_round_trip() {
+        let app = app();
+
+        let (status, created) = send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create("Async", "rust async")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["id"], 1);
+        assert_eq!(created["user_id"], "alice");
+        assert_eq!(created["search_request"]["query"], "rust async");
+
+        send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create("Traits", "rust traits")),
+        )
+        .await;
+        let (status, list) = send(&app, "GET", "/search/saved", Some("alice"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list["total"], 2);
+        assert_eq!(list["searches"][0]["name"], "Traits");
+
+        let (status, updated) = send(
+            &app,
+            "PUT",
+            "/search/saved/1",
+            Some("alice"),
+            Some(serde_json::json!({
+                "name": "Async Rust",
+                "description": "Futures and tasks",
+                "search_request": null,
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["name"], "Async Rust");
+        assert_eq!(updated["search_request"]["query"], "rust async");
+        assert_eq!(updated["created_at"], created["created_at"]);
+
+        let (status, fetched) = send(&app, "GET", "/search/saved/1", Some("alice"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(fetched, updated);
+
+        let (status, _) = send(&app, "DELETE", "/search/saved/1", Some("alice"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&app, "GET", "/search/saved/1", Some("alice"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, list) = send(&app, "GET", "/search/saved", Some("alice"), None).await;
+        assert_eq!(list["total"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_searches_are_scoped_by_user() {
+        let app = app();
+        send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create("Async", "rust async")),
+        )
+        .await;
+
+        let (_, list) = send(&app, "GET", "/search/saved", Some("bob"), None).await;
+        assert_eq!(list["total"], 0);
+        for method in ["GET", "DELETE"] {
+            let (status, _) = send(&app, method, "/search/saved/1", Some("bob"), None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{}", method);
+        }
+        let (status, _) = send(
+            &app,
+            "PUT",
+            "/search/saved/1",
+            Some("bob"),
+            Some(serde_json::json!({ "name": "Mine" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = send(&app, "GET", "/search/saved", None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_payloads_are_rejected() {
+        let app = app();
+
+        let (status, body) = send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create(" ", "rust async")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().contains("name"));
+
+        let (status, _) = send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create("Empty", "")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        send(
+            &app,
+            "POST",
+            "/search/saved",
+            Some("alice"),
+            Some(create("Async", "rust async")),
+        )
+        .await;
+        let (status, _) = send(
+            &app,
+            "PUT",
+            "/search/saved/1",
+            Some("alice"),
+            Some(serde_json::json!({ "name": "" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, list) = send(&app, "GET", "/search/saved", Some("alice"), None).await;
+        assert_eq!(list["total"], 1);
+        assert_eq!(list["searches"][0]["name"], "Async");
+    }
+}
@@ -30,6 +30,11 @@
 //!         include_snippets: Some(true),
 //!         highlight_terms: Some(true),
 //!         group_by: None,
+//!         dedup_by: None,
+//!         rerank_top_n: None,
+//!         explain: None,
+//!         sort: None,
+//!         cursor: None,
 //!     }),
 //!     aggregations: None,
 //! };
@@ -40,6 +45,7 @@ use base64::Engine;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use xze_core::search::rerank::MAX_RERANK_TOP_N;
 
 #[cfg(feature = "openapi")]
 #[allow(unused_imports)]
@@ -69,6 +75,10 @@ pub enum SearchError {
     /// Internal search error
     #[error("Internal search error: {0}")]
     InternalError(String),
+
+    /// Requested resource, such as a saved search, does not exist
+    #[error("Not found: {0}")]
+    NotFound(String),
 }
 
 /// Result type for search operations
@@ -132,6 +142,15 @@ impl AdvancedSearchRequest {
     /// Returns `SearchError::InvalidFilter` if filters are invalid
     /// Returns `SearchError::InvalidOptions` if options are invalid
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_limits(&SearchLimits::default())
+    }
+
+    /// Validates the search request against the given pagination limits
+    ///
+    /// # Errors
+    ///
+    /// Same as [`AdvancedSearchRequest::validate`]
+    pub fn validate_with_limits(&self, limits: &SearchLimits) -> Result<()> {
         // Validate that at least one query type is provided
         let has_simple_query = !self.query.trim().is_empty();
         let has_multi_match = self.multi_match.is_some();
@@ -161,7 +180,7 @@ impl AdvancedSearchRequest {
 
         // Validate options
         if let Some(ref options) = self.options {
-            options.validate()?;
+            options.validate_with_limits(limits)?;
         }
 
         Ok(())
@@ -510,6 +529,98 @@ impl SearchFilters {
     }
 }
 
+/// Result fields that `SearchOptions::dedup_by` can collapse on
+pub const DEDUP_FIELDS: &[&str] = &["path", "repository", "category", "title"];
+
+/// Result fields that `SearchOptions::sort` can order by
+pub const SORT_FIELDS: &[&str] = &["relevance", "updated_at", "title", "repository"];
+
+/// Direction of a sort clause
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Smallest, earliest or alphabetically first value first
+    #[default]
+    Asc,
+    /// Largest, latest or alphabetically last value first
+    Desc,
+}
+
+/// One key of a multi-key result sort
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SortClause {
+    /// Field to sort by: relevance, updated_at, title or repository
+    pub field: String,
+
+    /// Sort direction (default: asc)
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl SortClause {
+    /// Sort `field` in `order`
+    pub fn new(field: impl Into<String>, order: SortOrder) -> Self {
+        Self {
+            field: field.into(),
+            order,
+        }
+    }
+}
+
+/// Pagination limits enforced by the search handlers
+///
+/// Deep offsets make the database scan and discard every skipped row, so
+/// offsets above `max_offset` are rejected in favor of cursor-based
+/// pagination.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Largest page size a request may ask for
+    #[serde(default = "default_limit_max_results")]
+    pub max_results: usize,
+
+    /// Largest offset a request may ask for
+    #[serde(default = "default_limit_max_offset")]
+    pub max_offset: usize,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            max_results: default_limit_max_results(),
+            max_offset: default_limit_max_offset(),
+        }
+    }
+}
+
+fn default_limit_max_results() -> usize {
+    100
+}
+
+fn default_limit_max_offset() -> usize {
+    10_000
+}
+
+impl SearchLimits {
+    /// Rejects offsets deeper than `max_offset`
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidOptions` pointing the caller at cursor
+    /// pagination if `offset` exceeds `max_offset`
+    pub fn check_offset(&self, offset: usize) -> Result<()> {
+        if offset > self.max_offset {
+            return Err(SearchError::InvalidOptions(format!(
+                "offset cannot exceed {}; use cursor-based pagination with the \
+                 `cursor` returned in `pagination` to page further",
+                self.max_offset
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Search options for controlling result format and pagination
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -528,6 +639,33 @@ pub struct SearchOptions {
 
     /// Group results by field (e.g., "repository", "category")
     pub group_by: Option<String>,
+
+    /// Collapse results sharing this field to the top-scoring one
+    /// (e.g., "path", "repository"; default: off)
+    #[serde(default)]
+    pub dedup_by: Option<String>,
+
+    /// Rerank this many top candidates by model-judged relevance
+    /// (default: off, max: 50)
+    #[serde(default)]
+    pub rerank_top_n: Option<usize>,
+
+    /// Include per-result score details (default: false)
+    #[serde(default)]
+    pub explain: Option<bool>,
+
+    /// Sort results by these keys in turn, after filtering (default:
+    /// relevance). Sorting by any field other than `relevance` replaces
+    /// the similarity-based order; add a `relevance` clause last to break
+    /// ties by similarity.
+    #[serde(default)]
+    pub sort: Option<Vec<SortClause>>,
+
+    /// Resume after the result this cursor points at, taken from
+    /// `pagination.cursor` or `pagination.prev_cursor` of an earlier
+    /// response (cannot be combined with `offset`)
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 impl SearchOptions {
@@ -537,6 +675,16 @@ impl SearchOptions {
     ///
     /// Returns `SearchError::InvalidOptions` if any option is invalid
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_limits(&SearchLimits::default())
+    }
+
+    /// Validates the search options against the given pagination limits
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidOptions` if any option is invalid or
+    /// exceeds `limits`
+    pub fn validate_with_limits(&self, limits: &SearchLimits) -> Result<()> {
         // Validate max_results
         if let Some(max_results) = self.max_results {
             if max_results == 0 {
@@ -544,11 +692,64 @@ impl SearchOptions {
                     "max_results must be greater than 0".to_string(),
                 ));
             }
-            if max_results > 100 {
+            if max_results > limits.max_results {
+                return Err(SearchError::InvalidOptions(format!(
+                    "max_results cannot exceed {}",
+                    limits.max_results
+                )));
+            }
+        }
+
+        // Validate offset
+        if let Some(offset) = self.offset {
+            limits.check_offset(offset)?;
+        }
+
+        // Validate cursor
+        if let Some(ref cursor) = self.cursor {
+            if self.offset.is_some() {
                 return Err(SearchError::InvalidOptions(
-                    "max_results cannot exceed 100".to_string(),
+                    "offset and cursor cannot be combined; page with one or the other".to_string(),
                 ));
             }
+            PaginationCursor::decode(cursor)?;
+        }
+
+        // Validate dedup_by
+        if let Some(ref field) = self.dedup_by {
+            if !DEDUP_FIELDS.contains(&field.as_str()) {
+                return Err(SearchError::InvalidOptions(format!(
+                    "Invalid dedup_by field: {} (expected one of: {})",
+                    field,
+                    DEDUP_FIELDS.join(", ")
+                )));
+            }
+        }
+
+        // Validate sort
+        for clause in self.sort.iter().flatten() {
+            if !SORT_FIELDS.contains(&clause.field.as_str()) {
+                return Err(SearchError::InvalidOptions(format!(
+                    "Invalid sort field: {} (expected one of: {})",
+                    clause.field,
+                    SORT_FIELDS.join(", ")
+                )));
+            }
+        }
+
+        // Validate rerank_top_n
+        if let Some(top_n) = self.rerank_top_n {
+            if top_n == 0 {
+                return Err(SearchError::InvalidOptions(
+                    "rerank_top_n must be greater than 0".to_string(),
+                ));
+            }
+            if top_n > MAX_RERANK_TOP_N {
+                return Err(SearchError::InvalidOptions(format!(
+                    "rerank_top_n cannot exceed {}",
+                    MAX_RERANK_TOP_N
+                )));
+            }
         }
 
         Ok(())
@@ -564,6 +765,18 @@ impl SearchOptions {
         self.offset.unwrap_or(0)
     }
 
+    /// Gets the decoded cursor, if one was given
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidOptions` if the cursor is malformed
+    pub fn get_cursor(&self) -> Result<Option<PaginationCursor>> {
+        self.cursor
+            .as_deref()
+            .map(PaginationCursor::decode)
+            .transpose()
+    }
+
     /// Gets whether to include snippets
     pub fn get_include_snippets(&self) -> bool {
         self.include_snippets.unwrap_or(true)
@@ -573,6 +786,11 @@ impl SearchOptions {
     pub fn get_highlight_terms(&self) -> bool {
         self.highlight_terms.unwrap_or(false)
     }
+
+    /// Gets whether to include score details
+    pub fn get_explain(&self) -> bool {
+        self.explain.unwrap_or(false)
+    }
 }
 
 /// Aggregation request for computing statistics
@@ -718,6 +936,140 @@ pub struct SearchResult {
 
     /// Last updated timestamp
     pub updated_at: DateTime<Utc>,
+
+    /// Number of results merged into this one by `dedup_by` (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapsed_count: Option<usize>,
+
+    /// Score details (if `explain` is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<ScoreExplanation>,
+}
+
+/// Scores that determined a result's rank
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ScoreExplanation {
+    /// Embedding similarity from retrieval (0.0 to 1.0)
+    pub retrieval_score: Option<f32>,
+
+    /// Model-judged relevance from reranking (0.0 to 1.0), if the result
+    /// was among the reranked candidates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+}
+
+impl SearchResult {
+    /// Gets the value of a collapsible field by name
+    ///
+    /// Returns `None` for fields not listed in [`DEDUP_FIELDS`].
+    pub fn field_value(&self, field: &str) -> Option<&str> {
+        match field {
+            "path" => Some(&self.path),
+            "repository" => Some(&self.repository),
+            "category" => Some(&self.category),
+            "title" => Some(&self.title),
+            _ => None,
+        }
+    }
+
+    /// Stable numeric key of this result, stored as a cursor's `last_id`
+    pub fn cursor_key(&self) -> i64 {
+        let hash = xze_core::calculate_content_hash(&self.id);
+        u64::from_str_radix(&hash[..16], 16).unwrap_or_default() as i64
+    }
+
+    /// Encodes a cursor pointing at this result
+    ///
+    /// A `forward` cursor resumes after this result, a backward one ends
+    /// the page just before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InternalError` if encoding fails
+    pub fn cursor(&self, forward: bool) -> Result<String> {
+        PaginationCursor::new(self.cursor_key(), Some(self.updated_at), forward).encode()
+    }
+}
+
+/// Collapses results sharing the same `field` value to the top-scoring one
+///
+/// The kept result gets `collapsed_count` set to the number of other
+/// results merged into it. Output is ordered by descending similarity, so
+/// pagination applied afterwards sees only distinct results.
+pub fn collapse_results(results: Vec<SearchResult>, field: &str) -> Vec<SearchResult> {
+    let mut kept: Vec<SearchResult> = Vec::new();
+    let mut positions: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+    for result in results {
+        let key = result.field_value(field).unwrap_or_default().to_string();
+        match positions.get(&key) {
+            Some(&index) => {
+                let existing = &mut kept[index];
+                let merged =
+                    existing.collapsed_count.unwrap_or(0) + result.collapsed_count.unwrap_or(0) + 1;
+                if result.similarity.unwrap_or(0.0) > existing.similarity.unwrap_or(0.0) {
+                    *existing = result;
+                }
+                existing.collapsed_count = Some(merged);
+            }
+            None => {
+                positions.insert(key, kept.len());
+                kept.push(SearchResult {
+                    collapsed_count: Some(result.collapsed_count.unwrap_or(0)),
+                    ..result
+                });
+            }
+        }
+    }
+
+    kept.sort_by(|a, b| {
+        b.similarity
+            .unwrap_or(0.0)
+            .partial_cmp(&a.similarity.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    kept
+}
+
+/// Sorts results by `clauses` in turn
+///
+/// `results` must be in relevance order, most relevant first, with
+/// `result` picking the search result out of each item. The `relevance`
+/// field keeps that order when descending and reverses it when ascending;
+/// results equal on every clause stay in relevance order. Without clauses
+/// the results are returned unchanged.
+pub fn sort_results<T>(
+    results: Vec<T>,
+    clauses: &[SortClause],
+    result: impl Fn(&T) -> &SearchResult,
+) -> Vec<T> {
+    if clauses.is_empty() {
+        return results;
+    }
+
+    let mut ranked: Vec<(usize, T)> = results.into_iter().enumerate().collect();
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+        let (a, b) = (result(a), result(b));
+        clauses
+            .iter()
+            .map(|clause| {
+                let ordering = match clause.field.as_str() {
+                    "relevance" => rank_b.cmp(rank_a),
+                    "updated_at" => a.updated_at.cmp(&b.updated_at),
+                    "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+                    "repository" => a.repository.cmp(&b.repository),
+                    _ => std::cmp::Ordering::Equal,
+                };
+                match clause.order {
+                    SortOrder::Asc => ordering,
+                    SortOrder::Desc => ordering.reverse(),
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    ranked.into_iter().map(|(_, item)| item).collect()
 }
 
 /// Aggregation response structure
@@ -959,6 +1311,70 @@ impl PaginationCursor {
     }
 }
 
+/// A page cut from results in their final order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultPage<T> {
+    /// Results on the page
+    pub results: Vec<T>,
+    /// Whether results follow the page
+    pub has_more: bool,
+    /// Whether results precede the page
+    pub has_prev: bool,
+}
+
+impl<T> ResultPage<T> {
+    /// Cuts up to `limit` results starting at `start`
+    pub fn at(results: Vec<T>, start: usize, limit: usize) -> Self {
+        let total = results.len();
+        let end = start.saturating_add(limit).min(total);
+        Self {
+            has_more: end < total,
+            has_prev: start > 0,
+            results: results
+                .into_iter()
+                .skip(start)
+                .take(end.saturating_sub(start))
+                .collect(),
+        }
+    }
+
+    /// Cuts the page `cursor` points at
+    ///
+    /// A forward cursor starts the page after the result it names and a
+    /// backward cursor ends it just before, so inserted or removed results
+    /// elsewhere do not shift the page the way an offset would.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidOptions` if no result matches the cursor
+    pub fn after_cursor(
+        results: Vec<T>,
+        cursor: &PaginationCursor,
+        limit: usize,
+        result: impl Fn(&T) -> &SearchResult,
+    ) -> Result<Self> {
+        let anchor = results
+            .iter()
+            .position(|item| result(item).cursor_key() == cursor.last_id)
+            .ok_or_else(|| {
+                SearchError::InvalidOptions(
+                    "Cursor does not match any result; restart from the first page".to_string(),
+                )
+            })?;
+        let start = if cursor.forward {
+            anchor + 1
+        } else {
+            anchor.saturating_sub(limit)
+        };
+        let limit = if cursor.forward {
+            limit
+        } else {
+            anchor - start
+        };
+        Ok(Self::at(results, start, limit))
+    }
+}
+
 /// Validates if a category is valid according to Diataxis framework
 fn is_valid_category(category: &str) -> bool {
     matches!(
@@ -1019,6 +1435,45 @@ pub struct SavedSearch {
 }
 
 impl SavedSearch {
+    /// A new saved search owned by `user_id`
+    ///
+    /// Endpoints pass the id of the authenticated user rather than one sent
+    /// by the client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xze_serve::search::types::{AdvancedSearchRequest, CreateSavedSearchRequest, SavedSearch};
+    ///
+    /// let request = CreateSavedSearchRequest {
+    ///     name: "Rust Async Docs".to_string(),
+    ///     description: None,
+    ///     search_request: AdvancedSearchRequest {
+    ///         query: "rust async".to_string(),
+    ///         multi_match: None,
+    ///         bool_query: None,
+    ///         filters: None,
+    ///         options: None,
+    ///         aggregations: None,
+    ///     },
+    /// };
+    /// let saved = SavedSearch::for_user("user123", request);
+    /// assert_eq!(saved.user_id, "user123");
+    /// assert_eq!(saved.id, None);
+    /// ```
+    pub fn for_user(user_id: impl Into<String>, request: CreateSavedSearchRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            user_id: user_id.into(),
+            name: request.name,
+            description: request.description,
+            search_request: request.search_request,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
     /// Validates the saved search
     ///
     /// # Errors
@@ -1513,6 +1968,11 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(false),
                 group_by: Some("category".to_string()),
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: Some(AggregationRequest {
                 by_category: Some(true),
@@ -1593,6 +2053,41 @@ mod tests {
         assert!(large_offset.validate().is_ok());
     }
 
+    #[test]
+    fn test_search_options_rejects_offset_over_max() {
+        let limits = SearchLimits {
+            max_results: 50,
+            max_offset: 500,
+        };
+
+        let at_max = SearchOptions {
+            offset: Some(500),
+            ..Default::default()
+        };
+        assert!(at_max.validate_with_limits(&limits).is_ok());
+
+        let too_deep = SearchOptions {
+            offset: Some(501),
+            ..Default::default()
+        };
+        match too_deep.validate_with_limits(&limits) {
+            Err(SearchError::InvalidOptions(msg)) => assert!(msg.contains("cursor")),
+            other => panic!("expected InvalidOptions, got {:?}", other),
+        }
+
+        let too_many = SearchOptions {
+            max_results: Some(51),
+            ..Default::default()
+        };
+        assert!(too_many.validate_with_limits(&limits).is_err());
+
+        let default_max = SearchOptions {
+            offset: Some(1_000_000),
+            ..Default::default()
+        };
+        assert!(default_max.validate().is_err());
+    }
+
     #[test]
     fn test_similarity_range_edge_cases() {
         // Both min and max at boundaries
@@ -1689,6 +2184,145 @@ mod tests {
         assert!(by_similarity.validate().is_ok());
     }
 
+    #[test]
+    fn test_search_options_dedup_by_values() {
+        for field in DEDUP_FIELDS {
+            let options = SearchOptions {
+                dedup_by: Some(field.to_string()),
+                ..Default::default()
+            };
+            assert!(options.validate().is_ok());
+        }
+
+        let invalid = SearchOptions {
+            dedup_by: Some("similarity".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_search_options_rerank_top_n_is_capped() {
+        let options = |top_n| SearchOptions {
+            rerank_top_n: Some(top_n),
+            ..Default::default()
+        };
+
+        assert!(options(MAX_RERANK_TOP_N).validate().is_ok());
+        assert!(options(0).validate().is_err());
+        assert!(options(MAX_RERANK_TOP_N + 1).validate().is_err());
+    }
+
+    fn result_at(path: &str, similarity: f32) -> SearchResult {
+        SearchResult {
+            id: format!("{}-{}", path, similarity),
+            title: "Doc".to_string(),
+            content: String::new(),
+            snippet: None,
+            category: "tutorial".to_string(),
+            similarity: Some(similarity),
+            repository: "xze".to_string(),
+            path: path.to_string(),
+            tags: vec![],
+            updated_at: Utc::now(),
+            collapsed_count: None,
+            explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_collapse_results_keeps_top_scoring() {
+        let results = vec![
+            result_at("a.md", 0.5),
+            result_at("b.md", 0.7),
+            result_at("a.md", 0.9),
+            result_at("a.md", 0.6),
+        ];
+
+        let collapsed = collapse_results(results, "path");
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].path, "a.md");
+        assert_eq!(collapsed[0].similarity, Some(0.9));
+        assert_eq!(collapsed[0].collapsed_count, Some(2));
+        assert_eq!(collapsed[1].path, "b.md");
+        assert_eq!(collapsed[1].collapsed_count, Some(0));
+
+        let by_repository = collapse_results(collapsed, "repository");
+        assert_eq!(by_repository.len(), 1);
+        assert_eq!(by_repository[0].collapsed_count, Some(3));
+    }
+
+    #[test]
+    fn test_search_options_sort_fields() {
+        for field in SORT_FIELDS {
+            let options = SearchOptions {
+                sort: Some(vec![SortClause::new(*field, SortOrder::Desc)]),
+                ..Default::default()
+            };
+            assert!(options.validate().is_ok());
+        }
+
+        let invalid = SearchOptions {
+            sort: Some(vec![SortClause::new("similarity", SortOrder::Asc)]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(SearchError::InvalidOptions(_))
+        ));
+
+        let clause: SortClause = serde_json::from_str(r#"{"field":"title"}"#).unwrap();
+        assert_eq!(clause.order, SortOrder::Asc);
+    }
+
+    #[test]
+    fn test_sort_results_by_date_ascending() {
+        let now = Utc::now();
+        let mut results = vec![
+            result_at("new.md", 0.9),
+            result_at("old.md", 0.8),
+            result_at("mid.md", 0.7),
+        ];
+        results[0].updated_at = now;
+        results[1].updated_at = now - chrono::Duration::days(10);
+        results[2].updated_at = now - chrono::Duration::days(5);
+
+        let sorted = sort_results(
+            results.clone(),
+            &[SortClause::new("updated_at", SortOrder::Asc)],
+            |r| r,
+        );
+        let paths: Vec<&str> = sorted.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["old.md", "mid.md", "new.md"]);
+
+        let unsorted = sort_results(results, &[], |r| r);
+        let paths: Vec<&str> = unsorted.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["new.md", "old.md", "mid.md"]);
+    }
+
+    #[test]
+    fn test_sort_results_multi_key() {
+        let mut results = vec![
+            result_at("a.md", 0.9),
+            result_at("b.md", 0.8),
+            result_at("c.md", 0.7),
+        ];
+        results[0].repository = "zeta".to_string();
+        results[1].repository = "alpha".to_string();
+        results[2].repository = "zeta".to_string();
+
+        let sorted = sort_results(
+            results,
+            &[
+                SortClause::new("repository", SortOrder::Asc),
+                SortClause::new("relevance", SortOrder::Asc),
+            ],
+            |r| r,
+        );
+        let paths: Vec<&str> = sorted.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["b.md", "c.md", "a.md"]);
+    }
+
     #[test]
     fn test_pagination_info_boundary_conditions() {
         // First page
@@ -1749,6 +2383,11 @@ mod tests {
                 include_snippets: Some(true),
                 highlight_terms: Some(true),
                 group_by: Some("category".to_string()),
+                dedup_by: None,
+                rerank_top_n: None,
+                explain: None,
+                sort: None,
+                cursor: None,
             }),
             aggregations: Some(AggregationRequest {
                 by_category: Some(true),
@@ -1789,6 +2428,8 @@ mod tests {
             path: "docs/test.md".to_string(),
             tags: vec!["rust".to_string()],
             updated_at: Utc::now(),
+            collapsed_count: None,
+            explanation: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
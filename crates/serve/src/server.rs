@@ -1,20 +1,34 @@
 //! Server module for XZe serve crate
 
-use crate::api::create_routes;
+use crate::api::create_routes_with;
+use crate::cache::AnalysisWarmer;
 use crate::handlers::AppState;
+use crate::health::{health_routes, ReadinessCheck};
+use crate::load::{load_routes, load_tracking_middleware, LoadMonitor};
+use crate::middleware::{
+    auth::jwt_auth_middleware,
+    security::{cors_middleware, CorsConfig},
+    timeout_middleware,
+};
+use crate::search::{
+    analytics::{analytics_routes, AnalyticsState},
+    saved_search_routes, search_routes_with_state,
+    websocket::{connection::ConnectionRegistry, websocket_routes},
+};
 
+use crate::search::SearchState;
 use crate::ServerConfig;
 use anyhow;
-use axum::{
-    http::{
-        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        HeaderValue, Method,
-    },
-    Router,
-};
-use std::net::SocketAddr;
+use axum::{extract::DefaultBodyLimit, middleware, Router};
+use std::{future::Future, net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
 use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, limit::RequestBodyLimitLayer, trace::TraceLayer};
+use tower_http::{limit::RequestBodyLimitLayer, trace::TraceLayer};
+use xze_core::ai::{AIAnalysisService, OllamaClient};
+use xze_core::config::ModelConfig;
+use xze_core::kb::store::KbStore;
+use xze_core::search::rerank::Reranker;
+use xze_core::search::{SynonymMap, TextNormalizer};
 use xze_core::{Result, XzeError};
 
 /// XZe HTTP server
@@ -31,59 +45,234 @@ impl XzeServer {
         Ok(Self { config, app })
     }
 
-    /// Start the server
+    /// Start the server and run until Ctrl+C or SIGTERM
+    ///
+    /// Serves HTTPS when a TLS certificate and key are configured.
     pub async fn start(self) -> Result<()> {
-        let addr = format!("{}:{}", self.config.host, self.config.port);
-        let socket_addr: SocketAddr = addr
-            .parse()
-            .map_err(|e| XzeError::validation(format!("Invalid address {}: {}", addr, e)))?;
-
-        tracing::info!("Starting XZe server on {}", addr);
-
-        let listener = tokio::net::TcpListener::bind(socket_addr)
-            .await
-            .map_err(|e| XzeError::network(format!("Failed to bind to {}: {}", addr, e)))?;
-
-        axum::serve(listener, self.app)
-            .await
-            .map_err(|e| XzeError::network(format!("Server error: {}", e)))?;
-
-        Ok(())
+        let tls = crate::tls::load(&self.config)?;
+        let listener = bind(&self.config).await?;
+        match tls {
+            Some(tls) => crate::tls::serve_tls(listener, self.app, tls, shutdown_signal()).await,
+            None => serve(listener, self.app, shutdown_signal()).await,
+        }
     }
 
     /// Get the server configuration
     pub fn config(&self) -> &ServerConfig {
         &self.config
     }
+
+    /// The composed application, for serving on a listener of your own
+    pub fn into_router(self) -> Router {
+        self.app
+    }
 }
 
 /// Create the Axum application with middleware
 async fn create_app(config: &ServerConfig) -> Result<Router> {
     // Initialize application state with database connection
+    let synonyms = SynonymMap::load_optional(config.synonyms_path.as_deref())?;
     let state = AppState::new(config.clone())
         .await
-        .map_err(|e| XzeError::Generic(anyhow::anyhow!("Failed to connect to database: {}", e)))?;
+        .map_err(|e| XzeError::Generic(anyhow::anyhow!("Failed to connect to database: {}", e)))?
+        .with_synonyms(Arc::new(synonyms))
+        .with_normalizer(Arc::new(TextNormalizer::new(config.normalization.clone())));
+
+    if config.warmer.enabled {
+        tracing::info!(
+            "Starting analysis cache warmer for {} repositories every {}s",
+            config.warmer.repositories.len(),
+            config.warmer.interval_secs
+        );
+        AnalysisWarmer::new(config.warmer.clone(), state.analysis_cache.clone()).spawn();
+    }
+
+    Ok(build_router(state, config))
+}
 
-    let mut app = create_routes().with_state(state);
+/// Compose every route group and apply the middleware stack
+///
+/// Serves the v1 and legacy API, `/api/search` with the configured
+/// [`ServerConfig::search_limits`] and [`ServerConfig::rerank`] and
+/// responses cached in the state's search cache, the `/api/search/saved`
+/// endpoints backed by a `KbStore` on the state's pool, the
+/// `/api/ws/search` WebSocket, the `/api/search/analytics` endpoints, the
+/// `/livez` and `/readyz` probes and the `/metrics/load` indicator, plus
+/// `/metrics` with the `metrics` feature, leaving out the groups disabled
+/// in [`ServerConfig::endpoints`].
+///
+/// When the state has a search index, the search cache is subscribed to
+/// it so changed documents evict the cached searches they affect; this
+/// spawns a task, so call it from within a Tokio runtime.
+pub fn build_router(state: AppState, config: &ServerConfig) -> Router {
+    let registry = Arc::new(ConnectionRegistry::new());
+    let analytics = Arc::new(AnalyticsState::new());
+    let load = Arc::new(LoadMonitor::new(
+        config.load.clone(),
+        OllamaClient::shared(&state.ollama_url),
+    ));
 
-    // Add middleware layers
+    let readiness = Arc::new(ReadinessCheck::new(
+        xze_infra::InfraConfig {
+            ollama_url: state.ollama_url.clone(),
+            ..Default::default()
+        },
+        state.database_pool.clone(),
+    ));
+
+    let endpoints = &config.endpoints;
+    let mut api = Router::new();
+    if endpoints.enable_search {
+        if let Some(index) = &state.search_index {
+            state.search_cache.clone().watch_index(index);
+        }
+        let mut search =
+            SearchState::new(config.search_limits).with_cache(state.search_cache.clone());
+        if let Some(rerank) = &config.rerank {
+            let models = ModelConfig {
+                primary: rerank.model.clone(),
+                fallback: Vec::new(),
+                ..config.models.clone()
+            };
+            let scorer = AIAnalysisService::new(state.ollama_url.clone(), models);
+            search =
+                search.with_reranker(Arc::new(Reranker::from_config(Arc::new(scorer), rerank)));
+        }
+        let saved_searches = Arc::new(KbStore::new(state.database_pool.clone()));
+        api = api
+            .merge(search_routes_with_state(search))
+            .merge(saved_search_routes(saved_searches));
+    }
+    if endpoints.enable_websocket {
+        api = api.merge(websocket_routes(registry));
+    }
+    let mut app = create_routes_with(endpoints)
+        .with_state(state)
+        .nest("/api", api);
+    if endpoints.enable_analytics {
+        app = app.nest("/api/search/analytics", analytics_routes(analytics));
+    }
+    app = app
+        .merge(health_routes(readiness))
+        .merge(load_routes(load.clone()));
+    #[cfg(feature = "metrics")]
+    {
+        app = app.merge(crate::metrics::metrics_routes(OllamaClient::shared(
+            &config.ollama_url,
+        )));
+    }
+
+    // Count search requests for the load indicator
+    app = app.layer(middleware::from_fn(move |req, next| {
+        load_tracking_middleware(load.clone(), req, next)
+    }));
+
+    // Record request counts and latencies for `/metrics`
+    #[cfg(feature = "metrics")]
+    {
+        app = app.layer(middleware::from_fn(crate::metrics::metrics_middleware));
+    }
+
+    // Abort stalled handlers before they tie up connections
+    let timeouts = Arc::new(config.timeouts.clone());
+    app = app.layer(middleware::from_fn(move |req, next| {
+        timeout_middleware(timeouts.clone(), req, next)
+    }));
+
+    // Authenticate inside CORS so preflight requests need no token
+    if let Some(jwt) = &config.jwt {
+        let jwt = Arc::new(jwt.clone());
+        app = app.layer(middleware::from_fn(move |req, next| {
+            jwt_auth_middleware(jwt.clone(), req, next)
+        }));
+    }
+
+    // Add middleware layers; extractors such as `Json` enforce their own
+    // 2MB default unless `DefaultBodyLimit` raises it
     app = app.layer(
         ServiceBuilder::new()
             .layer(TraceLayer::new_for_http())
+            .layer(DefaultBodyLimit::max(config.max_request_size))
             .layer(RequestBodyLimitLayer::new(config.max_request_size)),
     );
 
     // Add CORS if enabled
     if config.cors_enabled {
-        let cors = CorsLayer::new()
-            .allow_origin("*".parse::<HeaderValue>().unwrap())
-            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-            .allow_headers([ACCEPT, AUTHORIZATION, CONTENT_TYPE]);
-
-        app = app.layer(cors);
+        let cors = Arc::new(CorsConfig::default());
+        app = app.layer(middleware::from_fn(move |req, next| {
+            cors_middleware(cors.clone(), req, next)
+        }));
     }
 
-    Ok(app)
+    app
+}
+
+/// Bind a listener to the configured host and port
+///
+/// # Errors
+///
+/// Returns a validation error for an unparsable address and a network
+/// error if the address cannot be bound, for example because the port is
+/// in use
+pub async fn bind(config: &ServerConfig) -> Result<TcpListener> {
+    let addr = format!("{}:{}", config.host, config.port);
+    let socket_addr: SocketAddr = addr
+        .parse()
+        .map_err(|e| XzeError::validation(format!("Invalid address {}: {}", addr, e)))?;
+
+    let listener = TcpListener::bind(socket_addr)
+        .await
+        .map_err(|e| XzeError::network(format!("Failed to bind to {}: {}", addr, e)))?;
+    tracing::info!("Starting XZe server on {}", addr);
+    Ok(listener)
+}
+
+/// Serve `app` on `listener` until `shutdown` resolves
+///
+/// In-flight requests finish before this returns.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .map_err(|e| XzeError::network(format!("Server error: {}", e)))?;
+
+    tracing::info!("XZe server stopped");
+    Ok(())
+}
+
+/// Resolve on Ctrl+C, or on SIGTERM on Unix
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutdown signal received");
 }
 
 /// Server builder for configuration
@@ -135,6 +324,49 @@ impl ServerBuilder {
         self
     }
 
+    /// Set request timeouts
+    pub fn timeouts(mut self, timeouts: crate::middleware::TimeoutConfig) -> Self {
+        self.config.timeouts = timeouts;
+        self
+    }
+
+    /// Choose which endpoint groups to mount
+    pub fn endpoints(mut self, endpoints: crate::api::EndpointToggles) -> Self {
+        self.config.endpoints = endpoints;
+        self
+    }
+
+    /// Require JWT bearer tokens
+    pub fn jwt(mut self, jwt: crate::middleware::JwtConfig) -> Self {
+        self.config.jwt = Some(jwt);
+        self
+    }
+
+    /// Set the largest page size and offset the search endpoints accept
+    pub fn search_limits(mut self, limits: crate::search::SearchLimits) -> Self {
+        self.config.search_limits = limits;
+        self
+    }
+
+    /// Rerank searches that set `rerank_top_n` with a model
+    pub fn rerank(mut self, rerank: xze_core::search::rerank::RerankConfig) -> Self {
+        self.config.rerank = Some(rerank);
+        self
+    }
+
+    /// Set the capacities the load indicator scores against
+    pub fn load(mut self, load: crate::load::LoadConfig) -> Self {
+        self.config.load = load;
+        self
+    }
+
+    /// Serve HTTPS with a PEM certificate chain and private key
+    pub fn tls<P: Into<std::path::PathBuf>>(mut self, cert_path: P, key_path: P) -> Self {
+        self.config.tls_cert_path = Some(cert_path.into());
+        self.config.tls_key_path = Some(key_path.into());
+        self
+    }
+
     /// Build the server with async initialization
     pub async fn build(self) -> Result<XzeServer> {
         XzeServer::new(self.config).await
@@ -173,4 +405,348 @@ mod tests {
         assert!(!builder.config.cors_enabled);
         assert_eq!(builder.config.max_request_size, 5 * 1024 * 1024);
     }
+
+    fn test_router(config: ServerConfig) -> Router {
+        let pool = sqlx::PgPool::connect_lazy(&config.database_url).unwrap();
+        build_router(AppState::from_pool(config.clone(), pool), &config)
+    }
+
+    async fn send(
+        app: Router,
+        request: axum::http::Request<axum::body::Body>,
+    ) -> axum::response::Response {
+        use tower::ServiceExt;
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_router_serves_every_route_group() {
+        use axum::{body::Body, http::Request};
+
+        let app = test_router(ServerConfig::default());
+        for uri in [
+            "/api/v1/health",
+            "/health",
+            "/api/search/analytics/stats",
+            "/livez",
+            "/metrics/load",
+            #[cfg(feature = "metrics")]
+            "/metrics",
+        ] {
+            let response = send(app.clone(), Request::get(uri).body(Body::empty()).unwrap()).await;
+            assert_eq!(response.status(), 200, "{}", uri);
+        }
+        let response = send(
+            app,
+            Request::get("/api/search?q=").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_ne!(response.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_cors_follows_config() {
+        use axum::{body::Body, http::Request};
+
+        let request = || {
+            Request::get("/health")
+                .header("origin", "https://docs.example.com")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let enabled = send(test_router(ServerConfig::default()), request()).await;
+        assert!(enabled
+            .headers()
+            .contains_key("access-control-allow-origin"));
+
+        let config = ServerConfig {
+            cors_enabled: false,
+            ..ServerConfig::default()
+        };
+        let disabled = send(test_router(config), request()).await;
+        assert!(!disabled
+            .headers()
+            .contains_key("access-control-allow-origin"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_endpoints_return_not_found() {
+        use crate::api::EndpointToggles;
+        use axum::{body::Body, http::Request};
+
+        let status = |app: Router, method: &str, uri: &str| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from("{}"))
+                .unwrap();
+            async move { send(app, request).await.status() }
+        };
+
+        let search_only = ServerConfig {
+            endpoints: EndpointToggles {
+                enable_analyze: false,
+                enable_admin: false,
+                ..EndpointToggles::default()
+            },
+            ..ServerConfig::default()
+        };
+        let app = test_router(search_only);
+        for (method, uri) in [
+            ("POST", "/api/v1/analyze"),
+            ("POST", "/analyze"),
+            ("POST", "/api/v1/call-graph"),
+            ("GET", "/api/v1/admin/warmer"),
+        ] {
+            assert_eq!(status(app.clone(), method, uri).await, 404, "{}", uri);
+        }
+        assert_ne!(status(app.clone(), "GET", "/api/search?q=rust").await, 404);
+        assert_eq!(status(app, "GET", "/api/v1/health").await, 200);
+
+        let worker = ServerConfig {
+            endpoints: EndpointToggles {
+                enable_search: false,
+                enable_websocket: false,
+                enable_analytics: false,
+                ..EndpointToggles::default()
+            },
+            ..ServerConfig::default()
+        };
+        let app = test_router(worker);
+        for uri in [
+            "/api/search?q=rust",
+            "/api/v1/search?q=rust",
+            "/search?q=rust",
+            "/api/ws/search",
+            "/api/search/analytics/stats",
+        ] {
+            assert_eq!(status(app.clone(), "GET", uri).await, 404, "{}", uri);
+        }
+        assert_ne!(status(app, "POST", "/api/v1/call-graph").await, 404);
+    }
+
+    #[tokio::test]
+    async fn test_jwt_follows_config() {
+        use axum::{body::Body, http::Request};
+
+        let config = ServerConfig {
+            jwt: Some(crate::middleware::JwtConfig::hs256("secret")),
+            ..ServerConfig::default()
+        };
+        let app = test_router(config);
+        let health = send(
+            app.clone(),
+            Request::get("/health").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(health.status(), 200);
+        let load = send(
+            app.clone(),
+            Request::get("/metrics/load").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(load.status(), 200);
+        let search = send(
+            app,
+            Request::get("/api/search?q=rust")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(search.status(), 401);
+
+        let open = send(
+            test_router(ServerConfig::default()),
+            Request::get("/api/search?q=rust")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_ne!(open.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_search_limits_follow_config() {
+        use crate::search::SearchLimits;
+        use axum::{body::Body, http::Request};
+
+        let config = ServerConfig {
+            search_limits: SearchLimits {
+                max_results: 5,
+                max_offset: 10,
+            },
+            ..ServerConfig::default()
+        };
+        let app = test_router(config);
+        for (uri, expected) in [
+            ("/api/search?q=rust&limit=5", 200),
+            ("/api/search?q=rust&limit=6", 400),
+            ("/api/search?q=rust&limit=5&offset=11", 400),
+        ] {
+            let response = send(app.clone(), Request::get(uri).body(Body::empty()).unwrap()).await;
+            assert_eq!(response.status(), expected, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_follows_config() {
+        use axum::{body::Body, http::Request};
+        use xze_core::search::rerank::RerankConfig;
+
+        let mut ollama = mockito::Server::new_async().await;
+        for (documents, rating) in [("Document [1245] ", "1"), ("Document 3 ", "9")] {
+            ollama
+                .mock("POST", "/api/generate")
+                .match_body(mockito::Matcher::Regex(documents.to_string()))
+                .with_body(format!(
+                    "{{\"response\":\"{}\",\"done\":true,\"done_reason\":\"stop\"}}\n",
+                    rating
+                ))
+                .create_async()
+                .await;
+        }
+        let request = || {
+            Request::post("/api/search/advanced")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"query":"rust","options":{"max_results":3,"rerank_top_n":5,"explain":true}}"#,
+                ))
+                .unwrap()
+        };
+
+        let config = ServerConfig {
+            ollama_url: ollama.url(),
+            rerank: Some(RerankConfig {
+                model: "llama3".to_string(),
+                max_top_n: 10,
+                cache_ttl_secs: 60,
+            }),
+            ..ServerConfig::default()
+        };
+        let response = send(test_router(config), request()).await;
+        assert_eq!(response.status(), 200);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["results"][0]["id"], "doc-advanced-2");
+        assert_eq!(body["results"][0]["explanation"]["rerank_score"], 0.9);
+
+        let disabled = send(test_router(ServerConfig::default()), request()).await;
+        assert_eq!(disabled.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_search_cache_follows_index_changes() {
+        use axum::{body::Body, http::Request};
+        use std::time::Duration;
+        use xze_core::kb::store::DocumentChunk;
+        use xze_core::search::{IndexedDocument, SearchIndex};
+
+        let config = ServerConfig::default();
+        let pool = sqlx::PgPool::connect_lazy(&config.database_url).unwrap();
+        let index = Arc::new(SearchIndex::new());
+        let state = AppState::from_pool(config.clone(), pool).with_search_index(index.clone());
+        let cache = state.search_cache.clone();
+        let app = build_router(state, &config);
+        let search = || {
+            Request::get("/api/search?q=rust")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        for _ in 0..2 {
+            let response = send(app.clone(), search()).await;
+            assert_eq!(response.status(), 200);
+        }
+        assert_eq!(cache.statistics().hits, 1);
+
+        index.upsert(IndexedDocument::new(
+            "docs/a.md",
+            vec![DocumentChunk::new(
+                "chunk_0".to_string(),
+                "content".to_string(),
+                vec![1.0],
+                serde_json::json!({ "category": "tutorial" }),
+            )],
+        ));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.statistics().invalidations == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("cached search was not invalidated");
+
+        send(app, search()).await;
+        assert_eq!(cache.statistics().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_body_limit() {
+        use axum::{body::Body, http::Request};
+
+        let config = ServerConfig {
+            max_request_size: 16,
+            ..ServerConfig::default()
+        };
+        let request = Request::post("/api/search/analytics/track")
+            .header("content-type", "application/json")
+            .body(Body::from(vec![b' '; 64]))
+            .unwrap();
+        let response = send(test_router(config), request).await;
+        assert_eq!(response.status(), 413);
+    }
+
+    #[tokio::test]
+    async fn test_bind_port_in_use_is_network_error() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = ServerConfig {
+            port: taken.local_addr().unwrap().port(),
+            ..ServerConfig::default()
+        };
+
+        let err = bind(&config).await.unwrap_err();
+        assert!(matches!(err, XzeError::Network { .. }), "{:?}", err);
+
+        let invalid = ServerConfig {
+            host: "not a host".to_string(),
+            ..ServerConfig::default()
+        };
+        assert!(matches!(
+            bind(&invalid).await,
+            Err(XzeError::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_serve_stops_on_shutdown() {
+        let config = ServerConfig {
+            port: 0,
+            ..ServerConfig::default()
+        };
+        let listener = bind(&config).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, test_router(config), async {
+            stopped.await.ok();
+        }));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        tokio::io::AsyncWriteExt::write_all(
+            &mut stream,
+            b"GET /health HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n",
+        )
+        .await
+        .unwrap();
+        let mut response = String::new();
+        tokio::io::AsyncReadExt::read_to_string(&mut stream, &mut response)
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+        stop.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
 }
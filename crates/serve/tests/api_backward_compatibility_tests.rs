@@ -47,6 +47,11 @@ async fn create_test_state() -> xze_serve::handlers::AppState {
         database_url,
         cors_enabled: false,
         max_request_size: 10 * 1024 * 1024,
+        warmer: Default::default(),
+        timeouts: Default::default(),
+        models: Default::default(),
+        repositories: Default::default(),
+        path_redaction: Default::default(),
     };
 
     AppState::new(config)
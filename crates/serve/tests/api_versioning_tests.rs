@@ -31,6 +31,9 @@ fn test_v1_handlers_module_exists() {
         repository_url: "https://github.com/test/repo".to_string(),
         branch: Some("main".to_string()),
         language: Some("rust".to_string()),
+        docs: None,
+        model: None,
+        fallback_models: None,
     };
 }
 
@@ -390,6 +393,11 @@ async fn create_test_state() -> xze_serve::handlers::AppState {
         database_url,
         cors_enabled: false,
         max_request_size: 10 * 1024 * 1024,
+        warmer: Default::default(),
+        timeouts: Default::default(),
+        models: Default::default(),
+        repositories: Default::default(),
+        path_redaction: Default::default(),
     };
 
     AppState::new(config)
@@ -45,6 +45,23 @@ async fn create_test_state() -> xze_serve::handlers::AppState {
         database_url,
         cors_enabled: false,
         max_request_size: 10 * 1024 * 1024,
+        warmer: Default::default(),
+        timeouts: Default::default(),
+        models: Default::default(),
+        repositories: Default::default(),
+        path_redaction: Default::default(),
+        tls_cert_path: None,
+        tls_key_path: None,
+        jwt: None,
+        endpoints: Default::default(),
+        load: Default::default(),
+        synonyms_path: None,
+        normalization: Default::default(),
+        search_limits: Default::default(),
+        search_cache: Default::default(),
+        rerank: None,
+        generation: Default::default(),
+        output: Default::default(),
     };
 
     AppState::new(config)
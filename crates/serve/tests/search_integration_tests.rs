@@ -11,7 +11,9 @@ use axum::{
 use serde_json::json;
 use tower::ServiceExt;
 use xze_serve::search::{
-    handlers::search_routes, AdvancedSearchRequest, SearchFilters, SearchOptions,
+    handlers::{search_routes, search_routes_with_limits},
+    types::SearchLimits,
+    AdvancedSearchRequest, SearchFilters, SearchOptions,
 };
 
 /// Creates a test router with search routes
@@ -257,6 +259,11 @@ fn test_search_request_serialization() {
             include_snippets: Some(true),
             highlight_terms: Some(false),
             group_by: None,
+            dedup_by: None,
+            rerank_top_n: None,
+            explain: None,
+            sort: None,
+            cursor: None,
         }),
         aggregations: None,
     };
@@ -304,6 +311,38 @@ async fn test_pagination_in_get_search() {
     assert!(body.contains("\"limit\":5"));
 }
 
+#[tokio::test]
+async fn test_get_search_offset_over_max_returns_error() {
+    let router = create_test_router();
+
+    let (status, body) = make_get_request(router, "/api/search?q=rust&offset=1000000").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("offset cannot exceed 10000"));
+    assert!(body.contains("cursor-based pagination"));
+}
+
+#[tokio::test]
+async fn test_post_search_offset_over_configured_max_returns_error() {
+    let limits = SearchLimits {
+        max_results: 100,
+        max_offset: 50,
+    };
+    let router = Router::new().nest("/api", search_routes_with_limits(limits));
+
+    let request_body = json!({
+        "query": "test",
+        "options": {
+            "offset": 51
+        }
+    });
+
+    let (status, body) = make_post_request(router, "/api/search/advanced", request_body).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("offset cannot exceed 50"));
+}
+
 #[tokio::test]
 async fn test_repository_filter_in_get_search() {
     let router = create_test_router();
@@ -0,0 +1,22 @@
+-- Migration: Add file size and modification time for incremental loading
+-- Created: 2026-10-16
+
+-- Record the size and modification time of each loaded file. When both
+-- still match on the next load, the stored file_hash is reused without
+-- reading the file again.
+ALTER TABLE IF EXISTS documents
+ADD COLUMN IF NOT EXISTS file_size BIGINT;
+
+ALTER TABLE IF EXISTS documents
+ADD COLUMN IF NOT EXISTS file_mtime_ns BIGINT;
+
+COMMENT ON COLUMN documents.file_size IS 'File size in bytes at the last load, for the incremental loading fast path';
+COMMENT ON COLUMN documents.file_mtime_ns IS 'File modification time in nanoseconds since the Unix epoch at the last load';
+
+-- file_hash may hold SHA-256, BLAKE3 (64 hex characters) or XXH3 (16 hex
+-- characters) digests depending on the configured hash algorithm
+COMMENT ON COLUMN documents.file_hash IS 'Content hash (SHA-256, BLAKE3 or XXH3) for change detection in incremental loading';
+
+-- Migration rollback (if needed):
+-- ALTER TABLE documents DROP COLUMN IF EXISTS file_size;
+-- ALTER TABLE documents DROP COLUMN IF EXISTS file_mtime_ns;
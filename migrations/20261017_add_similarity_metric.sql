@@ -0,0 +1,12 @@
+-- Migration: Record the similarity metric of semantic chunk embeddings
+-- Created: 2026-10-16
+
+-- Embeddings are only comparable under the metric their model was trained
+-- for. Existing rows were always compared with cosine similarity.
+ALTER TABLE IF EXISTS semantic_chunks
+ADD COLUMN IF NOT EXISTS similarity_metric TEXT NOT NULL DEFAULT 'cosine';
+
+COMMENT ON COLUMN semantic_chunks.similarity_metric IS 'Similarity metric for the embedding: cosine, dot_product or euclidean';
+
+-- Migration rollback (if needed):
+-- ALTER TABLE semantic_chunks DROP COLUMN IF EXISTS similarity_metric;
@@ -0,0 +1,12 @@
+-- Migration: Record the sentence overlap of semantic chunks
+-- Created: 2026-10-18
+
+-- Chunks may start with sentences repeated from the previous chunk. Readers
+-- skip that many sentences when joining chunks. Existing rows never overlap.
+ALTER TABLE IF EXISTS semantic_chunks
+ADD COLUMN IF NOT EXISTS overlap_sentences INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN semantic_chunks.overlap_sentences IS 'Number of leading sentences repeated from the previous chunk';
+
+-- Migration rollback (if needed):
+-- ALTER TABLE semantic_chunks DROP COLUMN IF EXISTS overlap_sentences;
@@ -5,8 +5,11 @@
 //! documentation following the Diátaxis Documentation Framework.
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
 use tracing::{error, info};
+use xze_core::documentation::GenerationStatus;
 use xze_core::{Result, XzeError};
 
 #[derive(Parser)]
@@ -66,13 +69,75 @@ enum Commands {
         #[arg(short, long)]
         language: Option<String>,
 
-        /// Output directory for generated documentation
-        #[arg(short = 'o', long)]
+        /// Write the documentation generated with --docs as files under
+        /// this directory instead of the configured output sink
+        #[arg(long, requires = "docs")]
         output_dir: Option<PathBuf>,
 
         /// Dry run - don't write files
         #[arg(long)]
         dry_run: bool,
+
+        /// Re-run analysis against the files recorded in a snapshot manifest
+        #[arg(long, conflicts_with_all = ["repos", "auto"])]
+        from_snapshot: Option<PathBuf>,
+
+        /// Generate documentation for these categories after analysis
+        /// (tutorial, how-to, reference, explanation)
+        ///
+        /// Uses the Ollama server from the configuration file. Nothing is
+        /// generated without this flag or with --dry-run. Cannot be
+        /// combined with --staged or --from-snapshot.
+        #[arg(long, value_delimiter = ',', conflicts_with_all = ["staged", "from_snapshot"])]
+        docs: Vec<String>,
+
+        /// Re-analyze repositories in auto mode even if HEAD is unchanged
+        #[arg(long)]
+        force: bool,
+
+        /// Output only aggregate statistics instead of the full code structure
+        #[arg(long, conflicts_with = "dry_run")]
+        stats_only: bool,
+
+        /// Check only files staged for commit and report undocumented items
+        ///
+        /// Meant for pre-commit hooks. Uses the repository given with
+        /// --repos, or the current directory, and exits nonzero when gaps
+        /// are found unless --warn-only is set.
+        #[arg(long, conflicts_with_all = ["auto", "from_snapshot", "dry_run", "stats_only"])]
+        staged: bool,
+
+        /// Report gaps found by --staged without failing
+        #[arg(long, requires = "staged")]
+        warn_only: bool,
+
+        /// Rewrite paths in the output relative to the repository root
+        ///
+        /// `relative` (the default when the flag is given without a value)
+        /// prints `src/lib.rs`; `hashed` also prefixes a short hash of the
+        /// repository root instead of its location.
+        #[arg(
+            long,
+            value_name = "MODE",
+            num_args = 0..=1,
+            default_missing_value = "relative",
+            default_value = "none"
+        )]
+        redact_paths: xze_core::repository::PathRedaction,
+    },
+
+    /// Capture a repository snapshot for reproducible analysis
+    Snapshot {
+        /// Repository path to snapshot
+        repo: PathBuf,
+
+        /// Output path for the snapshot manifest
+        #[arg(short = 'f', long, default_value = "xze-snapshot.json")]
+        file: PathBuf,
+
+        /// Programming language override
+        #[arg(short, long)]
+        language: Option<String>,
     },
 
     /// Start XZe as a web server
@@ -88,6 +153,18 @@ enum Commands {
         /// Ollama server URL
         #[arg(long, default_value = "http://localhost:11434")]
         ollama_url: String,
+
+        /// PEM certificate chain; serve HTTPS together with --tls-key
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+
+        /// PEM private key for --tls-cert
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+
+        /// Endpoint groups not to serve: analyze, search, websocket, analytics, admin
+        #[arg(long, value_delimiter = ',')]
+        disable: Vec<xze_serve::api::EndpointGroup>,
     },
 
     /// Initialize XZe configuration
@@ -105,6 +182,10 @@ enum Commands {
     Validate {
         /// Configuration file to validate
         config: PathBuf,
+
+        /// Check that configured repositories exist or are reachable
+        #[arg(long)]
+        check_repos: bool,
     },
 
     /// Classify query intent using Diataxis framework
@@ -113,12 +194,21 @@ enum Commands {
     /// Show version information
     Version,
 
-    /// Show health status
+    /// Show health status, dependency versions and compiled features
+    ///
+    /// Use `--output json` for a bundle to paste into issue reports.
     Health,
+
+    /// Run an end-to-end self-test of analysis, generation and validation
+    ///
+    /// Analyzes a small sample crate, generates a document with the
+    /// configured Ollama models and validates it, reporting each stage
+    /// with its timing. Exits nonzero if any stage fails.
+    Doctor,
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     // Initialize logging
@@ -132,6 +222,9 @@ async fn main() -> Result<()> {
 
     info!("Starting XZe v{}", env!("CARGO_PKG_VERSION"));
 
+    // Outcome of documentation generation, which sets the exit code
+    let mut status = GenerationStatus::Complete;
+
     // Handle commands
     match cli.command {
         Some(Commands::Load(ref args)) => {
@@ -152,24 +245,73 @@ async fn main() -> Result<()> {
             ref language,
             ref output_dir,
             dry_run,
+            ref from_snapshot,
+            ref docs,
+            force,
+            stats_only,
+            staged,
+            warn_only,
+            redact_paths,
         }) => {
-            handle_analyze(
-                repos.clone(),
-                auto,
-                language.clone(),
-                output_dir.clone(),
-                dry_run,
-                &cli,
-            )
-            .await?;
+            // parse_list reads an empty list as every category, but
+            // documentation is only generated when --docs is given
+            let categories = if docs.is_empty() {
+                Vec::new()
+            } else {
+                xze_core::types::DiátaxisCategory::parse_list(docs)?
+            };
+            let mode = AnalysisMode::from_flags(dry_run, stats_only);
+
+            if staged {
+                handle_analyze_staged(repos, warn_only, &cli)?;
+            } else if let Some(manifest) = from_snapshot {
+                handle_analyze_snapshot(manifest, mode, &cli).await?;
+            } else {
+                status = handle_analyze(
+                    repos.clone(),
+                    auto,
+                    language.clone(),
+                    AnalysisOutput {
+                        mode,
+                        redaction: redact_paths,
+                    },
+                    force,
+                    DocsRequest {
+                        categories: &categories,
+                        output_dir: output_dir.as_deref(),
+                    },
+                    &cli,
+                )
+                .await?;
+            }
+        }
+
+        Some(Commands::Snapshot {
+            ref repo,
+            ref file,
+            ref language,
+        }) => {
+            handle_snapshot(repo, file, language.as_deref(), &cli).await?;
         }
 
         Some(Commands::Serve {
             ref host,
             port,
             ref ollama_url,
+            ref tls_cert,
+            ref tls_key,
+            ref disable,
         }) => {
-            handle_serve(host.clone(), port, ollama_url.clone(), &cli).await?;
+            handle_serve(
+                host.clone(),
+                port,
+                ollama_url.clone(),
+                tls_cert.clone(),
+                tls_key.clone(),
+                disable,
+                &cli,
+            )
+            .await?;
         }
 
         Some(Commands::Init {
@@ -179,8 +321,11 @@ async fn main() -> Result<()> {
             handle_init(config.clone(), interactive, &cli).await?;
         }
 
-        Some(Commands::Validate { ref config }) => {
-            handle_validate(config, &cli).await?;
+        Some(Commands::Validate {
+            ref config,
+            check_repos,
+        }) => {
+            handle_validate(config, check_repos, &cli).await?;
         }
 
         Some(Commands::Classify(ref cmd)) => {
@@ -196,21 +341,42 @@ async fn main() -> Result<()> {
             handle_health(&cli).await?;
         }
 
+        Some(Commands::Doctor) => {
+            handle_doctor(&cli).await?;
+        }
+
         None => {
             // No subcommand provided - show help or run in demo mode
             handle_default(&cli).await?;
         }
     }
 
+    if status != GenerationStatus::Complete {
+        error!("Documentation generation did not complete; see the failures above");
+        return Ok(ExitCode::from(status.exit_code() as u8));
+    }
+
     info!("XZe completed successfully");
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-async fn handle_load(args: &xze_cli::LoadArgs, _cli: &Cli) -> Result<()> {
+async fn handle_load(args: &xze_cli::LoadArgs, cli: &Cli) -> Result<()> {
     info!("Executing load command");
 
+    // Without --backend, use the backend from the configuration file
+    let mut args = args.clone();
+    if args.backend.is_none() {
+        if let Some(config_path) = &cli.config {
+            args.backend = Some(
+                xze_core::XzeConfig::from_file(config_path)?
+                    .knowledge_base
+                    .backend,
+            );
+        }
+    }
+
     // Validate and execute the load command
-    xze_cli::execute_command(args.clone()).await?;
+    xze_cli::execute_command(args).await?;
 
     Ok(())
 }
@@ -233,16 +399,51 @@ async fn handle_search(args: &xze_cli::SearchArgs, _cli: &Cli) -> Result<()> {
     Ok(())
 }
 
+/// What an analysis run prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnalysisMode {
+    /// Analyze without printing results or recording progress
+    DryRun,
+    /// Print the full code structure
+    Full,
+    /// Print only aggregate statistics
+    StatsOnly,
+}
+
+/// What an analysis run prints and how paths in it are shown
+#[derive(Debug, Clone, Copy)]
+struct AnalysisOutput {
+    mode: AnalysisMode,
+    redaction: xze_core::repository::PathRedaction,
+}
+
+impl AnalysisMode {
+    fn from_flags(dry_run: bool, stats_only: bool) -> Self {
+        if dry_run {
+            Self::DryRun
+        } else if stats_only {
+            Self::StatsOnly
+        } else {
+            Self::Full
+        }
+    }
+}
+
 async fn handle_analyze(
     repos: Vec<PathBuf>,
     auto: bool,
     language: Option<String>,
-    _output_dir: Option<PathBuf>,
-    dry_run: bool,
+    output: AnalysisOutput,
+    force: bool,
+    docs: DocsRequest<'_>,
     cli: &Cli,
-) -> Result<()> {
+) -> Result<GenerationStatus> {
     info!("Running analysis mode");
 
+    // Combined outcome of the repositories documentation was generated for
+    let mut status: Option<GenerationStatus> = None;
+    let mut failed = Vec::new();
+
     if auto {
         info!("Using auto mode with configuration file");
         let config_path = cli
@@ -252,72 +453,555 @@ async fn handle_analyze(
 
         let config = xze_core::XzeConfig::from_file(config_path)?;
         config.validate()?;
+        let documentation = documentation_output(&config, docs, output.mode).await?;
 
         info!(
             "Found {} repositories in configuration",
             config.repositories.len()
         );
 
-        // TODO: Implement auto mode analysis
-        println!(
-            "Auto mode analysis would process {} configured repositories",
-            config.repositories.len()
+        let cache_dir = config
+            .filesystem
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("xze").join("repositories"));
+        let manager = xze_core::repository::RepositoryManager::new(cache_dir, config.clone())?;
+
+        let mut up_to_date = 0;
+        let mut changed = 0;
+
+        for repo_config in &config.repositories {
+            let repo_id = repo_config.id();
+            let repo = match manager.get_repository(&repo_id).await {
+                Ok(repo) => repo,
+                Err(e) => {
+                    error!("Failed to fetch repository {}: {}", repo_id, e);
+                    failed.push(repo_id.to_string());
+                    continue;
+                }
+            };
+
+            let head = match manager.head_commit(&repo) {
+                Ok(head) => head,
+                Err(e) => {
+                    error!("Failed to read HEAD of repository {}: {}", repo_id, e);
+                    failed.push(repo_id.to_string());
+                    continue;
+                }
+            };
+            if !force && repo.metadata.is_up_to_date(&head) {
+                info!("Repository {} is up to date at {}", repo_id, head);
+                up_to_date += 1;
+                continue;
+            }
+            changed += 1;
+
+            let language = language.clone().or_else(|| repo_config.language.clone());
+            let analyzed = match analyze_repository_path(
+                &repo.local_path,
+                language,
+                &config.coverage,
+                output,
+                cli,
+            ) {
+                Ok(analyzed) => analyzed,
+                Err(e) => {
+                    error!("Failed to analyze repository {}: {}", repo_id, e);
+                    failed.push(repo_id.to_string());
+                    continue;
+                }
+            };
+
+            if let (Some(service), Some(analyzed)) = (&documentation, &analyzed) {
+                let generated = generate_documentation(service, analyzed, docs.categories, cli)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!(
+                            "Failed to generate documentation for repository {}: {}",
+                            repo_id, e
+                        );
+                        GenerationStatus::Failed
+                    });
+                status = Some(status.map_or(generated, |s| s.combine(generated)));
+                // Retry categories that failed on the next run
+                if generated != GenerationStatus::Complete {
+                    continue;
+                }
+            }
+
+            // Only record the commit once the run has succeeded
+            if output.mode != AnalysisMode::DryRun {
+                manager.mark_analyzed(&repo_id, &head).await?;
+            }
+        }
+
+        info!(
+            "{} repos up to date, {} changed, {} failed",
+            up_to_date,
+            changed,
+            failed.len()
         );
     } else if !repos.is_empty() {
         info!("Using local mode with {} repositories", repos.len());
 
+        let config = match &cli.config {
+            Some(config_path) => xze_core::XzeConfig::from_file(config_path)?,
+            None => xze_core::XzeConfig::default(),
+        };
+        let documentation = documentation_output(&config, docs, output.mode).await?;
+
         for repo_path in &repos {
             info!("Analyzing repository: {:?}", repo_path);
 
             if !repo_path.exists() {
                 error!("Repository path does not exist: {:?}", repo_path);
+                failed.push(repo_path.display().to_string());
                 continue;
             }
 
-            // Use xze-core to analyze the repository
-            let (detected_lang, analyzer) =
-                xze_core::repository::analyzer::AnalyzerFactory::auto_detect_analyzer(repo_path)?;
-
-            let actual_language = if let Some(lang_str) = &language {
-                xze_core::types::ProgrammingLanguage::from(lang_str.as_str())
-            } else {
-                detected_lang
+            let analyzed = match analyze_repository_path(
+                repo_path,
+                language.clone(),
+                &config.coverage,
+                output,
+                cli,
+            ) {
+                Ok(analyzed) => analyzed,
+                Err(e) => {
+                    error!("Failed to analyze repository {:?}: {}", repo_path, e);
+                    failed.push(repo_path.display().to_string());
+                    continue;
+                }
             };
+            if let (Some(service), Some(analyzed)) = (&documentation, &analyzed) {
+                let generated = generate_documentation(service, analyzed, docs.categories, cli)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!(
+                            "Failed to generate documentation for repository {:?}: {}",
+                            repo_path, e
+                        );
+                        GenerationStatus::Failed
+                    });
+                status = Some(status.map_or(generated, |s| s.combine(generated)));
+            }
+        }
+
+        info!(
+            "{} repos analyzed, {} failed",
+            repos.len() - failed.len(),
+            failed.len()
+        );
+    } else {
+        return Err(XzeError::validation("No repositories specified. Use --repos for local mode or --auto for configuration mode"));
+    }
+
+    if !failed.is_empty() {
+        return Err(XzeError::pipeline(format!(
+            "Failed to analyze {} repositories: {}",
+            failed.len(),
+            failed.join(", ")
+        )));
+    }
+
+    // Complete when no documentation was requested
+    Ok(status.unwrap_or(GenerationStatus::Complete))
+}
+
+/// Generates the categories selected with `--docs` and delivers the
+/// documents to the configured output sink
+struct DocumentationOutput {
+    service: xze_core::documentation::DocumentationService,
+    sink: Arc<dyn xze_core::documentation::OutputSink>,
+}
+
+/// Documentation requested with `--docs`
+#[derive(Debug, Clone, Copy)]
+struct DocsRequest<'a> {
+    categories: &'a [xze_core::types::DiátaxisCategory],
+    /// Directory from `--output-dir`, replacing the configured sink
+    output_dir: Option<&'a Path>,
+}
 
-            info!("Using language: {}", actual_language);
+/// Documentation service and sink for the categories selected with
+/// `--docs`
+///
+/// `None` when no categories were selected, or on a dry run, which
+/// generates nothing.
+async fn documentation_output(
+    config: &xze_core::XzeConfig,
+    docs: DocsRequest<'_>,
+    mode: AnalysisMode,
+) -> Result<Option<DocumentationOutput>> {
+    if docs.categories.is_empty() {
+        return Ok(None);
+    }
 
-            let structure = analyzer.analyze(repo_path)?;
+    let names = docs
+        .categories
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if mode == AnalysisMode::DryRun {
+        info!("Dry run - would generate {} documentation", names);
+        return Ok(None);
+    }
+
+    let mut config = config.clone();
+    if let Some(output_dir) = docs.output_dir {
+        config.output.sink = xze_core::documentation::OutputSinkKind::Filesystem;
+        config.output.output_dir = output_dir.to_path_buf();
+    }
 
-            if dry_run {
-                info!(
-                    "Dry run - would analyze repository with {} items",
-                    structure.item_count()
+    info!(
+        "Documentation categories: {} ({} output)",
+        names, config.output.sink
+    );
+    let service = xze_core::documentation::DocumentationService::from_config(&config)?;
+    let sink = xze_core::documentation::build_sink(&config.output, output_kb(&config).await?)?;
+    Ok(Some(DocumentationOutput { service, sink }))
+}
+
+/// Knowledge base the `kb` output sink writes to, if that sink is selected
+///
+/// The store is opened at `DATABASE_URL`; the memory backend is rejected
+/// because it would not outlive the command.
+async fn output_kb(
+    config: &xze_core::XzeConfig,
+) -> Result<Option<Arc<dyn xze_core::kb::KbBackend>>> {
+    if config.output.sink != xze_core::documentation::OutputSinkKind::Kb {
+        return Ok(None);
+    }
+    if config.knowledge_base.backend != xze_core::kb::KbBackendKind::Postgres {
+        return Err(XzeError::validation(
+            "The kb output sink requires the postgres knowledge base backend",
+        ));
+    }
+
+    let database_url = std::env::var("DATABASE_URL")
+        .map_err(|_| XzeError::validation("The kb output sink requires DATABASE_URL"))?;
+    let store = xze_core::kb::store::KbStore::connect(&database_url)
+        .await
+        .map_err(|e| XzeError::Generic(anyhow::anyhow!("{}", e)))?;
+    Ok(Some(Arc::new(store)))
+}
+
+/// Generate the selected documentation for an analyzed repository
+///
+/// Delivers the generated documents to the output sink, prints them and
+/// returns whether every category was generated.
+async fn generate_documentation(
+    output: &DocumentationOutput,
+    repo: &xze_core::repository::Repository,
+    categories: &[xze_core::types::DiátaxisCategory],
+    cli: &Cli,
+) -> Result<GenerationStatus> {
+    info!("Generating documentation for {}", repo.id);
+
+    let report = output
+        .service
+        .generate_to_sink(repo, categories, output.sink.as_ref())
+        .await?;
+    print_generation_report(&report, cli)?;
+    Ok(report.status())
+}
+
+/// Print the documents a generation run produced and the categories that
+/// failed
+fn print_generation_report(
+    report: &xze_core::documentation::GenerationReport,
+    cli: &Cli,
+) -> Result<()> {
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(report)?),
+        "yaml" => println!("{}", serde_yaml::to_string(report)?),
+        _ => {
+            for document in &report.documents {
+                println!(
+                    "📄 {} ({}): {}",
+                    document.title,
+                    document.category,
+                    document.file_path.display()
                 );
-            } else {
-                // Output results
-                match cli.output.as_str() {
-                    "json" => {
-                        let json = serde_json::to_string_pretty(&structure)?;
-                        println!("{}", json);
-                    }
-                    "yaml" => {
-                        let yaml = serde_yaml::to_string(&structure)?;
-                        println!("{}", yaml);
-                    }
-                    _ => {
-                        print_analysis_results(&structure, repo_path);
+            }
+            for failure in &report.failures {
+                match &failure.subject {
+                    Some(subject) => {
+                        println!("❌ {} ({}): {}", failure.category, subject, failure.error)
                     }
+                    None => println!("❌ {}: {}", failure.category, failure.error),
                 }
             }
         }
+    }
+    Ok(())
+}
+
+/// Analyze a single repository checkout and print the results
+///
+/// Returns the analyzed repository, or `None` if there was nothing to
+/// analyze.
+fn analyze_repository_path(
+    repo_path: &Path,
+    language: Option<String>,
+    policy: &xze_core::repository::CoveragePolicy,
+    output: AnalysisOutput,
+    cli: &Cli,
+) -> Result<Option<xze_core::repository::Repository>> {
+    let AnalysisOutput { mode, redaction } = output;
+    // Use xze-core to analyze the repository
+    let language = language
+        .as_deref()
+        .map(xze_core::types::ProgrammingLanguage::from);
+    let (actual_language, structure) =
+        match xze_core::repository::analyze_repository(repo_path, language)? {
+            xze_core::repository::AnalysisOutcome::Analyzed {
+                language,
+                structure,
+            } => (language, structure),
+            xze_core::repository::AnalysisOutcome::Empty(empty) => {
+                print_empty_analysis(&redaction.root_label(repo_path), &empty, cli)?;
+                return Ok(None);
+            }
+        };
+
+    info!("Using language: {}", actual_language);
+
+    let mut repo = xze_core::repository::Repository::new(
+        repo_path.display().to_string().into(),
+        String::new(),
+        repo_path.to_path_buf(),
+        actual_language.clone(),
+    );
+    repo.update_structure_with_policy(structure, policy);
+    repo.redact_paths(redaction);
+    let structure = &repo.structure;
+
+    match mode {
+        AnalysisMode::DryRun => {
+            info!(
+                "Dry run - would analyze repository with {} items",
+                structure.item_count()
+            );
+        }
+        AnalysisMode::StatsOnly => {
+            let mut stats = repo.stats(&repo.coverage_report(policy))?;
+            stats.source = redaction.root_label(repo_path);
+            print_stats(&stats, cli)?;
+        }
+        AnalysisMode::Full => {
+            // Output results
+            match cli.output.as_str() {
+                "json" => println!("{}", structure.to_json()?),
+                "yaml" => println!("{}", structure.to_yaml()?),
+                _ => {
+                    let coverage = structure.coverage_report(policy.required_for(&actual_language));
+                    let root = redaction.root_label(repo_path);
+                    print_analysis_results(structure, Path::new(&root), &coverage);
+                }
+            }
+        }
+    }
+
+    Ok(Some(repo))
+}
+
+/// Report a repository with nothing to document
+fn print_empty_analysis(
+    root: &str,
+    empty: &xze_core::repository::EmptyAnalysis,
+    cli: &Cli,
+) -> Result<()> {
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(empty)?),
+        "yaml" => println!("{}", serde_yaml::to_string(empty)?),
+        _ => {
+            println!("⚠️  Nothing to analyze in {}: {}", root, empty);
+            println!(
+                "   Check the repository path. Supported languages: Rust, Go, Python, JavaScript, TypeScript, Java."
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn handle_snapshot(
+    repo_path: &Path,
+    output: &Path,
+    language: Option<&str>,
+    cli: &Cli,
+) -> Result<()> {
+    info!("Capturing snapshot of repository: {:?}", repo_path);
+
+    let config = match &cli.config {
+        Some(config_path) => Some(xze_core::XzeConfig::from_file(config_path)?),
+        None => None,
+    };
+    let language = language.map(xze_core::types::ProgrammingLanguage::from);
+
+    let snapshot = xze_core::repository::snapshot::RepositorySnapshot::capture(
+        repo_path,
+        language,
+        config.as_ref(),
+    )?;
+    snapshot.to_file(output)?;
+
+    println!(
+        "Snapshot of {} files ({}, analyzer: {}) written to {}",
+        snapshot.files.len(),
+        snapshot.language,
+        snapshot.analyzer,
+        output.display()
+    );
+
+    Ok(())
+}
+
+async fn handle_analyze_snapshot(manifest: &Path, mode: AnalysisMode, cli: &Cli) -> Result<()> {
+    info!("Analyzing snapshot manifest: {:?}", manifest);
+
+    let snapshot = xze_core::repository::snapshot::RepositorySnapshot::from_file(manifest)?;
+    info!(
+        "Snapshot of {} created by xze {} using the {} analyzer",
+        snapshot.source, snapshot.xze_version, snapshot.analyzer
+    );
+
+    let structure = snapshot.analyze()?;
+
+    match mode {
+        AnalysisMode::DryRun => {
+            info!(
+                "Dry run - would analyze snapshot with {} items",
+                structure.item_count()
+            );
+            return Ok(());
+        }
+        AnalysisMode::StatsOnly => {
+            let coverage =
+                structure.coverage_report(coverage_policy(cli)?.required_for(&snapshot.language));
+            return print_stats(&snapshot.stats(&structure, &coverage), cli);
+        }
+        AnalysisMode::Full => {}
+    }
+
+    match cli.output.as_str() {
+        "json" => println!("{}", structure.to_json()?),
+        "yaml" => println!("{}", structure.to_yaml()?),
+        _ => {
+            let coverage =
+                structure.coverage_report(coverage_policy(cli)?.required_for(&snapshot.language));
+            print_analysis_results(&structure, Path::new(&snapshot.source), &coverage);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the staged files of one repository for documentation gaps
+fn handle_analyze_staged(repos: &[PathBuf], warn_only: bool, cli: &Cli) -> Result<()> {
+    let repo_path = match repos {
+        [] => Path::new("."),
+        [repo] => repo.as_path(),
+        _ => return Err(XzeError::validation("--staged checks a single repository")),
+    };
+
+    let report = xze_core::repository::staged::check_staged(repo_path, &coverage_policy(cli)?)?;
+
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        "yaml" => println!("{}", serde_yaml::to_string(&report)?),
+        _ => {
+            for gap in &report.gaps {
+                println!("{}", gap);
+            }
+            if !report.is_clean() {
+                eprintln!(
+                    "{} undocumented items in {} staged files",
+                    report.gaps.len(),
+                    report.files_checked
+                );
+            }
+        }
+    }
+
+    if report.is_clean() || warn_only {
+        Ok(())
     } else {
-        return Err(XzeError::validation("No repositories specified. Use --repos for local mode or --auto for configuration mode"));
+        Err(XzeError::validation(format!(
+            "{} undocumented items in staged files",
+            report.gaps.len()
+        )))
     }
+}
 
+/// Print aggregate statistics in the requested output format
+fn print_stats(stats: &xze_core::repository::RepositoryStats, cli: &Cli) -> Result<()> {
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(stats)?),
+        "yaml" => println!("{}", serde_yaml::to_string(stats)?),
+        _ => {
+            println!();
+            println!("📊 Statistics for {}", stats.source);
+            println!("{:=<50}", "");
+            println!("  Language: {}", stats.language);
+            println!("  Files: {}", stats.file_count);
+            println!("  Lines of code: {}", stats.lines_of_code);
+            println!(
+                "  Modules: {}, functions: {}, types: {}",
+                stats.modules, stats.functions, stats.types
+            );
+            println!(
+                "  Documentation coverage: {:.1}%",
+                stats.doc_coverage * 100.0
+            );
+            println!(
+                "  Undocumented public items: {}",
+                stats.undocumented_public_items
+            );
+            println!("  Analysis warnings: {}", stats.warnings);
+            println!(
+                "  Markers: {} TODO, {} FIXME, {} deprecated",
+                stats.annotations.todo, stats.annotations.fixme, stats.annotations.deprecated
+            );
+            if stats.has_high_todo_density() {
+                println!(
+                    "  ⚠️  High TODO density: {:.1} per 1000 lines (threshold {:.0})",
+                    stats.todo_density(),
+                    xze_core::repository::HIGH_TODO_DENSITY
+                );
+            }
+            if !stats.languages.is_empty() {
+                println!("  Languages:");
+                for (language, counts) in &stats.languages {
+                    println!(
+                        "    • {}: {} files, {} lines",
+                        language, counts.files, counts.lines
+                    );
+                }
+            }
+        }
+    }
     Ok(())
 }
 
-async fn handle_serve(host: String, port: u16, ollama_url: String, cli: &Cli) -> Result<()> {
+/// Coverage policy from the configuration file, or the default policy
+fn coverage_policy(cli: &Cli) -> Result<xze_core::repository::CoveragePolicy> {
+    match &cli.config {
+        Some(config_path) => Ok(xze_core::XzeConfig::from_file(config_path)?.coverage),
+        None => Ok(xze_core::repository::CoveragePolicy::default()),
+    }
+}
+
+async fn handle_serve(
+    host: String,
+    port: u16,
+    ollama_url: String,
+    tls_cert_path: Option<PathBuf>,
+    tls_key_path: Option<PathBuf>,
+    disabled: &[xze_serve::api::EndpointGroup],
+    cli: &Cli,
+) -> Result<()> {
     info!("Starting XZe server on {}:{}", host, port);
     info!("Using Ollama at: {}", ollama_url);
 
@@ -328,18 +1012,55 @@ async fn handle_serve(host: String, port: u16, ollama_url: String, cli: &Cli) ->
         xze_core::XzeConfig::default()
     };
 
-    // TODO: Start the actual server using xze-serve
-    println!("🚀 XZe Server would start on http://{}:{}", host, port);
-    println!("📡 Ollama endpoint: {}", ollama_url);
+    let mut endpoints = xze_serve::api::EndpointToggles::default();
+    for group in disabled {
+        endpoints.set(*group, false);
+    }
+
+    let defaults = xze_serve::ServerConfig::default();
+    let server_config = xze_serve::ServerConfig {
+        host,
+        port,
+        ollama_url,
+        database_url: std::env::var("DATABASE_URL").unwrap_or(defaults.database_url.clone()),
+        models: config.ollama.models.clone(),
+        repositories: config.repositories.clone(),
+        normalization: config.knowledge_base.normalization.clone(),
+        search_limits: xze_serve::search::SearchLimits {
+            max_results: config.search.max_results,
+            max_offset: config.search.max_offset,
+        },
+        rerank: config.search.rerank.clone(),
+        generation: config.generation.clone(),
+        output: config.output.clone(),
+        tls_cert_path,
+        tls_key_path,
+        endpoints,
+        ..defaults
+    };
+
+    let scheme = if server_config.tls_cert_path.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    println!(
+        "🚀 XZe Server starting on {}://{}:{}",
+        scheme, server_config.host, server_config.port
+    );
+    println!("📡 Ollama endpoint: {}", server_config.ollama_url);
     println!(
         "⚙️  Configuration: {} repositories",
-        config.repositories.len()
+        server_config.repositories.len()
     );
+    if !disabled.is_empty() {
+        let names: Vec<String> = disabled.iter().map(|g| g.to_string()).collect();
+        println!("🚫 Disabled endpoints: {}", names.join(", "));
+    }
     println!("\nPress Ctrl+C to stop the server");
 
-    // For now, just simulate server running
-    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    println!("Server simulation complete");
+    xze_serve::init_server(server_config).await?;
+    println!("Server stopped");
 
     Ok(())
 }
@@ -385,7 +1106,7 @@ async fn handle_init(config_path: PathBuf, interactive: bool, _cli: &Cli) -> Res
     Ok(())
 }
 
-async fn handle_validate(config_path: &PathBuf, cli: &Cli) -> Result<()> {
+async fn handle_validate(config_path: &PathBuf, check_repos: bool, cli: &Cli) -> Result<()> {
     info!("Validating configuration at {:?}", config_path);
 
     if !config_path.exists() {
@@ -399,22 +1120,63 @@ async fn handle_validate(config_path: &PathBuf, cli: &Cli) -> Result<()> {
 
     // Validate configuration
     match config.validate() {
-        Ok(()) => match cli.output.as_str() {
-            "json" => {
-                let result = serde_json::json!({
-                    "valid": true,
-                    "message": "Configuration is valid",
-                    "repositories": config.repositories.len()
-                });
-                println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(()) => {
+            let checks = if check_repos {
+                let repositories = config.repositories.clone();
+                Some(
+                    tokio::task::spawn_blocking(move || {
+                        xze_core::git::check_repositories(&repositories)
+                    })
+                    .await
+                    .map_err(|e| {
+                        XzeError::repository(format!("Repository check task failed: {}", e))
+                    })?,
+                )
+            } else {
+                None
+            };
+            let unreachable = checks
+                .iter()
+                .flatten()
+                .filter(|check| !check.reachable)
+                .count();
+
+            match cli.output.as_str() {
+                "json" => {
+                    let mut result = serde_json::json!({
+                        "valid": true,
+                        "message": "Configuration is valid",
+                        "repositories": config.repositories.len()
+                    });
+                    if let Some(checks) = &checks {
+                        result["repositories_reachable"] = serde_json::json!(unreachable == 0);
+                        result["repository_checks"] = serde_json::to_value(checks)?;
+                    }
+                    println!("{}", serde_json::to_string_pretty(&result)?);
+                }
+                _ => {
+                    println!("✅ Configuration is valid");
+                    println!("📁 Found {} repositories", config.repositories.len());
+                    println!("🤖 Ollama URL: {}", config.ollama.url);
+                    println!("🧠 Primary model: {}", config.ollama.models.primary);
+                    for check in checks.iter().flatten() {
+                        let icon = if check.reachable { "✅" } else { "❌" };
+                        println!(
+                            "{} {} ({}): {}",
+                            icon, check.name, check.target, check.message
+                        );
+                    }
+                }
             }
-            _ => {
-                println!("✅ Configuration is valid");
-                println!("📁 Found {} repositories", config.repositories.len());
-                println!("🤖 Ollama URL: {}", config.ollama.url);
-                println!("🧠 Primary model: {}", config.ollama.models.primary);
+
+            if unreachable > 0 {
+                return Err(XzeError::validation(format!(
+                    "{} of {} repositories are unreachable",
+                    unreachable,
+                    config.repositories.len()
+                )));
             }
-        },
+        }
         Err(e) => {
             match cli.output.as_str() {
                 "json" => {
@@ -454,25 +1216,115 @@ async fn handle_version() -> Result<()> {
     Ok(())
 }
 
-async fn handle_health(_cli: &Cli) -> Result<()> {
+async fn handle_health(cli: &Cli) -> Result<()> {
     info!("Running health check");
 
-    match xze_core::health_check() {
-        Ok(()) => {
-            println!("✅ XZe is healthy");
-            println!("  ✓ Environment checks passed");
-            println!("  ✓ File system access OK");
-            println!("  ✓ Core library loaded");
-        }
-        Err(e) => {
-            println!("❌ Health check failed: {}", e);
-            return Err(XzeError::validation(format!("Health check failed: {}", e)));
+    let config = match &cli.config {
+        Some(config_path) => xze_core::XzeConfig::from_file(config_path)?,
+        None => xze_core::XzeConfig::default(),
+    };
+    let database_url = std::env::var("DATABASE_URL").ok();
+    let diagnostics = xze_core::diagnostics::collect(&config, database_url.as_deref())
+        .await
+        .with_features("xze-serve", xze_serve::compiled_features());
+
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&diagnostics)?),
+        _ => {
+            match &diagnostics.health_error {
+                None => {
+                    println!("✅ XZe is healthy");
+                    println!("  ✓ Environment checks passed");
+                    println!("  ✓ File system access OK");
+                    println!("  ✓ Core library loaded");
+                }
+                Some(e) => println!("❌ Health check failed: {}", e),
+            }
+            println!();
+            println!("Version: {}", diagnostics.xze_version);
+            println!(
+                "AI backend: {} ({})",
+                diagnostics.ai_backend, diagnostics.ai_endpoint
+            );
+            println!("Components:");
+            for component in &diagnostics.components {
+                let status = match (&component.version, &component.error) {
+                    (Some(version), _) => version.clone(),
+                    (None, Some(error)) => format!("unavailable: {}", error),
+                    (None, None) => "unknown".to_string(),
+                };
+                match &component.detail {
+                    Some(detail) => println!("  {}: {} [{}]", component.name, status, detail),
+                    None => println!("  {}: {}", component.name, status),
+                }
+            }
+            println!("Features:");
+            for (krate, features) in &diagnostics.features {
+                let list = if features.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    features.join(", ")
+                };
+                println!("  {}: {}", krate, list);
+            }
         }
     }
 
+    if let Some(e) = diagnostics.health_error {
+        return Err(XzeError::validation(format!("Health check failed: {}", e)));
+    }
+
     Ok(())
 }
 
+async fn handle_doctor(cli: &Cli) -> Result<()> {
+    use xze_core::self_test::StageStatus;
+
+    info!("Running self-test");
+
+    let config = match &cli.config {
+        Some(config_path) => xze_core::XzeConfig::from_file(config_path)?,
+        None => xze_core::XzeConfig::default(),
+    };
+    let report = xze_core::self_test::run(&config).await;
+
+    match cli.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        "yaml" => println!("{}", serde_yaml::to_string(&report)?),
+        _ => {
+            println!(
+                "🩺 Self-test with {} at {}",
+                config.ollama.models.primary, config.ollama.url
+            );
+            for stage in &report.stages {
+                let icon = match stage.status {
+                    StageStatus::Passed => "✅",
+                    StageStatus::Failed => "❌",
+                    StageStatus::Skipped => "⏭️ ",
+                };
+                println!(
+                    "{} {:<9} {:>6} ms  {}",
+                    icon, stage.stage, stage.duration_ms, stage.detail
+                );
+            }
+            println!();
+            if report.passed() {
+                println!("✅ All stages passed in {} ms", report.duration_ms());
+            } else {
+                println!("❌ Self-test failed");
+            }
+        }
+    }
+
+    match report.first_failure() {
+        Some(failure) => Err(XzeError::validation(format!(
+            "Self-test failed at the {} stage: {}",
+            failure.stage, failure.detail
+        ))),
+        None => Ok(()),
+    }
+}
+
 async fn handle_default(_cli: &Cli) -> Result<()> {
     println!("🚀 Welcome to XZe - Pipeline Documentation Tool");
     println!();
@@ -483,6 +1335,7 @@ async fn handle_default(_cli: &Cli) -> Result<()> {
     println!("  xze init                    # Create configuration file");
     println!("  xze analyze --repos ./repo  # Analyze a local repository");
     println!("  xze serve                   # Start web server");
+    println!("  xze doctor                  # Check the setup end to end");
     println!("  xze --help                  # Show all options");
     println!();
     println!("For more information, visit: https://github.com/xbcsmith/xze");
@@ -490,12 +1343,18 @@ async fn handle_default(_cli: &Cli) -> Result<()> {
     Ok(())
 }
 
-fn print_analysis_results(structure: &xze_core::CodeStructure, repo_path: &PathBuf) {
+fn print_analysis_results(
+    structure: &xze_core::CodeStructure,
+    repo_path: &Path,
+    coverage: &xze_core::repository::CoverageReport,
+) {
     println!();
     println!("📊 Analysis Results for {:?}", repo_path);
     println!("{:=<50}", "");
     println!();
 
+    print_warnings(structure);
+
     if structure.is_empty() {
         println!("No code structure found in the repository.");
         return;
@@ -570,5 +1429,42 @@ fn print_analysis_results(structure: &xze_core::CodeStructure, repo_path: &PathB
                 format!("{:?}", config.format).to_lowercase()
             );
         }
+        println!();
     }
+
+    println!("📝 Documentation Coverage:");
+    println!(
+        "  {:.1}% ({} of {} required items)",
+        coverage.coverage() * 100.0,
+        coverage.documented,
+        coverage.required
+    );
+    if !coverage.gaps.is_empty() {
+        println!("  Undocumented:");
+        for gap in coverage.gaps.iter().take(10) {
+            println!("    • {} ({})", gap.name, gap.kind);
+        }
+        if coverage.gaps.len() > 10 {
+            println!("    ... and {} more", coverage.gaps.len() - 10);
+        }
+    }
+}
+
+/// Print the problems analysis skipped past, most severe first
+fn print_warnings(structure: &xze_core::CodeStructure) {
+    if structure.warnings.is_empty() {
+        return;
+    }
+
+    let mut warnings: Vec<_> = structure.warnings.iter().collect();
+    warnings.sort_by_key(|w| std::cmp::Reverse(w.severity));
+
+    println!("⚠️  Analysis Warnings ({}):", warnings.len());
+    for warning in warnings.iter().take(10) {
+        println!("  • {}", warning);
+    }
+    if warnings.len() > 10 {
+        println!("  ... and {} more", warnings.len() - 10);
+    }
+    println!();
 }
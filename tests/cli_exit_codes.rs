@@ -0,0 +1,146 @@
+//! Integration tests for the exit codes of `xze analyze`
+//!
+//! These tests run the built binary against a mocked Ollama server and
+//! verify that documentation generation reports:
+//! - 0 when every requested category is generated
+//! - 2 when only some categories are generated
+//! - 1 when nothing is generated or analysis fails
+
+use mockito::{Matcher, Server, ServerGuard};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+use xze_core::XzeConfig;
+
+const GENERATED_DOC: &str = "# Guide\n\n## Overview\n\nThis guide explains the library in detail with examples.\n\n## Steps\n\n1. Install it.\n2. Use it.\n\n```rust\nfn main() {}\n```\n";
+
+/// Start an Ollama mock that answers tutorial prompts and fails reference ones
+async fn mock_ollama() -> ServerGuard {
+    let mut server = Server::new_async().await;
+    let body = serde_json::json!({
+        "response": GENERATED_DOC,
+        "done": true,
+        "done_reason": "stop",
+    });
+
+    server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::Regex("# Tutorial".to_string()))
+        .with_status(200)
+        .with_body(body.to_string())
+        .expect_at_least(0)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::Regex("# API Documentation".to_string()))
+        .with_status(500)
+        .with_body(r#"{"error":"model failed"}"#)
+        .expect_at_least(0)
+        .create_async()
+        .await;
+
+    server
+}
+
+/// Write a configuration pointing at the mock without retry delays
+fn write_config(dir: &Path, ollama_url: &str) -> std::path::PathBuf {
+    let mut config = XzeConfig::default();
+    config.ollama.url = ollama_url.parse().unwrap();
+    config.ollama.retry.max_retries = 0;
+    config.ollama.retry.initial_delay_ms = 0;
+
+    let path = dir.join("xze-config.yaml");
+    config.to_file(&path).unwrap();
+    path
+}
+
+/// Create a small repository to document
+fn write_repo(dir: &Path) -> std::path::PathBuf {
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(repo.join("src")).unwrap();
+    std::fs::write(
+        repo.join("src/lib.rs"),
+        "/// Add two numbers\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )
+    .unwrap();
+    repo
+}
+
+/// Run `xze analyze` for the given repository and categories
+fn analyze(config: &Path, repo: &Path, docs: &str, output: &Path) -> i32 {
+    let output = Command::new(env!("CARGO_BIN_EXE_xze"))
+        .arg("--config")
+        .arg(config)
+        .arg("analyze")
+        .arg("--repos")
+        .arg(repo)
+        .arg("--docs")
+        .arg(docs)
+        .arg("--output-dir")
+        .arg(output)
+        .output()
+        .unwrap();
+
+    output
+        .status
+        .code()
+        .expect("xze exited without a status code")
+}
+
+#[tokio::test]
+async fn test_complete_generation_exits_zero() {
+    let server = mock_ollama().await;
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path(), &server.url());
+    let repo = write_repo(dir.path());
+
+    let code = analyze(&config, &repo, "tutorial", &dir.path().join("out"));
+
+    assert_eq!(code, 0);
+}
+
+#[tokio::test]
+async fn test_partial_generation_exits_two() {
+    let server = mock_ollama().await;
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path(), &server.url());
+    let repo = write_repo(dir.path());
+
+    let code = analyze(
+        &config,
+        &repo,
+        "tutorial,reference",
+        &dir.path().join("out"),
+    );
+
+    assert_eq!(code, 2);
+}
+
+#[tokio::test]
+async fn test_failed_generation_exits_one() {
+    let server = mock_ollama().await;
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path(), &server.url());
+    let repo = write_repo(dir.path());
+
+    let code = analyze(&config, &repo, "reference", &dir.path().join("out"));
+
+    assert_eq!(code, 1);
+}
+
+#[tokio::test]
+async fn test_missing_repository_exits_one() {
+    let server = mock_ollama().await;
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path(), &server.url());
+
+    let code = analyze(
+        &config,
+        &dir.path().join("missing"),
+        "tutorial",
+        &dir.path().join("out"),
+    );
+
+    assert_eq!(code, 1);
+}
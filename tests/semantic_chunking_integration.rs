@@ -6,7 +6,9 @@
 //! - Boundary detection
 //! - Search integration
 
-use xze_core::semantic::{ChunkMetadata, ChunkerConfig, SemanticChunk, SentenceSplitter};
+use xze_core::semantic::{
+    ChunkMetadata, ChunkerConfig, SemanticChunk, SentenceSplitter, SimilarityMetric, SplitterKind,
+};
 
 /// Test end-to-end chunking of a sample document
 #[tokio::test]
@@ -39,10 +41,14 @@ Advanced features are available for power users.
         similarity_threshold: 0.7,
         min_chunk_sentences: 3,
         max_chunk_sentences: 30,
-        similarity_percentile: 0.5,
+        overlap_sentences: 0,
+        similarity_percentile: Some(0.5),
         min_sentence_length: 10,
         embedding_batch_size: 32,
+        max_concurrent_embedding_batches: 4,
         model_name: "nomic-embed-text".to_string(),
+        similarity_metric: SimilarityMetric::Cosine,
+        splitter: SplitterKind::default(),
     };
 
     // Validate configuration
@@ -197,21 +203,21 @@ fn test_configuration_validation() {
 
     // Invalid percentile (too high) - percentile is 0.0-1.0 not 0-100
     let invalid_config = ChunkerConfig {
-        similarity_percentile: 1.5,
+        similarity_percentile: Some(1.5),
         ..Default::default()
     };
     assert!(invalid_config.validate().is_err());
 
     // Invalid percentile (negative)
     let invalid_config = ChunkerConfig {
-        similarity_percentile: -0.1,
+        similarity_percentile: Some(-0.1),
         ..Default::default()
     };
     assert!(invalid_config.validate().is_err());
 
     // Valid configuration with valid percentile
     let valid_config = ChunkerConfig {
-        similarity_percentile: 0.75,
+        similarity_percentile: Some(0.75),
         ..Default::default()
     };
     assert!(valid_config.validate().is_ok());
@@ -393,10 +399,14 @@ fn test_chunker_configuration_bounds() {
         similarity_threshold: 0.0,
         min_chunk_sentences: 1,
         max_chunk_sentences: 1,
-        similarity_percentile: 0.0,
+        overlap_sentences: 0,
+        similarity_percentile: Some(0.0),
         min_sentence_length: 1,
         embedding_batch_size: 1,
+        max_concurrent_embedding_batches: 1,
         model_name: "test".to_string(),
+        similarity_metric: SimilarityMetric::Cosine,
+        splitter: SplitterKind::default(),
     };
     assert!(min_config.validate().is_ok());
 
@@ -405,10 +415,14 @@ fn test_chunker_configuration_bounds() {
         similarity_threshold: 1.0,
         min_chunk_sentences: 1,
         max_chunk_sentences: 1000,
-        similarity_percentile: 1.0,
+        overlap_sentences: 0,
+        similarity_percentile: Some(1.0),
         min_sentence_length: 1000,
         embedding_batch_size: 1000,
+        max_concurrent_embedding_batches: 16,
         model_name: "test".to_string(),
+        similarity_metric: SimilarityMetric::Cosine,
+        splitter: SplitterKind::default(),
     };
     assert!(max_config.validate().is_ok());
 }